@@ -0,0 +1,218 @@
+/* rxing C API, licensed under the Apache License, Version 2.0 */
+
+#ifndef RXING_H
+#define RXING_H
+
+/* Generated by cbindgen from crates/capi/src/lib.rs, do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Barcode symbologies. The values are part of the ABI and never change.
+typedef enum RxingBarcodeFormat {
+  RXING_BARCODE_FORMAT_AUSTRALIA_POST = 0,
+  RXING_BARCODE_FORMAT_AZTEC = 1,
+  RXING_BARCODE_FORMAT_CODABLOCK_F = 2,
+  RXING_BARCODE_FORMAT_CODABAR = 3,
+  RXING_BARCODE_FORMAT_CODE_11 = 4,
+  RXING_BARCODE_FORMAT_CODE_16K = 5,
+  RXING_BARCODE_FORMAT_CODE_32 = 6,
+  RXING_BARCODE_FORMAT_CODE_39 = 7,
+  RXING_BARCODE_FORMAT_CODE_49 = 8,
+  RXING_BARCODE_FORMAT_CODE_93 = 9,
+  RXING_BARCODE_FORMAT_CODE_128 = 10,
+  RXING_BARCODE_FORMAT_DATA_MATRIX = 11,
+  RXING_BARCODE_FORMAT_DOTCODE = 12,
+  RXING_BARCODE_FORMAT_EAN_8 = 13,
+  RXING_BARCODE_FORMAT_EAN_13 = 14,
+  RXING_BARCODE_FORMAT_HAN_XIN = 15,
+  RXING_BARCODE_FORMAT_IATA_2_OF_5 = 16,
+  RXING_BARCODE_FORMAT_INDUSTRIAL_2_OF_5 = 17,
+  RXING_BARCODE_FORMAT_INTELLIGENT_MAIL = 18,
+  RXING_BARCODE_FORMAT_ITF = 19,
+  RXING_BARCODE_FORMAT_KIX = 20,
+  RXING_BARCODE_FORMAT_MATRIX_2_OF_5 = 21,
+  RXING_BARCODE_FORMAT_MAXICODE = 22,
+  RXING_BARCODE_FORMAT_MSI = 23,
+  RXING_BARCODE_FORMAT_PDF_417 = 24,
+  RXING_BARCODE_FORMAT_PHARMACODE = 25,
+  RXING_BARCODE_FORMAT_PHARMACODE_TWO_TRACK = 26,
+  RXING_BARCODE_FORMAT_PLANET = 27,
+  RXING_BARCODE_FORMAT_POSTNET = 28,
+  RXING_BARCODE_FORMAT_PZN = 29,
+  RXING_BARCODE_FORMAT_QR_CODE = 30,
+  RXING_BARCODE_FORMAT_MICRO_QR_CODE = 31,
+  RXING_BARCODE_FORMAT_RECTANGULAR_MICRO_QR_CODE = 32,
+  RXING_BARCODE_FORMAT_RM4SCC = 33,
+  RXING_BARCODE_FORMAT_RSS_14 = 34,
+  RXING_BARCODE_FORMAT_RSS_EXPANDED = 35,
+  RXING_BARCODE_FORMAT_TELEPEN = 36,
+  RXING_BARCODE_FORMAT_UPC_A = 37,
+  RXING_BARCODE_FORMAT_UPC_E = 38,
+  RXING_BARCODE_FORMAT_UPC_EAN_EXTENSION = 39,
+  RXING_BARCODE_FORMAT_DX_FILM_EDGE = 40,
+  RXING_BARCODE_FORMAT_UNSUPPORTED = 255,
+} RxingBarcodeFormat;
+
+// Layout of the pixels of an [`RxingImage`].
+typedef enum RxingPixelFormat {
+  // One byte of luminance per pixel.
+  RXING_PIXEL_FORMAT_LUM = 0,
+  RXING_PIXEL_FORMAT_RGB = 1,
+  RXING_PIXEL_FORMAT_BGR = 2,
+  RXING_PIXEL_FORMAT_RGBA = 3,
+  RXING_PIXEL_FORMAT_BGRA = 4,
+} RxingPixelFormat;
+
+// Outcome of a call into the library.
+typedef enum RxingStatus {
+  RXING_STATUS_OK = 0,
+  // No barcode was found in the image.
+  RXING_STATUS_NOT_FOUND = 1,
+  // A pointer, size or option passed in was not valid.
+  RXING_STATUS_INVALID_ARGUMENT = 2,
+  // A barcode was found but its contents could not be decoded.
+  RXING_STATUS_FORMAT_ERROR = 3,
+  // A barcode was found but had too many errors to correct.
+  RXING_STATUS_CHECKSUM_ERROR = 4,
+  // The operation is not supported for the requested format.
+  RXING_STATUS_UNSUPPORTED = 5,
+  // Any other failure, see `rxing_last_error`.
+  RXING_STATUS_ERROR = 6,
+} RxingStatus;
+
+// An encoded barcode, one byte per pixel, 0 for dark and 255 for light modules.
+typedef struct RxingBitmap RxingBitmap;
+
+// A reader configured once and then used for any number of decodes, from any thread.
+typedef struct RxingReader RxingReader;
+
+// One barcode found by a decode, borrowed from its [`RxingResults`].
+typedef struct RxingResult RxingResult;
+
+// The barcodes found by one decode.
+typedef struct RxingResults RxingResults;
+
+typedef struct RxingReaderOptions {
+  // [`RxingBarcodeFormat`]s to look for, or null to look for all of them.
+  const uint32_t *formats;
+  size_t formats_len;
+  // Spend more time looking for barcodes, for example by also trying rotated images.
+  bool try_harder;
+  // Also look for barcodes printed light on dark.
+  bool also_inverted;
+  // The image contains only a barcode, as produced by `rxing_encode`.
+  bool pure_barcode;
+  // Look for every barcode in the image instead of stopping at the first one.
+  bool multiple;
+} RxingReaderOptions;
+
+// An image owned by the caller.
+typedef struct RxingImage {
+  const uint8_t *data;
+  uint32_t width;
+  uint32_t height;
+  // Bytes from the start of one row to the start of the next, or 0 for rows without padding.
+  size_t stride;
+  // A [`RxingPixelFormat`].
+  uint32_t format;
+} RxingImage;
+
+typedef struct RxingPoint {
+  float x;
+  float y;
+} RxingPoint;
+
+typedef struct RxingEncodeOptions {
+  // Preferred size of the bitmap, it grows if the barcode does not fit.
+  int32_t width;
+  int32_t height;
+  // Quiet zone around the barcode, or a negative value for the default of the format.
+  int32_t margin;
+  // Error correction level, as understood by the format, or null for its default.
+  const char *error_correction;
+  // Character set of the contents, or null for the default of the format.
+  const char *character_set;
+} RxingEncodeOptions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message of the last failure on this thread, or null if the last call succeeded.
+//
+// The string stays valid until the next call into the library on this thread.
+const char *rxing_last_error(void);
+
+// Options looking for every format, once per image, without trying harder.
+RxingReaderOptions rxing_reader_options_default(void);
+
+// Creates a reader, storing it in `out`. Null options are the default options.
+RxingStatus rxing_reader_new(const RxingReaderOptions *options, RxingReader **out);
+
+void rxing_reader_free(RxingReader *reader);
+
+// Decodes the barcodes in an image, storing them in `out`.
+//
+// Nothing is stored when no barcode is found, `RXING_STATUS_NOT_FOUND` is returned instead. A
+// reader is never changed by decoding, so several threads can decode with it at once.
+RxingStatus rxing_reader_decode(const RxingReader *reader,
+                                const RxingImage *image,
+                                RxingResults **out);
+
+size_t rxing_results_count(const RxingResults *results);
+
+// The result at `index`, or null if there is none.
+const RxingResult *rxing_results_get(const RxingResults *results, size_t index);
+
+void rxing_results_free(RxingResults *results);
+
+// The decoded text, nul terminated. Its length without the terminator is stored in `len`
+// unless it is null, which is needed if the text itself contains nuls.
+const char *rxing_result_text(const RxingResult *result, size_t *len);
+
+// The raw bytes of the barcode, for the formats which have them.
+const uint8_t *rxing_result_bytes(const RxingResult *result, size_t *len);
+
+RxingBarcodeFormat rxing_result_format(const RxingResult *result);
+
+// The points locating the barcode in the image, such as its corners or finder patterns.
+const RxingPoint *rxing_result_points(const RxingResult *result, size_t *len);
+
+size_t rxing_result_metadata_count(const RxingResult *result);
+
+// Name of the metadata entry at `index`, such as `ERROR_CORRECTION_LEVEL`, or null.
+const char *rxing_result_metadata_key(const RxingResult *result, size_t index);
+
+// Value of the metadata entry at `index` written as text, or null.
+const char *rxing_result_metadata_value(const RxingResult *result, size_t index);
+
+// Options for a bitmap of the smallest size of the barcode, with the defaults of its format.
+RxingEncodeOptions rxing_encode_options_default(void);
+
+// Encodes the nul terminated UTF-8 `contents` as a barcode, storing its bitmap in `out`.
+// Null options are the default options.
+RxingStatus rxing_encode(const char *contents,
+                         uint32_t format,
+                         const RxingEncodeOptions *options,
+                         RxingBitmap **out);
+
+uint32_t rxing_bitmap_width(const RxingBitmap *bitmap);
+
+uint32_t rxing_bitmap_height(const RxingBitmap *bitmap);
+
+// The pixels of the bitmap, row after row without padding, so its stride is its width.
+const uint8_t *rxing_bitmap_data(const RxingBitmap *bitmap);
+
+void rxing_bitmap_free(RxingBitmap *bitmap);
+
+// Name of a format, such as `qrcode`, the same as rxing displays it, or null for a value which
+// is not a format.
+const char *rxing_barcode_format_name(uint32_t format);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RXING_H */
@@ -0,0 +1,881 @@
+//! A C API for rxing.
+//!
+//! Every function returning a [`RxingStatus`] reports failures through it, and the message of the
+//! last failure on the calling thread can be read with [`rxing_last_error`]. Objects handed out
+//! by the library are opaque and must be released with their `_free` function. Strings and
+//! buffers borrowed from an object stay valid until that object is freed.
+//!
+//! The header for this API is `include/rxing.h`. It is generated from this file with cbindgen,
+//! and the tests check that it matches the declarations here.
+//!
+//! Formats are passed in as `uint32_t` rather than as the C enums, so that a value the library
+//! does not know is reported as `RXING_STATUS_INVALID_ARGUMENT` instead of being undefined
+//! behaviour.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    ffi::{CStr, CString, c_char},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use rxing::{
+    BarcodeFormat, BinaryBitmap, DecodeHints, EncodeHints, Exceptions, ImmutableReader,
+    Luma8LuminanceSource, MultiFormatReader, MultiFormatWriter, MultiUseMultiFormatReader,
+    RXingResult, RXingResultMetadataValue, Writer,
+    common::{HybridBinarizer, Result},
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+};
+
+/// Outcome of a call into the library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxingStatus {
+    Ok = 0,
+    /// No barcode was found in the image.
+    NotFound = 1,
+    /// A pointer, size or option passed in was not valid.
+    InvalidArgument = 2,
+    /// A barcode was found but its contents could not be decoded.
+    FormatError = 3,
+    /// A barcode was found but had too many errors to correct.
+    ChecksumError = 4,
+    /// The operation is not supported for the requested format.
+    Unsupported = 5,
+    /// Any other failure, see `rxing_last_error`.
+    Error = 6,
+}
+
+/// Barcode symbologies. The values are part of the ABI and never change.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxingBarcodeFormat {
+    AUSTRALIA_POST = 0,
+    AZTEC = 1,
+    CODABLOCK_F = 2,
+    CODABAR = 3,
+    CODE_11 = 4,
+    CODE_16K = 5,
+    CODE_32 = 6,
+    CODE_39 = 7,
+    CODE_49 = 8,
+    CODE_93 = 9,
+    CODE_128 = 10,
+    DATA_MATRIX = 11,
+    DOTCODE = 12,
+    EAN_8 = 13,
+    EAN_13 = 14,
+    HAN_XIN = 15,
+    IATA_2_OF_5 = 16,
+    INDUSTRIAL_2_OF_5 = 17,
+    INTELLIGENT_MAIL = 18,
+    ITF = 19,
+    KIX = 20,
+    MATRIX_2_OF_5 = 21,
+    MAXICODE = 22,
+    MSI = 23,
+    PDF_417 = 24,
+    PHARMACODE = 25,
+    PHARMACODE_TWO_TRACK = 26,
+    PLANET = 27,
+    POSTNET = 28,
+    PZN = 29,
+    QR_CODE = 30,
+    MICRO_QR_CODE = 31,
+    RECTANGULAR_MICRO_QR_CODE = 32,
+    RM4SCC = 33,
+    RSS_14 = 34,
+    RSS_EXPANDED = 35,
+    TELEPEN = 36,
+    UPC_A = 37,
+    UPC_E = 38,
+    UPC_EAN_EXTENSION = 39,
+    DX_FILM_EDGE = 40,
+    UNSUPPORTED = 255,
+}
+
+/// Layout of the pixels of an [`RxingImage`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxingPixelFormat {
+    /// One byte of luminance per pixel.
+    Lum = 0,
+    Rgb = 1,
+    Bgr = 2,
+    Rgba = 3,
+    Bgra = 4,
+}
+
+/// An image owned by the caller.
+#[repr(C)]
+pub struct RxingImage {
+    pub data: *const u8,
+    pub width: u32,
+    pub height: u32,
+    /// Bytes from the start of one row to the start of the next, or 0 for rows without padding.
+    pub stride: usize,
+    /// A [`RxingPixelFormat`].
+    pub format: u32,
+}
+
+#[repr(C)]
+pub struct RxingReaderOptions {
+    /// [`RxingBarcodeFormat`]s to look for, or null to look for all of them.
+    pub formats: *const u32,
+    pub formats_len: usize,
+    /// Spend more time looking for barcodes, for example by also trying rotated images.
+    pub try_harder: bool,
+    /// Also look for barcodes printed light on dark.
+    pub also_inverted: bool,
+    /// The image contains only a barcode, as produced by `rxing_encode`.
+    pub pure_barcode: bool,
+    /// Look for every barcode in the image instead of stopping at the first one.
+    pub multiple: bool,
+}
+
+#[repr(C)]
+pub struct RxingEncodeOptions {
+    /// Preferred size of the bitmap, it grows if the barcode does not fit.
+    pub width: i32,
+    pub height: i32,
+    /// Quiet zone around the barcode, or a negative value for the default of the format.
+    pub margin: i32,
+    /// Error correction level, as understood by the format, or null for its default.
+    pub error_correction: *const c_char,
+    /// Character set of the contents, or null for the default of the format.
+    pub character_set: *const c_char,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RxingPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A reader configured once and then used for any number of decodes, from any thread.
+pub struct RxingReader {
+    reader: MultiFormatReader,
+    hints: DecodeHints,
+    multiple: bool,
+}
+
+/// The barcodes found by one decode.
+pub struct RxingResults {
+    results: Vec<RxingResult>,
+}
+
+/// One barcode found by a decode, borrowed from its [`RxingResults`].
+pub struct RxingResult {
+    /// The text with a terminating nul, it may contain other nuls.
+    text: Vec<u8>,
+    bytes: Vec<u8>,
+    format: RxingBarcodeFormat,
+    points: Vec<RxingPoint>,
+    metadata: Vec<(CString, CString)>,
+}
+
+/// An encoded barcode, one byte per pixel, 0 for dark and 255 for light modules.
+pub struct RxingBitmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Message of the last failure on this thread, or null if the last call succeeded.
+///
+/// The string stays valid until the next call into the library on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn rxing_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| {
+        error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+fn set_last_error(message: Option<String>) {
+    LAST_ERROR.with(|error| *error.borrow_mut() = message.map(c_string));
+}
+
+fn status_of(error: &Exceptions) -> RxingStatus {
+    match error {
+        Exceptions::NotFoundException(_) => RxingStatus::NotFound,
+        Exceptions::IllegalArgumentException(_) => RxingStatus::InvalidArgument,
+        Exceptions::FormatException(_) => RxingStatus::FormatError,
+        Exceptions::ChecksumException(_) | Exceptions::ReedSolomonException(_) => {
+            RxingStatus::ChecksumError
+        }
+        Exceptions::UnsupportedOperationException(_) => RxingStatus::Unsupported,
+        _ => RxingStatus::Error,
+    }
+}
+
+/// Runs the body of an exported function, keeping panics from unwinding into the caller.
+fn ffi_call(body: impl FnOnce() -> Result<()>) -> RxingStatus {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => {
+            set_last_error(None);
+            RxingStatus::Ok
+        }
+        Ok(Err(error)) => {
+            set_last_error(Some(error.to_string()));
+            status_of(&error)
+        }
+        Err(_) => {
+            set_last_error(Some("rxing panicked".to_owned()));
+            RxingStatus::Error
+        }
+    }
+}
+
+fn c_string(value: String) -> CString {
+    CString::new(value.replace('\0', "")).unwrap_or_default()
+}
+
+unsafe fn optional_str<'a>(value: *const c_char) -> Result<Option<&'a str>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| Exceptions::illegal_argument_with("strings must be UTF-8"))
+}
+
+unsafe fn write_out<T>(out: *mut *mut T, value: T) -> Result<()> {
+    if out.is_null() {
+        return Err(Exceptions::illegal_argument_with("out pointer is null"));
+    }
+    unsafe { *out = Box::into_raw(Box::new(value)) };
+    Ok(())
+}
+
+/// Options looking for every format, once per image, without trying harder.
+#[unsafe(no_mangle)]
+pub extern "C" fn rxing_reader_options_default() -> RxingReaderOptions {
+    RxingReaderOptions {
+        formats: ptr::null(),
+        formats_len: 0,
+        try_harder: false,
+        also_inverted: false,
+        pure_barcode: false,
+        multiple: false,
+    }
+}
+
+/// Creates a reader, storing it in `out`. Null options are the default options.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_reader_new(
+    options: *const RxingReaderOptions,
+    out: *mut *mut RxingReader,
+) -> RxingStatus {
+    ffi_call(|| {
+        let default_options = rxing_reader_options_default();
+        let options = unsafe { options.as_ref() }.unwrap_or(&default_options);
+        let mut hints = DecodeHints {
+            TryHarder: Some(options.try_harder),
+            AlsoInverted: Some(options.also_inverted),
+            PureBarcode: Some(options.pure_barcode),
+            ..Default::default()
+        };
+        if !options.formats.is_null() && options.formats_len > 0 {
+            let formats = unsafe { slice::from_raw_parts(options.formats, options.formats_len) };
+            hints.PossibleFormats = Some(
+                formats
+                    .iter()
+                    .map(|&f| RxingBarcodeFormat::try_from(f).map(BarcodeFormat::from))
+                    .collect::<Result<HashSet<_>>>()?,
+            );
+        }
+        let mut reader = MultiFormatReader::default();
+        reader.set_hints(&hints);
+        unsafe {
+            write_out(
+                out,
+                RxingReader {
+                    reader,
+                    hints,
+                    multiple: options.multiple,
+                },
+            )
+        }
+    })
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_reader_free(reader: *mut RxingReader) {
+    if !reader.is_null() {
+        drop(unsafe { Box::from_raw(reader) });
+    }
+}
+
+/// Decodes the barcodes in an image, storing them in `out`.
+///
+/// Nothing is stored when no barcode is found, `RXING_STATUS_NOT_FOUND` is returned instead. A
+/// reader is never changed by decoding, so several threads can decode with it at once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_reader_decode(
+    reader: *const RxingReader,
+    image: *const RxingImage,
+    out: *mut *mut RxingResults,
+) -> RxingStatus {
+    ffi_call(|| {
+        let reader = unsafe { reader.as_ref() }
+            .ok_or_else(|| Exceptions::illegal_argument_with("reader is null"))?;
+        let image = unsafe { image.as_ref() }
+            .ok_or_else(|| Exceptions::illegal_argument_with("image is null"))?;
+        let luma = unsafe { luminances(image) }?;
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(Luma8LuminanceSource::new(
+            luma,
+            image.width,
+            image.height,
+        )));
+        let results = if reader.multiple {
+            let mut scanner =
+                GenericMultipleBarcodeReader::new(MultiUseMultiFormatReader::default());
+            scanner.decode_multiple_with_hints(&mut bitmap, &reader.hints)?
+        } else {
+            vec![
+                reader
+                    .reader
+                    .immutable_decode_with_hints(&mut bitmap, &reader.hints)?,
+            ]
+        };
+        let results = results.iter().map(RxingResult::from).collect();
+        unsafe { write_out(out, RxingResults { results }) }
+    })
+}
+
+/// Copies the image into a luminance buffer without row padding.
+unsafe fn luminances(image: &RxingImage) -> Result<Vec<u8>> {
+    if image.data.is_null() || image.width == 0 || image.height == 0 {
+        return Err(Exceptions::illegal_argument_with(
+            "image must have data and both dimensions greater than 0",
+        ));
+    }
+    let format = RxingPixelFormat::try_from(image.format)?;
+    let channels = match format {
+        RxingPixelFormat::Lum => 1,
+        RxingPixelFormat::Rgb | RxingPixelFormat::Bgr => 3,
+        RxingPixelFormat::Rgba | RxingPixelFormat::Bgra => 4,
+    };
+    let (width, height) = (image.width as usize, image.height as usize);
+    let row_len = width * channels;
+    let stride = if image.stride == 0 {
+        row_len
+    } else {
+        image.stride
+    };
+    if stride < row_len {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "stride {stride} is shorter than a row of {row_len} bytes"
+        )));
+    }
+    let data = unsafe { slice::from_raw_parts(image.data, stride * (height - 1) + row_len) };
+
+    let mut luma = Vec::with_capacity(width * height);
+    for row in data.chunks(stride) {
+        let pixels = &row[..row_len];
+        match format {
+            RxingPixelFormat::Lum => luma.extend_from_slice(pixels),
+            RxingPixelFormat::Rgb | RxingPixelFormat::Rgba => luma.extend(
+                pixels
+                    .chunks_exact(channels)
+                    .map(|p| luminance(p[0], p[1], p[2])),
+            ),
+            RxingPixelFormat::Bgr | RxingPixelFormat::Bgra => luma.extend(
+                pixels
+                    .chunks_exact(channels)
+                    .map(|p| luminance(p[2], p[1], p[0])),
+            ),
+        }
+    }
+    Ok(luma)
+}
+
+/// The same green favouring average as `RGBLuminanceSource`.
+fn luminance(r: u8, g: u8, b: u8) -> u8 {
+    ((r as u32 + 2 * g as u32 + b as u32) / 4) as u8
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_results_count(results: *const RxingResults) -> usize {
+    unsafe { results.as_ref() }.map_or(0, |results| results.results.len())
+}
+
+/// The result at `index`, or null if there is none.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_results_get(
+    results: *const RxingResults,
+    index: usize,
+) -> *const RxingResult {
+    unsafe { results.as_ref() }
+        .and_then(|results| results.results.get(index))
+        .map_or(ptr::null(), |result| result as *const RxingResult)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_results_free(results: *mut RxingResults) {
+    if !results.is_null() {
+        drop(unsafe { Box::from_raw(results) });
+    }
+}
+
+/// The decoded text, nul terminated. Its length without the terminator is stored in `len`
+/// unless it is null, which is needed if the text itself contains nuls.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_text(
+    result: *const RxingResult,
+    len: *mut usize,
+) -> *const c_char {
+    let Some(result) = (unsafe { result.as_ref() }) else {
+        return ptr::null();
+    };
+    if let Some(len) = unsafe { len.as_mut() } {
+        *len = result.text.len() - 1;
+    }
+    result.text.as_ptr().cast()
+}
+
+/// The raw bytes of the barcode, for the formats which have them.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_bytes(
+    result: *const RxingResult,
+    len: *mut usize,
+) -> *const u8 {
+    unsafe { borrow_slice(result, len, |result| &result.bytes) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_format(result: *const RxingResult) -> RxingBarcodeFormat {
+    unsafe { result.as_ref() }.map_or(RxingBarcodeFormat::UNSUPPORTED, |result| result.format)
+}
+
+/// The points locating the barcode in the image, such as its corners or finder patterns.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_points(
+    result: *const RxingResult,
+    len: *mut usize,
+) -> *const RxingPoint {
+    unsafe { borrow_slice(result, len, |result| &result.points) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_metadata_count(result: *const RxingResult) -> usize {
+    unsafe { result.as_ref() }.map_or(0, |result| result.metadata.len())
+}
+
+/// Name of the metadata entry at `index`, such as `ERROR_CORRECTION_LEVEL`, or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_metadata_key(
+    result: *const RxingResult,
+    index: usize,
+) -> *const c_char {
+    unsafe { result.as_ref() }
+        .and_then(|result| result.metadata.get(index))
+        .map_or(ptr::null(), |(key, _)| key.as_ptr())
+}
+
+/// Value of the metadata entry at `index` written as text, or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_result_metadata_value(
+    result: *const RxingResult,
+    index: usize,
+) -> *const c_char {
+    unsafe { result.as_ref() }
+        .and_then(|result| result.metadata.get(index))
+        .map_or(ptr::null(), |(_, value)| value.as_ptr())
+}
+
+unsafe fn borrow_slice<T>(
+    result: *const RxingResult,
+    len: *mut usize,
+    field: impl FnOnce(&RxingResult) -> &Vec<T>,
+) -> *const T {
+    let items = unsafe { result.as_ref() }.map_or(&[][..], |result| field(result).as_slice());
+    if let Some(len) = unsafe { len.as_mut() } {
+        *len = items.len();
+    }
+    if items.is_empty() {
+        ptr::null()
+    } else {
+        items.as_ptr()
+    }
+}
+
+impl From<&RXingResult> for RxingResult {
+    fn from(result: &RXingResult) -> Self {
+        let mut text = result.getText().as_bytes().to_vec();
+        text.push(0);
+        let mut metadata: Vec<_> = result
+            .getRXingResultMetadata()
+            .iter()
+            .map(|(key, value)| (c_string(format!("{key:?}")), c_string(metadata_text(value))))
+            .collect();
+        metadata.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            text,
+            bytes: result.getRawBytes().to_vec(),
+            format: (*result.getBarcodeFormat()).into(),
+            points: result
+                .getPoints()
+                .iter()
+                .map(|point| RxingPoint {
+                    x: point.x,
+                    y: point.y,
+                })
+                .collect(),
+            metadata,
+        }
+    }
+}
+
+fn metadata_text(value: &RXingResultMetadataValue) -> String {
+    use RXingResultMetadataValue::*;
+
+    match value {
+        OTHER(text)
+        | ErrorCorrectionLevel(text)
+        | SuggestedPrice(text)
+        | PossibleCountry(text)
+        | UpcEanExtension(text)
+        | Gs1Composite(text)
+        | Gtin(text)
+        | SymbologyIdentifier(text)
+        | ContentType(text) => text.clone(),
+        Orientation(number)
+        | IssueNumber(number)
+        | StructuredAppendSequence(number)
+        | StructuredAppendParity(number) => number.to_string(),
+        IsMirrored(flag) | IsInverted(flag) | FilteredClosed(flag) => flag.to_string(),
+        ByteSegments(segments) => segments
+            .iter()
+            .map(|segment| segment.iter().map(|byte| format!("{byte:02x}")).collect())
+            .collect::<Vec<String>>()
+            .join(" "),
+        Pdf417ExtraMetadata(extra) => format!("{extra:?}"),
+        FilteredResolution((width, height)) => format!("{width}x{height}"),
+    }
+}
+
+/// Options for a bitmap of the smallest size of the barcode, with the defaults of its format.
+#[unsafe(no_mangle)]
+pub extern "C" fn rxing_encode_options_default() -> RxingEncodeOptions {
+    RxingEncodeOptions {
+        width: 0,
+        height: 0,
+        margin: -1,
+        error_correction: ptr::null(),
+        character_set: ptr::null(),
+    }
+}
+
+/// Encodes the nul terminated UTF-8 `contents` as a barcode, storing its bitmap in `out`.
+/// Null options are the default options.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_encode(
+    contents: *const c_char,
+    format: u32,
+    options: *const RxingEncodeOptions,
+    out: *mut *mut RxingBitmap,
+) -> RxingStatus {
+    ffi_call(|| {
+        let contents = unsafe { optional_str(contents) }?
+            .ok_or_else(|| Exceptions::illegal_argument_with("contents is null"))?;
+        let format = BarcodeFormat::from(RxingBarcodeFormat::try_from(format)?);
+        let default_options = rxing_encode_options_default();
+        let options = unsafe { options.as_ref() }.unwrap_or(&default_options);
+        let hints = EncodeHints {
+            Margin: (options.margin >= 0).then(|| options.margin.to_string()),
+            ErrorCorrection: unsafe { optional_str(options.error_correction) }?.map(str::to_owned),
+            CharacterSet: unsafe { optional_str(options.character_set) }?.map(str::to_owned),
+            ..Default::default()
+        };
+        let matrix = MultiFormatWriter.encode_with_hints(
+            contents,
+            &format,
+            options.width,
+            options.height,
+            &hints,
+        )?;
+        let (width, height) = (matrix.getWidth(), matrix.getHeight());
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| if matrix.get(x, y) { 0 } else { 255 })
+            .collect();
+        unsafe {
+            write_out(
+                out,
+                RxingBitmap {
+                    width,
+                    height,
+                    pixels,
+                },
+            )
+        }
+    })
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_bitmap_width(bitmap: *const RxingBitmap) -> u32 {
+    unsafe { bitmap.as_ref() }.map_or(0, |bitmap| bitmap.width)
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_bitmap_height(bitmap: *const RxingBitmap) -> u32 {
+    unsafe { bitmap.as_ref() }.map_or(0, |bitmap| bitmap.height)
+}
+
+/// The pixels of the bitmap, row after row without padding, so its stride is its width.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_bitmap_data(bitmap: *const RxingBitmap) -> *const u8 {
+    unsafe { bitmap.as_ref() }.map_or(ptr::null(), |bitmap| bitmap.pixels.as_ptr())
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rxing_bitmap_free(bitmap: *mut RxingBitmap) {
+    if !bitmap.is_null() {
+        drop(unsafe { Box::from_raw(bitmap) });
+    }
+}
+
+/// Name of a format, such as `qrcode`, the same as rxing displays it, or null for a value which
+/// is not a format.
+#[unsafe(no_mangle)]
+pub extern "C" fn rxing_barcode_format_name(format: u32) -> *const c_char {
+    let Ok(format) = RxingBarcodeFormat::try_from(format) else {
+        return ptr::null();
+    };
+    let name: &'static CStr = match format {
+        RxingBarcodeFormat::AUSTRALIA_POST => c"australia post",
+        RxingBarcodeFormat::AZTEC => c"aztec",
+        RxingBarcodeFormat::CODABLOCK_F => c"codablock f",
+        RxingBarcodeFormat::CODABAR => c"codabar",
+        RxingBarcodeFormat::CODE_11 => c"code 11",
+        RxingBarcodeFormat::CODE_16K => c"code 16k",
+        RxingBarcodeFormat::CODE_32 => c"code 32",
+        RxingBarcodeFormat::CODE_39 => c"code 39",
+        RxingBarcodeFormat::CODE_49 => c"code 49",
+        RxingBarcodeFormat::CODE_93 => c"code 93",
+        RxingBarcodeFormat::CODE_128 => c"code 128",
+        RxingBarcodeFormat::DATA_MATRIX => c"datamatrix",
+        RxingBarcodeFormat::DOTCODE => c"dotcode",
+        RxingBarcodeFormat::EAN_8 => c"ean 8",
+        RxingBarcodeFormat::EAN_13 => c"ean 13",
+        RxingBarcodeFormat::HAN_XIN => c"han xin",
+        RxingBarcodeFormat::IATA_2_OF_5 => c"iata 2 of 5",
+        RxingBarcodeFormat::INDUSTRIAL_2_OF_5 => c"industrial 2 of 5",
+        RxingBarcodeFormat::INTELLIGENT_MAIL => c"intelligent mail",
+        RxingBarcodeFormat::ITF => c"itf",
+        RxingBarcodeFormat::KIX => c"kix",
+        RxingBarcodeFormat::MATRIX_2_OF_5 => c"matrix 2 of 5",
+        RxingBarcodeFormat::MAXICODE => c"maxicode",
+        RxingBarcodeFormat::MSI => c"msi",
+        RxingBarcodeFormat::PDF_417 => c"pdf 417",
+        RxingBarcodeFormat::PHARMACODE => c"pharmacode",
+        RxingBarcodeFormat::PHARMACODE_TWO_TRACK => c"pharmacode two track",
+        RxingBarcodeFormat::PLANET => c"planet",
+        RxingBarcodeFormat::POSTNET => c"postnet",
+        RxingBarcodeFormat::PZN => c"pzn",
+        RxingBarcodeFormat::QR_CODE => c"qrcode",
+        RxingBarcodeFormat::MICRO_QR_CODE => c"mqr",
+        RxingBarcodeFormat::RECTANGULAR_MICRO_QR_CODE => c"rmqr",
+        RxingBarcodeFormat::RM4SCC => c"rm4scc",
+        RxingBarcodeFormat::RSS_14 => c"rss 14",
+        RxingBarcodeFormat::RSS_EXPANDED => c"rss expanded",
+        RxingBarcodeFormat::TELEPEN => c"telepen",
+        RxingBarcodeFormat::UPC_A => c"upc a",
+        RxingBarcodeFormat::UPC_E => c"upc e",
+        RxingBarcodeFormat::UPC_EAN_EXTENSION => c"upc/ean extension",
+        RxingBarcodeFormat::DX_FILM_EDGE => c"DXFilmEdge",
+        RxingBarcodeFormat::UNSUPPORTED => c"unsupported",
+    };
+    name.as_ptr()
+}
+
+impl TryFrom<u32> for RxingBarcodeFormat {
+    type Error = Exceptions;
+
+    fn try_from(value: u32) -> Result<Self> {
+        use RxingBarcodeFormat::*;
+
+        const FORMATS: [RxingBarcodeFormat; 41] = [
+            AUSTRALIA_POST,
+            AZTEC,
+            CODABLOCK_F,
+            CODABAR,
+            CODE_11,
+            CODE_16K,
+            CODE_32,
+            CODE_39,
+            CODE_49,
+            CODE_93,
+            CODE_128,
+            DATA_MATRIX,
+            DOTCODE,
+            EAN_8,
+            EAN_13,
+            HAN_XIN,
+            IATA_2_OF_5,
+            INDUSTRIAL_2_OF_5,
+            INTELLIGENT_MAIL,
+            ITF,
+            KIX,
+            MATRIX_2_OF_5,
+            MAXICODE,
+            MSI,
+            PDF_417,
+            PHARMACODE,
+            PHARMACODE_TWO_TRACK,
+            PLANET,
+            POSTNET,
+            PZN,
+            QR_CODE,
+            MICRO_QR_CODE,
+            RECTANGULAR_MICRO_QR_CODE,
+            RM4SCC,
+            RSS_14,
+            RSS_EXPANDED,
+            TELEPEN,
+            UPC_A,
+            UPC_E,
+            UPC_EAN_EXTENSION,
+            DX_FILM_EDGE,
+        ];
+        match value {
+            255 => Ok(UNSUPPORTED),
+            _ => FORMATS.get(value as usize).copied().ok_or_else(|| {
+                Exceptions::illegal_argument_with(format!("unknown format {value}"))
+            }),
+        }
+    }
+}
+
+impl TryFrom<u32> for RxingPixelFormat {
+    type Error = Exceptions;
+
+    fn try_from(value: u32) -> Result<Self> {
+        match value {
+            0 => Ok(RxingPixelFormat::Lum),
+            1 => Ok(RxingPixelFormat::Rgb),
+            2 => Ok(RxingPixelFormat::Bgr),
+            3 => Ok(RxingPixelFormat::Rgba),
+            4 => Ok(RxingPixelFormat::Bgra),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "unknown pixel format {value}"
+            ))),
+        }
+    }
+}
+
+impl From<RxingBarcodeFormat> for BarcodeFormat {
+    fn from(format: RxingBarcodeFormat) -> Self {
+        match format {
+            RxingBarcodeFormat::AUSTRALIA_POST => BarcodeFormat::AUSTRALIA_POST,
+            RxingBarcodeFormat::AZTEC => BarcodeFormat::AZTEC,
+            RxingBarcodeFormat::CODABLOCK_F => BarcodeFormat::CODABLOCK_F,
+            RxingBarcodeFormat::CODABAR => BarcodeFormat::CODABAR,
+            RxingBarcodeFormat::CODE_11 => BarcodeFormat::CODE_11,
+            RxingBarcodeFormat::CODE_16K => BarcodeFormat::CODE_16K,
+            RxingBarcodeFormat::CODE_32 => BarcodeFormat::CODE_32,
+            RxingBarcodeFormat::CODE_39 => BarcodeFormat::CODE_39,
+            RxingBarcodeFormat::CODE_49 => BarcodeFormat::CODE_49,
+            RxingBarcodeFormat::CODE_93 => BarcodeFormat::CODE_93,
+            RxingBarcodeFormat::CODE_128 => BarcodeFormat::CODE_128,
+            RxingBarcodeFormat::DATA_MATRIX => BarcodeFormat::DATA_MATRIX,
+            RxingBarcodeFormat::DOTCODE => BarcodeFormat::DOTCODE,
+            RxingBarcodeFormat::EAN_8 => BarcodeFormat::EAN_8,
+            RxingBarcodeFormat::EAN_13 => BarcodeFormat::EAN_13,
+            RxingBarcodeFormat::HAN_XIN => BarcodeFormat::HAN_XIN,
+            RxingBarcodeFormat::IATA_2_OF_5 => BarcodeFormat::IATA_2_OF_5,
+            RxingBarcodeFormat::INDUSTRIAL_2_OF_5 => BarcodeFormat::INDUSTRIAL_2_OF_5,
+            RxingBarcodeFormat::INTELLIGENT_MAIL => BarcodeFormat::INTELLIGENT_MAIL,
+            RxingBarcodeFormat::ITF => BarcodeFormat::ITF,
+            RxingBarcodeFormat::KIX => BarcodeFormat::KIX,
+            RxingBarcodeFormat::MATRIX_2_OF_5 => BarcodeFormat::MATRIX_2_OF_5,
+            RxingBarcodeFormat::MAXICODE => BarcodeFormat::MAXICODE,
+            RxingBarcodeFormat::MSI => BarcodeFormat::MSI,
+            RxingBarcodeFormat::PDF_417 => BarcodeFormat::PDF_417,
+            RxingBarcodeFormat::PHARMACODE => BarcodeFormat::PHARMACODE,
+            RxingBarcodeFormat::PHARMACODE_TWO_TRACK => BarcodeFormat::PHARMACODE_TWO_TRACK,
+            RxingBarcodeFormat::PLANET => BarcodeFormat::PLANET,
+            RxingBarcodeFormat::POSTNET => BarcodeFormat::POSTNET,
+            RxingBarcodeFormat::PZN => BarcodeFormat::PZN,
+            RxingBarcodeFormat::QR_CODE => BarcodeFormat::QR_CODE,
+            RxingBarcodeFormat::MICRO_QR_CODE => BarcodeFormat::MICRO_QR_CODE,
+            RxingBarcodeFormat::RECTANGULAR_MICRO_QR_CODE => {
+                BarcodeFormat::RECTANGULAR_MICRO_QR_CODE
+            }
+            RxingBarcodeFormat::RM4SCC => BarcodeFormat::RM4SCC,
+            RxingBarcodeFormat::RSS_14 => BarcodeFormat::RSS_14,
+            RxingBarcodeFormat::RSS_EXPANDED => BarcodeFormat::RSS_EXPANDED,
+            RxingBarcodeFormat::TELEPEN => BarcodeFormat::TELEPEN,
+            RxingBarcodeFormat::UPC_A => BarcodeFormat::UPC_A,
+            RxingBarcodeFormat::UPC_E => BarcodeFormat::UPC_E,
+            RxingBarcodeFormat::UPC_EAN_EXTENSION => BarcodeFormat::UPC_EAN_EXTENSION,
+            RxingBarcodeFormat::DX_FILM_EDGE => BarcodeFormat::DXFilmEdge,
+            RxingBarcodeFormat::UNSUPPORTED => BarcodeFormat::UNSUPORTED_FORMAT,
+        }
+    }
+}
+
+impl From<BarcodeFormat> for RxingBarcodeFormat {
+    fn from(format: BarcodeFormat) -> Self {
+        match format {
+            BarcodeFormat::AUSTRALIA_POST => RxingBarcodeFormat::AUSTRALIA_POST,
+            BarcodeFormat::AZTEC => RxingBarcodeFormat::AZTEC,
+            BarcodeFormat::CODABLOCK_F => RxingBarcodeFormat::CODABLOCK_F,
+            BarcodeFormat::CODABAR => RxingBarcodeFormat::CODABAR,
+            BarcodeFormat::CODE_11 => RxingBarcodeFormat::CODE_11,
+            BarcodeFormat::CODE_16K => RxingBarcodeFormat::CODE_16K,
+            BarcodeFormat::CODE_32 => RxingBarcodeFormat::CODE_32,
+            BarcodeFormat::CODE_39 => RxingBarcodeFormat::CODE_39,
+            BarcodeFormat::CODE_49 => RxingBarcodeFormat::CODE_49,
+            BarcodeFormat::CODE_93 => RxingBarcodeFormat::CODE_93,
+            BarcodeFormat::CODE_128 => RxingBarcodeFormat::CODE_128,
+            BarcodeFormat::DATA_MATRIX => RxingBarcodeFormat::DATA_MATRIX,
+            BarcodeFormat::DOTCODE => RxingBarcodeFormat::DOTCODE,
+            BarcodeFormat::EAN_8 => RxingBarcodeFormat::EAN_8,
+            BarcodeFormat::EAN_13 => RxingBarcodeFormat::EAN_13,
+            BarcodeFormat::HAN_XIN => RxingBarcodeFormat::HAN_XIN,
+            BarcodeFormat::IATA_2_OF_5 => RxingBarcodeFormat::IATA_2_OF_5,
+            BarcodeFormat::INDUSTRIAL_2_OF_5 => RxingBarcodeFormat::INDUSTRIAL_2_OF_5,
+            BarcodeFormat::INTELLIGENT_MAIL => RxingBarcodeFormat::INTELLIGENT_MAIL,
+            BarcodeFormat::ITF => RxingBarcodeFormat::ITF,
+            BarcodeFormat::KIX => RxingBarcodeFormat::KIX,
+            BarcodeFormat::MATRIX_2_OF_5 => RxingBarcodeFormat::MATRIX_2_OF_5,
+            BarcodeFormat::MAXICODE => RxingBarcodeFormat::MAXICODE,
+            BarcodeFormat::MSI => RxingBarcodeFormat::MSI,
+            BarcodeFormat::PDF_417 => RxingBarcodeFormat::PDF_417,
+            BarcodeFormat::PHARMACODE => RxingBarcodeFormat::PHARMACODE,
+            BarcodeFormat::PHARMACODE_TWO_TRACK => RxingBarcodeFormat::PHARMACODE_TWO_TRACK,
+            BarcodeFormat::PLANET => RxingBarcodeFormat::PLANET,
+            BarcodeFormat::POSTNET => RxingBarcodeFormat::POSTNET,
+            BarcodeFormat::PZN => RxingBarcodeFormat::PZN,
+            BarcodeFormat::QR_CODE => RxingBarcodeFormat::QR_CODE,
+            BarcodeFormat::MICRO_QR_CODE => RxingBarcodeFormat::MICRO_QR_CODE,
+            BarcodeFormat::RECTANGULAR_MICRO_QR_CODE => {
+                RxingBarcodeFormat::RECTANGULAR_MICRO_QR_CODE
+            }
+            BarcodeFormat::RM4SCC => RxingBarcodeFormat::RM4SCC,
+            BarcodeFormat::RSS_14 => RxingBarcodeFormat::RSS_14,
+            BarcodeFormat::RSS_EXPANDED => RxingBarcodeFormat::RSS_EXPANDED,
+            BarcodeFormat::TELEPEN => RxingBarcodeFormat::TELEPEN,
+            BarcodeFormat::UPC_A => RxingBarcodeFormat::UPC_A,
+            BarcodeFormat::UPC_E => RxingBarcodeFormat::UPC_E,
+            BarcodeFormat::UPC_EAN_EXTENSION => RxingBarcodeFormat::UPC_EAN_EXTENSION,
+            BarcodeFormat::DXFilmEdge => RxingBarcodeFormat::DX_FILM_EDGE,
+            BarcodeFormat::UNSUPORTED_FORMAT => RxingBarcodeFormat::UNSUPPORTED,
+        }
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * Exercises the C API the way an application embedding rxing would. Run through
+ * tests/c_api.rs, which compiles this file against the library built by cargo.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "rxing.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                       \
+  do {                                                                         \
+    if (!(condition)) {                                                        \
+      const char *error = rxing_last_error();                                  \
+      fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n", __FILE__,  \
+              __LINE__, #condition, error ? error : "none");                   \
+      failures++;                                                              \
+    }                                                                          \
+  } while (0)
+
+static RxingBitmap *encode(const char *contents, RxingBarcodeFormat format) {
+  RxingEncodeOptions options = rxing_encode_options_default();
+  options.width = 150;
+  options.height = 150;
+  RxingBitmap *bitmap = NULL;
+  CHECK(rxing_encode(contents, format, &options, &bitmap) == RXING_STATUS_OK);
+  CHECK(bitmap != NULL);
+  return bitmap;
+}
+
+/* Copies a bitmap into a buffer of the given pixel format with padded rows. */
+static uint8_t *convert(const RxingBitmap *bitmap, RxingPixelFormat format,
+                        size_t channels, size_t stride) {
+  uint32_t width = rxing_bitmap_width(bitmap);
+  uint32_t height = rxing_bitmap_height(bitmap);
+  const uint8_t *pixels = rxing_bitmap_data(bitmap);
+  uint8_t *data = calloc(stride * height, 1);
+  for (uint32_t y = 0; y < height; y++) {
+    for (uint32_t x = 0; x < width; x++) {
+      uint8_t *pixel = data + y * stride + x * channels;
+      memset(pixel, pixels[y * width + x], channels);
+      if (format == RXING_PIXEL_FORMAT_RGBA || format == RXING_PIXEL_FORMAT_BGRA) {
+        pixel[3] = 255;
+      }
+    }
+  }
+  return data;
+}
+
+static RxingReader *new_reader(const uint32_t *formats, size_t len,
+                               bool multiple) {
+  RxingReaderOptions options = rxing_reader_options_default();
+  options.formats = formats;
+  options.formats_len = len;
+  options.try_harder = true;
+  options.multiple = multiple;
+  RxingReader *reader = NULL;
+  CHECK(rxing_reader_new(&options, &reader) == RXING_STATUS_OK);
+  CHECK(reader != NULL);
+  return reader;
+}
+
+static bool has_metadata(const RxingResult *result, const char *key) {
+  for (size_t i = 0; i < rxing_result_metadata_count(result); i++) {
+    if (strcmp(rxing_result_metadata_key(result, i), key) == 0) {
+      return rxing_result_metadata_value(result, i) != NULL;
+    }
+  }
+  return false;
+}
+
+static void test_round_trip_in_every_pixel_format(void) {
+  const char *text = "Hello from C";
+  RxingBitmap *bitmap = encode(text, RXING_BARCODE_FORMAT_QR_CODE);
+  uint32_t formats[] = {RXING_BARCODE_FORMAT_QR_CODE};
+  RxingReader *reader = new_reader(formats, 1, false);
+
+  struct {
+    RxingPixelFormat format;
+    size_t channels;
+  } layouts[] = {
+      {RXING_PIXEL_FORMAT_LUM, 1},  {RXING_PIXEL_FORMAT_RGB, 3},
+      {RXING_PIXEL_FORMAT_BGR, 3},  {RXING_PIXEL_FORMAT_RGBA, 4},
+      {RXING_PIXEL_FORMAT_BGRA, 4},
+  };
+  for (size_t i = 0; i < sizeof(layouts) / sizeof(layouts[0]); i++) {
+    size_t stride = rxing_bitmap_width(bitmap) * layouts[i].channels + 7;
+    uint8_t *data = convert(bitmap, layouts[i].format, layouts[i].channels, stride);
+    RxingImage image = {data, rxing_bitmap_width(bitmap),
+                        rxing_bitmap_height(bitmap), stride, layouts[i].format};
+
+    RxingResults *results = NULL;
+    CHECK(rxing_reader_decode(reader, &image, &results) == RXING_STATUS_OK);
+    CHECK(rxing_results_count(results) == 1);
+
+    const RxingResult *result = rxing_results_get(results, 0);
+    size_t text_len = 0;
+    CHECK(strcmp(rxing_result_text(result, &text_len), text) == 0);
+    CHECK(text_len == strlen(text));
+    CHECK(rxing_result_format(result) == RXING_BARCODE_FORMAT_QR_CODE);
+    CHECK(strcmp(rxing_barcode_format_name(rxing_result_format(result)), "qrcode") == 0);
+
+    size_t bytes_len = 0;
+    CHECK(rxing_result_bytes(result, &bytes_len) != NULL);
+    CHECK(bytes_len > 0);
+
+    size_t points_len = 0;
+    const RxingPoint *points = rxing_result_points(result, &points_len);
+    CHECK(points_len >= 3);
+    for (size_t p = 0; p < points_len; p++) {
+      CHECK(points[p].x >= 0 && points[p].x < image.width);
+      CHECK(points[p].y >= 0 && points[p].y < image.height);
+    }
+    CHECK(has_metadata(result, "ERROR_CORRECTION_LEVEL"));
+    CHECK(rxing_results_get(results, 1) == NULL);
+
+    rxing_results_free(results);
+    free(data);
+  }
+
+  rxing_reader_free(reader);
+  rxing_bitmap_free(bitmap);
+}
+
+static void test_multiple_barcodes(void) {
+  RxingBitmap *qr = encode("first", RXING_BARCODE_FORMAT_QR_CODE);
+  RxingBitmap *dm = encode("second", RXING_BARCODE_FORMAT_DATA_MATRIX);
+  uint32_t qr_width = rxing_bitmap_width(qr), dm_width = rxing_bitmap_width(dm);
+  uint32_t width = qr_width + dm_width;
+  uint32_t height = rxing_bitmap_height(qr) > rxing_bitmap_height(dm)
+                        ? rxing_bitmap_height(qr)
+                        : rxing_bitmap_height(dm);
+  uint8_t *data = malloc((size_t)width * height);
+  memset(data, 255, (size_t)width * height);
+  for (uint32_t y = 0; y < rxing_bitmap_height(qr); y++) {
+    memcpy(data + y * width, rxing_bitmap_data(qr) + y * qr_width, qr_width);
+  }
+  for (uint32_t y = 0; y < rxing_bitmap_height(dm); y++) {
+    memcpy(data + y * width + qr_width, rxing_bitmap_data(dm) + y * dm_width, dm_width);
+  }
+
+  RxingReader *reader = new_reader(NULL, 0, true);
+  RxingImage image = {data, width, height, 0, RXING_PIXEL_FORMAT_LUM};
+  RxingResults *results = NULL;
+  CHECK(rxing_reader_decode(reader, &image, &results) == RXING_STATUS_OK);
+  CHECK(rxing_results_count(results) == 2);
+
+  bool found_first = false, found_second = false;
+  for (size_t i = 0; i < rxing_results_count(results); i++) {
+    const RxingResult *result = rxing_results_get(results, i);
+    const char *text = rxing_result_text(result, NULL);
+    found_first |= strcmp(text, "first") == 0 &&
+                   rxing_result_format(result) == RXING_BARCODE_FORMAT_QR_CODE;
+    found_second |= strcmp(text, "second") == 0 &&
+                    rxing_result_format(result) == RXING_BARCODE_FORMAT_DATA_MATRIX;
+  }
+  CHECK(found_first);
+  CHECK(found_second);
+
+  rxing_results_free(results);
+  rxing_reader_free(reader);
+  free(data);
+  rxing_bitmap_free(qr);
+  rxing_bitmap_free(dm);
+}
+
+static void test_errors(void) {
+  RxingReader *reader = new_reader(NULL, 0, false);
+  uint8_t blank[64 * 64];
+  memset(blank, 255, sizeof(blank));
+  RxingImage image = {blank, 64, 64, 0, RXING_PIXEL_FORMAT_LUM};
+  RxingResults *results = NULL;
+
+  CHECK(rxing_reader_decode(reader, &image, &results) == RXING_STATUS_NOT_FOUND);
+  CHECK(results == NULL);
+  CHECK(rxing_last_error() != NULL);
+
+  image.stride = 32;
+  CHECK(rxing_reader_decode(reader, &image, &results) == RXING_STATUS_INVALID_ARGUMENT);
+  image.stride = 0;
+  image.format = 5;
+  CHECK(rxing_reader_decode(reader, &image, &results) == RXING_STATUS_INVALID_ARGUMENT);
+  CHECK(results == NULL);
+  CHECK(rxing_reader_decode(NULL, &image, &results) == RXING_STATUS_INVALID_ARGUMENT);
+  CHECK(rxing_reader_decode(reader, NULL, &results) == RXING_STATUS_INVALID_ARGUMENT);
+
+  RxingBitmap *bitmap = NULL;
+  CHECK(rxing_encode(NULL, RXING_BARCODE_FORMAT_QR_CODE, NULL, &bitmap) ==
+        RXING_STATUS_INVALID_ARGUMENT);
+  CHECK(rxing_encode("123", RXING_BARCODE_FORMAT_QR_CODE, NULL, NULL) ==
+        RXING_STATUS_INVALID_ARGUMENT);
+  CHECK(rxing_encode("123", 41, NULL, &bitmap) == RXING_STATUS_INVALID_ARGUMENT);
+  CHECK(bitmap == NULL);
+
+  RxingReaderOptions options = rxing_reader_options_default();
+  uint32_t formats[] = {RXING_BARCODE_FORMAT_QR_CODE, 1000};
+  options.formats = formats;
+  options.formats_len = 2;
+  RxingReader *unknown_format_reader = NULL;
+  CHECK(rxing_reader_new(&options, &unknown_format_reader) == RXING_STATUS_INVALID_ARGUMENT);
+  CHECK(unknown_format_reader == NULL);
+  CHECK(rxing_barcode_format_name(1000) == NULL);
+
+  CHECK(rxing_encode("123", RXING_BARCODE_FORMAT_QR_CODE, NULL, &bitmap) == RXING_STATUS_OK);
+  CHECK(rxing_last_error() == NULL);
+  rxing_bitmap_free(bitmap);
+
+  /* Freeing null is allowed, like free() */
+  rxing_results_free(NULL);
+  rxing_reader_free(NULL);
+  rxing_bitmap_free(NULL);
+  rxing_reader_free(reader);
+}
+
+int main(void) {
+  test_round_trip_in_every_pixel_format();
+  test_multiple_barcodes();
+  test_errors();
+  if (failures > 0) {
+    fprintf(stderr, "%d checks failed\n", failures);
+    return 1;
+  }
+  printf("all checks passed\n");
+  return 0;
+}
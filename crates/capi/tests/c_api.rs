@@ -0,0 +1,252 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Builds the shared library, which cargo does not do for integration tests, returning the
+/// directory it is in. That is the parent of the `deps` directory holding this test.
+#[cfg(unix)]
+fn build_library() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    let library_dir = exe.parent().and_then(Path::parent).unwrap().to_path_buf();
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let mut build = Command::new(cargo);
+    build.args(["build", "--lib", "-p", env!("CARGO_PKG_NAME")]);
+    if library_dir.ends_with("release") {
+        build.arg("--release");
+    }
+    let status = build.status().unwrap();
+    assert!(status.success(), "could not build the library");
+    library_dir
+}
+
+#[test]
+#[cfg(unix)]
+fn c_program_uses_the_library() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let library_dir = build_library();
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("capi_test");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+
+    let status = Command::new(&compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&program)
+        .arg(manifest_dir.join("tests/c/capi_test.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-lrxing_capi")
+        .status()
+        .unwrap_or_else(|e| panic!("could not run the C compiler {compiler}: {e}"));
+    assert!(status.success(), "the C test program did not compile");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "the C test program failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Every exported function has to be declared in the header, so regenerate it with cbindgen, as
+/// described in the README, after changing the API.
+#[test]
+fn header_declares_every_function() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source = fs::read_to_string(manifest_dir.join("src/lib.rs")).unwrap();
+    let header = fs::read_to_string(manifest_dir.join("include/rxing.h")).unwrap();
+
+    let exported: Vec<&str> = source
+        .split("extern \"C\" fn ")
+        .skip(1)
+        .filter_map(|rest| rest.split('(').next())
+        .collect();
+    assert!(!exported.is_empty());
+    for name in exported {
+        assert!(
+            header.contains(&format!(" {name}(")) || header.contains(&format!("*{name}(")),
+            "{name} is missing from include/rxing.h"
+        );
+    }
+}
+
+/// The C type the header declares for a Rust type used in the API.
+fn c_type(rust: &str) -> String {
+    if let Some(pointee) = rust.strip_prefix("*const ") {
+        return format!("const {}", c_pointer(&c_type(pointee)));
+    }
+    if let Some(pointee) = rust.strip_prefix("*mut ") {
+        return c_pointer(&c_type(pointee));
+    }
+    match rust {
+        "bool" => "bool",
+        "c_char" => "char",
+        "f32" => "float",
+        "i32" => "int32_t",
+        "u8" => "uint8_t",
+        "u32" => "uint32_t",
+        "usize" => "size_t",
+        named => named,
+    }
+    .to_owned()
+}
+
+fn c_pointer(pointee: &str) -> String {
+    if pointee.ends_with('*') {
+        format!("{pointee}*")
+    } else {
+        format!("{pointee} *")
+    }
+}
+
+/// A declaration of `name` with the C type of `rust`, such as `const char *name`.
+fn c_declaration(rust: &str, name: &str) -> String {
+    let ty = c_type(rust.trim());
+    if ty.ends_with('*') {
+        format!("{ty}{name}")
+    } else {
+        format!("{ty} {name}")
+    }
+}
+
+/// `QualifiedScreamingSnakeCase`, so `NotFound` is `NOT_FOUND` and `QR_CODE` stays as it is.
+fn screaming_snake(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() && previous_lower {
+            snake.push('_');
+        }
+        previous_lower = c.is_ascii_lowercase();
+        snake.push(c.to_ascii_uppercase());
+    }
+    snake
+}
+
+/// The header with comments removed and runs of whitespace collapsed to one space.
+fn normalized_header() -> String {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let header = fs::read_to_string(manifest_dir.join("include/rxing.h")).unwrap();
+    header
+        .lines()
+        .map(|line| line.split("//").next().unwrap())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn source() -> String {
+    fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs")).unwrap()
+}
+
+/// The items of `kind` with a body, such as `pub enum Name { ... }`, as their names and the
+/// lines of their bodies without comments and attributes.
+fn items<'a>(source: &'a str, kind: &str) -> Vec<(&'a str, Vec<&'a str>)> {
+    source
+        .split(&format!("\n{kind} "))
+        .skip(1)
+        .filter_map(|rest| {
+            let (name, rest) = rest.split_once(" {\n")?;
+            let body = rest.split("\n}").next()?;
+            let lines = body
+                .lines()
+                .map(str::trim)
+                .filter(|line| {
+                    !line.is_empty() && !line.starts_with("//") && !line.starts_with('#')
+                })
+                .collect();
+            Some((name, lines))
+        })
+        .collect()
+}
+
+#[test]
+fn header_function_signatures_match() {
+    let source = source();
+    let header = normalized_header();
+
+    let mut checked = 0;
+    for rest in source.split("extern \"C\" fn ").skip(1) {
+        let (name, rest) = rest.split_once('(').unwrap();
+        let (parameters, rest) = rest.split_once(')').unwrap();
+        let returned = rest.split('{').next().unwrap().trim();
+        let parameters: Vec<String> = parameters
+            .split(',')
+            .map(str::trim)
+            .filter(|parameter| !parameter.is_empty())
+            .map(|parameter| {
+                let (name, ty) = parameter.split_once(':').unwrap();
+                c_declaration(ty, name.trim())
+            })
+            .collect();
+        let parameters = if parameters.is_empty() {
+            "void".to_owned()
+        } else {
+            parameters.join(", ")
+        };
+        let declaration = match returned.strip_prefix("->") {
+            Some(ty) => c_declaration(ty, name),
+            None => format!("void {name}"),
+        };
+        let declaration = format!("{declaration}({parameters});");
+        assert!(
+            header.contains(&declaration),
+            "include/rxing.h does not declare `{declaration}`"
+        );
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn header_structs_match() {
+    let source = source();
+    let header = normalized_header();
+
+    for (name, lines) in items(&source, "pub struct") {
+        let fields: Vec<_> = lines
+            .iter()
+            .filter_map(|line| line.strip_prefix("pub "))
+            .map(|field| {
+                let (field, ty) = field.trim_end_matches(',').split_once(':').unwrap();
+                format!("{};", c_declaration(ty, field))
+            })
+            .collect();
+        let declaration = if fields.is_empty() {
+            format!("typedef struct {name} {name};")
+        } else {
+            format!("typedef struct {name} {{ {} }} {name};", fields.join(" "))
+        };
+        assert!(
+            header.contains(&declaration),
+            "include/rxing.h does not declare `{declaration}`"
+        );
+    }
+}
+
+#[test]
+fn header_enums_match() {
+    let source = source();
+    let header = normalized_header();
+
+    for (name, lines) in items(&source, "pub enum") {
+        let prefix = screaming_snake(name);
+        let variants: Vec<_> = lines
+            .iter()
+            .map(|line| {
+                let (variant, value) = line.trim_end_matches(',').split_once(" = ").unwrap();
+                format!("{prefix}_{} = {value},", screaming_snake(variant))
+            })
+            .collect();
+        let declaration = format!("typedef enum {name} {{ {} }} {name};", variants.join(" "));
+        assert!(
+            header.contains(&declaration),
+            "include/rxing.h does not declare `{declaration}`"
+        );
+    }
+}
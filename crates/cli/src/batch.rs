@@ -0,0 +1,470 @@
+//! Decoding of many images at once: every image in a directory, the files matching a glob
+//! pattern or an image read from stdin, on several threads, with one record per file.
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use rxing::{DecodeHints, Exceptions, RXingResult};
+use serde_json::json;
+
+/// Exit code when a file could not be read or decoding failed for a reason other than not
+/// finding a barcode, or when no file matched the input.
+const EXIT_ERRORS: u8 = 1;
+
+/// Exit code when `--fail-on-missing` is given and a file had no barcode. 2 is left to clap,
+/// which exits with it on invalid arguments.
+const EXIT_MISSING: u8 = 3;
+
+/// Extensions of the files read from directories, files matching a glob are read whatever
+/// their extension.
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "avif", "bmp", "dds", "exr", "ff", "gif", "hdr", "ico", "jpeg", "jpg", "pam", "pbm", "pgm",
+    "png", "pnm", "ppm", "qoi", "svg", "tga", "tif", "tiff", "webp",
+];
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// One line per barcode found, as `file: (format) text`
+    #[default]
+    Text,
+    /// One JSON object per file
+    Jsonl,
+    /// One row per barcode found, and one per file without any
+    Csv,
+}
+
+pub struct BatchOptions {
+    pub recursive: bool,
+    pub jobs: usize,
+    pub output_format: OutputFormat,
+    pub fail_on_missing: bool,
+    pub decode_multi: bool,
+    pub filtered: bool,
+}
+
+/// Whether `file_name` names more than a single image file, so has to be decoded in batch.
+pub fn is_batch_input(file_name: &str) -> bool {
+    file_name == "-" || has_wildcard(file_name) || Path::new(file_name).is_dir()
+}
+
+enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Input::File(path) => path.display().to_string(),
+            Input::Stdin => "-".to_owned(),
+        }
+    }
+}
+
+enum Outcome {
+    Found(Vec<RXingResult>),
+    Missing,
+    Error(String),
+}
+
+struct Report {
+    file: String,
+    outcome: Outcome,
+    duration: Duration,
+}
+
+#[derive(Default)]
+struct Summary {
+    found: usize,
+    missing: usize,
+    errors: usize,
+}
+
+pub fn run(file_name: &str, hints: &DecodeHints, options: &BatchOptions) -> ExitCode {
+    let started = Instant::now();
+    let inputs = match collect_inputs(file_name, options.recursive) {
+        Ok(inputs) if inputs.is_empty() => {
+            eprintln!("error: no images found for '{file_name}'");
+            return ExitCode::from(EXIT_ERRORS);
+        }
+        Ok(inputs) => inputs,
+        Err(error) => {
+            eprintln!("error: cannot read '{file_name}': {error}");
+            return ExitCode::from(EXIT_ERRORS);
+        }
+    };
+    let mut stdin_image = Vec::new();
+    if matches!(inputs[..], [Input::Stdin])
+        && let Err(error) = io::stdin().lock().read_to_end(&mut stdin_image)
+    {
+        eprintln!("error: cannot read the image from stdin: {error}");
+        return ExitCode::from(EXIT_ERRORS);
+    }
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    let mut summary = Summary::default();
+    let written = thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, inputs.len()) {
+            let sender = sender.clone();
+            let (next, stop, inputs, stdin_image) = (&next, &stop, &inputs, &stdin_image);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let Some(input) = inputs.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                        break;
+                    };
+                    let start = Instant::now();
+                    // A decoder bug on one file should not take the rest of the batch with it
+                    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                        decode(input, stdin_image, hints, options)
+                    }))
+                    .unwrap_or_else(|_| Outcome::Error("decoder panicked".to_owned()));
+                    let report = Report {
+                        file: input.name(),
+                        outcome,
+                        duration: start.elapsed(),
+                    };
+                    if sender.send(report).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Reports are written as they come in, so in the order the files finish decoding
+        let mut out = io::stdout().lock();
+        if let OutputFormat::Csv = options.output_format {
+            writeln!(out, "file,status,format,text,error,duration_ms")?;
+        }
+        for report in receiver {
+            match report.outcome {
+                Outcome::Found(_) => summary.found += 1,
+                Outcome::Missing => summary.missing += 1,
+                Outcome::Error(_) => summary.errors += 1,
+            }
+            if let Err(error) = write_report(&mut out, &report, options.output_format) {
+                stop.store(true, Ordering::Relaxed);
+                return Err(error);
+            }
+        }
+        out.flush()
+    });
+    if let Err(error) = written {
+        eprintln!("error: cannot write the results: {error}");
+        return ExitCode::from(EXIT_ERRORS);
+    }
+
+    eprintln!(
+        "{} file(s) in {:.2}s: {} with barcodes, {} without, {} errors",
+        inputs.len(),
+        started.elapsed().as_secs_f64(),
+        summary.found,
+        summary.missing,
+        summary.errors
+    );
+    if summary.errors > 0 {
+        ExitCode::from(EXIT_ERRORS)
+    } else if options.fail_on_missing && summary.missing > 0 {
+        ExitCode::from(EXIT_MISSING)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn decode(
+    input: &Input,
+    stdin_image: &[u8],
+    hints: &DecodeHints,
+    options: &BatchOptions,
+) -> Outcome {
+    let mut hints = hints.clone();
+    let results = match input {
+        Input::File(path) => decode_file(path, &mut hints, options),
+        Input::Stdin => decode_buffer(stdin_image, &mut hints, options),
+    };
+    match results {
+        Ok(results) if results.is_empty() => Outcome::Missing,
+        Ok(results) => Outcome::Found(results),
+        Err(Exceptions::NotFoundException(_)) => Outcome::Missing,
+        Err(error) => Outcome::Error(error.to_string()),
+    }
+}
+
+fn decode_file(
+    path: &Path,
+    hints: &mut DecodeHints,
+    options: &BatchOptions,
+) -> rxing::common::Result<Vec<RXingResult>> {
+    let file_name = &path.to_string_lossy();
+    let svg = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    match (svg, options.decode_multi, options.filtered) {
+        (true, true, _) => rxing::helpers::detect_multiple_in_svg_with_hints(file_name, hints),
+        (true, false, _) => {
+            rxing::helpers::detect_in_svg_with_hints(file_name, None, hints).map(|r| vec![r])
+        }
+        (false, true, _) => rxing::helpers::detect_multiple_in_file_with_hints(file_name, hints),
+        (false, false, true) => {
+            rxing::helpers::detect_in_file_filtered_with_hints(file_name, None, hints)
+                .map(|r| vec![r])
+        }
+        (false, false, false) => {
+            rxing::helpers::detect_in_file_with_hints(file_name, None, hints).map(|r| vec![r])
+        }
+    }
+}
+
+fn decode_buffer(
+    buffer: &[u8],
+    hints: &mut DecodeHints,
+    options: &BatchOptions,
+) -> rxing::common::Result<Vec<RXingResult>> {
+    if options.decode_multi {
+        rxing::helpers::detect_multiple_in_buffer_with_hints(buffer, hints)
+    } else if options.filtered {
+        let Ok(image) = image::load_from_memory(buffer) else {
+            return Err(Exceptions::illegal_argument_with(
+                "buffer cannot be loaded as image",
+            ));
+        };
+        rxing::helpers::detect_in_image_filtered_with_hints(image, None, hints).map(|r| vec![r])
+    } else {
+        rxing::helpers::detect_in_buffer_with_hints(buffer, None, hints).map(|r| vec![r])
+    }
+}
+
+fn write_report(out: &mut impl Write, report: &Report, format: OutputFormat) -> io::Result<()> {
+    let file = &report.file;
+    let duration_ms = report.duration.as_secs_f64() * 1000.0;
+    match format {
+        OutputFormat::Text => match &report.outcome {
+            Outcome::Found(results) => {
+                for result in results {
+                    let text = result.getText().escape_default();
+                    writeln!(out, "{file}: ({}) {text}", result.getBarcodeFormat())?;
+                }
+                Ok(())
+            }
+            Outcome::Missing => writeln!(out, "{file}: no barcode found"),
+            Outcome::Error(error) => writeln!(out, "{file}: error: {error}"),
+        },
+        OutputFormat::Jsonl => {
+            let (status, results, error) = match &report.outcome {
+                Outcome::Found(results) => ("found", results.as_slice(), None),
+                Outcome::Missing => ("not_found", &[][..], None),
+                Outcome::Error(error) => ("error", &[][..], Some(error)),
+            };
+            let results: Vec<_> = results
+                .iter()
+                .map(|result| {
+                    json!({
+                        "format": result.getBarcodeFormat().to_string(),
+                        "text": result.getText(),
+                        "points": result.getPoints().iter().map(|p| [p.x, p.y]).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            let record = json!({
+                "file": file,
+                "status": status,
+                "results": results,
+                "error": error,
+                "duration_ms": (duration_ms * 1000.0).round() / 1000.0,
+            });
+            writeln!(out, "{record}")
+        }
+        OutputFormat::Csv => {
+            let file = csv_field(file);
+            match &report.outcome {
+                Outcome::Found(results) => {
+                    for result in results {
+                        writeln!(
+                            out,
+                            "{file},found,{},{},,{duration_ms:.3}",
+                            csv_field(&result.getBarcodeFormat().to_string()),
+                            csv_field(result.getText())
+                        )?;
+                    }
+                    Ok(())
+                }
+                Outcome::Missing => writeln!(out, "{file},not_found,,,,{duration_ms:.3}"),
+                Outcome::Error(error) => {
+                    writeln!(out, "{file},error,,,{},{duration_ms:.3}", csv_field(error))
+                }
+            }
+        }
+    }
+}
+
+/// Quotes a CSV field if needed, as in RFC 4180.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn collect_inputs(file_name: &str, recursive: bool) -> io::Result<Vec<Input>> {
+    if file_name == "-" {
+        return Ok(vec![Input::Stdin]);
+    }
+    let mut files = Vec::new();
+    if has_wildcard(file_name) {
+        let (base, pattern) = split_glob(file_name);
+        expand_glob(&base, &pattern, &mut files)?;
+    } else if Path::new(file_name).is_dir() {
+        list_images(Path::new(file_name), recursive, &mut files)?;
+    } else {
+        files.push(PathBuf::from(file_name));
+    }
+    files.sort();
+    Ok(files.into_iter().map(Input::File).collect())
+}
+
+fn list_images(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if recursive {
+                list_images(&path, recursive, files)?;
+            }
+        } else if path.extension().is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|image| ext.eq_ignore_ascii_case(image))
+        }) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn has_wildcard(text: &str) -> bool {
+    text.contains(['*', '?'])
+}
+
+/// Splits a glob into the directory holding everything it matches and the components of the
+/// pattern below that directory.
+fn split_glob(glob: &str) -> (PathBuf, Vec<String>) {
+    let mut base = PathBuf::new();
+    let mut pattern = Vec::new();
+    for component in Path::new(glob).components() {
+        let text = component.as_os_str().to_string_lossy();
+        if pattern.is_empty() && !has_wildcard(&text) {
+            base.push(component);
+        } else {
+            pattern.push(text.into_owned());
+        }
+    }
+    if base.as_os_str().is_empty() {
+        base.push(".");
+    }
+    (base, pattern)
+}
+
+/// Adds the files below `dir` matching `pattern`, where a `**` component matches any number
+/// of directories.
+fn expand_glob(dir: &Path, pattern: &[String], files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let Some((component, rest)) = pattern.split_first() else {
+        if dir.is_file() {
+            files.push(dir.to_path_buf());
+        }
+        return Ok(());
+    };
+    if !has_wildcard(component) {
+        return expand_glob(&dir.join(component), rest, files);
+    }
+    if component == "**" {
+        expand_glob(dir, rest, files)?;
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        // Like shells, wildcards do not match hidden files
+        if name.starts_with('.') {
+            continue;
+        }
+        if component == "**" {
+            if entry.path().is_dir() {
+                expand_glob(&entry.path(), pattern, files)?;
+            }
+        } else if wildcard_match(component, &name) {
+            expand_glob(&entry.path(), rest, files)?;
+        }
+    }
+    Ok(())
+}
+
+/// Matches `name` against a pattern where `*` stands for any characters and `?` for one.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and of the name where it started matching
+    let mut star = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{csv_field, split_glob, wildcard_match};
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*.png", "scan.png"));
+        assert!(wildcard_match("scan-??.png", "scan-01.png"));
+        assert!(wildcard_match("*a*b*", "xaxxbx"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("*.png", "scan.jpg"));
+        assert!(!wildcard_match("scan-?.png", "scan-01.png"));
+    }
+
+    #[test]
+    fn glob_base() {
+        let (base, pattern) = split_glob("scans/2024/**/*.png");
+        assert_eq!(base, PathBuf::from("scans/2024"));
+        assert_eq!(pattern, ["**", "*.png"]);
+        assert_eq!(split_glob("*.png").0, PathBuf::from("."));
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -1,9 +1,13 @@
+mod batch;
+
 use std::{
     collections::{HashMap, HashSet},
     path::PathBuf,
     process::ExitCode,
+    thread,
 };
 
+use batch::{BatchOptions, OutputFormat};
 use clap::{ArgGroup, Parser, Subcommand};
 use rxing::{BarcodeFormat, MultiFormatWriter, Writer};
 #[allow(unused_imports)]
@@ -12,6 +16,9 @@ use serde_json::de;
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    /// The image to read or write. When decoding, this can also be a directory, a quoted glob
+    /// pattern such as 'scans/**/*.png', or - to read an image from stdin.
+    #[arg(verbatim_doc_comment)]
     file_name: String,
     #[command(subcommand)]
     command: Commands,
@@ -100,6 +107,24 @@ enum Commands {
         /// second time with an inverted image.
         #[arg(long, verbatim_doc_comment)]
         also_inverted: Option<bool>,
+
+        /// Also read the images in subdirectories when the input is a directory.
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// Number of images decoded at once when reading several, by default one per CPU.
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Print one record per file, including files without barcodes and the time taken. This
+        /// is the output for directories, globs and stdin, where it defaults to text.
+        /// Exits with 1 if any file could not be read or decoded, and 0 otherwise.
+        #[arg(long, value_enum, verbatim_doc_comment)]
+        output_format: Option<OutputFormat>,
+
+        /// Exit with 3 if any file has no barcode, for example to fail a CI job.
+        #[arg(long)]
+        fail_on_missing: bool,
     },
     #[command(group(
         ArgGroup::new("code_set_rules")
@@ -261,6 +286,10 @@ fn main() -> ExitCode {
             detailed_results_json,
             parsed_results,
             raw_bytes,
+            recursive,
+            jobs,
+            output_format,
+            fail_on_missing,
         } => decode_command(
             &cli.file_name,
             try_harder,
@@ -280,6 +309,10 @@ fn main() -> ExitCode {
             detailed_results_json,
             parsed_results,
             raw_bytes,
+            recursive,
+            jobs,
+            output_format,
+            fail_on_missing,
         ),
         Commands::Encode {
             barcode_type,
@@ -348,6 +381,10 @@ fn decode_command(
     detailed_results_json: &bool,
     parsed_bytes: &bool,
     raw_bytes: &bool,
+    recursive: &bool,
+    jobs: &Option<usize>,
+    output_format: &Option<OutputFormat>,
+    fail_on_missing: &bool,
 ) -> ExitCode {
     let mut hints: rxing::DecodingHintDictionary = HashMap::new();
     if let Some(other) = other {
@@ -438,6 +475,18 @@ fn decode_command(
         return ExitCode::FAILURE;
     }
 
+    if output_format.is_some() || batch::is_batch_input(file_name) {
+        let options = BatchOptions {
+            recursive: *recursive,
+            jobs: jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get())),
+            output_format: output_format.unwrap_or_default(),
+            fail_on_missing: *fail_on_missing,
+            decode_multi: *decode_multi,
+            filtered: *filtered,
+        };
+        return batch::run(file_name, &hints.into(), &options);
+    }
+
     if *decode_multi {
         let results = if extension == "svg" {
             rxing::helpers::detect_multiple_in_svg_with_hints(file_name, &mut hints.into())
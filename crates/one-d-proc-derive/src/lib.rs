@@ -60,6 +60,17 @@ fn impl_one_d_reader_macro(ast: &syn::DeriveInput) -> TokenStream {
              }
             }
         }
+
+        // The reader keeps scratch state while decoding rows, so decode with a copy of it.
+        impl #impl_generics crate::ImmutableReader for #name #ty_generics #where_clause {
+            fn immutable_decode_with_hints<B: crate::Binarizer>(
+                &self,
+                image: &mut crate::BinaryBitmap<B>,
+                hints: &crate::DecodeHints,
+            ) -> Result<crate::RXingResult, Exceptions> {
+                crate::Reader::decode_with_hints(&mut self.clone(), image, hints)
+            }
+        }
     };
 
     TokenStream::from(gen)
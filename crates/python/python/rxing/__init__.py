@@ -0,0 +1,31 @@
+"""Read and write barcodes with rxing, from NumPy arrays and PIL images."""
+
+from ._rxing import (
+    BarcodeFormat,
+    DecodeHints,
+    DecodeResult,
+    EncodeHints,
+    ItfBearerBars,
+    MsiChecksum,
+    RxingError,
+    SymbolShape,
+    decode,
+    decode_multiple,
+    encode,
+    encode_image,
+)
+
+__all__ = [
+    "BarcodeFormat",
+    "DecodeHints",
+    "DecodeResult",
+    "EncodeHints",
+    "ItfBearerBars",
+    "MsiChecksum",
+    "RxingError",
+    "SymbolShape",
+    "decode",
+    "decode_multiple",
+    "encode",
+    "encode_image",
+]
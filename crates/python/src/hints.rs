@@ -0,0 +1,366 @@
+use std::collections::HashSet;
+
+use pyo3::prelude::*;
+use rxing::{
+    Dimension, datamatrix::encoder::SymbolShapeHint, oned::ITFBearerBars, oned::MSIChecksum,
+    pdf417::encoder::Dimensions,
+};
+
+use crate::BarcodeFormat;
+
+/// Check digits of MSI barcodes
+#[pyclass(module = "rxing", eq, eq_int, hash, frozen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum MsiChecksum {
+    NONE,
+    MOD10,
+    MOD1010,
+    MOD11,
+    MOD1110,
+}
+
+impl From<MsiChecksum> for MSIChecksum {
+    fn from(checksum: MsiChecksum) -> Self {
+        match checksum {
+            MsiChecksum::NONE => MSIChecksum::None,
+            MsiChecksum::MOD10 => MSIChecksum::Mod10,
+            MsiChecksum::MOD1010 => MSIChecksum::Mod1010,
+            MsiChecksum::MOD11 => MSIChecksum::Mod11,
+            MsiChecksum::MOD1110 => MSIChecksum::Mod1110,
+        }
+    }
+}
+
+/// Shape of Data Matrix symbols
+#[pyclass(module = "rxing", eq, eq_int, hash, frozen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum SymbolShape {
+    NONE,
+    SQUARE,
+    RECTANGLE,
+}
+
+impl From<SymbolShape> for SymbolShapeHint {
+    fn from(shape: SymbolShape) -> Self {
+        match shape {
+            SymbolShape::NONE => SymbolShapeHint::FORCE_NONE,
+            SymbolShape::SQUARE => SymbolShapeHint::FORCE_SQUARE,
+            SymbolShape::RECTANGLE => SymbolShapeHint::FORCE_RECTANGLE,
+        }
+    }
+}
+
+/// Bearer bars around ITF barcodes
+#[pyclass(module = "rxing", eq, eq_int, hash, frozen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum ItfBearerBars {
+    NONE,
+    TOP_AND_BOTTOM,
+    FRAME,
+}
+
+impl From<ItfBearerBars> for ITFBearerBars {
+    fn from(bars: ItfBearerBars) -> Self {
+        match bars {
+            ItfBearerBars::NONE => ITFBearerBars::None,
+            ItfBearerBars::TOP_AND_BOTTOM => ITFBearerBars::TopAndBottom,
+            ItfBearerBars::FRAME => ITFBearerBars::Frame,
+        }
+    }
+}
+
+/// Hints for decoding, the fields of `rxing::DecodeHints`. Fields left as `None` keep the
+/// default of the reader.
+#[pyclass(module = "rxing", get_all, set_all)]
+#[derive(Debug, Clone, Default)]
+pub struct DecodeHints {
+    formats: Option<Vec<BarcodeFormat>>,
+    try_harder: Option<bool>,
+    pure_barcode: Option<bool>,
+    also_inverted: Option<bool>,
+    character_set: Option<String>,
+    allowed_lengths: Option<Vec<u32>>,
+    assume_code_39_check_digit: Option<bool>,
+    assume_gs1: Option<bool>,
+    return_codabar_start_end: Option<bool>,
+    allowed_ean_extensions: Option<Vec<u32>>,
+    qr_assume_spec_conform_input: Option<bool>,
+    telepen_as_numeric: Option<bool>,
+    code_11_check_digits: Option<u32>,
+    msi_checksum: Option<MsiChecksum>,
+    assume_two_of_five_check_digit: Option<bool>,
+    decode_composite: Option<bool>,
+    minimum_module_size: Option<u32>,
+    other: Option<String>,
+}
+
+#[pymethods]
+impl DecodeHints {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        formats=None,
+        try_harder=None,
+        pure_barcode=None,
+        also_inverted=None,
+        character_set=None,
+        allowed_lengths=None,
+        assume_code_39_check_digit=None,
+        assume_gs1=None,
+        return_codabar_start_end=None,
+        allowed_ean_extensions=None,
+        qr_assume_spec_conform_input=None,
+        telepen_as_numeric=None,
+        code_11_check_digits=None,
+        msi_checksum=None,
+        assume_two_of_five_check_digit=None,
+        decode_composite=None,
+        minimum_module_size=None,
+        other=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        formats: Option<Vec<BarcodeFormat>>,
+        try_harder: Option<bool>,
+        pure_barcode: Option<bool>,
+        also_inverted: Option<bool>,
+        character_set: Option<String>,
+        allowed_lengths: Option<Vec<u32>>,
+        assume_code_39_check_digit: Option<bool>,
+        assume_gs1: Option<bool>,
+        return_codabar_start_end: Option<bool>,
+        allowed_ean_extensions: Option<Vec<u32>>,
+        qr_assume_spec_conform_input: Option<bool>,
+        telepen_as_numeric: Option<bool>,
+        code_11_check_digits: Option<u32>,
+        msi_checksum: Option<MsiChecksum>,
+        assume_two_of_five_check_digit: Option<bool>,
+        decode_composite: Option<bool>,
+        minimum_module_size: Option<u32>,
+        other: Option<String>,
+    ) -> Self {
+        Self {
+            formats,
+            try_harder,
+            pure_barcode,
+            also_inverted,
+            character_set,
+            allowed_lengths,
+            assume_code_39_check_digit,
+            assume_gs1,
+            return_codabar_start_end,
+            allowed_ean_extensions,
+            qr_assume_spec_conform_input,
+            telepen_as_numeric,
+            code_11_check_digits,
+            msi_checksum,
+            assume_two_of_five_check_digit,
+            decode_composite,
+            minimum_module_size,
+            other,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl From<&DecodeHints> for rxing::DecodeHints {
+    fn from(hints: &DecodeHints) -> Self {
+        Self {
+            PossibleFormats: hints
+                .formats
+                .as_ref()
+                .map(|formats| formats.iter().map(|&f| f.into()).collect::<HashSet<_>>()),
+            TryHarder: hints.try_harder,
+            PureBarcode: hints.pure_barcode,
+            AlsoInverted: hints.also_inverted,
+            CharacterSet: hints.character_set.clone(),
+            AllowedLengths: hints.allowed_lengths.clone(),
+            AssumeCode39CheckDigit: hints.assume_code_39_check_digit,
+            AssumeGs1: hints.assume_gs1,
+            ReturnCodabarStartEnd: hints.return_codabar_start_end,
+            AllowedEanExtensions: hints.allowed_ean_extensions.clone(),
+            QrAssumeSpecConformInput: hints.qr_assume_spec_conform_input,
+            TelepenAsNumeric: hints.telepen_as_numeric,
+            Code11CheckDigits: hints.code_11_check_digits,
+            MsiChecksum: hints.msi_checksum.map(Into::into),
+            AssumeTwoOfFiveCheckDigit: hints.assume_two_of_five_check_digit,
+            DecodeComposite: hints.decode_composite,
+            MinimumModuleSize: hints.minimum_module_size,
+            Other: hints.other.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Hints for encoding, the fields of `rxing::EncodeHints`. Fields left as `None` keep the
+/// default of the writer.
+#[pyclass(module = "rxing", get_all, set_all)]
+#[derive(Debug, Clone, Default)]
+pub struct EncodeHints {
+    error_correction: Option<String>,
+    character_set: Option<String>,
+    margin: Option<u32>,
+    data_matrix_shape: Option<SymbolShape>,
+    data_matrix_compact: Option<bool>,
+    min_size: Option<(usize, usize)>,
+    max_size: Option<(usize, usize)>,
+    pdf417_compact: Option<bool>,
+    pdf417_compaction: Option<String>,
+    /// Minimum and maximum columns, then minimum and maximum rows
+    pdf417_dimensions: Option<(usize, usize, usize, usize)>,
+    pdf417_auto_eci: Option<bool>,
+    aztec_layers: Option<i32>,
+    qr_version: Option<u32>,
+    qr_mask_pattern: Option<u32>,
+    qr_compact: Option<bool>,
+    gs1_format: Option<bool>,
+    force_code_set: Option<String>,
+    force_c40: Option<bool>,
+    code128_compact: Option<bool>,
+    telepen_as_numeric: Option<bool>,
+    code_11_check_digits: Option<u32>,
+    msi_checksum: Option<MsiChecksum>,
+    two_of_five_check_digit: Option<bool>,
+    itf_14: Option<bool>,
+    itf_bearer_bars: Option<ItfBearerBars>,
+}
+
+#[pymethods]
+impl EncodeHints {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        error_correction=None,
+        character_set=None,
+        margin=None,
+        data_matrix_shape=None,
+        data_matrix_compact=None,
+        min_size=None,
+        max_size=None,
+        pdf417_compact=None,
+        pdf417_compaction=None,
+        pdf417_dimensions=None,
+        pdf417_auto_eci=None,
+        aztec_layers=None,
+        qr_version=None,
+        qr_mask_pattern=None,
+        qr_compact=None,
+        gs1_format=None,
+        force_code_set=None,
+        force_c40=None,
+        code128_compact=None,
+        telepen_as_numeric=None,
+        code_11_check_digits=None,
+        msi_checksum=None,
+        two_of_five_check_digit=None,
+        itf_14=None,
+        itf_bearer_bars=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        error_correction: Option<String>,
+        character_set: Option<String>,
+        margin: Option<u32>,
+        data_matrix_shape: Option<SymbolShape>,
+        data_matrix_compact: Option<bool>,
+        min_size: Option<(usize, usize)>,
+        max_size: Option<(usize, usize)>,
+        pdf417_compact: Option<bool>,
+        pdf417_compaction: Option<String>,
+        pdf417_dimensions: Option<(usize, usize, usize, usize)>,
+        pdf417_auto_eci: Option<bool>,
+        aztec_layers: Option<i32>,
+        qr_version: Option<u32>,
+        qr_mask_pattern: Option<u32>,
+        qr_compact: Option<bool>,
+        gs1_format: Option<bool>,
+        force_code_set: Option<String>,
+        force_c40: Option<bool>,
+        code128_compact: Option<bool>,
+        telepen_as_numeric: Option<bool>,
+        code_11_check_digits: Option<u32>,
+        msi_checksum: Option<MsiChecksum>,
+        two_of_five_check_digit: Option<bool>,
+        itf_14: Option<bool>,
+        itf_bearer_bars: Option<ItfBearerBars>,
+    ) -> Self {
+        Self {
+            error_correction,
+            character_set,
+            margin,
+            data_matrix_shape,
+            data_matrix_compact,
+            min_size,
+            max_size,
+            pdf417_compact,
+            pdf417_compaction,
+            pdf417_dimensions,
+            pdf417_auto_eci,
+            aztec_layers,
+            qr_version,
+            qr_mask_pattern,
+            qr_compact,
+            gs1_format,
+            force_code_set,
+            force_c40,
+            code128_compact,
+            telepen_as_numeric,
+            code_11_check_digits,
+            msi_checksum,
+            two_of_five_check_digit,
+            itf_14,
+            itf_bearer_bars,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl From<&EncodeHints> for rxing::EncodeHints {
+    // Sizes are deprecated in rxing but still honoured by the Data Matrix writer
+    #[allow(deprecated)]
+    fn from(hints: &EncodeHints) -> Self {
+        // Some hints are strings in rxing, as they came from a string keyed dictionary
+        let text = |value: Option<u32>| value.map(|v| v.to_string());
+        let flag = |value: Option<bool>| value.map(|v| v.to_string());
+        Self {
+            ErrorCorrection: hints.error_correction.clone(),
+            CharacterSet: hints.character_set.clone(),
+            Margin: text(hints.margin),
+            DataMatrixShape: hints.data_matrix_shape.map(Into::into),
+            DataMatrixCompact: hints.data_matrix_compact,
+            MinSize: hints.min_size.map(|(w, h)| Dimension::new(w, h)),
+            MaxSize: hints.max_size.map(|(w, h)| Dimension::new(w, h)),
+            Pdf417Compact: flag(hints.pdf417_compact),
+            Pdf417Compaction: hints.pdf417_compaction.clone(),
+            Pdf417Dimensions: hints.pdf417_dimensions.map(
+                |(min_cols, max_cols, min_rows, max_rows)| {
+                    Dimensions::new(min_cols, max_cols, min_rows, max_rows)
+                },
+            ),
+            Pdf417AutoEci: flag(hints.pdf417_auto_eci),
+            AztecLayers: hints.aztec_layers,
+            QrVersion: text(hints.qr_version),
+            QrMaskPattern: text(hints.qr_mask_pattern),
+            QrCompact: flag(hints.qr_compact),
+            Gs1Format: hints.gs1_format,
+            ForceCodeSet: hints.force_code_set.clone(),
+            ForceC40: hints.force_c40,
+            Code128Compact: hints.code128_compact,
+            TelepenAsNumeric: hints.telepen_as_numeric,
+            Code11CheckDigits: hints.code_11_check_digits,
+            MsiChecksum: hints.msi_checksum.map(Into::into),
+            TwoOfFiveCheckDigit: hints.two_of_five_check_digit,
+            Itf14: hints.itf_14,
+            ItfBearerBars: hints.itf_bearer_bars.map(Into::into),
+        }
+    }
+}
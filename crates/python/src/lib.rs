@@ -0,0 +1,466 @@
+//! Python bindings for rxing.
+//!
+//! Images are NumPy arrays of `uint8`, either greyscale `(height, width)` or with 1, 3 or 4
+//! channels `(height, width, channels)`, in any memory layout, or PIL images. Decoding and
+//! encoding release the GIL, so other Python threads keep running meanwhile. The Python side
+//! of the package lives in `python/rxing`.
+
+mod hints;
+
+use std::str::FromStr;
+
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2, PyReadonlyArray3, ndarray::Array2};
+use pyo3::{
+    IntoPyObjectExt, create_exception,
+    exceptions::{PyException, PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBytes, PyDict, PyList},
+};
+use rxing::{
+    BinaryBitmap, Exceptions, Luma8LuminanceSource, MultiFormatReader, MultiFormatWriter,
+    MultiUseMultiFormatReader, RXingResult, RXingResultMetadataValue, Reader, Writer,
+    common::{BitMatrix, HybridBinarizer},
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+};
+
+pub use hints::{DecodeHints, EncodeHints, ItfBearerBars, MsiChecksum, SymbolShape};
+
+create_exception!(
+    rxing,
+    RxingError,
+    PyException,
+    "A barcode could not be decoded or encoded."
+);
+
+macro_rules! barcode_formats {
+    ($($python:ident => $rust:ident),* $(,)?) => {
+        /// Barcode symbologies, the same as `rxing::BarcodeFormat`
+        #[pyclass(module = "rxing", eq, eq_int, hash, frozen)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        pub enum BarcodeFormat {
+            $($python),*
+        }
+
+        impl From<BarcodeFormat> for rxing::BarcodeFormat {
+            fn from(format: BarcodeFormat) -> Self {
+                match format {
+                    $(BarcodeFormat::$python => rxing::BarcodeFormat::$rust),*
+                }
+            }
+        }
+
+        impl From<rxing::BarcodeFormat> for BarcodeFormat {
+            fn from(format: rxing::BarcodeFormat) -> Self {
+                match format {
+                    $(rxing::BarcodeFormat::$rust => BarcodeFormat::$python),*
+                }
+            }
+        }
+    };
+}
+
+barcode_formats! {
+    AUSTRALIA_POST => AUSTRALIA_POST,
+    AZTEC => AZTEC,
+    CODABLOCK_F => CODABLOCK_F,
+    CODABAR => CODABAR,
+    CODE_11 => CODE_11,
+    CODE_16K => CODE_16K,
+    CODE_32 => CODE_32,
+    CODE_39 => CODE_39,
+    CODE_49 => CODE_49,
+    CODE_93 => CODE_93,
+    CODE_128 => CODE_128,
+    DATA_MATRIX => DATA_MATRIX,
+    DOTCODE => DOTCODE,
+    EAN_8 => EAN_8,
+    EAN_13 => EAN_13,
+    HAN_XIN => HAN_XIN,
+    IATA_2_OF_5 => IATA_2_OF_5,
+    INDUSTRIAL_2_OF_5 => INDUSTRIAL_2_OF_5,
+    INTELLIGENT_MAIL => INTELLIGENT_MAIL,
+    ITF => ITF,
+    KIX => KIX,
+    MATRIX_2_OF_5 => MATRIX_2_OF_5,
+    MAXICODE => MAXICODE,
+    MSI => MSI,
+    PDF_417 => PDF_417,
+    PHARMACODE => PHARMACODE,
+    PHARMACODE_TWO_TRACK => PHARMACODE_TWO_TRACK,
+    PLANET => PLANET,
+    POSTNET => POSTNET,
+    PZN => PZN,
+    QR_CODE => QR_CODE,
+    MICRO_QR_CODE => MICRO_QR_CODE,
+    RECTANGULAR_MICRO_QR_CODE => RECTANGULAR_MICRO_QR_CODE,
+    RM4SCC => RM4SCC,
+    RSS_14 => RSS_14,
+    RSS_EXPANDED => RSS_EXPANDED,
+    TELEPEN => TELEPEN,
+    UPC_A => UPC_A,
+    UPC_E => UPC_E,
+    UPC_EAN_EXTENSION => UPC_EAN_EXTENSION,
+    DX_FILM_EDGE => DXFilmEdge,
+    UNSUPPORTED => UNSUPORTED_FORMAT,
+}
+
+#[pymethods]
+impl BarcodeFormat {
+    /// Looks a format up by any of the names rxing accepts, such as `"qrcode"` or `"QR_CODE"`
+    #[staticmethod]
+    fn from_name(name: &str) -> PyResult<Self> {
+        format_from_name(name)
+    }
+
+    fn __str__(&self) -> String {
+        rxing::BarcodeFormat::from(*self).to_string()
+    }
+}
+
+/// A format given either as a [`BarcodeFormat`] or by name
+#[derive(FromPyObject)]
+enum FormatArg {
+    Format(BarcodeFormat),
+    Name(String),
+}
+
+impl FormatArg {
+    fn resolve(self) -> PyResult<rxing::BarcodeFormat> {
+        match self {
+            FormatArg::Format(format) => Ok(format.into()),
+            FormatArg::Name(name) => format_from_name(&name).map(Into::into),
+        }
+    }
+}
+
+fn format_from_name(name: &str) -> PyResult<BarcodeFormat> {
+    match rxing::BarcodeFormat::from_str(name) {
+        Ok(rxing::BarcodeFormat::UNSUPORTED_FORMAT) | Err(_) => Err(PyValueError::new_err(
+            format!("unknown barcode format '{name}'"),
+        )),
+        Ok(format) => Ok(format.into()),
+    }
+}
+
+/// A decoded barcode
+#[pyclass(module = "rxing", frozen)]
+pub struct DecodeResult {
+    #[pyo3(get)]
+    text: String,
+    raw_bytes: Vec<u8>,
+    #[pyo3(get)]
+    num_bits: usize,
+    #[pyo3(get)]
+    format: BarcodeFormat,
+    /// Points locating the barcode in the image, such as its corners or finder patterns
+    #[pyo3(get)]
+    points: Vec<(f32, f32)>,
+    metadata: Vec<(String, RXingResultMetadataValue)>,
+}
+
+#[pymethods]
+impl DecodeResult {
+    #[getter]
+    fn raw_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.raw_bytes)
+    }
+
+    /// The metadata of the barcode, keyed by names such as `ERROR_CORRECTION_LEVEL`
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (key, value) in &self.metadata {
+            dict.set_item(key, metadata_value(py, value)?)?;
+        }
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DecodeResult(text={:?}, format={:?}, points={:?})",
+            self.text, self.format, self.points
+        )
+    }
+}
+
+impl From<&RXingResult> for DecodeResult {
+    fn from(result: &RXingResult) -> Self {
+        let mut metadata: Vec<_> = result
+            .getRXingResultMetadata()
+            .iter()
+            .map(|(key, value)| (format!("{key:?}"), value.clone()))
+            .collect();
+        metadata.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            text: result.getText().to_owned(),
+            raw_bytes: result.getRawBytes().to_vec(),
+            num_bits: result.getNumBits(),
+            format: (*result.getBarcodeFormat()).into(),
+            points: result.getPoints().iter().map(|p| (p.x, p.y)).collect(),
+            metadata,
+        }
+    }
+}
+
+fn metadata_value<'py>(
+    py: Python<'py>,
+    value: &RXingResultMetadataValue,
+) -> PyResult<Bound<'py, PyAny>> {
+    use RXingResultMetadataValue::*;
+
+    match value {
+        OTHER(text)
+        | ErrorCorrectionLevel(text)
+        | SuggestedPrice(text)
+        | PossibleCountry(text)
+        | UpcEanExtension(text)
+        | Gs1Composite(text)
+        | Gtin(text)
+        | SymbologyIdentifier(text)
+        | ContentType(text) => text.into_bound_py_any(py),
+        Orientation(number)
+        | IssueNumber(number)
+        | StructuredAppendSequence(number)
+        | StructuredAppendParity(number) => number.into_bound_py_any(py),
+        IsMirrored(flag) | IsInverted(flag) | FilteredClosed(flag) => flag.into_bound_py_any(py),
+        ByteSegments(segments) => {
+            PyList::new(py, segments.iter().map(|s| PyBytes::new(py, s))).map(Bound::into_any)
+        }
+        FilteredResolution(resolution) => resolution.into_bound_py_any(py),
+        Pdf417ExtraMetadata(extra) => format!("{extra:?}").into_bound_py_any(py),
+    }
+}
+
+/// Decodes the first barcode found in `image`, returning `None` if there is none.
+#[pyfunction]
+#[pyo3(signature = (image, hints=None))]
+fn decode(
+    py: Python<'_>,
+    image: &Bound<'_, PyAny>,
+    hints: Option<&DecodeHints>,
+) -> PyResult<Option<DecodeResult>> {
+    let (luma, width, height) = luminances(image)?;
+    let hints = hints.map(rxing::DecodeHints::from).unwrap_or_default();
+    let result = py.detach(move || {
+        let mut bitmap = bitmap(luma, width, height);
+        MultiFormatReader::default().decode_with_hints(&mut bitmap, &hints)
+    });
+    match result {
+        Ok(result) => Ok(Some(DecodeResult::from(&result))),
+        Err(Exceptions::NotFoundException(_)) => Ok(None),
+        Err(e) => Err(py_error(e)),
+    }
+}
+
+/// Decodes every barcode found in `image`, returning an empty list if there is none.
+#[pyfunction]
+#[pyo3(signature = (image, hints=None))]
+fn decode_multiple(
+    py: Python<'_>,
+    image: &Bound<'_, PyAny>,
+    hints: Option<&DecodeHints>,
+) -> PyResult<Vec<DecodeResult>> {
+    let (luma, width, height) = luminances(image)?;
+    let mut hints = hints.map(rxing::DecodeHints::from).unwrap_or_default();
+    hints.TryHarder = hints.TryHarder.or(Some(true));
+    let results = py.detach(move || {
+        let mut bitmap = bitmap(luma, width, height);
+        let mut scanner = GenericMultipleBarcodeReader::new(MultiUseMultiFormatReader::default());
+        scanner.decode_multiple_with_hints(&mut bitmap, &hints)
+    });
+    match results {
+        Ok(results) => Ok(results.iter().map(DecodeResult::from).collect()),
+        Err(Exceptions::NotFoundException(_)) => Ok(Vec::new()),
+        Err(e) => Err(py_error(e)),
+    }
+}
+
+/// Encodes `contents` as a barcode of `format`, returning a `uint8` array of shape
+/// `(height, width)` with 0 for dark and 255 for light modules.
+///
+/// A width and height of 0 give the smallest size of the barcode, otherwise it is scaled up
+/// to at least that size.
+#[pyfunction]
+#[pyo3(signature = (contents, format, width=0, height=0, hints=None))]
+fn encode<'py>(
+    py: Python<'py>,
+    contents: &str,
+    format: FormatArg,
+    width: i32,
+    height: i32,
+    hints: Option<&EncodeHints>,
+) -> PyResult<Bound<'py, PyArray2<u8>>> {
+    let pixels = encode_pixels(py, contents, format, width, height, hints)?;
+    Ok(pixels.into_pyarray(py))
+}
+
+/// Encodes `contents` as a barcode of `format`, like [`encode`], returning a PIL image of
+/// mode `L`. Needs Pillow to be installed.
+#[pyfunction]
+#[pyo3(signature = (contents, format, width=0, height=0, hints=None))]
+fn encode_image<'py>(
+    py: Python<'py>,
+    contents: &str,
+    format: FormatArg,
+    width: i32,
+    height: i32,
+    hints: Option<&EncodeHints>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let pixels = encode_pixels(py, contents, format, width, height, hints)?;
+    let (rows, columns) = pixels.dim();
+    let bytes = PyBytes::new(py, &pixels.into_raw_vec_and_offset().0);
+    py.import("PIL.Image")?
+        .call_method1("frombytes", ("L", (columns, rows), bytes))
+}
+
+fn encode_pixels(
+    py: Python<'_>,
+    contents: &str,
+    format: FormatArg,
+    width: i32,
+    height: i32,
+    hints: Option<&EncodeHints>,
+) -> PyResult<Array2<u8>> {
+    let format = format.resolve()?;
+    let hints = hints.map(rxing::EncodeHints::from).unwrap_or_default();
+    let matrix = py
+        .detach(|| MultiFormatWriter.encode_with_hints(contents, &format, width, height, &hints))
+        .map_err(py_error)?;
+    Ok(pixels(&matrix))
+}
+
+fn pixels(matrix: &BitMatrix) -> Array2<u8> {
+    let (width, height) = (matrix.getWidth(), matrix.getHeight());
+    Array2::from_shape_fn((height as usize, width as usize), |(y, x)| {
+        if matrix.get(x as u32, y as u32) {
+            0
+        } else {
+            255
+        }
+    })
+}
+
+/// Reads the luminance of every pixel of a NumPy array or PIL image, row after row.
+fn luminances(image: &Bound<'_, PyAny>) -> PyResult<(Vec<u8>, u32, u32)> {
+    if image.hasattr("mode")? && image.hasattr("tobytes")? {
+        return pil_luminances(image);
+    }
+    if let Ok(array) = image.extract::<PyReadonlyArray2<u8>>() {
+        let array = array.as_array();
+        let (height, width) = array.dim();
+        return Ok((array.iter().copied().collect(), width as u32, height as u32));
+    }
+    if let Ok(array) = image.extract::<PyReadonlyArray3<u8>>() {
+        let array = array.as_array();
+        let (height, width, channels) = array.dim();
+        let luma = match channels {
+            1 => array.iter().copied().collect(),
+            3 => array
+                .rows()
+                .into_iter()
+                .map(|pixel| rgba_to_luma(pixel[0], pixel[1], pixel[2], 255))
+                .collect(),
+            4 => array
+                .rows()
+                .into_iter()
+                .map(|pixel| rgba_to_luma(pixel[0], pixel[1], pixel[2], pixel[3]))
+                .collect(),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "images need 1, 3 or 4 channels, not {channels}"
+                )));
+            }
+        };
+        return Ok((luma, width as u32, height as u32));
+    }
+    Err(PyTypeError::new_err(format!(
+        "expected a uint8 NumPy array or a PIL image, not {}",
+        image.get_type().name()?
+    )))
+}
+
+fn pil_luminances(image: &Bound<'_, PyAny>) -> PyResult<(Vec<u8>, u32, u32)> {
+    let (width, height): (u32, u32) = image.getattr("size")?.extract()?;
+    let luma = if image.getattr("mode")?.extract::<String>()? == "L" {
+        image.call_method0("tobytes")?.extract()?
+    } else {
+        // Through RGBA rather than L so transparent pixels come out white
+        let rgba: Vec<u8> = image
+            .call_method1("convert", ("RGBA",))?
+            .call_method0("tobytes")?
+            .extract()?;
+        rgba.chunks_exact(4)
+            .map(|pixel| rgba_to_luma(pixel[0], pixel[1], pixel[2], pixel[3]))
+            .collect()
+    };
+    Ok((luma, width, height))
+}
+
+/// The same green favouring average as `RGBLuminanceSource`. As red and blue weigh the same,
+/// BGR images such as OpenCV's come out the same. Transparent pixels are taken as lying on
+/// white.
+fn rgba_to_luma(r: u8, g: u8, b: u8, a: u8) -> u8 {
+    let [r, g, b, a] = [r, g, b, a].map(u32::from);
+    let luma = (r + 2 * g + b) / 4;
+    ((luma * a + 255 * (255 - a)) / 255) as u8
+}
+
+fn bitmap(
+    luma: Vec<u8>,
+    width: u32,
+    height: u32,
+) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+    BinaryBitmap::new(HybridBinarizer::new(Luma8LuminanceSource::new(
+        luma, width, height,
+    )))
+}
+
+fn py_error(error: Exceptions) -> PyErr {
+    match error {
+        Exceptions::IllegalArgumentException(message) => PyValueError::new_err(message),
+        error => RxingError::new_err(error.to_string()),
+    }
+}
+
+#[pymodule]
+fn _rxing(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<BarcodeFormat>()?;
+    m.add_class::<DecodeHints>()?;
+    m.add_class::<EncodeHints>()?;
+    m.add_class::<MsiChecksum>()?;
+    m.add_class::<SymbolShape>()?;
+    m.add_class::<ItfBearerBars>()?;
+    m.add_class::<DecodeResult>()?;
+    m.add("RxingError", m.py().get_type::<RxingError>())?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_multiple, m)?)?;
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_image, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BarcodeFormat, format_from_name, rgba_to_luma};
+
+    #[test]
+    fn transparent_pixels_are_white() {
+        assert_eq!(rgba_to_luma(0, 0, 0, 255), 0);
+        assert_eq!(rgba_to_luma(0, 0, 0, 0), 255);
+        assert_eq!(rgba_to_luma(100, 200, 0, 255), 125);
+        assert_eq!(rgba_to_luma(0, 200, 100, 255), 125);
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        for name in ["qrcode", "QR_CODE", "datamatrix", "DXFilmEdge", "ean 13"] {
+            let format = format_from_name(name).unwrap();
+            assert_eq!(
+                BarcodeFormat::from(rxing::BarcodeFormat::from(format)),
+                format
+            );
+        }
+        assert!(format_from_name("not a format").is_err());
+    }
+}
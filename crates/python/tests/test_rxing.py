@@ -0,0 +1,160 @@
+"""Tests of the Python bindings, run with `pytest` after `maturin develop --extras test`."""
+
+import threading
+
+import numpy as np
+import pytest
+from PIL import Image
+
+import rxing
+from rxing import BarcodeFormat, DecodeHints, EncodeHints
+
+
+def qr_code(text="Hello from Python"):
+    return rxing.encode(text, BarcodeFormat.QR_CODE, 150, 150)
+
+
+def test_encode_returns_grey_pixels():
+    pixels = qr_code()
+    assert pixels.dtype == np.uint8
+    assert pixels.shape == (150, 150)
+    assert set(np.unique(pixels)) == {0, 255}
+
+
+def test_decode_grey_array():
+    result = rxing.decode(qr_code())
+    assert result.text == "Hello from Python"
+    assert result.format == BarcodeFormat.QR_CODE
+    assert len(result.points) >= 3
+    assert isinstance(result.raw_bytes, bytes)
+    assert "ERROR_CORRECTION_LEVEL" in result.metadata
+
+
+@pytest.mark.parametrize("channels", [1, 3, 4])
+def test_decode_arrays_with_channels(channels):
+    pixels = np.repeat(qr_code()[:, :, np.newaxis], channels, axis=2)
+    if channels == 4:
+        pixels[:, :, 3] = 255
+    assert rxing.decode(pixels).text == "Hello from Python"
+
+
+def test_decode_strided_arrays():
+    pixels = qr_code()
+    assert rxing.decode(pixels.T.copy().T).text == "Hello from Python"
+    assert rxing.decode(np.asfortranarray(pixels)).text == "Hello from Python"
+
+    padded = np.full((150, 300), 255, dtype=np.uint8)
+    padded[:, ::2] = pixels
+    assert rxing.decode(padded[:, ::2]).text == "Hello from Python"
+
+    rgb = np.repeat(pixels[:, :, np.newaxis], 3, axis=2)
+    bgr = rgb[:, :, ::-1]
+    assert rxing.decode(bgr).text == "Hello from Python"
+
+
+def test_transparent_pixels_are_white():
+    pixels = np.zeros((150, 150, 4), dtype=np.uint8)
+    pixels[:, :, 3] = 255 - qr_code()
+    assert rxing.decode(pixels).text == "Hello from Python"
+
+
+@pytest.mark.parametrize("mode", ["L", "RGB", "RGBA", "1"])
+def test_decode_pil_images(mode):
+    image = Image.fromarray(qr_code()).convert(mode)
+    assert rxing.decode(image).text == "Hello from Python"
+
+
+def test_encode_image():
+    # Data Matrix has no quiet zone by default, which the detector needs
+    image = rxing.encode_image("pil", "datamatrix", 100, 100, EncodeHints(margin=4))
+    assert image.mode == "L"
+    assert image.size == (100, 100)
+    result = rxing.decode(image)
+    assert result.text == "pil"
+    assert result.format == BarcodeFormat.DATA_MATRIX
+
+
+def test_nothing_found():
+    blank = np.full((64, 64), 255, dtype=np.uint8)
+    assert rxing.decode(blank) is None
+    assert rxing.decode_multiple(blank) == []
+
+
+def test_decode_multiple():
+    qr = qr_code("first")
+    dm = rxing.encode("second", BarcodeFormat.DATA_MATRIX, 150, 150)
+    height = max(qr.shape[0], dm.shape[0])
+    image = np.full((height, qr.shape[1] + dm.shape[1]), 255, dtype=np.uint8)
+    image[: qr.shape[0], : qr.shape[1]] = qr
+    image[: dm.shape[0], qr.shape[1] :] = dm
+
+    found = {(r.text, r.format) for r in rxing.decode_multiple(image)}
+    assert found == {
+        ("first", BarcodeFormat.QR_CODE),
+        ("second", BarcodeFormat.DATA_MATRIX),
+    }
+
+
+def test_decode_hints():
+    pixels = qr_code()
+    only_aztec = DecodeHints(formats=[BarcodeFormat.AZTEC])
+    assert rxing.decode(pixels, only_aztec) is None
+
+    hints = DecodeHints(formats=[BarcodeFormat.QR_CODE], try_harder=True)
+    assert hints.try_harder
+    hints.pure_barcode = True
+    assert rxing.decode(pixels, hints).text == "Hello from Python"
+
+    inverted = 255 - pixels
+    assert rxing.decode(inverted, DecodeHints(also_inverted=True)).metadata["IS_INVERTED"]
+
+
+def test_encode_hints():
+    small = rxing.encode("margin", BarcodeFormat.QR_CODE, hints=EncodeHints(margin=0))
+    large = rxing.encode("margin", BarcodeFormat.QR_CODE, hints=EncodeHints(margin=10))
+    assert large.shape[0] == small.shape[0] + 20
+
+    hints = EncodeHints(error_correction="H", qr_version=5)
+    result = rxing.decode(rxing.encode("version", "qrcode", 200, 200, hints))
+    assert result.metadata["ERROR_CORRECTION_LEVEL"] == "H"
+
+    rectangle = rxing.encode(
+        "shape", "datamatrix", hints=EncodeHints(data_matrix_shape=rxing.SymbolShape.RECTANGLE)
+    )
+    assert rectangle.shape[1] > rectangle.shape[0]
+
+
+def test_formats_by_name():
+    assert BarcodeFormat.from_name("qrcode") == BarcodeFormat.QR_CODE
+    assert BarcodeFormat.from_name("QR_CODE") == BarcodeFormat.QR_CODE
+    assert str(BarcodeFormat.QR_CODE) == "qrcode"
+    with pytest.raises(ValueError):
+        BarcodeFormat.from_name("not a format")
+
+
+def test_errors():
+    with pytest.raises(ValueError):
+        rxing.encode("123", "not a format")
+    with pytest.raises(ValueError):
+        rxing.encode("not digits", BarcodeFormat.EAN_13)
+    with pytest.raises(TypeError):
+        rxing.decode("not an image")
+    with pytest.raises(TypeError):
+        rxing.decode(np.zeros((10, 10), dtype=np.float32))
+    with pytest.raises(ValueError):
+        rxing.decode(np.zeros((10, 10, 2), dtype=np.uint8))
+
+
+def test_decode_from_threads():
+    pixels = qr_code()
+    results = []
+
+    def run():
+        results.append(rxing.decode(pixels).text)
+
+    threads = [threading.Thread(target=run) for _ in range(4)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert results == ["Hello from Python"] * 4
@@ -0,0 +1,287 @@
+//! WebAssembly bindings for rxing.
+//!
+//! Images are read from an `ImageData`, such as the one of a canvas, or from the pixels of one
+//! as RGBA or luminance arrays. Hints are plain objects with the fields of [`DecodeHints`] and
+//! [`EncodeHints`], for example `{ TryHarder: true, PossibleFormats: ["QR_CODE"] }`, and
+//! results are plain objects too. Errors are thrown as `Error`s.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use rxing::{
+    BarcodeFormat, BinaryBitmap, DecodeHints, EncodeHints, Exceptions, Luma8LuminanceSource,
+    MultiFormatReader, MultiFormatWriter, MultiUseMultiFormatReader, Point, RXingResult,
+    RXingResultMetadataValue, Reader, Writer,
+    common::HybridBinarizer,
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+    pdf417::PDF417RXingResultMetadata,
+    render::{Color, RenderOptions, Renderer, SvgRenderer},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use wasm_bindgen::{Clamped, prelude::*};
+use web_sys::ImageData;
+
+/// Decodes the first barcode found in `image`, returning `null` if there is none.
+#[wasm_bindgen]
+pub fn decode(image: &ImageData, hints: JsValue) -> Result<JsValue, JsError> {
+    let luma = rgba_to_luma(&image.data());
+    decode_luma_image(luma, image.width(), image.height(), hints)
+}
+
+/// Decodes every barcode found in `image`, returning an empty array if there is none.
+#[wasm_bindgen(js_name = decodeMultiple)]
+pub fn decode_multiple(image: &ImageData, hints: JsValue) -> Result<JsValue, JsError> {
+    let mut hints: DecodeHints = from_js(hints)?;
+    hints.TryHarder = hints.TryHarder.or(Some(true));
+    let mut bitmap = bitmap(rgba_to_luma(&image.data()), image.width(), image.height())?;
+    let mut scanner = GenericMultipleBarcodeReader::new(MultiUseMultiFormatReader::default());
+    let results = match scanner.decode_multiple_with_hints(&mut bitmap, &hints) {
+        Ok(results) => results,
+        Err(Exceptions::NotFoundException(_)) => Vec::new(),
+        Err(e) => return Err(js_error(e)),
+    };
+    to_js(&results.iter().map(DecodeResult::from).collect::<Vec<_>>())
+}
+
+/// Decodes the first barcode found in the pixels of an image, four bytes per pixel in RGBA
+/// order as in `ImageData.data`, returning `null` if there is none.
+#[wasm_bindgen(js_name = decodeRgba)]
+pub fn decode_rgba(
+    data: Clamped<Vec<u8>>,
+    width: u32,
+    height: u32,
+    hints: JsValue,
+) -> Result<JsValue, JsError> {
+    decode_luma_image(rgba_to_luma(&data), width, height, hints)
+}
+
+/// Decodes the first barcode found in the pixels of a grayscale image, one byte per pixel,
+/// returning `null` if there is none.
+#[wasm_bindgen(js_name = decodeLuma)]
+pub fn decode_luma(
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    hints: JsValue,
+) -> Result<JsValue, JsError> {
+    decode_luma_image(data, width, height, hints)
+}
+
+/// Encodes `contents` as a barcode of `format`, given by name such as `"qrcode"` or
+/// `"QR_CODE"`, and renders it as an SVG document.
+///
+/// The render options are a plain object with any of `moduleSize` in millimeters,
+/// `foreground` and `background` colors as `#rrggbb`, `background: null` for a transparent
+/// background, `quietZone` in modules and `barHeight` in modules.
+#[wasm_bindgen(js_name = encodeSvg)]
+pub fn encode_svg(
+    contents: &str,
+    format: &str,
+    hints: JsValue,
+    render_options: JsValue,
+) -> Result<String, JsError> {
+    let format = BarcodeFormat::from_str(format).map_err(js_error)?;
+    let hints: EncodeHints = from_js(hints)?;
+    let options = from_js::<JsRenderOptions>(render_options)?.into_render_options()?;
+    // One pixel per module, the renderer scales the modules itself
+    let matrix = MultiFormatWriter
+        .encode_with_hints(contents, &format, 0, 0, &hints)
+        .map_err(js_error)?;
+    let svg = SvgRenderer.render_bit_matrix(&matrix, &format, &options);
+    String::from_utf8(svg).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn decode_luma_image(
+    luma: Vec<u8>,
+    width: u32,
+    height: u32,
+    hints: JsValue,
+) -> Result<JsValue, JsError> {
+    let hints: DecodeHints = from_js(hints)?;
+    let mut bitmap = bitmap(luma, width, height)?;
+    match MultiFormatReader::default().decode_with_hints(&mut bitmap, &hints) {
+        Ok(result) => to_js(&DecodeResult::from(&result)),
+        Err(Exceptions::NotFoundException(_)) => Ok(JsValue::NULL),
+        Err(e) => Err(js_error(e)),
+    }
+}
+
+fn bitmap(
+    luma: Vec<u8>,
+    width: u32,
+    height: u32,
+) -> Result<BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>>, JsError> {
+    if width == 0 || height == 0 || luma.len() != width as usize * height as usize {
+        return Err(JsError::new(&format!(
+            "{} pixels do not make an image of {width}x{height}",
+            luma.len()
+        )));
+    }
+    Ok(BinaryBitmap::new(HybridBinarizer::new(
+        Luma8LuminanceSource::new(luma, width, height),
+    )))
+}
+
+/// Converts RGBA pixels to luminance, the same green favouring average as
+/// `RGBLuminanceSource`. Transparent pixels are taken as lying on white, as a canvas shows
+/// them on a white page.
+fn rgba_to_luma(rgba: &[u8]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .map(|pixel| {
+            let [r, g, b, a] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(u32::from);
+            let luma = (r + 2 * g + b) / 4;
+            ((luma * a + 255 * (255 - a)) / 255) as u8
+        })
+        .collect()
+}
+
+fn from_js<T: DeserializeOwned + Default>(value: JsValue) -> Result<T, JsError> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(T::default());
+    }
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn js_error(error: Exceptions) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+/// A decoded barcode as handed to JavaScript
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DecodeResult<'a> {
+    text: &'a str,
+    raw_bytes: &'a [u8],
+    num_bits: usize,
+    format: BarcodeFormat,
+    points: &'a [Point],
+    metadata: BTreeMap<String, MetadataValue<'a>>,
+}
+
+impl<'a> From<&'a RXingResult> for DecodeResult<'a> {
+    fn from(result: &'a RXingResult) -> Self {
+        Self {
+            text: result.getText(),
+            raw_bytes: result.getRawBytes(),
+            num_bits: result.getNumBits(),
+            format: *result.getBarcodeFormat(),
+            points: result.getPoints(),
+            metadata: result
+                .getRXingResultMetadata()
+                .iter()
+                .map(|(key, value)| (format!("{key:?}"), MetadataValue::from(value)))
+                .collect(),
+        }
+    }
+}
+
+/// A metadata value as a plain JavaScript value, rather than tagged with its type
+#[derive(Serialize)]
+#[serde(untagged)]
+enum MetadataValue<'a> {
+    Text(&'a str),
+    Number(i32),
+    Flag(bool),
+    ByteSegments(&'a [Vec<u8>]),
+    Resolution { width: usize, height: usize },
+    Pdf417(&'a PDF417RXingResultMetadata),
+}
+
+impl<'a> From<&'a RXingResultMetadataValue> for MetadataValue<'a> {
+    fn from(value: &'a RXingResultMetadataValue) -> Self {
+        use RXingResultMetadataValue::*;
+
+        match value {
+            OTHER(text)
+            | ErrorCorrectionLevel(text)
+            | SuggestedPrice(text)
+            | PossibleCountry(text)
+            | UpcEanExtension(text)
+            | Gs1Composite(text)
+            | Gtin(text)
+            | SymbologyIdentifier(text)
+            | ContentType(text) => Self::Text(text),
+            Orientation(number)
+            | IssueNumber(number)
+            | StructuredAppendSequence(number)
+            | StructuredAppendParity(number) => Self::Number(*number),
+            IsMirrored(flag) | IsInverted(flag) | FilteredClosed(flag) => Self::Flag(*flag),
+            ByteSegments(segments) => Self::ByteSegments(segments),
+            FilteredResolution((width, height)) => Self::Resolution {
+                width: *width,
+                height: *height,
+            },
+            Pdf417ExtraMetadata(extra) => Self::Pdf417(extra),
+        }
+    }
+}
+
+/// [`RenderOptions`] as a plain JavaScript object, with colors as `#rrggbb`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JsRenderOptions {
+    module_size: f32,
+    foreground: String,
+    background: Option<String>,
+    quiet_zone: Option<u32>,
+    bar_height: u32,
+}
+
+impl Default for JsRenderOptions {
+    fn default() -> Self {
+        let options = RenderOptions::default();
+        Self {
+            module_size: options.module_size,
+            foreground: options.foreground.to_hex(),
+            background: options.background.map(|color| color.to_hex()),
+            quiet_zone: options.quiet_zone,
+            bar_height: options.bar_height,
+        }
+    }
+}
+
+impl JsRenderOptions {
+    fn into_render_options(self) -> Result<RenderOptions, JsError> {
+        Ok(RenderOptions {
+            module_size: self.module_size,
+            foreground: parse_color(&self.foreground)?,
+            background: self.background.as_deref().map(parse_color).transpose()?,
+            quiet_zone: self.quiet_zone,
+            bar_height: self.bar_height,
+            ..RenderOptions::default()
+        })
+    }
+}
+
+fn parse_color(hex: &str) -> Result<Color, JsError> {
+    let invalid = || JsError::new(&format!("invalid color '{hex}', expected #rrggbb"));
+    let digits = hex.strip_prefix('#').ok_or_else(invalid)?;
+    if digits.len() != 6 || !digits.is_ascii() {
+        return Err(invalid());
+    }
+    let component = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| invalid());
+    Ok(Color::new(component(0)?, component(2)?, component(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use rxing::render::Color;
+
+    use super::{parse_color, rgba_to_luma};
+
+    #[test]
+    fn transparent_pixels_are_white() {
+        let rgba = [0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 0, 100, 200, 0, 255];
+        assert_eq!(rgba_to_luma(&rgba), [0, 255, 255, 125]);
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_color("#ff8000").ok(), Some(Color::new(255, 128, 0)));
+    }
+}
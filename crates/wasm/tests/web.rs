@@ -0,0 +1,165 @@
+//! Run in a headless browser with `wasm-pack test --headless --firefox crates/wasm`, or
+//! `--chrome`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, JSON, Reflect};
+use rxing::{BarcodeFormat, MultiFormatWriter, Writer};
+use rxing_wasm::{decode, decode_luma, decode_multiple, decode_rgba, encode_svg};
+use wasm_bindgen::{Clamped, JsValue};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::ImageData;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Pixels of a barcode, one byte per pixel, with its width and height
+fn encode_luma(contents: &str, format: BarcodeFormat) -> (Vec<u8>, u32, u32) {
+    let matrix = MultiFormatWriter
+        .encode(contents, &format, 150, 150)
+        .unwrap();
+    let pixels = (0..matrix.getHeight())
+        .flat_map(|y| (0..matrix.getWidth()).map(move |x| (x, y)))
+        .map(|(x, y)| if matrix.get(x, y) { 0 } else { 255 })
+        .collect();
+    (pixels, matrix.getWidth(), matrix.getHeight())
+}
+
+fn to_rgba(luma: &[u8]) -> Vec<u8> {
+    luma.iter().flat_map(|&l| [l, l, l, 255]).collect()
+}
+
+fn hints(json: &str) -> JsValue {
+    JSON::parse(json).unwrap()
+}
+
+fn get(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &key.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn decodes_image_data() {
+    let (luma, width, height) = encode_luma("from a canvas", BarcodeFormat::QR_CODE);
+    let image =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(&to_rgba(&luma)), width, height)
+            .unwrap();
+
+    let result = decode(&image, hints(r#"{"PossibleFormats": ["QR_CODE"]}"#)).unwrap();
+
+    assert_eq!(get(&result, "text"), "from a canvas");
+    assert_eq!(get(&result, "format"), "QR_CODE");
+    let points = Array::from(&get(&result, "points"));
+    assert!(points.length() >= 3);
+    assert!(get(&points.get(0), "x").as_f64().is_some());
+    let metadata = get(&result, "metadata");
+    assert!(get(&metadata, "ERROR_CORRECTION_LEVEL").is_string());
+    assert!(Array::from(&get(&result, "rawBytes")).length() > 0);
+}
+
+#[wasm_bindgen_test]
+fn decodes_rgba_and_luma_arrays() {
+    let (luma, width, height) = encode_luma("12345670", BarcodeFormat::EAN_8);
+
+    let result = decode_rgba(Clamped(to_rgba(&luma)), width, height, JsValue::UNDEFINED).unwrap();
+    assert_eq!(get(&result, "text"), "12345670");
+    assert_eq!(get(&result, "format"), "EAN_8");
+
+    let result = decode_luma(luma, width, height, hints(r#"{"TryHarder": true}"#)).unwrap();
+    assert_eq!(get(&result, "text"), "12345670");
+}
+
+#[wasm_bindgen_test]
+fn transparent_pixels_are_light() {
+    let (luma, width, height) = encode_luma("on a transparent canvas", BarcodeFormat::QR_CODE);
+    // Dark modules drawn on a cleared canvas, which is transparent black
+    let rgba: Vec<u8> = luma
+        .iter()
+        .flat_map(|&l| if l == 0 { [0, 0, 0, 255] } else { [0, 0, 0, 0] })
+        .collect();
+
+    let result = decode_rgba(Clamped(rgba), width, height, JsValue::NULL).unwrap();
+    assert_eq!(get(&result, "text"), "on a transparent canvas");
+}
+
+#[wasm_bindgen_test]
+fn decodes_multiple_barcodes() {
+    let (first, first_width, height) = encode_luma("first", BarcodeFormat::QR_CODE);
+    let (second, second_width, _) = encode_luma("second", BarcodeFormat::QR_CODE);
+    let width = first_width + second_width;
+    let luma: Vec<u8> = first
+        .chunks(first_width as usize)
+        .zip(second.chunks(second_width as usize))
+        .flat_map(|(a, b)| a.iter().chain(b).copied())
+        .collect();
+    let image =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(&to_rgba(&luma)), width, height)
+            .unwrap();
+
+    let results = Array::from(&decode_multiple(&image, JsValue::UNDEFINED).unwrap());
+    let mut texts: Vec<String> = results
+        .iter()
+        .map(|result| get(&result, "text").as_string().unwrap())
+        .collect();
+    texts.sort();
+    assert_eq!(texts, ["first", "second"]);
+}
+
+#[wasm_bindgen_test]
+fn nothing_found_is_null() {
+    let blank = vec![255; 64 * 64];
+    assert!(
+        decode_luma(blank.clone(), 64, 64, JsValue::UNDEFINED)
+            .unwrap()
+            .is_null()
+    );
+
+    let image =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(&to_rgba(&blank)), 64, 64).unwrap();
+    assert_eq!(
+        Array::from(&decode_multiple(&image, JsValue::UNDEFINED).unwrap()).length(),
+        0
+    );
+}
+
+#[wasm_bindgen_test]
+fn invalid_input_throws() {
+    assert!(decode_luma(vec![0; 10], 4, 4, JsValue::UNDEFINED).is_err());
+    assert!(decode_luma(vec![0; 16], 4, 4, hints(r#"{"TryHarder": "yes"}"#)).is_err());
+    assert!(
+        encode_svg(
+            "text",
+            "no such format",
+            JsValue::UNDEFINED,
+            JsValue::UNDEFINED
+        )
+        .is_err()
+    );
+    assert!(
+        encode_svg(
+            "text",
+            "qrcode",
+            JsValue::UNDEFINED,
+            hints(r#"{"foreground": "red"}"#)
+        )
+        .is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn renders_svg() {
+    let svg = encode_svg(
+        "svg",
+        "QR_CODE",
+        hints(r#"{"ErrorCorrection": "H"}"#),
+        hints(
+            r##"{"moduleSize": 0.5, "foreground": "#000080", "background": null, "quietZone": 2}"##,
+        ),
+    )
+    .unwrap();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(r##"<g fill="#000080">"##));
+    assert!(!svg.contains("<rect"));
+    // Version 1 at level H is 21 modules, and 2 more on each side
+    assert!(svg.contains(r#"viewBox="0 0 25 25""#));
+    assert!(svg.contains(r#"width="12.5mm""#));
+}
@@ -0,0 +1,281 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    Binarizer, BinaryBitmap, DecodeHints, Exceptions, ImmutableReader, RXingResult, common::Result,
+};
+
+/// Decodes images on a pool of blocking threads, so that async code, such as the handlers of
+/// a web service, does not stall its executor while a barcode is decoded.
+///
+/// The reader and its hints are set up once and shared by every decode, and the reader can be
+/// cloned cheaply to hand it to each task. Decodes run on the global rayon thread pool and
+/// return a [`DecodeFuture`], which does not depend on any particular async runtime.
+pub struct AsyncReader<R> {
+    reader: Arc<R>,
+    hints: Arc<DecodeHints>,
+}
+
+impl<R> Clone for AsyncReader<R> {
+    fn clone(&self) -> Self {
+        Self {
+            reader: Arc::clone(&self.reader),
+            hints: Arc::clone(&self.hints),
+        }
+    }
+}
+
+impl<R: ImmutableReader + Send + Sync + 'static> AsyncReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_hints(reader, DecodeHints::default())
+    }
+
+    /// Shares the reader, decoding with these hints unless others are given for a decode.
+    pub fn with_hints(reader: R, hints: DecodeHints) -> Self {
+        Self {
+            reader: Arc::new(reader),
+            hints: Arc::new(hints),
+        }
+    }
+
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    pub fn hints(&self) -> &DecodeHints {
+        &self.hints
+    }
+
+    /// Starts decoding the image with the hints of this reader.
+    pub fn decode<B: Binarizer + Send + 'static>(&self, image: BinaryBitmap<B>) -> DecodeFuture {
+        self.spawn(image, Arc::clone(&self.hints))
+    }
+
+    /// Starts decoding the image with other hints than those of this reader.
+    pub fn decode_with_hints<B: Binarizer + Send + 'static>(
+        &self,
+        image: BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> DecodeFuture {
+        self.spawn(image, Arc::new(hints.clone()))
+    }
+
+    fn spawn<B: Binarizer + Send + 'static>(
+        &self,
+        mut image: BinaryBitmap<B>,
+        hints: Arc<DecodeHints>,
+    ) -> DecodeFuture {
+        let task = Arc::new(DecodeTask::default());
+        let reader = Arc::clone(&self.reader);
+        let worker = Arc::clone(&task);
+
+        rayon::spawn(move || {
+            // Nobody is waiting for a decode cancelled before it got a thread
+            if worker.is_cancelled() {
+                return;
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                reader.immutable_decode_with_hints(&mut image, &hints)
+            }))
+            .unwrap_or_else(|_| Err(Exceptions::runtime_with("decoder panicked")));
+            worker.finish(result);
+        });
+
+        DecodeFuture { task }
+    }
+}
+
+/// A decode started by an [`AsyncReader`], resolving to its result.
+///
+/// Dropping the future, or cancelling it through [`DecodeFuture::cancel_handle`], cancels the
+/// decode. A decode still waiting for a thread is then skipped. One already running cannot be
+/// interrupted, it finishes on its thread but its result is thrown away, and the future resolves
+/// right away.
+#[must_use = "futures do nothing unless polled, and dropping one cancels its decode"]
+pub struct DecodeFuture {
+    task: Arc<DecodeTask>,
+}
+
+impl DecodeFuture {
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle(Arc::clone(&self.task))
+    }
+}
+
+impl Future for DecodeFuture {
+    type Output = Result<RXingResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.task.is_cancelled() {
+            return Poll::Ready(Err(Exceptions::illegal_state_with("decode cancelled")));
+        }
+        let mut state = self.task.lock();
+        if let Some(result) = state.result.take() {
+            return Poll::Ready(result);
+        }
+        match &mut state.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            waker => *waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for DecodeFuture {
+    fn drop(&mut self) {
+        self.task.cancelled.store(true, Ordering::Release);
+    }
+}
+
+/// Cancels a [`DecodeFuture`] from elsewhere than the task awaiting it, for example on a
+/// timeout or when the client of a request goes away.
+#[derive(Clone)]
+pub struct CancelHandle(Arc<DecodeTask>);
+
+impl CancelHandle {
+    pub fn cancel(&self) {
+        self.0.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+/// What the thread decoding and the future awaiting the decode share
+#[derive(Default)]
+struct DecodeTask {
+    cancelled: AtomicBool,
+    state: Mutex<TaskState>,
+}
+
+#[derive(Default)]
+struct TaskState {
+    result: Option<Result<RXingResult>>,
+    waker: Option<Waker>,
+}
+
+impl DecodeTask {
+    fn lock(&self) -> std::sync::MutexGuard<'_, TaskState> {
+        // A panic never happens with the lock held, but do not give up on the result if it did
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        let waker = self.lock().waker.take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn finish(&self, result: Result<RXingResult>) {
+        let waker = {
+            let mut state = self.lock();
+            if !self.is_cancelled() {
+                state.result = Some(result);
+            }
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "encoders", feature = "qrcode"))]
+mod tests {
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::Arc,
+        task::{Context, Poll, Wake},
+        thread::{self, Thread},
+    };
+
+    use crate::{
+        BarcodeFormat, BinaryBitmap, DecodeHints, Luma8LuminanceSource, MultiFormatReader, Writer,
+        common::HybridBinarizer, qrcode::QRCodeWriter,
+    };
+
+    use super::AsyncReader;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    fn qr_image(text: &str) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+        let matrix = QRCodeWriter
+            .encode(text, &BarcodeFormat::QR_CODE, 200, 200)
+            .unwrap();
+        let pixels = (0..matrix.getHeight())
+            .flat_map(|y| (0..matrix.getWidth()).map(move |x| (x, y)))
+            .map(|(x, y)| if matrix.get(x, y) { 0 } else { 255 })
+            .collect();
+        BinaryBitmap::new(HybridBinarizer::new(Luma8LuminanceSource::new(
+            pixels,
+            matrix.getWidth(),
+            matrix.getHeight(),
+        )))
+    }
+
+    #[test]
+    fn decodes_concurrently_with_a_shared_reader() {
+        let reader = AsyncReader::with_hints(
+            MultiFormatReader::default(),
+            DecodeHints {
+                PossibleFormats: Some([BarcodeFormat::QR_CODE].into()),
+                ..Default::default()
+            },
+        );
+        let texts: Vec<String> = (0..8).map(|i| format!("request {i}")).collect();
+        let futures: Vec<_> = texts
+            .iter()
+            .map(|text| reader.clone().decode(qr_image(text)))
+            .collect();
+        for (future, text) in futures.into_iter().zip(&texts) {
+            let result = block_on(future).unwrap();
+            assert_eq!(result.getText(), text);
+            assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::QR_CODE);
+        }
+    }
+
+    #[test]
+    fn cancelled_decode_resolves_with_error() {
+        let reader = AsyncReader::new(MultiFormatReader::default());
+        let future = reader.decode(qr_image("never read"));
+        let handle = future.cancel_handle();
+        handle.cancel();
+        assert!(handle.is_cancelled());
+        assert!(block_on(future).is_err());
+    }
+}
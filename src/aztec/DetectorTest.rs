@@ -36,7 +36,12 @@
 
 use rand::RngExt;
 
-use crate::{Point, aztec::decoder, common::BitMatrix, exceptions::Exceptions};
+use crate::{
+    Point,
+    aztec::decoder,
+    common::{BitMatrix, DetectorRXingResult},
+    exceptions::Exceptions,
+};
 
 use super::{
     detector::{self, Detector},
@@ -77,6 +82,45 @@ fn test_aztec_rxing_result_sample() {
     assert_eq!("Histórico", res.getText());
 }
 
+#[test]
+fn test_smudged_modules_as_erasures() {
+    const TEXT: &str = "Smudged codewords are erasures, at half the cost of errors";
+    const SCALE: u32 = 4;
+    let aztec =
+        encoder::aztec_encoder::encode(TEXT, 25, encoder::aztec_encoder::DEFAULT_AZTEC_LAYERS)
+            .expect("encode should create");
+    let matrix = aztec.getMatrix();
+
+    // A 2 by 2 pixel checkerboard over some of the data modules: their centers read as one
+    // color whatever the module was, but not the rest of them
+    let quiet = 2 * SCALE;
+    let size = matrix.getWidth() * SCALE + 2 * quiet;
+    let smudge = 0..8;
+    let mut image = BitMatrix::with_single_dimension(size).expect("image should create");
+    for y in quiet..size - quiet {
+        for x in quiet..size - quiet {
+            let (mx, my) = ((x - quiet) / SCALE, (y - quiet) / SCALE);
+            let dark = if smudge.contains(&mx) && smudge.contains(&my) {
+                (x / 2 + y / 2) % 2 == 0
+            } else {
+                matrix.get(mx, my)
+            };
+            if dark {
+                image.set(x, y);
+            }
+        }
+    }
+
+    let r = Detector::new(&image).detect(false).expect("detect success");
+    assert!(decoder::decode(&r).is_err());
+    let res = decoder::decode_with_uncertain_modules(&r, || {
+        r.getSampledGrid()?.sample_uncertain_modules(&image).ok()
+    })
+    .expect("decode success");
+    assert_eq!(TEXT, res.getText());
+    assert!(res.getErasures() > 0);
+}
+
 // Test that we can tolerate errors in the parameter locator bits
 fn test_error_in_parameter_locator(data: &str) {
     let aztec =
@@ -22,7 +22,7 @@
 
 use crate::{
     Point,
-    common::{BitMatrix, DetectorRXingResult},
+    common::{BitMatrix, DetectorRXingResult, SampledGrid},
 };
 
 /**
@@ -37,6 +37,7 @@ pub struct AztecDetectorRXingResult {
     compact: bool,
     nbDatablocks: u32,
     nbLayers: u32,
+    sampled_grid: Option<SampledGrid>,
 }
 
 impl DetectorRXingResult for AztecDetectorRXingResult {
@@ -47,6 +48,10 @@ impl DetectorRXingResult for AztecDetectorRXingResult {
     fn getPoints(&self) -> &[Point] {
         &self.points
     }
+
+    fn getSampledGrid(&self) -> Option<&SampledGrid> {
+        self.sampled_grid.as_ref()
+    }
 }
 
 impl AztecDetectorRXingResult {
@@ -63,6 +68,14 @@ impl AztecDetectorRXingResult {
             compact,
             nbDatablocks,
             nbLayers,
+            sampled_grid: None,
+        }
+    }
+
+    pub fn with_sampled_grid(self, sampled_grid: SampledGrid) -> Self {
+        Self {
+            sampled_grid: Some(sampled_grid),
+            ..self
         }
     }
 
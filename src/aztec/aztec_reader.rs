@@ -28,7 +28,7 @@ use super::{decoder, detector::Detector};
  *
  * @author David Olivier
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct AztecReader;
 
 impl Reader for AztecReader {
@@ -70,7 +70,8 @@ impl AztecReader {
     ) -> Result<RXingResult> {
         // let notFoundException = None;
         // let formatException = None;
-        let mut detector = Detector::new(image.get_black_matrix());
+        let black = image.get_black_matrix();
+        let mut detector = Detector::new(black);
 
         //  try {
 
@@ -83,7 +84,13 @@ impl AztecReader {
         };
 
         let points = detectorRXingResult.getPoints();
-        let decoderRXingResult: DecoderRXingResult = decoder::decode(&detectorRXingResult)?;
+        let decoderRXingResult: DecoderRXingResult =
+            decoder::decode_with_uncertain_modules(&detectorRXingResult, || {
+                detectorRXingResult
+                    .getSampledGrid()?
+                    .sample_uncertain_modules(black)
+                    .ok()
+            })?;
         // } catch (NotFoundException e) {
         //   notFoundException = e;
         // } catch (FormatException e) {
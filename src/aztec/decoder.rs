@@ -74,10 +74,31 @@ const DIGIT_TABLE: [&str; 16] = [
 //   private AztecDetectorRXingResult ddata;
 
 pub fn decode(detectorRXingResult: &AztecDetectorRXingResult) -> Result<DecoderRXingResult> {
+    decode_with_uncertain_modules(detectorRXingResult, || None)
+}
+
+/**
+ * Decodes like {@link #decode}. Only if error correction fails are the uncertain modules asked
+ * for, and the codewords that hold one of them treated as erasures.
+ *
+ * @param uncertainModules gives the modules of the detected bits whose color was in doubt
+ */
+pub fn decode_with_uncertain_modules(
+    detectorRXingResult: &AztecDetectorRXingResult,
+    uncertainModules: impl FnOnce() -> Option<BitMatrix>,
+) -> Result<DecoderRXingResult> {
     //let mut detectorRXingResult = detectorRXingResult.clone();
     let matrix = detectorRXingResult.getBits();
     let rawbits = extract_bits(detectorRXingResult, matrix);
-    let corrected_bits = correct_bits(detectorRXingResult, &rawbits)?;
+    let uncertain_bits = || {
+        uncertainModules()
+            .filter(|uncertain| {
+                uncertain.getWidth() == matrix.getWidth()
+                    && uncertain.getHeight() == matrix.getHeight()
+            })
+            .map(|uncertain| extract_bits(detectorRXingResult, &uncertain))
+    };
+    let corrected_bits = correct_bits(detectorRXingResult, &rawbits, uncertain_bits)?;
     let raw_bytes = convertBoolArrayToByteArray(&corrected_bits.correct_bits);
     let result = get_encoded_data(&corrected_bits.correct_bits);
     let mut decoder_rxing_result = DecoderRXingResult::new(
@@ -87,6 +108,7 @@ pub fn decode(detectorRXingResult: &AztecDetectorRXingResult) -> Result<DecoderR
         format!("{}%", corrected_bits.ec_level),
     );
     decoder_rxing_result.setNumBits(corrected_bits.correct_bits.len());
+    decoder_rxing_result.setErasures(corrected_bits.erasures);
 
     Ok(decoder_rxing_result)
 }
@@ -301,12 +323,14 @@ fn get_character(table: Table, code: u32) -> Result<&'static str> {
 struct CorrectedBitsRXingResult {
     correct_bits: Vec<bool>,
     ec_level: u32,
+    erasures: usize,
 }
 impl CorrectedBitsRXingResult {
-    pub const fn new(correct_bits: Vec<bool>, ec_level: u32) -> Self {
+    pub const fn new(correct_bits: Vec<bool>, ec_level: u32, erasures: usize) -> Self {
         Self {
             correct_bits,
             ec_level,
+            erasures,
         }
     }
 }
@@ -314,12 +338,15 @@ impl CorrectedBitsRXingResult {
 /**
  * <p>Performs RS error correction on an array of bits.</p>
  *
+ * @param uncertain_bits gives the bits which were sampled from uncertain modules, the codewords
+ *   holding any of them are tried as erasures if correcting them as errors fails
  * @return the corrected array
  * @throws FormatException if the input contains too many errors
  */
 fn correct_bits(
     ddata: &AztecDetectorRXingResult,
     rawbits: &[bool],
+    uncertain_bits: impl FnOnce() -> Option<Vec<bool>>,
 ) -> Result<CorrectedBitsRXingResult> {
     let gf: GenericGFRef;
     let codeword_size;
@@ -345,7 +372,8 @@ fn correct_bits(
             "numCodewords {num_codewords}< numDataCodewords{num_data_codewords}"
         )));
     }
-    let mut offset = rawbits.len() % codeword_size;
+    let start = rawbits.len() % codeword_size;
+    let mut offset = start;
 
     let mut data_words = vec![0; num_codewords];
     for word in data_words.iter_mut().take(num_codewords) {
@@ -357,10 +385,29 @@ fn correct_bits(
 
     //try {
     let rs_decoder = ReedSolomonDecoder::new(gf);
-    rs_decoder.decode(
-        &mut data_words,
-        (num_codewords - num_data_codewords as usize) as i32,
-    )?;
+    let two_s = (num_codewords - num_data_codewords as usize) as i32;
+    let received_words = data_words.clone();
+    let mut erasures_used = 0;
+    if let Err(error) = rs_decoder.decode(&mut data_words, two_s) {
+        let erasures: Vec<usize> = uncertain_bits()
+            .map(|uncertain_bits| {
+                (0..num_codewords)
+                    .filter(|i| {
+                        read_code(&uncertain_bits, start + i * codeword_size, codeword_size) != 0
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        data_words = received_words;
+        if erasures.is_empty()
+            || rs_decoder
+                .decode_with_erasures(&mut data_words, two_s, &erasures)
+                .is_err()
+        {
+            return Err(error);
+        }
+        erasures_used = erasures.len();
+    }
     //} catch (ReedSolomonException ex) {
     //throw FormatException.getFormatInstance(ex);
     //}
@@ -408,6 +455,7 @@ fn correct_bits(
     Ok(CorrectedBitsRXingResult::new(
         corrected_bits,
         (100 * (num_codewords - num_data_codewords as usize) / num_codewords) as u32,
+        erasures_used,
     ))
 }
 
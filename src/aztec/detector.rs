@@ -17,7 +17,7 @@
 use crate::{
     Point,
     common::{
-        BitMatrix, DefaultGridSampler, GridSampler, Quadrilateral, Result,
+        BitMatrix, DefaultGridSampler, GridSampler, Quadrilateral, Result, SampledGrid,
         detector::WhiteRectangleDetector,
         reedsolomon::{self, ReedSolomonDecoder},
     },
@@ -98,7 +98,7 @@ impl<'a> Detector<'_> {
         );
 
         // 4. Sample the grid
-        let bits = self.sample_grid(self.image, src_quad)?;
+        let (bits, sampled_grid) = self.sample_grid(self.image, src_quad)?;
 
         // 5. Get the corners of the matrix.
         let corners = self.get_matrix_corner_points(&bulls_eye_corners);
@@ -109,7 +109,8 @@ impl<'a> Detector<'_> {
             self.compact,
             self.nb_data_blocks,
             self.nb_layers,
-        ))
+        )
+        .with_sampled_grid(sampled_grid))
     }
 
     /**
@@ -455,8 +456,13 @@ impl<'a> Detector<'_> {
      * Creates a BitMatrix by sampling the provided image.
      * topLeft, topRight, bottomRight, and bottomLeft are the centers of the squares on the
      * diagonal just outside the bull's eye.
+     * Also returns where the grid was sampled from.
      */
-    fn sample_grid(&self, image: &BitMatrix, quad: Quadrilateral) -> Result<BitMatrix> {
+    fn sample_grid(
+        &self,
+        image: &BitMatrix,
+        quad: Quadrilateral,
+    ) -> Result<(BitMatrix, SampledGrid)> {
         let sampler = DefaultGridSampler;
         let dimension = self.get_dimension();
 
@@ -471,8 +477,9 @@ impl<'a> Detector<'_> {
         );
 
         let (res, _) = sampler.sample_grid_detailed(image, dimension, dimension, dst, quad)?;
+        let sampled_grid = SampledGrid::detailed(dimension, dimension, dst, quad)?;
 
-        Ok(res)
+        Ok((res, sampled_grid))
     }
 
     /**
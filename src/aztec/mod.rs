@@ -11,6 +11,8 @@ pub mod detector;
 #[cfg(feature = "encoders")]
 pub mod encoder;
 
+#[cfg(feature = "decoders")]
+pub use aztec_detector_result::*;
 #[cfg(feature = "decoders")]
 pub use aztec_reader::*;
 #[cfg(feature = "encoders")]
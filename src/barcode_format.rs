@@ -29,15 +29,33 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum BarcodeFormat {
+    /** Australia Post 4-state customer barcode format. */
+    AUSTRALIA_POST,
+
     /** Aztec 2D barcode format. */
     AZTEC,
 
+    /** Codablock F stacked 1D format. */
+    CODABLOCK_F,
+
     /** CODABAR 1D format. */
     CODABAR,
 
+    /** Code 11 1D format. */
+    CODE_11,
+
+    /** Code 16K stacked 1D format. */
+    CODE_16K,
+
+    /** Code 32 (Italian Pharmacode) 1D format, a base 32 form of Code 39. */
+    CODE_32,
+
     /** Code 39 1D format. */
     CODE_39,
 
+    /** Code 49 stacked 1D format. */
+    CODE_49,
+
     /** Code 93 1D format. */
     CODE_93,
 
@@ -47,21 +65,60 @@ pub enum BarcodeFormat {
     /** Data Matrix 2D barcode format. */
     DATA_MATRIX,
 
+    /** DotCode 2D barcode format. */
+    DOTCODE,
+
     /** EAN-8 1D format. */
     EAN_8,
 
     /** EAN-13 1D format. */
     EAN_13,
 
+    /** Han Xin Code 2D barcode format. */
+    HAN_XIN,
+
+    /** IATA 2 of 5 1D format. */
+    IATA_2_OF_5,
+
+    /** Industrial (Standard) 2 of 5 1D format. */
+    INDUSTRIAL_2_OF_5,
+
+    /** USPS Intelligent Mail 4-state barcode format. */
+    INTELLIGENT_MAIL,
+
     /** ITF (Interleaved Two of Five) 1D format. */
     ITF,
 
+    /** KIX (Dutch Klant index) 4-state barcode format. */
+    KIX,
+
+    /** Matrix 2 of 5 1D format. */
+    MATRIX_2_OF_5,
+
     /** MaxiCode 2D barcode format. */
     MAXICODE,
 
+    /** MSI Plessey 1D format. */
+    MSI,
+
     /** PDF417 format. */
     PDF_417,
 
+    /** Pharmacode (Laetus one-track pharmaceutical binary code) 1D format. */
+    PHARMACODE,
+
+    /** Two-track Pharmacode 1D format. */
+    PHARMACODE_TWO_TRACK,
+
+    /** USPS PLANET 1D format. */
+    PLANET,
+
+    /** USPS POSTNET 1D format. */
+    POSTNET,
+
+    /** PZN (Pharmazentralnummer) 1D format, a Code 39 form. */
+    PZN,
+
     /** QR Code 2D barcode format. */
     QR_CODE,
 
@@ -69,6 +126,9 @@ pub enum BarcodeFormat {
 
     RECTANGULAR_MICRO_QR_CODE,
 
+    /** RM4SCC (Royal Mail 4-State Customer Code) barcode format. */
+    RM4SCC,
+
     /** RSS 14 */
     RSS_14,
 
@@ -99,20 +159,40 @@ impl Display for BarcodeFormat {
             f,
             "{}",
             match self {
+                BarcodeFormat::AUSTRALIA_POST => "australia post",
                 BarcodeFormat::AZTEC => "aztec",
+                BarcodeFormat::CODABLOCK_F => "codablock f",
                 BarcodeFormat::CODABAR => "codabar",
+                BarcodeFormat::CODE_11 => "code 11",
+                BarcodeFormat::CODE_16K => "code 16k",
+                BarcodeFormat::CODE_32 => "code 32",
                 BarcodeFormat::CODE_39 => "code 39",
+                BarcodeFormat::CODE_49 => "code 49",
                 BarcodeFormat::CODE_93 => "code 93",
                 BarcodeFormat::CODE_128 => "code 128",
                 BarcodeFormat::DATA_MATRIX => "datamatrix",
+                BarcodeFormat::DOTCODE => "dotcode",
                 BarcodeFormat::EAN_8 => "ean 8",
                 BarcodeFormat::EAN_13 => "ean 13",
+                BarcodeFormat::HAN_XIN => "han xin",
+                BarcodeFormat::IATA_2_OF_5 => "iata 2 of 5",
+                BarcodeFormat::INDUSTRIAL_2_OF_5 => "industrial 2 of 5",
+                BarcodeFormat::INTELLIGENT_MAIL => "intelligent mail",
                 BarcodeFormat::ITF => "itf",
+                BarcodeFormat::KIX => "kix",
+                BarcodeFormat::MATRIX_2_OF_5 => "matrix 2 of 5",
                 BarcodeFormat::MAXICODE => "maxicode",
+                BarcodeFormat::MSI => "msi",
                 BarcodeFormat::PDF_417 => "pdf 417",
+                BarcodeFormat::PHARMACODE => "pharmacode",
+                BarcodeFormat::PHARMACODE_TWO_TRACK => "pharmacode two track",
+                BarcodeFormat::PLANET => "planet",
+                BarcodeFormat::POSTNET => "postnet",
+                BarcodeFormat::PZN => "pzn",
                 BarcodeFormat::QR_CODE => "qrcode",
                 BarcodeFormat::MICRO_QR_CODE => "mqr",
                 BarcodeFormat::RECTANGULAR_MICRO_QR_CODE => "rmqr",
+                BarcodeFormat::RM4SCC => "rm4scc",
                 BarcodeFormat::RSS_14 => "rss 14",
                 BarcodeFormat::RSS_EXPANDED => "rss expanded",
                 BarcodeFormat::TELEPEN => "telepen",
@@ -135,23 +215,55 @@ impl From<String> for BarcodeFormat {
 impl From<&str> for BarcodeFormat {
     fn from(value: &str) -> Self {
         match value.to_lowercase().as_str() {
+            "australia post" | "australia_post" | "auspost" | "australia post 4-state" => {
+                BarcodeFormat::AUSTRALIA_POST
+            }
             "aztec" | "aztec code" | "aztec_code" => BarcodeFormat::AZTEC,
+            "codablock f" | "codablock_f" | "codablockf" | "codablock-f" => {
+                BarcodeFormat::CODABLOCK_F
+            }
             "codabar" | "coda" | "coda_bar" | "cod_a_bar" | "cod_a" => BarcodeFormat::CODABAR,
+            "code 11" | "code_11" | "code11" | "usd-8" => BarcodeFormat::CODE_11,
+            "code 16k" | "code_16k" | "code16k" | "en 12323" => BarcodeFormat::CODE_16K,
             "code 39" | "code_39" | "code39" | "alpha39" | "code_3_of_9" | "uss_39" | "usd-3" => {
                 BarcodeFormat::CODE_39
             }
+            "code 49" | "code_49" | "code49" => BarcodeFormat::CODE_49,
+            "code 32" | "code_32" | "code32" | "italian pharmacode" => BarcodeFormat::CODE_32,
+            "pzn" | "pharmazentralnummer" => BarcodeFormat::PZN,
             "code 93" | "code_93" | "code93" => BarcodeFormat::CODE_93,
             "code 128" | "code_128" | "code128" | "iso/ied 15417:2007" | "iso/_15417:2007" => {
                 BarcodeFormat::CODE_128
             }
             "datamatrix" | "data matrix" | "data_matrix" => BarcodeFormat::DATA_MATRIX,
+            "dotcode" | "dot code" | "dot_code" => BarcodeFormat::DOTCODE,
             "ean 8" | "ean_8" | "ean8" => BarcodeFormat::EAN_8,
             "ean 13" | "ean_13" | "ean13" => BarcodeFormat::EAN_13,
+            "han xin" | "han_xin" | "hanxin" | "han xin code" | "chinese sensible code" => {
+                BarcodeFormat::HAN_XIN
+            }
+            "iata 2 of 5" | "iata_2_of_5" | "iata" | "iata 2/5" => BarcodeFormat::IATA_2_OF_5,
+            "industrial 2 of 5" | "industrial_2_of_5" | "standard 2 of 5" | "code 2 of 5" => {
+                BarcodeFormat::INDUSTRIAL_2_OF_5
+            }
+            "intelligent mail" | "intelligent_mail" | "imb" | "usps imb" | "onecode" => {
+                BarcodeFormat::INTELLIGENT_MAIL
+            }
             "itf" | "itf_code" | "itf14" | "itf 14" | "itf_14" | "interleaved 2 of 5" => {
                 BarcodeFormat::ITF
             }
+            "kix" | "kix code" | "klant index" => BarcodeFormat::KIX,
+            "matrix 2 of 5" | "matrix_2_of_5" | "matrix 2/5" => BarcodeFormat::MATRIX_2_OF_5,
             "maxicode" | "maxi_code" => BarcodeFormat::MAXICODE,
+            "msi" | "msi plessey" | "msi_plessey" | "modified plessey" => BarcodeFormat::MSI,
             "pdf 417" | "pdf_417" | "pdf417" | "iso 15438" | "iso_15438" => BarcodeFormat::PDF_417,
+            "pharmacode" | "pharma" | "laetus pharmacode" => BarcodeFormat::PHARMACODE,
+            "pharmacode two track"
+            | "pharmacode_two_track"
+            | "pharmacode 2-track"
+            | "pharma two track" => BarcodeFormat::PHARMACODE_TWO_TRACK,
+            "planet" | "usps planet" => BarcodeFormat::PLANET,
+            "postnet" | "usps postnet" => BarcodeFormat::POSTNET,
             "qrcode" | "qr_code" | "qr code" => BarcodeFormat::QR_CODE,
             "mqr" | "microqr" | "micro_qr" | "micro_qrcode" | "micro_qr_code" | "mqr_code" => {
                 BarcodeFormat::MICRO_QR_CODE
@@ -159,6 +271,7 @@ impl From<&str> for BarcodeFormat {
             "rmqr" | "rectangular_mqr" | "rectangular_micro_qr" | "rmqr_code" => {
                 BarcodeFormat::RECTANGULAR_MICRO_QR_CODE
             }
+            "rm4scc" | "royal mail" | "royal mail 4-state" | "cbc" => BarcodeFormat::RM4SCC,
             "rss 14" | "rss_14" | "rss14" | "gs1 databar" | "gs1 databar coupon"
             | "gs1_databar_coupon" => BarcodeFormat::RSS_14,
             "rss expanded" | "expanded rss" | "rss_expanded" => BarcodeFormat::RSS_EXPANDED,
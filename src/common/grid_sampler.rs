@@ -21,7 +21,7 @@
 use crate::{Exceptions, point};
 use crate::{Point, common::Result};
 
-use super::{BitMatrix, PerspectiveTransform, Quadrilateral};
+use super::{BitMatrix, DefaultGridSampler, PerspectiveTransform, Quadrilateral};
 
 /**
  * Implementations of this class can, given locations of finder patterns for a QR code in an
@@ -165,6 +165,41 @@ pub trait GridSampler {
         Ok((bits, [Point::default(); 4]))
     }
 
+    /**
+     * Marks the modules of a grid sampled like {@link #sample_grid} whose color is in doubt:
+     * those where the points a quarter of a module from their center, along the diagonals, do not
+     * all fall on the same color, or fall off the image. Decoders can treat the codewords holding
+     * such modules as erasures.
+     *
+     * @return {@link BitMatrix} with the uncertain modules set
+     */
+    fn sample_uncertain_modules(
+        &self,
+        image: &BitMatrix,
+        dimensionX: u32,
+        dimensionY: u32,
+        controls: &[SamplerControl],
+    ) -> Result<BitMatrix> {
+        const OFFSETS: [(f32, f32); 4] = [(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)];
+
+        let mut uncertain = BitMatrix::new(dimensionX, dimensionY)?;
+        for SamplerControl { p0, p1, transform } in controls {
+            for y in (p0.y as u32)..(p1.y as u32).min(dimensionY) {
+                for x in (p0.x as u32)..(p1.x as u32).min(dimensionX) {
+                    let mut colors = OFFSETS.iter().map(|&(dx, dy)| {
+                        let p = transform.transform_point(point(x as f32 + dx, y as f32 + dy));
+                        image.is_in(p).then(|| image.get_point(p))
+                    });
+                    let first = colors.next().flatten();
+                    if first.is_none() || colors.any(|color| color != first) {
+                        uncertain.set(x, y);
+                    }
+                }
+            }
+        }
+        Ok(uncertain)
+    }
+
     /**
      * <p>Checks a set of points that have been transformed to sample points on an image against
      * the image's dimensions to see if the point are even within the image.</p>
@@ -239,6 +274,7 @@ pub trait GridSampler {
     }
 }
 
+#[derive(Clone)]
 pub struct SamplerControl {
     pub p0: Point,
     pub p1: Point,
@@ -254,3 +290,55 @@ impl SamplerControl {
         }
     }
 }
+
+/**
+ * Where a detector sampled a grid from the image, so that the modules whose color was in doubt
+ * can be marked later with {@link GridSampler#sample_uncertain_modules}, and only if a decoder
+ * needs them.
+ */
+#[derive(Clone)]
+pub struct SampledGrid {
+    dimensionX: u32,
+    dimensionY: u32,
+    controls: Vec<SamplerControl>,
+}
+
+impl SampledGrid {
+    pub fn new(dimensionX: u32, dimensionY: u32, controls: Vec<SamplerControl>) -> Self {
+        Self {
+            dimensionX,
+            dimensionY,
+            controls,
+        }
+    }
+
+    /**
+     * For a grid sampled like {@link GridSampler#sample_grid_detailed}.
+     */
+    pub fn detailed(
+        dimensionX: u32,
+        dimensionY: u32,
+        dst: Quadrilateral,
+        src: Quadrilateral,
+    ) -> Result<Self> {
+        let transform = PerspectiveTransform::quadrilateralToQuadrilateral(dst, src)?;
+        Ok(Self::new(
+            dimensionX,
+            dimensionY,
+            vec![SamplerControl::new(dimensionX, dimensionY, transform)],
+        ))
+    }
+
+    /**
+     * @param image the image the grid was sampled from
+     * @return {@link BitMatrix} with the uncertain modules set
+     */
+    pub fn sample_uncertain_modules(&self, image: &BitMatrix) -> Result<BitMatrix> {
+        DefaultGridSampler.sample_uncertain_modules(
+            image,
+            self.dimensionX,
+            self.dimensionY,
+            &self.controls,
+        )
+    }
+}
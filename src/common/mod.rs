@@ -73,6 +73,14 @@ pub trait DetectorRXingResult {
     fn getBits(&self) -> &BitMatrix;
 
     fn getPoints(&self) -> &[Point];
+
+    /**
+     * @return where {@link #getBits()} was sampled from, to mark its modules whose color was in
+     * doubt if the decoder needs them, when the detector kept track of it
+     */
+    fn getSampledGrid(&self) -> Option<&SampledGrid> {
+        None
+    }
 }
 
 // pub struct DetectorRXingResult {
@@ -1,6 +1,6 @@
 use rand::RngExt;
 
-use super::{GenericGFRef, ReedSolomonDecoder, ReedSolomonEncoder};
+use super::{ERASURE_MARGIN, GenericGFRef, ReedSolomonDecoder, ReedSolomonEncoder};
 /*
  * Copyrigh&t 2013 ZXing authors
  *
@@ -340,6 +340,99 @@ fn test_aztec() {
     test_encode_decode_random(azd12, 3072, 1023);
 }
 
+#[test]
+fn test_erasures() {
+    use super::{PredefinedGenericGF, get_predefined_genericgf};
+
+    test_encode_decode_erasures(
+        get_predefined_genericgf(PredefinedGenericGF::QrCodeField256),
+        20,
+        10,
+    );
+    test_encode_decode_erasures(
+        get_predefined_genericgf(PredefinedGenericGF::DataMatrixField256),
+        36,
+        24,
+    );
+    test_encode_decode_erasures(
+        get_predefined_genericgf(PredefinedGenericGF::AztecParam),
+        4,
+        6,
+    );
+    test_encode_decode_erasures(
+        get_predefined_genericgf(PredefinedGenericGF::AztecData6),
+        20,
+        12,
+    );
+    test_encode_decode_erasures(
+        get_predefined_genericgf(PredefinedGenericGF::AztecData10),
+        128,
+        64,
+    );
+}
+
+/// Corrupts e codewords flagged as erasures and (ecSize - ERASURE_MARGIN - e) / 2 unflagged
+/// ones, for every e, along with one flagged codeword left intact
+fn test_encode_decode_erasures(field: GenericGFRef, dataSize: usize, ecSize: usize) {
+    let mut encoder = ReedSolomonEncoder::new(field).expect("new");
+    let decoder = ReedSolomonDecoder::new(field);
+    let mut random = get_pseudo_random();
+    let mut codewords = vec![0; dataSize + ecSize];
+    for data in codewords.iter_mut().take(dataSize) {
+        *data = random.random_range(0..field.getSize() as i32);
+    }
+    encoder
+        .encode(&mut codewords, ecSize)
+        .expect("should encode");
+
+    let capacity = ecSize - ERASURE_MARGIN;
+    for numErasures in 0..=capacity {
+        let numErrors = (capacity - numErasures) / 2;
+        let mut positions: Vec<usize> = (0..codewords.len()).collect();
+        for i in 0..positions.len() {
+            let j = random.random_range(i..positions.len());
+            positions.swap(i, j);
+        }
+        let mut received = codewords.clone();
+        for &position in positions.iter().take(numErasures + numErrors) {
+            received[position] = (received[position]
+                + random.random_range(1..field.getSize() as i32))
+                % field.getSize() as i32;
+        }
+        let mut erasures = positions[..numErasures].to_vec();
+        if numErrors * 2 + numErasures < capacity {
+            // Costs one more codeword, which is left over
+            erasures.push(positions[numErasures + numErrors]);
+        }
+
+        let corrected = decoder
+            .decode_with_erasures(&mut received, ecSize as i32, &erasures)
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Decode in {field} ({dataSize},{ecSize}) failed at {numErasures} erasures and {numErrors} errors: {e:#?}"
+                )
+            });
+        assert_data_equals(
+            format!("Decode in {field} ({dataSize},{ecSize}) with {numErasures} erasures"),
+            &codewords,
+            &received,
+        );
+        assert!(corrected >= numErrors + numErasures);
+    }
+
+    // Erasures that would spend the spare codewords are refused
+    for numErasures in [capacity + 1, ecSize + 1] {
+        let mut received = codewords.clone();
+        let erasures: Vec<usize> = (0..numErasures).collect();
+        received[0] ^= 1;
+        assert!(
+            decoder
+                .decode_with_erasures(&mut received, ecSize as i32, &erasures)
+                .is_err()
+        );
+    }
+}
+
 pub(crate) fn corrupt(
     received: &mut [i32],
     howMany: i32,
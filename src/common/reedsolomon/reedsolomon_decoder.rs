@@ -21,6 +21,10 @@ use crate::common::Result;
 
 use super::{GenericGF, GenericGFPoly, GenericGFRef};
 
+/// Error-correction codewords that decoding with erasures leaves spare. With every one of them
+/// spent any received word decodes to some codeword, so a misread symbol would go unnoticed.
+pub const ERASURE_MARGIN: usize = 2;
+
 /**
  * <p>Implements Reed-Solomon decoding, as the name implies.</p>
  *
@@ -62,6 +66,32 @@ impl ReedSolomonDecoder {
      * @throws ReedSolomonException if decoding fails for any reason
      */
     pub fn decode(&self, received: &mut [i32], twoS: i32) -> Result<usize> {
+        self.decode_with_erasures(received, twoS, &[])
+    }
+
+    /**
+     * <p>Decodes like {@link #decode}, given the positions of codewords known to be unreliable,
+     * for example because some of their modules could not be told apart from their neighbours.
+     * Correcting an erasure costs one error-correction codeword where an error at an unknown
+     * position costs two. To still notice symbols too damaged to correct, ERASURE_MARGIN of the
+     * error-correction codewords are kept spare, so e erasures and up to
+     * (twoS - ERASURE_MARGIN - e) / 2 other errors can be corrected.</p>
+     *
+     * <p>Erasures make a wrong correction more likely than errors alone, so callers should only
+     * fall back on this once {@link #decode} failed.</p>
+     *
+     * @param received data and error-correction codewords
+     * @param twoS number of error-correction codewords available
+     * @param erasures indexes into received of the unreliable codewords
+     * @return number of codewords corrected, erasures included
+     * @throws ReedSolomonException if decoding fails for any reason
+     */
+    pub fn decode_with_erasures(
+        &self,
+        received: &mut [i32],
+        twoS: i32,
+        erasures: &[usize],
+    ) -> Result<usize> {
         let poly = GenericGFPoly::new(self.field, received)?;
         let mut syndromeCoefficients = vec![0; twoS as usize];
         let mut noError = true;
@@ -77,17 +107,53 @@ impl ReedSolomonDecoder {
         if noError {
             return Ok(0);
         }
-        let Ok(syndrome) = GenericGFPoly::new(self.field, &syndromeCoefficients) else {
+        if !erasures.is_empty() && erasures.len() + ERASURE_MARGIN > twoS as usize {
+            return Err(Exceptions::reed_solomon_with("Too many erasures"));
+        }
+        let Ok(mut syndrome) = GenericGFPoly::new(self.field, &syndromeCoefficients) else {
             return Err(Exceptions::REED_SOLOMON);
         };
+
+        // Fold the erasures into the syndrome, so that the key equation only has the unknown
+        // errors left to locate, with as many fewer error-correction codewords
+        let mut erasureLocations = Vec::with_capacity(erasures.len());
+        for &erasure in erasures {
+            if erasure >= received.len() {
+                return Err(Exceptions::reed_solomon_with("Bad erasure location"));
+            }
+            let location = self.field.exp((received.len() - 1 - erasure) as i32);
+            if erasureLocations.contains(&(location as usize)) {
+                continue;
+            }
+            erasureLocations.push(location as usize);
+            syndrome = syndrome.multiply(&GenericGFPoly::new(self.field, &[location, 1])?)?;
+        }
+        if !erasureLocations.is_empty() {
+            // Modulo x^twoS
+            let coefficients = syndrome.getCoefficients();
+            let keep = coefficients.len().min(twoS as usize);
+            syndrome = GenericGFPoly::new(self.field, &coefficients[coefficients.len() - keep..])?;
+        }
+
         let sigmaOmega = self.runEuclideanAlgorithm(
             &GenericGF::buildMonomial(self.field, twoS as usize, 1),
             &syndrome,
-            twoS as usize,
+            twoS as usize + erasureLocations.len(),
         )?;
         let sigma = &sigmaOmega[0];
         let omega = &sigmaOmega[1];
-        let errorLocations = self.findErrorLocations(sigma)?;
+        let mut errorLocations = self.findErrorLocations(sigma)?;
+        if errorLocations.iter().any(|l| erasureLocations.contains(l)) {
+            return Err(Exceptions::reed_solomon_with("Error located at an erasure"));
+        }
+        if !erasureLocations.is_empty()
+            && 2 * errorLocations.len() + erasureLocations.len() + ERASURE_MARGIN > twoS as usize
+        {
+            return Err(Exceptions::reed_solomon_with(
+                "Too many errors and erasures",
+            ));
+        }
+        errorLocations.extend_from_slice(&erasureLocations);
         let errorMagnitudes = self.findErrorMagnitudes(omega, &errorLocations)?;
         for (error_location, error_magnitude) in errorLocations.iter().zip(errorMagnitudes) {
             // for i in 0..errorLocations.len() {
@@ -103,6 +169,19 @@ impl ReedSolomonDecoder {
             received[position as usize] =
                 GenericGF::addOrSubtract(received[position as usize], error_magnitude);
         }
+
+        // With erasures the code is stretched closer to its limit, check that the correction
+        // did land on a codeword
+        if !erasureLocations.is_empty() {
+            let poly = GenericGFPoly::new(self.field, received)?;
+            for i in 0..twoS {
+                if poly.evaluateAt(self.field.exp(i + self.field.getGeneratorBase()) as usize) != 0
+                {
+                    return Err(Exceptions::reed_solomon_with("Erasures left errors"));
+                }
+            }
+        }
+
         Ok(errorLocations.len())
     }
 
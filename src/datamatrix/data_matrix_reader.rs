@@ -35,7 +35,7 @@ static DECODER: Lazy<Decoder> = Lazy::new(Decoder::new);
  *
  * @author bbrown@google.com (Brian Brown)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct DataMatrixReader;
 
 // private static final Point[] NO_POINTS = new Point[0];
@@ -173,10 +173,15 @@ impl DataMatrixReader {
         } else {
             //Result<DatamatrixDetectorResult, Exceptions>
             decoderRXingResult = if let Ok(fnd) = || -> Result<DecoderRXingResult> {
-                let detectorRXingResult =
-                    zxing_cpp_detector::detect(image.get_black_matrix(), try_harder, true)?;
+                let black = image.get_black_matrix();
+                let detectorRXingResult = zxing_cpp_detector::detect(black, try_harder, true)?;
                 for symbol in detectorRXingResult {
-                    let decoded = DECODER.decode(symbol.getBits());
+                    let decoded = DECODER.decode_with_uncertain_modules(symbol.getBits(), || {
+                        symbol
+                            .getSampledGrid()?
+                            .sample_uncertain_modules(black)
+                            .ok()
+                    });
                     if decoded.is_ok() {
                         points = symbol.getPoints().to_vec();
                         return decoded;
@@ -189,8 +194,17 @@ impl DataMatrixReader {
                 fnd
             } else if try_harder {
                 if let Ok(fnd) = || -> Result<DecoderRXingResult> {
-                    let detectorRXingResult = Detector::new(image.get_black_matrix())?.detect()?;
-                    let decoded = DECODER.decode(detectorRXingResult.getBits())?;
+                    let black = image.get_black_matrix();
+                    let detectorRXingResult = Detector::new(black)?.detect()?;
+                    let decoded = DECODER.decode_with_uncertain_modules(
+                        detectorRXingResult.getBits(),
+                        || {
+                            detectorRXingResult
+                                .getSampledGrid()?
+                                .sample_uncertain_modules(black)
+                                .ok()
+                        },
+                    )?;
                     points = detectorRXingResult.getPoints().to_vec();
                     Ok(decoded)
                 }() {
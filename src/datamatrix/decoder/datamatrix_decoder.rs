@@ -47,12 +47,54 @@ impl Decoder {
      * @throws ChecksumException if error correction fails
      */
     pub fn decode(&self, bits: &BitMatrix) -> Result<DecoderRXingResult> {
-        let decoded = self.perform_decode(bits, false, false);
+        self.decode_with_erasures(bits, None)
+    }
+
+    /**
+     * <p>Decodes a Data Matrix Code represented as a {@link BitMatrix}, like {@link #decode}.
+     * Only if that fails are the uncertain modules asked for, and the codewords that hold one of
+     * them treated as erasures where error correction fails without.</p>
+     *
+     * @param bits booleans representing white/black Data Matrix Code modules
+     * @param uncertainModules gives the modules of bits whose color was in doubt when sampled
+     * @return text and bytes encoded within the Data Matrix Code
+     * @throws FormatException if the Data Matrix Code cannot be decoded
+     * @throws ChecksumException if error correction fails
+     */
+    pub fn decode_with_uncertain_modules(
+        &self,
+        bits: &BitMatrix,
+        uncertainModules: impl FnOnce() -> Option<BitMatrix>,
+    ) -> Result<DecoderRXingResult> {
+        let decoded = self.decode(bits);
+        if decoded.is_ok() {
+            return decoded;
+        }
+        match uncertainModules() {
+            Some(uncertain) => self
+                .decode_with_erasures(bits, Some(&uncertain))
+                .or(decoded),
+            None => decoded,
+        }
+    }
+
+    fn decode_with_erasures(
+        &self,
+        bits: &BitMatrix,
+        uncertainModules: Option<&BitMatrix>,
+    ) -> Result<DecoderRXingResult> {
+        let decoded = self.perform_decode(bits, uncertainModules, false, false);
         if decoded.is_ok() {
             return decoded;
         }
 
-        self.perform_decode(&Self::flip_bitmatrix(bits)?, false, true)
+        let flippedUncertain = uncertainModules.map(Self::flip_bitmatrix).transpose()?;
+        self.perform_decode(
+            &Self::flip_bitmatrix(bits)?,
+            flippedUncertain.as_ref(),
+            false,
+            true,
+        )
     }
 
     fn flip_bitmatrix(bits: &BitMatrix) -> Result<BitMatrix> {
@@ -82,7 +124,7 @@ impl Decoder {
      * @throws ChecksumException if error correction fails
      */
     pub fn decode_bools(&self, image: &[Vec<bool>]) -> Result<DecoderRXingResult> {
-        self.perform_decode(&BitMatrix::parse_bools(image), false, false)
+        self.perform_decode(&BitMatrix::parse_bools(image), None, false, false)
     }
 
     /**
@@ -97,6 +139,7 @@ impl Decoder {
     fn perform_decode(
         &self,
         bits: &BitMatrix,
+        uncertainModules: Option<&BitMatrix>,
         fix259: bool,
         is_flipped: bool,
     ) -> Result<DecoderRXingResult> {
@@ -108,8 +151,20 @@ impl Decoder {
 
         let version = parser.getVersion();
 
+        // Any codeword read from the uncertain modules with a bit set holds one of them
+        let uncertainCodewords = match uncertainModules {
+            Some(uncertain)
+                if uncertain.getWidth() == bits.getWidth()
+                    && uncertain.getHeight() == bits.getHeight() =>
+            {
+                BitMatrixParser::new(uncertain)?.readCodewords()?
+            }
+            _ => vec![0; codewords.len()],
+        };
+
         // Separate into data blocks
         let dataBlocks = DataBlock::getDataBlocks(&codewords, version, fix259)?;
+        let uncertainBlocks = DataBlock::getDataBlocks(&uncertainCodewords, version, fix259)?;
 
         // Count total number of data bytes
         let totalBytes = dataBlocks
@@ -119,17 +174,28 @@ impl Decoder {
         let mut resultBytes = vec![0u8; totalBytes as usize];
 
         let dataBlocksCount = dataBlocks.len();
+        let mut erasures = 0;
         // Error-correct and copy data blocks together into a stream of bytes
         for j in 0..dataBlocksCount {
             // for (int j = 0; j < dataBlocksCount; j++) {
             let dataBlock = &dataBlocks[j];
             let mut codewordBytes = dataBlock.getCodewords().to_vec();
             let numDataCodewords = dataBlock.getNumDataCodewords() as usize;
-            let errors_corrected = self.correctErrors(&mut codewordBytes, numDataCodewords as u32);
-            if errors_corrected.is_err() && !fix259 {
-                return self.perform_decode(bits, true, is_flipped);
-            } else if errors_corrected.is_err() {
-                return Err(errors_corrected.err().unwrap());
+            let uncertain: Vec<usize> = uncertainBlocks[j]
+                .getCodewords()
+                .iter()
+                .enumerate()
+                .filter(|(_, flag)| **flag != 0)
+                .map(|(i, _)| i)
+                .collect();
+            let errors_corrected =
+                self.correctErrors(&mut codewordBytes, numDataCodewords as u32, &uncertain);
+            match errors_corrected {
+                Ok(used) => erasures += used,
+                Err(_) if !fix259 => {
+                    return self.perform_decode(bits, uncertainModules, true, is_flipped);
+                }
+                Err(e) => return Err(e),
             }
             for i in 0..numDataCodewords {
                 // for (int i = 0; i < numDataCodewords; i++) {
@@ -139,7 +205,9 @@ impl Decoder {
         }
 
         // Decode the contents of that stream of bytes
-        decoded_bit_stream_parser::decode(&resultBytes, is_flipped)
+        let mut result = decoded_bit_stream_parser::decode(&resultBytes, is_flipped)?;
+        result.setErasures(erasures);
+        Ok(result)
     }
 
     /**
@@ -148,9 +216,17 @@ impl Decoder {
      *
      * @param codewordBytes data and error correction codewords
      * @param numDataCodewords number of codewords that are data bytes
+     * @param erasures positions of the codewords holding uncertain modules, tried as erasures if
+     *   correcting them as errors fails
+     * @return number of erasures used
      * @throws ChecksumException if error correction fails
      */
-    fn correctErrors(&self, codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<()> {
+    fn correctErrors(
+        &self,
+        codewordBytes: &mut [u8],
+        numDataCodewords: u32,
+        erasures: &[usize],
+    ) -> Result<usize> {
         let _numCodewords = codewordBytes.len();
         // First read into an array of ints
         // let codewordsInts = vec![0;numCodewords];
@@ -161,10 +237,20 @@ impl Decoder {
         let mut codewordsInts: Vec<i32> = codewordBytes.iter().map(|x| *x as i32).collect();
 
         //try {
-        self.0.decode(
-            &mut codewordsInts,
-            codewordBytes.len() as i32 - numDataCodewords as i32,
-        )?;
+        let twoS = codewordBytes.len() as i32 - numDataCodewords as i32;
+        let mut erasuresUsed = 0;
+        if let Err(error) = self.0.decode(&mut codewordsInts, twoS) {
+            codewordsInts = codewordBytes.iter().map(|x| *x as i32).collect();
+            if erasures.is_empty()
+                || self
+                    .0
+                    .decode_with_erasures(&mut codewordsInts, twoS, erasures)
+                    .is_err()
+            {
+                return Err(error);
+            }
+            erasuresUsed = erasures.len();
+        }
         //} catch (ReedSolomonException ignored) {
         //throw ChecksumException.getChecksumInstance();
         //}
@@ -175,7 +261,7 @@ impl Decoder {
             codewordBytes[i] = codewordsInts[i] as u8;
         }
         // codewordsInts.into_iter().take(numDataCodewords as usize).map(|x| x as u8).collect::<Vec<u8>>()
-        Ok(())
+        Ok(erasuresUsed)
     }
 }
 
@@ -184,3 +270,69 @@ impl Default for Decoder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "encoders")]
+mod tests {
+    use crate::{
+        BarcodeFormat, BinaryBitmap, Luma8LuminanceSource, Reader, Writer,
+        common::{DetectorRXingResult, HybridBinarizer},
+        datamatrix::{DataMatrixReader, DataMatrixWriter, detector::zxing_cpp_detector},
+    };
+
+    use super::Decoder;
+
+    #[test]
+    fn corrects_smudged_modules_as_erasures() {
+        const TEXT: &str = "Smudged codewords are erasures, at half the cost of errors";
+        const SCALE: u32 = 4;
+        let matrix = DataMatrixWriter
+            .encode(TEXT, &BarcodeFormat::DATA_MATRIX, 0, 0)
+            .unwrap();
+
+        // A 2 by 2 pixel checkerboard over some of the data modules: their centers read as
+        // one color whatever the module was, but not the rest of them
+        let quiet = 2 * SCALE;
+        let size = matrix.getWidth() * SCALE + 2 * quiet;
+        let smudge = 4..16;
+        let mut pixels = vec![255u8; (size * size) as usize];
+        for y in quiet..size - quiet {
+            for x in quiet..size - quiet {
+                let (mx, my) = ((x - quiet) / SCALE, (y - quiet) / SCALE);
+                let dark = if smudge.contains(&mx) && smudge.contains(&my) {
+                    (x / 2 + y / 2) % 2 == 0
+                } else {
+                    matrix.get(mx, my)
+                };
+                if dark {
+                    pixels[(y * size + x) as usize] = 0;
+                }
+            }
+        }
+        let mut image = BinaryBitmap::new(HybridBinarizer::new(Luma8LuminanceSource::new(
+            pixels, size, size,
+        )));
+
+        let black = image.get_black_matrix();
+        let detected = zxing_cpp_detector::detect(black, false, true).unwrap();
+        let decoder = Decoder::new();
+        assert!(
+            detected
+                .iter()
+                .all(|d| decoder.decode(d.getBits()).is_err())
+        );
+        let result = detected
+            .iter()
+            .find_map(|d| {
+                decoder
+                    .decode_with_uncertain_modules(d.getBits(), || {
+                        d.getSampledGrid()?.sample_uncertain_modules(black).ok()
+                    })
+                    .ok()
+            })
+            .unwrap();
+        assert_eq!(result.getText(), TEXT);
+        assert!(result.getErasures() > 0);
+        assert_eq!(DataMatrixReader.decode(&mut image).unwrap().getText(), TEXT);
+    }
+}
@@ -540,19 +540,18 @@ fn decodeTextSegment(
                     }
                     shift = 0;
                 }
-                3
-                    if cValue < TEXT_SHIFT3_SET_CHARS.len() as u32 => {
-                        let textChar = TEXT_SHIFT3_SET_CHARS[cValue as usize];
-                        if upperShift {
-                            result.append_char(
-                                char::from_u32(textChar as u32 + 128).ok_or(Exceptions::PARSE)?,
-                            );
-                            upperShift = false;
-                        } else {
-                            result.append_char(textChar);
-                        }
-                        shift = 0;
+                3 if cValue < TEXT_SHIFT3_SET_CHARS.len() as u32 => {
+                    let textChar = TEXT_SHIFT3_SET_CHARS[cValue as usize];
+                    if upperShift {
+                        result.append_char(
+                            char::from_u32(textChar as u32 + 128).ok_or(Exceptions::PARSE)?,
+                        );
+                        upperShift = false;
+                    } else {
+                        result.append_char(textChar);
                     }
+                    shift = 0;
+                }
 
                 _ => return Err(Exceptions::FORMAT),
             }
@@ -17,7 +17,7 @@
 use crate::{
     Exceptions, Point,
     common::{
-        BitMatrix, DefaultGridSampler, GridSampler, Quadrilateral, Result,
+        BitMatrix, DefaultGridSampler, GridSampler, Quadrilateral, Result, SampledGrid,
         detector::WhiteRectangleDetector,
     },
     point,
@@ -85,7 +85,7 @@ impl<'a> Detector<'_> {
             dimensionRight = dimensionTop.max(dimensionRight);
         }
 
-        let bits = Self::sampleGrid(
+        let (bits, sampledGrid) = Self::sampleGrid(
             self.image,
             topLeft,
             bottomLeft,
@@ -95,10 +95,10 @@ impl<'a> Detector<'_> {
             dimensionRight,
         )?;
 
-        Ok(DatamatrixDetectorResult::new(
-            bits,
-            vec![topLeft, bottomLeft, bottomRight, topRight],
-        ))
+        Ok(
+            DatamatrixDetectorResult::new(bits, vec![topLeft, bottomLeft, bottomRight, topRight])
+                .with_sampled_grid(sampledGrid),
+        )
     }
 
     #[inline]
@@ -330,7 +330,7 @@ impl<'a> Detector<'_> {
         topRight: Point,
         dimensionX: u32,
         dimensionY: u32,
-    ) -> Result<BitMatrix> {
+    ) -> Result<(BitMatrix, SampledGrid)> {
         let sampler = DefaultGridSampler;
 
         let dst = Quadrilateral::new(
@@ -342,7 +342,8 @@ impl<'a> Detector<'_> {
         let src = Quadrilateral::new(topRight, topLeft, bottomRight, bottomLeft);
 
         let (res, _) = sampler.sample_grid_detailed(image, dimensionX, dimensionY, dst, src)?;
-        Ok(res)
+        let sampledGrid = SampledGrid::detailed(dimensionX, dimensionY, dst, src)?;
+        Ok((res, sampledGrid))
     }
 
     /**
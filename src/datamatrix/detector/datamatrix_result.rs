@@ -1,13 +1,17 @@
 use crate::{
     Point,
-    common::{BitMatrix, DetectorRXingResult},
+    common::{BitMatrix, DetectorRXingResult, SampledGrid},
 };
 
-pub struct DatamatrixDetectorResult(BitMatrix, Vec<Point>);
+pub struct DatamatrixDetectorResult(BitMatrix, Vec<Point>, Option<SampledGrid>);
 
 impl DatamatrixDetectorResult {
     pub fn new(bits: BitMatrix, points: Vec<Point>) -> Self {
-        Self(bits, points)
+        Self(bits, points, None)
+    }
+
+    pub fn with_sampled_grid(self, sampled_grid: SampledGrid) -> Self {
+        Self(self.0, self.1, Some(sampled_grid))
     }
 }
 
@@ -19,4 +23,8 @@ impl DetectorRXingResult for DatamatrixDetectorResult {
     fn getPoints(&self) -> &[Point] {
         &self.1
     }
+
+    fn getSampledGrid(&self) -> Option<&SampledGrid> {
+        self.2.as_ref()
+    }
 }
@@ -17,7 +17,7 @@ use crate::{
     Exceptions, Point,
     common::cpp_essentials::ByteMatrix,
     common::{
-        BitMatrix, DefaultGridSampler, GridSampler, Quadrilateral, Result,
+        BitMatrix, DefaultGridSampler, GridSampler, Quadrilateral, Result, SampledGrid,
         cpp_essentials::RegressionLineTrait,
     },
     datamatrix::detector::{
@@ -234,11 +234,12 @@ fn Scan(
         CHECK!(res.is_ok());
 
         let (res, _) = res?;
+        let sampledGrid = SampledGrid::detailed(dimT as u32, dimR as u32, dst, src)?;
 
-        return Ok(DatamatrixDetectorResult::new(
-            res,
-            sourcePoints.points().to_vec(),
-        ));
+        return Ok(
+            DatamatrixDetectorResult::new(res, sourcePoints.points().to_vec())
+                .with_sampled_grid(sampledGrid),
+        );
     }
 
     Err(Exceptions::NOT_FOUND)
@@ -127,6 +127,28 @@ pub enum DecodeHintType {
      */
     TELEPEN_AS_NUMERIC,
 
+    /**
+     * Number of check digits (0, 1 or 2) a Code 11 barcode carries. Maps to a {@code u32}.
+     */
+    CODE_11_CHECK_DIGITS,
+
+    /**
+     * Check digit scheme of MSI Plessey barcodes. Maps to a {@link MSIChecksum}.
+     */
+    MSI_CHECKSUM,
+
+    /**
+     * Assume Industrial, IATA and Matrix 2 of 5 codes end in a modulo 10 check digit. Doesn't
+     * matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    ASSUME_TWO_OF_FIVE_CHECK_DIGIT,
+
+    /**
+     * If true, looks for the 2D component of a GS1 Composite symbol above EAN/UPC, GS1 DataBar
+     * and GS1-128 barcodes. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    DECODE_COMPOSITE,
+
     /**
      * The minimum size (in pixels) for valid pattern modules.
      * Currently applied to QR code detectors only; has no effect on 1D barcode readers.
@@ -248,6 +270,34 @@ pub enum DecodeHintValue {
      */
     TelepenAsNumeric(bool),
 
+    /**
+     * Number of check digits (0, 1 or 2) the Code 11 reader validates and strips. By default two
+     * check digits are tried first, then one.
+     */
+    #[cfg(feature = "oned")]
+    Code11CheckDigits(u32),
+
+    /**
+     * Check digit scheme the MSI Plessey reader validates and strips. Defaults to a single
+     * modulo 10 check digit.
+     */
+    #[cfg(feature = "oned")]
+    MsiChecksum(crate::oned::MSIChecksum),
+
+    /**
+     * Assume Industrial, IATA and Matrix 2 of 5 codes end in a modulo 10 check digit, which is
+     * validated and removed from the result.
+     */
+    #[cfg(feature = "oned")]
+    AssumeTwoOfFiveCheckDigit(bool),
+
+    /**
+     * Look for the 2D component of a GS1 Composite symbol above EAN/UPC, GS1 DataBar and GS1-128
+     * barcodes, needs the pdf417 feature. When one is found the text holds the element strings
+     * of the whole message; barcodes without one are returned as they are.
+     */
+    DecodeComposite(bool),
+
     /**
      * The minimum size (in pixels) for valid pattern modules.
      * Currently applied to QR code detectors only; has no effect on 1D barcode readers.
@@ -351,6 +401,34 @@ pub struct DecodeHints {
      */
     pub TelepenAsNumeric: Option<bool>,
 
+    /**
+     * Number of check digits (0, 1 or 2) the Code 11 reader validates and strips. By default two
+     * check digits are tried first, then one.
+     */
+    #[cfg(feature = "oned")]
+    pub Code11CheckDigits: Option<u32>,
+
+    /**
+     * Check digit scheme the MSI Plessey reader validates and strips. Defaults to a single
+     * modulo 10 check digit.
+     */
+    #[cfg(feature = "oned")]
+    pub MsiChecksum: Option<crate::oned::MSIChecksum>,
+
+    /**
+     * Assume Industrial, IATA and Matrix 2 of 5 codes end in a modulo 10 check digit, which is
+     * validated and removed from the result.
+     */
+    #[cfg(feature = "oned")]
+    pub AssumeTwoOfFiveCheckDigit: Option<bool>,
+
+    /**
+     * Look for the 2D component of a GS1 Composite symbol above EAN/UPC, GS1 DataBar and GS1-128
+     * barcodes, needs the pdf417 feature. When one is found the text holds the element strings
+     * of the whole message; barcodes without one are returned as they are.
+     */
+    pub DecodeComposite: Option<bool>,
+
     /**
      * The minimum size (in pixels) for valid pattern modules.
      * Currently applied to QR code detectors only; has no effect on 1D barcode readers.
@@ -382,6 +460,15 @@ impl From<super::DecodingHintDictionary> for DecodeHints {
                 DecodeHintValue::AllowedEanExtensions(v) => new_self.AllowedEanExtensions = Some(v),
                 DecodeHintValue::AlsoInverted(v) => new_self.AlsoInverted = Some(v),
                 DecodeHintValue::TelepenAsNumeric(v) => new_self.TelepenAsNumeric = Some(v),
+                #[cfg(feature = "oned")]
+                DecodeHintValue::Code11CheckDigits(v) => new_self.Code11CheckDigits = Some(v),
+                #[cfg(feature = "oned")]
+                DecodeHintValue::MsiChecksum(v) => new_self.MsiChecksum = Some(v),
+                #[cfg(feature = "oned")]
+                DecodeHintValue::AssumeTwoOfFiveCheckDigit(v) => {
+                    new_self.AssumeTwoOfFiveCheckDigit = Some(v)
+                }
+                DecodeHintValue::DecodeComposite(v) => new_self.DecodeComposite = Some(v),
                 DecodeHintValue::MinimumModuleSize(v) => new_self.MinimumModuleSize = Some(v),
                 #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
                 DecodeHintValue::QrAssumeSpecConformInput(v) => {
@@ -479,6 +566,37 @@ impl From<DecodeHints> for super::DecodingHintDictionary {
             );
         }
 
+        #[cfg(feature = "oned")]
+        if let Some(v) = value.Code11CheckDigits {
+            new_self.insert(
+                DecodeHintType::CODE_11_CHECK_DIGITS,
+                DecodeHintValue::Code11CheckDigits(v),
+            );
+        }
+
+        #[cfg(feature = "oned")]
+        if let Some(v) = value.MsiChecksum {
+            new_self.insert(
+                DecodeHintType::MSI_CHECKSUM,
+                DecodeHintValue::MsiChecksum(v),
+            );
+        }
+
+        #[cfg(feature = "oned")]
+        if let Some(v) = value.AssumeTwoOfFiveCheckDigit {
+            new_self.insert(
+                DecodeHintType::ASSUME_TWO_OF_FIVE_CHECK_DIGIT,
+                DecodeHintValue::AssumeTwoOfFiveCheckDigit(v),
+            );
+        }
+
+        if let Some(v) = value.DecodeComposite {
+            new_self.insert(
+                DecodeHintType::DECODE_COMPOSITE,
+                DecodeHintValue::DecodeComposite(v),
+            );
+        }
+
         if let Some(v) = value.MinimumModuleSize {
             new_self.insert(
                 DecodeHintType::MINIMUM_MODULE_SIZE,
@@ -514,6 +632,15 @@ impl DecodeHints {
             DecodeHintValue::AllowedEanExtensions(v) => self.AllowedEanExtensions = Some(v),
             DecodeHintValue::AlsoInverted(v) => self.AlsoInverted = Some(v),
             DecodeHintValue::TelepenAsNumeric(v) => self.TelepenAsNumeric = Some(v),
+            #[cfg(feature = "oned")]
+            DecodeHintValue::Code11CheckDigits(v) => self.Code11CheckDigits = Some(v),
+            #[cfg(feature = "oned")]
+            DecodeHintValue::MsiChecksum(v) => self.MsiChecksum = Some(v),
+            #[cfg(feature = "oned")]
+            DecodeHintValue::AssumeTwoOfFiveCheckDigit(v) => {
+                self.AssumeTwoOfFiveCheckDigit = Some(v)
+            }
+            DecodeHintValue::DecodeComposite(v) => self.DecodeComposite = Some(v),
             DecodeHintValue::MinimumModuleSize(v) => self.MinimumModuleSize = Some(v),
             #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
             DecodeHintValue::QrAssumeSpecConformInput(v) => self.QrAssumeSpecConformInput = Some(v),
@@ -0,0 +1,596 @@
+//! Reads the dot stream out of a sampled DotCode grid, corrects errors and converts the
+//! data codewords back into text.
+
+use crate::{
+    DecodeHints, Exceptions,
+    common::{BitMatrix, DecoderRXingResult, ECIStringBuilder, Eci, Result},
+};
+
+use super::{
+    dotcode_common::{
+        CODEWORD_DOTS, DOT_PATTERNS, MASK_DOTS, MASK_WEIGHTS, MAX_DIMENSION, MIN_DIMENSION,
+        apply_mask, codewords::*, data_capacity, dot_count, dot_positions, ecc_codewords,
+    },
+    reed_solomon,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CodeSet {
+    A,
+    B,
+    C,
+    Binary,
+}
+
+/// Decode a grid with one module per dot position. All four orientations are tried.
+pub fn decode(bits: &BitMatrix, _hints: &DecodeHints) -> Result<DecoderRXingResult> {
+    let mut candidate = bits.clone();
+    let mut error = Exceptions::NOT_FOUND;
+    for _ in 0..4 {
+        match decode_oriented(&candidate) {
+            Ok(result) => return Ok(result),
+            Err(e) => error = e,
+        }
+        candidate.rotate90();
+    }
+    Err(error)
+}
+
+fn decode_oriented(bits: &BitMatrix) -> Result<DecoderRXingResult> {
+    let width = bits.getWidth();
+    let height = bits.getHeight();
+    if width < MIN_DIMENSION
+        || height < MIN_DIMENSION
+        || width > MAX_DIMENSION
+        || height > MAX_DIMENSION
+        || (width + height) % 2 == 0
+    {
+        return Err(Exceptions::FORMAT);
+    }
+
+    let capacity = data_capacity(dot_count(width, height)).ok_or(Exceptions::FORMAT)?;
+    let total = 1 + capacity + ecc_codewords(capacity);
+
+    let stream: Vec<bool> = dot_positions(width, height)
+        .into_iter()
+        .map(|(x, y)| bits.get(x, y))
+        .collect();
+
+    let mut codewords = Vec::with_capacity(total);
+    let mut erasures = Vec::new();
+    codewords.push(
+        stream[..MASK_DOTS]
+            .iter()
+            .fold(0, |acc, &b| (acc << 1) | b as u32),
+    );
+    for (i, chunk) in stream[MASK_DOTS..]
+        .chunks(CODEWORD_DOTS)
+        .take(total - 1)
+        .enumerate()
+    {
+        let pattern = chunk.iter().fold(0u16, |acc, &b| (acc << 1) | b as u16);
+        match DOT_PATTERNS.iter().position(|&p| p == pattern) {
+            Some(value) => codewords.push(value as u32),
+            None => {
+                codewords.push(0);
+                erasures.push(i + 1);
+            }
+        }
+    }
+
+    let corrected = reed_solomon::decode(&mut codewords, 1 + capacity, &erasures)
+        .map_err(|_| Exceptions::CHECKSUM)?;
+
+    let mask = codewords[0];
+    if mask as usize >= MASK_WEIGHTS.len() {
+        return Err(Exceptions::FORMAT);
+    }
+    let mut data = codewords[..1 + capacity].to_vec();
+    apply_mask(&mut data, mask, true);
+
+    let (result, gs1) = decode_codewords(&data[1..])?;
+    let bytes = result.bytes().to_vec();
+
+    let mut result = DecoderRXingResult::with_symbology(
+        data[1..].iter().map(|&cw| cw as u8).collect(),
+        result.build_result().to_string(),
+        vec![bytes],
+        String::new(),
+        if gs1 { 1 } else { 0 },
+    );
+    result.setErrorsCorrected(corrected);
+    result.setErasures(erasures.len());
+    Ok(result)
+}
+
+/// Convert data codewords (without the mask indicator) to text. Also reports whether the
+/// data is GS1, which is the case when it starts with a digit pair. Other data starting
+/// with digits is marked with a leading FNC1.
+pub fn decode_codewords(codewords: &[u32]) -> Result<(ECIStringBuilder, bool)> {
+    let mut result = ECIStringBuilder::with_capacity(codewords.len() * 2);
+    let gs1 = codewords
+        .first()
+        .is_some_and(|&cw| cw <= C_SEVENTEEN_TEN as u32);
+    let mut trailer: &[u8] = &[];
+    let mut set = CodeSet::C;
+    let mut binary = BinaryDecoder::default();
+    let mut i = 0;
+
+    let next = |i: &mut usize| -> Result<u32> {
+        let cw = *codewords.get(*i).ok_or(Exceptions::FORMAT)?;
+        *i += 1;
+        Ok(cw)
+    };
+    let push_pairs = |i: &mut usize, count: u32, result: &mut ECIStringBuilder| -> Result<()> {
+        for _ in 0..count {
+            push_pair(next(i)?, result)?;
+        }
+        Ok(())
+    };
+
+    while i < codewords.len() {
+        let position = i;
+        let cw = next(&mut i)?;
+
+        if set == CodeSet::Binary {
+            if cw < BINARY_BASE as u32 {
+                binary.push_digit(cw, &mut result)?;
+                continue;
+            }
+            binary.flush(&mut result)?;
+            match cw as u8 {
+                103..=108 => push_pairs(&mut i, cw - BINARY_SHIFT_C_BASE as u32, &mut result)?,
+                BINARY_EXIT_A => set = CodeSet::A,
+                BINARY_EXIT_B => set = CodeSet::B,
+                BINARY_EXIT_C => set = CodeSet::C,
+                _ => return Err(Exceptions::FORMAT),
+            }
+            continue;
+        }
+
+        // codewords shared by all code sets
+        match cw as u8 {
+            FNC1 => {
+                // a leading FNC1 only marks non GS1 data
+                if position > 0 {
+                    result.append_byte(0x1D);
+                }
+                continue;
+            }
+            FNC2 => {
+                let first = next(&mut i)?;
+                let value = if first < 40 {
+                    first
+                } else {
+                    (first - 40) * 12769 + next(&mut i)? * 113 + next(&mut i)? + 40
+                };
+                result.append_eci(Eci::from(value));
+                continue;
+            }
+            FNC3 => continue,
+            UPPER_SHIFT_A => {
+                let value = next(&mut i)?;
+                result.append_byte(set_a_char(value)? + 0x80);
+                continue;
+            }
+            UPPER_SHIFT_B => {
+                let value = next(&mut i)?;
+                if value > 95 {
+                    return Err(Exceptions::FORMAT);
+                }
+                result.append_byte(value as u8 + 32 + 0x80);
+                continue;
+            }
+            BINARY_LATCH => {
+                set = CodeSet::Binary;
+                continue;
+            }
+            _ => {}
+        }
+
+        match set {
+            CodeSet::C => match cw as u8 {
+                0..=99 => push_pair(cw, &mut result)?,
+                C_SEVENTEEN_TEN => {
+                    result.append_string("17");
+                    push_pairs(&mut i, 3, &mut result)?;
+                    result.append_string("10");
+                }
+                C_LATCH_A => set = CodeSet::A,
+                102..=105 => {
+                    for _ in 0..cw - C_SHIFT_B_BASE as u32 {
+                        push_set_b(next(&mut i)?, &mut result)?;
+                    }
+                }
+                C_LATCH_B => set = CodeSet::B,
+                _ => return Err(Exceptions::FORMAT),
+            },
+            CodeSet::B => match cw as u8 {
+                MACRO_05..=MACRO if position == 1 && codewords[0] == C_LATCH_B as u32 => {
+                    let (header, implied): (&[u8], &[u8]) = match cw as u8 {
+                        MACRO_05 => (b"[)>\x1E05\x1D", b"\x1E\x04"),
+                        MACRO_06 => (b"[)>\x1E06\x1D", b"\x1E\x04"),
+                        MACRO_12 => (b"[)>\x1E12\x1D", b"\x1E\x04"),
+                        _ => (b"[)>\x1E", b"\x04"),
+                    };
+                    result.append_bytes(header);
+                    trailer = implied;
+                }
+                0..=100 => push_set_b(cw, &mut result)?,
+                B_SHIFT_A => result.append_byte(set_a_char(next(&mut i)?)?),
+                B_LATCH_A => set = CodeSet::A,
+                103..=105 => push_pairs(&mut i, cw - SHIFT_C_BASE as u32, &mut result)?,
+                LATCH_C => set = CodeSet::C,
+                _ => return Err(Exceptions::FORMAT),
+            },
+            CodeSet::A => match cw as u8 {
+                0..=95 => result.append_byte(set_a_char(cw)?),
+                96..=101 => {
+                    for _ in 0..cw - A_SHIFT_B_BASE as u32 {
+                        push_set_b(next(&mut i)?, &mut result)?;
+                    }
+                }
+                A_LATCH_B => set = CodeSet::B,
+                103..=105 => push_pairs(&mut i, cw - SHIFT_C_BASE as u32, &mut result)?,
+                LATCH_C => set = CodeSet::C,
+                _ => return Err(Exceptions::FORMAT),
+            },
+            CodeSet::Binary => unreachable!(),
+        }
+    }
+
+    if set == CodeSet::Binary {
+        binary.flush(&mut result)?;
+    }
+    result.append_bytes(trailer);
+
+    Ok((result, gs1))
+}
+
+fn push_pair(value: u32, result: &mut ECIStringBuilder) -> Result<()> {
+    if value > 99 {
+        return Err(Exceptions::FORMAT);
+    }
+    result.append_byte(b'0' + (value / 10) as u8);
+    result.append_byte(b'0' + (value % 10) as u8);
+    Ok(())
+}
+
+fn set_a_char(value: u32) -> Result<u8> {
+    match value {
+        0..=63 => Ok(value as u8 + 32),
+        64..=95 => Ok(value as u8 - 64),
+        _ => Err(Exceptions::FORMAT),
+    }
+}
+
+fn push_set_b(value: u32, result: &mut ECIStringBuilder) -> Result<()> {
+    match value {
+        0..=95 => result.append_byte(value as u8 + 32),
+        96 => result.append_bytes(b"\r\n"),
+        97..=100 => result.append_byte(B_CONTROLS[(value - B_CONTROL_BASE as u32) as usize]),
+        _ => return Err(Exceptions::FORMAT),
+    }
+    Ok(())
+}
+
+/// Unpacks binary mode, where groups of up to 6 base 103 digits hold one value less of
+/// base 259. Values above 255 introduce an ECI whose bytes are the following values.
+#[derive(Default)]
+struct BinaryDecoder {
+    digits: Vec<u32>,
+    /// number of ECI bytes still expected and the value so far
+    eci: Option<(usize, u32)>,
+}
+
+impl BinaryDecoder {
+    fn push_digit(&mut self, digit: u32, result: &mut ECIStringBuilder) -> Result<()> {
+        self.digits.push(digit);
+        if self.digits.len() == 6 {
+            self.flush(result)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, result: &mut ECIStringBuilder) -> Result<()> {
+        if self.digits.is_empty() {
+            return Ok(());
+        }
+        if self.digits.len() == 1 {
+            return Err(Exceptions::FORMAT);
+        }
+        let count = self.digits.len() - 1;
+        let mut value = self
+            .digits
+            .iter()
+            .fold(0u64, |acc, &d| acc * BINARY_BASE as u64 + d as u64);
+        let mut values = vec![0u32; count];
+        for v in values.iter_mut().rev() {
+            *v = (value % BINARY_RADIX) as u32;
+            value /= BINARY_RADIX;
+        }
+        if value != 0 {
+            return Err(Exceptions::FORMAT);
+        }
+        self.digits.clear();
+
+        for v in values {
+            match self.eci {
+                Some((remaining, eci)) => {
+                    if v > 0xFF {
+                        return Err(Exceptions::FORMAT);
+                    }
+                    let eci = eci << 8 | v;
+                    if remaining == 1 {
+                        result.append_eci(Eci::from(eci));
+                        self.eci = None;
+                    } else {
+                        self.eci = Some((remaining - 1, eci));
+                    }
+                }
+                None if v > 0xFF => self.eci = Some((v as usize - 0xFF, 0)),
+                None => result.append_byte(v as u8),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "encoders"))]
+pub(crate) mod tests {
+    use super::*;
+    use crate::dotcode::encoder;
+
+    fn round_trip(bytes: &[u8], gs1: bool) {
+        let codewords = encoder::encode_high_level(bytes, None, gs1);
+        let (decoded, decoded_gs1) = decode_codewords(&codewords).unwrap();
+        assert_eq!(decoded.bytes(), bytes);
+        assert_eq!(decoded_gs1, gs1);
+    }
+
+    #[test]
+    fn high_level_round_trip() {
+        round_trip(b"1234567890", false);
+        round_trip(b"Hello World", false);
+        round_trip(b"abc12345678def\r\nXYZ", false);
+        round_trip(b"A1B2C3\x01\x02lower UPPER 99", false);
+        round_trip(b"123\tTAB\x1b[0m", false);
+        round_trip(
+            &[
+                0xC3, 0xA9, b'x', 0xFF, 0x80, 0x81, 0x82, 0x83, 0x84, 0x85, b'1', b'2',
+            ],
+            false,
+        );
+        round_trip(b"0112345678901231\x1d10ABC123", true);
+    }
+
+    #[test]
+    fn symbol_round_trip() {
+        let hints = crate::EncodeHints::default();
+        for contents in [
+            "1",
+            "DotCode",
+            "https://example.com/some/path?query=12345",
+            "Ünïcödé ✓",
+        ] {
+            let symbol = encoder::encode(contents, &hints).unwrap();
+            let result = decode(&symbol, &DecodeHints::default()).unwrap();
+            assert_eq!(result.getText(), contents);
+        }
+    }
+
+    #[test]
+    fn symbol_round_trip_with_errors() {
+        let hints = crate::EncodeHints::default();
+        let contents = "Error correction 0123456789";
+        let mut symbol = encoder::encode(contents, &hints).unwrap();
+        // flip a couple of dots belonging to different codewords
+        let positions = dot_positions(symbol.getWidth(), symbol.getHeight());
+        for &index in &[MASK_DOTS + 4, MASK_DOTS + 40] {
+            let (x, y) = positions[index];
+            symbol.flip_coords(x, y);
+        }
+        let result = decode(&symbol, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), contents);
+    }
+
+    #[test]
+    fn rotated_symbol() {
+        let mut symbol = encoder::encode("rotated", &crate::EncodeHints::default()).unwrap();
+        symbol.rotate90();
+        let result = decode(&symbol, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "rotated");
+    }
+
+    /// "Hello World"
+    const ZINT_HELLO_WORLD: &str = "
+        100010100010100010001000
+        010000010100000101000101
+        101010000010100000001010
+        010100000101000001010100
+        101000101010100000100000
+        010100000001000100010000
+        001010101000001000100010
+        010001000101000000010101
+        100000100010101000001010
+        000100000101010001000100
+        100000001010001010000010
+        010100010100000100010101
+        001000100010001000101000
+        000101010100010101010000
+        001010100000001000001000
+        000100000101010001000101
+        101000100000101010100010
+    ";
+
+    /// "ABCDEFGH"
+    const ZINT_ABCDEFGH: &str = "
+        100010001000000000101
+        010001000100010001010
+        101010100010100000101
+        010100000100000001010
+        001010100000101010000
+        010100010000010100010
+        100000001000000010101
+        010001010001000100000
+        000010100000101000100
+        000101010101010000010
+        001010100010100000001
+        000000000101010100000
+        101000101010001010000
+        000000010101010101010
+    ";
+
+    /// "1234567890"
+    const ZINT_NUMERIC: &str = "
+        1000101000100010001
+        0100000101010001010
+        0000100010001000001
+        0101010101010100000
+        1010001010000010000
+        0100000001000100010
+        1000001000100000101
+        0101000001010101000
+        0000100000101010101
+        0001010000010101000
+        1010101000000000100
+        0100010100010001010
+    ";
+
+    /// "abc"
+    const ZINT_ABC: &str = "
+        1010000010101000
+        0100010100010001
+        1010001000100010
+        0101010100010000
+        1010000000100010
+        0000000001000101
+        1000100010001010
+        0001010101000001
+        1010000000101000
+        0100000001010001
+        1010100010101010
+    ";
+
+    /// "Test 123"
+    const ZINT_TEST_123: &str = "
+        100000101000001010101
+        010100010100000101000
+        101000100000101010101
+        010001010101000001010
+        101010001010100000100
+        010001010001010000000
+        100010001000000000001
+        010100000100010001010
+        000010100010101010000
+        000100000001010100000
+        001000001000100010100
+        000101000101000101000
+        101010100010000000101
+        000000010100010100010
+    ";
+
+    /// "[)>\x1E06\x1D1P12345\x1E\x04", with the header and trailer sent as a macro
+    const ZINT_MACRO_06: &str = "
+        100010101010000010000
+        010001010101010100010
+        101010001010100000001
+        010101010000000101000
+        100000001000001000100
+        010100000100010101010
+        101000000010101010001
+        010000000101000001000
+        000000101010101000101
+        000101000000000001000
+        001000101000101010100
+        000101010101000101010
+        001010000000000010001
+        010001000001010000010
+    ";
+
+    /// "A"
+    const ZINT_A: &str = "
+        10001000101010
+        01010001010001
+        10001000001010
+        01000101000100
+        00001010000010
+        01000001000101
+        10001010000010
+        00000001010101
+        10101000101010
+    ";
+
+    /// "[01]09501101020917[17]190508[10]ABCD1234[21]10" in GS1 mode
+    const ZINT_GS1: &str = "
+        00101010000010000010101000000010
+        01000100010100000000010101000101
+        10100010101010001010000010000010
+        00010001010000010000010100000001
+        00001000001010101000001010001010
+        00000101000101000001010001010001
+        10101000100010001010100010000010
+        00010000010100000100010001000100
+        10100010101010000010001010001010
+        01000101000101000001000100000001
+        10001010101000101000100000001010
+        01010000010101000000010001010000
+        10000010000000101000101010000010
+        01000001000101000101000101010001
+        10000010101000100010100010000010
+        01010100010001000001010001010000
+        00101010001000101010000010001000
+        01000100000101000100010000010001
+        10001000100000101000101000101000
+        00010001010001010001000001010001
+        10001000100010001000101000101010
+    ";
+
+    pub(crate) fn parse(symbol: &str) -> BitMatrix {
+        let rows: Vec<&str> = symbol.split_whitespace().collect();
+        BitMatrix::parse_strings(&rows.join("\n"), "1", "0").unwrap()
+    }
+
+    /// Symbols generated by zint and their contents
+    pub(crate) const ZINT_VECTORS: [(&str, &str); 7] = [
+        (ZINT_HELLO_WORLD, "Hello World"),
+        (ZINT_ABCDEFGH, "ABCDEFGH"),
+        (ZINT_NUMERIC, "1234567890"),
+        (ZINT_ABC, "abc"),
+        (ZINT_TEST_123, "Test 123"),
+        (ZINT_MACRO_06, "[)>\u{1e}06\u{1d}1P12345\u{1e}\u{4}"),
+        (ZINT_A, "A"),
+    ];
+
+    #[test]
+    fn decodes_zint_symbols() {
+        for (symbol, contents) in ZINT_VECTORS {
+            let result = decode(&parse(symbol), &DecodeHints::default()).unwrap();
+            assert_eq!(result.getText(), contents);
+            assert_eq!(result.getSymbologyModifier(), 0, "{contents}");
+        }
+    }
+
+    #[test]
+    fn encodes_like_zint() {
+        let hints = crate::EncodeHints::default();
+        for (symbol, contents) in ZINT_VECTORS {
+            let encoded = encoder::encode(contents, &hints).unwrap();
+            assert_eq!(encoded, parse(symbol), "{contents}");
+        }
+        // "12345" gives a 17x12 symbol
+        let encoded = encoder::encode("12345", &hints).unwrap();
+        assert_eq!((encoded.getWidth(), encoded.getHeight()), (17, 12));
+    }
+
+    #[test]
+    fn gs1_like_zint() {
+        let contents = "01095011010209171719050810ABCD1234\u{1d}2110";
+        let result = decode(&parse(ZINT_GS1), &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), contents);
+        assert_eq!(result.getSymbologyModifier(), 1);
+
+        let hints = crate::EncodeHints::default().with(crate::EncodeHintValue::Gs1Format(true));
+        assert_eq!(encoder::encode(contents, &hints).unwrap(), parse(ZINT_GS1));
+    }
+}
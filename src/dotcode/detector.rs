@@ -0,0 +1,333 @@
+//! Locates a DotCode symbol in a binarized image.
+//!
+//! DotCode has no finder pattern, so the symbol is found from its dots alone:
+//!
+//! 1. collect the 4-connected components of the image and keep those with a dot like size
+//! 2. measure the distance from every dot to its nearest neighbour, which for DotCode is the
+//!    diagonal pitch of the grid
+//! 3. group dots lying within two diagonal pitches of each other and keep the largest group,
+//!    so that isolated specks around the symbol are ignored
+//! 4. estimate the grid rotation from the nearest neighbour directions and the module pitch
+//!    from their lengths
+//! 5. sample the grid through [`GridSampler`] using the extent of the group, which is always
+//!    bounded by the printed corner dots
+//!
+//! The sampled grid is only known up to a multiple of 90 degrees, the decoder resolves
+//! the final orientation.
+
+use std::collections::HashMap;
+
+use crate::{
+    Exceptions, Point,
+    common::{
+        BitMatrix, DefaultGridSampler, DetectorRXingResult, GridSampler, Quadrilateral, Result,
+    },
+    point,
+};
+
+use super::dotcode_common::{MAX_DIMENSION, MIN_DIMENSION};
+
+/// Minimum number of dots needed before attempting to build a grid
+const MIN_DOTS: usize = 12;
+
+/// Blobs larger than this fraction of the image are not considered to be dots
+const MAX_DOT_FRACTION: f64 = 0.02;
+
+/// Dots only sit on one checkerboard parity, so the other parity must be almost empty
+const MAX_OFF_LATTICE_RATIO: f64 = 0.1;
+
+pub struct DotCodeDetectorResult(BitMatrix, Vec<Point>);
+
+impl DotCodeDetectorResult {
+    pub fn new(bits: BitMatrix, points: Vec<Point>) -> Self {
+        Self(bits, points)
+    }
+}
+
+impl DetectorRXingResult for DotCodeDetectorResult {
+    fn getBits(&self) -> &BitMatrix {
+        &self.0
+    }
+
+    fn getPoints(&self) -> &[Point] {
+        &self.1
+    }
+}
+
+/// Check that the set modules of `bits` fall on a single checkerboard parity. Other matrix
+/// symbologies have adjacent dark modules, which DotCode never does.
+fn is_checkerboard(bits: &BitMatrix) -> bool {
+    let mut counts = [0usize; 2];
+    for y in 0..bits.getHeight() {
+        for x in 0..bits.getWidth() {
+            if bits.get(x, y) {
+                counts[((x + y) % 2) as usize] += 1;
+            }
+        }
+    }
+    let (on, off) = (counts[0].max(counts[1]), counts[0].min(counts[1]));
+    on > 0 && off as f64 <= on as f64 * MAX_OFF_LATTICE_RATIO
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Blob {
+    x: f64,
+    y: f64,
+    area: usize,
+}
+
+/// Find and sample a DotCode symbol in `image`
+pub fn detect(image: &BitMatrix) -> Result<DotCodeDetectorResult> {
+    let blobs = find_blobs(image);
+    let dots = filter_by_area(blobs);
+    if dots.len() < MIN_DOTS {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let cell = (median(dots.iter().map(|b| b.area as f64).collect()).sqrt() * 4.0).max(2.0);
+    let buckets = bucket(&dots, cell);
+    let neighbours: Vec<Option<(usize, f64)>> = (0..dots.len())
+        .map(|i| nearest_neighbour(&dots, &buckets, cell, i))
+        .collect();
+
+    let diagonal = median(neighbours.iter().flatten().map(|&(_, d)| d).collect());
+    if diagonal <= 0.0 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let symbol = largest_group(&dots, &buckets, cell, diagonal * 2.0);
+    if symbol.len() < MIN_DOTS {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    // The nearest neighbours of a dot are its diagonal neighbours, at 45 degrees from the
+    // grid axes. Averaging 4 * angle removes the ambiguity between the four directions.
+    let (mut sum_cos, mut sum_sin) = (0.0, 0.0);
+    for &i in &symbol {
+        if let Some((j, d)) = neighbours[i] {
+            if d <= diagonal * 1.25 {
+                let angle = (dots[j].y - dots[i].y).atan2(dots[j].x - dots[i].x);
+                sum_cos += (4.0 * angle).cos();
+                sum_sin += (4.0 * angle).sin();
+            }
+        }
+    }
+    let rotation = (sum_sin.atan2(sum_cos) - std::f64::consts::PI) / 4.0;
+    let (sin, cos) = rotation.sin_cos();
+
+    let rotated: Vec<(f64, f64)> = symbol
+        .iter()
+        .map(|&i| {
+            let Blob { x, y, .. } = dots[i];
+            (x * cos + y * sin, -x * sin + y * cos)
+        })
+        .collect();
+
+    let pitch = diagonal / std::f64::consts::SQRT_2;
+    let (u_min, u_max, width) = axis_extent(rotated.iter().map(|p| p.0), pitch)?;
+    let (v_min, v_max, height) = axis_extent(rotated.iter().map(|p| p.1), pitch)?;
+    if (width + height) % 2 == 0 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let to_image = |u: f64, v: f64| point((u * cos - v * sin) as f32, (u * sin + v * cos) as f32);
+    let top_left = to_image(u_min, v_min);
+    let top_right = to_image(u_max, v_min);
+    let bottom_right = to_image(u_max, v_max);
+    let bottom_left = to_image(u_min, v_max);
+
+    let dst = Quadrilateral::new(
+        point(0.5, 0.5),
+        point(width as f32 - 0.5, 0.5),
+        point(width as f32 - 0.5, height as f32 - 0.5),
+        point(0.5, height as f32 - 0.5),
+    );
+    let src = Quadrilateral::new(top_left, top_right, bottom_right, bottom_left);
+    let (bits, _) = DefaultGridSampler.sample_grid_detailed(image, width, height, dst, src)?;
+    if !is_checkerboard(&bits) {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    Ok(DotCodeDetectorResult::new(
+        bits,
+        vec![top_left, top_right, bottom_right, bottom_left],
+    ))
+}
+
+/// Returns the minimum and maximum coordinate along one axis and the number of modules
+/// it spans, refining the pitch with a least squares fit of the module indices.
+fn axis_extent(values: impl Iterator<Item = f64>, pitch: f64) -> Result<(f64, f64, u32)> {
+    let values: Vec<f64> = values.collect();
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let (mut numerator, mut denominator) = (0.0, 0.0);
+    for v in &values {
+        let offset = v - min;
+        let index = (offset / pitch).round();
+        numerator += offset * index;
+        denominator += index * index;
+    }
+    let pitch = if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        pitch
+    };
+
+    let modules = ((max - min) / pitch).round() as i64 + 1;
+    if modules < MIN_DIMENSION as i64 || modules > MAX_DIMENSION as i64 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    Ok((min, max, modules as u32))
+}
+
+fn find_blobs(image: &BitMatrix) -> Vec<Blob> {
+    let width = image.getWidth();
+    let height = image.getHeight();
+    let max_area = ((width as f64 * height as f64) * MAX_DOT_FRACTION).max(4.0) as usize;
+    let mut visited = vec![false; (width * height) as usize];
+    let mut blobs = Vec::new();
+    let mut stack = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let index = (y * width + x) as usize;
+            if visited[index] || !image.get(x, y) {
+                continue;
+            }
+            visited[index] = true;
+            stack.push((x, y));
+            let (mut sum_x, mut sum_y, mut area) = (0u64, 0u64, 0usize);
+            while let Some((px, py)) = stack.pop() {
+                sum_x += px as u64;
+                sum_y += py as u64;
+                area += 1;
+                let neighbours = [
+                    (px.wrapping_sub(1), py),
+                    (px + 1, py),
+                    (px, py.wrapping_sub(1)),
+                    (px, py + 1),
+                ];
+                for (nx, ny) in neighbours {
+                    if nx >= width || ny >= height {
+                        continue;
+                    }
+                    let n_index = (ny * width + nx) as usize;
+                    if !visited[n_index] && image.get(nx, ny) {
+                        visited[n_index] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            if area <= max_area {
+                blobs.push(Blob {
+                    x: sum_x as f64 / area as f64 + 0.5,
+                    y: sum_y as f64 / area as f64 + 0.5,
+                    area,
+                });
+            }
+        }
+    }
+    blobs
+}
+
+fn filter_by_area(blobs: Vec<Blob>) -> Vec<Blob> {
+    if blobs.is_empty() {
+        return blobs;
+    }
+    let typical = median(blobs.iter().map(|b| b.area as f64).collect());
+    blobs
+        .into_iter()
+        .filter(|b| {
+            let area = b.area as f64;
+            area >= typical / 4.0 && area <= typical * 4.0
+        })
+        .collect()
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    values[values.len() / 2]
+}
+
+fn bucket_key(blob: &Blob, cell: f64) -> (i64, i64) {
+    ((blob.x / cell) as i64, (blob.y / cell) as i64)
+}
+
+fn bucket(dots: &[Blob], cell: f64) -> HashMap<(i64, i64), Vec<usize>> {
+    let mut buckets: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, dot) in dots.iter().enumerate() {
+        buckets.entry(bucket_key(dot, cell)).or_default().push(i);
+    }
+    buckets
+}
+
+/// Indices of dots in the buckets around `dots[index]`
+fn nearby<'a>(
+    dots: &'a [Blob],
+    buckets: &'a HashMap<(i64, i64), Vec<usize>>,
+    cell: f64,
+    index: usize,
+) -> impl Iterator<Item = usize> + 'a {
+    let (bx, by) = bucket_key(&dots[index], cell);
+    (-1..=1)
+        .flat_map(move |dy| (-1..=1).map(move |dx| (bx + dx, by + dy)))
+        .filter_map(|key| buckets.get(&key))
+        .flatten()
+        .copied()
+        .filter(move |&j| j != index)
+}
+
+fn distance(a: &Blob, b: &Blob) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn nearest_neighbour(
+    dots: &[Blob],
+    buckets: &HashMap<(i64, i64), Vec<usize>>,
+    cell: f64,
+    index: usize,
+) -> Option<(usize, f64)> {
+    nearby(dots, buckets, cell, index)
+        .map(|j| (j, distance(&dots[index], &dots[j])))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Group dots closer than `limit` to each other and return the largest group
+fn largest_group(
+    dots: &[Blob],
+    buckets: &HashMap<(i64, i64), Vec<usize>>,
+    cell: f64,
+    limit: f64,
+) -> Vec<usize> {
+    // neighbours further away than the bucket size can not be found
+    let limit = limit.min(cell);
+    let mut group = vec![usize::MAX; dots.len()];
+    let mut best: Vec<usize> = Vec::new();
+
+    for start in 0..dots.len() {
+        if group[start] != usize::MAX {
+            continue;
+        }
+        group[start] = start;
+        let mut members = vec![start];
+        let mut cursor = 0;
+        while cursor < members.len() {
+            let i = members[cursor];
+            cursor += 1;
+            for j in nearby(dots, buckets, cell, i) {
+                if group[j] == usize::MAX && distance(&dots[i], &dots[j]) <= limit {
+                    group[j] = start;
+                    members.push(j);
+                }
+            }
+        }
+        if members.len() > best.len() {
+            best = members;
+        }
+    }
+    best
+}
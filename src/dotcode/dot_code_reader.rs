@@ -0,0 +1,193 @@
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, ImmutableReader, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    common::{DetectorRXingResult, Result},
+};
+
+use super::{decoder, detector};
+
+/// Locates and decodes a DotCode symbol in an image.
+///
+/// Only the dots themselves are used to find the symbol, so the image should not contain
+/// other dotted patterns of a similar size close to the code.
+#[derive(Default, Clone)]
+pub struct DotCodeReader;
+
+impl Reader for DotCodeReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl ImmutableReader for DotCodeReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl DotCodeReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let detector_result = detector::detect(image.get_black_matrix())?;
+        let decoder_result = decoder::decode(detector_result.getBits(), hints)?;
+
+        let mut result = RXingResult::new(
+            decoder_result.getText(),
+            decoder_result.getRawBytes().clone(),
+            detector_result.getPoints().to_vec(),
+            BarcodeFormat::DOTCODE,
+        );
+
+        let byte_segments = decoder_result.getByteSegments();
+        if !byte_segments.is_empty() {
+            result.putMetadata(
+                RXingResultMetadataType::BYTE_SEGMENTS,
+                RXingResultMetadataValue::ByteSegments(byte_segments.clone()),
+            );
+        }
+        result.putMetadata(
+            RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+            RXingResultMetadataValue::SymbologyIdentifier(format!(
+                "]J{}",
+                decoder_result.getSymbologyModifier()
+            )),
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, EncodeHintValue, EncodeHints,
+        Reader, Writer, common::HybridBinarizer,
+    };
+
+    use super::DotCodeReader;
+    use crate::{
+        common::BitMatrix,
+        dotcode::{
+            DotCodeWriter,
+            decoder::tests::{ZINT_VECTORS, parse},
+        },
+    };
+
+    fn render(contents: &str, size: i32) -> crate::common::BitMatrix {
+        DotCodeWriter
+            .encode(contents, &BarcodeFormat::DOTCODE, size, size)
+            .unwrap()
+    }
+
+    fn read(matrix: &crate::common::BitMatrix) -> crate::common::Result<crate::RXingResult> {
+        let image: image::DynamicImage = matrix.into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        DotCodeReader.decode(&mut bitmap)
+    }
+
+    #[test]
+    fn round_trip_small_modules() {
+        let matrix = render("DotCode 12345", 0);
+        let result = read(&matrix).unwrap();
+        assert_eq!(result.getText(), "DotCode 12345");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::DOTCODE);
+    }
+
+    #[test]
+    fn round_trip_round_dots() {
+        let contents = "https://github.com/rxing-core/rxing";
+        let result = read(&render(contents, 400)).unwrap();
+        assert_eq!(result.getText(), contents);
+    }
+
+    #[test]
+    fn round_trip_gs1() {
+        let hints = EncodeHints::default().with(EncodeHintValue::Gs1Format(true));
+        let matrix = DotCodeWriter
+            .encode_with_hints(
+                "01095011010209171719050810ABCD1234\u{1d}2110",
+                &BarcodeFormat::DOTCODE,
+                300,
+                300,
+                &hints,
+            )
+            .unwrap();
+        let result = read(&matrix).unwrap();
+        assert_eq!(
+            result.getText(),
+            "01095011010209171719050810ABCD1234\u{1d}2110"
+        );
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&crate::RXingResultMetadataType::SYMBOLOGY_IDENTIFIER),
+            Some(&crate::RXingResultMetadataValue::SymbologyIdentifier(
+                "]J1".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn reads_zint_symbols() {
+        for (symbol, contents) in ZINT_VECTORS {
+            let symbol = parse(symbol);
+            let (scale, quiet_zone) = (6, 3);
+            let mut matrix = BitMatrix::new(
+                (symbol.getWidth() + 2 * quiet_zone) * scale,
+                (symbol.getHeight() + 2 * quiet_zone) * scale,
+            )
+            .unwrap();
+            for y in 0..symbol.getHeight() {
+                for x in 0..symbol.getWidth() {
+                    if symbol.get(x, y) {
+                        matrix
+                            .setRegion(
+                                (x + quiet_zone) * scale,
+                                (y + quiet_zone) * scale,
+                                scale,
+                                scale,
+                            )
+                            .unwrap();
+                    }
+                }
+            }
+            assert_eq!(read(&matrix).unwrap().getText(), contents);
+        }
+    }
+
+    #[test]
+    fn ignores_isolated_dots() {
+        let mut matrix = render("isolated", 300);
+        // dot sized specks around the symbol
+        for (x, y) in [(1, 1), (290, 2), (3, 290), (290, 290)] {
+            matrix.setRegion(x, y, 8, 8).unwrap();
+        }
+        let result = read(&matrix).unwrap();
+        assert_eq!(result.getText(), "isolated");
+    }
+
+    #[test]
+    fn rotated_symbol() {
+        let mut matrix = render("turned around", 250);
+        matrix.rotate90();
+        let result = read(&matrix).unwrap();
+        assert_eq!(result.getText(), "turned around");
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    common::{BitMatrix, Result},
+};
+
+use super::encoder;
+
+/// Default quiet zone around the symbol, in modules
+const QUIET_ZONE_SIZE: u32 = 3;
+
+/// Dots are drawn as disks once each module is at least this many pixels wide, smaller
+/// renderings fill the whole module.
+const MIN_ROUND_DOT_SCALE: u32 = 3;
+
+/// Renders a DotCode symbol as a [`BitMatrix`].
+///
+/// The [`EncodeHints::Margin`] hint sets the quiet zone in modules, [`EncodeHints::CharacterSet`]
+/// selects the text encoding, announced with an ECI, and [`EncodeHints::Gs1Format`] marks the
+/// data as GS1, with group separators (ASCII 29) delimiting variable length fields. Without a
+/// character set, text that is not ISO-8859-1 is sent as UTF-8.
+#[derive(Default)]
+pub struct DotCodeWriter;
+
+impl Writer for DotCodeWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        if contents.is_empty() {
+            return Err(Exceptions::illegal_argument_with("Found empty contents"));
+        }
+
+        if format != &BarcodeFormat::DOTCODE {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Can only encode DOTCODE, but got {format:?}"
+            )));
+        }
+
+        if width < 0 || height < 0 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Requested dimensions can't be negative: {width}x{height}"
+            )));
+        }
+
+        let quiet_zone = if let Some(margin) = &hints.Margin {
+            margin
+                .parse::<u32>()
+                .map_err(|e| Exceptions::parse_with(format!("could not parse {margin}: {e}")))?
+        } else {
+            QUIET_ZONE_SIZE
+        };
+
+        let symbol = encoder::encode(contents, hints)?;
+        Self::render(&symbol, width as u32, height as u32, quiet_zone)
+    }
+}
+
+impl DotCodeWriter {
+    fn render(symbol: &BitMatrix, width: u32, height: u32, quiet_zone: u32) -> Result<BitMatrix> {
+        let input_width = symbol.getWidth() + 2 * quiet_zone;
+        let input_height = symbol.getHeight() + 2 * quiet_zone;
+        let output_width = width.max(input_width);
+        let output_height = height.max(input_height);
+
+        let scale = (output_width / input_width).min(output_height / input_height);
+        let left = (output_width - symbol.getWidth() * scale) / 2;
+        let top = (output_height - symbol.getHeight() * scale) / 2;
+
+        let mut output = BitMatrix::new(output_width, output_height)?;
+        for y in 0..symbol.getHeight() {
+            for x in 0..symbol.getWidth() {
+                if !symbol.get(x, y) {
+                    continue;
+                }
+                let dot_left = left + x * scale;
+                let dot_top = top + y * scale;
+                if scale < MIN_ROUND_DOT_SCALE {
+                    output.setRegion(dot_left, dot_top, scale, scale)?;
+                    continue;
+                }
+                // disk with a diameter of 80% of the module
+                let center = scale as f32 / 2.0;
+                let radius = scale as f32 * 0.4;
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let fx = dx as f32 + 0.5 - center;
+                        let fy = dy as f32 + 0.5 - center;
+                        if fx * fx + fy * fy <= radius * radius {
+                            output.set(dot_left + dx, dot_top + dy);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
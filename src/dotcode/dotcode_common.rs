@@ -0,0 +1,266 @@
+//! Layout information shared by the DotCode reader and writer.
+//!
+//! A DotCode symbol is a `width` x `height` grid where only the positions with an even
+//! `x + y` can hold a dot, and `width + height` is always odd. The dot stream is made of a
+//! 2 dot mask indicator followed by one 9 dot pattern per codeword. It is folded into the
+//! grid row by row (odd heights) or column by column (even heights), with the six corner
+//! positions filled last, so they may carry the end of the stream. Dots left over after
+//! the last codeword are printed.
+
+/// Codewords are elements of GF(113)
+pub const GF: u32 = 113;
+
+/// Number of dots used to store the mask indicator at the start of the dot stream
+pub const MASK_DOTS: usize = 2;
+
+/// Number of dots used to represent each codeword
+pub const CODEWORD_DOTS: usize = 9;
+
+/// Smallest allowed dimension in either direction
+pub const MIN_DIMENSION: u32 = 5;
+
+/// Largest dimension this implementation will generate or accept
+pub const MAX_DIMENSION: u32 = 200;
+
+/// The increment added to each successive codeword for masks 0 through 3
+pub const MASK_WEIGHTS: [u32; 4] = [0, 3, 7, 17];
+
+/// Nine dot patterns used for each codeword value, every pattern has five dots set.
+pub const DOT_PATTERNS: [u16; 113] = [
+    0x155, 0x0ab, 0x0ad, 0x0b5, 0x0d5, 0x156, 0x15a, 0x16a, 0x1aa, 0x0ae, // 0-9
+    0x0b6, 0x0ba, 0x0d6, 0x0da, 0x0ea, 0x12b, 0x12d, 0x135, 0x14b, 0x14d, // 10-19
+    0x153, 0x159, 0x165, 0x169, 0x195, 0x1a5, 0x1a9, 0x057, 0x05b, 0x05d, // 20-29
+    0x06b, 0x06d, 0x075, 0x097, 0x09b, 0x09d, 0x0a7, 0x0b3, 0x0b9, 0x0cb, // 30-39
+    0x0cd, 0x0d3, 0x0d9, 0x0e5, 0x0e9, 0x12e, 0x136, 0x13a, 0x14e, 0x15c, // 40-49
+    0x166, 0x16c, 0x172, 0x174, 0x196, 0x19a, 0x1a6, 0x1ac, 0x1b2, 0x1b4, // 50-59
+    0x1ca, 0x1d2, 0x1d4, 0x05e, 0x06e, 0x076, 0x07a, 0x09e, 0x0bc, 0x0ce, // 60-69
+    0x0dc, 0x0e6, 0x0ec, 0x0f2, 0x0f4, 0x117, 0x11b, 0x11d, 0x127, 0x133, // 70-79
+    0x139, 0x147, 0x163, 0x171, 0x18b, 0x18d, 0x193, 0x199, 0x1a3, 0x1b1, // 80-89
+    0x1c5, 0x1c9, 0x1d1, 0x02f, 0x037, 0x03b, 0x03d, 0x04f, 0x067, 0x073, // 90-99
+    0x079, 0x08f, 0x0c7, 0x0e3, 0x0f1, 0x11e, 0x13c, 0x178, 0x18e, 0x19c, // 100-109
+    0x1b8, 0x1c6, 0x1cc, // 110-112
+];
+
+/// Codeword values with a meaning in one or more code sets
+pub mod codewords {
+    /// Code Set C: "17" followed by three digit pairs and "10"
+    pub const C_SEVENTEEN_TEN: u8 = 100;
+    /// Code Set C: latch to Code Set A
+    pub const C_LATCH_A: u8 = 101;
+    /// Code Set C: shift the next 1 to 4 characters to Code Set B (102 - 105)
+    pub const C_SHIFT_B_BASE: u8 = 101;
+    /// Code Set C: latch to Code Set B
+    pub const C_LATCH_B: u8 = 106;
+
+    /// Code Sets A and B: shift the next 2 to 4 digit pairs to Code Set C (103 - 105)
+    pub const SHIFT_C_BASE: u8 = 101;
+    /// Code Sets A and B: latch to Code Set C
+    pub const LATCH_C: u8 = 106;
+
+    /// Code Set B: carriage return followed by line feed
+    pub const B_CRLF: u8 = 96;
+    /// Code Set B: the control characters HT, FS, GS and RS (97 - 100)
+    pub const B_CONTROLS: [u8; 4] = [0x09, 0x1C, 0x1D, 0x1E];
+    /// Code Set B: first value of [`B_CONTROLS`]
+    pub const B_CONTROL_BASE: u8 = 97;
+    /// Code Set B: shift the next character to Code Set A
+    pub const B_SHIFT_A: u8 = 101;
+    /// Code Set B: latch to Code Set A
+    pub const B_LATCH_A: u8 = 102;
+
+    /// Code Set A: shift the next 1 to 6 characters to Code Set B (96 - 101)
+    pub const A_SHIFT_B_BASE: u8 = 95;
+    /// Code Set A: latch to Code Set B
+    pub const A_LATCH_B: u8 = 102;
+
+    /// Code Sets A, B and C: function character 1
+    pub const FNC1: u8 = 107;
+    /// Code Sets A, B and C: function character 2, introducing an ECI
+    pub const FNC2: u8 = 108;
+    /// Code Sets A, B and C: function character 3, reader initialisation
+    pub const FNC3: u8 = 109;
+    /// Code Sets A, B and C: the next character is `value + 128` in Code Set A
+    pub const UPPER_SHIFT_A: u8 = 110;
+    /// Code Sets A, B and C: the next character is `value + 128` in Code Set B
+    pub const UPPER_SHIFT_B: u8 = 111;
+    /// Code Sets A, B and C: latch to binary mode
+    pub const BINARY_LATCH: u8 = 112;
+
+    /// Code Set B after an initial latch: the `[)>RS05GS` header and `RS EOT` trailer
+    pub const MACRO_05: u8 = 97;
+    /// Code Set B after an initial latch: the `[)>RS06GS` header and `RS EOT` trailer
+    pub const MACRO_06: u8 = 98;
+    /// Code Set B after an initial latch: the `[)>RS12GS` header and `RS EOT` trailer
+    pub const MACRO_12: u8 = 99;
+    /// Code Set B after an initial latch: the `[)>RS` header, followed by the two digit
+    /// format, and the `EOT` trailer
+    pub const MACRO: u8 = 100;
+
+    /// Binary mode: codewords below this value are base 103 digits
+    pub const BINARY_BASE: u8 = 103;
+    /// Binary mode: radix of the values packed into the base 103 digits. Values above 255
+    /// introduce an ECI of 1, 2 or 3 bytes.
+    pub const BINARY_RADIX: u64 = 259;
+    /// Binary mode: shift the next 2 to 7 digit pairs to Code Set C (103 - 108)
+    pub const BINARY_SHIFT_C_BASE: u8 = 101;
+    /// Binary mode: terminate and latch to Code Set A
+    pub const BINARY_EXIT_A: u8 = 109;
+    /// Binary mode: terminate and latch to Code Set B
+    pub const BINARY_EXIT_B: u8 = 110;
+    /// Binary mode: terminate and latch to Code Set C
+    pub const BINARY_EXIT_C: u8 = 111;
+
+    /// Padding added after the data. It is a latch in every code set so it never
+    /// changes the decoded content.
+    pub const PAD: u8 = 106;
+}
+
+/// Number of dot positions in a symbol
+pub fn dot_count(width: u32, height: u32) -> usize {
+    (width * height / 2) as usize
+}
+
+/// Number of dots needed for `data_codewords` data codewords (excluding the mask
+/// indicator) and their error correction.
+pub fn required_dots(data_codewords: usize) -> usize {
+    CODEWORD_DOTS * (data_codewords + ecc_codewords(data_codewords)) + MASK_DOTS
+}
+
+/// Number of error correction codewords for a given number of data codewords
+pub fn ecc_codewords(data_codewords: usize) -> usize {
+    3 + data_codewords / 2
+}
+
+/// The number of data codewords (excluding the mask indicator) in a symbol with `dots`
+/// dot positions, or `None` if the symbol is too small to hold anything. Symbols are
+/// always padded with as many codewords as fit, so this is also the number of data
+/// codewords a reader finds.
+pub fn data_capacity(dots: usize) -> Option<usize> {
+    if required_dots(1) > dots {
+        return None;
+    }
+    let mut data = 1;
+    while required_dots(data + 1) <= dots {
+        data += 1;
+    }
+    Some(data)
+}
+
+fn is_corner(x: u32, y: u32, width: u32, height: u32) -> bool {
+    // top left
+    if x == 0 && y == 0 {
+        return true;
+    }
+    if height % 2 == 1 {
+        // top right and bottom left for odd heights
+        if (x == width - 2 && y == 0) || (x == width - 1 && y == 1) {
+            return true;
+        }
+        if x == 0 && y == height - 1 {
+            return true;
+        }
+    } else {
+        // top right and bottom left for even heights
+        if x == width - 1 && y == 0 {
+            return true;
+        }
+        if (x == 0 && y == height - 2) || (x == 1 && y == height - 1) {
+            return true;
+        }
+    }
+    // bottom right
+    (x == width - 2 && y == height - 1) || (x == width - 1 && y == height - 2)
+}
+
+/// The grid position of every dot in dot stream order
+pub fn dot_positions(width: u32, height: u32) -> Vec<(u32, u32)> {
+    let mut positions = Vec::with_capacity(dot_count(width, height));
+    if height % 2 == 1 {
+        // Horizontal folding, from the bottom row up
+        for y in (0..height).rev() {
+            for x in 0..width {
+                if (x + y) % 2 == 0 && !is_corner(x, y, width, height) {
+                    positions.push((x, y));
+                }
+            }
+        }
+        positions.extend_from_slice(&[
+            (width - 2, 0),
+            (width - 2, height - 1),
+            (width - 1, 1),
+            (width - 1, height - 2),
+            (0, 0),
+            (0, height - 1),
+        ]);
+    } else {
+        // Vertical folding, from the leftmost column
+        for x in 0..width {
+            for y in 0..height {
+                if (x + y) % 2 == 0 && !is_corner(x, y, width, height) {
+                    positions.push((x, y));
+                }
+            }
+        }
+        positions.extend_from_slice(&[
+            (width - 1, height - 2),
+            (0, height - 2),
+            (width - 2, height - 1),
+            (1, height - 1),
+            (width - 1, 0),
+            (0, 0),
+        ]);
+    }
+    positions
+}
+
+/// Apply (`unmask == false`) or remove (`unmask == true`) one of the four data masks.
+/// `codewords` starts with the mask indicator, which is left untouched.
+pub fn apply_mask(codewords: &mut [u32], mask: u32, unmask: bool) {
+    let step = MASK_WEIGHTS[mask as usize];
+    let mut weight = 0;
+    for cw in codewords.iter_mut().skip(1) {
+        *cw = if unmask {
+            (*cw + GF - weight) % GF
+        } else {
+            (*cw + weight) % GF
+        };
+        weight = (weight + step) % GF;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_patterns_are_distinct_five_of_nine() {
+        let mut seen = std::collections::HashSet::new();
+        for pattern in DOT_PATTERNS {
+            assert_eq!(pattern.count_ones(), 5, "{pattern:#x}");
+            assert!(pattern < 0x200);
+            assert!(seen.insert(pattern));
+        }
+    }
+
+    #[test]
+    fn dot_positions_cover_grid() {
+        for (width, height) in [(5, 6), (6, 5), (13, 8), (10, 21)] {
+            let positions = dot_positions(width, height);
+            assert_eq!(positions.len(), dot_count(width, height));
+            let unique: std::collections::HashSet<_> = positions.iter().collect();
+            assert_eq!(unique.len(), positions.len());
+            assert!(positions.iter().all(|(x, y)| (x + y) % 2 == 0));
+        }
+    }
+
+    #[test]
+    fn capacity_matches_requirements() {
+        assert_eq!(data_capacity(10), None);
+        let dots = dot_count(20, 13);
+        let data = data_capacity(dots).unwrap();
+        assert!(required_dots(data) <= dots);
+        assert!(required_dots(data + 1) > dots);
+        // "12345" takes 5 data codewords and a 17x12 symbol
+        assert_eq!(data_capacity(dot_count(17, 12)), Some(5));
+    }
+}
@@ -0,0 +1,839 @@
+//! DotCode symbol generation: high level encoding into codewords, error correction,
+//! masking and placement of the dot stream into the symbol grid.
+//!
+//! The code set selection follows Annex F of ISS DotCode Rev. 4.0 and, like the mask
+//! selection and symbol sizing, gives the same symbols as zint.
+
+use crate::{
+    EncodeHints, Exceptions,
+    common::{BitMatrix, CharacterSet, Eci, Result},
+};
+
+use super::{
+    dotcode_common::{
+        CODEWORD_DOTS, DOT_PATTERNS, MASK_DOTS, MAX_DIMENSION, MIN_DIMENSION, apply_mask,
+        codewords::*, dot_count, dot_positions, ecc_codewords, required_dots,
+    },
+    reed_solomon,
+};
+
+/// Score given to a mask leaving one of the symbol edges without any dot
+const SCORE_UNLIT_EDGE: i32 = -99999;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum CodeSet {
+    A,
+    B,
+    C,
+    Binary,
+}
+
+/// Encode `contents` into a symbol. The returned matrix has one module per grid
+/// position, set wherever a dot is printed.
+pub fn encode(contents: &str, hints: &EncodeHints) -> Result<BitMatrix> {
+    let gs1 = hints.Gs1Format.unwrap_or(false);
+    let (eci, bytes) = characters(contents, hints)?;
+
+    let mut encoder = HighLevelEncoder::new(&bytes, gs1);
+    encoder.encode(eci);
+    let binary_finish = encoder.set == CodeSet::Binary;
+    encode_codewords(&encoder.codewords, binary_finish)
+}
+
+/// The bytes to encode and the ECI they need. Text is sent as ISO-8859-1 when possible
+/// and as UTF-8 otherwise, a character set given in the hints is always announced.
+fn characters(contents: &str, hints: &EncodeHints) -> Result<(Option<Eci>, Vec<u8>)> {
+    let charset = match &hints.CharacterSet {
+        Some(name) => CharacterSet::get_character_set_by_name(name).ok_or(
+            Exceptions::illegal_argument_with(format!("unsupported character set {name}")),
+        )?,
+        None => match CharacterSet::ISO8859_1.encode(contents) {
+            Ok(bytes) => return Ok((None, bytes)),
+            Err(_) => CharacterSet::UTF8,
+        },
+    };
+    let eci = Eci::from(charset);
+    if eci == Eci::Unknown {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "no ECI for character set {charset:?}"
+        )));
+    }
+    Ok((Some(eci), charset.encode(contents)?))
+}
+
+/// Symbol size for `data_count` data codewords, aiming for a 3:2 width to height ratio
+pub fn choose_size(data_count: usize) -> Result<(u32, u32)> {
+    let min_area = required_dots(data_count) as i32 * 2;
+
+    // single precision, as in the reference implementation
+    let h = (min_area as f64 * 0.666).sqrt() as f32;
+    let w = (min_area as f64 * 1.5).sqrt() as f32;
+    let mut height = h as i32;
+    let mut width = w as i32;
+
+    if (width + height) % 2 == 1 {
+        if width * height < min_area {
+            width += 1;
+            height += 1;
+        }
+    } else if h * (width as f32) < w * (height as f32) {
+        width += 1;
+        if width * height < min_area {
+            width -= 1;
+            height += 1;
+            if width * height < min_area {
+                width += 2;
+            }
+        }
+    } else {
+        height += 1;
+        if width * height < min_area {
+            width += 1;
+            height -= 1;
+            if width * height < min_area {
+                height += 2;
+            }
+        }
+    }
+
+    if width > MAX_DIMENSION as i32 || height > MAX_DIMENSION as i32 {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "{data_count} codewords do not fit in a DotCode symbol"
+        )));
+    }
+    Ok((width as u32, height as u32))
+}
+
+/// Build the dot grid for a sequence of data codewords. `binary_finish` tells whether the
+/// codewords end in binary mode, which the padding has to leave.
+pub fn encode_codewords(data: &[u32], binary_finish: bool) -> Result<BitMatrix> {
+    let (width, height) = choose_size(data.len())?;
+    encode_codewords_with_size(data, binary_finish, width, height)
+}
+
+/// Build the dot grid for a sequence of data codewords in a symbol of the given size
+pub fn encode_codewords_with_size(
+    data: &[u32],
+    binary_finish: bool,
+    width: u32,
+    height: u32,
+) -> Result<BitMatrix> {
+    if width < MIN_DIMENSION
+        || height < MIN_DIMENSION
+        || width > MAX_DIMENSION
+        || height > MAX_DIMENSION
+        || (width + height) % 2 == 0
+    {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "invalid DotCode dimensions {width}x{height}"
+        )));
+    }
+    let dots = dot_count(width, height);
+    if required_dots(data.len()) > dots {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "{} codewords do not fit in a {width}x{height} symbol",
+            data.len()
+        )));
+    }
+
+    let padded = pad(data, binary_finish, dots);
+
+    // masks 4 to 7 are masks 0 to 3 with all corner dots printed, they are only tried
+    // when none of the plain masks scores well
+    let mut best = (i32::MIN, 0);
+    for mask in 0..8 {
+        if mask == 4 && best.0 > dots as i32 {
+            break;
+        }
+        let grid = place_dots(&padded, mask, width, height);
+        let score = score_grid(&grid, width as i32, height as i32);
+        if score >= best.0 {
+            best = (score, mask);
+        }
+    }
+
+    let grid = place_dots(&padded, best.1, width, height);
+    let mut symbol = BitMatrix::new(width, height)?;
+    for (i, _) in grid.iter().enumerate().filter(|(_, dot)| **dot) {
+        symbol.set(i as u32 % width, i as u32 / width);
+    }
+    Ok(symbol)
+}
+
+/// Add pad codewords while they fit, each one taking its share of error correction
+fn pad(data: &[u32], binary_finish: bool, dots: usize) -> Vec<u32> {
+    let mut padded = data.to_vec();
+    let mut free = dots - required_dots(data.len());
+    while free >= CODEWORD_DOTS {
+        if padded.len() % 2 == 0 {
+            free -= CODEWORD_DOTS;
+        } else if free >= 2 * CODEWORD_DOTS {
+            free -= 2 * CODEWORD_DOTS;
+        } else {
+            break;
+        }
+        padded.push(if binary_finish && padded.len() == data.len() {
+            BINARY_EXIT_A as u32
+        } else {
+            PAD as u32
+        });
+    }
+    padded
+}
+
+/// Mask, protect and fold the codewords into a row-major grid of dots
+fn place_dots(data: &[u32], mask: u32, width: u32, height: u32) -> Vec<bool> {
+    let mut codewords = Vec::with_capacity(1 + data.len() + ecc_codewords(data.len()));
+    codewords.push(mask % 4);
+    codewords.extend_from_slice(data);
+    apply_mask(&mut codewords, mask % 4, false);
+    codewords.resize(1 + data.len() + ecc_codewords(data.len()), 0);
+    reed_solomon::encode(&mut codewords, 1 + data.len());
+
+    let mut stream = Vec::with_capacity(dot_count(width, height));
+    for i in (0..MASK_DOTS).rev() {
+        stream.push((codewords[0] >> i) & 1 == 1);
+    }
+    for &cw in &codewords[1..] {
+        let pattern = DOT_PATTERNS[cw as usize];
+        for i in (0..CODEWORD_DOTS).rev() {
+            stream.push((pattern >> i) & 1 == 1);
+        }
+    }
+
+    let mut grid = vec![false; (width * height) as usize];
+    for (i, (x, y)) in dot_positions(width, height).into_iter().enumerate() {
+        // dots after the end of the stream are printed
+        grid[(y * width + x) as usize] = stream.get(i).copied().unwrap_or(true);
+    }
+    if mask >= 4 {
+        let corners = if width % 2 == 1 {
+            [
+                (0, 0),
+                (width - 1, 0),
+                (0, height - 2),
+                (width - 1, height - 2),
+                (1, height - 1),
+                (width - 2, height - 1),
+            ]
+        } else {
+            [
+                (0, 0),
+                (width - 2, 0),
+                (width - 1, 1),
+                (width - 1, height - 2),
+                (0, height - 1),
+                (width - 2, height - 1),
+            ]
+        };
+        for (x, y) in corners {
+            grid[(y * width + x) as usize] = true;
+        }
+    }
+    grid
+}
+
+/// Mask score from Annex A, higher is better. Symbols with long printed edges, few empty
+/// areas and no empty rows or columns are preferred since they are easier to locate.
+fn score_grid(grid: &[bool], width: i32, height: i32) -> i32 {
+    let dot = |x: i32, y: i32| {
+        x >= 0 && x < width && y >= 0 && y < height && grid[(y * width + x) as usize]
+    };
+
+    // consecutive empty rows and columns are penalised by size^count
+    let empty_run_penalty = |lines: i32, size: i32, empty: &dyn Fn(i32) -> bool| {
+        let (mut penalty, mut local) = (0i32, 0i32);
+        for line in 1..lines - 1 {
+            if empty(line) {
+                local = if local == 0 {
+                    size
+                } else {
+                    local.wrapping_mul(size)
+                };
+            } else {
+                penalty = penalty.wrapping_add(local);
+                local = 0;
+            }
+        }
+        penalty.wrapping_add(local)
+    };
+    let penalty = empty_run_penalty(height, width, &|y| {
+        (y & 1..width).step_by(2).all(|x| !dot(x, y))
+    })
+    .wrapping_add(empty_run_penalty(width, height, &|x| {
+        (x & 1..height).step_by(2).all(|y| !dot(x, y))
+    }));
+
+    // the printed extent of each edge, weighted by the length of the other axis
+    let edge = |positions: &mut dyn Iterator<Item = (i32, i32, i32)>, scale: i32| {
+        let (mut sum, mut first, mut last) = (0, -1, -1);
+        for (x, y, along) in positions {
+            if dot(x, y) {
+                if first < 0 {
+                    first = along;
+                }
+                last = along;
+                sum += 1;
+            }
+        }
+        (sum != 0).then_some((sum + last - first) * scale)
+    };
+    let edges = [
+        edge(&mut (0..width).step_by(2).map(|x| (x, 0, x)), height),
+        edge(
+            &mut (width & 1..width).step_by(2).map(|x| (x, height - 1, x)),
+            height,
+        ),
+        edge(&mut (0..height).step_by(2).map(|y| (0, y, y)), width),
+        edge(
+            &mut (height & 1..height).step_by(2).map(|y| (width - 1, y, y)),
+            width,
+        ),
+    ];
+    let mut worst_edge = i32::MAX;
+    for edge in edges {
+        match edge {
+            Some(score) => worst_edge = worst_edge.min(score),
+            None => return SCORE_UNLIT_EDGE,
+        }
+    }
+
+    // empty crosses, and printed dots without any printed neighbour
+    let mut isolated = 0;
+    for y in 0..height {
+        for x in (y & 1..width).step_by(2) {
+            if !dot(x - 1, y - 1)
+                && !dot(x + 1, y - 1)
+                && !dot(x - 1, y + 1)
+                && !dot(x + 1, y + 1)
+                && (!dot(x, y)
+                    || (!dot(x - 2, y) && !dot(x, y - 2) && !dot(x + 2, y) && !dot(x, y + 2)))
+            {
+                isolated += 1;
+            }
+        }
+    }
+
+    worst_edge
+        .wrapping_sub(isolated * isolated)
+        .wrapping_sub(penalty)
+}
+
+/// Convert bytes to data codewords. GS1 data starts directly with its digits, while other
+/// data starting with two digits is marked with a leading FNC1. Group separators in GS1
+/// data are sent as FNC1.
+pub fn encode_high_level(bytes: &[u8], eci: Option<Eci>, gs1: bool) -> Vec<u32> {
+    let mut encoder = HighLevelEncoder::new(bytes, gs1);
+    encoder.encode(eci);
+    encoder.codewords
+}
+
+struct HighLevelEncoder<'a> {
+    bytes: &'a [u8],
+    gs1: bool,
+    codewords: Vec<u32>,
+    set: CodeSet,
+    position: usize,
+    /// start of the trailer implied by a macro, it is left out when the encoding
+    /// reaches it
+    trailer: Option<usize>,
+    /// base 259 values waiting to be packed into base 103 digits
+    binary_buffer: u64,
+    binary_count: usize,
+}
+
+impl<'a> HighLevelEncoder<'a> {
+    fn new(bytes: &'a [u8], gs1: bool) -> Self {
+        Self {
+            bytes,
+            gs1,
+            codewords: Vec::with_capacity(bytes.len() + 8),
+            set: CodeSet::C,
+            position: 0,
+            trailer: None,
+            binary_buffer: 0,
+            binary_count: 0,
+        }
+    }
+
+    fn push(&mut self, codeword: u8) {
+        self.codewords.push(codeword as u32);
+    }
+
+    fn byte(&self, position: usize) -> Option<u8> {
+        self.bytes.get(position).copied()
+    }
+
+    fn is_digit(&self, position: usize) -> bool {
+        self.byte(position).is_some_and(|b| b.is_ascii_digit())
+    }
+
+    fn pair(&self, position: usize) -> u8 {
+        (self.bytes[position] - b'0') * 10 + self.bytes[position + 1] - b'0'
+    }
+
+    /// Encodable in Code Set A
+    fn datum_a(&self, position: usize) -> bool {
+        self.byte(position).is_some_and(|b| b <= 95)
+    }
+
+    /// Number of bytes making up the next Code Set B character, 0 if it isn't one
+    fn datum_b(&self, position: usize) -> usize {
+        match self.byte(position) {
+            Some(32..=127) => 1,
+            Some(b) if B_CONTROLS.contains(&b) => 1,
+            Some(b'\r') if self.byte(position + 1) == Some(b'\n') => 2,
+            _ => 0,
+        }
+    }
+
+    /// Encodable as a Code Set C digit pair
+    fn datum_c(&self, position: usize) -> bool {
+        self.is_digit(position) && self.is_digit(position + 1)
+    }
+
+    fn is_binary(&self, position: usize) -> bool {
+        self.byte(position).is_some_and(|b| b >= 128)
+    }
+
+    /// "17", six digits and "10", the start of a GS1 expiry date followed by a batch
+    fn seventeen_ten(&self, position: usize) -> bool {
+        position + 9 < self.bytes.len()
+            && &self.bytes[position..position + 2] == b"17"
+            && &self.bytes[position + 8..position + 10] == b"10"
+            && self.bytes[position + 2..position + 8]
+                .iter()
+                .all(u8::is_ascii_digit)
+    }
+
+    /// Number of digit pairs starting at `position`
+    fn ahead_c(&self, position: usize) -> usize {
+        (position..self.bytes.len())
+            .step_by(2)
+            .take_while(|&i| self.datum_c(i))
+            .count()
+    }
+
+    /// Number of digit pairs worth sending in Code Set C, which is only the case when
+    /// starting at `position` gives more pairs than starting one digit later
+    fn try_c(&self, position: usize) -> usize {
+        if self.is_digit(position) {
+            let pairs = self.ahead_c(position);
+            if pairs > self.ahead_c(position + 1) {
+                return pairs;
+            }
+        }
+        0
+    }
+
+    /// Number of characters that are better sent in Code Set A than in Code Set C
+    fn ahead_a(&self, position: usize) -> usize {
+        (position..self.bytes.len())
+            .take_while(|&i| self.datum_a(i) && self.try_c(i) < 2)
+            .count()
+    }
+
+    /// Number of bytes and of characters that are better sent in Code Set B than in
+    /// Code Set C
+    fn ahead_b(&self, position: usize) -> (usize, usize) {
+        let mut i = position;
+        let mut count = 0;
+        while self.try_c(i) < 2 {
+            let length = self.datum_b(i);
+            if length == 0 {
+                break;
+            }
+            i += length;
+            count += 1;
+        }
+        (i - position, count)
+    }
+
+    /// Send the Code Set B character at the current position
+    fn push_b(&mut self) {
+        let b = self.bytes[self.position];
+        if b >= 32 {
+            self.push(b - 32);
+        } else if b == b'\r' {
+            self.push(B_CRLF);
+            self.position += 1;
+        } else if let Some(index) = B_CONTROLS.iter().position(|&c| c == b) {
+            self.push(B_CONTROL_BASE + index as u8);
+        }
+        self.position += 1;
+    }
+
+    /// Send the Code Set A character at the current position
+    fn push_a(&mut self) {
+        let b = self.bytes[self.position];
+        self.push(if b < 32 { b + 64 } else { b - 32 });
+        self.position += 1;
+    }
+
+    /// Send `count` digit pairs
+    fn push_pairs(&mut self, count: usize) {
+        for _ in 0..count {
+            let pair = self.pair(self.position);
+            self.push(pair);
+            self.position += 2;
+        }
+    }
+
+    /// Send the byte at the current position with an upper shift
+    fn push_upper_shift(&mut self) {
+        let low = self.bytes[self.position] - 128;
+        if low < 32 {
+            self.push(UPPER_SHIFT_A);
+            self.push(low + 64);
+        } else {
+            self.push(UPPER_SHIFT_B);
+            self.push(low - 32);
+        }
+        self.position += 1;
+    }
+
+    fn append_binary(&mut self, value: u32) {
+        self.binary_buffer = self.binary_buffer * BINARY_RADIX + value as u64;
+        self.binary_count += 1;
+        if self.binary_count == 5 {
+            self.flush_binary();
+        }
+    }
+
+    /// Send the buffered binary values, `n` values taking `n + 1` base 103 digits
+    fn flush_binary(&mut self) {
+        if self.binary_count == 0 {
+            return;
+        }
+        let mut digits = vec![0u8; self.binary_count + 1];
+        for digit in digits.iter_mut().rev() {
+            *digit = (self.binary_buffer % BINARY_BASE as u64) as u8;
+            self.binary_buffer /= BINARY_BASE as u64;
+        }
+        digits.into_iter().for_each(|digit| self.push(digit));
+        self.binary_buffer = 0;
+        self.binary_count = 0;
+    }
+
+    /// Encode the start of the message: reader initialisation, the non GS1 marker, a
+    /// leading control character or a macro header
+    fn encode_start(&mut self, eci: Option<Eci>) {
+        let bytes = self.bytes;
+        let length = bytes.len();
+        if !self.gs1 && eci.is_none() && length > 2 && self.datum_c(0) {
+            self.push(FNC1);
+        } else if B_CONTROLS.contains(&bytes[0]) {
+            // would be read as a macro in Code Set B
+            self.push(C_LATCH_A);
+            self.push(bytes[0] + 64);
+            self.set = CodeSet::A;
+            self.position = 1;
+        } else if length > 5 && bytes.starts_with(b"[)>\x1E") && bytes[length - 1] == 0x04 {
+            let format_05_06_12 = matches!(&bytes[4..6], b"05" | b"06" | b"12");
+            let rs_eot = bytes[length - 2] == 0x1E;
+            let mac = if length > 6 && format_05_06_12 && bytes[6] == 0x1D && rs_eot {
+                match bytes[5] {
+                    b'5' => Some(MACRO_05),
+                    b'6' => Some(MACRO_06),
+                    _ => Some(MACRO_12),
+                }
+            } else if !format_05_06_12 && self.datum_c(4) {
+                Some(MACRO)
+            } else {
+                None
+            };
+            if let Some(mac) = mac {
+                self.push(C_LATCH_B);
+                self.push(mac);
+                self.set = CodeSet::B;
+                if mac == MACRO {
+                    self.push(bytes[4] - 32);
+                    self.push(bytes[5] - 32);
+                    self.position = 6;
+                    self.trailer = Some(length - 1);
+                } else {
+                    self.position = 7;
+                    self.trailer = Some(length - 2);
+                }
+            }
+        }
+    }
+
+    fn encode_eci(&mut self, eci: Eci) {
+        let value = eci as u32;
+        self.push(FNC2);
+        if value <= 39 {
+            self.push(value as u8);
+        } else {
+            // (A - 40) * 12769 + B * 113 + C + 40
+            let value = value - 40;
+            self.push((value / 12769 + 40) as u8);
+            self.push((value % 12769 / 113) as u8);
+            self.push((value % 113) as u8);
+        }
+    }
+
+    fn encode(&mut self, eci: Option<Eci>) {
+        if self.bytes.is_empty() {
+            return;
+        }
+        self.encode_start(eci);
+        if let Some(eci) = eci {
+            self.encode_eci(eci);
+        }
+
+        while self.position < self.bytes.len() {
+            if self.trailer == Some(self.position) {
+                break;
+            }
+            match self.set {
+                CodeSet::C => self.encode_c(),
+                CodeSet::B => self.encode_b(),
+                CodeSet::A => self.encode_a(),
+                CodeSet::Binary => self.encode_binary(),
+            }
+        }
+
+        if self.set == CodeSet::Binary {
+            self.flush_binary();
+        }
+    }
+
+    fn encode_c(&mut self) {
+        let position = self.position;
+        if self.seventeen_ten(position) {
+            self.push(C_SEVENTEEN_TEN);
+            self.position += 2;
+            self.push_pairs(3);
+            self.position += 2;
+            return;
+        }
+
+        if self.gs1 && self.bytes[position] == 0x1D {
+            self.push(FNC1);
+            self.position += 1;
+            return;
+        }
+        if self.datum_c(position) {
+            self.push_pairs(1);
+            return;
+        }
+
+        if self.is_binary(position) {
+            if self.is_digit(position + 1) {
+                self.push_upper_shift();
+            } else {
+                self.push(BINARY_LATCH);
+                self.set = CodeSet::Binary;
+            }
+            return;
+        }
+
+        let a = self.ahead_a(position);
+        let (b, count) = self.ahead_b(position);
+        if a > b {
+            self.push(C_LATCH_A);
+            self.set = CodeSet::A;
+        } else if (1..=4).contains(&count) {
+            self.push(C_SHIFT_B_BASE + count as u8);
+            for _ in 0..count {
+                self.push_b();
+            }
+        } else {
+            self.push(C_LATCH_B);
+            self.set = CodeSet::B;
+        }
+    }
+
+    /// Shift or latch to Code Set C from Code Set A or B, returns `false` if the digits
+    /// ahead are not worth it
+    fn shift_or_latch_c(&mut self) -> bool {
+        let pairs = self.try_c(self.position);
+        if pairs < 2 {
+            return false;
+        }
+        if pairs <= 4 {
+            self.push(SHIFT_C_BASE + pairs as u8);
+            self.push_pairs(pairs);
+        } else {
+            self.push(LATCH_C);
+            self.set = CodeSet::C;
+        }
+        true
+    }
+
+    fn encode_b(&mut self) {
+        if self.shift_or_latch_c() {
+            return;
+        }
+
+        let position = self.position;
+        let b = self.bytes[position];
+        if self.gs1 && b == 0x1D {
+            self.push(FNC1);
+            self.position += 1;
+            return;
+        }
+        // a control character in the first position would be read as a macro
+        if self.datum_b(position) > 0 && (b >= 32 || b == b'\r' || position != 0) {
+            self.push_b();
+            return;
+        }
+
+        if self.is_binary(position) {
+            if self.datum_b(position + 1) > 0 {
+                self.push_upper_shift();
+            } else {
+                self.push(BINARY_LATCH);
+                self.set = CodeSet::Binary;
+            }
+            return;
+        }
+
+        if self.ahead_a(position) == 1 {
+            self.push(B_SHIFT_A);
+            self.push_a();
+        } else {
+            self.push(B_LATCH_A);
+            self.set = CodeSet::A;
+        }
+    }
+
+    fn encode_a(&mut self) {
+        if self.shift_or_latch_c() {
+            return;
+        }
+
+        let position = self.position;
+        if self.gs1 && self.bytes[position] == 0x1D {
+            self.push(FNC1);
+            self.position += 1;
+            return;
+        }
+        if self.datum_a(position) {
+            self.push_a();
+            return;
+        }
+
+        if self.is_binary(position) {
+            if self.datum_a(position + 1) {
+                self.push_upper_shift();
+            } else {
+                self.push(BINARY_LATCH);
+                self.set = CodeSet::Binary;
+            }
+            return;
+        }
+
+        let (_, count) = self.ahead_b(position);
+        if (1..=6).contains(&count) {
+            self.push(A_SHIFT_B_BASE + count as u8);
+            for _ in 0..count {
+                self.push_b();
+            }
+        } else {
+            self.push(A_LATCH_B);
+            self.set = CodeSet::B;
+        }
+    }
+
+    fn encode_binary(&mut self) {
+        let position = self.position;
+        let pairs = self.try_c(position);
+        if pairs >= 2 {
+            self.flush_binary();
+            if pairs <= 7 {
+                self.push(BINARY_SHIFT_C_BASE + pairs as u8);
+                self.push_pairs(pairs);
+            } else {
+                self.push(BINARY_EXIT_C);
+                self.set = CodeSet::C;
+            }
+            return;
+        }
+
+        // stay in binary mode while there is a non ASCII byte in the next four
+        if (position..position + 4).any(|i| self.is_binary(i)) {
+            self.append_binary(self.bytes[position] as u32);
+            self.position += 1;
+            return;
+        }
+
+        self.flush_binary();
+        if self.ahead_a(position) > self.ahead_b(position).0 {
+            self.push(BINARY_EXIT_A);
+            self.set = CodeSet::A;
+        } else {
+            self.push(BINARY_EXIT_B);
+            self.set = CodeSet::B;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_use_code_set_c() {
+        assert_eq!(
+            encode_high_level(b"123456", None, false),
+            vec![FNC1 as u32, 12, 34, 56]
+        );
+        assert_eq!(encode_high_level(b"123456", None, true), vec![12, 34, 56]);
+    }
+
+    #[test]
+    fn short_text_uses_shift() {
+        assert_eq!(
+            encode_high_level(b"12345", None, false),
+            vec![FNC1 as u32, 12, 34, C_SHIFT_B_BASE as u32 + 1, 21]
+        );
+    }
+
+    #[test]
+    fn gs1_separators_are_fnc1() {
+        let codewords = encode_high_level(b"0112345678901231\x1d10ABC", None, true);
+        assert_eq!(codewords[0], 1);
+        assert!(codewords[1..].contains(&(FNC1 as u32)));
+    }
+
+    #[test]
+    fn eci_follows_fnc2() {
+        assert_eq!(
+            encode_high_level(b"a", Some(Eci::UTF8), false),
+            vec![FNC2 as u32, 26, C_SHIFT_B_BASE as u32 + 1, 65]
+        );
+        assert_eq!(
+            encode_high_level(b"a", Some(Eci::Binary), false),
+            vec![FNC2 as u32, 40, 7, 68, C_SHIFT_B_BASE as u32 + 1, 65]
+        );
+    }
+
+    #[test]
+    fn macro_header_is_compacted() {
+        assert_eq!(
+            encode_high_level(b"[)>\x1e06\x1d1P12345\x1e\x04", None, false),
+            vec![
+                C_LATCH_B as u32,
+                MACRO_06 as u32,
+                17,
+                48,
+                17,
+                SHIFT_C_BASE as u32 + 2,
+                23,
+                45
+            ]
+        );
+    }
+
+    #[test]
+    fn sizes_match_zint() {
+        // "12345" is 5 data codewords
+        assert_eq!(choose_size(5).unwrap(), (17, 12));
+        let (w1, h1) = choose_size(5).unwrap();
+        let (w2, h2) = choose_size(50).unwrap();
+        assert!(w1 * h1 < w2 * h2);
+        assert_eq!((w2 + h2) % 2, 1);
+    }
+}
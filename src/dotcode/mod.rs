@@ -0,0 +1,19 @@
+pub mod dotcode_common;
+pub mod reed_solomon;
+
+#[cfg(feature = "decoders")]
+pub mod decoder;
+#[cfg(feature = "decoders")]
+pub mod detector;
+#[cfg(feature = "encoders")]
+pub mod encoder;
+
+#[cfg(feature = "decoders")]
+mod dot_code_reader;
+#[cfg(feature = "decoders")]
+pub use dot_code_reader::*;
+
+#[cfg(feature = "encoders")]
+mod dot_code_writer;
+#[cfg(feature = "encoders")]
+pub use dot_code_writer::*;
@@ -0,0 +1,357 @@
+//! Reed-Solomon error correction over the prime field GF(113) used by DotCode.
+//!
+//! The generic implementation in [`crate::common::reedsolomon`] only supports fields of
+//! characteristic 2, so DotCode carries its own small prime field implementation. The
+//! generator polynomial has the roots `3^1 .. 3^n` and the codewords are interleaved across
+//! `ceil(total / 112)` blocks: block `k` holds every codeword whose index is `k` modulo the
+//! number of blocks, its data codewords first and its check codewords after them.
+
+use once_cell::sync::Lazy;
+
+use crate::Exceptions;
+use crate::common::Result;
+
+use super::dotcode_common::GF;
+
+const GF_SIZE: usize = GF as usize;
+const GENERATOR: u32 = 3;
+
+struct GF113 {
+    exp: [u32; GF_SIZE],
+    log: [u32; GF_SIZE],
+}
+
+impl GF113 {
+    fn new() -> Self {
+        let mut exp = [0; GF_SIZE];
+        let mut log = [0; GF_SIZE];
+        let mut x = 1;
+        for (i, e) in exp.iter_mut().enumerate().take(GF_SIZE - 1) {
+            *e = x;
+            log[x as usize] = i as u32;
+            x = (x * GENERATOR) % GF;
+        }
+        exp[GF_SIZE - 1] = 1;
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u32, b: u32) -> u32 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[((self.log[a as usize] + self.log[b as usize]) % (GF - 1)) as usize]
+    }
+
+    fn inv(&self, a: u32) -> u32 {
+        debug_assert!(a != 0);
+        self.exp[((GF - 1 - self.log[a as usize]) % (GF - 1)) as usize]
+    }
+
+    fn pow(&self, power: usize) -> u32 {
+        self.exp[power % (GF_SIZE - 1)]
+    }
+
+    /// Evaluate a polynomial with coefficients in ascending order of degree
+    fn eval(&self, poly: &[u32], x: u32) -> u32 {
+        poly.iter()
+            .rev()
+            .fold(0, |acc, &c| (self.mul(acc, x) + c) % GF)
+    }
+}
+
+static FIELD: Lazy<GF113> = Lazy::new(GF113::new);
+
+fn add(a: u32, b: u32) -> u32 {
+    (a + b) % GF
+}
+
+fn sub(a: u32, b: u32) -> u32 {
+    (a + GF - b) % GF
+}
+
+/// Number of interleaved blocks used for a symbol with `total` codewords
+fn block_step(total: usize) -> usize {
+    total.div_ceil(GF_SIZE - 1)
+}
+
+/// Indices of the data and check codewords of block `start`
+fn block_indices(
+    start: usize,
+    step: usize,
+    data_count: usize,
+    total: usize,
+) -> (Vec<usize>, Vec<usize>) {
+    (start..total).step_by(step).partition(|&i| i < data_count)
+}
+
+/// Compute the error correction codewords for `codewords[..data_count]` and write them
+/// into `codewords[data_count..]`.
+pub fn encode(codewords: &mut [u32], data_count: usize) {
+    let total = codewords.len();
+    let step = block_step(total);
+    let field = &*FIELD;
+
+    for start in 0..step {
+        let (block_data, block_ecc) = block_indices(start, step, data_count, total);
+        let nc = block_ecc.len();
+
+        // generator polynomial, highest degree first, leading coefficient 1
+        let mut generator = vec![1u32];
+        for i in 1..=nc {
+            let root = field.pow(i);
+            let mut next = vec![0; generator.len() + 1];
+            for (j, &g) in generator.iter().enumerate() {
+                next[j] = add(next[j], g);
+                next[j + 1] = sub(next[j + 1], field.mul(g, root));
+            }
+            generator = next;
+        }
+
+        // polynomial long division of data * x^nc by the generator
+        let mut remainder = vec![0u32; nc];
+        for &index in &block_data {
+            let factor = add(codewords[index], remainder.first().copied().unwrap_or(0));
+            remainder.rotate_left(1);
+            if let Some(last) = remainder.last_mut() {
+                *last = 0;
+            }
+            for (r, &g) in remainder.iter_mut().zip(generator.iter().skip(1)) {
+                *r = sub(*r, field.mul(factor, g));
+            }
+        }
+
+        for (&index, &r) in block_ecc.iter().zip(remainder.iter()) {
+            codewords[index] = sub(0, r);
+        }
+    }
+}
+
+/// Correct errors in place. `erasures` holds the indices of codewords that are known to
+/// be unreliable. Returns the number of corrected codewords.
+pub fn decode(codewords: &mut [u32], data_count: usize, erasures: &[usize]) -> Result<usize> {
+    let total = codewords.len();
+    let step = block_step(total);
+    let mut corrected = 0;
+
+    for start in 0..step {
+        let indices: Vec<usize> = (start..total).step_by(step).collect();
+        let nc = indices.iter().filter(|&&i| i >= data_count).count();
+        let mut block: Vec<u32> = indices.iter().map(|&i| codewords[i]).collect();
+        let block_erasures: Vec<usize> = indices
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| erasures.contains(i))
+            .map(|(position, _)| position)
+            .collect();
+        corrected += decode_block(&mut block, nc, &block_erasures)?;
+        for (&index, value) in indices.iter().zip(block) {
+            codewords[index] = value;
+        }
+    }
+
+    Ok(corrected)
+}
+
+/// Decode a single block. The block is stored highest degree first, so the codeword at
+/// position `p` is the coefficient of `x^(len - 1 - p)`.
+fn decode_block(block: &mut [u32], nc: usize, erasures: &[usize]) -> Result<usize> {
+    let field = &*FIELD;
+    let n = block.len();
+    if erasures.len() > nc {
+        return Err(Exceptions::reed_solomon_with("too many erasures"));
+    }
+    if nc == 0 {
+        return Ok(0);
+    }
+
+    let mut received: Vec<u32> = block.iter().rev().copied().collect();
+    let syndromes: Vec<u32> = (1..=nc)
+        .map(|i| field.eval(&received, field.pow(i)))
+        .collect();
+    if syndromes.iter().all(|&s| s == 0) {
+        return Ok(0);
+    }
+
+    // erasure locator polynomial, ascending order
+    let mut erasure_locator = vec![1u32];
+    for &position in erasures {
+        let x = field.pow(n - 1 - position);
+        let mut next = vec![0; erasure_locator.len() + 1];
+        for (j, &c) in erasure_locator.iter().enumerate() {
+            next[j] = add(next[j], c);
+            next[j + 1] = sub(next[j + 1], field.mul(c, x));
+        }
+        erasure_locator = next;
+    }
+    let erasure_count = erasure_locator.len() - 1;
+
+    // Berlekamp-Massey initialised with the erasure locator
+    let mut locator = erasure_locator.clone();
+    let mut previous = erasure_locator;
+    let mut length = erasure_count;
+    let mut shift = 1;
+    let mut previous_discrepancy = 1;
+    for r in erasure_count..nc {
+        let mut discrepancy = 0;
+        for (j, &c) in locator.iter().enumerate() {
+            if j <= r {
+                discrepancy = add(discrepancy, field.mul(c, syndromes[r - j]));
+            }
+        }
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+        let scale = field.mul(discrepancy, field.inv(previous_discrepancy));
+        let mut next = locator.clone();
+        if next.len() < previous.len() + shift {
+            next.resize(previous.len() + shift, 0);
+        }
+        for (j, &p) in previous.iter().enumerate() {
+            next[j + shift] = sub(next[j + shift], field.mul(scale, p));
+        }
+        if 2 * length <= r + erasure_count {
+            length = r + 1 + erasure_count - length;
+            previous = locator;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        locator = next;
+    }
+    while locator.len() > 1 && locator.last() == Some(&0) {
+        locator.pop();
+    }
+    let degree = locator.len() - 1;
+    if degree == 0 || 2 * degree > nc + erasure_count {
+        return Err(Exceptions::reed_solomon_with("too many errors"));
+    }
+
+    // Chien search
+    let mut positions = Vec::with_capacity(degree);
+    for power in 0..n {
+        let x_inv = field.inv(field.pow(power));
+        if field.eval(&locator, x_inv) == 0 {
+            positions.push(power);
+        }
+    }
+    if positions.len() != degree {
+        return Err(Exceptions::reed_solomon_with(
+            "error locator degree mismatch",
+        ));
+    }
+
+    // error evaluator omega = S(x) * locator(x) mod x^nc
+    let mut omega = vec![0u32; nc];
+    for (i, &s) in syndromes.iter().enumerate() {
+        for (j, &c) in locator.iter().enumerate() {
+            if i + j < nc {
+                omega[i + j] = add(omega[i + j], field.mul(s, c));
+            }
+        }
+    }
+    // formal derivative of the locator
+    let derivative: Vec<u32> = locator
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(j, &c)| field.mul(c, j as u32 % GF))
+        .collect();
+
+    // Forney, with syndromes starting at alpha^1
+    for &power in &positions {
+        let x_inv = field.inv(field.pow(power));
+        let denominator = field.eval(&derivative, x_inv);
+        if denominator == 0 {
+            return Err(Exceptions::reed_solomon_with("zero Forney denominator"));
+        }
+        let magnitude = sub(
+            0,
+            field.mul(field.eval(&omega, x_inv), field.inv(denominator)),
+        );
+        received[power] = sub(received[power], magnitude);
+    }
+
+    // make sure the correction produced a valid codeword
+    if (1..=nc).any(|i| field.eval(&received, field.pow(i)) != 0) {
+        return Err(Exceptions::reed_solomon_with("correction failed"));
+    }
+
+    for (b, r) in block.iter_mut().zip(received.iter().rev()) {
+        *b = *r;
+    }
+    Ok(positions.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_message(rng: &mut java_rand::Random, data: usize, ecc: usize) -> Vec<u32> {
+        let mut message: Vec<u32> = (0..data + ecc).map(|_| rng.next_u32_bound(GF)).collect();
+        encode(&mut message, data);
+        message
+    }
+
+    #[test]
+    fn encoded_message_has_no_errors() {
+        let mut rng = java_rand::Random::new(0);
+        for (data, ecc) in [(1, 3), (10, 8), (80, 43), (200, 103)] {
+            let message = random_message(&mut rng, data, ecc);
+            let mut received = message.clone();
+            assert_eq!(decode(&mut received, data, &[]).unwrap(), 0);
+            assert_eq!(message, received);
+        }
+    }
+
+    #[test]
+    fn corrects_errors() {
+        let mut rng = java_rand::Random::new(0);
+        for (data, ecc) in [(4, 5), (10, 8), (40, 23), (200, 103)] {
+            let message = random_message(&mut rng, data, ecc);
+            let total = data + ecc;
+            let blocks = block_step(total);
+            let mut received = message.clone();
+            // corrupt up to half the check words of each block
+            for start in 0..blocks {
+                let (block_data, block_ecc) = block_indices(start, blocks, data, total);
+                for &index in block_data
+                    .iter()
+                    .chain(&block_ecc)
+                    .take(block_ecc.len() / 2)
+                {
+                    received[index] = (received[index] + 1 + rng.next_u32_bound(GF - 1)) % GF;
+                }
+            }
+            decode(&mut received, data, &[]).unwrap();
+            assert_eq!(message, received);
+        }
+    }
+
+    #[test]
+    fn corrects_erasures() {
+        let mut rng = java_rand::Random::new(0);
+        let (data, ecc) = (20, 13);
+        let message = random_message(&mut rng, data, ecc);
+        let mut received = message.clone();
+        let erasures: Vec<usize> = (0..ecc).map(|i| i * 2).collect();
+        for &e in &erasures {
+            received[e] = 0;
+        }
+        decode(&mut received, data, &erasures).unwrap();
+        assert_eq!(message, received);
+    }
+
+    #[test]
+    fn too_many_errors() {
+        let mut rng = java_rand::Random::new(0);
+        let (data, ecc) = (10, 8);
+        let message = random_message(&mut rng, data, ecc);
+        let mut received = message;
+        for value in received.iter_mut().take(12) {
+            *value = (*value + 5) % GF;
+        }
+        assert!(decode(&mut received, data, &[]).is_err());
+    }
+}
@@ -188,6 +188,31 @@ pub enum EncodeHintType {
      * Will translate the numeric values received by the Telepen writer into the Telepen Alphanumeric form.
      */
     TELEPEN_AS_NUMERIC,
+
+    /**
+     * Number of check digits (0, 1 or 2) to append to Code 11 barcodes (type {@link Integer}).
+     */
+    CODE_11_CHECK_DIGITS,
+
+    /**
+     * Check digit scheme for MSI Plessey barcodes (type {@link MSIChecksum}).
+     */
+    MSI_CHECKSUM,
+
+    /**
+     * Append a modulo 10 check digit to Industrial, IATA and Matrix 2 of 5 barcodes (type {@link Boolean}).
+     */
+    TWO_OF_FIVE_CHECK_DIGIT,
+
+    /**
+     * Render ITF barcodes as ITF-14, carrying a GTIN-14 (type {@link Boolean}).
+     */
+    ITF_14,
+
+    /**
+     * Bearer bars drawn around ITF barcodes (type {@link ITFBearerBars}).
+     */
+    ITF_BEARER_BARS,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -348,6 +373,39 @@ pub enum EncodeHintValue {
      * Translate the numeric values received by the Telepen reader into the Telepen Alphaumeric form; use {@link Boolean#TRUE}.
      */
     TelepenAsNumeric(bool),
+
+    /**
+     * Number of check digits (0, 1 or 2) the Code 11 writer appends. By default one check digit
+     * is added to contents of up to 10 characters and two to longer ones.
+     */
+    #[cfg(feature = "oned")]
+    Code11CheckDigits(u32),
+
+    /**
+     * Check digit scheme the MSI Plessey writer applies. Defaults to a single modulo 10 check digit.
+     */
+    #[cfg(feature = "oned")]
+    MsiChecksum(crate::oned::MSIChecksum),
+
+    /**
+     * Append a modulo 10 check digit to Industrial, IATA and Matrix 2 of 5 barcodes; use {@link Boolean#TRUE}.
+     */
+    #[cfg(feature = "oned")]
+    TwoOfFiveCheckDigit(bool),
+
+    /**
+     * Render ITF barcodes as ITF-14; use {@link Boolean#TRUE}. The contents are then a GTIN-14,
+     * or its first 13 digits to have the check digit computed, and the quiet zones are at least
+     * ten modules wide on each side.
+     */
+    #[cfg(feature = "oned")]
+    Itf14(bool),
+
+    /**
+     * Bearer bars the ITF writer draws. ITF-14 defaults to a full frame, plain ITF to none.
+     */
+    #[cfg(all(feature = "oned", feature = "encoders"))]
+    ItfBearerBars(crate::oned::ITFBearerBars),
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -509,6 +567,39 @@ pub struct EncodeHints {
      * Translate the numeric values received by the Telepen reader into the Telepen Alphaumeric form; use {@link Boolean#TRUE}.
      */
     pub TelepenAsNumeric: Option<bool>,
+
+    /**
+     * Number of check digits (0, 1 or 2) the Code 11 writer appends. By default one check digit
+     * is added to contents of up to 10 characters and two to longer ones.
+     */
+    #[cfg(feature = "oned")]
+    pub Code11CheckDigits: Option<u32>,
+
+    /**
+     * Check digit scheme the MSI Plessey writer applies. Defaults to a single modulo 10 check digit.
+     */
+    #[cfg(feature = "oned")]
+    pub MsiChecksum: Option<crate::oned::MSIChecksum>,
+
+    /**
+     * Append a modulo 10 check digit to Industrial, IATA and Matrix 2 of 5 barcodes; use {@link Boolean#TRUE}.
+     */
+    #[cfg(feature = "oned")]
+    pub TwoOfFiveCheckDigit: Option<bool>,
+
+    /**
+     * Render ITF barcodes as ITF-14; use {@link Boolean#TRUE}. The contents are then a GTIN-14,
+     * or its first 13 digits to have the check digit computed, and the quiet zones are at least
+     * ten modules wide on each side.
+     */
+    #[cfg(feature = "oned")]
+    pub Itf14: Option<bool>,
+
+    /**
+     * Bearer bars the ITF writer draws. ITF-14 defaults to a full frame, plain ITF to none.
+     */
+    #[cfg(all(feature = "oned", feature = "encoders"))]
+    pub ItfBearerBars: Option<crate::oned::ITFBearerBars>,
 }
 
 impl From<super::EncodingHintDictionary> for EncodeHints {
@@ -539,6 +630,16 @@ impl From<super::EncodingHintDictionary> for EncodeHints {
                 EncodeHintValue::ForceC40(v) => new_self.ForceC40 = Some(v),
                 EncodeHintValue::Code128Compact(v) => new_self.Code128Compact = Some(v),
                 EncodeHintValue::TelepenAsNumeric(v) => new_self.TelepenAsNumeric = Some(v),
+                #[cfg(feature = "oned")]
+                EncodeHintValue::Code11CheckDigits(v) => new_self.Code11CheckDigits = Some(v),
+                #[cfg(feature = "oned")]
+                EncodeHintValue::MsiChecksum(v) => new_self.MsiChecksum = Some(v),
+                #[cfg(feature = "oned")]
+                EncodeHintValue::TwoOfFiveCheckDigit(v) => new_self.TwoOfFiveCheckDigit = Some(v),
+                #[cfg(feature = "oned")]
+                EncodeHintValue::Itf14(v) => new_self.Itf14 = Some(v),
+                #[cfg(all(feature = "oned", feature = "encoders"))]
+                EncodeHintValue::ItfBearerBars(v) => new_self.ItfBearerBars = Some(v),
             }
         }
 
@@ -571,6 +672,16 @@ impl EncodeHints {
             EncodeHintValue::ForceC40(v) => self.ForceC40 = Some(v),
             EncodeHintValue::Code128Compact(v) => self.Code128Compact = Some(v),
             EncodeHintValue::TelepenAsNumeric(v) => self.TelepenAsNumeric = Some(v),
+            #[cfg(feature = "oned")]
+            EncodeHintValue::Code11CheckDigits(v) => self.Code11CheckDigits = Some(v),
+            #[cfg(feature = "oned")]
+            EncodeHintValue::MsiChecksum(v) => self.MsiChecksum = Some(v),
+            #[cfg(feature = "oned")]
+            EncodeHintValue::TwoOfFiveCheckDigit(v) => self.TwoOfFiveCheckDigit = Some(v),
+            #[cfg(feature = "oned")]
+            EncodeHintValue::Itf14(v) => self.Itf14 = Some(v),
+            #[cfg(all(feature = "oned", feature = "encoders"))]
+            EncodeHintValue::ItfBearerBars(v) => self.ItfBearerBars = Some(v),
         };
         self
     }
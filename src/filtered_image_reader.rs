@@ -1,6 +1,7 @@
 use crate::common::{BitMatrix, HybridBinarizer, Result};
 use crate::{
-    Binarizer, BinaryBitmap, DecodeHints, Exceptions, Luma8LuminanceSource, LuminanceSource, Reader,
+    Binarizer, BinaryBitmap, DecodeHints, Exceptions, ImmutableReader, Luma8LuminanceSource,
+    LuminanceSource, Reader,
 };
 
 pub const DEFAULT_DOWNSCALE_THRESHHOLD: usize = 500;
@@ -30,6 +31,35 @@ impl<R: Reader> Reader for FilteredImageReader<R> {
         &mut self,
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
+    ) -> crate::common::Result<crate::RXingResult> {
+        Self::decode_filtered(image, hints, |image, hints| {
+            self.0.decode_with_hints(image, hints)
+        })
+    }
+}
+
+impl<R: Reader + ImmutableReader> ImmutableReader for FilteredImageReader<R> {
+    fn immutable_decode_with_hints<B: crate::Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> crate::common::Result<crate::RXingResult> {
+        Self::decode_filtered(image, hints, |image, hints| {
+            self.0.immutable_decode_with_hints(image, hints)
+        })
+    }
+}
+
+impl<R: Reader> FilteredImageReader<R> {
+    /// Decodes each layer of the image pyramid, plain and closed, with the given decode
+    /// function until one of them holds a barcode.
+    fn decode_filtered<B: crate::Binarizer>(
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+        mut decode: impl FnMut(
+            &mut BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>>,
+            &DecodeHints,
+        ) -> Result<crate::RXingResult>,
     ) -> crate::common::Result<crate::RXingResult> {
         let orig_w = image.get_source().get_width();
         let orig_h = image.get_source().get_height();
@@ -83,7 +113,7 @@ impl<R: Reader> Reader for FilteredImageReader<R> {
                         continue;
                     };
                 }
-                if let Ok(mut res) = self.0.decode_with_hints(&mut b, hints) {
+                if let Ok(mut res) = decode(&mut b, hints) {
                     res.putMetadata(
                         crate::RXingResultMetadataType::FILTERED_CLOSED,
                         crate::RXingResultMetadataValue::FilteredClosed(close),
@@ -0,0 +1,401 @@
+use std::borrow::Cow;
+
+use crate::common::Result;
+use crate::{Exceptions, LuminanceSource};
+
+/// Order of the color filters in each 2 by 2 cell of a Bayer sensor, starting at the top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BayerPattern {
+    Rggb,
+    Bggr,
+    Grbg,
+    Gbrg,
+}
+
+/// Layout of the pixels of a camera frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// A plane of 8 bit luma followed by a plane of interleaved U and V at half resolution
+    Nv12,
+    /// A plane of 8 bit luma followed by a plane of interleaved V and U at half resolution
+    Nv21,
+    /// Y0 U Y1 V for each pair of pixels
+    Yuyv,
+    /// U Y0 V Y1 for each pair of pixels
+    Uyvy,
+    /// 8 bit red, green and blue
+    Rgb24,
+    /// 8 bit blue, green and red
+    Bgr24,
+    /// 8 bit blue, green, red and alpha
+    Bgra,
+    /// 5 bit red, 6 bit green and 5 bit blue, in little endian 16 bit words
+    Rgb565,
+    /// One 8 bit sample per pixel, behind the color filter of its pattern
+    Bayer(BayerPattern),
+}
+
+impl PixelFormat {
+    /// Bytes taken by a row of `width` pixels of the luma plane or of the packed pixels
+    fn row_bytes(self, width: usize) -> usize {
+        match self {
+            Self::Nv12 | Self::Nv21 | Self::Bayer(_) => width,
+            Self::Yuyv | Self::Uyvy => width.div_ceil(2) * 4,
+            Self::Rgb24 | Self::Bgr24 => width * 3,
+            Self::Bgra => width * 4,
+            Self::Rgb565 => width * 2,
+        }
+    }
+}
+
+/// A [`LuminanceSource`] reading a camera frame in place, in any of the [`PixelFormat`]s
+/// delivered by capture pipelines. Rows may be padded to a stride, and the luma of each
+/// pixel is only worked out when a row, a column or the matrix is fetched. Cropping keeps
+/// borrowing the frame.
+#[derive(Debug, Clone)]
+pub struct FrameLuminanceSource<'a> {
+    data: &'a [u8],
+    format: PixelFormat,
+    /// Bytes from the start of a row to the start of the next one
+    stride: usize,
+    data_width: usize,
+    data_height: usize,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    invert: bool,
+}
+
+impl<'a> FrameLuminanceSource<'a> {
+    /// Reads a frame of `width` by `height` pixels with rows packed one after another
+    pub fn new(data: &'a [u8], format: PixelFormat, width: usize, height: usize) -> Result<Self> {
+        Self::with_stride(data, format, width, height, format.row_bytes(width))
+    }
+
+    /// Reads a frame of `width` by `height` pixels with rows `stride` bytes apart
+    pub fn with_stride(
+        data: &'a [u8],
+        format: PixelFormat,
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Result<Self> {
+        let row_bytes = format.row_bytes(width);
+        if stride < row_bytes {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "stride of {stride} bytes is shorter than a row of {row_bytes} bytes"
+            )));
+        }
+        if height > 0 && data.len() < stride * (height - 1) + row_bytes {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "{} bytes of data are too few for {width}x{height} pixels",
+                data.len()
+            )));
+        }
+        Ok(Self {
+            data,
+            format,
+            stride,
+            data_width: width,
+            data_height: height,
+            left: 0,
+            top: 0,
+            width,
+            height,
+            invert: false,
+        })
+    }
+
+    /// Luma of the pixel at `x`, `y` of the whole frame
+    fn luma(&self, x: usize, y: usize) -> u8 {
+        let row = &self.data[y * self.stride..];
+        match self.format {
+            PixelFormat::Nv12 | PixelFormat::Nv21 => row[x],
+            PixelFormat::Yuyv => row[x * 2],
+            PixelFormat::Uyvy => row[x * 2 + 1],
+            PixelFormat::Rgb24 => rgb_luma(row[x * 3], row[x * 3 + 1], row[x * 3 + 2]),
+            PixelFormat::Bgr24 => rgb_luma(row[x * 3 + 2], row[x * 3 + 1], row[x * 3]),
+            PixelFormat::Bgra => rgb_luma(row[x * 4 + 2], row[x * 4 + 1], row[x * 4]),
+            PixelFormat::Rgb565 => {
+                let pixel = u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]);
+                let (r, g, b) = (pixel >> 11, (pixel >> 5) & 0x3F, pixel & 0x1F);
+                rgb_luma(
+                    ((r << 3) | (r >> 2)) as u8,
+                    ((g << 2) | (g >> 4)) as u8,
+                    ((b << 3) | (b >> 2)) as u8,
+                )
+            }
+            PixelFormat::Bayer(pattern) => self.bayer_luma(pattern, x, y),
+        }
+    }
+
+    /// Luma of the 2 by 2 cell holding the pixel at `x`, `y`. A last odd row or column
+    /// reuses the one before it.
+    fn bayer_luma(&self, pattern: BayerPattern, x: usize, y: usize) -> u8 {
+        let (x, y) = (x & !1, y & !1);
+        let at = |dx: usize, dy: usize| {
+            let x = (x + dx).min(self.data_width - 1);
+            let y = (y + dy).min(self.data_height - 1);
+            self.data[y * self.stride + x] as u16
+        };
+        let (top_left, top_right, bottom_left, bottom_right) =
+            (at(0, 0), at(1, 0), at(0, 1), at(1, 1));
+        let (r, g, b) = match pattern {
+            BayerPattern::Rggb => (top_left, top_right + bottom_left, bottom_right),
+            BayerPattern::Bggr => (bottom_right, top_right + bottom_left, top_left),
+            BayerPattern::Grbg => (top_right, top_left + bottom_right, bottom_left),
+            BayerPattern::Gbrg => (bottom_left, top_left + bottom_right, top_right),
+        };
+        rgb_luma(r as u8, (g / 2) as u8, b as u8)
+    }
+
+    fn invert_if_should(&self, byte: u8) -> u8 {
+        if self.invert { u8::MAX - byte } else { byte }
+    }
+}
+
+/// `.299R + 0.587G + 0.114B`, as [`crate::BufferedImageLuminanceSource`] computes it
+fn rgb_luma(r: u8, g: u8, b: u8) -> u8 {
+    ((306 * r as u32 + 601 * g as u32 + 117 * b as u32 + 0x200) >> 10) as u8
+}
+
+impl LuminanceSource for FrameLuminanceSource<'_> {
+    const SUPPORTS_CROP: bool = true;
+    const SUPPORTS_ROTATION: bool = false;
+
+    fn get_row(&self, y: usize) -> Option<Cow<'_, [u8]>> {
+        if y >= self.height {
+            return None;
+        }
+        let y = self.top + y;
+        if matches!(self.format, PixelFormat::Nv12 | PixelFormat::Nv21) && !self.invert {
+            let start = y * self.stride + self.left;
+            return Some(Cow::Borrowed(&self.data[start..start + self.width]));
+        }
+        Some(Cow::Owned(
+            (self.left..self.left + self.width)
+                .map(|x| self.invert_if_should(self.luma(x, y)))
+                .collect(),
+        ))
+    }
+
+    fn get_column(&self, x: usize) -> Vec<u8> {
+        (self.top..self.top + self.height)
+            .map(|y| self.invert_if_should(self.luma(self.left + x, y)))
+            .collect()
+    }
+
+    fn get_matrix(&self) -> Cow<'_, [u8]> {
+        let mut matrix = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            if let Some(row) = self.get_row(y) {
+                matrix.extend_from_slice(&row);
+            }
+        }
+        Cow::Owned(matrix)
+    }
+
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn crop(&self, left: usize, top: usize, width: usize, height: usize) -> Result<Self> {
+        if left + width > self.width || top + height > self.height {
+            return Err(Exceptions::illegal_argument_with(
+                "Crop rectangle does not fit within image data.",
+            ));
+        }
+        Ok(Self {
+            left: self.left + left,
+            top: self.top + top,
+            width,
+            height,
+            ..self.clone()
+        })
+    }
+
+    fn invert(&mut self) {
+        self.invert = !self.invert;
+    }
+
+    fn get_luma8_point(&self, x: usize, y: usize) -> u8 {
+        self.invert_if_should(self.luma(self.left + x, self.top + y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::LuminanceSource;
+
+    use super::{BayerPattern, FrameLuminanceSource, PixelFormat};
+
+    /// Luma of a 4 by 2 frame, and the same frame in each pixel format with 2 bytes of
+    /// padding at the end of each row
+    const LUMA: [u8; 8] = [0, 76, 150, 29, 255, 128, 10, 200];
+
+    fn padded(rows: [&[u8]; 2]) -> Vec<u8> {
+        rows.iter()
+            .flat_map(|row| [*row, &[0xEE, 0xEE]].concat())
+            .collect()
+    }
+
+    fn assert_luma(data: &[u8], format: PixelFormat, expected: &[u8]) {
+        let stride = data.len() / 2;
+        let source = FrameLuminanceSource::with_stride(data, format, 4, 2, stride).unwrap();
+        assert_eq!(source.get_matrix().as_ref(), expected, "{format:?}");
+        assert_eq!(
+            source.get_column(1),
+            [expected[1], expected[5]],
+            "{format:?}"
+        );
+        assert_eq!(source.get_luma8_point(2, 1), expected[6], "{format:?}");
+    }
+
+    #[test]
+    fn reads_yuv_formats() {
+        let nv12 = [padded([&LUMA[..4], &LUMA[4..]]), vec![128; 4]].concat();
+        let source = FrameLuminanceSource::with_stride(&nv12, PixelFormat::Nv12, 4, 2, 6).unwrap();
+        assert_eq!(source.get_matrix().as_ref(), LUMA);
+        // Rows of the luma plane are borrowed
+        assert!(matches!(
+            source.get_row(1),
+            Some(Cow::Borrowed([255, 128, 10, 200]))
+        ));
+        assert_luma(&nv12[..12], PixelFormat::Nv21, &LUMA);
+
+        let yuyv = padded([
+            &[0, 90, 76, 240, 150, 90, 29, 240],
+            &[255, 90, 128, 240, 10, 90, 200, 240],
+        ]);
+        assert_luma(&yuyv, PixelFormat::Yuyv, &LUMA);
+        let uyvy = padded([
+            &[90, 0, 240, 76, 90, 150, 240, 29],
+            &[90, 255, 240, 128, 90, 10, 240, 200],
+        ]);
+        assert_luma(&uyvy, PixelFormat::Uyvy, &LUMA);
+    }
+
+    #[test]
+    fn reads_rgb_formats() {
+        // Black, red, green and blue over white, gray, dark gray and light gray
+        let expected = [0, 76, 150, 29, 255, 128, 10, 200];
+        let rgb24 = padded([
+            &[0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255],
+            &[255, 255, 255, 128, 128, 128, 10, 10, 10, 200, 200, 200],
+        ]);
+        assert_luma(&rgb24, PixelFormat::Rgb24, &expected);
+        let bgr24 = padded([
+            &[0, 0, 0, 0, 0, 255, 0, 255, 0, 255, 0, 0],
+            &[255, 255, 255, 128, 128, 128, 10, 10, 10, 200, 200, 200],
+        ]);
+        assert_luma(&bgr24, PixelFormat::Bgr24, &expected);
+        let bgra = padded([
+            &[0, 0, 0, 255, 0, 0, 255, 255, 0, 255, 0, 255, 255, 0, 0, 255],
+            &[
+                255, 255, 255, 255, 128, 128, 128, 255, 10, 10, 10, 255, 200, 200, 200, 255,
+            ],
+        ]);
+        assert_luma(&bgra, PixelFormat::Bgra, &expected);
+
+        // Black, red, green and blue, then white and the grays rounded to 5 and 6 bits
+        let rgb565 = padded([
+            &[0x00, 0x00, 0x00, 0xF8, 0xE0, 0x07, 0x1F, 0x00],
+            &[0xFF, 0xFF, 0x10, 0x84, 0x41, 0x08, 0x59, 0xCE],
+        ]);
+        assert_luma(
+            &rgb565,
+            PixelFormat::Rgb565,
+            &[0, 76, 150, 29, 255, 131, 8, 204],
+        );
+    }
+
+    #[test]
+    fn reads_bayer_cells() {
+        // Two cells, the first red and the second a gray of 100
+        let rggb = padded([&[255, 0, 100, 100], &[0, 0, 100, 100]]);
+        assert_luma(
+            &rggb,
+            PixelFormat::Bayer(BayerPattern::Rggb),
+            &[76, 76, 100, 100, 76, 76, 100, 100],
+        );
+        let bggr = padded([&[0, 0, 100, 100], &[0, 255, 100, 100]]);
+        assert_luma(
+            &bggr,
+            PixelFormat::Bayer(BayerPattern::Bggr),
+            &[76, 76, 100, 100, 76, 76, 100, 100],
+        );
+        let grbg = padded([&[0, 255, 100, 100], &[0, 0, 100, 100]]);
+        assert_luma(
+            &grbg,
+            PixelFormat::Bayer(BayerPattern::Grbg),
+            &[76, 76, 100, 100, 76, 76, 100, 100],
+        );
+        let gbrg = padded([&[0, 0, 100, 100], &[255, 0, 100, 100]]);
+        assert_luma(
+            &gbrg,
+            PixelFormat::Bayer(BayerPattern::Gbrg),
+            &[76, 76, 100, 100, 76, 76, 100, 100],
+        );
+    }
+
+    #[test]
+    fn crops_and_inverts_in_place() {
+        let rgb24: Vec<u8> = LUMA.iter().flat_map(|&l| [l, l, l]).collect();
+        let mut source = FrameLuminanceSource::new(&rgb24, PixelFormat::Rgb24, 4, 2).unwrap();
+        let cropped = source.crop(1, 1, 2, 1).unwrap();
+        assert_eq!(cropped.get_matrix().as_ref(), [128, 10]);
+        assert!(cropped.crop(1, 0, 2, 1).is_err());
+
+        source.invert();
+        assert_eq!(source.get_row(0).unwrap().as_ref(), [255, 179, 105, 226]);
+    }
+
+    #[test]
+    fn rejects_short_frames() {
+        let data = [0; 23];
+        assert!(FrameLuminanceSource::new(&data, PixelFormat::Rgb24, 4, 2).is_err());
+        assert!(FrameLuminanceSource::with_stride(&data, PixelFormat::Rgb24, 4, 2, 11).is_err());
+        assert!(FrameLuminanceSource::with_stride(&data, PixelFormat::Rgb24, 3, 2, 12).is_ok());
+    }
+
+    #[cfg(all(feature = "image", feature = "encoders", feature = "qrcode"))]
+    #[test]
+    fn decodes_padded_frame() {
+        use crate::{
+            BarcodeFormat, BinaryBitmap, Reader, Writer,
+            common::HybridBinarizer,
+            qrcode::{QRCodeReader, QRCodeWriter},
+        };
+
+        let matrix = QRCodeWriter
+            .encode("frame", &BarcodeFormat::QR_CODE, 100, 100)
+            .unwrap();
+        let (width, height) = (matrix.getWidth() as usize, matrix.getHeight() as usize);
+        // A YUYV frame with 16 bytes of padding per row
+        let stride = width * 2 + 16;
+        let mut frame = vec![128; stride * height];
+        for y in 0..height {
+            for x in 0..width {
+                frame[y * stride + x * 2] = if matrix.get(x as u32, y as u32) {
+                    0
+                } else {
+                    255
+                };
+            }
+        }
+        let source =
+            FrameLuminanceSource::with_stride(&frame, PixelFormat::Yuyv, width, height, stride)
+                .unwrap();
+        let result = QRCodeReader
+            .decode(&mut BinaryBitmap::new(HybridBinarizer::new(source)))
+            .unwrap();
+        assert_eq!(result.getText(), "frame");
+    }
+}
@@ -0,0 +1,27 @@
+//! Mode indicators and in-band values of the Han Xin Code data stream, shared by the
+//! encoder and the decoder.
+
+pub const MODE_TERMINATOR: u32 = 0x0;
+pub const MODE_NUMERIC: u32 = 0x1;
+pub const MODE_TEXT: u32 = 0x2;
+pub const MODE_BINARY: u32 = 0x3;
+pub const MODE_REGION_1: u32 = 0x4;
+pub const MODE_REGION_2: u32 = 0x5;
+pub const MODE_DOUBLE_BYTE: u32 = 0x6;
+pub const MODE_FOUR_BYTE: u32 = 0x7;
+pub const MODE_ECI: u32 = 0x8;
+
+/// Terminators of the last numeric group holding one, two or three digits
+pub const NUMERIC_END: [u32; 3] = [0x3FD, 0x3FE, 0x3FF];
+pub const TEXT_SWITCH: u32 = 62;
+pub const TEXT_END: u32 = 63;
+pub const REGION_SWITCH: u32 = 0xFFE;
+pub const REGION_END: u32 = 0xFFF;
+pub const DOUBLE_BYTE_END: u32 = 0x7FFF;
+
+/// First region 1 values of the GB 2312 symbol rows A1 to A3 and of the pinyin in row A8
+pub const REGION_1_SYMBOLS: u32 = 0xEB0;
+pub const REGION_1_PINYIN: u32 = 0xFCA;
+
+/// Codewords are interleaved by taking every `PICKET_FENCE_STEP`th one
+pub const PICKET_FENCE_STEP: usize = 13;
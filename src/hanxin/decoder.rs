@@ -0,0 +1,504 @@
+//! Reads the codewords of a sampled Han Xin Code symbol, corrects errors and converts the
+//! bit stream back into text.
+
+use crate::{
+    DecodeHints, Exceptions,
+    common::{
+        BitMatrix, BitSource, CharacterSet, DecoderRXingResult, Eci, Result,
+        reedsolomon::{PredefinedGenericGF, ReedSolomonDecoder, get_predefined_genericgf},
+    },
+};
+
+use super::{
+    ErrorCorrectionLevel, Version,
+    common::{
+        DOUBLE_BYTE_END, MODE_BINARY, MODE_DOUBLE_BYTE, MODE_ECI, MODE_FOUR_BYTE, MODE_NUMERIC,
+        MODE_REGION_1, MODE_REGION_2, MODE_TERMINATOR, MODE_TEXT, NUMERIC_END, PICKET_FENCE_STEP,
+        REGION_1_PINYIN, REGION_1_SYMBOLS, REGION_END, REGION_SWITCH, TEXT_END, TEXT_SWITCH,
+    },
+    symbol::{
+        FUNCTION_INFO_BITS, data_positions, decode_function_info, function_info_positions,
+        is_masked,
+    },
+};
+
+/// Decode a grid with one module per sample. All four orientations are tried.
+pub fn decode(bits: &BitMatrix, hints: &DecodeHints) -> Result<DecoderRXingResult> {
+    let mut candidate = bits.clone();
+    let mut error = Exceptions::NOT_FOUND;
+    for _ in 0..4 {
+        match decode_oriented(&candidate, hints) {
+            Ok(result) => return Ok(result),
+            Err(e) => error = e,
+        }
+        candidate.rotate90();
+    }
+    Err(error)
+}
+
+/// Reads version, error correction level and mask from either copy of the function
+/// information
+pub fn read_function_info(bits: &BitMatrix) -> Result<(Version, ErrorCorrectionLevel, u8)> {
+    let size = bits.getWidth();
+    if size != bits.getHeight() {
+        return Err(Exceptions::FORMAT);
+    }
+    let dimension_version = Version::getProvisionalVersionForDimension(size)?;
+    let mut error = Exceptions::FORMAT;
+    for positions in function_info_positions(size) {
+        let mut info = [false; FUNCTION_INFO_BITS];
+        for (bit, &(x, y)) in info.iter_mut().zip(positions.iter()) {
+            *bit = bits.get(x, y);
+        }
+        match decode_function_info(&info) {
+            Ok(result) if result.0 == dimension_version => return Ok(result),
+            Ok(_) => error = Exceptions::FORMAT,
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+fn decode_oriented(bits: &BitMatrix, _hints: &DecodeHints) -> Result<DecoderRXingResult> {
+    let (version, ec_level, mask) = read_function_info(bits)?;
+
+    let mut codewords = vec![0u8; version.getTotalCodewords()];
+    for (i, (x, y)) in data_positions(version)
+        .into_iter()
+        .take(8 * codewords.len())
+        .enumerate()
+    {
+        if bits.get(x, y) != is_masked(mask, x, y) {
+            codewords[i / 8] |= 0x80 >> (i % 8);
+        }
+    }
+
+    // undo the picket fence interleaving
+    let mut stream = vec![0u8; codewords.len()];
+    let mut index = 0;
+    for start in 0..PICKET_FENCE_STEP {
+        for position in (start..stream.len()).step_by(PICKET_FENCE_STEP) {
+            stream[position] = codewords[index];
+            index += 1;
+        }
+    }
+
+    let rs = ReedSolomonDecoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::HanXinField256,
+    ));
+    let mut data = Vec::with_capacity(version.getDataCodewords(ec_level));
+    let mut errors_corrected = 0;
+    let mut offset = 0;
+    for (data_count, ec_count) in version.getECBlocks(ec_level) {
+        let mut block: Vec<i32> = stream[offset..offset + data_count + ec_count]
+            .iter()
+            .map(|&cw| cw as i32)
+            .collect();
+        errors_corrected += rs
+            .decode(&mut block, ec_count as i32)
+            .map_err(|_| Exceptions::CHECKSUM)?;
+        data.extend(block[..data_count].iter().map(|&cw| cw as u8));
+        offset += data_count + ec_count;
+    }
+
+    let segments = decode_bit_stream(&data)?;
+    let mut text = String::new();
+    for (charset, bytes) in &segments {
+        text.push_str(&charset.decode(bytes)?);
+    }
+
+    let mut result = DecoderRXingResult::new(
+        data,
+        text,
+        segments.into_iter().map(|(_, bytes)| bytes).collect(),
+        ec_level.to_string(),
+    );
+    result.setErrorsCorrected(errors_corrected);
+    Ok(result)
+}
+
+/// Parses the data codewords into runs of bytes sharing one character set
+pub fn decode_bit_stream(data: &[u8]) -> Result<Vec<(CharacterSet, Vec<u8>)>> {
+    let mut source = BitSource::new(data);
+    let mut segments: Vec<(CharacterSet, Vec<u8>)> = Vec::new();
+    // ECI designators only apply to binary mode, which defaults to ISO-8859-1. All other
+    // modes are ASCII or GB 18030.
+    let mut charset = CharacterSet::ISO8859_1;
+
+    let mut push = |charset: CharacterSet, bytes: &[u8]| match segments.last_mut() {
+        Some((last, existing)) if *last == charset => existing.extend_from_slice(bytes),
+        _ => segments.push((charset, bytes.to_vec())),
+    };
+
+    while source.available() >= 4 {
+        let mode = source.readBits(4)?;
+        match mode {
+            MODE_TERMINATOR => break,
+            MODE_NUMERIC => {
+                let mut digits = Vec::new();
+                let mut last_group = String::new();
+                loop {
+                    let value = source.readBits(10)?;
+                    if let Some(count) = NUMERIC_END.iter().position(|&end| end == value) {
+                        let count = count + 1;
+                        if last_group.len() < count {
+                            return Err(Exceptions::FORMAT);
+                        }
+                        digits.extend_from_slice(&last_group.as_bytes()[3 - count..]);
+                        break;
+                    }
+                    if value > 999 {
+                        return Err(Exceptions::FORMAT);
+                    }
+                    digits.extend_from_slice(last_group.as_bytes());
+                    last_group = format!("{value:03}");
+                }
+                push(CharacterSet::GB18030, &digits);
+            }
+            MODE_TEXT => {
+                let mut submode = 1;
+                let mut text = Vec::new();
+                loop {
+                    let value = source.readBits(6)?;
+                    match value {
+                        TEXT_END => break,
+                        TEXT_SWITCH => submode = 3 - submode,
+                        _ => text.push(text_char(submode, value)?),
+                    }
+                }
+                push(CharacterSet::GB18030, &text);
+            }
+            MODE_BINARY => {
+                let count = source.readBits(13)? as usize;
+                let mut bytes = Vec::with_capacity(count);
+                for _ in 0..count {
+                    bytes.push(source.readBits(8)? as u8);
+                }
+                push(charset, &bytes);
+            }
+            MODE_REGION_1 | MODE_REGION_2 => {
+                let mut region_1 = mode == MODE_REGION_1;
+                let mut bytes = Vec::new();
+                loop {
+                    let value = source.readBits(12)?;
+                    let (first, second) = match value {
+                        REGION_END => break,
+                        REGION_SWITCH => {
+                            region_1 = !region_1;
+                            continue;
+                        }
+                        _ if !region_1 => (0xD8 + value / 0x5E, 0xA1 + value % 0x5E),
+                        REGION_1_PINYIN.. => (0xA8, 0xA1 + value - REGION_1_PINYIN),
+                        REGION_1_SYMBOLS.. => {
+                            let value = value - REGION_1_SYMBOLS;
+                            (0xA1 + value / 0x5E, 0xA1 + value % 0x5E)
+                        }
+                        _ => (0xB0 + value / 0x5E, 0xA1 + value % 0x5E),
+                    };
+                    if first > 0xF7 || second > 0xFE {
+                        return Err(Exceptions::FORMAT);
+                    }
+                    bytes.extend([first as u8, second as u8]);
+                }
+                push(CharacterSet::GB18030, &bytes);
+            }
+            MODE_DOUBLE_BYTE => {
+                let mut bytes = Vec::new();
+                loop {
+                    let value = source.readBits(15)?;
+                    if value == DOUBLE_BYTE_END {
+                        break;
+                    }
+                    let first = 0x81 + value / 0xBE;
+                    let second = value % 0xBE;
+                    if first > 0xFE {
+                        return Err(Exceptions::FORMAT);
+                    }
+                    bytes.push(first as u8);
+                    bytes.push(if second < 0x3F {
+                        second + 0x40
+                    } else {
+                        second + 0x41
+                    } as u8);
+                }
+                push(CharacterSet::GB18030, &bytes);
+            }
+            MODE_FOUR_BYTE => {
+                let value = source.readBits(21)?;
+                let first = 0x81 + value / 12600;
+                if first > 0xFE {
+                    return Err(Exceptions::FORMAT);
+                }
+                push(
+                    CharacterSet::GB18030,
+                    &[
+                        first as u8,
+                        (0x30 + value % 12600 / 1260) as u8,
+                        (0x81 + value % 1260 / 10) as u8,
+                        (0x30 + value % 10) as u8,
+                    ],
+                );
+            }
+            MODE_ECI => {
+                let value = if source.readBits(1)? == 0 {
+                    source.readBits(7)?
+                } else if source.readBits(1)? == 0 {
+                    source.readBits(14)?
+                } else if source.readBits(1)? == 0 {
+                    source.readBits(21)?
+                } else {
+                    return Err(Exceptions::FORMAT);
+                };
+                charset = CharacterSet::from(Eci::from(value));
+                if charset == CharacterSet::Unknown {
+                    return Err(Exceptions::FORMAT);
+                }
+            }
+            _ => return Err(Exceptions::FORMAT),
+        }
+    }
+
+    Ok(segments)
+}
+
+fn text_char(submode: u8, value: u32) -> Result<u8> {
+    let value = value as u8;
+    let c = match (submode, value) {
+        (1, 0..=9) => b'0' + value,
+        (1, 10..=35) => b'A' + value - 10,
+        (1, 36..=61) => b'a' + value - 36,
+        (2, 0..=27) => value,
+        (2, 28..=43) => 0x20 + value - 28,
+        (2, 44..=50) => 0x3A + value - 44,
+        (2, 51..=56) => 0x5B + value - 51,
+        (2, 57..=61) => 0x7B + value - 57,
+        _ => return Err(Exceptions::FORMAT),
+    };
+    Ok(c)
+}
+
+#[cfg(all(test, feature = "encoders"))]
+mod tests {
+    use super::*;
+    use crate::{EncodeHintValue, EncodeHints, hanxin::encoder};
+
+    /// "Hello World", version 1 with the error correction level raised to L3
+    const ZINT_HELLO_WORLD: &str = "
+        11111110101101101111111
+        10000000110111100000001
+        10111110110000001111101
+        10100000011111100000101
+        10101110010100001110101
+        10101110011000101110101
+        10101110100100101110101
+        00000000011010100000000
+        00010101100001111000000
+        11110110101000111100010
+        11001010000101010111000
+        01101000101110111111011
+        10000001101001111100100
+        01101101011000011000000
+        00000011100000110101000
+        00000000110110000000000
+        11111110110100101110101
+        00000010111111001110101
+        11111010001001001110101
+        00001010100010000000101
+        11101010001111101111101
+        11101010101001100000001
+        11101010100100101111111
+    ";
+
+    /// "1234567890" at level L4
+    const ZINT_NUMERIC_L4: &str = "
+        11111110101000101111111
+        10000000010010000000001
+        10111110100001001111101
+        10100000101011000000101
+        10101110010001101110101
+        10101110111000101110101
+        10101110010100001110101
+        00000000111101100000000
+        00010101101000001000000
+        00001101111110100111110
+        10101001100110100101001
+        11000010111101010001110
+        10101001111100101010101
+        01111110100010110100001
+        00000010000101110101000
+        00000000101011100000000
+        11111110011001001110101
+        00000010101001101110101
+        11111010100011001110101
+        00001010011011100000101
+        11101010000001101111101
+        11101010011011000000001
+        11101010101010101111111
+    ";
+
+    /// "汉信码 Han Xin Code 0123456789 ABCDEFGHIJKLMNOPQRSTUVWXYZ" with ECI 32, version 5
+    const ZINT_MIXED_VERSION_5: &str = "
+        1111111011100010101101001111111
+        1000000001110000001110000000001
+        1011111010010101101100101111101
+        1010000001011010000011100000101
+        1010111011010010001010101110101
+        1010111001110110110010101110101
+        1010111011110000100100001110101
+        0000000000011000011010100000000
+        0001100101111110011110011000000
+        1011011011001110000100100111010
+        1001111011000110111100000011100
+        1101001111111111111001001010101
+        1011100000011011001000100100100
+        1001000100010010110001110101011
+        1100001000100111100111100100000
+        1000110000010010100000101111000
+        1111111111111110001100100110001
+        0000000000000010100100011100100
+        1001000110011010110101010111100
+        1100111111100011000111110110111
+        1101001011000011100001011011111
+        0100000100100010010101011000100
+        0000001101000010011111010011000
+        0000000011111010001111000000000
+        1111111000111010000100101110101
+        0000001010010011110000001110101
+        1111101010101010111101101110101
+        0000101011010010110001000000101
+        1110101010011010110111101111101
+        1110101001110011010011000000001
+        1110101000100011111111101111111
+    ";
+
+    fn parse(symbol: &str) -> BitMatrix {
+        let rows: Vec<&str> = symbol.split_whitespace().collect();
+        BitMatrix::parse_strings(&rows.join("\n"), "1", "0").unwrap()
+    }
+
+    #[test]
+    fn decodes_zint_symbols() {
+        for (symbol, contents, level) in [
+            (ZINT_HELLO_WORLD, "Hello World", "L3"),
+            (ZINT_NUMERIC_L4, "1234567890", "L4"),
+            (
+                ZINT_MIXED_VERSION_5,
+                "汉信码 Han Xin Code 0123456789 ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                "L1",
+            ),
+        ] {
+            let result = decode(&parse(symbol), &DecodeHints::default()).unwrap();
+            assert_eq!(result.getText(), contents);
+            assert_eq!(result.getECLevel(), level, "{contents}");
+        }
+    }
+
+    #[test]
+    fn encodes_like_zint() {
+        let hints = EncodeHints::default();
+        for (symbol, contents, level) in [
+            (ZINT_HELLO_WORLD, "Hello World", None),
+            (
+                ZINT_NUMERIC_L4,
+                "1234567890",
+                Some(ErrorCorrectionLevel::L4),
+            ),
+            (
+                ZINT_MIXED_VERSION_5,
+                "汉信码 Han Xin Code 0123456789 ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                None,
+            ),
+        ] {
+            let encoded = encoder::encode(contents, level, &hints).unwrap();
+            assert_eq!(encoded, parse(symbol), "{contents}");
+        }
+    }
+
+    fn round_trip(contents: &str, hints: &EncodeHints) {
+        let mut bits = encoder::encode_high_level(contents, hints).unwrap();
+        while bits.get_size() % 8 != 0 {
+            bits.appendBit(false);
+        }
+        let length = bits.get_size() / 8;
+        let mut data = vec![0u8; length];
+        bits.toBytes(0, &mut data, 0, length);
+        let segments = decode_bit_stream(&data).unwrap();
+        let text: String = segments
+            .iter()
+            .map(|(charset, bytes)| charset.decode(bytes).unwrap())
+            .collect();
+        assert_eq!(text, contents);
+    }
+
+    #[test]
+    fn high_level_round_trip() {
+        let hints = EncodeHints::default();
+        round_trip("1", &hints);
+        round_trip("12345", &hints);
+        round_trip("123456", &hints);
+        round_trip("Han Xin Code, 2007!", &hints);
+        round_trip("汉信码", &hints);
+        // region 2, double byte and four byte characters
+        round_trip("齄鬯丂亐€", &hints);
+        round_trip("㐀𠀀😀", &hints);
+        round_trip("订单号 20240101-0001 数量: 12 件 \u{1c}", &hints);
+        round_trip(
+            "Grüße ✓",
+            &EncodeHints::default().with(EncodeHintValue::CharacterSet("UTF-8".into())),
+        );
+    }
+
+    #[test]
+    fn symbol_round_trip() {
+        for (contents, level) in [
+            ("Han Xin", ErrorCorrectionLevel::L1),
+            ("汉信码 GB 18030", ErrorCorrectionLevel::L2),
+            (&"0123456789".repeat(40) as &str, ErrorCorrectionLevel::L3),
+            (
+                &"中华人民共和国".repeat(60) as &str,
+                ErrorCorrectionLevel::L4,
+            ),
+        ] {
+            let symbol = encoder::encode(contents, Some(level), &EncodeHints::default()).unwrap();
+            let result = decode(&symbol, &DecodeHints::default()).unwrap();
+            assert_eq!(result.getText(), contents);
+            assert_eq!(result.getECLevel(), level.to_string());
+        }
+    }
+
+    #[test]
+    fn symbol_round_trip_with_errors() {
+        let contents = "Error correction 汉信码 0123456789";
+        let mut symbol = encoder::encode(
+            contents,
+            Some(ErrorCorrectionLevel::L4),
+            &EncodeHints::default(),
+        )
+        .unwrap();
+        let positions =
+            data_positions(Version::getProvisionalVersionForDimension(symbol.getWidth()).unwrap());
+        for &index in &[3, 90, 200, 330] {
+            let (x, y) = positions[index];
+            symbol.flip_coords(x, y);
+        }
+        // damage one copy of the function information
+        for (x, y) in function_info_positions(symbol.getWidth())[0].iter().take(8) {
+            symbol.flip_coords(*x, *y);
+        }
+        let result = decode(&symbol, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), contents);
+    }
+
+    #[test]
+    fn rotated_symbol() {
+        let mut symbol = encoder::encode(
+            "rotated",
+            Some(ErrorCorrectionLevel::L2),
+            &EncodeHints::default(),
+        )
+        .unwrap();
+        symbol.rotate90();
+        let result = decode(&symbol, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "rotated");
+    }
+}
@@ -0,0 +1,293 @@
+//! Locates a Han Xin Code symbol through its four finder patterns.
+//!
+//! A line through the 3x3 core of a finder pattern crosses dark and light modules in the
+//! ratio 1:1:1:1:3, or 3:1:1:1:1 depending on the corner, both horizontally and
+//! vertically. Candidates found on the image rows are confirmed on their column, grouped,
+//! and every set of four is tried in each orientation. A set is accepted once the sampled
+//! grid shows the expected finder patterns and valid function information.
+
+use crate::{
+    Exceptions, Point,
+    common::{
+        BitMatrix, DefaultGridSampler, DetectorRXingResult, GridSampler, Quadrilateral, Result,
+    },
+    point,
+};
+
+use super::{
+    Version,
+    decoder::read_function_info,
+    symbol::draw_function_patterns,
+    version::{MAX_VERSION, MIN_VERSION},
+};
+
+/// Most candidates considered when combining finder patterns
+const MAX_CANDIDATES: usize = 8;
+
+/// Largest share of finder pattern modules that may be sampled wrongly
+const MAX_FINDER_ERRORS: usize = 4 * 49 / 8;
+
+pub struct HanXinDetectorResult(BitMatrix, Vec<Point>);
+
+impl HanXinDetectorResult {
+    pub fn new(bits: BitMatrix, points: Vec<Point>) -> Self {
+        Self(bits, points)
+    }
+}
+
+impl DetectorRXingResult for HanXinDetectorResult {
+    fn getBits(&self) -> &BitMatrix {
+        &self.0
+    }
+
+    fn getPoints(&self) -> &[Point] {
+        &self.1
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Candidate {
+    x: f32,
+    y: f32,
+    module: f32,
+    count: u32,
+}
+
+/// Find and sample a Han Xin Code symbol in `image`
+pub fn detect(image: &BitMatrix) -> Result<HanXinDetectorResult> {
+    let mut candidates = find_candidates(image);
+    if candidates.len() < 4 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.count));
+    candidates.truncate(MAX_CANDIDATES);
+
+    let n = candidates.len();
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    let set = [candidates[a], candidates[b], candidates[c], candidates[d]];
+                    if let Some(result) = try_finder_set(image, set) {
+                        return Ok(result);
+                    }
+                }
+            }
+        }
+    }
+    Err(Exceptions::NOT_FOUND)
+}
+
+/// Centre of the finder core in module coordinates, in the order top left, top right,
+/// bottom right, bottom left
+fn core_centres(size: u32) -> [Point; 4] {
+    let size = size as f32;
+    [
+        point(5.5, 5.5),
+        point(size - 5.5, 5.5),
+        point(size - 5.5, size - 5.5),
+        point(1.5, size - 1.5),
+    ]
+}
+
+fn try_finder_set(image: &BitMatrix, mut set: [Candidate; 4]) -> Option<HanXinDetectorResult> {
+    let smallest = set.iter().map(|c| c.module).fold(f32::INFINITY, f32::min);
+    let largest = set.iter().map(|c| c.module).fold(0.0, f32::max);
+    if largest > smallest * 1.5 {
+        return None;
+    }
+    let module = set.iter().map(|c| c.module).sum::<f32>() / 4.0;
+
+    // clockwise order, starting at the smallest angle from the centroid
+    let cx = set.iter().map(|c| c.x).sum::<f32>() / 4.0;
+    let cy = set.iter().map(|c| c.y).sum::<f32>() / 4.0;
+    set.sort_by(|a, b| {
+        (a.y - cy)
+            .atan2(a.x - cx)
+            .total_cmp(&(b.y - cy).atan2(b.x - cx))
+    });
+
+    for rotation in 0..4 {
+        let corners: [Candidate; 4] = std::array::from_fn(|i| set[(i + rotation) % 4]);
+        let [top_left, top_right, bottom_right, _] = corners;
+        let distance = |a: &Candidate, b: &Candidate| (a.x - b.x).hypot(a.y - b.y);
+        let span =
+            (distance(&top_left, &top_right) + distance(&top_right, &bottom_right)) / 2.0 / module;
+        let estimate = (span.round() as i64 + 11) | 1;
+
+        for size in [estimate, estimate - 2, estimate + 2] {
+            let Ok(size) = u32::try_from(size) else {
+                continue;
+            };
+            let Ok(version) = Version::getProvisionalVersionForDimension(size) else {
+                continue;
+            };
+            if !(MIN_VERSION..=MAX_VERSION).contains(&version.getVersionNumber()) {
+                continue;
+            }
+            let [tl, tr, br, bl] = core_centres(size);
+            let dst = Quadrilateral::new(tl, tr, br, bl);
+            let src = Quadrilateral::new(
+                point(corners[0].x, corners[0].y),
+                point(corners[1].x, corners[1].y),
+                point(corners[2].x, corners[2].y),
+                point(corners[3].x, corners[3].y),
+            );
+            let Ok((bits, _)) =
+                DefaultGridSampler.sample_grid_detailed(image, size, size, dst, src)
+            else {
+                continue;
+            };
+            if finder_errors(&bits, version) > MAX_FINDER_ERRORS {
+                continue;
+            }
+            if read_function_info(&bits).is_ok() {
+                let points = corners.iter().map(|c| point(c.x, c.y)).collect();
+                return Some(HanXinDetectorResult::new(bits, points));
+            }
+        }
+    }
+    None
+}
+
+/// Number of modules in the four finder patterns differing from the expected ones
+fn finder_errors(bits: &BitMatrix, version: Version) -> usize {
+    let size = version.getDimensionForVersion();
+    let Ok(mut expected) = BitMatrix::with_single_dimension(size) else {
+        return usize::MAX;
+    };
+    draw_function_patterns(version, &mut expected);
+    let mut errors = 0;
+    for (left, top) in [(0, 0), (size - 7, 0), (size - 7, size - 7), (0, size - 7)] {
+        for y in top..top + 7 {
+            for x in left..left + 7 {
+                if bits.get(x, y) != expected.get(x, y) {
+                    errors += 1;
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Checks that `runs`, alternating dark and light starting with dark, follow the ratio
+/// 1:1:1:1:3 or 3:1:1:1:1. Returns the module size and whether the long run comes last.
+fn finder_ratio(runs: &[u32; 5]) -> Option<(f32, bool)> {
+    let total: u32 = runs.iter().sum();
+    if total < 7 {
+        return None;
+    }
+    let module = total as f32 / 7.0;
+    let tolerance = module * 0.6;
+    let fits = |pattern: [f32; 5]| {
+        runs.iter()
+            .zip(pattern)
+            .all(|(&run, units)| (run as f32 - units * module).abs() < units * tolerance)
+    };
+    if fits([1.0, 1.0, 1.0, 1.0, 3.0]) {
+        Some((module, true))
+    } else if fits([3.0, 1.0, 1.0, 1.0, 1.0]) {
+        Some((module, false))
+    } else {
+        None
+    }
+}
+
+/// Runs of equal pixels along a line, as `(start, length, dark)`
+fn runs(length: u32, get: impl Fn(u32) -> bool) -> Vec<(u32, u32, bool)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=length {
+        if i == length || get(i) != get(start) {
+            runs.push((start, i - start, get(start)));
+            start = i;
+        }
+    }
+    runs
+}
+
+/// Finds the finder pattern core crossing `position` on a line and returns its centre
+fn cross_check(line: &[(u32, u32, bool)], position: u32, module: f32) -> Option<f32> {
+    let index = line
+        .iter()
+        .position(|&(start, length, _)| position >= start && position < start + length)?;
+    if !line[index].2 {
+        return None;
+    }
+    let window = |from: usize| -> Option<[u32; 5]> {
+        let slice = line.get(from..from + 5)?;
+        Some(std::array::from_fn(|i| slice[i].1))
+    };
+    let matches = |runs: Option<[u32; 5]>, long_last: bool| {
+        runs.and_then(|r| finder_ratio(&r))
+            .is_some_and(|(m, last)| last == long_last && m < module * 1.5 && m > module / 1.5)
+    };
+    let (start, length, _) = line[index];
+    let centred = start as f32 + length as f32 / 2.0;
+    if (index >= 4 && matches(window(index - 4), true)) || matches(window(index), false) {
+        Some(centred)
+    } else {
+        None
+    }
+}
+
+fn find_candidates(image: &BitMatrix) -> Vec<Candidate> {
+    let width = image.getWidth();
+    let height = image.getHeight();
+    let mut candidates: Vec<Candidate> = Vec::new();
+    let mut columns: Vec<Option<Vec<(u32, u32, bool)>>> = vec![None; width as usize];
+
+    for y in 0..height {
+        let row = runs(width, |x| image.get(x, y));
+        for window in row.windows(5) {
+            if !window[0].2 {
+                continue;
+            }
+            let lengths = std::array::from_fn(|k| window[k].1);
+            let Some((module, long_last)) = finder_ratio(&lengths) else {
+                continue;
+            };
+            let core = if long_last { window[4] } else { window[0] };
+            let row_centre = core.0 as f32 + core.1 as f32 / 2.0;
+            let x = row_centre as u32;
+
+            let column =
+                columns[x as usize].get_or_insert_with(|| runs(height, |cy| image.get(x, cy)));
+            let Some(centre_y) = cross_check(column, y, module) else {
+                continue;
+            };
+            // re-centre horizontally on the row through the vertical centre
+            let row_y = centre_y as u32;
+            let centre_x = if row_y == y {
+                row_centre
+            } else {
+                let line = runs(width, |cx| image.get(cx, row_y));
+                match cross_check(&line, x, module) {
+                    Some(cx) => cx,
+                    None => continue,
+                }
+            };
+            add_candidate(&mut candidates, centre_x, centre_y, module);
+        }
+    }
+    candidates
+}
+
+fn add_candidate(candidates: &mut Vec<Candidate>, x: f32, y: f32, module: f32) {
+    for candidate in candidates.iter_mut() {
+        if (candidate.x - x).hypot(candidate.y - y) < 2.0 * module {
+            let count = candidate.count as f32;
+            candidate.x = (candidate.x * count + x) / (count + 1.0);
+            candidate.y = (candidate.y * count + y) / (count + 1.0);
+            candidate.module = (candidate.module * count + module) / (count + 1.0);
+            candidate.count += 1;
+            return;
+        }
+    }
+    candidates.push(Candidate {
+        x,
+        y,
+        module,
+        count: 1,
+    });
+}
@@ -0,0 +1,648 @@
+//! Converts text into Han Xin Code codewords and builds the symbol.
+//!
+//! Text that fits ISO-8859-1 is encoded as is, anything else as GB 18030 behind an ECI
+//! designator, unless another character set is requested. The modes are chosen by a
+//! shortest path search over the characters, so that for example short digit runs stay in
+//! text mode and GB 2312 characters use the region modes where that is cheaper.
+
+use crate::{
+    EncodeHints, Exceptions,
+    common::{
+        BitArray, BitMatrix, CharacterSet, Eci, Result,
+        reedsolomon::{PredefinedGenericGF, ReedSolomonEncoder, get_predefined_genericgf},
+    },
+};
+
+use super::{
+    ErrorCorrectionLevel, Version,
+    common::{
+        DOUBLE_BYTE_END, MODE_BINARY, MODE_DOUBLE_BYTE, MODE_ECI, MODE_FOUR_BYTE, MODE_NUMERIC,
+        MODE_REGION_1, MODE_REGION_2, MODE_TEXT, NUMERIC_END, PICKET_FENCE_STEP, REGION_1_PINYIN,
+        REGION_1_SYMBOLS, REGION_END, REGION_SWITCH, TEXT_END, TEXT_SWITCH,
+    },
+    symbol::{
+        MASK_COUNT, data_positions, draw_function_patterns, encode_function_info,
+        function_info_positions, is_masked,
+    },
+    version::{MAX_VERSION, MIN_VERSION},
+};
+
+/// Costs are counted in sixths of a bit, so that numeric groups of two and three digits
+/// have whole costs
+const COST_UNIT: u32 = 6;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Mode {
+    Numeric,
+    Text,
+    Binary,
+    Region1,
+    Region2,
+    DoubleByte,
+    FourByte,
+}
+
+impl Mode {
+    const ALL: [Mode; 7] = [
+        Mode::Numeric,
+        Mode::Text,
+        Mode::Binary,
+        Mode::Region1,
+        Mode::Region2,
+        Mode::DoubleByte,
+        Mode::FourByte,
+    ];
+
+    /// Bits spent on the mode indicator and the binary count when entering this mode
+    const fn head(self) -> u32 {
+        match self {
+            Mode::Binary => 4 + 13,
+            Mode::FourByte => 0,
+            _ => 4,
+        }
+    }
+
+    /// Bits of the terminator when leaving this mode
+    const fn terminator(self) -> u32 {
+        match self {
+            Mode::Numeric => 10,
+            Mode::Text => 6,
+            Mode::Region1 | Mode::Region2 => 12,
+            Mode::DoubleByte => 15,
+            Mode::Binary | Mode::FourByte => 0,
+        }
+    }
+
+    /// Bits spent when switching from this mode to `next`. The two regions switch into
+    /// each other with their terminator alone.
+    const fn switch(self, next: Mode) -> u32 {
+        match (self, next) {
+            (Mode::Region1, Mode::Region2) | (Mode::Region2, Mode::Region1) => 12,
+            _ => self.terminator() + next.head(),
+        }
+    }
+}
+
+/// Value of a character in text submode 1: digits and letters
+fn text1_value(c: u32) -> Option<u32> {
+    match char::from_u32(c)? {
+        d @ '0'..='9' => Some(d as u32 - '0' as u32),
+        u @ 'A'..='Z' => Some(u as u32 - 'A' as u32 + 10),
+        l @ 'a'..='z' => Some(l as u32 - 'a' as u32 + 36),
+        _ => None,
+    }
+}
+
+/// Value of a character in text submode 2: controls and punctuation
+fn text2_value(c: u32) -> Option<u32> {
+    match c {
+        0x00..=0x1B => Some(c),
+        0x20..=0x2F => Some(c - 0x20 + 28),
+        0x3A..=0x40 => Some(c - 0x3A + 44),
+        0x5B..=0x60 => Some(c - 0x5B + 51),
+        0x7B..=0x7F => Some(c - 0x7B + 57),
+        _ => None,
+    }
+}
+
+/// Submode and value of a character in text mode
+pub fn text_value(c: u32) -> Option<(u8, u32)> {
+    text1_value(c)
+        .map(|value| (1, value))
+        .or_else(|| text2_value(c).map(|value| (2, value)))
+}
+
+fn is_digit(c: u32) -> bool {
+    (b'0' as u32..=b'9' as u32).contains(&c)
+}
+
+/// Region 1: GB 2312 hanzi level 1, symbol rows A1 to A3 and the pinyin in row A8
+fn is_region1(c: u32) -> bool {
+    let (first, second) = (c >> 8, c & 0xFF);
+    ((0xB0..=0xD7).contains(&first) || (0xA1..=0xA3).contains(&first))
+        && (0xA1..=0xFE).contains(&second)
+        || (0xA8A1..=0xA8C0).contains(&c)
+}
+
+/// Region 2: GB 2312 hanzi level 2
+fn is_region2(c: u32) -> bool {
+    (0xD8..=0xF7).contains(&(c >> 8)) && (0xA1..=0xFE).contains(&(c & 0xFF))
+}
+
+fn is_double_byte(c: u32) -> bool {
+    let second = c & 0xFF;
+    (0x81..=0xFE).contains(&(c >> 8))
+        && ((0x40..=0x7E).contains(&second) || (0x80..=0xFE).contains(&second))
+}
+
+fn is_four_byte(first: u32, second: u32) -> bool {
+    (0x81..=0xFE).contains(&(first >> 8))
+        && (0x30..=0x39).contains(&(first & 0xFF))
+        && (0x81..=0xFE).contains(&(second >> 8))
+        && (0x30..=0x39).contains(&(second & 0xFF))
+}
+
+/// Encodes `contents` into a symbol without quiet zone. Without an explicit error
+/// correction level, the highest level fitting the smallest possible version is used.
+pub fn encode(
+    contents: &str,
+    ec_level: Option<ErrorCorrectionLevel>,
+    hints: &EncodeHints,
+) -> Result<BitMatrix> {
+    let bits = encode_high_level(contents, hints)?;
+    let (version, ec_level, data) = build_data_codewords(&bits, ec_level)?;
+    let codewords = add_error_correction(&data, version, ec_level)?;
+
+    let mut best: Option<(u32, BitMatrix)> = None;
+    for mask in 0..MASK_COUNT {
+        let matrix = build_matrix(&codewords, version, ec_level, mask)?;
+        let penalty = penalty(&matrix);
+        if best.as_ref().is_none_or(|(p, _)| penalty < *p) {
+            best = Some((penalty, matrix));
+        }
+    }
+    best.map(|(_, m)| m).ok_or(Exceptions::ILLEGAL_STATE)
+}
+
+/// Bit stream of `contents`, without terminator
+pub fn encode_high_level(contents: &str, hints: &EncodeHints) -> Result<BitArray> {
+    let (eci, characters) = characters(contents, hints)?;
+
+    let mut bits = BitArray::new();
+    if let Some(eci) = eci {
+        append_eci(&mut bits, eci as u32)?;
+    }
+
+    let modes = choose_modes(&characters);
+    let mut start = 0;
+    while start < characters.len() {
+        let mode = modes[start];
+        let end = (start..characters.len())
+            .find(|&i| modes[i] != mode)
+            .unwrap_or(characters.len());
+        let run = &characters[start..end];
+        let previous = start.checked_sub(1).map(|i| modes[i]);
+        let next = modes.get(end).copied();
+        match mode {
+            Mode::Numeric => append_numeric(&mut bits, run)?,
+            Mode::Text => append_text(&mut bits, run)?,
+            Mode::Binary => append_binary(&mut bits, run)?,
+            Mode::Region1 | Mode::Region2 => append_region(&mut bits, mode, run, previous, next)?,
+            Mode::DoubleByte => append_double_byte(&mut bits, run)?,
+            Mode::FourByte => append_four_byte(&mut bits, run)?,
+        }
+        start = end;
+    }
+
+    Ok(bits)
+}
+
+/// The ECI to announce and the characters to encode. Single bytes are stored as they are,
+/// two byte GB 18030 characters as one value and four byte ones as two.
+fn characters(contents: &str, hints: &EncodeHints) -> Result<(Option<Eci>, Vec<u32>)> {
+    let charset = match &hints.CharacterSet {
+        Some(name) => CharacterSet::get_character_set_by_name(name).ok_or(Exceptions::WRITER)?,
+        None => match CharacterSet::ISO8859_1.encode(contents) {
+            Ok(bytes) => return Ok((None, bytes.into_iter().map(u32::from).collect())),
+            Err(_) => CharacterSet::GB18030,
+        },
+    };
+    let eci = Eci::from(charset);
+    if eci == Eci::Unknown {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "no ECI for character set {charset:?}"
+        )));
+    }
+    let bytes = charset.encode(contents)?;
+    let multibyte = matches!(charset, CharacterSet::GB18030 | CharacterSet::GB2312);
+    if !multibyte {
+        return Ok((Some(eci), bytes.into_iter().map(u32::from).collect()));
+    }
+
+    let pair = |i: usize| (bytes[i] as u32) << 8 | bytes[i + 1] as u32;
+    let mut characters = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if i + 1 < bytes.len() && is_double_byte(pair(i)) {
+            characters.push(pair(i));
+            i += 2;
+        } else if i + 3 < bytes.len() && is_four_byte(pair(i), pair(i + 2)) {
+            characters.extend([pair(i), pair(i + 2)]);
+            i += 4;
+        } else {
+            characters.push(bytes[i] as u32);
+            i += 1;
+        }
+    }
+    Ok((Some(eci), characters))
+}
+
+/// Mode of every character, minimising the length of the bit stream
+fn choose_modes(characters: &[u32]) -> Vec<Mode> {
+    let index = |mode: Mode| mode as usize;
+    let length = characters.len();
+    // cheapest stream up to the current character that ends in each mode
+    let mut costs: [u32; 7] = Mode::ALL.map(|mode| mode.head() * COST_UNIT);
+    // mode of each character on the cheapest stream ending in each mode
+    let mut char_modes: Vec<[Option<Mode>; 7]> = vec![[None; 7]; length];
+
+    let mut numeric_end = 0;
+    let mut numeric_cost = 0;
+    let mut four_byte_end = 0;
+    let mut text_submode = 1;
+
+    for (i, &c) in characters.iter().enumerate() {
+        let mut current = [0; 7];
+        let modes = &mut char_modes[i];
+        let mut extend = |mode: Mode, cost: u32| {
+            current[index(mode)] = costs[index(mode)] + cost;
+            modes[index(mode)] = Some(mode);
+        };
+
+        // digits are grouped by three, each group costing ten bits
+        let (text1, text2) = if i < numeric_end || is_digit(c) {
+            if i >= numeric_end {
+                let digits = characters[i..]
+                    .iter()
+                    .take(3)
+                    .take_while(|&&d| is_digit(d))
+                    .count();
+                numeric_end = i + digits;
+                numeric_cost = 10 * COST_UNIT / digits as u32;
+            }
+            extend(Mode::Numeric, numeric_cost);
+            (true, false)
+        } else {
+            (text1_value(c).is_some(), text2_value(c).is_some())
+        };
+
+        if text1 || text2 {
+            if (text_submode == 1 && text2) || (text_submode == 2 && text1) {
+                extend(Mode::Text, 12 * COST_UNIT);
+                text_submode = if text2 { 2 } else { 1 };
+            } else {
+                extend(Mode::Text, 6 * COST_UNIT);
+            }
+        } else {
+            text_submode = 1;
+        }
+
+        let width = if c > 0xFF { 16 } else { 8 };
+        extend(Mode::Binary, width * COST_UNIT);
+
+        if i < four_byte_end || (i + 1 < length && is_four_byte(c, characters[i + 1])) {
+            four_byte_end = four_byte_end.max(i + 2);
+            // the indicator and the 21 bit value, spread over both halves
+            extend(Mode::FourByte, (4 + 21) * COST_UNIT / 2);
+        } else if is_double_byte(c) {
+            extend(Mode::DoubleByte, 15 * COST_UNIT);
+            if is_region1(c) {
+                extend(Mode::Region1, 12 * COST_UNIT);
+            } else if is_region2(c) {
+                extend(Mode::Region2, 12 * COST_UNIT);
+            }
+        }
+
+        if i == length - 1 {
+            for mode in Mode::ALL {
+                if modes[index(mode)].is_some() {
+                    current[index(mode)] += mode.terminator() * COST_UNIT;
+                }
+            }
+        }
+
+        // start a new segment after this character to switch modes
+        for to in Mode::ALL {
+            for from in Mode::ALL {
+                if from == to || modes[index(from)].is_none() {
+                    continue;
+                }
+                let switched = current[index(from)] + from.switch(to) * COST_UNIT;
+                if modes[index(to)].is_none() || switched < current[index(to)] {
+                    current[index(to)] = switched;
+                    modes[index(to)] = Some(from);
+                }
+            }
+        }
+
+        costs = current;
+    }
+
+    // the first of the cheapest modes wins
+    let mut mode = Mode::ALL
+        .into_iter()
+        .min_by_key(|&mode| costs[index(mode)])
+        .unwrap_or(Mode::Binary);
+    let mut modes = vec![Mode::Binary; length];
+    for i in (0..length).rev() {
+        mode = char_modes[i][index(mode)].unwrap_or(Mode::Binary);
+        modes[i] = mode;
+    }
+    modes
+}
+
+fn append(bits: &mut BitArray, value: u32, count: usize) -> Result<()> {
+    bits.appendBits(value as usize, count)
+}
+
+fn append_eci(bits: &mut BitArray, value: u32) -> Result<()> {
+    append(bits, MODE_ECI, 4)?;
+    if value < 0x80 {
+        append(bits, value, 8)
+    } else if value < 0x4000 {
+        append(bits, 0x2, 2)?;
+        append(bits, value, 14)
+    } else {
+        append(bits, 0x6, 3)?;
+        append(bits, value, 21)
+    }
+}
+
+fn append_numeric(bits: &mut BitArray, digits: &[u32]) -> Result<()> {
+    append(bits, MODE_NUMERIC, 4)?;
+    let mut last = 0;
+    for group in digits.chunks(3) {
+        let value = group.iter().fold(0, |acc, &d| acc * 10 + d - b'0' as u32);
+        append(bits, value, 10)?;
+        last = group.len();
+    }
+    append(bits, NUMERIC_END[last - 1], 10)
+}
+
+fn append_text(bits: &mut BitArray, text: &[u32]) -> Result<()> {
+    append(bits, MODE_TEXT, 4)?;
+    let mut submode = 1;
+    for &c in text {
+        let (required, value) = text_value(c).ok_or(Exceptions::ILLEGAL_STATE)?;
+        if required != submode {
+            append(bits, TEXT_SWITCH, 6)?;
+            submode = required;
+        }
+        append(bits, value, 6)?;
+    }
+    append(bits, TEXT_END, 6)
+}
+
+fn append_binary(bits: &mut BitArray, data: &[u32]) -> Result<()> {
+    append(bits, MODE_BINARY, 4)?;
+    let count: usize = data.iter().map(|&c| if c > 0xFF { 2 } else { 1 }).sum();
+    append(bits, count as u32, 13)?;
+    for &c in data {
+        append(bits, c, if c > 0xFF { 16 } else { 8 })?;
+    }
+    Ok(())
+}
+
+/// Region runs directly following the other region share its mode indicator, and end
+/// with a switch instead of a terminator when the other region follows
+fn append_region(
+    bits: &mut BitArray,
+    mode: Mode,
+    data: &[u32],
+    previous: Option<Mode>,
+    next: Option<Mode>,
+) -> Result<()> {
+    let (indicator, other) = if mode == Mode::Region1 {
+        (MODE_REGION_1, Mode::Region2)
+    } else {
+        (MODE_REGION_2, Mode::Region1)
+    };
+    if previous != Some(other) {
+        append(bits, indicator, 4)?;
+    }
+    for &c in data {
+        let (first, second) = (c >> 8, c & 0xFF);
+        let value = match first {
+            0xA1..=0xA3 => (first - 0xA1) * 0x5E + second - 0xA1 + REGION_1_SYMBOLS,
+            0xA8 => second - 0xA1 + REGION_1_PINYIN,
+            0xB0..=0xD7 => (first - 0xB0) * 0x5E + second - 0xA1,
+            _ => (first - 0xD8) * 0x5E + second - 0xA1,
+        };
+        append(bits, value, 12)?;
+    }
+    let end = if next == Some(other) {
+        REGION_SWITCH
+    } else {
+        REGION_END
+    };
+    append(bits, end, 12)
+}
+
+fn append_double_byte(bits: &mut BitArray, data: &[u32]) -> Result<()> {
+    append(bits, MODE_DOUBLE_BYTE, 4)?;
+    for &c in data {
+        let (first, second) = (c >> 8, c & 0xFF);
+        let second = if second < 0x80 {
+            second - 0x40
+        } else {
+            second - 0x41
+        };
+        append(bits, (first - 0x81) * 0xBE + second, 15)?;
+    }
+    append(bits, DOUBLE_BYTE_END, 15)
+}
+
+fn append_four_byte(bits: &mut BitArray, data: &[u32]) -> Result<()> {
+    for pair in data.chunks(2) {
+        let [high, low] = pair else {
+            return Err(Exceptions::ILLEGAL_STATE);
+        };
+        append(bits, MODE_FOUR_BYTE, 4)?;
+        let value = ((high >> 8) - 0x81) * 12600
+            + ((high & 0xFF) - 0x30) * 1260
+            + ((low >> 8) - 0x81) * 10
+            + ((low & 0xFF) - 0x30);
+        append(bits, value, 21)?;
+    }
+    Ok(())
+}
+
+/// Chooses the smallest version holding `bits`, raises the error correction level as far
+/// as that version allows unless it was requested, and pads the data with zero bits
+fn build_data_codewords(
+    bits: &BitArray,
+    ec_level: Option<ErrorCorrectionLevel>,
+) -> Result<(Version, ErrorCorrectionLevel, Vec<u8>)> {
+    let needed = bits.get_size().div_ceil(8);
+    let minimum = ec_level.unwrap_or(ErrorCorrectionLevel::L1);
+    let version = (MIN_VERSION..=MAX_VERSION)
+        .filter_map(|number| Version::getVersionForNumber(number).ok())
+        .find(|v| v.getDataCodewords(minimum) >= needed)
+        .ok_or_else(|| Exceptions::writer_with(format!("data too big {needed}/{minimum}")))?;
+
+    let ec_level = match ec_level {
+        Some(ec_level) => ec_level,
+        None => [
+            ErrorCorrectionLevel::L4,
+            ErrorCorrectionLevel::L3,
+            ErrorCorrectionLevel::L2,
+        ]
+        .into_iter()
+        .find(|&level| version.getDataCodewords(level) >= needed)
+        .unwrap_or(minimum),
+    };
+
+    let mut data = vec![0u8; version.getDataCodewords(ec_level)];
+    bits.toBytes(0, &mut data, 0, needed);
+    Ok((version, ec_level, data))
+}
+
+/// Splits the data into blocks, appends their error correction codewords and interleaves
+/// the result
+pub fn add_error_correction(
+    data: &[u8],
+    version: Version,
+    ec_level: ErrorCorrectionLevel,
+) -> Result<Vec<u8>> {
+    let mut encoder = ReedSolomonEncoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::HanXinField256,
+    ))?;
+    let mut stream = Vec::with_capacity(version.getTotalCodewords());
+    let mut offset = 0;
+    for (data_count, ec_count) in version.getECBlocks(ec_level) {
+        let mut block: Vec<i32> = data[offset..offset + data_count]
+            .iter()
+            .map(|&b| b as i32)
+            .collect();
+        block.resize(data_count + ec_count, 0);
+        encoder.encode(&mut block, ec_count)?;
+        stream.extend(block.into_iter().map(|cw| cw as u8));
+        offset += data_count;
+    }
+
+    Ok((0..PICKET_FENCE_STEP)
+        .flat_map(|start| stream.iter().skip(start).step_by(PICKET_FENCE_STEP))
+        .copied()
+        .collect())
+}
+
+fn build_matrix(
+    codewords: &[u8],
+    version: Version,
+    ec_level: ErrorCorrectionLevel,
+    mask: u8,
+) -> Result<BitMatrix> {
+    let size = version.getDimensionForVersion();
+    let mut matrix = BitMatrix::with_single_dimension(size)?;
+    draw_function_patterns(version, &mut matrix);
+
+    let info = encode_function_info(version, ec_level, mask)?;
+    for positions in function_info_positions(size) {
+        for (&(x, y), &bit) in positions.iter().zip(info.iter()) {
+            if bit {
+                matrix.set(x, y);
+            }
+        }
+    }
+
+    for (i, (x, y)) in data_positions(version).into_iter().enumerate() {
+        let bit = codewords
+            .get(i / 8)
+            .is_some_and(|cw| cw & (0x80 >> (i % 8)) != 0);
+        if bit != is_masked(mask, x, y) {
+            matrix.set(x, y);
+        }
+    }
+    Ok(matrix)
+}
+
+/// Penalises sequences resembling the finder patterns next to a light area of three
+/// modules, and runs of three or more equal modules (Table 9)
+fn penalty(matrix: &BitMatrix) -> u32 {
+    let size = matrix.getWidth() as usize;
+    let mut score = 0;
+    for transpose in [false, true] {
+        for a in 0..size {
+            let line: Vec<bool> = (0..size)
+                .map(|b| {
+                    let (x, y) = if transpose { (a, b) } else { (b, a) };
+                    matrix.get(x as u32, y as u32)
+                })
+                .collect();
+
+            let mut b = 0;
+            while b + 7 <= size {
+                let window = &line[b..b + 7];
+                if window == [true, false, true, false, true, true, true]
+                    || window == [true, true, true, false, true, false, true]
+                {
+                    let light_before = line[b.saturating_sub(3)..b].iter().all(|&m| !m);
+                    let light_after = line[b + 7..].iter().take(3).all(|&m| !m);
+                    if light_before || light_after {
+                        score += 50;
+                    }
+                    // skip to the next possible match
+                    b += 1;
+                }
+                b += 1;
+            }
+
+            let mut run = 0;
+            let mut color = false;
+            for &module in &line {
+                if module == color {
+                    run += 1;
+                } else {
+                    if run >= 3 {
+                        score += 4 * run;
+                    }
+                    run = 1;
+                    color = module;
+                }
+            }
+            if run >= 3 {
+                score += 4 * run;
+            }
+        }
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_values_are_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for c in 0..0x80 {
+            if let Some(value) = text_value(c) {
+                assert!(value.1 < TEXT_SWITCH);
+                assert!(seen.insert(value), "{c:#x}");
+            }
+        }
+    }
+
+    #[test]
+    fn chooses_compact_modes() {
+        let hints = EncodeHints::default();
+        let numeric = encode_high_level("1234567890", &hints).unwrap();
+        // mode, four groups and the terminator
+        assert_eq!(numeric.get_size(), 4 + 4 * 10 + 10);
+
+        // GB 18030 is announced through ECI 32
+        let region = encode_high_level("汉信码", &hints).unwrap();
+        assert_eq!(region.get_size(), 4 + 8 + 4 + 3 * 12 + 12);
+
+        // short digit runs stay in text mode
+        let text = encode_high_level("AB12CD", &hints).unwrap();
+        assert_eq!(text.get_size(), 4 + 6 * 6 + 6);
+
+        let eci = encode_high_level(
+            "é",
+            &EncodeHints::default().with(crate::EncodeHintValue::CharacterSet("UTF-8".into())),
+        )
+        .unwrap();
+        assert_eq!(eci.get_size(), 4 + 8 + 4 + 13 + 2 * 8);
+    }
+
+    #[test]
+    fn symbol_sizes() {
+        let hints = EncodeHints::default();
+        let small = encode("Han Xin", None, &hints).unwrap();
+        assert_eq!(small.getWidth(), 23);
+        let large = encode(&"汉".repeat(1000), Some(ErrorCorrectionLevel::L4), &hints).unwrap();
+        assert!(large.getWidth() > 100);
+        assert!(encode(&"汉".repeat(10000), Some(ErrorCorrectionLevel::L4), &hints).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::Exceptions;
+use crate::common::Result;
+
+/// The four error correction levels of Han Xin Code
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ErrorCorrectionLevel {
+    /// L1 = ~8% correction
+    L1,
+    /// L2 = ~15% correction
+    L2,
+    /// L3 = ~23% correction
+    L3,
+    /// L4 = ~30% correction
+    L4,
+}
+
+impl ErrorCorrectionLevel {
+    /// Level encoded by the two error correction bits of the function information
+    pub fn forBits(bits: u8) -> Result<Self> {
+        match bits {
+            0 => Ok(Self::L1),
+            1 => Ok(Self::L2),
+            2 => Ok(Self::L3),
+            3 => Ok(Self::L4),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "{bits} is not a valid bit selection"
+            ))),
+        }
+    }
+
+    pub const fn get_value(&self) -> u8 {
+        match self {
+            ErrorCorrectionLevel::L1 => 0,
+            ErrorCorrectionLevel::L2 => 1,
+            ErrorCorrectionLevel::L3 => 2,
+            ErrorCorrectionLevel::L4 => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for ErrorCorrectionLevel {
+    type Error = Exceptions;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        ErrorCorrectionLevel::forBits(value)
+    }
+}
+
+impl From<ErrorCorrectionLevel> for u8 {
+    fn from(value: ErrorCorrectionLevel) -> Self {
+        value.get_value()
+    }
+}
+
+impl FromStr for ErrorCorrectionLevel {
+    type Err = Exceptions;
+
+    /// Accepts the level names `L1` to `L4` or the numbers `1` to `4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "L1" | "1" => Ok(ErrorCorrectionLevel::L1),
+            "L2" | "2" => Ok(ErrorCorrectionLevel::L2),
+            "L3" | "3" => Ok(ErrorCorrectionLevel::L3),
+            "L4" | "4" => Ok(ErrorCorrectionLevel::L4),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "could not parse {s} into an ec level"
+            ))),
+        }
+    }
+}
+
+impl Display for ErrorCorrectionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorCorrectionLevel::L1 => "L1",
+            ErrorCorrectionLevel::L2 => "L2",
+            ErrorCorrectionLevel::L3 => "L3",
+            ErrorCorrectionLevel::L4 => "L4",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorCorrectionLevel;
+
+    #[test]
+    fn parse_and_display() {
+        for level in [
+            ErrorCorrectionLevel::L1,
+            ErrorCorrectionLevel::L2,
+            ErrorCorrectionLevel::L3,
+            ErrorCorrectionLevel::L4,
+        ] {
+            assert_eq!(level.to_string().parse::<ErrorCorrectionLevel>(), Ok(level));
+            assert_eq!(ErrorCorrectionLevel::forBits(level.get_value()), Ok(level));
+        }
+        assert_eq!("3".parse(), Ok(ErrorCorrectionLevel::L3));
+        assert!("H".parse::<ErrorCorrectionLevel>().is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, ImmutableReader, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    common::{DetectorRXingResult, Result},
+};
+
+use super::{decoder, detector};
+
+/// Locates and decodes a Han Xin Code symbol in an image using its four finder patterns.
+#[derive(Default, Clone)]
+pub struct HanXinReader;
+
+impl Reader for HanXinReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl ImmutableReader for HanXinReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl HanXinReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let detector_result = detector::detect(image.get_black_matrix())?;
+        let decoder_result = decoder::decode(detector_result.getBits(), hints)?;
+
+        let mut result = RXingResult::new(
+            decoder_result.getText(),
+            decoder_result.getRawBytes().clone(),
+            detector_result.getPoints().to_vec(),
+            BarcodeFormat::HAN_XIN,
+        );
+
+        let byte_segments = decoder_result.getByteSegments();
+        if !byte_segments.is_empty() {
+            result.putMetadata(
+                RXingResultMetadataType::BYTE_SEGMENTS,
+                RXingResultMetadataValue::ByteSegments(byte_segments.clone()),
+            );
+        }
+        result.putMetadata(
+            RXingResultMetadataType::ERROR_CORRECTION_LEVEL,
+            RXingResultMetadataValue::ErrorCorrectionLevel(decoder_result.getECLevel().to_owned()),
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, EncodeHintValue, EncodeHints,
+        RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+        common::{BitMatrix, HybridBinarizer, Result},
+    };
+
+    use super::HanXinReader;
+    use crate::hanxin::HanXinWriter;
+
+    /// "汉信码" as generated by zint, converted to GB 18030 without an ECI designator
+    const ZINT_SYMBOL: &str = "
+        11111110100001001111111
+        10000000001010100000001
+        10111110110010101111101
+        10100000010101100000101
+        10101110001111101110101
+        10101110000010001110101
+        10101110111100101110101
+        00000000111010000000000
+        00010101101010000000000
+        11000001111000000100111
+        01000111011011011011111
+        01111000011110101000011
+        01001011101001001001000
+        00010000100011011011101
+        00000000010000110101000
+        00000000000001100000000
+        11111110111011101110101
+        00000010000111001110101
+        11111010111101001110101
+        00001010101011000000101
+        11101010110000101111101
+        11101010101111000000001
+        11101010000100101111111
+    ";
+
+    fn read(matrix: &BitMatrix) -> Result<crate::RXingResult> {
+        let image: image::DynamicImage = matrix.into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        HanXinReader.decode(&mut bitmap)
+    }
+
+    #[test]
+    fn round_trip() {
+        let contents = "汉信码 Han Xin Code";
+        let matrix = HanXinWriter
+            .encode(contents, &BarcodeFormat::HAN_XIN, 200, 200)
+            .unwrap();
+        let result = read(&matrix).unwrap();
+        assert_eq!(result.getText(), contents);
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::HAN_XIN);
+    }
+
+    #[test]
+    fn round_trip_large_version() {
+        let contents = "供应商编号 0123456789 商品名称 ".repeat(12);
+        let hints = EncodeHints::default().with(EncodeHintValue::ErrorCorrection("L3".into()));
+        let matrix = HanXinWriter
+            .encode_with_hints(&contents, &BarcodeFormat::HAN_XIN, 500, 500, &hints)
+            .unwrap();
+        let result = read(&matrix).unwrap();
+        assert_eq!(result.getText(), contents);
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::ERROR_CORRECTION_LEVEL),
+            Some(&RXingResultMetadataValue::ErrorCorrectionLevel(
+                "L3".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn reads_zint_symbol() {
+        let rows: Vec<&str> = ZINT_SYMBOL.split_whitespace().collect();
+        let symbol = BitMatrix::parse_strings(&rows.join("\n"), "1", "0").unwrap();
+        let (scale, quiet_zone) = (6, 3);
+        let size = (symbol.getWidth() + 2 * quiet_zone) * scale;
+        let mut matrix = BitMatrix::with_single_dimension(size).unwrap();
+        for y in 0..symbol.getHeight() {
+            for x in 0..symbol.getWidth() {
+                if symbol.get(x, y) {
+                    matrix
+                        .setRegion(
+                            (x + quiet_zone) * scale,
+                            (y + quiet_zone) * scale,
+                            scale,
+                            scale,
+                        )
+                        .unwrap();
+                }
+            }
+        }
+        assert_eq!(read(&matrix).unwrap().getText(), "汉信码");
+    }
+
+    #[test]
+    fn rotated_symbol() {
+        for turns in 1..4 {
+            let mut matrix = HanXinWriter
+                .encode("turned around", &BarcodeFormat::HAN_XIN, 150, 150)
+                .unwrap();
+            for _ in 0..turns {
+                matrix.rotate90();
+            }
+            let result = read(&matrix).unwrap();
+            assert_eq!(result.getText(), "turned around");
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    common::{BitMatrix, Result},
+};
+
+use super::{ErrorCorrectionLevel, encoder};
+
+/// Default quiet zone around the symbol, in modules
+const QUIET_ZONE_SIZE: u32 = 3;
+
+/// Renders a Han Xin Code symbol as a [`BitMatrix`].
+///
+/// Text is encoded as ISO-8859-1 where possible and as GB 18030 otherwise.
+/// [`EncodeHints::CharacterSet`] selects another character set through ECI,
+/// [`EncodeHints::ErrorCorrection`] fixes the level as `L1` to `L4` (or `1` to `4`) instead of
+/// the highest one fitting the symbol, and [`EncodeHints::Margin`] sets the quiet zone in
+/// modules.
+#[derive(Default)]
+pub struct HanXinWriter;
+
+impl Writer for HanXinWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        if contents.is_empty() {
+            return Err(Exceptions::illegal_argument_with("Found empty contents"));
+        }
+
+        if format != &BarcodeFormat::HAN_XIN {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Can only encode HAN_XIN, but got {format:?}"
+            )));
+        }
+
+        if width < 0 || height < 0 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Requested dimensions can't be negative: {width}x{height}"
+            )));
+        }
+
+        let ec_level = hints
+            .ErrorCorrection
+            .as_ref()
+            .map(|ec_level| ec_level.parse::<ErrorCorrectionLevel>())
+            .transpose()?;
+
+        let quiet_zone = if let Some(margin) = &hints.Margin {
+            margin
+                .parse::<u32>()
+                .map_err(|e| Exceptions::parse_with(format!("could not parse {margin}: {e}")))?
+        } else {
+            QUIET_ZONE_SIZE
+        };
+
+        let symbol = encoder::encode(contents, ec_level, hints)?;
+        Self::render(&symbol, width as u32, height as u32, quiet_zone)
+    }
+}
+
+impl HanXinWriter {
+    fn render(symbol: &BitMatrix, width: u32, height: u32, quiet_zone: u32) -> Result<BitMatrix> {
+        let input_size = symbol.getWidth();
+        let full_size = input_size + 2 * quiet_zone;
+        let output_width = width.max(full_size);
+        let output_height = height.max(full_size);
+
+        let scale = (output_width / full_size).min(output_height / full_size);
+        let left = (output_width - input_size * scale) / 2;
+        let top = (output_height - input_size * scale) / 2;
+
+        let mut output = BitMatrix::new(output_width, output_height)?;
+        for y in 0..input_size {
+            for x in 0..input_size {
+                if symbol.get(x, y) {
+                    output.setRegion(left + x * scale, top + y * scale, scale, scale)?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+}
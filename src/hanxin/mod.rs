@@ -0,0 +1,24 @@
+pub mod common;
+pub mod error_correction_level;
+pub mod symbol;
+pub mod version;
+
+pub use error_correction_level::ErrorCorrectionLevel;
+pub use version::Version;
+
+#[cfg(feature = "decoders")]
+pub mod decoder;
+#[cfg(feature = "decoders")]
+pub mod detector;
+#[cfg(feature = "encoders")]
+pub mod encoder;
+
+#[cfg(feature = "decoders")]
+mod han_xin_reader;
+#[cfg(feature = "decoders")]
+pub use han_xin_reader::*;
+
+#[cfg(feature = "encoders")]
+mod han_xin_writer;
+#[cfg(feature = "encoders")]
+pub use han_xin_writer::*;
@@ -0,0 +1,377 @@
+//! Module layout of a Han Xin Code symbol, shared by the encoder and the decoder.
+//!
+//! Each corner holds a 7x7 finder pattern, a light separator and one row and one column of
+//! function information, filling a 9x9 region. The finder patterns are nested L shapes
+//! around a 3x3 core; the top left one is turned differently from the other three, which
+//! fixes the orientation of the symbol. From version 4 on the symbol is divided into
+//! regions by alignment patterns (Annex A), with assistant alignment patterns along the
+//! edges. All remaining modules carry codewords, filled row by row, left to right, most
+//! significant bit first.
+
+use crate::{
+    Exceptions,
+    common::{
+        BitMatrix, Result,
+        reedsolomon::{
+            PredefinedGenericGF, ReedSolomonDecoder, ReedSolomonEncoder, get_predefined_genericgf,
+        },
+    },
+};
+
+use super::{ErrorCorrectionLevel, Version};
+
+/// Side of the square reserved at every corner
+pub const CORNER_SIZE: u32 = 9;
+
+/// Number of function information bits, each stored twice
+pub const FUNCTION_INFO_BITS: usize = 34;
+
+/// Number of masks, mask 0 leaves the data unchanged
+pub const MASK_COUNT: u8 = 4;
+
+const FUNCTION_INFO_DATA_NIBBLES: usize = 3;
+const FUNCTION_INFO_EC_NIBBLES: usize = 4;
+
+const FINDER_TOP_LEFT: [u8; 7] = [0x7F, 0x40, 0x5F, 0x50, 0x57, 0x57, 0x57];
+const FINDER_TOP_RIGHT: [u8; 7] = [0x7F, 0x01, 0x7D, 0x05, 0x75, 0x75, 0x75];
+const FINDER_BOTTOM_RIGHT: [u8; 7] = [0x75, 0x75, 0x75, 0x05, 0x7D, 0x01, 0x7F];
+
+/// Function modules of a symbol: the ones reserved for patterns and function information,
+/// and which of them are dark
+struct Layout {
+    size: u32,
+    reserved: BitMatrix,
+    dark: BitMatrix,
+}
+
+impl Layout {
+    fn new(version: Version) -> Self {
+        let size = version.getDimensionForVersion();
+        let mut layout = Self {
+            size,
+            reserved: BitMatrix::with_single_dimension(size).expect("version sizes are valid"),
+            dark: BitMatrix::with_single_dimension(size).expect("version sizes are valid"),
+        };
+
+        layout.finder(&FINDER_TOP_LEFT, 0, 0);
+        layout.finder(&FINDER_TOP_RIGHT, 0, size - 7);
+        layout.finder(&FINDER_TOP_RIGHT, size - 7, 0);
+        layout.finder(&FINDER_BOTTOM_RIGHT, size - 7, size - 7);
+
+        // separators and function information, along both edges of every corner
+        for line in [7, 8] {
+            for i in 0..=line {
+                for (x, y) in [
+                    (i, line),
+                    (line, i),
+                    (size - 1 - i, line),
+                    (line, size - 1 - i),
+                    (size - 1 - line, i),
+                    (i, size - 1 - line),
+                    (size - 1 - i, size - 1 - line),
+                    (size - 1 - line, size - 1 - i),
+                ] {
+                    layout.reserved.set(x, y);
+                }
+            }
+        }
+
+        if let Some(parameters) = version.getAlignmentParameters() {
+            layout.alignment_patterns(parameters);
+        }
+        layout
+    }
+
+    fn finder(&mut self, pattern: &[u8; 7], left: u32, top: u32) {
+        for (dy, row) in pattern.iter().enumerate() {
+            for dx in 0..7 {
+                let (x, y) = (left + dx, top + dy as u32);
+                self.reserved.set(x, y);
+                if row & (0x40 >> dx) != 0 {
+                    self.dark.set(x, y);
+                }
+            }
+        }
+    }
+
+    /// Claims a module for a pattern unless it is outside the symbol or already taken
+    fn plot(&mut self, x: i32, y: i32, dark: bool) {
+        let size = self.size as i32;
+        if x < 0 || y < 0 || x >= size || y >= size {
+            return;
+        }
+        let (x, y) = (x as u32, y as u32);
+        if !self.reserved.get(x, y) {
+            self.reserved.set(x, y);
+            if dark {
+                self.dark.set(x, y);
+            }
+        }
+    }
+
+    /// A dark line along the top and right edge of a region, with a light line inside it
+    fn alignment(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.plot(x, y, true);
+        self.plot(x - 1, y + 1, false);
+        for i in 1..=width {
+            self.plot(x - i, y, true);
+            self.plot(x - i - 1, y + 1, false);
+        }
+        for i in 1..height {
+            self.plot(x, y + i, true);
+            self.plot(x - 1, y + i + 1, false);
+        }
+    }
+
+    /// A dark module surrounded by light ones
+    fn assistant(&mut self, x: i32, y: i32) {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                self.plot(x + dx, y + dy, dx == 0 && dy == 0);
+            }
+        }
+    }
+
+    /// Width of region `index`: the first `m` regions are `k` modules wide, the following
+    /// ones `r - 1`
+    fn region_size(index: usize, (k, r, m): (u32, u32, u32)) -> i32 {
+        (if (index as u32) < m { k } else { r - 1 }) as i32
+    }
+
+    fn alignment_patterns(&mut self, parameters: (u32, u32, u32)) {
+        let size = self.size as i32;
+        let last = size - 1;
+        let odd_m = parameters.2 % 2 == 1;
+
+        // offsets of the regions from the top and from the right edge
+        let mut boundaries = Vec::new();
+        let mut offset = 0;
+        while offset < size {
+            boundaries.push(offset);
+            offset += Self::region_size(boundaries.len() - 1, parameters);
+        }
+
+        // assistant patterns down the left and right edges
+        for (index, &y) in boundaries.iter().enumerate() {
+            if index % 2 == 0 {
+                if odd_m {
+                    self.assistant(0, y);
+                }
+            } else {
+                if !odd_m {
+                    self.assistant(0, y);
+                }
+                self.assistant(last, y);
+            }
+        }
+
+        // and along the bottom and top edges, counted from the right
+        for (index, &offset) in boundaries.iter().enumerate() {
+            let x = last - offset;
+            if index % 2 == 0 {
+                if odd_m {
+                    self.assistant(x, last);
+                }
+            } else {
+                if !odd_m {
+                    self.assistant(x, last);
+                }
+                self.assistant(x, 0);
+            }
+        }
+
+        // alignment patterns on every other region, in a checkerboard
+        for (row, &y) in boundaries.iter().enumerate() {
+            for (column, &offset) in boundaries.iter().enumerate() {
+                let x = last - offset;
+                if (row + column) % 2 == 0 && !(y == 0 && x == last) {
+                    self.alignment(
+                        x,
+                        y,
+                        Self::region_size(column, parameters),
+                        Self::region_size(row, parameters),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Positions of the codeword modules in placement order
+pub fn data_positions(version: Version) -> Vec<(u32, u32)> {
+    let layout = Layout::new(version);
+    let size = layout.size;
+    let mut positions = Vec::with_capacity((size * size) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            if !layout.reserved.get(x, y) {
+                positions.push((x, y));
+            }
+        }
+    }
+    positions
+}
+
+/// Draws the finder and alignment patterns. Function information is left light.
+pub fn draw_function_patterns(version: Version, matrix: &mut BitMatrix) {
+    let layout = Layout::new(version);
+    for y in 0..layout.size {
+        for x in 0..layout.size {
+            if layout.dark.get(x, y) {
+                matrix.set(x, y);
+            }
+        }
+    }
+}
+
+/// Whether mask `mask` inverts the data module at `(x, y)`
+pub fn is_masked(mask: u8, x: u32, y: u32) -> bool {
+    let i = y + 1;
+    let j = x + 1;
+    match mask {
+        1 => (i + j) % 2 == 0,
+        2 => ((i + j) % 3 + j % 3) % 2 == 0,
+        3 => (i % j + j % i + i % 3 + j % 3) % 2 == 0,
+        _ => false,
+    }
+}
+
+/// Positions of the two copies of the function information bits. The first copy runs
+/// along the inner edges of the top left and top right corners, the second one is its
+/// point reflection through the centre of the symbol.
+pub fn function_info_positions(size: u32) -> [[(u32, u32); FUNCTION_INFO_BITS]; 2] {
+    let edge = CORNER_SIZE - 1;
+    let mut primary = [(0, 0); FUNCTION_INFO_BITS];
+    for (i, position) in primary.iter_mut().enumerate() {
+        let i = i as u32;
+        *position = match i {
+            0..=8 => (i, edge),
+            9..=16 => (edge, 2 * edge - i),
+            17..=25 => (size - 1 - edge, i - 17),
+            _ => (size - 1 - edge + i - 25, edge),
+        };
+    }
+    let mut copy = primary;
+    for position in copy.iter_mut() {
+        *position = (size - 1 - position.0, size - 1 - position.1);
+    }
+    [primary, copy]
+}
+
+/// Function information bits for a symbol: the version, error correction level and mask,
+/// protected by four Reed-Solomon codewords over GF(16). The last six bits stay light.
+pub fn encode_function_info(
+    version: Version,
+    ec_level: ErrorCorrectionLevel,
+    mask: u8,
+) -> Result<[bool; FUNCTION_INFO_BITS]> {
+    let value = ((version.getVersionNumber() + 20) << 4)
+        | ((ec_level.get_value() as u32) << 2)
+        | (mask as u32 & 0x3);
+    let mut nibbles = [0i32; FUNCTION_INFO_DATA_NIBBLES + FUNCTION_INFO_EC_NIBBLES];
+    for (i, nibble) in nibbles
+        .iter_mut()
+        .take(FUNCTION_INFO_DATA_NIBBLES)
+        .enumerate()
+    {
+        *nibble = ((value >> (4 * (FUNCTION_INFO_DATA_NIBBLES - 1 - i))) & 0xF) as i32;
+    }
+    ReedSolomonEncoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::HanXinFunctionInfo,
+    ))?
+    .encode(&mut nibbles, FUNCTION_INFO_EC_NIBBLES)?;
+
+    let mut bits = [false; FUNCTION_INFO_BITS];
+    for (i, bit) in bits.iter_mut().take(4 * nibbles.len()).enumerate() {
+        *bit = nibbles[i / 4] & (0x8 >> (i % 4)) != 0;
+    }
+    Ok(bits)
+}
+
+/// Recovers version, error correction level and mask from function information bits
+pub fn decode_function_info(
+    bits: &[bool; FUNCTION_INFO_BITS],
+) -> Result<(Version, ErrorCorrectionLevel, u8)> {
+    let mut nibbles = [0i32; FUNCTION_INFO_DATA_NIBBLES + FUNCTION_INFO_EC_NIBBLES];
+    for (i, nibble) in nibbles.iter_mut().enumerate() {
+        *nibble = bits[4 * i..4 * i + 4]
+            .iter()
+            .fold(0, |acc, &b| (acc << 1) | b as i32);
+    }
+    ReedSolomonDecoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::HanXinFunctionInfo,
+    ))
+    .decode(&mut nibbles, FUNCTION_INFO_EC_NIBBLES as i32)
+    .map_err(|_| Exceptions::FORMAT)?;
+
+    let value = nibbles[..FUNCTION_INFO_DATA_NIBBLES]
+        .iter()
+        .fold(0u32, |acc, &n| (acc << 4) | n as u32);
+    let number = (value >> 4).checked_sub(20).ok_or(Exceptions::FORMAT)?;
+    let version = Version::getVersionForNumber(number).map_err(|_| Exceptions::FORMAT)?;
+    let ec_level = ErrorCorrectionLevel::forBits(((value >> 2) & 0x3) as u8)?;
+    Ok((version, ec_level, (value & 0x3) as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_info_round_trip() {
+        for number in [1, 4, 40, 84] {
+            let version = Version::getVersionForNumber(number).unwrap();
+            for mask in 0..MASK_COUNT {
+                let mut bits =
+                    encode_function_info(version, ErrorCorrectionLevel::L3, mask).unwrap();
+                // a single damaged nibble is corrected
+                bits[5] = !bits[5];
+                bits[6] = !bits[6];
+                assert_eq!(
+                    decode_function_info(&bits),
+                    Ok((version, ErrorCorrectionLevel::L3, mask))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn function_info_stays_in_corners() {
+        for number in [1, 84] {
+            let version = Version::getVersionForNumber(number).unwrap();
+            let size = version.getDimensionForVersion();
+            let data = data_positions(version);
+            let [primary, copy] = function_info_positions(size);
+            let mut seen = std::collections::HashSet::new();
+            for (x, y) in primary.into_iter().chain(copy) {
+                assert!(!data.contains(&(x, y)));
+                assert!(seen.insert((x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn codewords_fit_the_data_modules() {
+        for number in 1..=84 {
+            let version = Version::getVersionForNumber(number).unwrap();
+            let modules = data_positions(version).len();
+            let needed = 8 * version.getTotalCodewords();
+            // at most a few modules are left over
+            assert!(
+                modules >= needed && modules - needed < 8,
+                "version {number}"
+            );
+        }
+    }
+
+    #[test]
+    fn finder_patterns_do_not_overlap_data() {
+        let version = Version::getVersionForNumber(5).unwrap();
+        let size = version.getDimensionForVersion();
+        let mut matrix = BitMatrix::with_single_dimension(size).unwrap();
+        draw_function_patterns(version, &mut matrix);
+        for (x, y) in data_positions(version) {
+            assert!(!matrix.get(x, y));
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::common::Result;
+use crate::{Exceptions, LuminanceSource};
+
+/// Share of the darkest and of the brightest samples left out of the range tone mapping
+/// stretches, so that specular highlights and dead pixels do not flatten the rest
+const CLIPPED_SHARE: f32 = 0.005;
+
+/// Smallest local deviation, relative to the median one across the image, that
+/// [`ToneMapping::LocalContrast`] scales samples by, to keep sensor noise in flat areas from
+/// being raised to full contrast
+const MIN_LOCAL_DEVIATION: f32 = 0.25;
+
+/// How high precision samples are brought down to 8 bit luminance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMapping {
+    /// Stretches the range of the samples to 0 to 255, apart from the darkest and
+    /// brightest 0.5%
+    Global,
+    /// Normalizes each sample by the mean and deviation of the square `2 * radius + 1`
+    /// samples wide around it, which evens out uneven lighting and raises faint marks.
+    /// The radius should span a few modules.
+    LocalContrast { radius: usize },
+}
+
+impl Default for ToneMapping {
+    fn default() -> Self {
+        Self::LocalContrast { radius: 16 }
+    }
+}
+
+/// A [`LuminanceSource`] for 12 or 16 bit and floating point grey images, such as those of
+/// industrial cameras and X-ray scanners, tone mapped to 8 bit before binarization so that
+/// the precision low contrast marks depend on is not rounded away first.
+#[derive(Debug, Clone)]
+pub struct HdrLuminanceSource {
+    /// Tone mapped samples of the whole image, shared by its crops
+    luma: Arc<[u8]>,
+    data_width: usize,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    invert: bool,
+}
+
+impl HdrLuminanceSource {
+    /// Tone maps `width` by `height` samples of `bit_depth` bits, from 1 to 16, stored in the
+    /// low bits of each value
+    pub fn from_u16(
+        data: &[u16],
+        width: usize,
+        height: usize,
+        bit_depth: u32,
+        mapping: ToneMapping,
+    ) -> Result<Self> {
+        if !(1..=16).contains(&bit_depth) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "bit depth of {bit_depth} is not between 1 and 16"
+            )));
+        }
+        let max = ((1u32 << bit_depth) - 1) as f32;
+        let samples: Vec<f32> = data.iter().map(|&v| v as f32 / max).collect();
+        Self::from_f32(&samples, width, height, mapping)
+    }
+
+    /// Tone maps `width` by `height` floating point samples of any range
+    pub fn from_f32(
+        data: &[f32],
+        width: usize,
+        height: usize,
+        mapping: ToneMapping,
+    ) -> Result<Self> {
+        if data.len() < width * height {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "{} samples are too few for {width}x{height} pixels",
+                data.len()
+            )));
+        }
+        let data = &data[..width * height];
+        if data.iter().any(|v| !v.is_finite()) {
+            return Err(Exceptions::illegal_argument_with(
+                "samples must be finite numbers",
+            ));
+        }
+
+        let luma = match mapping {
+            ToneMapping::Global => {
+                let (low, high) = clipped_range(data);
+                let range = (high - low).max(f32::EPSILON);
+                data.iter().map(|&v| to_byte((v - low) / range)).collect()
+            }
+            ToneMapping::LocalContrast { radius } => local_contrast(data, width, height, radius),
+        };
+        Ok(Self {
+            luma,
+            data_width: width,
+            left: 0,
+            top: 0,
+            width,
+            height,
+            invert: false,
+        })
+    }
+
+    /// Tone maps the luma of an image, keeping the precision of 16 bit and floating point
+    /// images
+    #[cfg(feature = "image")]
+    pub fn from_image(image: &image::DynamicImage, mapping: ToneMapping) -> Result<Self> {
+        let luma = image.to_luma32f();
+        Self::from_f32(
+            luma.as_raw(),
+            luma.width() as usize,
+            luma.height() as usize,
+            mapping,
+        )
+    }
+}
+
+/// The values between which all but the darkest and brightest [`CLIPPED_SHARE`] lie
+fn clipped_range(data: &[f32]) -> (f32, f32) {
+    if data.is_empty() {
+        return (0.0, 1.0);
+    }
+    let mut sorted = data.to_vec();
+    let last = sorted.len() - 1;
+    let clipped = (last as f32 * CLIPPED_SHARE) as usize;
+    let (_, &mut low, _) = sorted.select_nth_unstable_by(clipped, f32::total_cmp);
+    let (_, &mut high, _) = sorted.select_nth_unstable_by(last - clipped, f32::total_cmp);
+    (low, high)
+}
+
+fn to_byte(value: f32) -> u8 {
+    (value * u8::MAX as f32).round().clamp(0.0, u8::MAX as f32) as u8
+}
+
+/// Summed area table of `width` by `height` values, one row and column larger
+fn summed_area(values: &[f32], width: usize, height: usize) -> Vec<f64> {
+    let stride = width + 1;
+    let mut table = vec![0.0; stride * (height + 1)];
+    for y in 0..height {
+        let mut row_sum = 0.0;
+        for x in 0..width {
+            row_sum += values[y * width + x] as f64;
+            let i = (y + 1) * stride + x + 1;
+            table[i] = table[i - stride] + row_sum;
+        }
+    }
+    table
+}
+
+/// Mean of each value of `values` and its neighbors up to `radius` away, from a summed area
+/// table
+fn local_means(table: &[f64], width: usize, height: usize, radius: usize) -> Vec<f32> {
+    let stride = width + 1;
+    let mut means = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let (top, bottom) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+        for x in 0..width {
+            let (left, right) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+            let sum = table[bottom * stride + right]
+                - table[top * stride + right]
+                - table[bottom * stride + left]
+                + table[top * stride + left];
+            means.push((sum / ((right - left) * (bottom - top)) as f64) as f32);
+        }
+    }
+    means
+}
+
+/// Takes the mean of its neighborhood off each sample, which removes uneven lighting, and
+/// scales what is left by its local deviation, so that faint marks reach full contrast. Mid
+/// grey is the local mean, and black and white are 2.5 deviations to either side of it.
+fn local_contrast(data: &[f32], width: usize, height: usize, radius: usize) -> Arc<[u8]> {
+    let means = local_means(&summed_area(data, width, height), width, height, radius);
+    let residuals: Vec<f32> = data.iter().zip(&means).map(|(v, mean)| v - mean).collect();
+    let squares: Vec<f32> = residuals.iter().map(|r| r * r).collect();
+    let variances = local_means(&summed_area(&squares, width, height), width, height, radius);
+
+    let mut deviations: Vec<f32> = variances.iter().map(|v| v.max(0.0).sqrt()).collect();
+    let median = if deviations.is_empty() {
+        0.0
+    } else {
+        let middle = deviations.len() / 2;
+        *deviations.select_nth_unstable_by(middle, f32::total_cmp).1
+    };
+    let min_deviation = (median * MIN_LOCAL_DEVIATION).max(f32::MIN_POSITIVE);
+    residuals
+        .iter()
+        .zip(variances)
+        .map(|(residual, variance)| {
+            let deviation = variance.max(0.0).sqrt().max(min_deviation);
+            to_byte(0.5 + residual / deviation / 5.0)
+        })
+        .collect()
+}
+
+impl LuminanceSource for HdrLuminanceSource {
+    const SUPPORTS_CROP: bool = true;
+    const SUPPORTS_ROTATION: bool = false;
+
+    fn get_row(&self, y: usize) -> Option<Cow<'_, [u8]>> {
+        if y >= self.height {
+            return None;
+        }
+        let start = (self.top + y) * self.data_width + self.left;
+        let row = &self.luma[start..start + self.width];
+        if self.invert {
+            Some(Cow::Owned(self.invert_block_of_bytes(row.to_vec())))
+        } else {
+            Some(Cow::Borrowed(row))
+        }
+    }
+
+    fn get_column(&self, x: usize) -> Vec<u8> {
+        (0..self.height)
+            .map(|y| self.get_luma8_point(x, y))
+            .collect()
+    }
+
+    fn get_matrix(&self) -> Cow<'_, [u8]> {
+        if self.width == self.data_width && !self.invert {
+            let start = self.top * self.data_width;
+            return Cow::Borrowed(&self.luma[start..start + self.width * self.height]);
+        }
+        let mut matrix = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            if let Some(row) = self.get_row(y) {
+                matrix.extend_from_slice(&row);
+            }
+        }
+        Cow::Owned(matrix)
+    }
+
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn crop(&self, left: usize, top: usize, width: usize, height: usize) -> Result<Self> {
+        if left + width > self.width || top + height > self.height {
+            return Err(Exceptions::illegal_argument_with(
+                "Crop rectangle does not fit within image data.",
+            ));
+        }
+        Ok(Self {
+            luma: Arc::clone(&self.luma),
+            data_width: self.data_width,
+            left: self.left + left,
+            top: self.top + top,
+            width,
+            height,
+            invert: self.invert,
+        })
+    }
+
+    fn invert(&mut self) {
+        self.invert = !self.invert;
+    }
+
+    fn get_luma8_point(&self, x: usize, y: usize) -> u8 {
+        let luma = self.luma[(self.top + y) * self.data_width + self.left + x];
+        if self.invert { u8::MAX - luma } else { luma }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LuminanceSource;
+
+    use super::{HdrLuminanceSource, ToneMapping};
+
+    #[test]
+    fn stretches_range() {
+        // 12 bit samples between 1000 and 1100
+        let data: Vec<u16> = (0..256).map(|i| 1000 + (i % 101) as u16).collect();
+        let source = HdrLuminanceSource::from_u16(&data, 16, 16, 12, ToneMapping::Global).unwrap();
+        // Samples 0, 25 and 100, at 1000, 1025 and 1100
+        assert_eq!(source.get_luma8_point(0, 0), 0);
+        assert_eq!(source.get_luma8_point(9, 1), 64);
+        assert_eq!(source.get_luma8_point(4, 6), 255);
+    }
+
+    #[test]
+    fn rejects_invalid_samples() {
+        assert!(HdrLuminanceSource::from_u16(&[0; 4], 2, 2, 17, ToneMapping::Global).is_err());
+        assert!(HdrLuminanceSource::from_u16(&[0; 3], 2, 2, 16, ToneMapping::Global).is_err());
+        assert!(
+            HdrLuminanceSource::from_f32(&[0.0, f32::NAN, 0.0, 0.0], 2, 2, ToneMapping::Global)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn evens_out_lighting() {
+        // A faint step on a steep ramp: the step is darker than the far side of the ramp
+        let (width, height) = (64, 8);
+        let data: Vec<f32> = (0..width * height)
+            .map(|i| {
+                let x = i % width;
+                x as f32 * 0.01 + if x % 8 < 4 { 0.0 } else { 0.005 }
+            })
+            .collect();
+        let source = HdrLuminanceSource::from_f32(
+            &data,
+            width,
+            height,
+            ToneMapping::LocalContrast { radius: 4 },
+        )
+        .unwrap();
+        let row = source.get_row(4).unwrap();
+        // Each step up is brighter than the samples just before it, across the whole ramp
+        for x in (12..56).step_by(8) {
+            assert!(row[x + 1] > row[x - 2], "{x}: {:?}", &row[x - 4..x + 4]);
+        }
+    }
+
+    #[test]
+    fn crops_and_inverts() {
+        let data: Vec<f32> = (0..16).map(|i| i as f32).collect();
+        let mut source = HdrLuminanceSource::from_f32(&data, 4, 4, ToneMapping::Global).unwrap();
+        let cropped = source.crop(1, 2, 2, 2).unwrap();
+        assert_eq!(cropped.get_matrix().as_ref(), [153, 170, 221, 238]);
+        source.invert();
+        assert_eq!(source.get_column(0), [255, 187, 119, 51]);
+    }
+
+    #[cfg(all(feature = "encoders", feature = "qrcode"))]
+    #[test]
+    fn decodes_faint_mark() {
+        use crate::{
+            BarcodeFormat, BinaryBitmap, Reader, Writer,
+            common::HybridBinarizer,
+            qrcode::{QRCodeReader, QRCodeWriter},
+        };
+
+        let matrix = QRCodeWriter
+            .encode("etched", &BarcodeFormat::QR_CODE, 120, 120)
+            .unwrap();
+        let (width, height) = (matrix.getWidth() as usize, matrix.getHeight() as usize);
+        // Marks 24 levels deep out of 65535, under light falling off across the part
+        let data: Vec<u16> = (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as u32, (i / width) as u32);
+                let light = 30000 + x * 6 + y * 4;
+                (light - if matrix.get(x, y) { 24 } else { 0 }) as u16
+            })
+            .collect();
+
+        // Rounded to 8 bits, the marks are gone
+        let flat: Vec<u8> = data.iter().map(|&v| (v >> 8) as u8).collect();
+        let eight_bit = crate::Luma8LuminanceSource::new(flat, width as u32, height as u32);
+        assert!(
+            QRCodeReader
+                .decode(&mut BinaryBitmap::new(HybridBinarizer::new(eight_bit)))
+                .is_err()
+        );
+
+        let source =
+            HdrLuminanceSource::from_u16(&data, width, height, 16, ToneMapping::default()).unwrap();
+        let result = QRCodeReader
+            .decode(&mut BinaryBitmap::new(HybridBinarizer::new(source)))
+            .unwrap();
+        assert_eq!(result.getText(), "etched");
+    }
+}
@@ -48,6 +48,10 @@ pub use dimension::*;
 pub mod aztec;
 #[cfg(feature = "datamatrix")]
 pub mod datamatrix;
+#[cfg(feature = "dotcode")]
+pub mod dotcode;
+#[cfg(feature = "hanxin")]
+pub mod hanxin;
 #[cfg(feature = "maxicode")]
 pub mod maxicode;
 #[cfg(feature = "oned")]
@@ -60,6 +64,9 @@ pub mod qrcode;
 #[cfg(all(feature = "multi_barcode_readers", feature = "decoders"))]
 pub mod multi;
 
+#[cfg(feature = "encoders")]
+pub mod render;
+
 // Simple methods to help detect barcodes in common situations
 pub mod helpers;
 
@@ -89,6 +96,11 @@ mod reader;
 #[cfg(feature = "decoders")]
 pub use reader::*;
 
+#[cfg(all(feature = "async_reader", feature = "decoders"))]
+mod async_reader;
+#[cfg(all(feature = "async_reader", feature = "decoders"))]
+pub use async_reader::*;
+
 #[cfg(feature = "decoders")]
 mod rxing_result_metadata;
 #[cfg(feature = "decoders")]
@@ -142,6 +154,27 @@ mod luma_luma_source;
 #[cfg(feature = "decoders")]
 pub use luma_luma_source::*;
 
+#[cfg(feature = "decoders")]
+mod frame_luminance_source;
+#[cfg(feature = "decoders")]
+pub use frame_luminance_source::*;
+
+#[cfg(feature = "decoders")]
+mod hdr_luminance_source;
+#[cfg(feature = "decoders")]
+pub use hdr_luminance_source::*;
+
+#[cfg(all(
+    feature = "decoders",
+    any(feature = "qrcode", feature = "datamatrix", feature = "aztec")
+))]
+mod multi_frame_fusion;
+#[cfg(all(
+    feature = "decoders",
+    any(feature = "qrcode", feature = "datamatrix", feature = "aztec")
+))]
+pub use multi_frame_fusion::*;
+
 // Writing
 #[cfg(feature = "encoders")]
 mod encode_hints;
@@ -165,3 +198,8 @@ pub use multi_format_writer::*;
 mod writer;
 #[cfg(feature = "encoders")]
 pub use writer::*;
+
+#[cfg(feature = "encoders")]
+mod physical_size;
+#[cfg(feature = "encoders")]
+pub use physical_size::*;
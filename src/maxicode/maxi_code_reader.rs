@@ -25,7 +25,7 @@ use super::{decoder::maxicode_decoder, detector};
 /**
  * This implementation can detect and decode a MaxiCode in an image.
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MaxiCodeReader {
     // private final Decoder decoder = new Decoder();
 }
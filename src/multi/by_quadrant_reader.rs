@@ -15,7 +15,9 @@
  */
 
 use crate::common::Result;
-use crate::{Binarizer, DecodeHints, Exceptions, Point, RXingResult, Reader, point};
+use crate::{
+    Binarizer, DecodeHints, Exceptions, ImmutableReader, Point, RXingResult, Reader, point,
+};
 
 /**
  * This class attempts to decode a barcode from an image, not by scanning the whole image,
@@ -37,14 +39,48 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<crate::RXingResult> {
+        Self::decode_quadrants(image, hints, |image, hints| {
+            self.0.decode_with_hints(image, hints)
+        })
+    }
+
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+impl<T: Reader + ImmutableReader> ImmutableReader for ByQuadrantReader<T> {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        Self::decode_quadrants(image, hints, |image, hints| {
+            self.0.immutable_decode_with_hints(image, hints)
+        })
+    }
+}
+
+impl<T: Reader> ByQuadrantReader<T> {
+    pub fn new(delegate: T) -> Self {
+        Self(delegate)
+    }
+
+    /**
+     * Tries the four quadrants and then the center of the image with the given decode
+     * function, returning the first barcode found with its points made absolute.
+     */
+    fn decode_quadrants<B: Binarizer>(
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+        mut decode: impl FnMut(&mut crate::BinaryBitmap<B>, &DecodeHints) -> Result<RXingResult>,
+    ) -> Result<RXingResult> {
         let width = image.get_width();
         let height = image.get_height();
         let halfWidth = width / 2;
         let halfHeight = height / 2;
 
-        let attempt = self
-            .0
-            .decode_with_hints(&mut image.crop(0, 0, halfWidth, halfHeight), hints);
+        let attempt = decode(&mut image.crop(0, 0, halfWidth, halfHeight), hints);
         // No need to call makeAbsolute as results will be relative to original top left here
         // This is a match because only NotFoundExceptions should be ignored
         match attempt {
@@ -53,9 +89,7 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
         }
 
         // try {
-        let result = self
-            .0
-            .decode_with_hints(&mut image.crop(halfWidth, 0, halfWidth, halfHeight), hints);
+        let result = decode(&mut image.crop(halfWidth, 0, halfWidth, halfHeight), hints);
         // This is a match because only NotFoundExceptions should be ignored
         match result {
             Ok(res) => {
@@ -66,9 +100,7 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
             _ => return result,
         }
 
-        let result = self
-            .0
-            .decode_with_hints(&mut image.crop(0, halfHeight, halfWidth, halfHeight), hints);
+        let result = decode(&mut image.crop(0, halfHeight, halfWidth, halfHeight), hints);
         // This is a match because only NotFoundExceptions should be ignored
         match result {
             Ok(res) => {
@@ -79,7 +111,7 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
             _ => return result,
         }
 
-        let result = self.0.decode_with_hints(
+        let result = decode(
             &mut image.crop(halfWidth, halfHeight, halfWidth, halfHeight),
             hints,
         );
@@ -97,7 +129,7 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
         let quarterWidth = halfWidth / 2;
         let quarterHeight = halfHeight / 2;
         let mut center = image.crop(quarterWidth, quarterHeight, halfWidth, halfHeight);
-        let result = self.0.decode_with_hints(&mut center, hints)?;
+        let result = decode(&mut center, hints)?;
 
         let points = Self::makeAbsolute(
             result.getPoints(),
@@ -107,16 +139,6 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
         Ok(result.with_point(points))
     }
 
-    fn reset(&mut self) {
-        self.0.reset()
-    }
-}
-
-impl<T: Reader> ByQuadrantReader<T> {
-    pub fn new(delegate: T) -> Self {
-        Self(delegate)
-    }
-
     fn makeAbsolute(points: &[Point], leftOffset: f32, topOffset: f32) -> Vec<Point> {
         // let mut result = Vec::new();
         // if !points.is_empty() {
@@ -10,6 +10,9 @@ pub use by_quadrant_reader::*;
 mod generic_multiple_barcode_reader;
 pub use generic_multiple_barcode_reader::*;
 
+mod stream_decoder;
+pub use stream_decoder::*;
+
 #[cfg(test)]
 #[cfg(all(
     feature = "image",
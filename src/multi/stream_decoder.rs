@@ -0,0 +1,630 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, Point, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader, point,
+};
+
+use super::{GenericMultipleBarcodeReader, MultipleBarcodeReader};
+
+/// Tuning of a [`StreamDecoder`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamOptions {
+    /// Frames a symbol may go unseen before it is reported as having left
+    pub max_missed_frames: u32,
+
+    /// Padding added on every side of a predicted region, relative to the size of the symbol
+    pub search_margin: f32,
+
+    /// Every this many frames the whole frame is searched even if every tracked symbol was
+    /// found in its predicted region, so that new symbols are picked up. 0 searches the whole
+    /// frame only when a tracked symbol is missing.
+    pub full_search_interval: u32,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            max_missed_frames: 3,
+            search_margin: 0.5,
+            full_search_interval: 5,
+        }
+    }
+}
+
+/// A symbol followed across frames
+#[derive(Debug, Clone)]
+pub struct TrackedSymbol {
+    /// Unique for the life of the decoder
+    pub id: u64,
+
+    /// The latest decode of the symbol, its points in frame coordinates
+    pub result: RXingResult,
+
+    /// Frame in which the symbol was first seen, counted from 1
+    pub first_frame: u64,
+
+    /// Frame in which the symbol was last seen
+    pub last_frame: u64,
+
+    /// Bounding box of the symbol when last seen, as `(left, top, right, bottom)`, `None` if
+    /// the reader returned no points
+    pub bounds: Option<(f32, f32, f32, f32)>,
+
+    /// Motion of the center of the symbol, in pixels per frame
+    pub velocity: Point,
+}
+
+impl TrackedSymbol {
+    /// Where the symbol is expected in `frame`, padded by `margin` of its size on every side
+    fn predicted_region(&self, frame: u64, margin: f32) -> Option<(f32, f32, f32, f32)> {
+        let (left, top, right, bottom) = self.bounds?;
+        let elapsed = (frame - self.last_frame) as f32;
+        let (dx, dy) = (self.velocity.x * elapsed, self.velocity.y * elapsed);
+        // Each missed frame widens the search to make up for a wrong velocity
+        let pad = margin * elapsed * f32::max(right - left, bottom - top);
+        Some((
+            left + dx - pad,
+            top + dy - pad,
+            right + dx + pad,
+            bottom + dy + pad,
+        ))
+    }
+}
+
+/// What changed in a frame passed to [`StreamDecoder::decode_frame`]
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// A symbol not tracked so far was decoded
+    Entered(TrackedSymbol),
+
+    /// A tracked symbol was not seen for more than [`StreamOptions::max_missed_frames`]
+    Left(TrackedSymbol),
+
+    /// Every part of a QR Code structured append sequence or of a Macro PDF417 file has been
+    /// seen, in this frame or earlier ones, and was joined into one result
+    Assembled(RXingResult),
+}
+
+/**
+ * Decodes successive frames of a video stream, following each symbol from frame to frame.
+ *
+ * A symbol is reported once, with [`StreamEvent::Entered`], when it first decodes, and again
+ * with [`StreamEvent::Left`] once it has gone unseen for a few frames. A decode is taken for a
+ * tracked symbol when it has the same format and contents and lies where the symbol is
+ * expected, so that several symbols with the same contents, such as identical labels, are
+ * tracked separately; contents alone are matched only when the reader returns no points. In
+ * between, each frame is
+ * first searched only where the tracked symbols are expected, their last position moved by their
+ * velocity; the whole frame is searched when one of them is not found there, when nothing is
+ * tracked, and every [`StreamOptions::full_search_interval`] frames. Bitmaps that do not support
+ * cropping are always searched whole.
+ *
+ * Parts of QR Code structured append sequences and segments of Macro PDF417 files are kept
+ * across frames, so that a sequence too large to fit in view at once is emitted with
+ * [`StreamEvent::Assembled`] once every part has been seen. Parts are joined as whole symbols.
+ *
+ * Rows of a PDF417 symbol too large to decode from a single frame are not yet combined across
+ * frames, each frame has to decode the whole symbol on its own. Doing so needs the codewords of
+ * each row from the PDF417 scanning decoder before error correction, which it does not expose.
+ */
+pub struct StreamDecoder<R: Reader> {
+    reader: GenericMultipleBarcodeReader<R>,
+    hints: DecodeHints,
+    options: StreamOptions,
+    tracks: Vec<TrackedSymbol>,
+    next_id: u64,
+    frame: u64,
+    sequences: SequenceAssembler,
+}
+
+impl<R: Reader> StreamDecoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, DecodeHints::default(), StreamOptions::default())
+    }
+
+    pub fn with_options(reader: R, hints: DecodeHints, options: StreamOptions) -> Self {
+        Self {
+            reader: GenericMultipleBarcodeReader::new(reader),
+            hints,
+            options,
+            tracks: Vec::new(),
+            next_id: 0,
+            frame: 0,
+            sequences: SequenceAssembler::default(),
+        }
+    }
+
+    /// The symbols currently tracked
+    pub fn tracked(&self) -> &[TrackedSymbol] {
+        &self.tracks
+    }
+
+    /// Number of frames decoded so far
+    pub fn frame_count(&self) -> u64 {
+        self.frame
+    }
+
+    /// Forgets every tracked symbol and partial sequence, without reporting them as having left
+    pub fn reset(&mut self) {
+        self.tracks.clear();
+        self.sequences = SequenceAssembler::default();
+    }
+
+    /// Decodes the next frame of the stream, returning what changed since the previous one
+    pub fn decode_frame<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Vec<StreamEvent> {
+        self.frame += 1;
+        let width = image.get_width() as f32;
+        let height = image.get_height() as f32;
+
+        let mut found: Vec<RXingResult> = Vec::new();
+        let mut full_search = self.tracks.is_empty() || !image.is_crop_supported();
+        if self.options.full_search_interval > 0
+            && self.frame % self.options.full_search_interval as u64 == 0
+        {
+            full_search = true;
+        }
+
+        if !full_search {
+            for i in 0..self.tracks.len() {
+                let Some((left, top, right, bottom)) =
+                    self.tracks[i].predicted_region(self.frame, self.options.search_margin)
+                else {
+                    full_search = true;
+                    continue;
+                };
+                let left = left.clamp(0.0, width) as usize;
+                let top = top.clamp(0.0, height) as usize;
+                let right = right.clamp(0.0, width).ceil() as usize;
+                let bottom = bottom.clamp(0.0, height).ceil() as usize;
+                if right <= left || bottom <= top {
+                    full_search = true;
+                    continue;
+                }
+
+                let mut region = image.crop(left, top, right - left, bottom - top);
+                let results = self
+                    .reader
+                    .decode_multiple_with_hints(&mut region, &self.hints)
+                    .unwrap_or_default();
+                let key = key_of(&self.tracks[i].result);
+                if !results.iter().any(|r| key_of(r) == key) {
+                    full_search = true;
+                }
+                found.extend(
+                    results
+                        .into_iter()
+                        .map(|r| translate(r, left as f32, top as f32)),
+                );
+            }
+        }
+
+        if full_search {
+            found.extend(
+                self.reader
+                    .decode_multiple_with_hints(image, &self.hints)
+                    .unwrap_or_default(),
+            );
+        }
+
+        let mut events = Vec::new();
+        let mut seen: Vec<RXingResult> = Vec::new();
+        let mut matched = vec![false; self.tracks.len()];
+        for result in found {
+            // The same symbol found by the search of its region and of the whole frame
+            if seen.iter().any(|s| same_symbol(s, &result)) {
+                continue;
+            }
+            seen.push(result.clone());
+
+            if let Some(i) = self.track_of(&result, &matched) {
+                matched[i] = true;
+                let track = &mut self.tracks[i];
+                let bounds = bounds_of(&result);
+                if let (Some(old), Some(new)) = (track.bounds, bounds) {
+                    let elapsed = (self.frame - track.last_frame) as f32;
+                    track.velocity = point(
+                        ((new.0 + new.2) - (old.0 + old.2)) / 2.0 / elapsed,
+                        ((new.1 + new.3) - (old.1 + old.3)) / 2.0 / elapsed,
+                    );
+                }
+                track.bounds = bounds;
+                track.last_frame = self.frame;
+                track.result = result;
+                continue;
+            }
+
+            let track = TrackedSymbol {
+                id: self.next_id,
+                bounds: bounds_of(&result),
+                result,
+                first_frame: self.frame,
+                last_frame: self.frame,
+                velocity: point(0.0, 0.0),
+            };
+            self.next_id += 1;
+            let assembled = self.sequences.add(&track.result);
+            events.push(StreamEvent::Entered(track.clone()));
+            events.extend(assembled.map(StreamEvent::Assembled));
+            self.tracks.push(track);
+            matched.push(true);
+        }
+
+        let (frame, max_missed) = (self.frame, self.options.max_missed_frames as u64);
+        let (kept, left): (Vec<_>, Vec<_>) = std::mem::take(&mut self.tracks)
+            .into_iter()
+            .partition(|t| frame - t.last_frame <= max_missed);
+        self.tracks = kept;
+        events.extend(left.into_iter().map(StreamEvent::Left));
+
+        events
+    }
+
+    /// The track not yet matched in this frame that `result` continues: the one with the same
+    /// contents whose predicted region holds it, nearest to where it was expected, or failing
+    /// positions, the first one with the same contents
+    fn track_of(&self, result: &RXingResult, matched: &[bool]) -> Option<usize> {
+        let key = key_of(result);
+        let candidates = self
+            .tracks
+            .iter()
+            .enumerate()
+            .filter(|(i, track)| !matched[*i] && key_of(&track.result) == key);
+        let Some(bounds) = bounds_of(result) else {
+            return candidates.map(|(i, _)| i).next();
+        };
+        let center = center_of(bounds);
+        let mut unplaced = None;
+        let mut nearest: Option<(usize, f32)> = None;
+        for (i, track) in candidates {
+            let Some(region) = track.predicted_region(self.frame, self.options.search_margin)
+            else {
+                unplaced.get_or_insert(i);
+                continue;
+            };
+            if !overlap(region, bounds) {
+                continue;
+            }
+            let expected = center_of(region);
+            let distance = (expected.x - center.x).hypot(expected.y - center.y);
+            if nearest.is_none_or(|(_, d)| distance < d) {
+                nearest = Some((i, distance));
+            }
+        }
+        nearest.map(|(i, _)| i).or(unplaced)
+    }
+}
+
+/// Contents of a symbol, the same for identical symbols wherever they are
+fn key_of(result: &RXingResult) -> (BarcodeFormat, String) {
+    (*result.getBarcodeFormat(), result.getText().to_owned())
+}
+
+/// Whether two decodes of one frame are of the same symbol: the same contents, overlapping
+/// unless either has no points
+fn same_symbol(a: &RXingResult, b: &RXingResult) -> bool {
+    key_of(a) == key_of(b)
+        && match (bounds_of(a), bounds_of(b)) {
+            (Some(a), Some(b)) => overlap(a, b),
+            _ => true,
+        }
+}
+
+fn overlap(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) -> bool {
+    a.0 <= b.2 && b.0 <= a.2 && a.1 <= b.3 && b.1 <= a.3
+}
+
+fn center_of((left, top, right, bottom): (f32, f32, f32, f32)) -> Point {
+    point((left + right) / 2.0, (top + bottom) / 2.0)
+}
+
+fn bounds_of(result: &RXingResult) -> Option<(f32, f32, f32, f32)> {
+    let points = result.getPoints();
+    if points.is_empty() {
+        return None;
+    }
+    Some(points.iter().fold(
+        (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+        |(left, top, right, bottom), p| {
+            (left.min(p.x), top.min(p.y), right.max(p.x), bottom.max(p.y))
+        },
+    ))
+}
+
+fn translate(mut result: RXingResult, x: f32, y: f32) -> RXingResult {
+    for p in result.getPointsMut() {
+        *p = point(p.x + x, p.y + y);
+    }
+    result
+}
+
+/// Parts of structured append sequences seen so far
+#[derive(Default)]
+struct SequenceAssembler {
+    /// QR Code parts by parity, then by position
+    qr_code: HashMap<i32, BTreeMap<i32, RXingResult>>,
+
+    /// Macro PDF417 segments by file id, then by segment index
+    #[cfg(feature = "pdf417")]
+    pdf417: HashMap<String, BTreeMap<usize, RXingResult>>,
+}
+
+impl SequenceAssembler {
+    /// Records `result` if it is part of a sequence, returning the whole sequence once it is
+    fn add(&mut self, result: &RXingResult) -> Option<RXingResult> {
+        let metadata = result.getRXingResultMetadata();
+        if let (
+            Some(RXingResultMetadataValue::StructuredAppendSequence(sequence)),
+            Some(RXingResultMetadataValue::StructuredAppendParity(parity)),
+        ) = (
+            metadata.get(&RXingResultMetadataType::STRUCTURED_APPEND_SEQUENCE),
+            metadata.get(&RXingResultMetadataType::STRUCTURED_APPEND_PARITY),
+        ) {
+            // Position in the high nibble, total count minus one in the low one
+            let count = (sequence & 0x0F) as usize + 1;
+            let parts = self.qr_code.entry(*parity).or_default();
+            parts.insert(sequence >> 4, result.clone());
+            if parts.len() < count {
+                return None;
+            }
+            let parts = self.qr_code.remove(parity)?;
+            return Some(join(parts.into_values(), BarcodeFormat::QR_CODE));
+        }
+
+        #[cfg(feature = "pdf417")]
+        if let Some(RXingResultMetadataValue::Pdf417ExtraMetadata(macro_pdf417)) =
+            metadata.get(&RXingResultMetadataType::PDF417_EXTRA_METADATA)
+        {
+            let file_id = macro_pdf417.getFileId().to_owned();
+            let segments = self.pdf417.entry(file_id.clone()).or_default();
+            segments.insert(macro_pdf417.getSegmentIndex(), result.clone());
+
+            // The count is optional, but the last segment is always marked
+            let count = segments.values().find_map(|r| {
+                match r
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::PDF417_EXTRA_METADATA)
+                {
+                    Some(RXingResultMetadataValue::Pdf417ExtraMetadata(m)) if m.isLastSegment() => {
+                        Some(m.getSegmentIndex() + 1)
+                    }
+                    Some(RXingResultMetadataValue::Pdf417ExtraMetadata(m))
+                        if m.getSegmentCount() > 0 =>
+                    {
+                        Some(m.getSegmentCount() as usize)
+                    }
+                    _ => None,
+                }
+            })?;
+            if segments.len() < count || segments.keys().any(|&i| i >= count) {
+                return None;
+            }
+            let segments = self.pdf417.remove(&file_id)?;
+            return Some(join(segments.into_values(), BarcodeFormat::PDF_417));
+        }
+
+        None
+    }
+}
+
+/// Joins the parts of a sequence, in order, the way `QRCodeMultiReader` does
+fn join(parts: impl Iterator<Item = RXingResult>, format: BarcodeFormat) -> RXingResult {
+    let mut text = String::new();
+    let mut raw_bytes = Vec::new();
+    let mut byte_segment = Vec::new();
+    for part in parts {
+        text.push_str(part.getText());
+        raw_bytes.extend_from_slice(part.getRawBytes());
+        if let Some(RXingResultMetadataValue::ByteSegments(segments)) = part
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::BYTE_SEGMENTS)
+        {
+            for segment in segments {
+                byte_segment.extend_from_slice(segment);
+            }
+        }
+    }
+
+    let mut result = RXingResult::new(&text, raw_bytes, Vec::new(), format);
+    if !byte_segment.is_empty() {
+        result.putMetadata(
+            RXingResultMetadataType::BYTE_SEGMENTS,
+            RXingResultMetadataValue::ByteSegments(vec![byte_segment]),
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, RXingResult, RXingResultMetadataType, RXingResultMetadataValue};
+
+    use super::SequenceAssembler;
+
+    fn part(text: &str, sequence: i32, parity: i32) -> RXingResult {
+        let mut result = RXingResult::new(
+            text,
+            text.as_bytes().to_vec(),
+            Vec::new(),
+            BarcodeFormat::QR_CODE,
+        );
+        result.putMetadata(
+            RXingResultMetadataType::STRUCTURED_APPEND_SEQUENCE,
+            RXingResultMetadataValue::StructuredAppendSequence(sequence),
+        );
+        result.putMetadata(
+            RXingResultMetadataType::STRUCTURED_APPEND_PARITY,
+            RXingResultMetadataValue::StructuredAppendParity(parity),
+        );
+        result
+    }
+
+    #[test]
+    fn assembles_structured_append() {
+        let mut assembler = SequenceAssembler::default();
+        assert!(assembler.add(&part("ghi", 0x22, 7)).is_none());
+        assert!(assembler.add(&part("other", 0x01, 9)).is_none());
+        assert!(assembler.add(&part("abc", 0x02, 7)).is_none());
+        assert!(assembler.add(&part("abc", 0x02, 7)).is_none());
+        let joined = assembler.add(&part("def", 0x12, 7)).expect("complete");
+        assert_eq!(joined.getText(), "abcdefghi");
+        assert_eq!(joined.getRawBytes(), b"abcdefghi");
+        assert!(assembler.qr_code.contains_key(&9));
+        assert!(!assembler.qr_code.contains_key(&7));
+
+        let unrelated = RXingResult::new("x", Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+        assert!(assembler.add(&unrelated).is_none());
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "encoders"))]
+    fn qr_code(contents: &str) -> crate::common::BitMatrix {
+        use crate::{EncodeHints, Writer, qrcode::QRCodeWriter};
+
+        QRCodeWriter
+            .encode_with_hints(
+                contents,
+                &BarcodeFormat::QR_CODE,
+                0,
+                0,
+                &EncodeHints::default(),
+            )
+            .unwrap()
+    }
+
+    /// A 400x200 frame holding each symbol at its left, top and pixels per module. Symbols of
+    /// different module sizes keep their finder patterns from being mixed up.
+    #[cfg(all(feature = "qrcode", feature = "encoders"))]
+    fn frame(
+        symbols: &[(&crate::common::BitMatrix, usize, usize, usize)],
+    ) -> crate::BinaryBitmap<crate::common::HybridBinarizer<crate::Luma8LuminanceSource>> {
+        use crate::{BinaryBitmap, Luma8LuminanceSource, common::HybridBinarizer};
+
+        const WIDTH: usize = 400;
+        const HEIGHT: usize = 200;
+        let mut pixels = vec![255u8; WIDTH * HEIGHT];
+        for &(matrix, left, top, scale) in symbols {
+            for y in 0..matrix.getHeight() as usize * scale {
+                for x in 0..matrix.getWidth() as usize * scale {
+                    if matrix.get((x / scale) as u32, (y / scale) as u32) {
+                        pixels[(top + y) * WIDTH + left + x] = 0;
+                    }
+                }
+            }
+        }
+        BinaryBitmap::new(HybridBinarizer::new(Luma8LuminanceSource::new(
+            pixels,
+            WIDTH as u32,
+            HEIGHT as u32,
+        )))
+    }
+
+    fn record(events: Vec<super::StreamEvent>, entered: &mut Vec<String>, left: &mut Vec<String>) {
+        use super::StreamEvent;
+
+        for event in events {
+            match event {
+                StreamEvent::Entered(s) => entered.push(s.result.getText().to_owned()),
+                StreamEvent::Left(s) => left.push(s.result.getText().to_owned()),
+                StreamEvent::Assembled(_) => panic!("nothing to assemble"),
+            }
+        }
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "encoders"))]
+    #[test]
+    fn tracks_moving_symbols() {
+        use crate::qrcode::QRCodeReader;
+
+        use super::StreamDecoder;
+
+        let first = qr_code("first");
+        let second = qr_code("second");
+
+        let mut decoder = StreamDecoder::new(QRCodeReader);
+        let (mut entered, mut left) = (Vec::new(), Vec::new());
+        for i in 0..4 {
+            record(
+                decoder.decode_frame(&mut frame(&[(&first, 10 + i * 12, 20, 4)])),
+                &mut entered,
+                &mut left,
+            );
+        }
+        assert_eq!(entered, ["first"]);
+        let tracked = &decoder.tracked()[0];
+        assert_eq!(tracked.first_frame, 1);
+        assert_eq!(tracked.last_frame, 4);
+        assert!((tracked.velocity.x - 12.0).abs() < 1.0);
+        assert!(tracked.velocity.y.abs() < 1.0);
+
+        // The second symbol is only picked up by the periodic search of the whole frame
+        record(
+            decoder.decode_frame(&mut frame(&[(&first, 58, 20, 4), (&second, 250, 60, 3)])),
+            &mut entered,
+            &mut left,
+        );
+        assert_eq!(entered, ["first", "second"]);
+
+        for _ in 0..3 {
+            record(
+                decoder.decode_frame(&mut frame(&[(&second, 250, 60, 3)])),
+                &mut entered,
+                &mut left,
+            );
+        }
+        assert!(left.is_empty());
+        record(
+            decoder.decode_frame(&mut frame(&[(&second, 250, 60, 3)])),
+            &mut entered,
+            &mut left,
+        );
+        assert_eq!(left, ["first"]);
+        assert_eq!(entered, ["first", "second"]);
+        assert_eq!(decoder.tracked().len(), 1);
+        assert_eq!(decoder.frame_count(), 9);
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "encoders"))]
+    #[test]
+    fn tracks_identical_symbols_separately() {
+        use crate::qrcode::QRCodeReader;
+
+        use super::StreamDecoder;
+
+        const LABEL: &str = "identical shipping label";
+        // Long enough for an alignment pattern, without which the multiple barcode reader
+        // takes symbols with the same contents for one
+        let label = qr_code(LABEL);
+        let mut decoder = StreamDecoder::new(QRCodeReader);
+        let (mut entered, mut left) = (Vec::new(), Vec::new());
+        for i in 0..4 {
+            record(
+                decoder.decode_frame(&mut frame(&[
+                    (&label, 10 + i * 8, 20, 4),
+                    (&label, 300 - i * 8, 60, 3),
+                ])),
+                &mut entered,
+                &mut left,
+            );
+        }
+        assert_eq!(entered, [LABEL, LABEL]);
+        let mut tracked: Vec<_> = decoder.tracked().iter().collect();
+        tracked.sort_by(|a, b| a.velocity.x.total_cmp(&b.velocity.x));
+        assert_ne!(tracked[0].id, tracked[1].id);
+        assert!((tracked[0].velocity.x + 8.0).abs() < 1.0);
+        assert!((tracked[1].velocity.x - 8.0).abs() < 1.0);
+        let moving_left = tracked[0].id;
+
+        // The label moving right goes out of view, the other is still followed
+        for i in 4..8 {
+            record(
+                decoder.decode_frame(&mut frame(&[(&label, 300 - i * 8, 60, 3)])),
+                &mut entered,
+                &mut left,
+            );
+        }
+        assert_eq!(left, [LABEL]);
+        assert_eq!(entered, [LABEL, LABEL]);
+        assert_eq!(decoder.tracked().len(), 1);
+        assert_eq!(decoder.tracked()[0].id, moving_left);
+    }
+}
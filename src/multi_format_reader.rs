@@ -21,7 +21,9 @@ use crate::common::Result;
 use crate::oned::cpp::ODReader;
 #[cfg(feature = "qrcode")]
 use crate::qrcode::cpp_port::QrReader;
-use crate::{BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, RXingResult, Reader};
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, ImmutableReader, RXingResult, Reader,
+};
 use crate::{DecodeHints, ONE_D_FORMATS};
 
 #[cfg(feature = "aztec")]
@@ -30,11 +32,23 @@ use crate::aztec::AztecReader;
 #[cfg(feature = "datamatrix")]
 use crate::datamatrix::DataMatrixReader;
 
+#[cfg(feature = "dotcode")]
+use crate::dotcode::DotCodeReader;
+
+#[cfg(feature = "hanxin")]
+use crate::hanxin::HanXinReader;
+
 #[cfg(feature = "maxicode")]
 use crate::maxicode::MaxiCodeReader;
 
 #[cfg(feature = "oned")]
-use crate::oned::MultiFormatOneDReader;
+use crate::oned::{MultiFormatOneDReader, PharmacodeTwoTrackReader};
+
+#[cfg(feature = "oned")]
+use crate::oned::postal::{FourStateReader, PostnetReader};
+
+#[cfg(feature = "oned")]
+use crate::oned::stacked::StackedReader;
 
 #[cfg(feature = "pdf417")]
 use crate::pdf417::PDF417Reader;
@@ -50,7 +64,7 @@ use crate::qrcode::QRCodeReader;
  * @author Sean Owen
  * @author dswitkin@google.com (Daniel Switkin)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MultiFormatReader {
     hints: DecodeHints,
     possible_formats: HashSet<BarcodeFormat>,
@@ -97,6 +111,27 @@ impl Reader for MultiFormatReader {
     }
 }
 
+impl ImmutableReader for MultiFormatReader {
+    /**
+     * Decode an image using the hints provided, like decode_with_hints(), but without touching
+     * the state of this reader, so that it can be shared.
+     *
+     * @param image The pixel data to decode
+     * @param hints The hints to use
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let mut reader = Self::default();
+        reader.set_hints(hints);
+        reader.decode_internal(image)
+    }
+}
+
 impl MultiFormatReader {
     /**
      * Decode an image using the state set up by calling setHints() previously. Continuous scan
@@ -117,6 +152,22 @@ impl MultiFormatReader {
         self.decode_internal(image)
     }
 
+    /**
+     * Decode an image using the state set up by calling setHints() previously, like
+     * decodeWithState(), but with a copy of that state. A reader set up once can so be shared
+     * between threads decoding at the same time.
+     *
+     * @param image The pixel data to decode
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
+    pub fn immutable_decode_with_state<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult> {
+        self.clone().decode_with_state(image)
+    }
+
     /**
      * This method adds state to the MultiFormatReader. By setting the hints once, subsequent calls
      * to decodeWithState(image) can reuse the same set of readers without reallocating memory. This
@@ -209,6 +260,31 @@ impl MultiFormatReader {
                     BarcodeFormat::MAXICODE => {
                         MaxiCodeReader::default().decode_with_hints(image, &self.hints)
                     }
+                    #[cfg(feature = "dotcode")]
+                    BarcodeFormat::DOTCODE => DotCodeReader.decode_with_hints(image, &self.hints),
+                    #[cfg(feature = "hanxin")]
+                    BarcodeFormat::HAN_XIN => HanXinReader.decode_with_hints(image, &self.hints),
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::INTELLIGENT_MAIL
+                    | BarcodeFormat::RM4SCC
+                    | BarcodeFormat::KIX
+                    | BarcodeFormat::AUSTRALIA_POST => {
+                        FourStateReader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::POSTNET | BarcodeFormat::PLANET => {
+                        PostnetReader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::CODE_16K
+                    | BarcodeFormat::CODE_49
+                    | BarcodeFormat::CODABLOCK_F => {
+                        StackedReader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::PHARMACODE_TWO_TRACK => {
+                        PharmacodeTwoTrackReader.decode_with_hints(image, &self.hints)
+                    }
                     #[cfg(feature = "oned")]
                     BarcodeFormat::DXFilmEdge => {
                         ODReader::new(&self.hints).decode_with_hints(image, &self.hints)
@@ -258,10 +334,30 @@ impl MultiFormatReader {
             if let Ok(res) = MaxiCodeReader::default().decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
+            #[cfg(feature = "dotcode")]
+            if let Ok(res) = DotCodeReader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "hanxin")]
+            if let Ok(res) = HanXinReader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
             #[cfg(feature = "oned")]
             if let Ok(res) = ODReader::new(&self.hints).decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
+            #[cfg(feature = "oned")]
+            if let Ok(res) = FourStateReader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "oned")]
+            if let Ok(res) = PostnetReader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "oned")]
+            if let Ok(res) = StackedReader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
 
             if self.try_harder {
                 #[cfg(feature = "oned")]
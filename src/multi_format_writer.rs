@@ -18,8 +18,10 @@ use crate::{BarcodeFormat, EncodeHints, Exceptions, Writer, common::Result};
 
 #[cfg(feature = "oned")]
 use crate::oned::{
-    CodaBarWriter, Code39Writer, Code93Writer, Code128Writer, EAN8Writer, EAN13Writer, ITFWriter,
-    TelepenWriter, UPCAWriter, UPCEWriter,
+    CodaBarWriter, Code11Writer, Code32Writer, Code39Writer, Code93Writer, Code128Writer,
+    DXFilmEdgeWriter, EAN8Writer, EAN13Writer, IATATwoOfFiveWriter, ITFWriter,
+    IndustrialTwoOfFiveWriter, MSIWriter, MatrixTwoOfFiveWriter, PZNWriter,
+    PharmacodeTwoTrackWriter, PharmacodeWriter, TelepenWriter, UPCAWriter, UPCEWriter,
 };
 
 #[cfg(feature = "aztec")]
@@ -28,6 +30,21 @@ use crate::aztec::AztecWriter;
 #[cfg(feature = "datamatrix")]
 use crate::datamatrix::DataMatrixWriter;
 
+#[cfg(feature = "dotcode")]
+use crate::dotcode::DotCodeWriter;
+
+#[cfg(feature = "hanxin")]
+use crate::hanxin::HanXinWriter;
+
+#[cfg(feature = "oned")]
+use crate::oned::postal::{
+    AustraliaPostWriter, IntelligentMailWriter, KIXWriter, PlanetWriter, PostnetWriter,
+    RM4SCCWriter,
+};
+
+#[cfg(feature = "oned")]
+use crate::oned::stacked::{CodablockFWriter, Code16KWriter, Code49Writer};
+
 #[cfg(feature = "pdf417")]
 use crate::pdf417::PDF417Writer;
 
@@ -81,16 +98,58 @@ impl Writer for MultiFormatWriter {
             BarcodeFormat::CODE_128 => Box::<Code128Writer>::default(),
             #[cfg(feature = "oned")]
             BarcodeFormat::ITF => Box::<ITFWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::INDUSTRIAL_2_OF_5 => Box::<IndustrialTwoOfFiveWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::IATA_2_OF_5 => Box::<IATATwoOfFiveWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::MATRIX_2_OF_5 => Box::<MatrixTwoOfFiveWriter>::default(),
             #[cfg(feature = "pdf417")]
             BarcodeFormat::PDF_417 => Box::<PDF417Writer>::default(),
             #[cfg(feature = "oned")]
             BarcodeFormat::CODABAR => Box::<CodaBarWriter>::default(),
             #[cfg(feature = "datamatrix")]
             BarcodeFormat::DATA_MATRIX => Box::<DataMatrixWriter>::default(),
+            #[cfg(feature = "dotcode")]
+            BarcodeFormat::DOTCODE => Box::<DotCodeWriter>::default(),
+            #[cfg(feature = "hanxin")]
+            BarcodeFormat::HAN_XIN => Box::<HanXinWriter>::default(),
             #[cfg(feature = "oned")]
             BarcodeFormat::TELEPEN => Box::<TelepenWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::CODE_11 => Box::<Code11Writer>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::CODE_32 => Box::<Code32Writer>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::PZN => Box::<PZNWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::MSI => Box::<MSIWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::PHARMACODE => Box::<PharmacodeWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::PHARMACODE_TWO_TRACK => Box::<PharmacodeTwoTrackWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::DXFilmEdge => Box::<DXFilmEdgeWriter>::default(),
             #[cfg(feature = "aztec")]
             BarcodeFormat::AZTEC => Box::<AztecWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::INTELLIGENT_MAIL => Box::<IntelligentMailWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::RM4SCC => Box::<RM4SCCWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::KIX => Box::<KIXWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::AUSTRALIA_POST => Box::<AustraliaPostWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::POSTNET => Box::<PostnetWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::PLANET => Box::<PlanetWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::CODE_16K => Box::<Code16KWriter>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::CODE_49 => Box::<Code49Writer>::default(),
+            #[cfg(feature = "oned")]
+            BarcodeFormat::CODABLOCK_F => Box::<CodablockFWriter>::default(),
             _ => {
                 return Err(Exceptions::illegal_argument_with(format!(
                     "No encoder available for format {format:?}"
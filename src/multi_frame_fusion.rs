@@ -0,0 +1,499 @@
+use crate::{
+    Exceptions,
+    common::{BitMatrix, DecoderRXingResult, Result},
+};
+
+#[cfg(feature = "qrcode")]
+use crate::DecodeHints;
+#[cfg(feature = "aztec")]
+use crate::{aztec::AztecDetectorRXingResult, common::DetectorRXingResult};
+
+/// A symbol decoded from the fused frames of a [`MultiFrameFusion`]
+pub struct FusedDecode {
+    pub result: DecoderRXingResult,
+
+    /// The module grid that decoded
+    pub matrix: BitMatrix,
+
+    /// Share of the frames' votes agreeing with `matrix`, averaged over the modules: 1 when
+    /// every frame sampled every module alike
+    pub confidence: f32,
+}
+
+/**
+ * Combines several sampled module grids of the same symbol, so that a symbol whose frames each
+ * fail error correction, on different modules, may still decode.
+ *
+ * Every frame votes on the color of each module with its weight. Frames added with the
+ * luminance of each module rather than its color also scale each vote by the distance of the
+ * module from the threshold, so that a module sampled close to it counts for little against a
+ * frame that saw it clearly. The grid of the weighted majority is tried first; if it does not
+ * decode, the modules on which the frames disagree most are taken from each frame in turn,
+ * heaviest first, so that a frame trusted more than the others breaks the ties. Frames must be
+ * sampled from the same symbol, aligned on its corners, and be the same size.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct MultiFrameFusion {
+    frames: Vec<Frame>,
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    grid: BitMatrix,
+    weight: f32,
+    /// How sure the frame is of each module, from 0 to 1 row by row, or `None` when it is
+    /// sure of all of them
+    certainty: Option<Vec<f32>>,
+}
+
+impl MultiFrameFusion {
+    /// Modules where the weight of the minority is at least this share of the total are
+    /// taken from single frames when the majority fails
+    const UNCERTAIN: f32 = 0.25;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a frame with a weight of 1
+    pub fn add_frame(&mut self, grid: &BitMatrix) -> Result<()> {
+        self.add_weighted_frame(grid, 1.0)
+    }
+
+    /// Adds a frame, weighted for example by the sharpness or contrast of the image it was
+    /// sampled from
+    pub fn add_weighted_frame(&mut self, grid: &BitMatrix, weight: f32) -> Result<()> {
+        self.push(Frame {
+            grid: grid.clone(),
+            weight,
+            certainty: None,
+        })
+    }
+
+    /// Adds a frame of the luminance sampled at the center of each module, row by row, `width`
+    /// modules to a row. Modules darker than `threshold` are dark, and each votes with
+    /// `weight` scaled by how far it is from the threshold, relative to the furthest a module
+    /// could be on its side.
+    pub fn add_luminance_frame(
+        &mut self,
+        luminances: &[u8],
+        width: u32,
+        threshold: u8,
+        weight: f32,
+    ) -> Result<()> {
+        if width == 0 || luminances.is_empty() || luminances.len() % width as usize != 0 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "{} luminances do not make rows of {width} modules",
+                luminances.len()
+            )));
+        }
+        let height = (luminances.len() / width as usize) as u32;
+        let mut grid = BitMatrix::new(width, height)?;
+        let mut certainty = Vec::with_capacity(luminances.len());
+        for (i, &luminance) in luminances.iter().enumerate() {
+            let (luminance, threshold) = (luminance as f32, threshold as f32);
+            if luminance < threshold {
+                grid.set(i as u32 % width, i as u32 / width);
+                certainty.push((threshold - luminance) / threshold);
+            } else {
+                certainty.push((luminance - threshold) / (255.0 - threshold).max(1.0));
+            }
+        }
+        self.push(Frame {
+            grid,
+            weight,
+            certainty: Some(certainty),
+        })
+    }
+
+    fn push(&mut self, frame: Frame) -> Result<()> {
+        let Frame { grid, weight, .. } = &frame;
+        if !(*weight > 0.0 && weight.is_finite()) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "frame weight must be positive, found {weight}"
+            )));
+        }
+        if let Some(Frame { grid: first, .. }) = self.frames.first() {
+            if first.getWidth() != grid.getWidth() || first.getHeight() != grid.getHeight() {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "frame of {}x{} modules does not match the {}x{} of the first",
+                    grid.getWidth(),
+                    grid.getHeight(),
+                    first.getWidth(),
+                    first.getHeight()
+                )));
+            }
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The grid of the weighted majority, ties going to light
+    pub fn fused(&self) -> Result<BitMatrix> {
+        let votes = self.votes()?;
+        let first = &self.frames[0].grid;
+        let mut fused = BitMatrix::new(first.getWidth(), first.getHeight())?;
+        for y in 0..fused.getHeight() {
+            for x in 0..fused.getWidth() {
+                if votes[(y * fused.getWidth() + x) as usize] > 0.5 {
+                    fused.set(x, y);
+                }
+            }
+        }
+        Ok(fused)
+    }
+
+    /// Share of the frames' votes agreeing with the majority, averaged over the modules
+    pub fn confidence(&self) -> Result<f32> {
+        self.agreement(&self.fused()?)
+    }
+
+    #[cfg(feature = "qrcode")]
+    pub fn decode_qr_code(&self, hints: &DecodeHints) -> Result<FusedDecode> {
+        self.decode_with(|bits| {
+            crate::qrcode::decoder::qrcode_decoder::decode_bitmatrix_with_hints(bits, hints)
+        })
+    }
+
+    #[cfg(feature = "datamatrix")]
+    pub fn decode_data_matrix(&self) -> Result<FusedDecode> {
+        let decoder = crate::datamatrix::decoder::Decoder::new();
+        self.decode_with(|bits| decoder.decode(bits))
+    }
+
+    /// Decodes as the symbol found by `detected`, whose bits are replaced by the fused ones
+    #[cfg(feature = "aztec")]
+    pub fn decode_aztec(&self, detected: &AztecDetectorRXingResult) -> Result<FusedDecode> {
+        let points = detected.getPoints();
+        let points = [points[0], points[1], points[2], points[3]];
+        self.decode_with(|bits| {
+            crate::aztec::decoder::decode(&AztecDetectorRXingResult::new(
+                bits.clone(),
+                points,
+                detected.isCompact(),
+                detected.getNbDatablocks(),
+                detected.getNbLayers(),
+            ))
+        })
+    }
+
+    /// Share of the votes for dark for each module, row by row, or one half for a module no
+    /// frame is at all sure of
+    fn votes(&self) -> Result<Vec<f32>> {
+        let Some(Frame { grid: first, .. }) = self.frames.first() else {
+            return Err(Exceptions::illegal_argument_with("no frames to fuse"));
+        };
+        let (width, height) = (first.getWidth(), first.getHeight());
+        let mut dark = vec![0.0; (width * height) as usize];
+        let mut total = vec![0.0; (width * height) as usize];
+        for frame in &self.frames {
+            for y in 0..height {
+                for x in 0..width {
+                    let i = (y * width + x) as usize;
+                    let vote = frame.weight * frame.certainty.as_ref().map_or(1.0, |c| c[i]);
+                    total[i] += vote;
+                    if frame.grid.get(x, y) {
+                        dark[i] += vote;
+                    }
+                }
+            }
+        }
+        Ok(dark
+            .iter()
+            .zip(&total)
+            .map(|(dark, total)| if *total > 0.0 { dark / total } else { 0.5 })
+            .collect())
+    }
+
+    fn agreement(&self, matrix: &BitMatrix) -> Result<f32> {
+        let votes = self.votes()?;
+        let width = matrix.getWidth();
+        let sum: f32 = votes
+            .iter()
+            .enumerate()
+            .map(|(i, &dark)| {
+                let (x, y) = (i as u32 % width, i as u32 / width);
+                if matrix.get(x, y) { dark } else { 1.0 - dark }
+            })
+            .sum();
+        Ok(sum / votes.len() as f32)
+    }
+
+    /// Tries `decode` on the majority grid, then on the majority grid with its uncertain
+    /// modules taken from each frame, returning the error of the majority if none decodes
+    fn decode_with<F>(&self, decode: F) -> Result<FusedDecode>
+    where
+        F: Fn(&BitMatrix) -> Result<DecoderRXingResult>,
+    {
+        let votes = self.votes()?;
+        let fused = self.fused()?;
+        let error = match decode(&fused) {
+            Ok(result) => return self.fused_decode(result, fused),
+            Err(error) => error,
+        };
+
+        let width = fused.getWidth();
+        let uncertain: Vec<(u32, u32)> = votes
+            .iter()
+            .enumerate()
+            .filter(|(_, dark)| dark.min(1.0 - **dark) >= Self::UNCERTAIN)
+            .map(|(i, _)| (i as u32 % width, i as u32 / width))
+            .collect();
+        if uncertain.is_empty() {
+            return Err(error);
+        }
+
+        let mut frames: Vec<&Frame> = self.frames.iter().collect();
+        frames.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        for Frame { grid, .. } in frames {
+            let mut candidate = fused.clone();
+            for &(x, y) in &uncertain {
+                candidate.set_bool(x, y, grid.get(x, y));
+            }
+            if candidate == fused {
+                continue;
+            }
+            if let Ok(result) = decode(&candidate) {
+                return self.fused_decode(result, candidate);
+            }
+        }
+
+        Err(error)
+    }
+
+    fn fused_decode(&self, result: DecoderRXingResult, matrix: BitMatrix) -> Result<FusedDecode> {
+        Ok(FusedDecode {
+            confidence: self.agreement(&matrix)?,
+            result,
+            matrix,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::BitMatrix;
+
+    use super::MultiFrameFusion;
+
+    /// `matrix` with about 2 in 5 of the modules flipped in every `bands`th row, starting at
+    /// row `band`
+    fn damage(matrix: &BitMatrix, band: u32, bands: u32) -> BitMatrix {
+        let mut damaged = matrix.clone();
+        let mut seed = 0x2545_f491_u32.wrapping_add(band);
+        for y in (band..matrix.getHeight()).step_by(bands as usize) {
+            for x in 0..matrix.getWidth() {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                if seed % 5 < 2 {
+                    damaged.flip_coords(x, y);
+                }
+            }
+        }
+        damaged
+    }
+
+    #[test]
+    fn votes_by_weight() {
+        let dark = BitMatrix::parse_strings("X X \nX X \n", "X ", "  ").unwrap();
+        let light = BitMatrix::parse_strings("    \n    \n", "X ", "  ").unwrap();
+        let mut fusion = MultiFrameFusion::new();
+        assert!(fusion.fused().is_err());
+        fusion.add_frame(&dark).unwrap();
+        fusion.add_frame(&light).unwrap();
+        assert_eq!(fusion.fused().unwrap(), light);
+        assert_eq!(fusion.confidence().unwrap(), 0.5);
+
+        fusion.add_weighted_frame(&dark, 0.5).unwrap();
+        assert_eq!(fusion.fused().unwrap(), dark);
+        assert_eq!(fusion.frame_count(), 3);
+
+        assert!(fusion.add_weighted_frame(&dark, 0.0).is_err());
+        assert!(
+            fusion
+                .add_frame(&BitMatrix::with_single_dimension(3).unwrap())
+                .is_err()
+        );
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "encoders"))]
+    #[test]
+    fn fuses_qr_code_frames() {
+        use crate::{
+            BarcodeFormat, DecodeHints, EncodeHints, Writer,
+            qrcode::{QRCodeWriter, decoder::qrcode_decoder::decode_bitmatrix},
+        };
+
+        let matrix = QRCodeWriter
+            .encode_with_hints(
+                "Fused from three damaged frames",
+                &BarcodeFormat::QR_CODE,
+                0,
+                0,
+                &EncodeHints {
+                    Margin: Some("0".to_owned()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let mut fusion = MultiFrameFusion::new();
+        for band in 0..3 {
+            let frame = damage(&matrix, band, 3);
+            assert!(decode_bitmatrix(&frame).is_err());
+            fusion.add_frame(&frame).unwrap();
+        }
+        let fused = fusion.decode_qr_code(&DecodeHints::default()).unwrap();
+        assert_eq!(fused.result.getText(), "Fused from three damaged frames");
+        assert_eq!(fused.matrix, matrix);
+        assert!(fused.confidence > 0.8 && fused.confidence < 1.0);
+
+        // Two frames tie wherever the damaged one is wrong, each is tried for those modules
+        let mut fusion = MultiFrameFusion::new();
+        fusion.add_frame(&damage(&matrix, 1, 3)).unwrap();
+        fusion.add_frame(&matrix).unwrap();
+        assert_ne!(fusion.fused().unwrap(), matrix);
+        let fused = fusion.decode_qr_code(&DecodeHints::default()).unwrap();
+        assert_eq!(fused.matrix, matrix);
+    }
+
+    #[test]
+    fn weighs_luminance_by_distance_from_threshold() {
+        let mut fusion = MultiFrameFusion::new();
+        // Dark, barely dark, barely light and light with a threshold of 100
+        fusion
+            .add_luminance_frame(&[0, 90, 110, 255], 2, 100, 1.0)
+            .unwrap();
+        assert_eq!(
+            fusion.fused().unwrap(),
+            BitMatrix::parse_strings("X X \n    \n", "X ", "  ").unwrap()
+        );
+        // A lighter frame sure of the opposite outvotes the barely sampled modules only
+        fusion
+            .add_luminance_frame(&[255, 255, 0, 0], 2, 100, 0.5)
+            .unwrap();
+        assert_eq!(
+            fusion.fused().unwrap(),
+            BitMatrix::parse_strings("X   \nX   \n", "X ", "  ").unwrap()
+        );
+
+        assert!(fusion.add_luminance_frame(&[0, 0, 0], 2, 100, 1.0).is_err());
+        assert!(fusion.add_luminance_frame(&[0; 4], 4, 100, 1.0).is_err());
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "encoders"))]
+    #[test]
+    fn luminance_outvotes_majority() {
+        use crate::{BarcodeFormat, DecodeHints, EncodeHints, Writer, qrcode::QRCodeWriter};
+
+        let matrix = QRCodeWriter
+            .encode_with_hints(
+                "Weighted by distance from the threshold",
+                &BarcodeFormat::QR_CODE,
+                0,
+                0,
+                &EncodeHints {
+                    Margin: Some("0".to_owned()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        // Four frames share the same glare, sampling the damaged modules just across the
+        // threshold, and one frame sees the symbol clearly
+        let damaged = damage(&matrix, 0, 2);
+        let luminances = |grid: &BitMatrix, dark: u8, light: u8| -> Vec<u8> {
+            (0..grid.getHeight())
+                .flat_map(|y| (0..grid.getWidth()).map(move |x| (x, y)))
+                .map(|(x, y)| if grid.get(x, y) { dark } else { light })
+                .collect()
+        };
+        let glare: Vec<u8> = luminances(&damaged, 0, 255)
+            .iter()
+            .zip(luminances(&matrix, 0, 255))
+            .map(|(&seen, actual)| match (seen, seen == actual) {
+                (_, true) => seen,
+                (0, false) => 120,
+                (_, false) => 136,
+            })
+            .collect();
+
+        let mut majority = MultiFrameFusion::new();
+        let mut weighted = MultiFrameFusion::new();
+        for _ in 0..4 {
+            majority.add_frame(&damaged).unwrap();
+            weighted
+                .add_luminance_frame(&glare, matrix.getWidth(), 128, 1.0)
+                .unwrap();
+        }
+        majority.add_frame(&matrix).unwrap();
+        weighted
+            .add_luminance_frame(&luminances(&matrix, 0, 255), matrix.getWidth(), 128, 1.0)
+            .unwrap();
+
+        assert!(majority.decode_qr_code(&DecodeHints::default()).is_err());
+        let fused = weighted.decode_qr_code(&DecodeHints::default()).unwrap();
+        assert_eq!(
+            fused.result.getText(),
+            "Weighted by distance from the threshold"
+        );
+        assert_eq!(fused.matrix, matrix);
+    }
+
+    #[cfg(all(feature = "datamatrix", feature = "encoders"))]
+    #[test]
+    fn fuses_data_matrix_frames() {
+        use crate::{
+            BarcodeFormat, Writer,
+            datamatrix::{DataMatrixWriter, decoder::Decoder},
+        };
+
+        let matrix = DataMatrixWriter
+            .encode("Fused Data Matrix", &BarcodeFormat::DATA_MATRIX, 0, 0)
+            .unwrap();
+
+        let mut fusion = MultiFrameFusion::new();
+        for band in 0..3 {
+            let frame = damage(&matrix, band, 3);
+            assert!(Decoder::new().decode(&frame).is_err());
+            fusion.add_frame(&frame).unwrap();
+        }
+        let fused = fusion.decode_data_matrix().unwrap();
+        assert_eq!(fused.result.getText(), "Fused Data Matrix");
+    }
+
+    #[cfg(all(feature = "aztec", feature = "encoders"))]
+    #[test]
+    fn fuses_aztec_frames() {
+        use crate::{
+            aztec::{AztecDetectorRXingResult, decoder, encoder::aztec_encoder},
+            point,
+        };
+
+        let code = aztec_encoder::encode("Fused Aztec symbol", 10, 0).unwrap();
+        let detected = |bits: BitMatrix| {
+            AztecDetectorRXingResult::new(
+                bits,
+                [point(0.0, 0.0); 4],
+                code.isCompact(),
+                code.getCodeWords(),
+                code.getLayers(),
+            )
+        };
+
+        let mut fusion = MultiFrameFusion::new();
+        for band in 0..3 {
+            let frame = damage(code.getMatrix(), band, 3);
+            assert!(decoder::decode(&detected(frame.clone())).is_err());
+            fusion.add_frame(&frame).unwrap();
+        }
+        let fused = fusion
+            .decode_aztec(&detected(damage(code.getMatrix(), 0, 3)))
+            .unwrap();
+        assert_eq!(fused.result.getText(), "Fused Aztec symbol");
+    }
+}
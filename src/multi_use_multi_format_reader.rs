@@ -20,7 +20,9 @@ use crate::DecodeHints;
 use crate::common::Result;
 #[cfg(feature = "qrcode")]
 use crate::qrcode::cpp_port::QrReader;
-use crate::{BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, RXingResult, Reader};
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, ImmutableReader, RXingResult, Reader,
+};
 
 #[cfg(feature = "aztec")]
 use crate::aztec::AztecReader;
@@ -28,30 +30,47 @@ use crate::aztec::AztecReader;
 #[cfg(feature = "datamatrix")]
 use crate::datamatrix::DataMatrixReader;
 
+#[cfg(feature = "dotcode")]
+use crate::dotcode::DotCodeReader;
+#[cfg(feature = "hanxin")]
+use crate::hanxin::HanXinReader;
+
 #[cfg(feature = "maxicode")]
 use crate::maxicode::MaxiCodeReader;
 
 #[cfg(feature = "oned")]
-use crate::oned::MultiFormatOneDReader;
+use crate::oned::postal::{FourStateReader, PostnetReader};
+#[cfg(feature = "oned")]
+use crate::oned::stacked::StackedReader;
+#[cfg(feature = "oned")]
+use crate::oned::{MultiFormatOneDReader, PharmacodeTwoTrackReader};
 
 #[cfg(feature = "pdf417")]
 use crate::pdf417::PDF417Reader;
 #[cfg(feature = "qrcode")]
 use crate::qrcode::QRCodeReader;
 
-pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 12] = [
+pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 20] = [
     BarcodeFormat::UPC_A,
     BarcodeFormat::UPC_E,
     BarcodeFormat::EAN_13,
     BarcodeFormat::EAN_8,
     BarcodeFormat::CODABAR,
+    BarcodeFormat::CODE_32,
     BarcodeFormat::CODE_39,
+    BarcodeFormat::PZN,
     BarcodeFormat::CODE_93,
     BarcodeFormat::CODE_128,
     BarcodeFormat::ITF,
     BarcodeFormat::RSS_14,
     BarcodeFormat::RSS_EXPANDED,
     BarcodeFormat::TELEPEN,
+    BarcodeFormat::CODE_11,
+    BarcodeFormat::MSI,
+    BarcodeFormat::PHARMACODE,
+    BarcodeFormat::INDUSTRIAL_2_OF_5,
+    BarcodeFormat::IATA_2_OF_5,
+    BarcodeFormat::MATRIX_2_OF_5,
 ];
 
 /**
@@ -62,7 +81,7 @@ pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 12] = [
  * @author Sean Owen
  * @author dswitkin@google.com (Daniel Switkin)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MultiUseMultiFormatReader {
     hints: DecodeHints,
     possible_formats: HashSet<BarcodeFormat>,
@@ -79,6 +98,18 @@ pub struct MultiUseMultiFormatReader {
     pdf417_reader: PDF417Reader,
     #[cfg(feature = "maxicode")]
     maxicode_reader: MaxiCodeReader,
+    #[cfg(feature = "dotcode")]
+    dotcode_reader: DotCodeReader,
+    #[cfg(feature = "hanxin")]
+    han_xin_reader: HanXinReader,
+    #[cfg(feature = "oned")]
+    four_state_reader: FourStateReader,
+    #[cfg(feature = "oned")]
+    postnet_reader: PostnetReader,
+    #[cfg(feature = "oned")]
+    stacked_reader: StackedReader,
+    #[cfg(feature = "oned")]
+    pharmacode_two_track_reader: PharmacodeTwoTrackReader,
     #[cfg(feature = "qrcode")]
     cpp_qrcode_reader: QrReader,
 }
@@ -128,11 +159,44 @@ impl Reader for MultiUseMultiFormatReader {
         self.pdf417_reader.reset();
         #[cfg(feature = "maxicode")]
         self.maxicode_reader.reset();
+        #[cfg(feature = "dotcode")]
+        self.dotcode_reader.reset();
+        #[cfg(feature = "hanxin")]
+        self.han_xin_reader.reset();
+        #[cfg(feature = "oned")]
+        self.four_state_reader.reset();
+        #[cfg(feature = "oned")]
+        self.postnet_reader.reset();
+        #[cfg(feature = "oned")]
+        self.stacked_reader.reset();
+        #[cfg(feature = "oned")]
+        self.pharmacode_two_track_reader.reset();
         #[cfg(feature = "qrcode")]
         self.cpp_qrcode_reader.reset();
     }
 }
 
+impl ImmutableReader for MultiUseMultiFormatReader {
+    /**
+     * Decode an image using the hints provided, like decode_with_hints(), but without touching
+     * the state of this reader, so that it can be shared.
+     *
+     * @param image The pixel data to decode
+     * @param hints The hints to use
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let mut reader = Self::default();
+        reader.set_hints(hints);
+        reader.decode_internal(image)
+    }
+}
+
 impl MultiUseMultiFormatReader {
     /**
      * Decode an image using the state set up by calling setHints() previously. Continuous scan
@@ -153,6 +217,22 @@ impl MultiUseMultiFormatReader {
         self.decode_internal(image)
     }
 
+    /**
+     * Decode an image using the state set up by calling setHints() previously, like
+     * decodeWithState(), but with a copy of that state. A reader set up once can so be shared
+     * between threads decoding at the same time.
+     *
+     * @param image The pixel data to decode
+     * @return The contents of the image
+     * @throws NotFoundException Any errors which occurred
+     */
+    pub fn immutable_decode_with_state<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult> {
+        self.clone().decode_with_state(image)
+    }
+
     /**
      * This method adds state to the MultiFormatReader. By setting the hints once, subsequent calls
      * to decodeWithState(image) can reuse the same set of readers without reallocating memory. This
@@ -255,6 +335,35 @@ impl MultiUseMultiFormatReader {
                     BarcodeFormat::MAXICODE => {
                         self.maxicode_reader.decode_with_hints(image, &self.hints)
                     }
+                    #[cfg(feature = "dotcode")]
+                    BarcodeFormat::DOTCODE => {
+                        self.dotcode_reader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "hanxin")]
+                    BarcodeFormat::HAN_XIN => {
+                        self.han_xin_reader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::INTELLIGENT_MAIL
+                    | BarcodeFormat::RM4SCC
+                    | BarcodeFormat::KIX
+                    | BarcodeFormat::AUSTRALIA_POST => {
+                        self.four_state_reader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::POSTNET | BarcodeFormat::PLANET => {
+                        self.postnet_reader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::CODE_16K
+                    | BarcodeFormat::CODE_49
+                    | BarcodeFormat::CODABLOCK_F => {
+                        self.stacked_reader.decode_with_hints(image, &self.hints)
+                    }
+                    #[cfg(feature = "oned")]
+                    BarcodeFormat::PHARMACODE_TWO_TRACK => self
+                        .pharmacode_two_track_reader
+                        .decode_with_hints(image, &self.hints),
                     _ => Err(Exceptions::UNSUPPORTED_OPERATION),
                 };
                 if res.is_ok() {
@@ -301,6 +410,26 @@ impl MultiUseMultiFormatReader {
             if let Ok(res) = self.maxicode_reader.decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
+            #[cfg(feature = "dotcode")]
+            if let Ok(res) = self.dotcode_reader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "hanxin")]
+            if let Ok(res) = self.han_xin_reader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "oned")]
+            if let Ok(res) = self.four_state_reader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "oned")]
+            if let Ok(res) = self.postnet_reader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
+            #[cfg(feature = "oned")]
+            if let Ok(res) = self.stacked_reader.decode_with_hints(image, &self.hints) {
+                return Ok(res);
+            }
 
             if self.try_harder {
                 #[cfg(feature = "oned")]
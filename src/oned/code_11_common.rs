@@ -0,0 +1,35 @@
+/// Number of check digits added to `length` data characters unless told otherwise: the C check
+/// digit, followed by the K check digit once there are more than 10 data characters.
+pub fn default_check_digit_count(length: usize) -> u32 {
+    if length <= 10 { 1 } else { 2 }
+}
+
+/// The `count` check digits of the character values `values`, as values themselves
+pub fn check_digits(values: &[u8], count: u32) -> Vec<u8> {
+    let mut checked = values.to_vec();
+    for max_weight in [10, 9].into_iter().take(count as usize) {
+        let sum: u32 = checked
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &v)| (i as u32 % max_weight + 1) * v as u32)
+            .sum();
+        checked.push((sum % 11) as u8);
+    }
+    checked.split_off(values.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_digits;
+
+    #[test]
+    fn c_and_k() {
+        // "123-45"
+        let values = [1, 2, 3, 10, 4, 5];
+        assert_eq!(check_digits(&values, 1), [5]);
+        // "123" takes "-" as its C check digit
+        assert_eq!(check_digits(&[1, 2, 3], 2), [10, 4]);
+        assert!(check_digits(&values, 0).is_empty());
+    }
+}
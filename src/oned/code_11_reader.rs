@@ -0,0 +1,207 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::common::{BitArray, Result};
+use crate::{
+    BarcodeFormat, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue, point,
+};
+
+use super::{OneDReader, code_11_common, oned_constants::code_11, record_all_runs};
+
+/**
+ * <p>Decodes Code 11 barcodes. The check digits are validated and removed from the result;
+ * how many there are can be set with [`DecodeHints::Code11CheckDigits`].</p>
+ */
+#[derive(OneDReader, Clone, Default)]
+pub struct Code11Reader;
+
+impl OneDReader for Code11Reader {
+    fn decode_row(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let runs = record_all_runs(row);
+
+        let mut last_error = Exceptions::NOT_FOUND;
+        // black runs have odd indices
+        for start in (1..runs.len().saturating_sub(5)).step_by(2) {
+            let (values, end) = match Self::read_characters(&runs, start) {
+                Some(found) => found,
+                None => continue,
+            };
+            let text = match Self::strip_check_digits(&values, hints) {
+                Ok(text) => text,
+                Err(e) => {
+                    last_error = e;
+                    continue;
+                }
+            };
+
+            let left = runs[..start].iter().sum::<u32>();
+            let right = left + runs[start..end].iter().sum::<u32>();
+            let mut result = RXingResult::new(
+                &text,
+                Vec::new(),
+                vec![
+                    point(left as f32, rowNumber as f32),
+                    point(right as f32, rowNumber as f32),
+                ],
+                BarcodeFormat::CODE_11,
+            );
+            result.putMetadata(
+                RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+                RXingResultMetadataValue::SymbologyIdentifier("]H3".to_owned()),
+            );
+            return Ok(result);
+        }
+
+        Err(last_error)
+    }
+}
+
+impl Code11Reader {
+    /// Reads the characters between a start pattern at `start` and the next stop pattern,
+    /// returning their values and the index of the run following the stop pattern
+    fn read_characters(runs: &[u32], start: usize) -> Option<(Vec<u8>, usize)> {
+        let (pattern, threshold) = Self::to_pattern(&runs[start..start + 5])?;
+        if pattern != code_11::START_STOP_ENCODING {
+            return None;
+        }
+        let start_width: u32 = runs[start..start + 5].iter().sum();
+        // quiet zone of half a character before the start pattern, unless it is cut off
+        if start > 1 && runs[start - 1] * 2 < start_width {
+            return None;
+        }
+
+        let mut values = Vec::new();
+        let mut gap_threshold = threshold;
+        let mut offset = start + 5;
+        loop {
+            // the inter-character gap is a narrow space
+            if runs.get(offset).is_none_or(|&gap| 2 * gap > gap_threshold) {
+                return None;
+            }
+            offset += 1;
+            let elements = runs.get(offset..offset + 5)?;
+            let width: u32 = elements.iter().sum();
+            if 5 * width < 3 * start_width || 2 * width > 3 * start_width {
+                return None;
+            }
+            let (pattern, threshold) = Self::to_pattern(elements)?;
+            gap_threshold = threshold;
+            offset += 5;
+
+            if pattern == code_11::START_STOP_ENCODING {
+                // the stop pattern has a quiet zone after it
+                if runs.get(offset).is_some_and(|&quiet| quiet * 2 < width) {
+                    return None;
+                }
+                return Some((values, offset));
+            }
+            let value = code_11::CHARACTER_ENCODINGS
+                .iter()
+                .position(|&encoding| encoding == pattern)?;
+            values.push(value as u8);
+        }
+    }
+
+    /// Classifies five elements as wide (1) or narrow (0) bits. Returns the pattern along with
+    /// twice the width that separates narrow from wide elements.
+    fn to_pattern(elements: &[u32]) -> Option<(u32, u32)> {
+        let min = *elements.iter().min()?;
+        let max = *elements.iter().max()?;
+        // every character has at least one wide element
+        if 2 * max < 3 * min {
+            return None;
+        }
+        let threshold = min + max;
+        let pattern = elements
+            .iter()
+            .fold(0, |pattern, &w| pattern << 1 | u32::from(2 * w > threshold));
+        Some((pattern, threshold))
+    }
+
+    /// Validates and removes the check digits at the end of `values`
+    fn strip_check_digits(values: &[u8], hints: &DecodeHints) -> Result<String> {
+        let counts = match hints.Code11CheckDigits {
+            Some(count) => vec![count],
+            // one check digit up to 10 data characters, two above that, though some encoders
+            // always add two. On short symbols the K check digit is computed just like a C
+            // check digit would be, so two check digits are always also valid as one.
+            None => vec![2, 1],
+        };
+        for count in counts {
+            let Some(length) = values.len().checked_sub(count as usize) else {
+                continue;
+            };
+            if length == 0 || count > 2 {
+                continue;
+            }
+            let (data, check) = values.split_at(length);
+            if code_11_common::check_digits(data, count) == check {
+                return Ok(data
+                    .iter()
+                    .map(|&v| code_11::ALPHABET_STRING.as_bytes()[v as usize] as char)
+                    .collect());
+            }
+        }
+        Err(Exceptions::CHECKSUM)
+    }
+}
+
+#[cfg(test)]
+mod Code11ReaderTestCase {
+    use crate::{
+        DecodeHintValue, DecodeHints,
+        common::BitMatrix,
+        oned::{Code11Reader, OneDReader},
+    };
+
+    #[test]
+    fn testDecode() {
+        // "123-45" with the check digit "5"
+        doTest(
+            "123-45",
+            "000001011001011010110100101101100101010110101011011011011010110110101011001000000",
+            &DecodeHints::default(),
+        );
+        // two check digits on a short message
+        doTest(
+            "123-45",
+            "00000101100101101011010010110110010101011010101101101101101011011010100101101011001000000",
+            &DecodeHints::default(),
+        );
+        doTest(
+            "0123456789-0",
+            "00000101100101010110110101101001011011001010101101101101101010011010101001101101001011010101011010101011011010110100101101011001000000",
+            &DecodeHints::default(),
+        );
+    }
+
+    #[test]
+    fn testCheckDigitsHint() {
+        doTest(
+            "12",
+            "0000010110010110101101001011010110010000",
+            &DecodeHints::default().with(DecodeHintValue::Code11CheckDigits(0)),
+        );
+        // the same symbol fails the check digits that are expected by default
+        let matrix = BitMatrix::parse_strings("0000010110010110101101001011010110010000", "1", "0")
+            .expect("bitmatrix parse");
+        assert!(
+            Code11Reader
+                .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+                .is_err()
+        );
+    }
+
+    fn doTest(expected: &str, encoded: &str, hints: &DecodeHints) {
+        let matrix = BitMatrix::parse_strings(encoded, "1", "0").expect("bitmatrix parse");
+        let result = Code11Reader
+            .decode_row(0, &matrix.getRow(0), hints)
+            .expect("decode row");
+        assert_eq!(expected, result.getText());
+    }
+}
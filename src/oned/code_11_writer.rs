@@ -0,0 +1,153 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::common::Result;
+use crate::{BarcodeFormat, EncodeHints, Exceptions};
+
+use super::{OneDimensionalCodeWriter, code_11_common, oned_constants::code_11};
+
+/**
+ * This object renders a Code 11 barcode as a BitMatrix. The contents are digits and dashes; the
+ * check digits are appended, as many as the [`EncodeHints::Code11CheckDigits`] hint asks for.
+ */
+#[derive(OneDWriter, Default)]
+pub struct Code11Writer;
+
+impl OneDimensionalCodeWriter for Code11Writer {
+    fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        self.encode_oned_with_hints(contents, &EncodeHints::default())
+    }
+
+    fn encode_oned_with_hints(&self, contents: &str, hints: &EncodeHints) -> Result<Vec<bool>> {
+        let mut values = contents
+            .chars()
+            .map(|c| {
+                code_11::ALPHABET_STRING
+                    .find(c)
+                    .map(|i| i as u8)
+                    .ok_or_else(|| {
+                        Exceptions::illegal_argument_with(format!(
+                            "Requested contents contains a not encodable character: '{c}'"
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<u8>>>()?;
+
+        let check_digit_count = hints
+            .Code11CheckDigits
+            .unwrap_or_else(|| code_11_common::default_check_digit_count(values.len()));
+        if check_digit_count > 2 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Code 11 takes 0, 1 or 2 check digits, not {check_digit_count}"
+            )));
+        }
+        values.extend(code_11_common::check_digits(&values, check_digit_count));
+
+        let encodings = std::iter::once(code_11::START_STOP_ENCODING)
+            .chain(
+                values
+                    .iter()
+                    .map(|&v| code_11::CHARACTER_ENCODINGS[v as usize]),
+            )
+            .chain(std::iter::once(code_11::START_STOP_ENCODING));
+
+        let mut result = Vec::new();
+        for (i, encoding) in encodings.enumerate() {
+            if i > 0 {
+                // narrow inter-character gap
+                result.push(false);
+            }
+            for element in 0..5 {
+                let width = if encoding & (0x10 >> element) != 0 {
+                    2
+                } else {
+                    1
+                };
+                result.extend(std::iter::repeat_n(element % 2 == 0, width));
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn getSupportedWriteFormats(&self) -> Option<Vec<crate::BarcodeFormat>> {
+        Some(vec![BarcodeFormat::CODE_11])
+    }
+}
+
+#[cfg(test)]
+mod Code11WriterTestCase {
+    use crate::{
+        BarcodeFormat, EncodeHintValue, EncodeHints, Writer,
+        common::{BitMatrix, bit_matrix_test_case},
+    };
+
+    use super::Code11Writer;
+
+    #[test]
+    fn testEncode() {
+        // one check digit for up to 10 characters
+        doTest(
+            "123-45",
+            None,
+            concat!(
+                "00000",
+                "1011001011010110100101101100101010110101011011011011010110110101011001",
+                "00000"
+            ),
+        );
+        // two check digits for longer contents
+        doTest(
+            "0123456789-0",
+            None,
+            concat!(
+                "00000",
+                "101100101010110110101101001011011001010101101101101101010011010101001101101001011010101011010101011011010110100101101011001",
+                "00000"
+            ),
+        );
+    }
+
+    #[test]
+    fn testCheckDigitsHint() {
+        doTest(
+            "123-45",
+            Some(2),
+            concat!(
+                "00000",
+                "101100101101011010010110110010101011010101101101101101011011010100101101011001",
+                "00000"
+            ),
+        );
+        doTest(
+            "12",
+            Some(0),
+            concat!("00000", "1011001011010110100101101011001", "00000"),
+        );
+    }
+
+    #[test]
+    fn testInvalidContents() {
+        assert!(
+            Code11Writer
+                .encode("12A", &BarcodeFormat::CODE_11, 0, 0)
+                .is_err()
+        );
+        let hints = EncodeHints::default().with(EncodeHintValue::Code11CheckDigits(3));
+        assert!(
+            Code11Writer
+                .encode_with_hints("12", &BarcodeFormat::CODE_11, 0, 0, &hints)
+                .is_err()
+        );
+    }
+
+    fn doTest(input: &str, check_digits: Option<u32>, expected: &str) {
+        let mut hints = EncodeHints::default();
+        if let Some(count) = check_digits {
+            hints = hints.with(EncodeHintValue::Code11CheckDigits(count));
+        }
+        let result: BitMatrix = Code11Writer
+            .encode_with_hints(input, &BarcodeFormat::CODE_11, 0, 0, &hints)
+            .expect("must encode");
+        assert_eq!(expected, bit_matrix_test_case::matrix_to_string(&result));
+    }
+}
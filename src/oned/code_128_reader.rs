@@ -31,7 +31,7 @@ use super::{OneDReader, one_d_reader, oned_constants::code_128::CODE_PATTERNS};
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, Default)]
+#[derive(OneDReader, Clone, Default)]
 pub struct Code128Reader;
 
 impl OneDReader for Code128Reader {
@@ -0,0 +1,161 @@
+//! Pharmaceutical numbers carried by Code 39: the Italian Code 32 and the German PZN.
+
+use crate::{Exceptions, common::Result};
+
+/// Base 32 digits of Code 32, the digits and the letters but for the vowels
+const CODE_32_ALPHABET: &str = "0123456789BCDFGHJKLMNPQRSTUVWXYZ";
+
+/// Check digit of the first eight digits of a Code 32 number. Digits in odd positions count
+/// once, those in even positions are doubled and the digits of the product added.
+fn code_32_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits[..8]
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = d as u32 * (1 + i as u32 % 2);
+            d / 10 + d % 10
+        })
+        .sum();
+    (sum % 10) as u8
+}
+
+/// Check digit of a PZN, weighting its digits 1 to 7 for a PZN8 and 2 to 7 for the older
+/// PZN7. `None` if the sum leaves 10, which no PZN is given.
+fn pzn_check_digit(digits: &[u8]) -> Option<u8> {
+    let first_weight = 8 - digits.len() as u32;
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| (first_weight + i as u32) * d as u32)
+        .sum();
+    Some((sum % 11) as u8).filter(|&check| check < 10)
+}
+
+fn parse_digits(contents: &str) -> Option<Vec<u8>> {
+    contents
+        .bytes()
+        .map(|b| b.is_ascii_digit().then(|| b - b'0'))
+        .collect()
+}
+
+/// The Code 39 data of a Code 32 number. The contents are its 8 digits, optionally
+/// followed by the check digit and preceded by the `A` it is printed with.
+#[cfg(feature = "encoders")]
+pub fn code_32_to_code_39(contents: &str) -> Result<String> {
+    let number = contents.strip_prefix('A').unwrap_or(contents);
+    let mut digits = parse_digits(number)
+        .filter(|digits| digits.len() == 8 || digits.len() == 9)
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "Code 32 contents must be 8 digits, optionally followed by the check digit, got '{contents}'"
+            ))
+        })?;
+    let check = code_32_check_digit(&digits);
+    if digits.len() == 9 && digits[8] != check {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Invalid Code 32 check digit {}, expected {check}",
+            digits[8]
+        )));
+    }
+    digits.truncate(8);
+    digits.push(check);
+
+    let mut value = digits.iter().fold(0, |value, &d| value * 10 + d as u32);
+    let mut data = vec![0; 6];
+    for c in data.iter_mut().rev() {
+        *c = CODE_32_ALPHABET.as_bytes()[(value % 32) as usize];
+        value /= 32;
+    }
+    Ok(String::from_utf8(data).expect("alphabet is ASCII"))
+}
+
+/// The Code 32 number, with the `A` it is printed with, carried by Code 39 data
+#[cfg(feature = "decoders")]
+pub fn code_32_from_code_39(data: &str) -> Result<String> {
+    if data.len() != 6 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let value = data.chars().try_fold(0, |value, c| {
+        CODE_32_ALPHABET
+            .find(c)
+            .map(|digit| value * 32 + digit as u32)
+    });
+    let number = value
+        .filter(|&value| value < 1_000_000_000)
+        .map(|value| format!("{value:09}"))
+        .ok_or(Exceptions::NOT_FOUND)?;
+    let digits = parse_digits(&number).ok_or(Exceptions::NOT_FOUND)?;
+    if digits[8] != code_32_check_digit(&digits) {
+        return Err(Exceptions::CHECKSUM);
+    }
+    Ok(format!("A{number}"))
+}
+
+/// The Code 39 data of a PZN8. The contents are its 7 digits, optionally followed by the
+/// check digit.
+#[cfg(feature = "encoders")]
+pub fn pzn_to_code_39(contents: &str) -> Result<String> {
+    let digits = parse_digits(contents)
+        .filter(|digits| digits.len() == 7 || digits.len() == 8)
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "PZN contents must be 7 digits, optionally followed by the check digit, got '{contents}'"
+            ))
+        })?;
+    let check = pzn_check_digit(&digits[..7]).ok_or_else(|| {
+        Exceptions::illegal_argument_with(format!(
+            "'{contents}' is not a valid PZN, its check digit would be 10"
+        ))
+    })?;
+    if digits.len() == 8 && digits[7] != check {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Invalid PZN check digit {}, expected {check}",
+            digits[7]
+        )));
+    }
+    Ok(format!("-{}{check}", &contents[..7]))
+}
+
+/// The digits of the PZN8 or PZN7 carried by Code 39 data, a `-` followed by the digits
+#[cfg(feature = "decoders")]
+pub fn pzn_from_code_39(data: &str) -> Result<String> {
+    let number = data.strip_prefix('-').ok_or(Exceptions::NOT_FOUND)?;
+    let digits = parse_digits(number)
+        .filter(|digits| digits.len() == 7 || digits.len() == 8)
+        .ok_or(Exceptions::NOT_FOUND)?;
+    let (check, data_digits) = digits.split_last().ok_or(Exceptions::NOT_FOUND)?;
+    if pzn_check_digit(data_digits) != Some(*check) {
+        return Err(Exceptions::CHECKSUM);
+    }
+    Ok(number.to_owned())
+}
+
+#[cfg(all(test, feature = "encoders", feature = "decoders"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_32() {
+        // AIC 01234567 has the check digit 6
+        assert_eq!(code_32_to_code_39("01234567").unwrap(), "0CSSBD");
+        assert_eq!(code_32_to_code_39("A012345676").unwrap(), "0CSSBD");
+        assert!(code_32_to_code_39("012345675").is_err());
+        assert_eq!(code_32_from_code_39("0CSSBD").unwrap(), "A012345676");
+        // 012345675 has the wrong check digit
+        assert!(code_32_from_code_39("0CSSBC").is_err());
+        // vowels are not base 32 digits
+        assert!(code_32_from_code_39("0CSSBA").is_err());
+    }
+
+    #[test]
+    fn pzn() {
+        assert_eq!(pzn_to_code_39("1234567").unwrap(), "-12345678");
+        assert_eq!(pzn_to_code_39("12345678").unwrap(), "-12345678");
+        assert!(pzn_to_code_39("12345679").is_err());
+        assert_eq!(pzn_from_code_39("-12345678").unwrap(), "12345678");
+        assert!(pzn_from_code_39("-12345679").is_err());
+        assert!(pzn_from_code_39("12345678").is_err());
+        // PZN7, weighted from 2
+        assert_eq!(pzn_from_code_39("-1234562").unwrap(), "1234562");
+    }
+}
@@ -0,0 +1,169 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::common::{BitArray, Result};
+use crate::{
+    BarcodeFormat, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
+
+use super::{Code39Reader, OneDReader, code_39_pharma_common};
+
+macro_rules! code_39_pharma_reader {
+    ($(#[$doc:meta])* $name:ident, $from_code_39:path, $format:expr) => {
+        $(#[$doc])*
+        #[derive(OneDReader, Clone, Default)]
+        pub struct $name;
+
+        impl OneDReader for $name {
+            fn decode_row(
+                &mut self,
+                rowNumber: u32,
+                row: &BitArray,
+                hints: &DecodeHints,
+            ) -> Result<RXingResult> {
+                let code_39 = Code39Reader::default().decode_row(rowNumber, row, hints)?;
+                let text = $from_code_39(code_39.getText())?;
+                Ok(pharma_result(&code_39, &text, $format))
+            }
+        }
+    };
+}
+
+fn pharma_result(code_39: &RXingResult, text: &str, format: BarcodeFormat) -> RXingResult {
+    let mut result = RXingResult::new(text, Vec::new(), code_39.getPoints().to_vec(), format);
+    result.putMetadata(
+        RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+        RXingResultMetadataValue::SymbologyIdentifier("]A0".to_owned()),
+    );
+    result
+}
+
+/// Reports a Code 39 result as PZN or Code 32 when its contents are a number of either with
+/// a valid check digit, otherwise returns it unchanged
+pub(crate) fn relabel_code_39(code_39: RXingResult) -> RXingResult {
+    if let Ok(text) = code_39_pharma_common::pzn_from_code_39(code_39.getText()) {
+        return pharma_result(&code_39, &text, BarcodeFormat::PZN);
+    }
+    if let Ok(text) = code_39_pharma_common::code_32_from_code_39(code_39.getText()) {
+        return pharma_result(&code_39, &text, BarcodeFormat::CODE_32);
+    }
+    code_39
+}
+
+code_39_pharma_reader!(
+    /**
+     * <p>Decodes Code 32, the Italian pharmaceutical code, which carries a 9 digit number
+     * in base 32 as six Code 39 characters. The check digit is validated, and the result is
+     * the number preceded by `A`, as it is printed.</p>
+     *
+     * <p>Code 32 is plain Code 39 to anyone not expecting it. When formats are given in
+     * [`DecodeHints::PossibleFormats`] it is only read if it is among them, without them Code
+     * 39 whose check digit validates as Code 32 is reported as Code 32.</p>
+     */
+    Code32Reader,
+    code_39_pharma_common::code_32_from_code_39,
+    BarcodeFormat::CODE_32
+);
+
+code_39_pharma_reader!(
+    /**
+     * <p>Decodes PZN, the German Pharmazentralnummer, carried by Code 39 as a `-` followed by
+     * its digits. The modulo 11 check digit of a PZN8, or of an older PZN7, is validated,
+     * and the result is the digits.</p>
+     *
+     * <p>As with [`Code32Reader`], PZN is only read when it is among the
+     * [`DecodeHints::PossibleFormats`] given, or when none are given and the check digit
+     * validates.</p>
+     */
+    PZNReader,
+    code_39_pharma_common::pzn_from_code_39,
+    BarcodeFormat::PZN
+);
+
+#[cfg(test)]
+mod Code39PharmaReaderTestCase {
+    use crate::{
+        BarcodeFormat, DecodeHints,
+        common::BitMatrix,
+        oned::{Code32Reader, OneDReader, PZNReader},
+    };
+
+    // Code 39 "0CSSBD", Code 32 A012345676
+    const CODE_32: &str = "1001011011010101001101101011011010010101011010110010101101011001010110100101101010110010110100101101101";
+    // Code 39 "-12345678"
+    const PZN: &str = "1001011011010100101011011011010010101101011001010110110110010101010100110101101101001101010101100110101010100101101101101001011010100101101101";
+    // Code 39 "-1234565", a PZN7 with the wrong check digit
+    const BAD_PZN: &str = "100101101101010010101101101101001010110101100101011011011001010101010011010110110100110101010110011010101101001101010100101101101";
+
+    fn decode(reader: &mut impl OneDReader, encoded: &str) -> Option<(String, BarcodeFormat)> {
+        let padded = format!("0000000000{encoded}0000000000");
+        let matrix = BitMatrix::parse_strings(&padded, "1", "0").expect("bitmatrix parse");
+        reader
+            .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+            .ok()
+            .map(|result| (result.getText().to_owned(), *result.getBarcodeFormat()))
+    }
+
+    #[test]
+    fn testDecode() {
+        assert_eq!(
+            decode(&mut Code32Reader, CODE_32),
+            Some(("A012345676".to_owned(), BarcodeFormat::CODE_32))
+        );
+        assert_eq!(
+            decode(&mut PZNReader, PZN),
+            Some(("12345678".to_owned(), BarcodeFormat::PZN))
+        );
+    }
+
+    #[test]
+    fn testRejectsOtherCode39() {
+        assert_eq!(decode(&mut Code32Reader, PZN), None);
+        assert_eq!(decode(&mut PZNReader, CODE_32), None);
+        assert_eq!(decode(&mut PZNReader, BAD_PZN), None);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn testPossibleFormats() {
+        use std::collections::HashSet;
+
+        use crate::{
+            BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, MultiFormatReader,
+            MultiFormatWriter, Reader, Writer, common::HybridBinarizer,
+        };
+
+        let matrix = MultiFormatWriter
+            .encode("1234567", &BarcodeFormat::PZN, 200, 50)
+            .expect("encode");
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+
+        let result = MultiFormatReader::default()
+            .decode(&mut bitmap)
+            .expect("decode");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::PZN);
+        assert_eq!(result.getText(), "12345678");
+
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::CODE_39,
+        ])));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut bitmap, &hints)
+            .expect("decode");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::CODE_39);
+        assert_eq!(result.getText(), "-12345678");
+
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::CODE_39,
+            BarcodeFormat::PZN,
+        ])));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut bitmap, &hints)
+            .expect("decode");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::PZN);
+        assert_eq!(result.getText(), "12345678");
+    }
+}
@@ -0,0 +1,87 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::BarcodeFormat;
+use crate::common::Result;
+
+use super::{Code39Writer, OneDimensionalCodeWriter, code_39_pharma_common};
+
+macro_rules! code_39_pharma_writer {
+    ($(#[$doc:meta])* $name:ident, $to_code_39:path, $format:expr) => {
+        $(#[$doc])*
+        #[derive(OneDWriter, Default)]
+        pub struct $name;
+
+        impl OneDimensionalCodeWriter for $name {
+            fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+                Code39Writer.encode_oned(&$to_code_39(contents)?)
+            }
+
+            fn getSupportedWriteFormats(&self) -> Option<Vec<crate::BarcodeFormat>> {
+                Some(vec![$format])
+            }
+        }
+    };
+}
+
+code_39_pharma_writer!(
+    /**
+     * This object renders a Code 32 barcode as a BitMatrix. The contents are the 8 digits of
+     * the number, optionally followed by its check digit and preceded by `A`; the check digit
+     * is added when missing and validated otherwise.
+     */
+    Code32Writer,
+    code_39_pharma_common::code_32_to_code_39,
+    BarcodeFormat::CODE_32
+);
+
+code_39_pharma_writer!(
+    /**
+     * This object renders a PZN8 barcode as a BitMatrix. The contents are the 7 digits of the
+     * PZN, optionally followed by its check digit; the check digit is added when missing and
+     * validated otherwise.
+     */
+    PZNWriter,
+    code_39_pharma_common::pzn_to_code_39,
+    BarcodeFormat::PZN
+);
+
+#[cfg(test)]
+mod Code39PharmaWriterTestCase {
+    use crate::{
+        BarcodeFormat, Writer,
+        common::{BitMatrix, bit_matrix_test_case},
+        oned::Code39Writer,
+    };
+
+    use super::{Code32Writer, PZNWriter};
+
+    fn encode(writer: &impl Writer, format: BarcodeFormat, contents: &str) -> Option<String> {
+        let result: BitMatrix = writer.encode(contents, &format, 0, 0).ok()?;
+        Some(bit_matrix_test_case::matrix_to_string(&result))
+    }
+
+    #[test]
+    fn testEncode() {
+        // AIC 01234567, check digit 6, is "0CSSBD" in base 32
+        assert_eq!(
+            encode(&Code32Writer, BarcodeFormat::CODE_32, "01234567"),
+            encode(&Code39Writer, BarcodeFormat::CODE_39, "0CSSBD")
+        );
+        assert_eq!(
+            encode(&PZNWriter, BarcodeFormat::PZN, "1234567"),
+            encode(&Code39Writer, BarcodeFormat::CODE_39, "-12345678")
+        );
+        assert!(encode(&Code32Writer, BarcodeFormat::CODE_39, "01234567").is_none());
+    }
+
+    #[test]
+    fn testCheckDigits() {
+        assert_eq!(
+            encode(&Code32Writer, BarcodeFormat::CODE_32, "A012345676"),
+            encode(&Code32Writer, BarcodeFormat::CODE_32, "01234567")
+        );
+        assert!(encode(&Code32Writer, BarcodeFormat::CODE_32, "012345675").is_none());
+        assert!(encode(&PZNWriter, BarcodeFormat::PZN, "12345679").is_none());
+        assert!(encode(&PZNWriter, BarcodeFormat::PZN, "123456").is_none());
+    }
+}
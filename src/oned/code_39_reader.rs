@@ -34,7 +34,7 @@ use crate::oned::oned_constants::code_39::*;
  * @author Sean Owen
  * @see Code93Reader
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct Code39Reader {
     usingCheckDigit: bool,
     extendedMode: bool,
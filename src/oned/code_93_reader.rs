@@ -34,7 +34,7 @@ use crate::oned::oned_constants::code_93::*;
  * @author Sean Owen
  * @see Code39Reader
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct Code93Reader {
     decodeRowRXingResult: String,
     counters: [u32; 6],
@@ -8,7 +8,7 @@
 use crate::Binarizer;
 use crate::common::cpp_essentials::{GetPatternRow, PatternRow, PatternView};
 use crate::{BarcodeFormat, BinaryBitmap, DecodeHints, Exceptions, PointT, point};
-use crate::{RXingResult, Reader};
+use crate::{ImmutableReader, RXingResult, Reader};
 
 #[cfg(feature = "multi_barcode_readers")]
 use crate::multi::MultipleBarcodeReader;
@@ -432,6 +432,16 @@ impl Reader for ODReader<'_> {
     }
 }
 
+impl ImmutableReader for ODReader<'_> {
+    fn immutable_decode_with_hints<B: crate::Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> crate::common::Result<crate::RXingResult> {
+        self.decode_single(hints, image)
+    }
+}
+
 #[cfg(feature = "multi_barcode_readers")]
 impl MultipleBarcodeReader for ODReader<'_> {
     fn decode_multiple<B: crate::Binarizer>(
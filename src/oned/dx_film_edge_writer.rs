@@ -0,0 +1,270 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    common::{BitMatrix, Result},
+};
+
+use super::postal::check_arguments;
+
+/// Clock track of a DX code carrying a frame number
+const CLOCK_FN: &str = "1111101010101010101010101010111";
+
+/// Clock track of a DX code without a frame number
+const CLOCK_NO_FN: &str = "11111010101010101010111";
+
+/// Smallest height of each track, in modules
+const TRACK_HEIGHT: u32 = 3;
+
+/// Frame numbers printed as letters, and the numbers they are encoded as
+const NAMED_FRAMES: [(&str, u32); 5] = [("S", 62), ("X", 62), ("K", 63), ("00", 63), ("F", 0)];
+
+fn invalid(contents: &str, reason: &str) -> Exceptions {
+    Exceptions::illegal_argument_with(format!(
+        "Invalid DX film edge contents '{contents}': {reason}"
+    ))
+}
+
+/// Parses the DX number, either as its two parts `DX1-DX2` or as a 4 digit DX extract or
+/// 6 digit DX full number, into its two parts
+fn parse_dx_number(contents: &str, dx: &str) -> Result<(u32, u32)> {
+    let (dx_1, dx_2) = if let Some((dx_1, dx_2)) = dx.split_once('-') {
+        let parse = |part: &str| {
+            part.parse::<u32>()
+                .ok()
+                .filter(|_| part.bytes().all(|b| b.is_ascii_digit()))
+        };
+        parse(dx_1)
+            .zip(parse(dx_2))
+            .ok_or_else(|| invalid(contents, "expected DX1-DX2"))?
+    } else {
+        let extract = match dx.len() {
+            4 => dx,
+            // The DX full number adds a digit on each side of the DX extract
+            6 => &dx[1..5],
+            _ => {
+                return Err(invalid(
+                    contents,
+                    "expected DX1-DX2, a 4 digit DX extract or a 6 digit DX full number",
+                ));
+            }
+        };
+        let extract = extract
+            .parse::<u32>()
+            .ok()
+            .filter(|_| dx.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| invalid(contents, "the DX number must be digits"))?;
+        (extract >> 4, extract & 0xF)
+    };
+    if !(1..=127).contains(&dx_1) {
+        return Err(invalid(contents, "DX part 1 must be from 1 to 127"));
+    }
+    if dx_2 > 15 {
+        return Err(invalid(contents, "DX part 2 must be from 0 to 15"));
+    }
+    Ok((dx_1, dx_2))
+}
+
+/// Parses a frame number from 0 to 63, optionally followed by `A` for a half frame
+fn parse_frame(contents: &str, frame: &str) -> Result<(u32, bool)> {
+    let frame = frame.to_ascii_uppercase();
+    let (number, half) = match frame.strip_suffix('A') {
+        Some(number) => (number, true),
+        None => (frame.as_str(), false),
+    };
+    let number = NAMED_FRAMES
+        .iter()
+        .find(|(name, _)| *name == number)
+        .map(|(_, value)| *value)
+        .or_else(|| {
+            number
+                .parse::<u32>()
+                .ok()
+                .filter(|_| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .filter(|number| *number <= 63)
+        .ok_or_else(|| {
+            invalid(
+                contents,
+                "the frame number must be from 0 to 63, optionally followed by 'A'",
+            )
+        })?;
+    Ok((number, half))
+}
+
+/// Encodes the clock and data tracks of a DX code
+fn encode_tracks(contents: &str) -> Result<(Vec<bool>, Vec<bool>)> {
+    let (dx, frame) = match contents.split_once('/') {
+        Some((dx, frame)) => (dx, Some(parse_frame(contents, frame)?)),
+        None => (contents, None),
+    };
+    let (dx_1, dx_2) = parse_dx_number(contents, dx)?;
+
+    let append = |bits: &mut Vec<bool>, value: u32, count: u32| {
+        bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+    };
+    // Start pattern, then the signal starting with a separator
+    let mut data = vec![true, false, true, false, true, false];
+    append(&mut data, dx_1, 7);
+    data.push(false);
+    append(&mut data, dx_2, 4);
+    if let Some((number, half)) = frame {
+        append(&mut data, number, 6);
+        data.push(half);
+        data.push(false);
+    }
+    let parity = data[6..].iter().filter(|bit| **bit).count() % 2 == 1;
+    data.push(parity);
+    // Separator, then the stop pattern
+    data.extend([false, true, false, true]);
+
+    let clock = if frame.is_some() {
+        CLOCK_FN
+    } else {
+        CLOCK_NO_FN
+    };
+    Ok((clock.chars().map(|c| c == '1').collect(), data))
+}
+
+/// Renders a DX film edge barcode, the clock track above the data track.
+///
+/// The contents are the DX number, either as its two parts `DX1-DX2` as the reader
+/// returns them, or as a 4 digit DX extract or 6 digit DX full number, optionally followed
+/// by `/` and a frame number from 0 to 63. The frame number may be followed by `A` for a
+/// half frame, and may also be given as `S` or `X` (62), `K` or `00` (63) or `F` (0). A
+/// frame number makes both tracks longer.
+#[derive(Default)]
+pub struct DXFilmEdgeWriter;
+
+impl Writer for DXFilmEdgeWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        let margin = check_arguments(
+            contents,
+            format,
+            BarcodeFormat::DXFilmEdge,
+            width,
+            height,
+            hints,
+        )?;
+        let (clock, data) = encode_tracks(contents)?;
+
+        let code_width = clock.len() as u32;
+        let full_width = code_width + 2 * margin;
+        let output_width = (width.max(0) as u32).max(full_width);
+        let multiple = output_width / full_width;
+        let output_height = (height.max(0) as u32).max(2 * TRACK_HEIGHT * multiple);
+        let left = (output_width - code_width * multiple) / 2;
+        let middle = output_height / 2;
+
+        let mut output = BitMatrix::new(output_width, output_height)?;
+        for (track, top, bottom) in [(clock, 0, middle), (data, middle, output_height)] {
+            for (x, _) in track.iter().enumerate().filter(|(_, dark)| **dark) {
+                output.setRegion(left + x as u32 * multiple, top, multiple, bottom - top)?;
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, Writer, common::BitMatrix};
+
+    use super::DXFilmEdgeWriter;
+
+    fn encode(contents: &str) -> Option<(String, String)> {
+        let matrix: BitMatrix = DXFilmEdgeWriter
+            .encode(contents, &BarcodeFormat::DXFilmEdge, 0, 0)
+            .ok()?;
+        let row = |y| {
+            (6..matrix.getWidth() - 6)
+                .map(|x| if matrix.get(x, y) { '1' } else { '0' })
+                .collect()
+        };
+        Some((row(0), row(matrix.getHeight() - 1)))
+    }
+
+    fn tracks(clock: &str, data: &str) -> Option<(String, String)> {
+        Some((clock.to_owned(), data.to_owned()))
+    }
+
+    #[test]
+    fn encode_without_frame_number() {
+        let expected = tracks("11111010101010101010111", "10101010011110011100101");
+        assert_eq!(encode("79-7"), expected);
+        assert_eq!(encode("1271"), expected);
+        assert_eq!(encode("012710"), expected);
+    }
+
+    #[test]
+    fn encode_with_frame_number() {
+        assert_eq!(
+            encode("79-7/11A"),
+            tracks(
+                "1111101010101010101010101010111",
+                "1010101001111001110010111000101"
+            )
+        );
+        assert_eq!(
+            encode("115-10/K"),
+            tracks(
+                "1111101010101010101010101010111",
+                "1010101110011010101111110010101"
+            )
+        );
+        assert_eq!(encode("115-10/k"), encode("115-10/63"));
+    }
+
+    #[test]
+    fn rejects_invalid_contents() {
+        for contents in [
+            "0-7", "128-0", "79-16", "79", "12345", "7a-7", "79-7/64", "79-7/", "79-7/11B",
+        ] {
+            assert_eq!(encode(contents), None, "{contents}");
+        }
+        assert!(
+            DXFilmEdgeWriter
+                .encode("79-7", &BarcodeFormat::CODE_128, 0, 0)
+                .is_err()
+        );
+    }
+
+    #[cfg(all(feature = "image", feature = "decoders"))]
+    #[test]
+    fn round_trip() {
+        use crate::{
+            BinaryBitmap, BufferedImageLuminanceSource, MultiFormatReader, Reader,
+            common::HybridBinarizer,
+        };
+
+        for contents in ["79-7", "115-10/11A", "1-0/0"] {
+            let matrix = DXFilmEdgeWriter
+                .encode(contents, &BarcodeFormat::DXFilmEdge, 200, 40)
+                .expect("encode");
+            let image: image::DynamicImage = (&matrix).into();
+            let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+                BufferedImageLuminanceSource::new(image),
+            ));
+            let result = MultiFormatReader::default()
+                .decode(&mut bitmap)
+                .expect("decode");
+            assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::DXFilmEdge);
+            assert_eq!(result.getText(), contents);
+        }
+    }
+}
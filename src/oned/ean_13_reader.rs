@@ -33,7 +33,7 @@ use crate::common::Result;
  * @author Sean Owen
  * @author alasdair@google.com (Alasdair Mackintosh)
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(OneDReader, EANReader, Clone, Default)]
 pub struct EAN13Reader;
 impl UPCEANReader for EAN13Reader {
     fn getBarcodeFormat(&self) -> crate::BarcodeFormat {
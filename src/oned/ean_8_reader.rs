@@ -27,7 +27,7 @@ use super::oned_constants::upc_ean_shared::*;
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(OneDReader, EANReader, Clone, Default)]
 pub struct EAN8Reader;
 
 impl UPCEANReader for EAN8Reader {
@@ -0,0 +1,581 @@
+use std::ops::Range;
+
+use crate::{
+    BarcodeFormat, EncodeHints, Exceptions,
+    common::Result,
+    oned::{
+        CodaBarWriter, Code11Writer, Code32Writer, Code39Writer, Code93Writer, Code128Writer,
+        EAN8Writer, EAN13Writer, IATATwoOfFiveWriter, ITFWriter, IndustrialTwoOfFiveWriter,
+        MSIWriter, MatrixTwoOfFiveWriter, OneDimensionalCodeWriter, PZNWriter, PharmacodeWriter,
+        TelepenWriter, UPCEWriter,
+        oned_constants::{upc_ean_extension, upc_ean_shared},
+        upcean_common,
+    },
+};
+
+#[cfg(feature = "image")]
+use super::HriFont;
+
+/// Quiet zone on each side of formats without a quiet zone of their own, in modules
+const DEFAULT_QUIET_ZONE: u32 = 10;
+
+/// Quiet zone to the right of an add-on, in modules
+const ADD_ON_QUIET_ZONE: u32 = 5;
+
+/// Size of the digits printed in the quiet zones of UPC symbols, relative to the others
+const OUTER_DIGIT_SCALE: f32 = 0.75;
+
+/// Separates the contents of an EAN or UPC symbol from those of its add-on
+pub const ADD_ON_SEPARATOR: char = '+';
+
+/// Options of [`HriLayout`]. Sizes are in modules unless noted otherwise.
+#[derive(Clone)]
+pub struct HriOptions {
+    /// Height of the bars, not counting the extension of guard bars
+    pub bar_height: f32,
+
+    /// Height of the text
+    pub font_size: f32,
+
+    /// Space between the bars and the text
+    pub text_gap: f32,
+
+    /// Width of a module when rendering, in pixels for images and in user units for SVG
+    pub module_size: u32,
+
+    /// Font family of the text in SVG documents
+    pub font_family: String,
+
+    /// Font of the text in images
+    #[cfg(feature = "image")]
+    pub font: HriFont,
+}
+
+impl Default for HriOptions {
+    fn default() -> Self {
+        Self {
+            bar_height: 50.0,
+            font_size: 8.0,
+            text_gap: 1.0,
+            module_size: 2,
+            font_family: "OCR-B, monospace".to_owned(),
+            #[cfg(feature = "image")]
+            font: HriFont::default(),
+        }
+    }
+}
+
+/// A bar of an [`HriLayout`], spanning one or more dark modules
+#[derive(Debug, Clone, PartialEq)]
+pub struct HriBar {
+    /// Left edge, in modules from the left edge of the layout
+    pub x: u32,
+    /// Width, in modules
+    pub width: u32,
+    /// Top edge, in modules from the top of the layout
+    pub top: f32,
+    /// Bottom edge, in modules from the top of the layout
+    pub bottom: f32,
+}
+
+/// A run of text of an [`HriLayout`], placed in a box one line high
+#[derive(Debug, Clone, PartialEq)]
+pub struct HriText {
+    pub text: String,
+    /// Left edge of the box, in modules from the left edge of the layout
+    pub left: f32,
+    /// Width of the box, in modules
+    pub width: f32,
+    /// Top edge of the box, in modules from the top of the layout
+    pub top: f32,
+    /// Height of the text, in modules
+    pub size: f32,
+    /// Whether the characters are spread evenly over the box, each centered in an equal
+    /// share of it, rather than centered together
+    pub spread: bool,
+}
+
+/// Bars and text of a one dimensional barcode, measured in modules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HriLayout {
+    /// Width, in modules, quiet zones included
+    pub width: u32,
+    /// Height, in modules
+    pub height: f32,
+    pub bars: Vec<HriBar>,
+    pub texts: Vec<HriText>,
+}
+
+/// How the text of an EAN or UPC symbol is laid out, in modules of the main symbol
+struct UpcEanRules {
+    quiet_zones: (u32, u32),
+    /// Modules of the bars extending into the text
+    extended: &'static [Range<u32>],
+    /// Digits printed in the left and right quiet zones, if any
+    outer_left: Option<Range<usize>>,
+    outer_right: Option<Range<usize>>,
+    /// Digits printed between the guard bars, and the modules they are spread over
+    groups: &'static [(Range<usize>, Range<u32>)],
+    /// Space between the main symbol and an add-on
+    add_on_gap: u32,
+}
+
+const EAN_13_RULES: UpcEanRules = UpcEanRules {
+    quiet_zones: (11, 7),
+    extended: &[0..3, 45..50, 92..95],
+    outer_left: Some(0..1),
+    outer_right: None,
+    groups: &[(1..7, 3..45), (7..13, 50..92)],
+    add_on_gap: 7,
+};
+
+const EAN_8_RULES: UpcEanRules = UpcEanRules {
+    quiet_zones: (7, 7),
+    extended: &[0..3, 31..36, 64..67],
+    outer_left: None,
+    outer_right: None,
+    groups: &[(0..4, 3..31), (4..8, 36..64)],
+    add_on_gap: 7,
+};
+
+const UPC_A_RULES: UpcEanRules = UpcEanRules {
+    quiet_zones: (9, 9),
+    // The bars of the first and last digits extend along with the guard bars
+    extended: &[0..10, 45..50, 85..95],
+    outer_left: Some(0..1),
+    outer_right: Some(11..12),
+    groups: &[(1..6, 10..45), (6..11, 50..85)],
+    add_on_gap: 9,
+};
+
+const UPC_E_RULES: UpcEanRules = UpcEanRules {
+    quiet_zones: (9, 7),
+    extended: &[0..3, 45..51],
+    outer_left: Some(0..1),
+    outer_right: Some(7..8),
+    groups: &[(1..7, 3..45)],
+    add_on_gap: 7,
+};
+
+impl HriLayout {
+    /// Lays out `contents` encoded as `format`. EAN-13, UPC-A and UPC-E contents may end
+    /// with [`ADD_ON_SEPARATOR`] and the 2 or 5 digits of an add-on.
+    pub fn new(contents: &str, format: &BarcodeFormat, options: &HriOptions) -> Result<Self> {
+        Self::with_hints(contents, format, &EncodeHints::default(), options)
+    }
+
+    /// As [`HriLayout::new`], encoding the bars with `hints`
+    pub fn with_hints(
+        contents: &str,
+        format: &BarcodeFormat,
+        hints: &EncodeHints,
+        options: &HriOptions,
+    ) -> Result<Self> {
+        match format {
+            BarcodeFormat::EAN_13
+            | BarcodeFormat::EAN_8
+            | BarcodeFormat::UPC_A
+            | BarcodeFormat::UPC_E => Self::upc_ean(contents, format, options),
+            _ => {
+                let modules = encode_modules(contents, format, hints)?;
+                Ok(Self::centered(
+                    &modules,
+                    &display_text(contents, format),
+                    options,
+                ))
+            }
+        }
+    }
+
+    /// The contents centered below the bars
+    fn centered(modules: &[bool], text: &str, options: &HriOptions) -> Self {
+        let text_top = options.bar_height + options.text_gap;
+        let mut layout = Self {
+            width: modules.len() as u32 + 2 * DEFAULT_QUIET_ZONE,
+            height: text_top + options.font_size,
+            bars: Vec::new(),
+            texts: vec![HriText {
+                text: text.to_owned(),
+                left: DEFAULT_QUIET_ZONE as f32,
+                width: modules.len() as f32,
+                top: text_top,
+                size: options.font_size,
+                spread: false,
+            }],
+        };
+        layout.add_bars(modules, DEFAULT_QUIET_ZONE, 0.0, |_| options.bar_height);
+        layout
+    }
+
+    fn upc_ean(contents: &str, format: &BarcodeFormat, options: &HriOptions) -> Result<Self> {
+        let (main, add_on) = match contents.split_once(ADD_ON_SEPARATOR) {
+            Some((main, add_on)) if format != &BarcodeFormat::EAN_8 => (main, Some(add_on)),
+            Some(_) => {
+                return Err(Exceptions::illegal_argument_with(
+                    "EAN-8 symbols do not take an add-on",
+                ));
+            }
+            None => (contents, None),
+        };
+
+        let (modules, digits, rules) = match format {
+            BarcodeFormat::EAN_13 => (
+                EAN13Writer.encode_oned(main)?,
+                with_check_digit(main, 12, main)?,
+                &EAN_13_RULES,
+            ),
+            BarcodeFormat::EAN_8 => (
+                EAN8Writer.encode_oned(main)?,
+                with_check_digit(main, 7, main)?,
+                &EAN_8_RULES,
+            ),
+            BarcodeFormat::UPC_A => (
+                EAN13Writer.encode_oned(&format!("0{main}"))?,
+                with_check_digit(main, 11, main)?,
+                &UPC_A_RULES,
+            ),
+            _ => {
+                let modules = UPCEWriter.encode_oned(main)?;
+                let upc_a =
+                    upcean_common::convertUPCEtoUPCA(main).ok_or(Exceptions::ILLEGAL_ARGUMENT)?;
+                (modules, with_check_digit(main, 7, &upc_a)?, &UPC_E_RULES)
+            }
+        };
+        let add_on = add_on.map(encode_add_on).transpose()?;
+
+        let font_size = options.font_size;
+        let text_top = options.bar_height + options.text_gap;
+        let extended_bottom = text_top + font_size / 2.0;
+        let (left_zone, right_zone) = rules.quiet_zones;
+        let main_width = modules.len() as u32;
+        let add_on_left = left_zone + main_width + rules.add_on_gap;
+        let width = match &add_on {
+            Some((add_on_modules, _)) => {
+                add_on_left + add_on_modules.len() as u32 + ADD_ON_QUIET_ZONE
+            }
+            None => left_zone + main_width + right_zone,
+        };
+
+        let mut layout = Self {
+            width,
+            height: text_top + font_size,
+            bars: Vec::new(),
+            texts: Vec::new(),
+        };
+        layout.add_bars(&modules, left_zone, 0.0, |x| {
+            if rules.extended.iter().any(|range| range.contains(&x)) {
+                extended_bottom
+            } else {
+                options.bar_height
+            }
+        });
+
+        let text = |range: Range<usize>, left: f32, width: f32, size: f32, spread: bool| HriText {
+            text: digits[range].to_owned(),
+            left,
+            width,
+            top: text_top + font_size - size,
+            size,
+            spread,
+        };
+        let outer_size = if rules.outer_right.is_some() {
+            font_size * OUTER_DIGIT_SCALE
+        } else {
+            font_size
+        };
+        if let Some(range) = rules.outer_left.clone() {
+            // Printed in the quiet zone, a module away from the bars
+            layout
+                .texts
+                .push(text(range, left_zone as f32 - 8.0, 7.0, outer_size, false));
+        }
+        for (range, modules) in rules.groups {
+            layout.texts.push(text(
+                range.clone(),
+                (left_zone + modules.start) as f32,
+                modules.len() as f32,
+                font_size,
+                true,
+            ));
+        }
+        if let Some(range) = rules.outer_right.clone() {
+            let left = (left_zone + main_width) as f32 + 1.0;
+            layout.texts.push(text(range, left, 7.0, outer_size, false));
+        }
+
+        if let Some((add_on_modules, add_on_digits)) = add_on {
+            // The digits of the add-on are printed above its bars, which start below them
+            let top = font_size + options.text_gap;
+            layout.add_bars(&add_on_modules, add_on_left, top, |_| extended_bottom);
+            let start = upc_ean_extension::START_PATTERN.iter().sum::<u32>();
+            layout.texts.push(HriText {
+                text: add_on_digits,
+                left: (add_on_left + start) as f32,
+                width: (add_on_modules.len() as u32 - start) as f32,
+                top: 0.0,
+                size: font_size,
+                spread: true,
+            });
+        }
+        Ok(layout)
+    }
+
+    /// Adds a bar for each run of dark modules, `left` modules from the left edge, from `top`
+    /// down to the bottom given for the first module of the run
+    fn add_bars(&mut self, modules: &[bool], left: u32, top: f32, bottom: impl Fn(u32) -> f32) {
+        let mut x = 0;
+        while x < modules.len() {
+            if !modules[x] {
+                x += 1;
+                continue;
+            }
+            let width = modules[x..].iter().take_while(|dark| **dark).count();
+            self.bars.push(HriBar {
+                x: left + x as u32,
+                width: width as u32,
+                top,
+                bottom: bottom(x as u32),
+            });
+            x += width;
+        }
+    }
+}
+
+/// The digits of an EAN or UPC number, with the check digit computed over `checked` added
+/// when the number has `length` digits
+fn with_check_digit(digits: &str, length: usize, checked: &str) -> Result<String> {
+    if digits.len() != length {
+        return Ok(digits.to_owned());
+    }
+    let check = upcean_common::getStandardUPCEANChecksum(&checked.chars().collect::<Vec<_>>())?;
+    Ok(format!("{digits}{check}"))
+}
+
+/// Encodes a 2 or 5 digit add-on, returning its modules and digits
+fn encode_add_on(digits: &str) -> Result<(Vec<bool>, String)> {
+    let values = digits
+        .bytes()
+        .map(|b| b.is_ascii_digit().then(|| (b - b'0') as usize))
+        .collect::<Option<Vec<_>>>()
+        .filter(|values| values.len() == 2 || values.len() == 5)
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "Add-on must be 2 or 5 digits, got '{digits}'"
+            ))
+        })?;
+    // Parities of the digits, the first digit in the highest bit, 1 for the G patterns
+    let parities = if values.len() == 2 {
+        (values[0] * 10 + values[1]) % 4
+    } else {
+        let check = (3 * (values[0] + values[2] + values[4]) + 9 * (values[1] + values[3])) % 10;
+        upc_ean_extension::EXTENSION_5_CHECK_DIGIT_ENCODINGS[check]
+    };
+
+    let mut modules = Vec::new();
+    append_pattern(&mut modules, &upc_ean_extension::START_PATTERN, true);
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            append_pattern(&mut modules, &upc_ean_extension::SEPARATOR_PATTERN, false);
+        }
+        let parity = (parities >> (values.len() - 1 - i)) & 1;
+        append_pattern(
+            &mut modules,
+            &upc_ean_shared::L_AND_G_PATTERNS[value + 10 * parity],
+            false,
+        );
+    }
+    Ok((modules, digits.to_owned()))
+}
+
+fn append_pattern(modules: &mut Vec<bool>, pattern: &[u32], start_color: bool) {
+    let mut color = start_color;
+    for &width in pattern {
+        modules.extend(std::iter::repeat_n(color, width as usize));
+        color = !color;
+    }
+}
+
+/// Encodes the modules of a format printing its contents centered below the bars
+fn encode_modules(
+    contents: &str,
+    format: &BarcodeFormat,
+    hints: &EncodeHints,
+) -> Result<Vec<bool>> {
+    match format {
+        BarcodeFormat::CODABAR => CodaBarWriter.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::CODE_11 => Code11Writer.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::CODE_32 => Code32Writer.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::CODE_39 => Code39Writer.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::CODE_93 => Code93Writer.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::CODE_128 => Code128Writer.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::IATA_2_OF_5 => IATATwoOfFiveWriter.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::INDUSTRIAL_2_OF_5 => {
+            IndustrialTwoOfFiveWriter.encode_oned_with_hints(contents, hints)
+        }
+        BarcodeFormat::ITF => ITFWriter.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::MATRIX_2_OF_5 => {
+            MatrixTwoOfFiveWriter.encode_oned_with_hints(contents, hints)
+        }
+        BarcodeFormat::MSI => MSIWriter.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::PHARMACODE => PharmacodeWriter.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::PZN => PZNWriter.encode_oned_with_hints(contents, hints),
+        BarcodeFormat::TELEPEN => TelepenWriter.encode_oned_with_hints(contents, hints),
+        _ => Err(Exceptions::illegal_argument_with(format!(
+            "No human readable layout for format {format:?}"
+        ))),
+    }
+}
+
+/// The text printed below the bars of formats other than EAN and UPC
+fn display_text(contents: &str, format: &BarcodeFormat) -> String {
+    let text = match format {
+        // Code 39 is printed with its start and stop characters
+        BarcodeFormat::CODE_39 => format!("*{contents}*"),
+        // FNC1 to FNC4 are not printed
+        BarcodeFormat::CODE_128 => contents
+            .chars()
+            .filter(|c| !('\u{00f1}'..='\u{00f4}').contains(c))
+            .collect(),
+        _ => contents.to_owned(),
+    };
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BarcodeFormat;
+
+    use super::{HriLayout, HriOptions, HriText, encode_add_on};
+
+    fn lay_out(contents: &str, format: BarcodeFormat) -> HriLayout {
+        HriLayout::new(contents, &format, &HriOptions::default()).unwrap()
+    }
+
+    fn texts(layout: &HriLayout) -> Vec<(&str, f32, f32)> {
+        layout
+            .texts
+            .iter()
+            .map(|text| (text.text.as_str(), text.left, text.width))
+            .collect()
+    }
+
+    fn modules(modules: &[bool]) -> String {
+        modules
+            .iter()
+            .map(|dark| if *dark { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn ean_13() {
+        let layout = lay_out("123456789012", BarcodeFormat::EAN_13);
+        assert_eq!(layout.width, 113);
+        assert_eq!(layout.height, 59.0);
+        // The leading digit in the quiet zone, the others between the guard bars
+        assert_eq!(
+            texts(&layout),
+            [
+                ("1", 3.0, 7.0),
+                ("234567", 14.0, 42.0),
+                ("890128", 61.0, 42.0)
+            ]
+        );
+        // Guard bars extend halfway into the text, the others stop above it
+        let bottoms = |x| layout.bars.iter().find(|bar| bar.x == x).unwrap().bottom;
+        assert_eq!(bottoms(11), 55.0);
+        assert_eq!(bottoms(13), 55.0);
+        assert_eq!(bottoms(57), 55.0);
+        assert_eq!(bottoms(105), 55.0);
+        assert_eq!(bottoms(16), 50.0);
+    }
+
+    #[test]
+    fn upc_a_and_upc_e() {
+        let layout = lay_out("01234567890", BarcodeFormat::UPC_A);
+        assert_eq!(layout.width, 113);
+        assert_eq!(
+            texts(&layout),
+            [
+                ("0", 1.0, 7.0),
+                ("12345", 19.0, 35.0),
+                ("67890", 59.0, 35.0),
+                ("5", 105.0, 7.0)
+            ]
+        );
+        // The outer digits are smaller, and share the baseline of the others
+        assert_eq!(layout.texts[0].size, 6.0);
+        assert_eq!(layout.texts[0].top, 53.0);
+        // The bars of the first digit extend along with the guard bars
+        for bar in &layout.bars[..4] {
+            assert_eq!((bar.x < 19, bar.bottom), (true, 55.0));
+        }
+        assert_eq!(
+            (layout.bars[4].x >= 19, layout.bars[4].bottom),
+            (true, 50.0)
+        );
+
+        let layout = lay_out("0123456", BarcodeFormat::UPC_E);
+        assert_eq!(layout.width, 67);
+        assert_eq!(
+            texts(&layout),
+            [("0", 1.0, 7.0), ("123456", 12.0, 42.0), ("5", 61.0, 7.0)]
+        );
+    }
+
+    #[test]
+    fn ean_8() {
+        let layout = lay_out("1234567", BarcodeFormat::EAN_8);
+        assert_eq!(layout.width, 81);
+        assert_eq!(texts(&layout), [("1234", 10.0, 28.0), ("5670", 43.0, 28.0)]);
+        assert!(
+            HriLayout::new("1234567+12", &BarcodeFormat::EAN_8, &HriOptions::default()).is_err()
+        );
+    }
+
+    #[test]
+    fn add_on() {
+        // As produced by other encoders
+        let (two, _) = encode_add_on("12").unwrap();
+        assert_eq!(modules(&two), "10110011001010010011");
+        let (five, _) = encode_add_on("12345").unwrap();
+        assert_eq!(
+            modules(&five),
+            "10110110011010010011010100001010100011010110001"
+        );
+        assert!(encode_add_on("123").is_err());
+
+        let layout = lay_out("123456789012+12", BarcodeFormat::EAN_13);
+        // 11 + 95 + 7 + 20 + 5
+        assert_eq!(layout.width, 138);
+        assert_eq!(
+            layout.texts.last(),
+            Some(&HriText {
+                text: "12".to_owned(),
+                left: 117.0,
+                width: 16.0,
+                top: 0.0,
+                size: 8.0,
+                spread: true,
+            })
+        );
+        // The add-on bars start below its digits
+        let add_on_bar = layout.bars.iter().find(|bar| bar.x == 113).unwrap();
+        assert_eq!((add_on_bar.top, add_on_bar.bottom), (9.0, 55.0));
+    }
+
+    #[test]
+    fn centered_text() {
+        let layout = lay_out("CODE39", BarcodeFormat::CODE_39);
+        assert_eq!(layout.texts.len(), 1);
+        assert_eq!(layout.texts[0].text, "*CODE39*");
+        assert_eq!(layout.texts[0].left, 10.0);
+        assert_eq!(layout.texts[0].width, (layout.width - 20) as f32);
+        assert!(!layout.texts[0].spread);
+        assert!(layout.bars.iter().all(|bar| bar.bottom == 50.0));
+
+        let layout = lay_out("\u{00f1}0112345", BarcodeFormat::CODE_128);
+        assert_eq!(layout.texts[0].text, "0112345");
+
+        assert!(HriLayout::new("1234", &BarcodeFormat::QR_CODE, &HriOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+//! Human readable interpretation (HRI) of one dimensional barcodes.
+//!
+//! The writers render only the bars of a symbol. [`HriLayout`] places the bars together with
+//! the text printed along them, following the rules of each symbology: the digits of EAN and
+//! UPC symbols sit between extended guard bars, the leading EAN-13 digit and the outer UPC
+//! digits are printed in the quiet zones, and the digits of a 2 or 5 digit add-on are
+//! printed above its bars. Other formats get their contents centered below the bars.
+//!
+//! A layout is measured in modules and can be rendered to an image with
+//! [`HriLayout::render_image`], with the `image` feature, or to an SVG document with
+//! [`HriLayout::render_svg`], with the `svg_write` feature.
+
+mod layout;
+pub use layout::*;
+
+#[cfg(feature = "image")]
+mod bitmap_font;
+
+#[cfg(feature = "image")]
+mod raster;
+#[cfg(feature = "image")]
+pub use raster::*;
+
+#[cfg(feature = "svg_write")]
+mod svg;
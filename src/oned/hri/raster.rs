@@ -0,0 +1,168 @@
+use ab_glyph::{FontArc, PxScale};
+use image::{GrayImage, Luma};
+use imageproc::drawing::{draw_text_mut, text_size};
+
+use crate::{Exceptions, common::Result};
+
+use super::{HriLayout, HriOptions, HriText, bitmap_font};
+
+const DARK: Luma<u8> = Luma([0]);
+const LIGHT: Luma<u8> = Luma([u8::MAX]);
+
+/// Font of the text of images rendered by [`HriLayout::render_image`]
+#[derive(Clone, Default)]
+pub enum HriFont {
+    /// A built in 5 by 7 pixel font, scaled to the font size
+    #[default]
+    Bitmap,
+    /// A TrueType or OpenType font
+    Outline(FontArc),
+}
+
+impl HriFont {
+    /// Loads a TrueType or OpenType font from the contents of its file
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        FontArc::try_from_vec(data)
+            .map(Self::Outline)
+            .map_err(|e| Exceptions::illegal_argument_with(format!("Invalid font: {e}")))
+    }
+
+    /// Width of `text`, in pixels
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        match self {
+            Self::Bitmap => {
+                let pixel = size / bitmap_font::GLYPH_HEIGHT as f32;
+                let count = text.chars().count() as f32;
+                (count * (bitmap_font::GLYPH_WIDTH + 1) as f32 - 1.0).max(0.0) * pixel
+            }
+            Self::Outline(font) => text_size(PxScale::from(size), font, text).0 as f32,
+        }
+    }
+
+    /// Draws `text` with its top left corner at `x`, `y`
+    fn draw(&self, image: &mut GrayImage, text: &str, x: f32, y: f32, size: f32) {
+        match self {
+            Self::Bitmap => {
+                let pixel = size / bitmap_font::GLYPH_HEIGHT as f32;
+                let advance = (bitmap_font::GLYPH_WIDTH + 1) as f32 * pixel;
+                for (i, c) in text.chars().enumerate() {
+                    let left = x + i as f32 * advance;
+                    let right = left + bitmap_font::GLYPH_WIDTH as f32 * pixel;
+                    for py in
+                        y.round().max(0.0) as u32..((y + size).round() as u32).min(image.height())
+                    {
+                        for px in
+                            left.round().max(0.0) as u32..(right.round() as u32).min(image.width())
+                        {
+                            let glyph_x = ((px as f32 + 0.5 - left) / pixel) as u32;
+                            let glyph_y = ((py as f32 + 0.5 - y) / pixel) as u32;
+                            if bitmap_font::is_set(c, glyph_x, glyph_y) {
+                                image.put_pixel(px, py, DARK);
+                            }
+                        }
+                    }
+                }
+            }
+            Self::Outline(font) => draw_text_mut(
+                image,
+                DARK,
+                x.round() as i32,
+                y.round() as i32,
+                PxScale::from(size),
+                font,
+                text,
+            ),
+        }
+    }
+}
+
+impl HriLayout {
+    /// Renders the layout to an image, black on white, each module `options.module_size`
+    /// pixels wide and the text drawn with `options.font`
+    pub fn render_image(&self, options: &HriOptions) -> GrayImage {
+        let scale = options.module_size as f32;
+        let mut image = GrayImage::from_pixel(
+            self.width * options.module_size,
+            (self.height * scale).ceil() as u32,
+            LIGHT,
+        );
+
+        for bar in &self.bars {
+            let top = (bar.top * scale).round() as u32;
+            let bottom = ((bar.bottom * scale).round() as u32).min(image.height());
+            for y in top..bottom {
+                for x in bar.x * options.module_size..(bar.x + bar.width) * options.module_size {
+                    image.put_pixel(x, y, DARK);
+                }
+            }
+        }
+
+        for text in &self.texts {
+            draw_text(&mut image, text, scale, &options.font);
+        }
+        image
+    }
+}
+
+fn draw_text(image: &mut GrayImage, text: &HriText, scale: f32, font: &HriFont) {
+    let size = text.size * scale;
+    let top = text.top * scale;
+    let mut draw_centered = |s: &str, center: f32| {
+        let left = center - font.text_width(s, size) / 2.0;
+        font.draw(image, s, left, top, size);
+    };
+
+    if text.spread {
+        let cell = text.width / text.text.chars().count() as f32;
+        for (i, c) in text.text.chars().enumerate() {
+            let center = text.left + (i as f32 + 0.5) * cell;
+            draw_centered(c.encode_utf8(&mut [0; 4]), center * scale);
+        }
+    } else {
+        draw_centered(&text.text, (text.left + text.width / 2.0) * scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::GrayImage;
+
+    use crate::{
+        BarcodeFormat,
+        oned::hri::{HriLayout, HriOptions},
+    };
+
+    fn dark_in(image: &GrayImage, xs: std::ops::Range<u32>, ys: std::ops::Range<u32>) -> usize {
+        xs.flat_map(|x| ys.clone().map(move |y| (x, y)))
+            .filter(|&(x, y)| image.get_pixel(x, y).0[0] == 0)
+            .count()
+    }
+
+    #[test]
+    fn render_ean_13() {
+        let options = HriOptions::default();
+        let layout = HriLayout::new("5901234123457", &BarcodeFormat::EAN_13, &options).unwrap();
+        let image = layout.render_image(&options);
+        assert_eq!(image.dimensions(), (226, 118));
+
+        // The start guard bars extend into the text, which is drawn between them
+        assert_eq!(dark_in(&image, 22..24, 0..110), 220);
+        assert_eq!(dark_in(&image, 22..24, 110..118), 0);
+        // The leading digit is drawn in the quiet zone
+        assert!(dark_in(&image, 6..20, 102..118) > 0);
+        // Text only below the bars
+        assert!(dark_in(&image, 28..90, 102..118) > 0);
+        assert_eq!(dark_in(&image, 28..90, 100..102), 0);
+    }
+
+    #[test]
+    fn render_add_on() {
+        let options = HriOptions::default();
+        let layout = HriLayout::new("5901234123457+12", &BarcodeFormat::EAN_13, &options).unwrap();
+        let image = layout.render_image(&options);
+        // The add-on digits are drawn above its bars
+        assert!(dark_in(&image, 234..266, 0..16) > 0);
+        assert_eq!(dark_in(&image, 226..276, 16..18), 0);
+        assert!(dark_in(&image, 226..276, 18..110) > 0);
+    }
+}
@@ -0,0 +1,81 @@
+use svg::{
+    Document,
+    node::element::{Rectangle, Text},
+};
+
+use super::{HriLayout, HriOptions};
+
+impl HriLayout {
+    /// Renders the layout to an SVG document, each module `options.module_size` user units
+    /// wide and the text set in `options.font_family`
+    pub fn render_svg(&self, options: &HriOptions) -> Document {
+        let scale = options.module_size as f32;
+        let mut document = Document::new().set(
+            "viewBox",
+            (0, 0, self.width as f32 * scale, self.height * scale),
+        );
+
+        for bar in &self.bars {
+            document = document.add(
+                Rectangle::new()
+                    .set("x", bar.x as f32 * scale)
+                    .set("y", bar.top * scale)
+                    .set("width", bar.width as f32 * scale)
+                    .set("height", (bar.bottom - bar.top) * scale),
+            );
+        }
+
+        for text in &self.texts {
+            let mut runs = Vec::new();
+            if text.spread {
+                let cell = text.width / text.text.chars().count() as f32;
+                for (i, c) in text.text.chars().enumerate() {
+                    runs.push((c.to_string(), text.left + (i as f32 + 0.5) * cell));
+                }
+            } else {
+                runs.push((text.text.clone(), text.left + text.width / 2.0));
+            }
+            for (run, center) in runs {
+                document = document.add(
+                    Text::new(run)
+                        .set("x", center * scale)
+                        .set("y", (text.top + text.size) * scale)
+                        .set("font-family", options.font_family.as_str())
+                        .set("font-size", text.size * scale)
+                        .set("text-anchor", "middle"),
+                );
+            }
+        }
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BarcodeFormat,
+        oned::hri::{HriLayout, HriOptions},
+    };
+
+    #[test]
+    fn render_upc_a() {
+        let options = HriOptions {
+            module_size: 1,
+            font_family: "Helvetica".to_owned(),
+            ..HriOptions::default()
+        };
+        let layout = HriLayout::new("01234567890", &BarcodeFormat::UPC_A, &options).unwrap();
+        let svg = layout.render_svg(&options).to_string();
+
+        assert!(svg.contains(r#"viewBox="0 0 113 59""#));
+        // The first guard bar, extended into the text
+        assert!(svg.contains(r#"<rect height="55" width="1" x="9" y="0"/>"#));
+        // The smaller number system digit in the quiet zone, and the digits each centered
+        // over their 7 modules
+        assert!(svg.contains(
+            r#"<text font-family="Helvetica" font-size="6" text-anchor="middle" x="4.5" y="59">"#
+        ));
+        assert!(svg.contains(r#"font-size="8" text-anchor="middle" x="22.5" y="59">"#));
+        assert_eq!(svg.matches("<text").count(), 12);
+    }
+}
@@ -24,7 +24,7 @@ use crate::{
     point,
 };
 
-use super::{OneDReader, one_d_reader};
+use super::{OneDReader, one_d_reader, upcean_common};
 
 const MAX_AVG_VARIANCE: f32 = 0.38;
 const MAX_INDIVIDUAL_VARIANCE: f32 = 0.5;
@@ -85,14 +85,15 @@ const PATTERNS: [[u32; 5]; 20] = [
  * required checksum function.</p>
  *
  * <p>The checksum is optional and is not applied by this Reader. The consumer of the decoded
- * value will have to apply a checksum if required.</p>
+ * value will have to apply a checksum if required. Only 14 digit results passing the GTIN
+ * checksum, as ITF-14 symbols do, carry their GTIN in the GTIN metadata.</p>
  *
  * <p><a href="http://en.wikipedia.org/wiki/Interleaved_2_of_5">http://en.wikipedia.org/wiki/Interleaved_2_of_5</a>
  * is a great reference for Interleaved 2 of 5 information.</p>
  *
  * @author kevin.osullivan@sita.aero, SITA Lab.
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct ITFReader {
     // Stores the actual narrow line width of the image being decoded.
     narrowLineWidth: i32,
@@ -162,6 +163,14 @@ impl OneDReader for ITFReader {
             RXingResultMetadataValue::SymbologyIdentifier("]I0".to_owned()),
         );
 
+        // ITF-14 carries a GTIN-14, whose check digit tells it apart from other 14 digit data
+        if length == 14 && upcean_common::checkStandardUPCEANChecksum(&resultString)? {
+            resultObject.putMetadata(
+                RXingResultMetadataType::GTIN,
+                RXingResultMetadataValue::Gtin(resultString),
+            );
+        }
+
         Ok(resultObject)
     }
 }
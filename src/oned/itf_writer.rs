@@ -14,22 +14,186 @@
  * limitations under the License.
  */
 
-use rxing_one_d_proc_derive::OneDWriter;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use crate::common::Result;
-use crate::{BarcodeFormat, Exceptions};
+use crate::common::{BitMatrix, Result};
+use crate::{BarcodeFormat, EncodeHints, Exceptions, Writer};
 
-use super::OneDimensionalCodeWriter;
+use super::{OneDimensionalCodeWriter, upcean_common};
+
+/// Bearer bars drawn around an ITF barcode. They keep a scan line that leaves the symbol
+/// through its top or bottom edge from being read as a shorter barcode.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ITFBearerBars {
+    /// No bearer bars
+    None,
+    /// Bars along the top and bottom of the symbol and its quiet zones
+    TopAndBottom,
+    /// A frame enclosing the symbol and its quiet zones
+    Frame,
+}
+
+/// Thickness of bearer bars in modules, the nominal 4.8 rounded up
+const BEARER_WIDTH: u32 = 5;
+
+/// Smallest quiet zone of ITF-14 on either side, in modules
+const ITF_14_QUIET_ZONE: u32 = 10;
 
 /**
  * This object renders a ITF code as a {@link BitMatrix}.
  *
+ * With [`EncodeHints::Itf14`] it renders ITF-14 instead: the contents are checked to be a
+ * GTIN-14, the quiet zones widened to ten modules and the symbol framed by bearer bars, unless
+ * [`EncodeHints::ItfBearerBars`] asks for other ones.
+ *
  * @author erik.barbara@gmail.com (Erik Barbara)
  */
-#[derive(OneDWriter, Default)]
+#[derive(Default)]
 pub struct ITFWriter;
 
+impl Writer for ITFWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        if contents.is_empty() {
+            return Err(Exceptions::illegal_argument_with("Found empty contents"));
+        }
+        if width < 0 || height < 0 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Negative size is not allowed. Input: {width}x{height}"
+            )));
+        }
+        if format != &BarcodeFormat::ITF {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Can only encode ITF, but got {format:?}"
+            )));
+        }
+
+        let itf_14 = matches!(hints.Itf14, Some(true));
+        let mut sides_margin = self.getDefaultMargin();
+        if let Some(margin) = &hints.Margin {
+            sides_margin = margin.parse::<u32>().map_err(|_| {
+                Exceptions::illegal_argument_with(format!("Invalid margin value: '{margin}'"))
+            })?;
+        }
+        if itf_14 {
+            sides_margin = sides_margin.max(2 * ITF_14_QUIET_ZONE);
+        }
+
+        let code = self.encode_oned_with_hints(contents, hints)?;
+
+        let default_bearer_bars = if itf_14 {
+            ITFBearerBars::Frame
+        } else {
+            ITFBearerBars::None
+        };
+        match hints.ItfBearerBars.unwrap_or(default_bearer_bars) {
+            ITFBearerBars::None => Self::renderRXingResult(&code, width, height, sides_margin),
+            bearer_bars => {
+                Self::render_with_bearer_bars(&code, width, height, sides_margin, bearer_bars)
+            }
+        }
+    }
+}
+
+impl ITFWriter {
+    /// Renders `code` between bearer bars. The quiet zones lie inside them, so the margin is
+    /// part of the symbol rather than left around it.
+    fn render_with_bearer_bars(
+        code: &[bool],
+        width: i32,
+        height: i32,
+        sides_margin: u32,
+        bearer_bars: ITFBearerBars,
+    ) -> Result<BitMatrix> {
+        let frame_width = if bearer_bars == ITFBearerBars::Frame {
+            2 * BEARER_WIDTH
+        } else {
+            0
+        };
+        let full_width = code.len() as u32 + sides_margin + frame_width;
+        let output_width = (width as u32).max(full_width);
+        let multiple = output_width / full_width;
+        let bearer = BEARER_WIDTH * multiple;
+        // leave the bars at least as tall as the bearers
+        let output_height = (height as u32).max(3 * bearer);
+        let left = (output_width - full_width * multiple) / 2;
+
+        let mut output = BitMatrix::new(output_width, output_height)?;
+        output.setRegion(left, 0, full_width * multiple, bearer)?;
+        output.setRegion(left, output_height - bearer, full_width * multiple, bearer)?;
+        if bearer_bars == ITFBearerBars::Frame {
+            output.setRegion(left, 0, bearer, output_height)?;
+            output.setRegion(
+                left + (full_width - BEARER_WIDTH) * multiple,
+                0,
+                bearer,
+                output_height,
+            )?;
+        }
+
+        let code_left = left + (full_width - code.len() as u32) / 2 * multiple;
+        for (x, _) in code.iter().enumerate().filter(|(_, black)| **black) {
+            output.setRegion(
+                code_left + x as u32 * multiple,
+                bearer,
+                multiple,
+                output_height - 2 * bearer,
+            )?;
+        }
+        Ok(output)
+    }
+
+    /// The GTIN-14 of ITF-14 `contents`, its check digit appended if only 13 digits are given
+    fn gtin_14(contents: &str) -> Result<String> {
+        if !contents.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Exceptions::illegal_argument_with(
+                "Input should only contain digits 0-9",
+            ));
+        }
+        match contents.len() {
+            13 => {
+                let digits: Vec<char> = contents.chars().collect();
+                let check = upcean_common::getStandardUPCEANChecksum(&digits)?;
+                Ok(format!("{contents}{check}"))
+            }
+            14 if upcean_common::checkStandardUPCEANChecksum(contents)? => Ok(contents.to_owned()),
+            14 => Err(Exceptions::illegal_argument_with(
+                "Invalid GTIN-14 check digit",
+            )),
+            length => Err(Exceptions::illegal_argument_with(format!(
+                "ITF-14 contents should be 13 or 14 digits long, but got {length}"
+            ))),
+        }
+    }
+}
+
 impl OneDimensionalCodeWriter for ITFWriter {
+    fn encode_oned_with_hints(&self, contents: &str, hints: &EncodeHints) -> Result<Vec<bool>> {
+        if matches!(hints.Itf14, Some(true)) {
+            self.encode_oned(&Self::gtin_14(contents)?)
+        } else {
+            self.encode_oned(contents)
+        }
+    }
+
     fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
         let length = contents.chars().count();
         if length % 2 != 0 {
@@ -95,9 +259,14 @@ const PATTERNS: [[usize; 5]; 10] = [
  */
 #[cfg(test)]
 mod ITFWriterTestCase {
-    use crate::{BarcodeFormat, Writer, common::bit_matrix_test_case};
+    use crate::{
+        BarcodeFormat, DecodeHints, EncodeHintValue, EncodeHints, RXingResultMetadataType,
+        RXingResultMetadataValue, Writer,
+        common::{BitMatrix, bit_matrix_test_case},
+        oned::{ITFReader, OneDReader},
+    };
 
-    use super::ITFWriter;
+    use super::{ITFBearerBars, ITFWriter};
 
     #[test]
     fn testEncode() {
@@ -123,4 +292,117 @@ mod ITFWriterTestCase {
             .encode("00123456789abc", &BarcodeFormat::ITF, 0, 0)
             .expect("should fail");
     }
+
+    fn itf_14(
+        contents: &str,
+        bearer_bars: Option<ITFBearerBars>,
+    ) -> crate::common::Result<BitMatrix> {
+        let mut hints = EncodeHints::default().with(EncodeHintValue::Itf14(true));
+        if let Some(bearer_bars) = bearer_bars {
+            hints = hints.with(EncodeHintValue::ItfBearerBars(bearer_bars));
+        }
+        ITFWriter.encode_with_hints(contents, &BarcodeFormat::ITF, 0, 0, &hints)
+    }
+
+    fn row_to_string(matrix: &BitMatrix, y: u32) -> String {
+        (0..matrix.getWidth())
+            .map(|x| if matrix.get(x, y) { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn testEncodeITF14() {
+        // the check digit is appended to 13 digits and verified on 14
+        let matrix = itf_14("0012345678901", Some(ITFBearerBars::None)).unwrap();
+        let code = "1010101011100011100010111010001010111000111011101000101000111010001110\
+00101010001010111000111010111010001110001011101000101011100011101";
+        let quiet_zone = "0".repeat(10);
+        assert_eq!(
+            bit_matrix_test_case::matrix_to_string(&matrix),
+            format!("{quiet_zone}{code}{quiet_zone}")
+        );
+        assert_eq!(
+            itf_14("00123456789012", Some(ITFBearerBars::None)).unwrap(),
+            matrix
+        );
+        assert!(itf_14("00123456789013", None).is_err());
+        assert!(itf_14("001234567890", None).is_err());
+        assert!(itf_14("0012345678901A", None).is_err());
+    }
+
+    #[test]
+    fn testEncodeBearerBars() {
+        let frame = itf_14("0012345678901", None).unwrap();
+        // 135 modules of symbol, 10 of quiet zone on each side and a 5 module frame
+        assert_eq!(frame.getWidth(), 135 + 2 * 10 + 2 * 5);
+        assert_eq!(frame.getHeight(), 15);
+        assert_eq!(row_to_string(&frame, 0), "1".repeat(165));
+        assert_eq!(row_to_string(&frame, 14), "1".repeat(165));
+        let middle = row_to_string(&frame, 7);
+        assert!(middle.starts_with(&format!("11111{}1", "0".repeat(10))));
+        assert!(middle.ends_with(&format!("1{}11111", "0".repeat(10))));
+
+        let bars = itf_14("0012345678901", Some(ITFBearerBars::TopAndBottom)).unwrap();
+        assert_eq!(bars.getWidth(), 155);
+        assert_eq!(row_to_string(&bars, 0), "1".repeat(155));
+        assert!(row_to_string(&bars, 7).starts_with(&format!("{}1", "0".repeat(10))));
+
+        // plain ITF may ask for bearer bars too, and keeps its narrower margin
+        let hints =
+            EncodeHints::default().with(EncodeHintValue::ItfBearerBars(ITFBearerBars::Frame));
+        let plain = ITFWriter
+            .encode_with_hints("0012", &BarcodeFormat::ITF, 0, 0, &hints)
+            .unwrap();
+        assert_eq!(plain.getWidth(), 9 + 9 * 4 + 10 + 2 * 5);
+    }
+
+    #[test]
+    fn testBearerBarsRead() {
+        for bearer_bars in [ITFBearerBars::Frame, ITFBearerBars::TopAndBottom] {
+            let matrix = itf_14("0012345678901", Some(bearer_bars)).unwrap();
+            let (mut read, mut bearer_rows) = (0, 0);
+            for y in 0..matrix.getHeight() {
+                match ITFReader::default().decode_row(y, &matrix.getRow(y), &DecodeHints::default())
+                {
+                    Ok(result) => {
+                        assert_eq!(result.getText(), "00123456789012");
+                        assert_eq!(
+                            result
+                                .getRXingResultMetadata()
+                                .get(&RXingResultMetadataType::GTIN),
+                            Some(&RXingResultMetadataValue::Gtin("00123456789012".to_owned()))
+                        );
+                        read += 1;
+                    }
+                    Err(_) => bearer_rows += 1,
+                }
+            }
+            // every row through the bars reads, none through the bearers
+            assert_eq!((read, bearer_rows), (5, 10));
+        }
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn testBearerBarsImage() {
+        use crate::{
+            BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, MultiFormatReader, Reader,
+            common::HybridBinarizer,
+        };
+
+        let hints = EncodeHints::default().with(EncodeHintValue::Itf14(true));
+        let matrix = ITFWriter
+            .encode_with_hints("1540014128876", &BarcodeFormat::ITF, 400, 120, &hints)
+            .unwrap();
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        let hints = DecodeHints::default().with(DecodeHintValue::TryHarder(true));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut bitmap, &hints)
+            .unwrap();
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::ITF);
+        assert_eq!(result.getText(), "15400141288763");
+    }
 }
@@ -1,7 +1,8 @@
 #[cfg(feature = "decoders")]
 mod one_d_reader;
 
-#[cfg(feature = "decoders")] // there is no rss writer
+// the composite writer needs the DataBar width tables
+#[cfg(any(feature = "decoders", feature = "encoders"))]
 pub mod rss;
 
 #[cfg(feature = "decoders")]
@@ -25,6 +26,36 @@ mod multi_format_one_d_reader;
 #[cfg(feature = "decoders")]
 pub use multi_format_one_d_reader::*;
 
+#[cfg(feature = "decoders")]
+mod code_39_pharma_reader;
+#[cfg(feature = "decoders")]
+pub use code_39_pharma_reader::*;
+
+#[cfg(feature = "decoders")]
+mod code_11_reader;
+#[cfg(feature = "decoders")]
+pub use code_11_reader::*;
+
+#[cfg(feature = "decoders")]
+mod msi_reader;
+#[cfg(feature = "decoders")]
+pub use msi_reader::*;
+
+#[cfg(feature = "decoders")]
+mod pharmacode_reader;
+#[cfg(feature = "decoders")]
+pub use pharmacode_reader::*;
+
+#[cfg(feature = "decoders")]
+mod pharmacode_two_track_reader;
+#[cfg(feature = "decoders")]
+pub use pharmacode_two_track_reader::*;
+
+#[cfg(feature = "decoders")]
+mod two_of_five_reader;
+#[cfg(feature = "decoders")]
+pub use two_of_five_reader::*;
+
 #[cfg(feature = "decoders")]
 mod code_93_reader;
 #[cfg(feature = "decoders")]
@@ -104,6 +135,41 @@ mod code_39_writer;
 #[cfg(feature = "encoders")]
 pub use code_39_writer::*;
 
+#[cfg(feature = "encoders")]
+mod code_39_pharma_writer;
+#[cfg(feature = "encoders")]
+pub use code_39_pharma_writer::*;
+
+#[cfg(feature = "encoders")]
+mod code_11_writer;
+#[cfg(feature = "encoders")]
+pub use code_11_writer::*;
+
+#[cfg(feature = "encoders")]
+mod msi_writer;
+#[cfg(feature = "encoders")]
+pub use msi_writer::*;
+
+#[cfg(feature = "encoders")]
+mod pharmacode_writer;
+#[cfg(feature = "encoders")]
+pub use pharmacode_writer::*;
+
+#[cfg(feature = "encoders")]
+mod pharmacode_two_track_writer;
+#[cfg(feature = "encoders")]
+pub use pharmacode_two_track_writer::*;
+
+#[cfg(feature = "encoders")]
+mod dx_film_edge_writer;
+#[cfg(feature = "encoders")]
+pub use dx_film_edge_writer::*;
+
+#[cfg(feature = "encoders")]
+mod two_of_five_writer;
+#[cfg(feature = "encoders")]
+pub use two_of_five_writer::*;
+
 #[cfg(feature = "encoders")]
 mod code_93_writer;
 #[cfg(feature = "encoders")]
@@ -155,9 +221,27 @@ pub use upc_e_writer::*;
 
 mod telepen_common;
 
+mod code_39_pharma_common;
+
+mod code_11_common;
+
+mod msi_common;
+pub use msi_common::*;
+
+mod pharmacode_common;
+
+mod two_of_five_common;
+
 #[cfg(feature = "decoders")] // This is fine as CPP only reads and never writes
 pub mod cpp;
 
-mod oned_constants;
+pub mod postal;
+
+pub mod stacked;
+
+#[cfg(feature = "encoders")]
+pub mod hri;
+
+pub(crate) mod oned_constants;
 
 pub(crate) mod upcean_common;
@@ -0,0 +1,115 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Check digit schemes of MSI Plessey barcodes
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MSIChecksum {
+    /// No check digit
+    None,
+    /// A single modulo 10 (Luhn) check digit
+    #[default]
+    Mod10,
+    /// Two modulo 10 check digits, the second one also covering the first
+    Mod1010,
+    /// A modulo 11 check digit with the IBM weights 2 to 7, written as "10" when it is 10
+    Mod11,
+    /// A modulo 11 check digit followed by a modulo 10 one
+    Mod1110,
+}
+
+impl MSIChecksum {
+    /// The check digits to append to `digits`
+    pub fn check_digits(self, digits: &[u8]) -> Vec<u8> {
+        match self {
+            MSIChecksum::None => Vec::new(),
+            MSIChecksum::Mod10 => vec![mod_10(digits)],
+            MSIChecksum::Mod1010 => {
+                let first = mod_10(digits);
+                let second = mod_10(&[digits, &[first]].concat());
+                vec![first, second]
+            }
+            MSIChecksum::Mod11 => mod_11(digits),
+            MSIChecksum::Mod1110 => {
+                let mut check = mod_11(digits);
+                check.push(mod_10(&[digits, &check].concat()));
+                check
+            }
+        }
+    }
+
+    /// Number of leading data digits in `digits`, if the digits after them are valid check digits
+    pub fn data_length(self, digits: &[u8]) -> Option<usize> {
+        let candidates = match self {
+            MSIChecksum::None => 0..=0,
+            MSIChecksum::Mod10 => 1..=1,
+            MSIChecksum::Mod1010 => 2..=2,
+            MSIChecksum::Mod11 => 1..=2,
+            MSIChecksum::Mod1110 => 2..=3,
+        };
+        candidates
+            .filter(|&count| count < digits.len())
+            .map(|count| digits.len() - count)
+            .find(|&length| self.check_digits(&digits[..length]) == digits[length..])
+    }
+}
+
+/// Luhn check digit, doubling every other digit starting from the last one
+fn mod_10(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = d as u32;
+            if i % 2 == 0 {
+                2 * d / 10 + 2 * d % 10
+            } else {
+                d
+            }
+        })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Modulo 11 check digit with weights 2 to 7 from the last digit, as the digits of its value
+fn mod_11(digits: &[u8]) -> Vec<u8> {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| (i as u32 % 6 + 2) * d as u32)
+        .sum();
+    match (11 - sum % 11) % 11 {
+        10 => vec![1, 0],
+        check => vec![check as u8],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MSIChecksum;
+
+    #[test]
+    fn check_digits() {
+        let digits = [1, 2, 3, 4, 5, 6];
+        assert_eq!(MSIChecksum::Mod10.check_digits(&digits), [6]);
+        assert_eq!(MSIChecksum::Mod1010.check_digits(&digits), [6, 6]);
+        assert_eq!(MSIChecksum::Mod11.check_digits(&digits), [0]);
+        assert_eq!(MSIChecksum::Mod1110.check_digits(&digits), [0, 9]);
+        assert_eq!(MSIChecksum::Mod11.check_digits(&[7]), [8]);
+        // the modulo 11 check digit 10 takes two digits
+        assert_eq!(MSIChecksum::Mod11.check_digits(&[6]), [1, 0]);
+    }
+
+    #[test]
+    fn data_length() {
+        assert_eq!(
+            MSIChecksum::Mod10.data_length(&[1, 2, 3, 4, 5, 6, 6]),
+            Some(6)
+        );
+        assert_eq!(MSIChecksum::Mod10.data_length(&[1, 2, 3, 4, 5, 6, 7]), None);
+        assert_eq!(MSIChecksum::Mod11.data_length(&[6, 1, 0]), Some(1));
+        assert_eq!(MSIChecksum::None.data_length(&[4, 2]), Some(2));
+    }
+}
@@ -0,0 +1,176 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::common::{BitArray, Result};
+use crate::{BarcodeFormat, DecodeHints, Exceptions, RXingResult, point};
+
+use super::{OneDReader, record_all_runs};
+
+/**
+ * <p>Decodes MSI Plessey barcodes. The check digits are validated and removed from the result
+ * according to the [`DecodeHints::MsiChecksum`] hint, a single modulo 10 check digit by
+ * default.</p>
+ *
+ * <p>With so little structure to go on, MSI is only read when it is asked for in
+ * [`DecodeHints::PossibleFormats`].</p>
+ */
+#[derive(OneDReader, Clone, Default)]
+pub struct MSIReader;
+
+impl OneDReader for MSIReader {
+    fn decode_row(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let runs = record_all_runs(row);
+        let checksum = hints.MsiChecksum.unwrap_or_default();
+
+        let mut last_error = Exceptions::NOT_FOUND;
+        // black runs have odd indices
+        for start in (1..runs.len().saturating_sub(3)).step_by(2) {
+            let Some((digits, end)) = Self::read_digits(&runs, start) else {
+                continue;
+            };
+            let Some(length) = checksum.data_length(&digits) else {
+                last_error = Exceptions::CHECKSUM;
+                continue;
+            };
+            if length == 0 {
+                continue;
+            }
+
+            let text: String = digits[..length]
+                .iter()
+                .map(|&d| char::from(b'0' + d))
+                .collect();
+            let left = runs[..start].iter().sum::<u32>();
+            let right = left + runs[start..end].iter().sum::<u32>();
+            return Ok(RXingResult::new(
+                &text,
+                Vec::new(),
+                vec![
+                    point(left as f32, rowNumber as f32),
+                    point(right as f32, rowNumber as f32),
+                ],
+                BarcodeFormat::MSI,
+            ));
+        }
+
+        Err(last_error)
+    }
+}
+
+impl MSIReader {
+    /// Reads the digits between a start bit at `start` and the stop pattern, returning them
+    /// along with the index of the run following the stop pattern
+    fn read_digits(runs: &[u32], start: usize) -> Option<(Vec<u8>, usize)> {
+        // the start bit is a 1: a wide bar and a narrow space, three modules in all
+        let bit_width = runs[start] + runs[start + 1];
+        if runs[start] <= runs[start + 1] {
+            return None;
+        }
+        // quiet zone of at least four modules before the start bit, unless it is cut off
+        if start > 1 && 3 * runs[start - 1] < 4 * bit_width {
+            return None;
+        }
+        // every bit is a narrow and a wide element, twice as wide
+        let is_bit = |bar: u32, space: u32| {
+            let width = bar + space;
+            3 * width >= 2 * bit_width
+                && 2 * width <= 3 * bit_width
+                && 2 * bar.max(space) >= 3 * bar.min(space)
+        };
+
+        let mut bits = Vec::new();
+        let mut offset = start + 2;
+        loop {
+            let bar = *runs.get(offset)?;
+            match runs.get(offset + 1) {
+                Some(&space) if is_bit(bar, space) => bits.push(bar > space),
+                space => {
+                    // the stop pattern ends on a narrow bar, after a 0 bit, followed by a
+                    // quiet zone
+                    let is_quiet = space.is_none_or(|&space| 3 * space >= 4 * bit_width);
+                    if bits.pop() != Some(false) || 2 * bar > bit_width || !is_quiet {
+                        return None;
+                    }
+                    break;
+                }
+            }
+            offset += 2;
+        }
+
+        if bits.is_empty() || bits.len() % 4 != 0 {
+            return None;
+        }
+        let digits = bits
+            .chunks(4)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |digit, &bit| digit << 1 | u8::from(bit))
+            })
+            .collect::<Vec<u8>>();
+        if digits.iter().any(|&d| d > 9) {
+            return None;
+        }
+        Some((digits, offset + 1))
+    }
+}
+
+#[cfg(test)]
+mod MSIReaderTestCase {
+    use crate::{
+        DecodeHintValue, DecodeHints,
+        common::BitMatrix,
+        oned::{MSIChecksum, MSIReader, OneDReader},
+    };
+
+    #[test]
+    fn testDecode() {
+        doTest(
+            "12",
+            "00000000001101001001001101001001101001001101001101001000000000",
+            None,
+        );
+        doTest(
+            "12",
+            "00000000001101001001001101001001101001001000000000",
+            Some(MSIChecksum::None),
+        );
+        doTest(
+            "7",
+            "00000000001101001101101101101001001001001000000000",
+            Some(MSIChecksum::Mod11),
+        );
+    }
+
+    #[test]
+    fn testBadCheckDigit() {
+        // "12" followed by the check digit 4 instead of 5
+        let matrix = BitMatrix::parse_strings(
+            "000000000011010010010011010010011010010011010010010010000000000",
+            "1",
+            "0",
+        )
+        .expect("bitmatrix parse");
+        assert!(
+            MSIReader
+                .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+                .is_err()
+        );
+    }
+
+    fn doTest(expected: &str, encoded: &str, checksum: Option<MSIChecksum>) {
+        let mut hints = DecodeHints::default();
+        if let Some(checksum) = checksum {
+            hints = hints.with(DecodeHintValue::MsiChecksum(checksum));
+        }
+        let matrix = BitMatrix::parse_strings(encoded, "1", "0").expect("bitmatrix parse");
+        let result = MSIReader
+            .decode_row(0, &matrix.getRow(0), &hints)
+            .expect("decode row");
+        assert_eq!(expected, result.getText());
+    }
+}
@@ -0,0 +1,100 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::common::Result;
+use crate::{BarcodeFormat, EncodeHints};
+
+use super::OneDimensionalCodeWriter;
+
+/**
+ * This object renders an MSI Plessey barcode as a BitMatrix. The contents are digits; check
+ * digits are appended according to the [`EncodeHints::MsiChecksum`] hint, a single modulo 10
+ * check digit by default.
+ */
+#[derive(OneDWriter, Default)]
+pub struct MSIWriter;
+
+impl OneDimensionalCodeWriter for MSIWriter {
+    fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        self.encode_oned_with_hints(contents, &EncodeHints::default())
+    }
+
+    fn encode_oned_with_hints(&self, contents: &str, hints: &EncodeHints) -> Result<Vec<bool>> {
+        Self::checkNumeric(contents)?;
+        let mut digits: Vec<u8> = contents.bytes().map(|b| b - b'0').collect();
+        digits.extend(hints.MsiChecksum.unwrap_or_default().check_digits(&digits));
+
+        // start bit 1, four bits per digit, each bit a bar and a space of which the wider one
+        // is two modules, then the stop pattern
+        let mut result = vec![true, true, false];
+        for digit in digits {
+            for bit in (0..4).rev() {
+                if digit & (1 << bit) != 0 {
+                    result.extend([true, true, false]);
+                } else {
+                    result.extend([true, false, false]);
+                }
+            }
+        }
+        result.extend([true, false, false, true]);
+
+        Ok(result)
+    }
+
+    fn getSupportedWriteFormats(&self) -> Option<Vec<crate::BarcodeFormat>> {
+        Some(vec![BarcodeFormat::MSI])
+    }
+}
+
+#[cfg(test)]
+mod MSIWriterTestCase {
+    use crate::{
+        BarcodeFormat, EncodeHintValue, EncodeHints, Writer,
+        common::{BitMatrix, bit_matrix_test_case},
+        oned::MSIChecksum,
+    };
+
+    use super::MSIWriter;
+
+    #[test]
+    fn testEncode() {
+        doTest(
+            "12",
+            None,
+            concat!(
+                "00000",
+                "1101001001001101001001101001001101001101001",
+                "00000"
+            ),
+        );
+    }
+
+    #[test]
+    fn testChecksumHint() {
+        doTest(
+            "12",
+            Some(MSIChecksum::None),
+            concat!("00000", "1101001001001101001001101001001", "00000"),
+        );
+        doTest(
+            "7",
+            Some(MSIChecksum::Mod11),
+            concat!("00000", "1101001101101101101001001001001", "00000"),
+        );
+    }
+
+    #[test]
+    fn testNumericOnly() {
+        assert!(MSIWriter.encode("12A", &BarcodeFormat::MSI, 0, 0).is_err());
+    }
+
+    fn doTest(input: &str, checksum: Option<MSIChecksum>, expected: &str) {
+        let mut hints = EncodeHints::default();
+        if let Some(checksum) = checksum {
+            hints = hints.with(EncodeHintValue::MsiChecksum(checksum));
+        }
+        let result: BitMatrix = MSIWriter
+            .encode_with_hints(input, &BarcodeFormat::MSI, 0, 0, &hints)
+            .expect("must encode");
+        assert_eq!(expected, bit_matrix_test_case::matrix_to_string(&result));
+    }
+}
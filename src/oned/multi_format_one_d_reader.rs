@@ -15,15 +15,25 @@
  */
 
 use super::CodaBarReader;
+use super::Code11Reader;
+use super::Code32Reader;
 use super::Code39Reader;
 use super::Code93Reader;
 use super::Code128Reader;
+use super::IATATwoOfFiveReader;
 use super::ITFReader;
+use super::IndustrialTwoOfFiveReader;
+use super::MSIReader;
+use super::MatrixTwoOfFiveReader;
 use super::MultiFormatUPCEANReader;
 use super::OneDReader;
+use super::PZNReader;
+use super::PharmacodeReader;
 use super::TelepenReader;
+use super::relabel_code_39;
 use super::rss::RSS14Reader;
 use super::rss::expanded::RSSExpandedReader;
+use super::stacked::StackedReader;
 use crate::DecodeHints;
 use crate::Exceptions;
 use crate::common::Result;
@@ -33,7 +43,7 @@ use crate::{BarcodeFormat, Binarizer, RXingResult};
  * @author dswitkin@google.com (Daniel Switkin)
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MultiFormatOneDReader {
     internal_hints: DecodeHints,
     possible_formats: HashSet<BarcodeFormat>,
@@ -68,6 +78,17 @@ impl OneDReader for MultiFormatOneDReader {
                     return Ok(res);
                 }
             }
+            // Code 32 and PZN are plain Code 39 to anyone asking for other formats
+            if possible_formats.contains(&BarcodeFormat::CODE_32) {
+                if let Ok(res) = Code32Reader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::PZN) {
+                if let Ok(res) = PZNReader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
             if possible_formats.contains(&BarcodeFormat::CODE_39) {
                 if let Ok(res) = Code39Reader::with_use_check_digit(*use_code_39_check_digit)
                     .decode_row(row_number, row, hints)
@@ -110,6 +131,38 @@ impl OneDReader for MultiFormatOneDReader {
                     return Ok(res);
                 }
             }
+            // Code 11, Industrial, IATA and Matrix 2 of 5, MSI and Pharmacode are too easily read
+            // into other barcodes to be guessed
+            if possible_formats.contains(&BarcodeFormat::CODE_11) {
+                if let Ok(res) = Code11Reader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::INDUSTRIAL_2_OF_5) {
+                if let Ok(res) = IndustrialTwoOfFiveReader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::IATA_2_OF_5) {
+                if let Ok(res) = IATATwoOfFiveReader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::MATRIX_2_OF_5) {
+                if let Ok(res) = MatrixTwoOfFiveReader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::MSI) {
+                if let Ok(res) = MSIReader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::PHARMACODE) {
+                if let Ok(res) = PharmacodeReader.decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
         } else {
             if let Ok(res) =
                 MultiFormatUPCEANReader::new(internal_hints).decode_row(row_number, row, hints)
@@ -119,7 +172,7 @@ impl OneDReader for MultiFormatOneDReader {
             if let Ok(res) = Code39Reader::with_use_check_digit(*use_code_39_check_digit)
                 .decode_row(row_number, row, hints)
             {
-                return Ok(res);
+                return Ok(relabel_code_39(res));
             }
             if let Ok(res) = CodaBarReader::default().decode_row(row_number, row, hints) {
                 return Ok(res);
@@ -147,6 +200,13 @@ impl OneDReader for MultiFormatOneDReader {
         Err(Exceptions::NOT_FOUND)
     }
 }
+
+/// Whether a Code 128 result is a row of a Codablock F symbol, which starts in code set A
+/// and then gives the code set of the row
+fn is_codablock_f_row(result: &RXingResult) -> bool {
+    matches!(result.getRawBytes(), [103, 98..=100, ..])
+}
+
 impl MultiFormatOneDReader {
     pub fn new(hints: &DecodeHints) -> Self {
         let use_code_39_check_digit = matches!(hints.AssumeCode39CheckDigit, Some(true));
@@ -168,9 +228,58 @@ impl MultiFormatOneDReader {
 
 use crate::RXingResultMetadataType;
 use crate::RXingResultMetadataValue;
-use crate::Reader;
+use crate::{ImmutableReader, Reader};
 use std::collections::HashSet;
 
+impl MultiFormatOneDReader {
+    /// Replaces a Code 128 `result` that is a row of a Codablock F symbol by the whole symbol
+    /// when Codablock F may be read and the symbol can be assembled from `image`, otherwise
+    /// returns `result` unchanged.
+    fn with_codablock_f<B: Binarizer>(
+        &self,
+        image: &crate::BinaryBitmap<B>,
+        result: RXingResult,
+        hints: &DecodeHints,
+    ) -> RXingResult {
+        if (!self.possible_formats.is_empty()
+            && !self.possible_formats.contains(&BarcodeFormat::CODABLOCK_F))
+            || result.getBarcodeFormat() != &BarcodeFormat::CODE_128
+            || !is_codablock_f_row(&result)
+        {
+            return result;
+        }
+        StackedReader::decode_matrix(
+            image.get_black_matrix(),
+            &[BarcodeFormat::CODABLOCK_F],
+            hints,
+        )
+        .unwrap_or(result)
+    }
+
+    /// Adds the 2D component of a GS1 Composite symbol to `result` when the DecodeComposite
+    /// hint is set and one is found above it, otherwise returns `result` unchanged.
+    #[cfg(feature = "pdf417")]
+    fn with_composite<B: Binarizer>(
+        image: &crate::BinaryBitmap<B>,
+        result: RXingResult,
+        hints: &DecodeHints,
+    ) -> RXingResult {
+        if !matches!(hints.DecodeComposite, Some(true)) {
+            return result;
+        }
+        crate::pdf417::composite::decode_composite(image, &result).unwrap_or(result)
+    }
+
+    #[cfg(not(feature = "pdf417"))]
+    fn with_composite<B: Binarizer>(
+        _image: &crate::BinaryBitmap<B>,
+        result: RXingResult,
+        _hints: &DecodeHints,
+    ) -> RXingResult {
+        result
+    }
+}
+
 impl Reader for MultiFormatOneDReader {
     fn decode<B: Binarizer>(&mut self, image: &mut crate::BinaryBitmap<B>) -> Result<RXingResult> {
         self.decode_with_hints(image, &DecodeHints::default())
@@ -182,15 +291,17 @@ impl Reader for MultiFormatOneDReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        let first_try = self._do_decode(image, hints);
-        if first_try.is_ok() {
-            return first_try;
+        if let Ok(result) = self._do_decode(image, hints) {
+            let result = self.with_codablock_f(image, result, hints);
+            return Ok(Self::with_composite(image, result, hints));
         }
 
         let tryHarder = hints.TryHarder.unwrap_or(false);
         if tryHarder && image.is_rotate_supported() {
             let mut rotatedImage = image.rotate_counter_clockwise();
-            let mut result = self._do_decode(&mut rotatedImage, hints)?;
+            let result = self._do_decode(&mut rotatedImage, hints)?;
+            let result = self.with_codablock_f(&rotatedImage, result, hints);
+            let mut result = Self::with_composite(&rotatedImage, result, hints);
             // Record that we found it rotated 90 degrees CCW / 270 degrees CW
             let metadata = result.getRXingResultMetadata();
             let mut orientation = 270;
@@ -230,3 +341,13 @@ impl Reader for MultiFormatOneDReader {
         self.rss_expanded_reader.reset();
     }
 }
+
+impl ImmutableReader for MultiFormatOneDReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.clone().decode_with_hints(image, hints)
+    }
+}
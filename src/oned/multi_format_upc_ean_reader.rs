@@ -17,9 +17,9 @@
 use crate::DecodeHints;
 use crate::Exceptions;
 use crate::RXingResult;
-use crate::Reader;
 use crate::common::Result;
 use crate::{BarcodeFormat, Binarizer};
+use crate::{ImmutableReader, Reader};
 
 use super::EAN8Reader;
 use super::EAN13Reader;
@@ -35,6 +35,7 @@ use super::{OneDReader, UPCEANReader};
  *
  * @author Sean Owen
  */
+#[derive(Clone)]
 pub struct MultiFormatUPCEANReader {
     possible_formats: HashSet<BarcodeFormat>,
 }
@@ -234,3 +235,13 @@ impl Reader for MultiFormatUPCEANReader {
         }
     }
 }
+
+impl ImmutableReader for MultiFormatUPCEANReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.clone().decode_with_hints(image, hints)
+    }
+}
@@ -328,3 +328,24 @@ pub fn record_pattern_in_reverse<const N: usize>(
 
     Ok(())
 }
+
+/**
+ * Records the size of every run of white and black pixels in a row. The first count is always a
+ * run of white pixels, empty when the row starts on a black pixel, so black runs have odd indices.
+ *
+ * @param row row to count from
+ */
+pub fn record_all_runs(row: &BitArray) -> Vec<u32> {
+    let mut runs = vec![0];
+    let mut is_black = false;
+    for i in 0..row.get_size() {
+        if row.get(i) != is_black {
+            runs.push(0);
+            is_black = !is_black;
+        }
+        if let Some(run) = runs.last_mut() {
+            *run += 1;
+        }
+    }
+    runs
+}
@@ -50,6 +50,22 @@ pub mod code_39 {
     pub const ASTERISK_ENCODING: u32 = 0x094;
 }
 
+pub mod code_11 {
+    pub const ALPHABET_STRING: &str = "0123456789-";
+
+    /**
+     * These represent the encodings of characters, as patterns of wide and narrow bars.
+     * The 5 least-significant bits of each int correspond to the pattern of wide and narrow,
+     * with 1s representing "wide" and 0s representing narrow.
+     */
+    pub const CHARACTER_ENCODINGS: [u32; 11] = [
+        0x01, 0x11, 0x09, 0x18, 0x05, 0x14, 0x0C, 0x03, 0x12, 0x10, // 0-9
+        0x04, // -
+    ];
+
+    pub const START_STOP_ENCODING: u32 = 0x06;
+}
+
 pub mod code_93 {
     // Note that 'abcd' are dummy characters in place of control characters.
     pub const ALPHABET_STRING: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%abcd*";
@@ -281,6 +297,25 @@ pub mod upc_ean_shared {
     };
 }
 
+pub mod upc_ean_extension {
+    /**
+     * Start pattern of a 2 or 5 digit extension, a bar, a space and a double bar.
+     */
+    pub const START_PATTERN: [u32; 3] = [1, 1, 2];
+
+    /**
+     * Pattern separating the digits of an extension.
+     */
+    pub const SEPARATOR_PATTERN: [u32; 2] = [1, 1];
+
+    /**
+     * Parities of the digits of a 5 digit extension for each value of its check digit,
+     * using Odd = 0 and Even = 1 as for EAN-13.
+     */
+    pub const EXTENSION_5_CHECK_DIGIT_ENCODINGS: [usize; 10] =
+        [0x18, 0x14, 0x12, 0x11, 0x0C, 0x06, 0x03, 0x0A, 0x09, 0x05];
+}
+
 pub mod upc_e {
     /**
      * The pattern that marks the middle, and end, of a UPC-E pattern.
@@ -0,0 +1,70 @@
+use std::ops::RangeInclusive;
+
+use crate::{Exceptions, common::Result};
+
+/// Values a one-track Pharmacode can hold, from two narrow bars to sixteen wide ones
+pub const ONE_TRACK_RANGE: RangeInclusive<u32> = 3..=131070;
+
+/// Values a two-track Pharmacode can hold, from two bottom bars to sixteen full ones
+pub const TWO_TRACK_RANGE: RangeInclusive<u32> = 4..=64570080;
+
+/// Parses the contents of a Pharmacode, a plain decimal number within `range`
+#[cfg(feature = "encoders")]
+pub fn parse_value(contents: &str, range: RangeInclusive<u32>) -> Result<u32> {
+    contents
+        .parse::<u32>()
+        .ok()
+        .filter(|value| range.contains(value))
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "Pharmacode contents must be a number from {} to {}, got '{contents}'",
+                range.start(),
+                range.end()
+            ))
+        })
+}
+
+/// Writes `value` in bijective base `base`, most significant digit first. The digits run from
+/// 1 to `base`, one per bar.
+#[cfg(feature = "encoders")]
+pub fn to_digits(mut value: u32, base: u32) -> Vec<u8> {
+    let mut digits = Vec::new();
+    while value > 0 {
+        let digit = (value - 1) % base + 1;
+        digits.push(digit as u8);
+        value = (value - digit) / base;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Reads bijective base `base` digits back into a value within `range`
+#[cfg(feature = "decoders")]
+pub fn from_digits(digits: &[u8], base: u32, range: RangeInclusive<u32>) -> Result<u32> {
+    let value = digits.iter().try_fold(0u32, |value, &digit| {
+        value.checked_mul(base)?.checked_add(digit as u32)
+    });
+    value
+        .filter(|value| range.contains(value))
+        .ok_or(Exceptions::NOT_FOUND)
+}
+
+#[cfg(all(test, feature = "encoders", feature = "decoders"))]
+mod tests {
+    use super::{ONE_TRACK_RANGE, TWO_TRACK_RANGE, from_digits, to_digits};
+
+    #[test]
+    fn bijective_digits() {
+        assert_eq!(to_digits(1234, 2), [1, 1, 2, 2, 1, 2, 1, 1, 2, 2]);
+        assert_eq!(to_digits(1234, 3), [1, 1, 2, 3, 1, 3, 1]);
+        assert_eq!(to_digits(3, 2), [1, 1]);
+        assert_eq!(to_digits(131070, 2), [2; 16]);
+        assert_eq!(to_digits(64570080, 3), [3; 16]);
+        assert_eq!(
+            from_digits(&[1, 1, 2, 3, 1, 3, 1], 3, TWO_TRACK_RANGE).unwrap(),
+            1234
+        );
+        // a single bar is below the smallest value
+        assert!(from_digits(&[2], 2, ONE_TRACK_RANGE).is_err());
+    }
+}
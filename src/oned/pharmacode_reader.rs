@@ -0,0 +1,124 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::common::{BitArray, Result};
+use crate::{BarcodeFormat, DecodeHints, Exceptions, RXingResult, point};
+
+use super::{OneDReader, pharmacode_common, record_all_runs};
+
+/**
+ * <p>Decodes one-track Pharmacode. The symbology has no start or stop pattern and no check
+ * digit, so any short run of evenly spaced bars reads as some value; it is only attempted when
+ * [`BarcodeFormat::PHARMACODE`] is requested explicitly.</p>
+ */
+#[derive(OneDReader, Clone, Default)]
+pub struct PharmacodeReader;
+
+impl OneDReader for PharmacodeReader {
+    fn decode_row(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        _hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let runs = record_all_runs(row);
+
+        // black runs have odd indices
+        for start in (1..runs.len()).step_by(2) {
+            let Some((value, end)) = Self::read_bars(&runs, start) else {
+                continue;
+            };
+
+            let left = runs[..start].iter().sum::<u32>();
+            let right = left + runs[start..end].iter().sum::<u32>();
+            return Ok(RXingResult::new(
+                &value.to_string(),
+                Vec::new(),
+                vec![
+                    point(left as f32, rowNumber as f32),
+                    point(right as f32, rowNumber as f32),
+                ],
+                BarcodeFormat::PHARMACODE,
+            ));
+        }
+
+        Err(Exceptions::NOT_FOUND)
+    }
+}
+
+impl PharmacodeReader {
+    /// Reads the bars from `start` up to the next quiet zone, returning the value they encode
+    /// and the index of the run following the last bar
+    fn read_bars(runs: &[u32], start: usize) -> Option<(u32, usize)> {
+        // spaces are two modules, the width every other element is measured against
+        let space = *runs.get(start + 1)?;
+        let is_space = |w: u32| 3 * w >= 2 * space && 2 * w <= 3 * space;
+
+        let mut end = start + 1;
+        while runs.get(end).is_some_and(|&w| is_space(w)) && end + 1 < runs.len() {
+            end += 2;
+        }
+        // both quiet zones are at least two spaces wide
+        let is_quiet = |w: &u32| *w >= 2 * space;
+        if !runs.get(end).is_none_or(is_quiet) || !is_quiet(&runs[start - 1]) {
+            return None;
+        }
+
+        let digits = runs[start..end]
+            .iter()
+            .step_by(2)
+            .map(|&bar| {
+                if 4 * bar < space || 4 * bar > 9 * space {
+                    None
+                } else if bar < space {
+                    Some(1)
+                } else {
+                    Some(2)
+                }
+            })
+            .collect::<Option<Vec<u8>>>()?;
+
+        let value =
+            pharmacode_common::from_digits(&digits, 2, pharmacode_common::ONE_TRACK_RANGE).ok()?;
+        (digits.len() >= 2).then_some((value, end))
+    }
+}
+
+#[cfg(test)]
+mod PharmacodeReaderTestCase {
+    use crate::{
+        DecodeHints,
+        common::BitMatrix,
+        oned::{OneDReader, PharmacodeReader},
+    };
+
+    #[test]
+    fn testDecode() {
+        doTest("1234", "0000010010011100111001001110010010011100111000000");
+        doTest("3", "00000100100000");
+        doTest(
+            "131070",
+            "0000011100111001110011100111001110011100111001110011100111001110011100111001110011100000",
+        );
+        // scaled up by two
+        doTest("4", "00000000001100001111110000000000");
+    }
+
+    #[test]
+    fn testRejectsMissingQuietZone() {
+        let matrix =
+            BitMatrix::parse_strings("10010011100111000000", "1", "0").expect("bitmatrix parse");
+        assert!(
+            PharmacodeReader
+                .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+                .is_err()
+        );
+    }
+
+    fn doTest(expected: &str, encoded: &str) {
+        let matrix = BitMatrix::parse_strings(encoded, "1", "0").expect("bitmatrix parse");
+        let result = PharmacodeReader
+            .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+            .expect("decode row");
+        assert_eq!(expected, result.getText());
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, Exceptions, ImmutableReader, RXingResult,
+    Reader,
+    common::{BitMatrix, Result},
+    point,
+};
+
+use super::{pharmacode_common, postal::bar_sampler::scan_rows};
+
+/// Reads two-track Pharmacode from the tracks each bar fills.
+///
+/// A single row only crosses the bars of one track, so the vertical extent of the bars it
+/// finds decides which further rows are sampled to pick up the bars of the other track. As
+/// the symbology has no start or stop pattern and no check digit, it is only attempted when
+/// [`BarcodeFormat::PHARMACODE_TWO_TRACK`] is requested explicitly. The tracks are told apart
+/// by the bars that fill them, so a symbol whose bars all fill the same single track reads as
+/// if they filled both.
+#[derive(Default, Clone)]
+pub struct PharmacodeTwoTrackReader;
+
+/// A bar, `left` and `top` inclusive, `right` and `bottom` exclusive
+#[derive(Clone, Copy, Debug)]
+struct Bar {
+    left: u32,
+    right: u32,
+    top: u32,
+    bottom: u32,
+}
+
+impl Reader for PharmacodeTwoTrackReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl ImmutableReader for PharmacodeTwoTrackReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl PharmacodeTwoTrackReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let requested = hints
+            .PossibleFormats
+            .as_ref()
+            .is_some_and(|possible| possible.contains(&BarcodeFormat::PHARMACODE_TWO_TRACK));
+        if !requested {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Self::decode_matrix(image.get_black_matrix())
+    }
+
+    /// Looks for a two-track Pharmacode in a binarized image
+    pub fn decode_matrix(matrix: &BitMatrix) -> Result<RXingResult> {
+        for y in scan_rows(matrix.getHeight()) {
+            let bars = sample_row(matrix, y);
+            if bars.is_empty() {
+                continue;
+            }
+            let top = bars.iter().map(|b| b.top).min().unwrap_or_default();
+            let bottom = bars.iter().map(|b| b.bottom).max().unwrap_or_default();
+            let height = bottom - top;
+
+            // rows within both tracks, should the bars found so far cover both, and rows above
+            // and below them, should they only cover one
+            let mut probes = vec![top + height / 4, bottom - 1 - height / 4, y];
+            probes.extend(top.checked_sub(height / 2));
+            probes.push(bottom + height / 2);
+            let mut all_bars: Vec<Bar> = probes
+                .into_iter()
+                .filter(|&probe| probe < matrix.getHeight())
+                .flat_map(|probe| sample_row(matrix, probe))
+                .collect();
+            all_bars.sort_unstable_by_key(|b| b.left);
+            all_bars.dedup_by(|b, a| b.left < a.right);
+
+            for group in regular_groups(&all_bars) {
+                if !group.iter().any(|b| b.top <= y && y < b.bottom) {
+                    continue;
+                }
+                if let Some(value) = decode_bars(group) {
+                    let first = group[0];
+                    let last = group[group.len() - 1];
+                    return Ok(RXingResult::new(
+                        &value.to_string(),
+                        Vec::new(),
+                        vec![
+                            point(first.left as f32, y as f32),
+                            point(last.right as f32, y as f32),
+                        ],
+                        BarcodeFormat::PHARMACODE_TWO_TRACK,
+                    ));
+                }
+            }
+        }
+        Err(Exceptions::NOT_FOUND)
+    }
+}
+
+/// The dark runs crossing row `y`, each followed up and down its centre column
+fn sample_row(matrix: &BitMatrix, y: u32) -> Vec<Bar> {
+    let width = matrix.getWidth();
+    let mut bars = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if !matrix.get(x, y) {
+            x += 1;
+            continue;
+        }
+        let left = x;
+        while x < width && matrix.get(x, y) {
+            x += 1;
+        }
+        let centre = (left + x) / 2;
+        let mut top = y;
+        while top > 0 && matrix.get(centre, top - 1) {
+            top -= 1;
+        }
+        let mut bottom = y;
+        while bottom < matrix.getHeight() && matrix.get(centre, bottom) {
+            bottom += 1;
+        }
+        bars.push(Bar {
+            left,
+            right: x,
+            top,
+            bottom: bottom.max(y + 1),
+        });
+    }
+    bars
+}
+
+/// Splits bars sorted from left to right into runs of bars of similar width and spacing,
+/// bounded on both sides by a quiet zone
+fn regular_groups(bars: &[Bar]) -> Vec<&[Bar]> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for i in 1..=bars.len() {
+        let continues = bars.get(i).is_some_and(|bar| {
+            let previous = bars[i - 1];
+            let width = previous.right - previous.left;
+            let gap = bar.left - previous.right;
+            2 * (bar.right - bar.left) >= width
+                && bar.right - bar.left <= 2 * width
+                && gap <= 2 * width
+                && 2 * gap >= width
+        });
+        if !continues {
+            if i - start >= 2 {
+                groups.push(&bars[start..i]);
+            }
+            start = i;
+        }
+    }
+    groups
+}
+
+/// The value of a group of bars, from the tracks each one fills
+fn decode_bars(bars: &[Bar]) -> Option<u32> {
+    let top = bars.iter().map(|b| b.top).min()?;
+    let bottom = bars.iter().map(|b| b.bottom).max()?;
+    let quarter = (bottom - top) / 4;
+    let middle = (top + bottom) / 2;
+
+    let digits = bars
+        .iter()
+        .map(|bar| {
+            let in_top = bar.top <= top + quarter;
+            let in_bottom = bar.bottom >= bottom - quarter;
+            // bars filling a single track end near the middle
+            let ends_in_middle = |edge: u32| edge.abs_diff(middle) <= quarter;
+            match (in_top, in_bottom) {
+                (true, true) => Some(3),
+                (true, false) if ends_in_middle(bar.bottom) => Some(2),
+                (false, true) if ends_in_middle(bar.top) => Some(1),
+                _ => None,
+            }
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    pharmacode_common::from_digits(&digits, 3, pharmacode_common::TWO_TRACK_RANGE).ok()
+}
+
+#[cfg(all(test, feature = "image", feature = "encoders"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, DecodeHints,
+        MultiFormatReader, Reader, Writer,
+        common::{BitMatrix, HybridBinarizer, Result},
+        oned::PharmacodeTwoTrackWriter,
+    };
+
+    use super::PharmacodeTwoTrackReader;
+
+    fn hints() -> DecodeHints {
+        DecodeHints::default().with(DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::PHARMACODE_TWO_TRACK,
+        ])))
+    }
+
+    fn read(matrix: &BitMatrix, hints: &DecodeHints) -> Result<crate::RXingResult> {
+        let image: image::DynamicImage = matrix.into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        PharmacodeTwoTrackReader.decode_with_hints(&mut bitmap, hints)
+    }
+
+    #[test]
+    fn round_trip() {
+        for value in ["1234", "5", "64570080", "987654"] {
+            let matrix = PharmacodeTwoTrackWriter
+                .encode(value, &BarcodeFormat::PHARMACODE_TWO_TRACK, 200, 60)
+                .unwrap();
+            let result = read(&matrix, &hints()).unwrap();
+            assert_eq!(result.getText(), value);
+            assert_eq!(
+                result.getBarcodeFormat(),
+                &BarcodeFormat::PHARMACODE_TWO_TRACK
+            );
+        }
+    }
+
+    #[test]
+    fn only_when_requested() {
+        let matrix = PharmacodeTwoTrackWriter
+            .encode("1234", &BarcodeFormat::PHARMACODE_TWO_TRACK, 200, 60)
+            .unwrap();
+        assert!(read(&matrix, &DecodeHints::default()).is_err());
+
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut bitmap, &hints())
+            .unwrap();
+        assert_eq!(result.getText(), "1234");
+    }
+}
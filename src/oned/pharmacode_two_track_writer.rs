@@ -0,0 +1,116 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Writer,
+    common::{BitMatrix, Result},
+};
+
+use super::{pharmacode_common, postal::check_arguments};
+
+/// Smallest height, in modules, of a rendered two-track Pharmacode
+const MIN_HEIGHT: u32 = 8;
+
+/// Renders a two-track Pharmacode. The contents are a number from 4 to 64570080.
+///
+/// Each bar is one module wide and separated from the next by a one module space. A bar
+/// fills the bottom track, the top track or both, so the symbol cannot be described as a
+/// single row of modules like the other one dimensional formats.
+#[derive(Default)]
+pub struct PharmacodeTwoTrackWriter;
+
+impl Writer for PharmacodeTwoTrackWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        let margin = check_arguments(
+            contents,
+            format,
+            BarcodeFormat::PHARMACODE_TWO_TRACK,
+            width,
+            height,
+            hints,
+        )?;
+        let value = pharmacode_common::parse_value(contents, pharmacode_common::TWO_TRACK_RANGE)?;
+        let digits = pharmacode_common::to_digits(value, 3);
+
+        let code_width = 2 * digits.len() as u32 - 1;
+        let full_width = code_width + 2 * margin;
+        let output_width = (width.max(0) as u32).max(full_width);
+        let multiple = output_width / full_width;
+        let output_height = (height.max(0) as u32).max(MIN_HEIGHT * multiple);
+        let left = (output_width - code_width * multiple) / 2;
+        let middle = output_height / 2;
+
+        let mut output = BitMatrix::new(output_width, output_height)?;
+        for (i, digit) in digits.into_iter().enumerate() {
+            // 1 fills the bottom track, 2 the top track and 3 both
+            let (top, bottom) = match digit {
+                1 => (middle, output_height),
+                2 => (0, middle),
+                _ => (0, output_height),
+            };
+            output.setRegion(left + 2 * i as u32 * multiple, top, multiple, bottom - top)?;
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, Writer, common::BitMatrix};
+
+    use super::PharmacodeTwoTrackWriter;
+
+    fn row_to_string(matrix: &BitMatrix, y: u32) -> String {
+        (0..matrix.getWidth())
+            .map(|x| if matrix.get(x, y) { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn encode() {
+        let matrix = PharmacodeTwoTrackWriter
+            .encode("1234", &BarcodeFormat::PHARMACODE_TWO_TRACK, 0, 0)
+            .unwrap();
+        assert_eq!(matrix.getHeight(), 8);
+        // top and bottom track, as produced by other encoders
+        let margin = "000000";
+        assert_eq!(
+            row_to_string(&matrix, 0),
+            format!("{margin}0000101000100{margin}")
+        );
+        assert_eq!(
+            row_to_string(&matrix, 7),
+            format!("{margin}1010001010101{margin}")
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        for contents in ["3", "64570081", "12A"] {
+            assert!(
+                PharmacodeTwoTrackWriter
+                    .encode(contents, &BarcodeFormat::PHARMACODE_TWO_TRACK, 0, 0)
+                    .is_err()
+            );
+        }
+        assert!(
+            PharmacodeTwoTrackWriter
+                .encode("1234", &BarcodeFormat::PHARMACODE, 0, 0)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::BarcodeFormat;
+use crate::common::Result;
+
+use super::{OneDimensionalCodeWriter, pharmacode_common};
+
+/**
+ * This object renders a one-track Pharmacode as a BitMatrix. The contents are a number from 3
+ * to 131070, written with narrow bars one module wide and wide bars three modules wide,
+ * separated by two module spaces.
+ */
+#[derive(OneDWriter, Default)]
+pub struct PharmacodeWriter;
+
+impl OneDimensionalCodeWriter for PharmacodeWriter {
+    fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        let value = pharmacode_common::parse_value(contents, pharmacode_common::ONE_TRACK_RANGE)?;
+
+        let mut result = Vec::new();
+        for (i, digit) in pharmacode_common::to_digits(value, 2)
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                result.extend([false, false]);
+            }
+            let width = if digit == 1 { 1 } else { 3 };
+            result.extend(std::iter::repeat_n(true, width));
+        }
+
+        Ok(result)
+    }
+
+    fn getSupportedWriteFormats(&self) -> Option<Vec<crate::BarcodeFormat>> {
+        Some(vec![BarcodeFormat::PHARMACODE])
+    }
+}
+
+#[cfg(test)]
+mod PharmacodeWriterTestCase {
+    use crate::{
+        BarcodeFormat, Writer,
+        common::{BitMatrix, bit_matrix_test_case},
+    };
+
+    use super::PharmacodeWriter;
+
+    #[test]
+    fn testEncode() {
+        doTest(
+            "1234",
+            concat!("00000", "10010011100111001001110010010011100111", "00000"),
+        );
+        doTest("3", concat!("00000", "1001", "00000"));
+        doTest(
+            "131070",
+            concat!(
+                "00000",
+                "111001110011100111001110011100111001110011100111001110011100111001110011100111",
+                "00000"
+            ),
+        );
+    }
+
+    #[test]
+    fn testOutOfRange() {
+        for contents in ["2", "131071", "12A", ""] {
+            assert!(
+                PharmacodeWriter
+                    .encode(contents, &BarcodeFormat::PHARMACODE, 0, 0)
+                    .is_err()
+            );
+        }
+    }
+
+    fn doTest(input: &str, expected: &str) {
+        let result: BitMatrix = PharmacodeWriter
+            .encode(input, &BarcodeFormat::PHARMACODE, 0, 0)
+            .expect("must encode");
+        assert_eq!(expected, bit_matrix_test_case::matrix_to_string(&result));
+    }
+}
@@ -0,0 +1,329 @@
+//! Australia Post 4-State customer barcodes.
+//!
+//! The contents are a two digit format control code (FCC), an eight digit delivery point
+//! identifier (DPID) and, for the longer formats, customer information. Digits take two
+//! bars each, other customer information characters three. The bars between the start and
+//! stop pairs are read in groups of three as base 4 symbols over GF(64) and protected by
+//! four Reed-Solomon parity symbols.
+//!
+//! Unused customer information bars are filled with trackers. The character `z` in the
+//! alphanumeric character set is three trackers as well, so trailing `z`s of alphanumeric
+//! customer information cannot be told apart from the filler and are not returned.
+//! Customer information that reads as digits is returned as digits.
+
+use crate::{
+    Exceptions,
+    common::{
+        Result,
+        reedsolomon::{
+            PredefinedGenericGF, ReedSolomonDecoder, ReedSolomonEncoder, get_predefined_genericgf,
+        },
+    },
+};
+
+use super::FourStateBar;
+
+const FCC_LENGTH: usize = 2;
+const DPID_LENGTH: usize = 8;
+
+/// Start and stop are both an ascender followed by a tracker
+const START_STOP: [u8; 2] = [1, 3];
+const PARITY_SYMBOLS: usize = 4;
+const FILLER: u8 = 3;
+
+/// Bars before the customer information: start, FCC and DPID
+const HEADER_BARS: usize = 2 + 2 * (FCC_LENGTH + DPID_LENGTH);
+/// Bars after the customer information: parity and stop
+const TRAILER_BARS: usize = 3 * PARITY_SYMBOLS + 2;
+
+/// Bar values as used by Australia Post: 0 full, 1 ascender, 2 descender, 3 tracker
+const N_TABLE: [[u8; 2]; 10] = [
+    [0, 0],
+    [0, 1],
+    [0, 2],
+    [1, 0],
+    [1, 1],
+    [1, 2],
+    [2, 0],
+    [2, 1],
+    [2, 2],
+    [3, 0],
+];
+
+const C_ALPHABET: &[u8; 64] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz #";
+
+const C_TABLE: [[u8; 3]; 64] = [
+    [2, 2, 2],
+    [3, 0, 0],
+    [3, 0, 1],
+    [3, 0, 2],
+    [3, 1, 0],
+    [3, 1, 1],
+    [3, 1, 2],
+    [3, 2, 0],
+    [3, 2, 1],
+    [3, 2, 2],
+    [0, 0, 0],
+    [0, 0, 1],
+    [0, 0, 2],
+    [0, 1, 0],
+    [0, 1, 1],
+    [0, 1, 2],
+    [0, 2, 0],
+    [0, 2, 1],
+    [0, 2, 2],
+    [1, 0, 0],
+    [1, 0, 1],
+    [1, 0, 2],
+    [1, 1, 0],
+    [1, 1, 1],
+    [1, 1, 2],
+    [1, 2, 0],
+    [1, 2, 1],
+    [1, 2, 2],
+    [2, 0, 0],
+    [2, 0, 1],
+    [2, 0, 2],
+    [2, 1, 0],
+    [2, 1, 1],
+    [2, 1, 2],
+    [2, 2, 0],
+    [2, 2, 1],
+    [0, 2, 3],
+    [0, 3, 0],
+    [0, 3, 1],
+    [0, 3, 2],
+    [0, 3, 3],
+    [1, 0, 3],
+    [1, 1, 3],
+    [1, 2, 3],
+    [1, 3, 0],
+    [1, 3, 1],
+    [1, 3, 2],
+    [1, 3, 3],
+    [2, 0, 3],
+    [2, 1, 3],
+    [2, 2, 3],
+    [2, 3, 0],
+    [2, 3, 1],
+    [2, 3, 2],
+    [2, 3, 3],
+    [3, 0, 3],
+    [3, 1, 3],
+    [3, 2, 3],
+    [3, 3, 0],
+    [3, 3, 1],
+    [3, 3, 2],
+    [3, 3, 3],
+    [0, 0, 3],
+    [0, 1, 3],
+];
+
+/// Total number of bars for a format control code
+fn bar_count(fcc: &str) -> Option<usize> {
+    match fcc {
+        "11" | "44" | "45" | "87" | "92" => Some(37),
+        "59" => Some(52),
+        "62" => Some(67),
+        _ => None,
+    }
+}
+
+fn reed_solomon_symbols(values: &[u8]) -> Vec<i32> {
+    values
+        .chunks(3)
+        .map(|t| t.iter().fold(0, |acc, &v| acc * 4 + v as i32))
+        .collect()
+}
+
+/// Encodes the FCC, DPID and customer information into 37, 52 or 67 bars
+pub fn encode(contents: &str) -> Result<Vec<FourStateBar>> {
+    if !contents.is_ascii() || contents.len() < FCC_LENGTH + DPID_LENGTH {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "expected a format control code and a delivery point identifier, got {contents}"
+        )));
+    }
+    let (header, customer) = contents.split_at(FCC_LENGTH + DPID_LENGTH);
+    if let Some(c) = header.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "format control code and delivery point identifier only contain digits, found {c}"
+        )));
+    }
+    let total = bar_count(&header[..FCC_LENGTH]).ok_or_else(|| {
+        Exceptions::illegal_argument_with(format!(
+            "unsupported format control code {}",
+            &header[..FCC_LENGTH]
+        ))
+    })?;
+
+    let mut values = START_STOP.to_vec();
+    for digit in header.bytes() {
+        values.extend_from_slice(&N_TABLE[(digit - b'0') as usize]);
+    }
+    if customer.bytes().all(|b| b.is_ascii_digit()) {
+        for digit in customer.bytes() {
+            values.extend_from_slice(&N_TABLE[(digit - b'0') as usize]);
+        }
+    } else {
+        for c in customer.bytes() {
+            let index = C_ALPHABET.iter().position(|&a| a == c).ok_or_else(|| {
+                Exceptions::illegal_argument_with(format!("cannot encode {}", c as char))
+            })?;
+            values.extend_from_slice(&C_TABLE[index]);
+        }
+    }
+    if values.len() > total - TRAILER_BARS {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "customer information {customer} does not fit format control code {}",
+            &header[..FCC_LENGTH]
+        )));
+    }
+    values.resize(total - TRAILER_BARS, FILLER);
+
+    let mut symbols = reed_solomon_symbols(&values[2..]);
+    let data_symbols = symbols.len();
+    symbols.resize(data_symbols + PARITY_SYMBOLS, 0);
+    ReedSolomonEncoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::MaxicodeField64,
+    ))?
+    .encode(&mut symbols, PARITY_SYMBOLS)?;
+    for &parity in &symbols[data_symbols..] {
+        values.extend([
+            (parity >> 4) as u8,
+            (parity >> 2 & 3) as u8,
+            (parity & 3) as u8,
+        ]);
+    }
+    values.extend_from_slice(&START_STOP);
+
+    values.into_iter().map(FourStateBar::from_digit).collect()
+}
+
+/// Decodes 37, 52 or 67 bars, correcting up to two damaged symbols
+pub fn decode(bars: &[FourStateBar]) -> Result<String> {
+    if !matches!(bars.len(), 37 | 52 | 67) {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let mut values: Vec<u8> = bars.iter().map(FourStateBar::to_digit).collect();
+    if values[..2] != START_STOP || values[values.len() - 2..] != START_STOP {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let end = values.len() - 2;
+    let mut symbols = reed_solomon_symbols(&values[2..end]);
+    ReedSolomonDecoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::MaxicodeField64,
+    ))
+    .decode(&mut symbols, PARITY_SYMBOLS as i32)
+    .map_err(|_| Exceptions::CHECKSUM)?;
+    for (i, symbol) in symbols.iter().enumerate() {
+        let i = 2 + 3 * i;
+        values[i..i + 3].copy_from_slice(&[
+            (symbol >> 4) as u8,
+            (symbol >> 2 & 3) as u8,
+            (symbol & 3) as u8,
+        ]);
+    }
+
+    let n_digit = |pair: &[u8]| {
+        N_TABLE
+            .iter()
+            .position(|n| n == pair)
+            .map(|d| (b'0' + d as u8) as char)
+    };
+    let mut text = String::with_capacity(FCC_LENGTH + DPID_LENGTH);
+    for pair in values[2..HEADER_BARS].chunks(2) {
+        text.push(n_digit(pair).ok_or(Exceptions::FORMAT)?);
+    }
+    if bar_count(&text[..FCC_LENGTH]) != Some(bars.len()) {
+        return Err(Exceptions::FORMAT);
+    }
+
+    let customer = &values[HEADER_BARS..bars.len() - TRAILER_BARS];
+    let used = customer
+        .iter()
+        .rposition(|&v| v != FILLER)
+        .map_or(0, |p| p + 1);
+    let numeric: Option<String> = if used % 2 == 0 {
+        customer[..used].chunks(2).map(n_digit).collect()
+    } else {
+        None
+    };
+    if let Some(numeric) = numeric {
+        text.push_str(&numeric);
+    } else {
+        let used = used.div_ceil(3) * 3;
+        let alphanumeric: Option<String> = customer[..used.min(customer.len())]
+            .chunks(3)
+            .map(|triple| {
+                C_TABLE
+                    .iter()
+                    .position(|c| c == triple)
+                    .map(|i| C_ALPHABET[i] as char)
+            })
+            .collect();
+        text.push_str(&alphanumeric.ok_or(Exceptions::FORMAT)?);
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(bars: &[FourStateBar]) -> String {
+        bars.iter().map(|b| b.to_digit().to_string()).collect()
+    }
+
+    #[test]
+    fn standard_customer_barcode() {
+        let bars = encode("1139987520").unwrap();
+        assert_eq!(bars.len(), 37);
+        let text = to_string(&bars);
+        assert!(text.starts_with("13"));
+        assert!(text.ends_with("13"));
+        // FCC 11 followed by the DPID 39987520
+        assert_eq!(&text[2..22], "01011030302221120200");
+        assert_eq!(decode(&bars).unwrap(), "1139987520");
+    }
+
+    #[test]
+    fn customer_information() {
+        for contents in [
+            "593998752012345678",
+            "5939987520AbC",
+            "6239987520123456789012345",
+            "6239987520Z9#a x",
+        ] {
+            let bars = encode(contents).unwrap();
+            assert_eq!(decode(&bars).unwrap(), contents);
+        }
+        assert!(encode("59399875201234567890").is_err());
+        assert!(encode("1339987520").is_err());
+    }
+
+    #[test]
+    fn matches_zint() {
+        for (contents, expected) in [
+            (
+                "5939987520AbCde",
+                "1312301030302221120200000030002032033303302130213213",
+            ),
+            (
+                "6239987520ABCDEFGHIJ",
+                "1320021030302221120200000001002010011012020021022100300000323131213",
+            ),
+        ] {
+            assert_eq!(to_string(&encode(contents).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn corrects_damaged_bars() {
+        let mut bars = encode("5939987520AbC").unwrap();
+        for i in [7, 30] {
+            bars[i] = FourStateBar::from_digit((bars[i].to_digit() + 1) % 4).unwrap();
+        }
+        assert_eq!(decode(&bars).unwrap(), "5939987520AbC");
+    }
+}
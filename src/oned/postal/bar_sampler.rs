@@ -0,0 +1,206 @@
+//! Samples height modulated barcodes, where the information is in how far each bar
+//! extends up and down rather than in bar widths.
+//!
+//! A scan row crossing every bar is split into dark runs. Each run is then followed up and
+//! down over the band of columns it covers, and the median extent of those columns gives
+//! the top and bottom of the bar, so that a single damaged column does not change it.
+
+use crate::common::BitMatrix;
+
+use super::FourStateBar;
+
+/// Fewest bars in any supported postal barcode
+pub const MIN_BARS: usize = 20;
+
+/// A sampled bar, `top` inclusive and `bottom` exclusive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bar {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+impl Bar {
+    pub fn height(&self) -> u32 {
+        self.bottom - self.top
+    }
+}
+
+/// Rows worth sampling, starting at the centre of the image and moving outwards
+pub fn scan_rows(height: u32) -> impl Iterator<Item = u32> {
+    let step = (height / 64).max(1);
+    let middle = height / 2;
+    (0..=height / step).flat_map(move |i| {
+        let offset = i * step;
+        let below = Some(middle + offset).filter(|&y| y < height);
+        let above = middle.checked_sub(offset).filter(|_| offset > 0);
+        [below, above].into_iter().flatten()
+    })
+}
+
+/// Groups of regularly spaced bars crossing row `y`, each with at least [`MIN_BARS`] bars
+pub fn sample_row(image: &BitMatrix, y: u32) -> Vec<Vec<Bar>> {
+    let width = image.getWidth();
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if image.get(x, y) {
+            let start = x;
+            while x < width && image.get(x, y) {
+                x += 1;
+            }
+            runs.push((start, x));
+        } else {
+            x += 1;
+        }
+    }
+    if runs.len() < MIN_BARS {
+        return Vec::new();
+    }
+
+    let mut widths: Vec<u32> = runs.iter().map(|r| r.1 - r.0).collect();
+    widths.sort_unstable();
+    let bar_width = widths[widths.len() / 2];
+    let mut gaps: Vec<u32> = runs.windows(2).map(|w| w[1].0 - w[0].1).collect();
+    gaps.sort_unstable();
+    let gap = gaps[gaps.len() / 2];
+
+    let fits_width = |run: &(u32, u32)| {
+        let w = run.1 - run.0;
+        2 * w >= bar_width && w <= 2 * bar_width + 1
+    };
+
+    let mut groups: Vec<Vec<(u32, u32)>> = Vec::new();
+    let mut current: Vec<(u32, u32)> = Vec::new();
+    for run in runs {
+        let continues = current
+            .last()
+            .is_some_and(|last| run.0 - last.1 <= 2 * gap + 1 && fits_width(&run));
+        if !continues && !current.is_empty() {
+            groups.push(std::mem::take(&mut current));
+        }
+        if fits_width(&run) {
+            current.push(run);
+        }
+    }
+    groups.push(current);
+
+    groups
+        .into_iter()
+        .filter(|group| group.len() >= MIN_BARS)
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|(left, right)| measure_bar(image, left, right, y))
+                .collect()
+        })
+        .collect()
+}
+
+/// Follows the columns `left..right` up and down from `y`
+fn measure_bar(image: &BitMatrix, left: u32, right: u32, y: u32) -> Bar {
+    let mut tops = Vec::with_capacity((right - left) as usize);
+    let mut bottoms = Vec::with_capacity((right - left) as usize);
+    for x in left..right {
+        let mut top = y;
+        while top > 0 && image.get(x, top - 1) {
+            top -= 1;
+        }
+        let mut bottom = y;
+        while bottom < image.getHeight() && image.get(x, bottom) {
+            bottom += 1;
+        }
+        tops.push(top);
+        bottoms.push(bottom.max(y + 1));
+    }
+    tops.sort_unstable();
+    bottoms.sort_unstable();
+    Bar {
+        left,
+        right,
+        top: tops[tops.len() / 2],
+        bottom: bottoms[bottoms.len() / 2],
+    }
+}
+
+/// Threshold halfway between the extreme values, or `None` when they are too close to
+/// tell two levels apart
+fn split(values: impl Iterator<Item = u32> + Clone, span: u32) -> Option<u32> {
+    let min = values.clone().min()?;
+    let max = values.max()?;
+    if (max - min) * 6 < span {
+        None
+    } else {
+        Some((min + max).div_ceil(2))
+    }
+}
+
+/// Classifies sampled bars as ascenders, descenders, full bars or trackers
+pub fn classify_four_state(bars: &[Bar]) -> Vec<FourStateBar> {
+    let span = bars.iter().map(|b| b.bottom).max().unwrap_or(0)
+        - bars.iter().map(|b| b.top).min().unwrap_or(0);
+    let top = split(bars.iter().map(|b| b.top), span);
+    let bottom = split(bars.iter().map(|b| b.bottom), span);
+    bars.iter()
+        .map(|bar| {
+            FourStateBar::new(
+                top.is_some_and(|t| bar.top < t),
+                bottom.is_some_and(|b| bar.bottom > b),
+            )
+        })
+        .collect()
+}
+
+/// Classifies sampled bars as tall (`true`) or short by their height alone
+pub fn classify_two_state(bars: &[Bar]) -> Option<Vec<bool>> {
+    let span = bars.iter().map(Bar::height).max()?;
+    let threshold = split(bars.iter().map(Bar::height), span)?;
+    Some(bars.iter().map(|bar| bar.height() >= threshold).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_bar_extents() {
+        let mut image = BitMatrix::new(120, 30).unwrap();
+        let bars = [
+            FourStateBar::Full,
+            FourStateBar::Ascender,
+            FourStateBar::Descender,
+            FourStateBar::Tracker,
+        ];
+        for i in 0..40u32 {
+            let (top, bottom) = match bars[i as usize % 4] {
+                FourStateBar::Full => (0, 30),
+                FourStateBar::Ascender => (0, 20),
+                FourStateBar::Descender => (10, 30),
+                FourStateBar::Tracker => (10, 20),
+            };
+            image.setRegion(i * 3, top, 2, bottom - top).unwrap();
+        }
+        // a speck touching one column of the first tracker must not change it
+        image.set(10, 9);
+
+        let groups = sample_row(&image, 15);
+        assert_eq!(groups.len(), 1);
+        let classified = classify_four_state(&groups[0]);
+        assert_eq!(classified.len(), 40);
+        for (i, bar) in classified.iter().enumerate() {
+            assert_eq!(*bar, bars[i % 4], "bar {i}");
+        }
+    }
+
+    #[test]
+    fn scans_from_the_middle() {
+        let rows: Vec<u32> = scan_rows(10).collect();
+        assert_eq!(rows[0], 5);
+        assert_eq!(rows.len(), 10);
+        let mut sorted = rows.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 10);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+
+use crate::{Exceptions, common::Result};
+
+#[cfg(feature = "encoders")]
+use crate::{BarcodeFormat, EncodeHints, common::BitMatrix};
+
+/// Default quiet zone on either side of a postal barcode, in bar widths
+#[cfg(feature = "encoders")]
+pub const DEFAULT_MARGIN: u32 = 6;
+
+/// Smallest rendered height, in bar widths. Each of the three bands is a third of it.
+#[cfg(feature = "encoders")]
+const MIN_HEIGHT: u32 = 9;
+
+/// One bar of a height modulated postal barcode. Every bar covers the central tracker
+/// band, ascenders and descenders extend it upwards or downwards, full bars in both
+/// directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FourStateBar {
+    Full,
+    Ascender,
+    Descender,
+    Tracker,
+}
+
+impl FourStateBar {
+    pub const fn new(ascender: bool, descender: bool) -> Self {
+        match (ascender, descender) {
+            (true, true) => FourStateBar::Full,
+            (true, false) => FourStateBar::Ascender,
+            (false, true) => FourStateBar::Descender,
+            (false, false) => FourStateBar::Tracker,
+        }
+    }
+
+    pub const fn has_ascender(&self) -> bool {
+        matches!(self, FourStateBar::Full | FourStateBar::Ascender)
+    }
+
+    pub const fn has_descender(&self) -> bool {
+        matches!(self, FourStateBar::Full | FourStateBar::Descender)
+    }
+
+    /// The same bar seen upside down
+    pub const fn flipped(&self) -> Self {
+        FourStateBar::new(self.has_descender(), self.has_ascender())
+    }
+
+    /// Bar for the digits `0` (full), `1` (ascender), `2` (descender) and `3` (tracker)
+    /// commonly used in the postal specifications
+    pub fn from_digit(digit: u8) -> Result<Self> {
+        match digit {
+            0 => Ok(FourStateBar::Full),
+            1 => Ok(FourStateBar::Ascender),
+            2 => Ok(FourStateBar::Descender),
+            3 => Ok(FourStateBar::Tracker),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "{digit} is not a four state bar"
+            ))),
+        }
+    }
+
+    pub const fn to_digit(&self) -> u8 {
+        match self {
+            FourStateBar::Full => 0,
+            FourStateBar::Ascender => 1,
+            FourStateBar::Descender => 2,
+            FourStateBar::Tracker => 3,
+        }
+    }
+
+    /// Parses a sequence of `F`, `A`, `D` and `T` characters
+    pub fn parse_sequence(bars: &str) -> Result<Vec<Self>> {
+        bars.chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'F' => Ok(FourStateBar::Full),
+                'A' => Ok(FourStateBar::Ascender),
+                'D' => Ok(FourStateBar::Descender),
+                'T' => Ok(FourStateBar::Tracker),
+                _ => Err(Exceptions::illegal_argument_with(format!(
+                    "{c} is not a four state bar"
+                ))),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for FourStateBar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FourStateBar::Full => "F",
+            FourStateBar::Ascender => "A",
+            FourStateBar::Descender => "D",
+            FourStateBar::Tracker => "T",
+        })
+    }
+}
+
+/// Checks the arguments shared by all postal writers and returns the quiet zone
+#[cfg(feature = "encoders")]
+pub(crate) fn check_arguments(
+    contents: &str,
+    format: &BarcodeFormat,
+    expected: BarcodeFormat,
+    width: i32,
+    height: i32,
+    hints: &EncodeHints,
+) -> Result<u32> {
+    if contents.is_empty() {
+        return Err(Exceptions::illegal_argument_with("Found empty contents"));
+    }
+
+    if format != &expected {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Can only encode {expected:?}, but got {format:?}"
+        )));
+    }
+
+    if width < 0 || height < 0 {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Negative size is not allowed. Input: {width}x{height}"
+        )));
+    }
+
+    if let Some(margin) = &hints.Margin {
+        margin.parse::<u32>().map_err(|_| {
+            Exceptions::illegal_argument_with(format!("Invalid margin value: '{margin}'"))
+        })
+    } else {
+        Ok(DEFAULT_MARGIN)
+    }
+}
+
+/// Renders bars one module wide separated by one module spaces. The height is split in
+/// three equal bands, the tracker occupying the middle one.
+#[cfg(feature = "encoders")]
+pub fn render(bars: &[FourStateBar], width: i32, height: i32, margin: u32) -> Result<BitMatrix> {
+    if bars.is_empty() {
+        return Err(Exceptions::illegal_argument_with("no bars to render"));
+    }
+    let code_width = 2 * bars.len() as u32 - 1;
+    let full_width = code_width + 2 * margin;
+    let output_width = (width.max(0) as u32).max(full_width);
+    let multiple = output_width / full_width;
+    let output_height = (height.max(0) as u32).max(MIN_HEIGHT * multiple);
+    let left = (output_width - code_width * multiple) / 2;
+
+    let band_top = output_height / 3;
+    let band_bottom = output_height - output_height / 3;
+
+    let mut output = BitMatrix::new(output_width, output_height)?;
+    for (i, bar) in bars.iter().enumerate() {
+        let top = if bar.has_ascender() { 0 } else { band_top };
+        let bottom = if bar.has_descender() {
+            output_height
+        } else {
+            band_bottom
+        };
+        output.setRegion(left + 2 * i as u32 * multiple, top, multiple, bottom - top)?;
+    }
+    Ok(output)
+}
@@ -0,0 +1,209 @@
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, Exceptions, ImmutableReader, RXingResult,
+    Reader,
+    common::{BitMatrix, Result},
+    point,
+};
+
+use super::{
+    FourStateBar, australia_post,
+    bar_sampler::{Bar, classify_four_state, sample_row, scan_rows},
+    intelligent_mail, royal_mail,
+};
+
+/// Formats tried when no possible formats are requested. KIX has no check character or
+/// start and stop bars, so any multiple of four bars would be accepted and it must be
+/// requested explicitly.
+const DEFAULT_FORMATS: [BarcodeFormat; 3] = [
+    BarcodeFormat::INTELLIGENT_MAIL,
+    BarcodeFormat::RM4SCC,
+    BarcodeFormat::AUSTRALIA_POST,
+];
+
+/// Reads USPS Intelligent Mail, RM4SCC, KIX and Australia Post barcodes.
+///
+/// Unlike the other one dimensional readers, which decode the widths of bars along a
+/// single row, these barcodes are read from the height of each bar. A scan row through the
+/// tracker band locates the bars, and each bar is measured over the full band of columns
+/// it covers. Barcodes printed upside down are read as well, except KIX which has no way
+/// to tell its orientation.
+#[derive(Default, Clone)]
+pub struct FourStateReader;
+
+impl Reader for FourStateReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl ImmutableReader for FourStateReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl FourStateReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let formats: Vec<BarcodeFormat> = match &hints.PossibleFormats {
+            Some(possible) => DEFAULT_FORMATS
+                .iter()
+                .chain(&[BarcodeFormat::KIX])
+                .filter(|f| possible.contains(f))
+                .copied()
+                .collect(),
+            None => DEFAULT_FORMATS.to_vec(),
+        };
+        if formats.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Self::decode_matrix(image.get_black_matrix(), &formats)
+    }
+
+    /// Looks for any of `formats` in a binarized image
+    pub fn decode_matrix(matrix: &BitMatrix, formats: &[BarcodeFormat]) -> Result<RXingResult> {
+        let mut last_error = Exceptions::NOT_FOUND;
+        for y in scan_rows(matrix.getHeight()) {
+            for group in sample_row(matrix, y) {
+                match Self::decode_bars(&group, formats) {
+                    Ok((text, format)) => {
+                        let first = group[0];
+                        let last = group[group.len() - 1];
+                        return Ok(RXingResult::new(
+                            &text,
+                            Vec::new(),
+                            vec![
+                                point(first.left as f32, y as f32),
+                                point(last.right as f32, y as f32),
+                            ],
+                            format,
+                        ));
+                    }
+                    Err(
+                        e @ (Exceptions::ChecksumException(_) | Exceptions::FormatException(_)),
+                    ) => last_error = e,
+                    Err(_) => {}
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    fn decode_bars(group: &[Bar], formats: &[BarcodeFormat]) -> Result<(String, BarcodeFormat)> {
+        let bars = classify_four_state(group);
+        let upside_down: Vec<FourStateBar> = bars.iter().rev().map(FourStateBar::flipped).collect();
+        let mut last_error = Exceptions::NOT_FOUND;
+        for &format in formats {
+            let decode: fn(&[FourStateBar]) -> Result<String> = match format {
+                BarcodeFormat::INTELLIGENT_MAIL => intelligent_mail::decode,
+                BarcodeFormat::RM4SCC => royal_mail::decode_rm4scc,
+                BarcodeFormat::KIX => royal_mail::decode_kix,
+                BarcodeFormat::AUSTRALIA_POST => australia_post::decode,
+                _ => continue,
+            };
+            let orientations = if format == BarcodeFormat::KIX {
+                &[&bars][..]
+            } else {
+                &[&bars, &upside_down][..]
+            };
+            for bars in orientations {
+                match decode(bars) {
+                    Ok(text) => return Ok((text, format)),
+                    Err(Exceptions::NotFoundException(_)) => {}
+                    Err(e) => last_error = e,
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, DecodeHints,
+        Reader, Writer,
+        common::{BitMatrix, HybridBinarizer, Result},
+    };
+
+    use super::FourStateReader;
+    use crate::oned::postal::{
+        AustraliaPostWriter, IntelligentMailWriter, KIXWriter, RM4SCCWriter,
+    };
+
+    fn read(matrix: &BitMatrix, hints: &DecodeHints) -> Result<crate::RXingResult> {
+        let image: image::DynamicImage = matrix.into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        FourStateReader.decode_with_hints(&mut bitmap, hints)
+    }
+
+    #[test]
+    fn intelligent_mail() {
+        let matrix = IntelligentMailWriter
+            .encode(
+                "01234567094987654321-01234567891",
+                &BarcodeFormat::INTELLIGENT_MAIL,
+                400,
+                60,
+            )
+            .unwrap();
+        let result = read(&matrix, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "0123456709498765432101234567891");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::INTELLIGENT_MAIL);
+    }
+
+    #[test]
+    fn upside_down() {
+        let mut matrix = RM4SCCWriter
+            .encode("SN34RD1A", &BarcodeFormat::RM4SCC, 300, 50)
+            .unwrap();
+        matrix.rotate180();
+        let result = read(&matrix, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "SN34RD1A");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::RM4SCC);
+    }
+
+    #[test]
+    fn australia_post() {
+        let matrix = AustraliaPostWriter
+            .encode("5939987520AbC", &BarcodeFormat::AUSTRALIA_POST, 0, 0)
+            .unwrap();
+        let result = read(&matrix, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "5939987520AbC");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::AUSTRALIA_POST);
+    }
+
+    #[test]
+    fn kix_only_when_requested() {
+        let matrix = KIXWriter
+            .encode("2500GG30250", &BarcodeFormat::KIX, 200, 40)
+            .unwrap();
+        assert!(read(&matrix, &DecodeHints::default()).is_err());
+
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::KIX,
+        ])));
+        let result = read(&matrix, &hints).unwrap();
+        assert_eq!(result.getText(), "2500GG30250");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::KIX);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Writer,
+    common::{BitMatrix, Result},
+};
+
+use super::{
+    FourStateBar, australia_post,
+    four_state::{check_arguments, render},
+    intelligent_mail, royal_mail,
+};
+
+fn encode_four_state(
+    contents: &str,
+    format: &BarcodeFormat,
+    expected: BarcodeFormat,
+    width: i32,
+    height: i32,
+    hints: &EncodeHints,
+    encode: fn(&str) -> Result<Vec<FourStateBar>>,
+) -> Result<BitMatrix> {
+    let margin = check_arguments(contents, format, expected, width, height, hints)?;
+    render(&encode(contents)?, width, height, margin)
+}
+
+macro_rules! four_state_writer {
+    ($(#[$doc:meta])* $name:ident, $format:expr, $encode:path) => {
+        $(#[$doc])*
+        #[derive(Default)]
+        pub struct $name;
+
+        impl Writer for $name {
+            fn encode(
+                &self,
+                contents: &str,
+                format: &BarcodeFormat,
+                width: i32,
+                height: i32,
+            ) -> Result<BitMatrix> {
+                self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+            }
+
+            fn encode_with_hints(
+                &self,
+                contents: &str,
+                format: &BarcodeFormat,
+                width: i32,
+                height: i32,
+                hints: &EncodeHints,
+            ) -> Result<BitMatrix> {
+                encode_four_state(contents, format, $format, width, height, hints, $encode)
+            }
+        }
+    };
+}
+
+four_state_writer!(
+    /// Renders a USPS Intelligent Mail barcode. The contents are the 20 digit tracking code
+    /// followed by an optional 5, 9 or 11 digit routing code, optionally separated by `-`.
+    IntelligentMailWriter,
+    BarcodeFormat::INTELLIGENT_MAIL,
+    intelligent_mail::encode
+);
+
+four_state_writer!(
+    /// Renders a Royal Mail 4-State Customer Code, adding the check character.
+    RM4SCCWriter,
+    BarcodeFormat::RM4SCC,
+    royal_mail::encode_rm4scc
+);
+
+four_state_writer!(
+    /// Renders a Dutch KIX code.
+    KIXWriter,
+    BarcodeFormat::KIX,
+    royal_mail::encode_kix
+);
+
+four_state_writer!(
+    /// Renders an Australia Post customer barcode. The contents are the format control
+    /// code, the delivery point identifier and, for format control codes 59 and 62, the
+    /// customer information.
+    AustraliaPostWriter,
+    BarcodeFormat::AUSTRALIA_POST,
+    australia_post::encode
+);
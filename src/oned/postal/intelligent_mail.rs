@@ -0,0 +1,324 @@
+//! USPS Intelligent Mail barcode (USPS-B-3200).
+//!
+//! The 20 digit tracking code and the optional 5, 9 or 11 digit routing code are combined
+//! into a single 102 bit value, protected by an 11 bit CRC. The value is split into ten
+//! codewords, each mapped to a 13 bit character of either five or two set bits. The bits
+//! of the CRC select which characters are inverted, and the 130 character bits are spread
+//! over the ascenders and descenders of 65 bars.
+
+use once_cell::sync::Lazy;
+
+use crate::{Exceptions, common::Result};
+
+use super::FourStateBar;
+
+/// Number of bars in every Intelligent Mail barcode
+pub const BAR_COUNT: usize = 65;
+
+/// Length of the tracking code
+pub const TRACKING_LENGTH: usize = 20;
+
+const CHARACTER_COUNT: usize = 10;
+const CHARACTER_BITS: usize = 13;
+const TABLE_5_OF_13_LENGTH: usize = 1287;
+const TABLE_2_OF_13_LENGTH: usize = 78;
+const CRC_GENERATOR: u16 = 0x0F35;
+
+/// Bit of the bar pattern, plus one, holding each character bit. Character `i`, bit `j`
+/// is found at index `13 * i + j`. The first 65 bits are the descenders, the last 65 the
+/// ascenders.
+const BAR_TO_CHARACTER: [u8; 130] = [
+    67, 6, 78, 16, 86, 95, 34, 40, 45, 113, 117, 121, 62, 87, 18, 104, 41, 76, 57, 119, 115, 72,
+    97, 2, 127, 26, 105, 35, 122, 52, 114, 7, 24, 82, 68, 63, 94, 44, 77, 112, 70, 100, 39, 30,
+    107, 15, 125, 85, 10, 65, 54, 88, 20, 106, 46, 66, 8, 116, 29, 61, 99, 80, 90, 37, 123, 51, 25,
+    84, 129, 56, 4, 109, 96, 28, 36, 47, 11, 71, 33, 102, 21, 9, 17, 49, 124, 79, 64, 91, 42, 69,
+    53, 60, 14, 1, 27, 103, 126, 75, 89, 50, 120, 19, 32, 110, 92, 111, 130, 59, 31, 12, 81, 43,
+    55, 5, 74, 22, 101, 128, 58, 118, 48, 108, 38, 98, 93, 23, 83, 13, 73, 3,
+];
+
+/// Characters for codewords `0..1287` followed by those for `1287..1365`
+static CHARACTERS: Lazy<Vec<u16>> = Lazy::new(|| {
+    let mut table = n_of_13_table(5, TABLE_5_OF_13_LENGTH);
+    table.extend(n_of_13_table(2, TABLE_2_OF_13_LENGTH));
+    table
+});
+
+/// Codeword for each 13 bit character, `None` for characters that are not valid
+static CODEWORDS: Lazy<Vec<Option<u16>>> = Lazy::new(|| {
+    let mut codewords = vec![None; 1 << CHARACTER_BITS];
+    for (codeword, &character) in CHARACTERS.iter().enumerate() {
+        codewords[character as usize] = Some(codeword as u16);
+    }
+    codewords
+});
+
+/// Characters with `n` of 13 bits set. Pairs of a character and its mirror image fill the
+/// table from the start, symmetrical characters from the end.
+fn n_of_13_table(n: u32, length: usize) -> Vec<u16> {
+    let mut table = vec![0; length];
+    let mut lower = 0;
+    let mut upper = length - 1;
+    for count in 0u16..1 << CHARACTER_BITS {
+        if count.count_ones() != n {
+            continue;
+        }
+        let reverse = count.reverse_bits() >> (16 - CHARACTER_BITS);
+        if reverse < count {
+            continue;
+        }
+        if reverse == count {
+            table[upper] = count;
+            upper = upper.saturating_sub(1);
+        } else {
+            table[lower] = count;
+            table[lower + 1] = reverse;
+            lower += 2;
+        }
+    }
+    table
+}
+
+/// Frame check sequence over the 102 bit value, stored in 13 bytes most significant first
+fn crc11(value: u128) -> u16 {
+    let bytes = value.to_be_bytes();
+    let mut fcs: u16 = 0x07FF;
+    let mut step = |data: u16, bits: u32| {
+        let mut data = data;
+        for _ in 0..bits {
+            if (fcs ^ data) & 0x400 != 0 {
+                fcs = (fcs << 1) ^ CRC_GENERATOR;
+            } else {
+                fcs <<= 1;
+            }
+            fcs &= 0x7FF;
+            data <<= 1;
+        }
+    };
+    // the two most significant bits of the first byte are not part of the value
+    step((bytes[3] as u16) << 5, 6);
+    for &byte in &bytes[4..] {
+        step((byte as u16) << 3, 8);
+    }
+    fcs
+}
+
+/// Splits `contents` into tracking and routing code. The routing code may be separated
+/// from the tracking code by a `-`.
+fn parse_contents(contents: &str) -> Result<(&str, &str)> {
+    let (tracking, routing) = match contents.split_once('-') {
+        Some(parts) => parts,
+        None if contents.len() >= TRACKING_LENGTH && contents.is_ascii() => {
+            contents.split_at(TRACKING_LENGTH)
+        }
+        None => (contents, ""),
+    };
+    if tracking.len() != TRACKING_LENGTH || !matches!(routing.len(), 0 | 5 | 9 | 11) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "expected a {TRACKING_LENGTH} digit tracking code and a 0, 5, 9 or 11 digit routing code, got {contents}"
+        )));
+    }
+    if let Some(c) = tracking
+        .chars()
+        .chain(routing.chars())
+        .find(|c| !c.is_ascii_digit())
+    {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Intelligent Mail barcodes only contain digits, found {c}"
+        )));
+    }
+    if tracking.as_bytes()[1] > b'4' {
+        return Err(Exceptions::illegal_argument_with(
+            "the second digit of the barcode identifier must be 0 to 4",
+        ));
+    }
+    Ok((tracking, routing))
+}
+
+/// Encodes a 20 digit tracking code, followed by an optional routing code of 5, 9 or 11
+/// digits, into 65 bars
+pub fn encode(contents: &str) -> Result<Vec<FourStateBar>> {
+    let (tracking, routing) = parse_contents(contents)?;
+
+    let routing_value = routing.parse::<u128>().unwrap_or(0);
+    let mut value: u128 = match routing.len() {
+        5 => routing_value + 1,
+        9 => routing_value + 100_001,
+        11 => routing_value + 1_000_100_001,
+        _ => 0,
+    };
+    let digits = tracking.bytes().map(|b| (b - b'0') as u128);
+    for (i, digit) in digits.enumerate() {
+        value = value * if i == 1 { 5 } else { 10 } + digit;
+    }
+
+    let fcs = crc11(value);
+
+    let mut codewords = [0u16; CHARACTER_COUNT];
+    codewords[9] = (value % 636) as u16;
+    value /= 636;
+    for codeword in codewords[1..9].iter_mut().rev() {
+        *codeword = (value % 1365) as u16;
+        value /= 1365;
+    }
+    codewords[0] = value as u16;
+    codewords[9] *= 2;
+    if fcs & 0x400 != 0 {
+        codewords[0] += 659;
+    }
+
+    let mut pattern = [false; 2 * BAR_COUNT];
+    for (i, &codeword) in codewords.iter().enumerate() {
+        let mut character = CHARACTERS[codeword as usize];
+        if fcs & (1 << i) != 0 {
+            character ^= 0x1FFF;
+        }
+        for j in 0..CHARACTER_BITS {
+            pattern[BAR_TO_CHARACTER[CHARACTER_BITS * i + j] as usize - 1] =
+                character & (1 << j) != 0;
+        }
+    }
+
+    Ok((0..BAR_COUNT)
+        .map(|i| FourStateBar::new(pattern[i + BAR_COUNT], pattern[i]))
+        .collect())
+}
+
+/// Decodes 65 bars, read from left to right, back into the tracking code followed by the
+/// routing code
+pub fn decode(bars: &[FourStateBar]) -> Result<String> {
+    if bars.len() != BAR_COUNT {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let mut pattern = [false; 2 * BAR_COUNT];
+    for (i, bar) in bars.iter().enumerate() {
+        pattern[i] = bar.has_descender();
+        pattern[i + BAR_COUNT] = bar.has_ascender();
+    }
+
+    let mut fcs = 0u16;
+    let mut codewords = [0u16; CHARACTER_COUNT];
+    for (i, codeword) in codewords.iter_mut().enumerate() {
+        let mut character = 0u16;
+        for j in 0..CHARACTER_BITS {
+            if pattern[BAR_TO_CHARACTER[CHARACTER_BITS * i + j] as usize - 1] {
+                character |= 1 << j;
+            }
+        }
+        if matches!(character.count_ones(), 8 | 11) {
+            character ^= 0x1FFF;
+            fcs |= 1 << i;
+        }
+        *codeword = CODEWORDS[character as usize].ok_or(Exceptions::FORMAT)?;
+    }
+
+    if codewords[0] >= 659 {
+        codewords[0] -= 659;
+        fcs |= 0x400;
+    }
+    if codewords[9] % 2 != 0 {
+        return Err(Exceptions::FORMAT);
+    }
+    codewords[9] /= 2;
+    if codewords[9] >= 636 || codewords[1..9].iter().any(|&c| c >= 1365) {
+        return Err(Exceptions::FORMAT);
+    }
+
+    let mut value = codewords[0] as u128;
+    for &codeword in &codewords[1..9] {
+        value = value * 1365 + codeword as u128;
+    }
+    value = value * 636 + codewords[9] as u128;
+
+    if crc11(value) != fcs {
+        return Err(Exceptions::CHECKSUM);
+    }
+
+    let mut tracking = [0u8; TRACKING_LENGTH];
+    for digit in tracking[2..].iter_mut().rev() {
+        *digit = (value % 10) as u8;
+        value /= 10;
+    }
+    tracking[1] = (value % 5) as u8;
+    value /= 5;
+    tracking[0] = (value % 10) as u8;
+    value /= 10;
+
+    let mut text: String = tracking.iter().map(|d| (b'0' + d) as char).collect();
+    match value {
+        0 => {}
+        1..=100_000 => text.push_str(&format!("{:05}", value - 1)),
+        100_001..=1_000_100_000 => text.push_str(&format!("{:09}", value - 100_001)),
+        1_000_100_001..=101_000_100_000 => text.push_str(&format!("{:011}", value - 1_000_100_001)),
+        _ => return Err(Exceptions::FORMAT),
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // examples from USPS-B-3200, appendix C
+    const TRACKING: &str = "01234567094987654321";
+    const VECTORS: [(&str, &str); 4] = [
+        (
+            "",
+            "ATTFATTDTTADTAATTDTDTATTDAFDDFADFDFTFFFFFTATFAAAATDFFTDAADFTFDTDT",
+        ),
+        (
+            "01234",
+            "DTTAFADDTTFTDTFTFDTDDADADAFADFATDDFTAAAFDTTADFAAATDFDTDFADDDTDFFT",
+        ),
+        (
+            "012345678",
+            "ADFTTAFDTTTTFATTADTAAATFTFTATDAAAFDDADATATDTDTTDFDTDATADADTDFFTFA",
+        ),
+        (
+            "01234567891",
+            "AADTFFDFTDADTAADAATFDTDDAAADDTDTTDAFADADDDTFFFDDTTTADFAAADFTDAADA",
+        ),
+    ];
+
+    #[test]
+    fn character_tables() {
+        assert_eq!(CHARACTERS.len(), 1365);
+        assert_eq!(CHARACTERS[0], 0x001F);
+        assert_eq!(CHARACTERS[1], 0x1F00);
+        assert_eq!(CHARACTERS[1287], 0x0003);
+        assert_eq!(CHARACTERS[1288], 0x1800);
+        assert_eq!(CODEWORDS.iter().flatten().count(), 1365);
+    }
+
+    #[test]
+    fn specification_examples() {
+        for (routing, expected) in VECTORS {
+            let contents = format!("{TRACKING}{routing}");
+            let bars: String = encode(&contents)
+                .unwrap()
+                .iter()
+                .map(|b| b.to_string())
+                .collect();
+            assert_eq!(bars, expected, "routing {routing:?}");
+            let decoded = decode(&FourStateBar::parse_sequence(expected).unwrap()).unwrap();
+            assert_eq!(decoded, contents);
+        }
+    }
+
+    #[test]
+    fn separated_routing_code() {
+        assert_eq!(
+            encode(&format!("{TRACKING}-01234")),
+            encode(&format!("{TRACKING}01234"))
+        );
+        assert!(encode("0123456709498765432").is_err());
+        assert!(encode("01234567094987654321012").is_err());
+        assert!(encode("09234567094987654321").is_err());
+    }
+
+    #[test]
+    fn detects_damage() {
+        let mut bars = FourStateBar::parse_sequence(VECTORS[1].1).unwrap();
+        bars[30] = bars[30].flipped();
+        assert!(decode(&bars).is_err());
+    }
+}
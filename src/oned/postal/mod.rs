@@ -0,0 +1,36 @@
+//! Height modulated postal barcodes.
+//!
+//! These encode their data in how far each bar extends above and below a central band,
+//! or in the plain height of each bar, rather than in the widths of bars and spaces, so they cannot be read from a single
+//! [`crate::common::BitArray`] row like the other one dimensional formats.
+
+pub mod australia_post;
+pub mod intelligent_mail;
+pub mod postnet;
+pub mod royal_mail;
+
+mod four_state;
+pub use four_state::*;
+
+#[cfg(feature = "decoders")]
+pub mod bar_sampler;
+
+#[cfg(feature = "decoders")]
+mod four_state_reader;
+#[cfg(feature = "decoders")]
+pub use four_state_reader::*;
+
+#[cfg(feature = "decoders")]
+mod postnet_reader;
+#[cfg(feature = "decoders")]
+pub use postnet_reader::*;
+
+#[cfg(feature = "encoders")]
+mod four_state_writer;
+#[cfg(feature = "encoders")]
+pub use four_state_writer::*;
+
+#[cfg(feature = "encoders")]
+mod postnet_writer;
+#[cfg(feature = "encoders")]
+pub use postnet_writer::*;
@@ -0,0 +1,208 @@
+//! USPS POSTNET and PLANET barcodes.
+//!
+//! Both encode digits in five bars of two heights between a tall frame bar at either end,
+//! followed by a check digit bringing the sum of all digits to a multiple of ten. A
+//! POSTNET digit has two tall bars, a PLANET digit two short ones. POSTNET carries a 5, 9
+//! or 11 digit ZIP code, PLANET an 11 or 13 digit tracking number.
+
+use crate::{Exceptions, common::Result};
+
+#[cfg(feature = "encoders")]
+use crate::common::BitMatrix;
+
+/// Tall bars of the POSTNET digits, weighted 7, 4, 2, 1 and 0. PLANET digits are the
+/// complement.
+const POSTNET_DIGITS: [[bool; 5]; 10] = [
+    [true, true, false, false, false],
+    [false, false, false, true, true],
+    [false, false, true, false, true],
+    [false, false, true, true, false],
+    [false, true, false, false, true],
+    [false, true, false, true, false],
+    [false, true, true, false, false],
+    [true, false, false, false, true],
+    [true, false, false, true, false],
+    [true, false, true, false, false],
+];
+
+const BARS_PER_DIGIT: usize = 5;
+
+/// Smallest rendered height, in bar widths
+#[cfg(feature = "encoders")]
+const MIN_HEIGHT: usize = 5;
+
+/// Whether the bars are POSTNET or PLANET
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    Postnet,
+    Planet,
+}
+
+impl Variant {
+    /// Allowed numbers of data digits, without the check digit
+    pub const fn data_lengths(&self) -> &'static [usize] {
+        match self {
+            Variant::Postnet => &[5, 9, 11],
+            Variant::Planet => &[11, 13],
+        }
+    }
+
+    fn digit_bars(&self, digit: usize) -> [bool; 5] {
+        let bars = POSTNET_DIGITS[digit];
+        match self {
+            Variant::Postnet => bars,
+            Variant::Planet => bars.map(|tall| !tall),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Variant::Postnet => "POSTNET",
+            Variant::Planet => "PLANET",
+        }
+    }
+}
+
+/// Digit making the sum of `digits` and itself a multiple of ten
+pub fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits.iter().map(|&d| d as u32).sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Bars for `contents`, `true` for tall ones, including the frame bars. A check digit is
+/// added unless `contents` already ends in a valid one.
+pub fn encode(contents: &str, variant: Variant) -> Result<Vec<bool>> {
+    if let Some(c) = contents.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "{} barcodes only contain digits, found {c}",
+            variant.name()
+        )));
+    }
+    let mut digits: Vec<u8> = contents.bytes().map(|b| b - b'0').collect();
+    let lengths = variant.data_lengths();
+    if digits.len() > 1 && lengths.contains(&(digits.len() - 1)) {
+        let check = digits.pop().unwrap_or_default();
+        if check != check_digit(&digits) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Contents do not pass checksum: {contents}"
+            )));
+        }
+    } else if !lengths.contains(&digits.len()) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "{} requires {lengths:?} digits, got {}",
+            variant.name(),
+            digits.len()
+        )));
+    }
+    digits.push(check_digit(&digits));
+
+    let mut bars = Vec::with_capacity(BARS_PER_DIGIT * digits.len() + 2);
+    bars.push(true);
+    for &digit in &digits {
+        bars.extend(variant.digit_bars(digit as usize));
+    }
+    bars.push(true);
+    Ok(bars)
+}
+
+/// Decodes bars, including the frame bars, read from left to right. Returns the data
+/// digits without the check digit.
+pub fn decode(bars: &[bool], variant: Variant) -> Result<String> {
+    if bars.len() < 2 || (bars.len() - 2) % BARS_PER_DIGIT != 0 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let count = (bars.len() - 2) / BARS_PER_DIGIT;
+    if count == 0 || !variant.data_lengths().contains(&(count - 1)) {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    if !bars[0] || !bars[bars.len() - 1] {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let mut digits = bars[1..bars.len() - 1]
+        .chunks(BARS_PER_DIGIT)
+        .map(|chunk| {
+            (0..10)
+                .find(|&d| variant.digit_bars(d) == chunk)
+                .map(|d| d as u8)
+                .ok_or(Exceptions::FORMAT)
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    let check = digits.pop().ok_or(Exceptions::FORMAT)?;
+    if check != check_digit(&digits) {
+        return Err(Exceptions::CHECKSUM);
+    }
+    Ok(digits.iter().map(|d| (b'0' + d) as char).collect())
+}
+
+/// Renders bars one module wide separated by one module spaces, all standing on the
+/// same baseline. Short bars are two fifths of the height. The side margin is split
+/// between both sides, as for the other one dimensional writers.
+#[cfg(feature = "encoders")]
+pub fn render(bars: &[bool], width: i32, height: i32, sides_margin: u32) -> Result<BitMatrix> {
+    if bars.is_empty() {
+        return Err(Exceptions::illegal_argument_with("no bars to render"));
+    }
+    let code_width = 2 * bars.len() - 1;
+    let full_width = code_width + sides_margin as usize;
+    let output_width = (width.max(0) as usize).max(full_width);
+    let multiple = output_width / full_width;
+    let output_height = (height.max(0) as usize).max(MIN_HEIGHT * multiple);
+    let short_height = output_height * 2 / 5;
+    let left = (output_width - code_width * multiple) / 2;
+
+    let mut output = BitMatrix::new(output_width as u32, output_height as u32)?;
+    for (i, &tall) in bars.iter().enumerate() {
+        let bar_height = if tall { output_height } else { short_height };
+        output.setRegion(
+            (left + 2 * i * multiple) as u32,
+            (output_height - bar_height) as u32,
+            multiple as u32,
+            bar_height as u32,
+        )?;
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(bars: &[bool]) -> String {
+        bars.iter().map(|&b| if b { '|' } else { '.' }).collect()
+    }
+
+    #[test]
+    fn postnet_zip() {
+        // ZIP 55555 has the check digit 5
+        let bars = encode("55555", Variant::Postnet).unwrap();
+        assert_eq!(to_string(&bars), format!("|{}|", ".|.|.".repeat(6)));
+        assert_eq!(decode(&bars, Variant::Postnet).unwrap(), "55555");
+    }
+
+    #[test]
+    fn check_digits() {
+        assert_eq!(check_digit(&[1, 2, 3, 4, 5, 6, 7, 8, 9]), 5);
+        assert!(encode("1234567895", Variant::Postnet).is_ok());
+        assert!(encode("1234567891", Variant::Postnet).is_err());
+        assert!(encode("1234", Variant::Postnet).is_err());
+
+        let mut bars = encode("123456789", Variant::Postnet).unwrap();
+        // swap a tall and a short bar within the last data digit
+        bars.swap(41, 42);
+        assert!(decode(&bars, Variant::Postnet).is_err());
+    }
+
+    #[test]
+    fn planet_is_the_complement() {
+        let postnet = encode("40123456789", Variant::Postnet).unwrap();
+        let planet = encode("40123456789", Variant::Planet).unwrap();
+        assert_eq!(postnet.len(), planet.len());
+        for i in 1..postnet.len() - 1 {
+            assert_ne!(postnet[i], planet[i]);
+        }
+        assert_eq!(decode(&planet, Variant::Planet).unwrap(), "40123456789");
+        assert!(decode(&planet, Variant::Postnet).is_err());
+        assert!(encode("123456789", Variant::Planet).is_err());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, Exceptions, ImmutableReader, RXingResult,
+    Reader,
+    common::{BitMatrix, Result},
+    point,
+};
+
+use super::{
+    bar_sampler::{Bar, classify_two_state, sample_row, scan_rows},
+    postnet::{self, Variant},
+};
+
+/// Reads POSTNET and PLANET barcodes from the heights of their bars.
+///
+/// Bars are located and measured with the same sampler as the 4-state postal barcodes.
+/// Short bars stand on the baseline of the tall ones, which tells whether the barcode is
+/// upside down.
+#[derive(Default, Clone)]
+pub struct PostnetReader;
+
+impl Reader for PostnetReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl ImmutableReader for PostnetReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl PostnetReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let formats: Vec<BarcodeFormat> = [BarcodeFormat::POSTNET, BarcodeFormat::PLANET]
+            .into_iter()
+            .filter(|f| {
+                hints
+                    .PossibleFormats
+                    .as_ref()
+                    .is_none_or(|possible| possible.contains(f))
+            })
+            .collect();
+        if formats.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Self::decode_matrix(image.get_black_matrix(), &formats)
+    }
+
+    /// Looks for POSTNET or PLANET barcodes, as listed in `formats`, in a binarized image
+    pub fn decode_matrix(matrix: &BitMatrix, formats: &[BarcodeFormat]) -> Result<RXingResult> {
+        let mut last_error = Exceptions::NOT_FOUND;
+        for y in scan_rows(matrix.getHeight()) {
+            for group in sample_row(matrix, y) {
+                match Self::decode_bars(&group, formats) {
+                    Ok((text, format)) => {
+                        let first = group[0];
+                        let last = group[group.len() - 1];
+                        return Ok(RXingResult::new(
+                            &text,
+                            Vec::new(),
+                            vec![
+                                point(first.left as f32, y as f32),
+                                point(last.right as f32, y as f32),
+                            ],
+                            format,
+                        ));
+                    }
+                    Err(Exceptions::NotFoundException(_)) => {}
+                    Err(e) => last_error = e,
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    fn decode_bars(group: &[Bar], formats: &[BarcodeFormat]) -> Result<(String, BarcodeFormat)> {
+        let mut bars = classify_two_state(group).ok_or(Exceptions::NOT_FOUND)?;
+        if is_upside_down(group, &bars) {
+            bars.reverse();
+        }
+        let mut last_error = Exceptions::NOT_FOUND;
+        for &format in formats {
+            let variant = match format {
+                BarcodeFormat::POSTNET => Variant::Postnet,
+                BarcodeFormat::PLANET => Variant::Planet,
+                _ => continue,
+            };
+            match postnet::decode(&bars, variant) {
+                Ok(text) => return Ok((text, format)),
+                Err(Exceptions::NotFoundException(_)) => {}
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+/// Whether short bars line up with the tops of the tall bars rather than their bottoms
+fn is_upside_down(group: &[Bar], tall: &[bool]) -> bool {
+    let median = |values: &mut Vec<u32>| {
+        values.sort_unstable();
+        values.get(values.len() / 2).copied().unwrap_or_default()
+    };
+    let mut short_tops: Vec<u32> = Vec::new();
+    let mut short_bottoms: Vec<u32> = Vec::new();
+    let mut tall_tops: Vec<u32> = Vec::new();
+    let mut tall_bottoms: Vec<u32> = Vec::new();
+    for (bar, &is_tall) in group.iter().zip(tall) {
+        if is_tall {
+            tall_tops.push(bar.top);
+            tall_bottoms.push(bar.bottom);
+        } else {
+            short_tops.push(bar.top);
+            short_bottoms.push(bar.bottom);
+        }
+    }
+    let top_offset = median(&mut short_tops).abs_diff(median(&mut tall_tops));
+    let bottom_offset = median(&mut short_bottoms).abs_diff(median(&mut tall_bottoms));
+    top_offset < bottom_offset
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, DecodeHints,
+        MultiFormatReader, MultiFormatWriter, Reader, Writer,
+        common::{BitMatrix, HybridBinarizer, Result},
+    };
+
+    use super::PostnetReader;
+    use crate::oned::postal::{PlanetWriter, PostnetWriter};
+
+    fn read(matrix: &BitMatrix, hints: &DecodeHints) -> Result<crate::RXingResult> {
+        let image: image::DynamicImage = matrix.into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        PostnetReader.decode_with_hints(&mut bitmap, hints)
+    }
+
+    #[test]
+    fn postnet() {
+        let matrix = PostnetWriter
+            .encode("12345678901", &BarcodeFormat::POSTNET, 300, 40)
+            .unwrap();
+        let result = read(&matrix, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "12345678901");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::POSTNET);
+    }
+
+    #[test]
+    fn planet_upside_down() {
+        let mut matrix = PlanetWriter
+            .encode("4012345678901", &BarcodeFormat::PLANET, 300, 40)
+            .unwrap();
+        matrix.rotate180();
+        let result = read(&matrix, &DecodeHints::default()).unwrap();
+        assert_eq!(result.getText(), "4012345678901");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::PLANET);
+    }
+
+    #[test]
+    fn multi_format() {
+        let matrix = MultiFormatWriter
+            .encode("123456789", &BarcodeFormat::POSTNET, 300, 40)
+            .unwrap();
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        let result = MultiFormatReader::default().decode(&mut bitmap).unwrap();
+        assert_eq!(result.getText(), "123456789");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::POSTNET);
+    }
+
+    #[test]
+    fn honours_possible_formats() {
+        let matrix = PostnetWriter
+            .encode("55555", &BarcodeFormat::POSTNET, 0, 30)
+            .unwrap();
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::PLANET,
+        ])));
+        assert!(read(&matrix, &hints).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::{
+    BarcodeFormat,
+    common::{BitMatrix, Result},
+    oned::OneDimensionalCodeWriter,
+};
+
+use super::postnet::{self, Variant};
+
+/**
+ * Renders a POSTNET barcode. The contents are a 5, 9 or 11 digit ZIP code, optionally
+ * followed by its check digit. Each entry of the encoded pattern is one bar, tall or short.
+ */
+#[derive(OneDWriter, Default)]
+pub struct PostnetWriter;
+
+impl OneDimensionalCodeWriter for PostnetWriter {
+    fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        postnet::encode(contents, Variant::Postnet)
+    }
+
+    fn getSupportedWriteFormats(&self) -> Option<Vec<BarcodeFormat>> {
+        Some(vec![BarcodeFormat::POSTNET])
+    }
+
+    fn renderRXingResult(
+        code: &[bool],
+        width: i32,
+        height: i32,
+        sidesMargin: u32,
+    ) -> Result<BitMatrix> {
+        postnet::render(code, width, height, sidesMargin)
+    }
+}
+
+/**
+ * Renders a PLANET barcode. The contents are an 11 or 13 digit tracking number, optionally
+ * followed by its check digit.
+ */
+#[derive(OneDWriter, Default)]
+pub struct PlanetWriter;
+
+impl OneDimensionalCodeWriter for PlanetWriter {
+    fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        postnet::encode(contents, Variant::Planet)
+    }
+
+    fn getSupportedWriteFormats(&self) -> Option<Vec<BarcodeFormat>> {
+        Some(vec![BarcodeFormat::PLANET])
+    }
+
+    fn renderRXingResult(
+        code: &[bool],
+        width: i32,
+        height: i32,
+        sidesMargin: u32,
+    ) -> Result<BitMatrix> {
+        postnet::render(code, width, height, sidesMargin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, Writer};
+
+    use super::{PlanetWriter, PostnetWriter};
+
+    #[test]
+    fn renders_bars_on_a_baseline() {
+        let matrix = PostnetWriter
+            .encode("55555", &BarcodeFormat::POSTNET, 0, 10)
+            .unwrap();
+        // 32 bars with 31 spaces and the default margin of 10
+        assert_eq!(matrix.getWidth(), 73);
+        assert_eq!(matrix.getHeight(), 10);
+        let left = 5;
+        // frame bar, then a short bar
+        assert!(matrix.get(left, 0) && matrix.get(left, 9));
+        assert!(!matrix.get(left + 2, 5) && matrix.get(left + 2, 6) && matrix.get(left + 2, 9));
+        assert!(!matrix.get(left + 1, 9));
+    }
+
+    #[test]
+    fn rejects_other_formats() {
+        assert!(
+            PlanetWriter
+                .encode("40123456789", &BarcodeFormat::POSTNET, 0, 0)
+                .is_err()
+        );
+        assert!(
+            PlanetWriter
+                .encode("40123456789", &BarcodeFormat::PLANET, 0, 0)
+                .is_ok()
+        );
+    }
+}
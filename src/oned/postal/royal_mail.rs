@@ -0,0 +1,170 @@
+//! Royal Mail 4-State Customer Code (RM4SCC) and its Dutch variant KIX (Klant index).
+//!
+//! Both encode the characters `0-9` and `A-Z` in four bars each. The characters are laid
+//! out in a 6x6 table: the row selects which two of the four bars carry an ascender, the
+//! column which two carry a descender. RM4SCC adds a start bar, a check character derived
+//! from the row and column sums, and a stop bar. KIX has neither, so it reads the same
+//! way up as upside down and is only tried when requested.
+
+use crate::{Exceptions, common::Result};
+
+use super::FourStateBar;
+
+const ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+const BARS_PER_CHARACTER: usize = 4;
+
+/// Which two of the four bars are extended, for row and column values `1..=6`
+const EXTENDED_BARS: [[bool; 4]; 6] = [
+    [false, false, true, true],
+    [false, true, false, true],
+    [false, true, true, false],
+    [true, false, false, true],
+    [true, false, true, false],
+    [true, true, false, false],
+];
+
+const START: FourStateBar = FourStateBar::Ascender;
+const STOP: FourStateBar = FourStateBar::Full;
+
+/// Row and column, both `1..=6`, of a character
+fn row_and_column(c: char) -> Result<(usize, usize)> {
+    let index = ALPHABET
+        .iter()
+        .position(|&a| a as char == c.to_ascii_uppercase())
+        .ok_or_else(|| Exceptions::illegal_argument_with(format!("cannot encode {c}")))?;
+    Ok((index / 6 + 1, index % 6 + 1))
+}
+
+fn character_bars(row: usize, column: usize) -> impl Iterator<Item = FourStateBar> {
+    let ascenders = EXTENDED_BARS[row - 1];
+    let descenders = EXTENDED_BARS[column - 1];
+    (0..BARS_PER_CHARACTER).map(move |i| FourStateBar::new(ascenders[i], descenders[i]))
+}
+
+fn check_character(characters: &[(usize, usize)]) -> (usize, usize) {
+    let wrap = |sum: usize| match sum % 6 {
+        0 => 6,
+        v => v,
+    };
+    let top: usize = characters.iter().map(|c| c.0).sum();
+    let bottom: usize = characters.iter().map(|c| c.1).sum();
+    (wrap(top), wrap(bottom))
+}
+
+fn encode_characters(contents: &str) -> Result<Vec<(usize, usize)>> {
+    contents.chars().map(row_and_column).collect()
+}
+
+fn decode_characters(bars: &[FourStateBar]) -> Result<Vec<(usize, usize)>> {
+    if bars.is_empty() || bars.len() % BARS_PER_CHARACTER != 0 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let position = |extended: [bool; 4]| {
+        EXTENDED_BARS
+            .iter()
+            .position(|&e| e == extended)
+            .map(|p| p + 1)
+            .ok_or(Exceptions::FORMAT)
+    };
+    bars.chunks(BARS_PER_CHARACTER)
+        .map(|chunk| {
+            let row = position(std::array::from_fn(|i| chunk[i].has_ascender()))?;
+            let column = position(std::array::from_fn(|i| chunk[i].has_descender()))?;
+            Ok((row, column))
+        })
+        .collect()
+}
+
+fn to_text(characters: &[(usize, usize)]) -> String {
+    characters
+        .iter()
+        .map(|&(row, column)| ALPHABET[(row - 1) * 6 + column - 1] as char)
+        .collect()
+}
+
+/// Encodes `contents` as RM4SCC, adding start, check character and stop
+pub fn encode_rm4scc(contents: &str) -> Result<Vec<FourStateBar>> {
+    let characters = encode_characters(contents)?;
+    let (row, column) = check_character(&characters);
+    let mut bars = Vec::with_capacity(BARS_PER_CHARACTER * (characters.len() + 1) + 2);
+    bars.push(START);
+    for &(row, column) in &characters {
+        bars.extend(character_bars(row, column));
+    }
+    bars.extend(character_bars(row, column));
+    bars.push(STOP);
+    Ok(bars)
+}
+
+/// Decodes RM4SCC bars, verifying start, stop and check character
+pub fn decode_rm4scc(bars: &[FourStateBar]) -> Result<String> {
+    if bars.len() < 2 + 2 * BARS_PER_CHARACTER {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    if bars[0] != START || bars[bars.len() - 1] != STOP {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let mut characters = decode_characters(&bars[1..bars.len() - 1])?;
+    let check = characters.pop().ok_or(Exceptions::FORMAT)?;
+    if check_character(&characters) != check {
+        return Err(Exceptions::CHECKSUM);
+    }
+    Ok(to_text(&characters))
+}
+
+/// Encodes `contents` as KIX, four bars per character without start, stop or check
+pub fn encode_kix(contents: &str) -> Result<Vec<FourStateBar>> {
+    Ok(encode_characters(contents)?
+        .into_iter()
+        .flat_map(|(row, column)| character_bars(row, column))
+        .collect())
+}
+
+/// Decodes KIX bars
+pub fn decode_kix(bars: &[FourStateBar]) -> Result<String> {
+    Ok(to_text(&decode_characters(bars)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(bars: &[FourStateBar]) -> String {
+        bars.iter().map(|b| b.to_string()).collect()
+    }
+
+    #[test]
+    fn character_table() {
+        assert_eq!(to_string(&encode_kix("0").unwrap()), "TTFF");
+        assert_eq!(to_string(&encode_kix("1").unwrap()), "TDAF");
+        assert_eq!(to_string(&encode_kix("Z").unwrap()), "FFTT");
+    }
+
+    #[test]
+    fn rm4scc_round_trip() {
+        // SN34RD1A has the check character K
+        let bars = encode_rm4scc("SN34RD1A").unwrap();
+        assert_eq!(bars.len(), 4 * 9 + 2);
+        assert_eq!(
+            to_string(&bars[33..37]),
+            to_string(&encode_kix("K").unwrap())
+        );
+        assert_eq!(decode_rm4scc(&bars).unwrap(), "SN34RD1A");
+    }
+
+    #[test]
+    fn rm4scc_rejects_bad_check() {
+        let mut bars = encode_rm4scc("SN34RD1A").unwrap();
+        bars.swap(1, 2);
+        assert!(decode_rm4scc(&bars).is_err());
+    }
+
+    #[test]
+    fn kix_round_trip() {
+        let bars = encode_kix("2500GG30250").unwrap();
+        assert_eq!(bars.len(), 44);
+        assert_eq!(decode_kix(&bars).unwrap(), "2500GG30250");
+        assert!(encode_kix("1234 AB").is_err());
+    }
+}
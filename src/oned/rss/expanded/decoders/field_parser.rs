@@ -51,9 +51,10 @@ static TWO_DIGIT_DATA_LENGTH: Lazy<HashMap<String, DataLength>> = Lazy::new(|| {
     hm.insert("30".into(), DataLength::variable(8));
     hm.insert("37".into(), DataLength::variable(8));
     //internal company codes
-    for i in 90..=99 {
-        // for (int i = 90; i <= 99; i++) {
-        hm.insert(i.to_string(), DataLength::variable(30));
+    hm.insert("90".into(), DataLength::variable(30));
+    for i in 91..=99 {
+        // for (int i = 91; i <= 99; i++) {
+        hm.insert(i.to_string(), DataLength::variable(90));
     }
     hm
 });
@@ -42,6 +42,7 @@ pub struct GeneralAppIdDecoder<'a> {
     information: &'a BitArray,
     current: CurrentParsingState, //= new CurrentParsingState();
     buffer: String,               //= new StringBuilder();
+    fnc1LatchesNumeric: bool,
 }
 
 impl<'a> GeneralAppIdDecoder<'_> {
@@ -50,6 +51,7 @@ impl<'a> GeneralAppIdDecoder<'_> {
             information,
             current: CurrentParsingState::new(),
             buffer: String::new(),
+            fnc1LatchesNumeric: false,
         }
     }
 
@@ -79,6 +81,17 @@ impl<'a> GeneralAppIdDecoder<'_> {
         Ok(buff)
     }
 
+    /// Start parsing in alphanumeric mode, which the AI 90 encodation of composite components selects
+    pub fn setAlphaMode(&mut self) {
+        self.current.setAlpha();
+    }
+
+    /// Return to numeric mode after an FNC1 in alphanumeric or ISO/IEC 646 mode, as composite
+    /// components do. DataBar Expanded symbols keep the current mode instead.
+    pub fn setFnc1LatchesNumeric(&mut self) {
+        self.fnc1LatchesNumeric = true;
+    }
+
     fn isStillNumeric(&self, pos: usize) -> bool {
         // It's numeric if it still has 7 positions
         // and one of the first 4 bits is "1".
@@ -249,6 +262,7 @@ impl<'a> GeneralAppIdDecoder<'_> {
             self.current.setPosition(iso.getNewPosition());
 
             if iso.isFNC1() {
+                self.latchNumericAfterFnc1();
                 let information =
                     DecodedInformation::new(self.current.getPosition(), self.buffer.clone());
                 return Ok(BlockParsedRXingResult::with_information(
@@ -280,6 +294,7 @@ impl<'a> GeneralAppIdDecoder<'_> {
             self.current.setPosition(alpha.getNewPosition());
 
             if alpha.isFNC1() {
+                self.latchNumericAfterFnc1();
                 let information =
                     DecodedInformation::new(self.current.getPosition(), self.buffer.clone());
                 return Ok(BlockParsedRXingResult::with_information(
@@ -307,6 +322,12 @@ impl<'a> GeneralAppIdDecoder<'_> {
         Ok(BlockParsedRXingResult::new())
     }
 
+    fn latchNumericAfterFnc1(&mut self) {
+        if self.fnc1LatchesNumeric {
+            self.current.setNumeric();
+        }
+    }
+
     fn isStillIsoIec646(&self, pos: usize) -> bool {
         if pos + 5 > self.information.get_size() {
             return false;
@@ -25,8 +25,8 @@
  */
 
 use crate::{
-    BarcodeFormat, Binarizer, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
-    RXingResultMetadataValue, Reader,
+    BarcodeFormat, Binarizer, DecodeHints, Exceptions, ImmutableReader, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
     common::{BitArray, Result},
     oned::{
         OneDReader, record_pattern, record_pattern_in_reverse,
@@ -125,7 +125,7 @@ const FINDER_PATTERN_SEQUENCES: [&[u32]; 10] = [
  * @author Pablo Orduña, University of Deusto (pablo.orduna@deusto.es)
  * @author Eduardo Castillejo, University of Deusto (eduardo.castillejo@deusto.es)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct RSSExpandedReader {
     decodeFinderCounters: [u32; 4],
     dataCharacterCounters: [u32; 8],
@@ -222,6 +222,16 @@ impl Reader for RSSExpandedReader {
     }
 }
 
+impl ImmutableReader for RSSExpandedReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.clone().decode_with_hints(image, hints)
+    }
+}
+
 impl RSSExpandedReader {
     pub fn new() -> Self {
         Self::default()
@@ -1,18 +1,29 @@
+#[cfg(feature = "decoders")]
 pub mod expanded;
 
+#[cfg(feature = "decoders")]
 mod finder_pattern;
+#[cfg(feature = "decoders")]
 pub use finder_pattern::*;
 
+#[cfg(feature = "decoders")]
 mod pair;
+#[cfg(feature = "decoders")]
 pub use pair::*;
 
+#[cfg(feature = "decoders")]
 mod data_character;
+#[cfg(feature = "decoders")]
 pub use data_character::*;
 
 pub mod rss_utils;
 
+#[cfg(feature = "decoders")]
 mod abstract_rss_reader;
+#[cfg(feature = "decoders")]
 pub use abstract_rss_reader::*;
 
+#[cfg(feature = "decoders")]
 mod rss_14_reader;
+#[cfg(feature = "decoders")]
 pub use rss_14_reader::*;
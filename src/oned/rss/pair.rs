@@ -17,7 +17,7 @@ use super::{DataCharacter, DataCharacterTrait, FinderPattern};
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-#[derive(Hash, Eq, PartialEq)]
+#[derive(Hash, Eq, PartialEq, Clone)]
 pub struct Pair {
     finderPattern: FinderPattern,
     count: u32,
@@ -15,8 +15,8 @@
  */
 
 use crate::{
-    BarcodeFormat, Binarizer, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
-    RXingResultMetadataValue, Reader,
+    BarcodeFormat, Binarizer, DecodeHints, Exceptions, ImmutableReader, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
     common::{BitArray, Result},
     oned::{OneDReader, one_d_reader},
     point,
@@ -29,7 +29,7 @@ use super::{
 /**
  * Decodes RSS-14, including truncated and stacked variants. See ISO/IEC 24724:2006.
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct RSS14Reader {
     possibleLeftPairs: Vec<Pair>,
     possibleRightPairs: Vec<Pair>,
@@ -125,6 +125,16 @@ impl Reader for RSS14Reader {
     }
 }
 
+impl ImmutableReader for RSS14Reader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.clone().decode_with_hints(image, hints)
+    }
+}
+
 impl RSS14Reader {
     const OUTSIDE_EVEN_TOTAL_SUBSET: [u32; 5] = [1, 10, 34, 70, 126];
     const INSIDE_ODD_TOTAL_SUBSET: [u32; 4] = [4, 20, 48, 81];
@@ -177,7 +187,12 @@ impl RSS14Reader {
     }
 
     fn constructRXingResult(&self, leftPair: &Pair, rightPair: &Pair) -> Option<RXingResult> {
-        let symbolValue: u64 = 4537077 * leftPair.getValue() as u64 + rightPair.getValue() as u64;
+        let mut symbolValue: u64 =
+            4537077 * leftPair.getValue() as u64 + rightPair.getValue() as u64;
+        // Symbols linked to a composite component carry 10^13 on top of their value
+        if symbolValue >= 10_000_000_000_000 {
+            symbolValue -= 10_000_000_000_000;
+        }
         let text = symbolValue.to_string();
 
         let mut buffer = String::with_capacity(14);
@@ -55,6 +55,55 @@ pub fn getRSSvalue<const S: usize>(widths: &[u32; S], maxWidth: u32, noNarrow: b
     val
 }
 
+/* The inverse of getRSSvalue, getRSSwidths in ISO/IEC 24724 Appendix B. */
+pub fn getRSSwidths<const S: usize>(
+    mut val: u32,
+    mut n: u32,
+    maxWidth: u32,
+    noNarrow: bool,
+) -> [u32; S] {
+    let elements = S as u32;
+    let mut widths = [0; S];
+
+    let mut narrowMask = 0;
+    for bar in 0..(elements - 1) {
+        let mut elmWidth = 1;
+        narrowMask |= 1 << bar;
+        loop {
+            let mut subVal = combins_pre(n - elmWidth - 1, elements - bar - 2);
+            if noNarrow
+                && (narrowMask == 0)
+                && (n - elmWidth - (elements - bar - 1) >= elements - bar - 1)
+            {
+                subVal -= combins_pre(n - elmWidth - (elements - bar), elements - bar - 2);
+            }
+            if elements - bar - 1 > 1 {
+                let mut lessVal = 0;
+                let mut mxwElement = n - elmWidth - (elements - bar - 2);
+                while mxwElement > maxWidth {
+                    lessVal += combins_pre(n - elmWidth - mxwElement - 1, elements - bar - 3);
+
+                    mxwElement -= 1;
+                }
+                subVal -= lessVal * (elements - 1 - bar);
+            } else if n - elmWidth > maxWidth {
+                subVal -= 1;
+            }
+            if val < subVal {
+                break;
+            }
+            val -= subVal;
+
+            elmWidth += 1;
+            narrowMask &= !(1 << bar)
+        }
+        n -= elmWidth;
+        widths[bar as usize] = elmWidth;
+    }
+    widths[S - 1] = n;
+    widths
+}
+
 #[inline(always)]
 const fn combins(n: u32, r: u32) -> u32 {
     if n as usize <= N_MAX && r as usize <= R_MAX {
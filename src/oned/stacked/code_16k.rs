@@ -0,0 +1,591 @@
+//! Code 16K, EN 12323.
+//!
+//! Each row holds five Code 128 symbol characters between a start and a stop pattern,
+//! which together give the row number. The first character holds the row count and the
+//! initial code set, and the last two are the symbol check characters.
+
+#[cfg(feature = "decoders")]
+use std::collections::HashMap;
+
+use crate::{Exceptions, common::Result};
+
+#[cfg(feature = "encoders")]
+use crate::oned::oned_constants::code_128::CODE_PATTERNS;
+
+#[cfg(feature = "decoders")]
+use super::row_sampler::{ScannedRow, code_128_value, to_modules};
+
+/// Start and stop patterns, tables 3 and 4
+const START_STOP: [[u32; 4]; 8] = [
+    [3, 2, 1, 1],
+    [2, 2, 2, 1],
+    [2, 1, 2, 2],
+    [1, 4, 1, 1],
+    [1, 1, 3, 2],
+    [1, 2, 3, 1],
+    [1, 1, 1, 4],
+    [3, 1, 1, 2],
+];
+
+/// Stop pattern of each row, table 5. The start pattern is the row number modulo 8.
+const STOP_PATTERNS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 4, 5, 6, 7, 0, 1, 2, 3];
+
+/// Fewest and most rows of a symbol
+pub const MIN_ROWS: usize = 2;
+pub const MAX_ROWS: usize = 16;
+
+/// Width of a row in modules
+pub const ROW_WIDTH: u32 = 70;
+
+const SHIFT: u32 = 98;
+const CODE_C: u32 = 99;
+const CODE_B: u32 = 100;
+const CODE_A: u32 = 101;
+const FNC1: u32 = 102;
+const PAD: u32 = 103;
+
+/// FNC1 among the Latin-1 characters being encoded
+#[cfg(feature = "encoders")]
+const FNC1_CHARACTER: u16 = 0x100;
+
+// Code sets of the characters, as chosen by the rules of ISO/IEC 15417 annex E
+#[cfg(feature = "encoders")]
+const LATCH_A: u8 = b'A';
+#[cfg(feature = "encoders")]
+const LATCH_B: u8 = b'B';
+#[cfg(feature = "encoders")]
+const LATCH_C: u8 = b'C';
+#[cfg(feature = "encoders")]
+const SHIFT_A: u8 = b'a';
+#[cfg(feature = "encoders")]
+const SHIFT_B: u8 = b'b';
+#[cfg(feature = "encoders")]
+const A_B_OR_C: u8 = b'9';
+#[cfg(feature = "encoders")]
+const A_OR_B: u8 = b'Z';
+
+#[cfg(feature = "encoders")]
+fn character_mode(c: u16) -> u8 {
+    match c {
+        FNC1_CHARACTER => A_B_OR_C,
+        0..=31 => SHIFT_A,
+        48..=57 => A_B_OR_C,
+        32..=95 => A_OR_B,
+        96..=127 => SHIFT_B,
+        128..=159 => SHIFT_A,
+        160..=223 => A_OR_B,
+        _ => SHIFT_B,
+    }
+}
+
+/// Merges neighbouring blocks of the same mode
+#[cfg(feature = "encoders")]
+fn merge_blocks(blocks: &mut Vec<(usize, u8)>) {
+    blocks.dedup_by(|next, block| {
+        if next.1 == block.1 {
+            block.0 += next.0;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Chooses the code set of each block, ISO/IEC 15417 annex E
+#[cfg(feature = "encoders")]
+fn smooth_blocks(blocks: &mut Vec<(usize, u8)>) {
+    let count = blocks.len();
+    let mut next_shift = 0;
+    let mut next_shift_index = 0;
+    for i in 0..count {
+        let (length, mut current) = blocks[i];
+        if i == next_shift_index {
+            next_shift = 0;
+            // The next shift tells whether latching to A or B is better
+            if let Some(j) = (i + 1..count).find(|&j| matches!(blocks[j].1, SHIFT_A | SHIFT_B)) {
+                next_shift = blocks[j].1;
+                next_shift_index = j;
+            }
+        }
+
+        if i == 0 {
+            if current == A_B_OR_C {
+                if (count == 1 && length == 2) || length >= 4 {
+                    blocks[i].1 = LATCH_C;
+                    current = LATCH_C;
+                } else {
+                    current = A_OR_B;
+                }
+            }
+            match current {
+                A_OR_B if next_shift == SHIFT_A => blocks[i].1 = LATCH_A,
+                A_OR_B => blocks[i].1 = LATCH_B,
+                SHIFT_A => blocks[i].1 = LATCH_A,
+                SHIFT_B => blocks[i].1 = LATCH_B,
+                _ => {}
+            }
+        } else {
+            let last = blocks[i - 1].1;
+            if current == A_B_OR_C {
+                if length >= 4 {
+                    blocks[i].1 = LATCH_C;
+                    current = LATCH_C;
+                } else {
+                    current = A_OR_B;
+                }
+            }
+            match current {
+                A_OR_B => {
+                    blocks[i].1 = if last == LATCH_A || last == SHIFT_B {
+                        LATCH_A
+                    } else if last == LATCH_B || last == SHIFT_A || next_shift != SHIFT_A {
+                        LATCH_B
+                    } else {
+                        LATCH_A
+                    }
+                }
+                SHIFT_A if length > 1 || matches!(last, LATCH_A | SHIFT_B | LATCH_C) => {
+                    blocks[i].1 = LATCH_A
+                }
+                SHIFT_B if length > 1 || matches!(last, LATCH_B | SHIFT_A | LATCH_C) => {
+                    blocks[i].1 = LATCH_B
+                }
+                _ => {}
+            }
+        }
+    }
+    merge_blocks(blocks);
+}
+
+/// Code set of every character, making sure runs in code set C have an even number of
+/// digits
+#[cfg(feature = "encoders")]
+fn character_sets(blocks: &[(usize, u8)], source: &[u16]) -> Vec<u8> {
+    let mut sets: Vec<u8> = blocks
+        .iter()
+        .flat_map(|&(length, mode)| std::iter::repeat_n(mode, length))
+        .collect();
+    let length = sets.len();
+
+    let mut c_count = 0;
+    let mut have_non_c = false;
+    for i in 0..length {
+        if sets[i] == LATCH_C && source[i] != FNC1_CHARACTER {
+            c_count += 1;
+            continue;
+        }
+        if sets[i] != LATCH_C {
+            have_non_c = true;
+        }
+        if c_count & 1 == 1 {
+            have_non_c = true;
+            if i > c_count {
+                sets[i - c_count] = LATCH_B;
+            } else {
+                sets[i - 1] = LATCH_B;
+            }
+        }
+        c_count = 0;
+    }
+    if c_count & 1 == 1 {
+        if length > c_count && have_non_c {
+            sets[length - c_count] = LATCH_B;
+            if c_count < 4 {
+                for set in &mut sets[length - c_count + 1..] {
+                    *set = LATCH_B;
+                }
+            }
+        } else {
+            sets[length - 1] = LATCH_B;
+        }
+    }
+    for i in 1..length.saturating_sub(1) {
+        if sets[i] == LATCH_C && sets[i - 1] != LATCH_C && sets[i + 1] != LATCH_C {
+            sets[i] = sets[i + 1];
+        }
+    }
+    if length > 1 && sets[length - 1] == LATCH_C && sets[length - 2] != LATCH_C {
+        sets[length - 1] = sets[length - 2];
+    }
+    sets
+}
+
+/// Value of a character in code set A, the extended ones following FNC4
+#[cfg(feature = "encoders")]
+fn value_a(c: u16) -> u32 {
+    let c = c as u32 & 0x7F;
+    if c < 32 { c + 64 } else { c - 32 }
+}
+
+/// Value of a character in code set B, the extended ones following FNC4
+#[cfg(feature = "encoders")]
+fn value_b(c: u16) -> Result<u32> {
+    let c = c as u32 & 0x7F;
+    if c < 32 {
+        return Err(Exceptions::illegal_argument_with(
+            "control characters are not in code set B",
+        ));
+    }
+    Ok(c - 32)
+}
+
+/// Encodes Latin-1 contents, [`super::ESCAPE_FNC_1`] standing for FNC1, into the
+/// symbol characters of the rows, check characters included
+#[cfg(feature = "encoders")]
+pub fn encode_codewords(contents: &str) -> Result<Vec<u32>> {
+    let mut source = contents
+        .chars()
+        .map(|c| match c {
+            super::ESCAPE_FNC_1 => Ok(FNC1_CHARACTER),
+            '\0'..='\u{ff}' => Ok(c as u16),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "Code 16K cannot encode '{c}'"
+            ))),
+        })
+        .collect::<Result<Vec<u16>>>()?;
+    // A leading FNC1 is given by the mode when possible
+    let gs1 = source.first() == Some(&FNC1_CHARACTER);
+    if gs1 {
+        source.remove(0);
+    }
+    if source.is_empty() {
+        return Err(Exceptions::illegal_argument_with("Found empty contents"));
+    }
+
+    let mut blocks: Vec<(usize, u8)> = Vec::new();
+    for &c in &source {
+        let mode = character_mode(c);
+        match blocks.last_mut() {
+            Some(block) if block.1 == mode => block.0 += 1,
+            _ => blocks.push((1, mode)),
+        }
+    }
+    smooth_blocks(&mut blocks);
+    let sets = character_sets(&blocks, &source);
+    let extended: Vec<bool> = source
+        .iter()
+        .map(|&c| c >= 128 && c != FNC1_CHARACTER)
+        .collect();
+
+    let set_at = |i: usize| sets.get(i).copied().unwrap_or_default();
+    let mut mode = (sets[0] - b'A') as u32;
+    if gs1 {
+        match sets[0] {
+            LATCH_B => mode = 3,
+            LATCH_C => mode = 4,
+            _ => {}
+        }
+    } else if sets[0] == LATCH_B && set_at(1) == LATCH_C {
+        mode = if extended[0] { 6 } else { 5 };
+    } else if sets[0] == LATCH_B
+        && set_at(1) == LATCH_B
+        && set_at(2) == LATCH_C
+        && !extended[0]
+        && !extended[1]
+    {
+        mode = 6;
+    }
+
+    let mut values = vec![0];
+    if gs1 && mode < 3 {
+        values.push(FNC1);
+    }
+    let mut current_set = sets[0];
+    let mut read = 0;
+    while read < source.len() {
+        if read != 0 && sets[read] != current_set {
+            match sets[read] {
+                LATCH_A => {
+                    values.push(CODE_A);
+                    current_set = LATCH_A;
+                }
+                LATCH_B => {
+                    values.push(CODE_B);
+                    current_set = LATCH_B;
+                }
+                LATCH_C => {
+                    // Modes 5 and 6 switch to code set C by themselves
+                    if !((read == 1 && mode >= 5) || (read == 2 && mode == 6)) {
+                        values.push(CODE_C);
+                    }
+                    current_set = LATCH_C;
+                }
+                _ => {}
+            }
+        }
+
+        if extended[read] {
+            // FNC4
+            match current_set {
+                LATCH_A => values.push(CODE_A),
+                LATCH_B => values.push(CODE_B),
+                _ => {}
+            }
+        }
+        if matches!(sets[read], SHIFT_A | SHIFT_B) {
+            values.push(SHIFT);
+        }
+
+        if source[read] == FNC1_CHARACTER {
+            values.push(FNC1);
+            read += 1;
+        } else {
+            match sets[read] {
+                LATCH_A | SHIFT_A => values.push(value_a(source[read])),
+                LATCH_B | SHIFT_B => values.push(value_b(source[read])?),
+                _ => {
+                    let digits = source
+                        .get(read..read + 2)
+                        .filter(|pair| {
+                            pair.iter()
+                                .all(|&d| (b'0' as u16..=b'9' as u16).contains(&d))
+                        })
+                        .ok_or(Exceptions::ILLEGAL_STATE)?;
+                    values.push(
+                        10 * (digits[0] as u32 - b'0' as u32) + digits[1] as u32 - b'0' as u32,
+                    );
+                    read += 1;
+                }
+            }
+            read += 1;
+        }
+
+        if values.len() > MAX_ROWS * 5 - 2 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Contents need more than the {} Code 16K characters that fit",
+                MAX_ROWS * 5 - 2
+            )));
+        }
+    }
+
+    let mut pads = (5 - (values.len() + 2) % 5) % 5;
+    if values.len() + pads < 8 {
+        pads = 8 - values.len();
+    }
+    let rows = (values.len() + pads + 2) / 5;
+    values.extend(std::iter::repeat_n(PAD, pads));
+    values[0] = 7 * (rows as u32 - 2) + mode;
+    let (first, second) = check_characters(&values);
+    values.push(first);
+    values.push(second);
+    Ok(values)
+}
+
+/// Encodes Latin-1 contents, [`super::ESCAPE_FNC_1`] standing for FNC1, into the modules
+/// of each row
+#[cfg(feature = "encoders")]
+pub fn encode(contents: &str) -> Result<Vec<Vec<bool>>> {
+    let values = encode_codewords(contents)?;
+    Ok(values
+        .chunks(5)
+        .enumerate()
+        .map(|(row, characters)| {
+            let mut widths: Vec<u32> = START_STOP[row % 8].to_vec();
+            // Separator bar between the start pattern and the first character
+            widths.push(1);
+            for &value in characters {
+                widths.extend_from_slice(CODE_PATTERNS[value as usize]);
+            }
+            widths.extend_from_slice(&START_STOP[STOP_PATTERNS[row]]);
+            widths
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &width)| std::iter::repeat_n(i % 2 == 0, width as usize))
+                .collect()
+        })
+        .collect())
+}
+
+/// The two symbol check characters of the values before them
+fn check_characters(values: &[u32]) -> (u32, u32) {
+    let mut first_sum = 0;
+    let mut second_sum = 0;
+    for (i, &value) in values.iter().enumerate() {
+        first_sum += (i as u32 + 2) * value;
+        second_sum += (i as u32 + 1) * value;
+    }
+    let first = first_sum % 107;
+    let second = (second_sum + first * (values.len() as u32 + 1)) % 107;
+    (first, second)
+}
+
+/// Reads a row from `runs`, which start with its first bar, if it has a quiet zone of at
+/// least two modules before it
+#[cfg(feature = "decoders")]
+pub fn read_row(runs: &[u32], quiet_zone: u32) -> Option<ScannedRow> {
+    let row = runs.get(..39)?;
+    let width: u32 = row.iter().sum();
+    if quiet_zone * ROW_WIDTH < 2 * width {
+        return None;
+    }
+    let modules = to_modules(row, ROW_WIDTH, 4)?;
+    let start = START_STOP.iter().position(|p| p[..] == modules[..4])?;
+    let stop = START_STOP.iter().position(|p| p[..] == modules[35..])?;
+    if modules[4] != 1 {
+        return None;
+    }
+    let number = (0..MAX_ROWS).find(|&r| r % 8 == start && STOP_PATTERNS[r] == stop)?;
+    let codewords = row[5..35]
+        .chunks(6)
+        .map(code_128_value)
+        .collect::<Option<Vec<u32>>>()?;
+
+    Some(ScannedRow {
+        number,
+        codewords,
+        runs: 39,
+    })
+}
+
+/// Decodes the message from the characters of the rows read, keyed by row number. Returns
+/// the text and the symbology identifier modifier.
+#[cfg(feature = "decoders")]
+pub fn decode(rows: &HashMap<usize, Vec<u32>>, convert_fnc1: bool) -> Result<(String, u32)> {
+    let first_row = rows.get(&0).ok_or(Exceptions::NOT_FOUND)?;
+    let row_count = first_row[0] as usize / 7 + 2;
+    let mode = first_row[0] % 7;
+    if row_count > MAX_ROWS {
+        return Err(Exceptions::FORMAT);
+    }
+    let mut values = Vec::with_capacity(row_count * 5);
+    for r in 0..row_count {
+        values.extend_from_slice(rows.get(&r).ok_or(Exceptions::NOT_FOUND)?);
+    }
+    let data_length = values.len() - 2;
+    if check_characters(&values[..data_length]) != (values[data_length], values[data_length + 1]) {
+        return Err(Exceptions::checksum_with(
+            "Code 16K check characters failed",
+        ));
+    }
+
+    let mut text = String::new();
+    let mut symbology_modifier = 0;
+    let fnc1 = |text: &mut String, symbology_modifier: &mut u32| {
+        if text.is_empty() {
+            // FNC1 at first or second character determines the symbology
+            *symbology_modifier = 1;
+            if convert_fnc1 {
+                text.push_str("]K1");
+            }
+        } else {
+            if text.chars().count() == 1 {
+                *symbology_modifier = 2;
+            }
+            if convert_fnc1 {
+                text.push(29 as char);
+            }
+        }
+    };
+
+    let mut set = match mode {
+        0 => CODE_A,
+        1 | 3 | 5 | 6 => CODE_B,
+        _ => CODE_C,
+    };
+    if mode == 3 || mode == 4 {
+        fnc1(&mut text, &mut symbology_modifier);
+    }
+    // Modes 5 and 6 start in code set B for one or two characters, then switch to C
+    let mut until_code_c = match mode {
+        5 => 1,
+        6 => 2,
+        _ => 0,
+    };
+    let mut shift = false;
+    let mut extended = false;
+    for &value in &values[1..data_length] {
+        let current = match (shift, set) {
+            (true, CODE_A) => CODE_B,
+            (true, _) => CODE_A,
+            (false, set) => set,
+        };
+        let was_shifted = shift;
+        shift = false;
+        match value {
+            0..=95 if current != CODE_C => {
+                let c = match current {
+                    CODE_A if value >= 64 => value - 64,
+                    _ => value + 32,
+                };
+                let c = if extended { c + 128 } else { c };
+                extended = false;
+                text.push(char::from(c as u8));
+            }
+            0..=99 if current == CODE_C => text.push_str(&format!("{value:02}")),
+            _ if was_shifted => return Err(Exceptions::FORMAT),
+            96 | 97 => {}
+            SHIFT if current != CODE_C => {
+                shift = true;
+            }
+            CODE_C => set = CODE_C,
+            CODE_B if current == CODE_B => extended = true,
+            CODE_B => set = CODE_B,
+            CODE_A if current == CODE_A => extended = true,
+            CODE_A => set = CODE_A,
+            FNC1 => fnc1(&mut text, &mut symbology_modifier),
+            PAD => {}
+            _ => return Err(Exceptions::FORMAT),
+        }
+        if until_code_c > 0 && !shift {
+            until_code_c -= 1;
+            if until_code_c == 0 {
+                set = CODE_C;
+            }
+        }
+    }
+    Ok((text, symbology_modifier))
+}
+
+#[cfg(all(test, feature = "encoders"))]
+mod tests {
+    use super::*;
+
+    fn to_strings(rows: &[Vec<bool>]) -> Vec<String> {
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&dark| if dark { '1' } else { '0' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn code_set_b_and_c() {
+        // Reference rows generated with zint
+        assert_eq!(
+            to_strings(&encode("AB1234").unwrap()),
+            [
+                "1110010101100110111010111001110111010011101001100011011101001110001101",
+                "1100110100101111011001011110110010111101101110100001011000010110011001",
+            ]
+        );
+    }
+
+    #[test]
+    fn extended_and_control_characters() {
+        // Reference rows generated with zint
+        assert_eq!(
+            to_strings(&encode("Ab12345678cd\x7f\t\u{e9}").unwrap()),
+            [
+                "1110010100110110001010111001110110111100101001100011011101001110001101",
+                "1100110100011101001001111010110100001000101111010011011110110010011001",
+                "1101100101000010111000010111010111100101101000010001011110010110010011",
+                "1000010100101111011001011110110010111101101000100111010001110010111101",
+            ]
+        );
+    }
+
+    #[test]
+    fn gs1() {
+        // Reference rows generated with zint
+        assert_eq!(
+            to_strings(&encode("\u{f1}0112345678901231").unwrap()),
+            [
+                "1110010100111011011001100100110100110001101110100111000111010010001101",
+                "1100110100111101011001000010010100110001100100111001001011110110011001",
+                "1101100100101111011001011110110010111101100100100111011101001110010011",
+            ]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Stacked one dimensional barcodes.
+//!
+//! Code 16K, Code 49 and Codablock F stack several short linear rows on top of each other,
+//! separated by bars. Each row carries its own start and stop patterns and a row indicator,
+//! so the rows can be read from separate scan lines and put back in order before the
+//! message is decoded across all of them.
+
+pub mod codablock_f;
+pub mod code_16k;
+pub mod code_49;
+
+/// Stands for FNC1 in the contents given to the writers
+#[cfg(feature = "encoders")]
+pub const ESCAPE_FNC_1: char = '\u{00f1}';
+
+#[cfg(feature = "decoders")]
+pub mod row_sampler;
+
+#[cfg(feature = "decoders")]
+mod stacked_reader;
+#[cfg(feature = "decoders")]
+pub use stacked_reader::*;
+
+#[cfg(feature = "encoders")]
+mod stacked_writer;
+#[cfg(feature = "encoders")]
+pub use stacked_writer::*;
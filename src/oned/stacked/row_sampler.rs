@@ -0,0 +1,81 @@
+//! Samples the rows of stacked barcodes.
+//!
+//! Every scan line is turned into the widths of its alternating light and dark runs. The
+//! symbologies then try to read a row starting at each dark run, rounding the run widths
+//! to modules over a whole row or symbol character so that slightly skewed or stretched
+//! rows still decode.
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+
+use crate::{common::BitMatrix, oned::oned_constants::code_128::CODE_PATTERNS};
+
+/// A row of a stacked symbol read along one scan line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScannedRow {
+    /// Row number, as given by the row indicators
+    pub number: usize,
+    /// Symbol characters of the row, without its start, stop and row check characters
+    pub codewords: Vec<u32>,
+    /// Number of runs the row spans, from its first bar to its last
+    pub runs: usize,
+}
+
+/// Widths of the runs along row `y`, alternately light and dark. The first and last runs
+/// are light, and empty if the row starts or ends dark.
+pub fn row_runs(image: &BitMatrix, y: u32) -> Vec<u32> {
+    let mut runs = Vec::new();
+    let mut dark = false;
+    let mut width = 0;
+    for x in 0..image.getWidth() {
+        if image.get(x, y) != dark {
+            runs.push(width);
+            width = 0;
+            dark = !dark;
+        }
+        width += 1;
+    }
+    runs.push(width);
+    if dark {
+        runs.push(0);
+    }
+    runs
+}
+
+/// Shares `modules` modules between `runs` by rounding the position of each edge, or
+/// `None` if a run gets no module or more than `max` modules
+pub fn to_modules(runs: &[u32], modules: u32, max: u32) -> Option<Vec<u32>> {
+    let width: u64 = runs.iter().map(|&run| run as u64).sum();
+    if width < modules as u64 {
+        return None;
+    }
+    let mut result = Vec::with_capacity(runs.len());
+    let mut edge = 0;
+    let mut previous = 0;
+    for &run in runs {
+        edge += run as u64;
+        let rounded = ((2 * edge * modules as u64 + width) / (2 * width)) as u32;
+        let run_modules = rounded - previous;
+        if run_modules == 0 || run_modules > max {
+            return None;
+        }
+        result.push(run_modules);
+        previous = rounded;
+    }
+    Some(result)
+}
+
+static CODE_128_VALUES: Lazy<HashMap<&'static [u32], u32>> = Lazy::new(|| {
+    CODE_PATTERNS[..106]
+        .iter()
+        .enumerate()
+        .map(|(value, &pattern)| (pattern, value as u32))
+        .collect()
+});
+
+/// Value of the Code 128 symbol character, stop excepted, spanning the six `runs`
+pub fn code_128_value(runs: &[u32]) -> Option<u32> {
+    let modules = to_modules(runs, 11, 4)?;
+    CODE_128_VALUES.get(modules.as_slice()).copied()
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, Exceptions, ImmutableReader, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    common::{BitMatrix, Result},
+    point,
+};
+
+use super::{
+    codablock_f, code_16k, code_49,
+    row_sampler::{ScannedRow, row_runs},
+};
+
+const FORMATS: [BarcodeFormat; 3] = [
+    BarcodeFormat::CODE_16K,
+    BarcodeFormat::CODE_49,
+    BarcodeFormat::CODABLOCK_F,
+];
+
+/// Reads Code 16K, Code 49 and Codablock F barcodes.
+///
+/// Every scan line of the image is searched for rows, upright and upside down. The symbol
+/// characters read most often for each row number are kept, so a few misread lines do not
+/// spoil the symbol, and the message is decoded once all of its rows have been seen.
+#[derive(Default, Clone)]
+pub struct StackedReader;
+
+impl Reader for StackedReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+impl ImmutableReader for StackedReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        self.internal_decode_with_hints(image, hints)
+    }
+}
+
+/// Rows of one symbol seen so far, and where they were seen
+#[derive(Default)]
+struct Candidate {
+    /// How often each reading of each row number was seen
+    rows: HashMap<usize, HashMap<Vec<u32>, usize>>,
+    left: u32,
+    top: u32,
+    right: u32,
+    bottom: u32,
+}
+
+impl Candidate {
+    fn add(&mut self, row: ScannedRow, left: u32, right: u32, y: u32) {
+        if self.rows.is_empty() {
+            (self.left, self.top, self.right) = (left, y, right);
+        }
+        self.left = self.left.min(left);
+        self.right = self.right.max(right);
+        self.top = self.top.min(y);
+        self.bottom = self.bottom.max(y);
+        *self
+            .rows
+            .entry(row.number)
+            .or_default()
+            .entry(row.codewords)
+            .or_default() += 1;
+    }
+
+    /// The reading of each row seen most often
+    fn best_rows(&self) -> HashMap<usize, Vec<u32>> {
+        self.rows
+            .iter()
+            .filter_map(|(&number, readings)| {
+                readings
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(codewords, _)| (number, codewords.clone()))
+            })
+            .collect()
+    }
+}
+
+impl StackedReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let formats: Vec<BarcodeFormat> = FORMATS
+            .into_iter()
+            .filter(|f| {
+                hints
+                    .PossibleFormats
+                    .as_ref()
+                    .is_none_or(|possible| possible.contains(f))
+            })
+            .collect();
+        if formats.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Self::decode_matrix(image.get_black_matrix(), &formats, hints)
+    }
+
+    /// Looks for Code 16K, Code 49 or Codablock F barcodes, as listed in `formats`, in a
+    /// binarized image
+    pub fn decode_matrix(
+        matrix: &BitMatrix,
+        formats: &[BarcodeFormat],
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let convert_fnc1 = hints.AssumeGs1.unwrap_or(false);
+        let width = matrix.getWidth();
+
+        // Candidates by format, upright and upside down
+        let mut candidates: HashMap<(BarcodeFormat, bool), Candidate> = HashMap::new();
+        for y in 0..matrix.getHeight() {
+            let runs = row_runs(matrix, y);
+            for reversed in [false, true] {
+                let runs = if reversed {
+                    runs.iter().rev().copied().collect()
+                } else {
+                    runs.clone()
+                };
+                let mut i = 1;
+                let mut x = runs[0];
+                while i < runs.len() {
+                    let found = formats.iter().find_map(|&format| {
+                        read_row(format, &runs[i..], runs[i - 1]).map(|row| (format, row))
+                    });
+                    let Some((format, row)) = found else {
+                        x += runs[i] + runs[i + 1];
+                        i += 2;
+                        continue;
+                    };
+                    let spanned = row.runs;
+                    let row_width: u32 = runs[i..i + spanned].iter().sum();
+                    let (left, right) = if reversed {
+                        (width - x - row_width, width - x)
+                    } else {
+                        (x, x + row_width)
+                    };
+                    candidates
+                        .entry((format, reversed))
+                        .or_default()
+                        .add(row, left, right, y);
+                    // Carry on from the dark run after the light one ending the row
+                    x += row_width + runs.get(i + spanned).copied().unwrap_or_default();
+                    i += spanned + 1;
+                }
+            }
+        }
+
+        let mut last_error = Exceptions::NOT_FOUND;
+        for &format in formats {
+            for reversed in [false, true] {
+                let Some(candidate) = candidates.get(&(format, reversed)) else {
+                    continue;
+                };
+                match decode_rows(format, &candidate.best_rows(), convert_fnc1) {
+                    Ok((text, raw_bytes, identifier)) => {
+                        let mut points = vec![
+                            point(candidate.left as f32, candidate.top as f32),
+                            point(candidate.right as f32, candidate.bottom as f32),
+                        ];
+                        if reversed {
+                            points.reverse();
+                        }
+                        let mut result = RXingResult::new(&text, raw_bytes, points, format);
+                        result.putMetadata(
+                            RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+                            RXingResultMetadataValue::SymbologyIdentifier(identifier),
+                        );
+                        return Ok(result);
+                    }
+                    Err(Exceptions::NotFoundException(_)) => {}
+                    Err(e) => last_error = e,
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+fn read_row(format: BarcodeFormat, runs: &[u32], quiet_zone: u32) -> Option<ScannedRow> {
+    match format {
+        BarcodeFormat::CODE_16K => code_16k::read_row(runs, quiet_zone),
+        BarcodeFormat::CODE_49 => code_49::read_row(runs, quiet_zone),
+        BarcodeFormat::CODABLOCK_F => codablock_f::read_row(runs, quiet_zone),
+        _ => None,
+    }
+}
+
+/// Decodes the rows of a symbol, returning its text, the symbol characters of all its rows
+/// in order and its symbology identifier
+fn decode_rows(
+    format: BarcodeFormat,
+    rows: &HashMap<usize, Vec<u32>>,
+    convert_fnc1: bool,
+) -> Result<(String, Vec<u8>, String)> {
+    let ((text, modifier), identifier) = match format {
+        BarcodeFormat::CODE_16K => (code_16k::decode(rows, convert_fnc1)?, 'K'),
+        BarcodeFormat::CODE_49 => (code_49::decode(rows, convert_fnc1)?, 'T'),
+        BarcodeFormat::CODABLOCK_F => (codablock_f::decode(rows, convert_fnc1)?, 'O'),
+        _ => return Err(Exceptions::NOT_FOUND),
+    };
+    let mut numbers: Vec<&usize> = rows.keys().collect();
+    numbers.sort_unstable();
+    let raw_bytes = numbers
+        .into_iter()
+        .flat_map(|number| rows[number].iter().map(|&codeword| codeword as u8))
+        .collect();
+    Ok((text, raw_bytes, format!("]{identifier}{modifier}")))
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, DecodeHints,
+        MultiFormatReader, MultiFormatWriter, RXingResultMetadataType, RXingResultMetadataValue,
+        Reader, Writer,
+        common::{BitMatrix, HybridBinarizer},
+        oned::oned_constants::code_128::CODE_PATTERNS,
+    };
+
+    use super::StackedReader;
+
+    fn encode(contents: &str, format: BarcodeFormat) -> BitMatrix {
+        MultiFormatWriter.encode(contents, &format, 0, 0).unwrap()
+    }
+
+    fn read(matrix: &BitMatrix, format: BarcodeFormat, hints: &DecodeHints) -> (String, String) {
+        let result = StackedReader::decode_matrix(matrix, &[format], hints).unwrap();
+        assert_eq!(result.getBarcodeFormat(), &format);
+        let Some(RXingResultMetadataValue::SymbologyIdentifier(identifier)) = result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER)
+        else {
+            panic!("no symbology identifier");
+        };
+        (result.getText().to_owned(), identifier.clone())
+    }
+
+    #[test]
+    fn code_16k() {
+        for contents in [
+            "AB1234",
+            "Ab12345678cd\x7f\t\u{e9}",
+            "Code 16K over many rows 12345678901234567890",
+        ] {
+            let matrix = encode(contents, BarcodeFormat::CODE_16K);
+            let (text, identifier) =
+                read(&matrix, BarcodeFormat::CODE_16K, &DecodeHints::default());
+            assert_eq!(text, contents);
+            assert_eq!(identifier, "]K0");
+        }
+    }
+
+    #[test]
+    fn code_49() {
+        for contents in [
+            "CODE 49",
+            "abc\tDEF",
+            "MULTIPLE ROWS OF CODE 49 1234567890abc",
+        ] {
+            let matrix = encode(contents, BarcodeFormat::CODE_49);
+            let (text, identifier) = read(&matrix, BarcodeFormat::CODE_49, &DecodeHints::default());
+            assert_eq!(text, contents);
+            assert_eq!(identifier, "]T0");
+        }
+    }
+
+    #[test]
+    fn codablock_f() {
+        for contents in [
+            "A",
+            "12A4",
+            "Codablock F \u{fc}ber 1234567890 12345678 lower case and more text",
+        ] {
+            let matrix = encode(contents, BarcodeFormat::CODABLOCK_F);
+            let (text, identifier) =
+                read(&matrix, BarcodeFormat::CODABLOCK_F, &DecodeHints::default());
+            assert_eq!(text, contents);
+            assert_eq!(identifier, "]O4");
+        }
+    }
+
+    #[test]
+    fn gs1() {
+        let hints = DecodeHints::default().with(DecodeHintValue::AssumeGs1(true));
+        for format in [BarcodeFormat::CODE_16K, BarcodeFormat::CODE_49] {
+            let matrix = encode("\u{f1}0112345678901231", format);
+            let (text, _) = read(&matrix, format, &hints);
+            let (plain, identifier) = read(&matrix, format, &DecodeHints::default());
+            assert_eq!(plain, "0112345678901231");
+            assert_eq!(&text[3..], plain);
+            assert_eq!(&text[..3], identifier);
+        }
+    }
+
+    #[test]
+    fn upside_down() {
+        let mut matrix = encode("UPSIDE DOWN 1234", BarcodeFormat::CODE_49);
+        matrix.rotate180();
+        let (text, _) = read(&matrix, BarcodeFormat::CODE_49, &DecodeHints::default());
+        assert_eq!(text, "UPSIDE DOWN 1234");
+    }
+
+    #[test]
+    fn codablock_f_is_not_read_as_code_128() {
+        let matrix = encode("Codablock F rows are Code 128", BarcodeFormat::CODABLOCK_F);
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        let result = MultiFormatReader::default().decode(&mut bitmap).unwrap();
+        assert_eq!(result.getText(), "Codablock F rows are Code 128");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::CODABLOCK_F);
+    }
+
+    #[test]
+    fn code_128_starting_like_codablock_f_row() {
+        // Start A, Code B, "ab": a lone Code 128 symbol beginning like a Codablock F row
+        let mut codewords = vec![103, 100, 65, 66];
+        let checksum = codewords
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| i.max(1) * c)
+            .sum::<usize>()
+            % 103;
+        codewords.extend([checksum, 106]);
+        let mut matrix = BitMatrix::new(200, 40).unwrap();
+        let mut x = 20;
+        for codeword in codewords {
+            for (i, &width) in CODE_PATTERNS[codeword].iter().enumerate() {
+                if i % 2 == 0 {
+                    matrix.setRegion(x, 0, 2 * width, 40).unwrap();
+                }
+                x += 2 * width;
+            }
+        }
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        let result = MultiFormatReader::default().decode(&mut bitmap).unwrap();
+        assert_eq!(result.getText(), "ab");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::CODE_128);
+    }
+
+    #[test]
+    fn honours_possible_formats() {
+        let matrix = encode("CODE 49", BarcodeFormat::CODE_49);
+        let image: image::DynamicImage = (&matrix).into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(HashSet::from([
+            BarcodeFormat::CODE_16K,
+        ])));
+        assert!(
+            StackedReader
+                .decode_with_hints(&mut bitmap, &hints)
+                .is_err()
+        );
+    }
+}
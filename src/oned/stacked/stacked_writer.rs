@@ -0,0 +1,174 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    common::{BitMatrix, Result},
+    oned::postal::check_arguments,
+};
+
+use super::{codablock_f, code_16k, code_49};
+
+/// Default quiet zone on each side of the rows, in modules
+const QUIET_ZONE: u32 = 10;
+
+/// Default height of a row, in modules
+const ROW_HEIGHT: u32 = 10;
+
+fn encode_stacked(
+    contents: &str,
+    format: &BarcodeFormat,
+    expected: BarcodeFormat,
+    width: i32,
+    height: i32,
+    hints: &EncodeHints,
+    encode: fn(&str) -> Result<Vec<Vec<bool>>>,
+) -> Result<BitMatrix> {
+    let margin = check_arguments(contents, format, expected, width, height, hints)?;
+    let margin = if hints.Margin.is_some() {
+        margin
+    } else {
+        QUIET_ZONE
+    };
+    render(&encode(contents)?, width, height, margin)
+}
+
+/// Renders the rows one above the other, with a separator bar one module high above,
+/// between and below them spanning the width of the rows. Rows are stretched to fill the
+/// requested height.
+fn render(rows: &[Vec<bool>], width: i32, height: i32, margin: u32) -> Result<BitMatrix> {
+    let code_width = rows
+        .first()
+        .map(|row| row.len() as u32)
+        .ok_or_else(|| Exceptions::illegal_argument_with("no rows to render"))?;
+    let row_count = rows.len() as u32;
+    let full_width = code_width + 2 * margin;
+    let output_width = (width.max(0) as u32).max(full_width);
+    let multiple = output_width / full_width;
+    let left = (output_width - code_width * multiple) / 2;
+
+    let separators = (row_count + 1) * multiple;
+    let output_height = (height.max(0) as u32).max(row_count * ROW_HEIGHT * multiple + separators);
+    let row_height = (output_height - separators) / row_count;
+    let top = (output_height - separators - row_height * row_count) / 2;
+
+    let mut output = BitMatrix::new(output_width, output_height)?;
+    let mut y = top;
+    for row in rows {
+        output.setRegion(left, y, code_width * multiple, multiple)?;
+        y += multiple;
+        for (x, _) in row.iter().enumerate().filter(|(_, dark)| **dark) {
+            output.setRegion(left + x as u32 * multiple, y, multiple, row_height)?;
+        }
+        y += row_height;
+    }
+    output.setRegion(left, y, code_width * multiple, multiple)?;
+    Ok(output)
+}
+
+macro_rules! stacked_writer {
+    ($(#[$doc:meta])* $name:ident, $format:expr, $encode:path) => {
+        $(#[$doc])*
+        #[derive(Default)]
+        pub struct $name;
+
+        impl Writer for $name {
+            fn encode(
+                &self,
+                contents: &str,
+                format: &BarcodeFormat,
+                width: i32,
+                height: i32,
+            ) -> Result<BitMatrix> {
+                self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+            }
+
+            fn encode_with_hints(
+                &self,
+                contents: &str,
+                format: &BarcodeFormat,
+                width: i32,
+                height: i32,
+                hints: &EncodeHints,
+            ) -> Result<BitMatrix> {
+                encode_stacked(contents, format, $format, width, height, hints, $encode)
+            }
+        }
+    };
+}
+
+stacked_writer!(
+    /// Renders a Code 16K barcode of 2 to 16 rows. The contents are Latin-1 text, with
+    /// [`super::ESCAPE_FNC_1`] standing for FNC1.
+    Code16KWriter,
+    BarcodeFormat::CODE_16K,
+    code_16k::encode
+);
+
+stacked_writer!(
+    /// Renders a Code 49 barcode of 2 to 8 rows. The contents are ASCII text, with
+    /// [`super::ESCAPE_FNC_1`] standing for FNC1.
+    Code49Writer,
+    BarcodeFormat::CODE_49,
+    code_49::encode
+);
+
+stacked_writer!(
+    /// Renders a Codablock F barcode of 2 to 44 rows, as close to square as the contents
+    /// allow. The contents are Latin-1 text.
+    CodablockFWriter,
+    BarcodeFormat::CODABLOCK_F,
+    codablock_f::encode
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, EncodeHintValue, EncodeHints, Writer};
+
+    use super::{Code16KWriter, Code49Writer};
+
+    #[test]
+    fn renders_rows_between_separators() {
+        let matrix = Code49Writer
+            .encode("CODE 49", &BarcodeFormat::CODE_49, 0, 0)
+            .unwrap();
+        // Two rows of 70 modules with a quiet zone of 10, each 10 modules high, and three
+        // separators
+        assert_eq!(matrix.getWidth(), 90);
+        assert_eq!(matrix.getHeight(), 23);
+        for y in [0, 11, 22] {
+            assert!(
+                !matrix.get(9, y) && matrix.get(10, y) && matrix.get(79, y) && !matrix.get(80, y)
+            );
+        }
+        // Each row starts with a narrow bar and space
+        for y in [1, 10, 12, 21] {
+            assert!(matrix.get(10, y) && !matrix.get(11, y));
+        }
+    }
+
+    #[test]
+    fn stretches_rows_to_the_requested_size() {
+        let matrix = Code16KWriter
+            .encode_with_hints(
+                "AB1234",
+                &BarcodeFormat::CODE_16K,
+                140,
+                100,
+                &EncodeHints::default().with(EncodeHintValue::Margin("0".to_owned())),
+            )
+            .unwrap();
+        assert_eq!(matrix.getWidth(), 140);
+        assert_eq!(matrix.getHeight(), 100);
+        // Modules are two pixels wide and the rows share what the separators leave, the
+        // middle separator covering lines 49 and 50
+        assert!(matrix.get(0, 0) && matrix.get(139, 0) && matrix.get(0, 99));
+        assert!(matrix.get(5, 25) && !matrix.get(6, 25) && matrix.get(80, 50));
+    }
+
+    #[test]
+    fn rejects_other_formats() {
+        assert!(
+            Code49Writer
+                .encode("CODE 49", &BarcodeFormat::CODE_128, 0, 0)
+                .is_err()
+        );
+    }
+}
@@ -33,7 +33,7 @@ use super::OneDReader;
  *
  * @author Chris Wood
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct TelepenReader {
     // Keep some instance variables to avoid reallocations
     counters: Box<[u32]>,
@@ -0,0 +1,207 @@
+//! Shared tables and logic of the non interleaved 2 of 5 symbologies.
+//!
+//! Industrial and IATA 2 of 5 carry each digit in five bars, two of them wide, separated by
+//! narrow spaces. Matrix 2 of 5 uses three bars and the two spaces between them instead, plus a
+//! narrow gap after every character. Wide elements are three times as wide as narrow ones.
+
+#[cfg(feature = "decoders")]
+use crate::{DecodeHints, Exceptions, common::Result};
+
+#[cfg(feature = "decoders")]
+use super::one_d_reader;
+
+/// Element widths of each digit with five bars and narrow spaces
+const BAR_PATTERNS: [[u32; 10]; 10] = [
+    [1, 1, 1, 1, 3, 1, 3, 1, 1, 1], // 0
+    [3, 1, 1, 1, 1, 1, 1, 1, 3, 1], // 1
+    [1, 1, 3, 1, 1, 1, 1, 1, 3, 1], // 2
+    [3, 1, 3, 1, 1, 1, 1, 1, 1, 1], // 3
+    [1, 1, 1, 1, 3, 1, 1, 1, 3, 1], // 4
+    [3, 1, 1, 1, 3, 1, 1, 1, 1, 1], // 5
+    [1, 1, 3, 1, 3, 1, 1, 1, 1, 1], // 6
+    [1, 1, 1, 1, 1, 1, 3, 1, 3, 1], // 7
+    [3, 1, 1, 1, 1, 1, 3, 1, 1, 1], // 8
+    [1, 1, 3, 1, 1, 1, 3, 1, 1, 1], // 9
+];
+
+/// Element widths of each Matrix 2 of 5 digit, the last one being the gap to the next character
+const MATRIX_PATTERNS: [[u32; 6]; 10] = [
+    [1, 1, 3, 3, 1, 1], // 0
+    [3, 1, 1, 1, 3, 1], // 1
+    [1, 3, 1, 1, 3, 1], // 2
+    [3, 3, 1, 1, 1, 1], // 3
+    [1, 1, 3, 1, 3, 1], // 4
+    [3, 1, 3, 1, 1, 1], // 5
+    [1, 3, 3, 1, 1, 1], // 6
+    [1, 1, 1, 3, 3, 1], // 7
+    [3, 1, 1, 3, 1, 1], // 8
+    [1, 3, 1, 3, 1, 1], // 9
+];
+
+#[cfg(feature = "decoders")]
+const MAX_AVG_VARIANCE: f32 = 0.38;
+#[cfg(feature = "decoders")]
+const MAX_INDIVIDUAL_VARIANCE: f32 = 0.5;
+
+/// Any length from this many digits up is read unless [`DecodeHints::AllowedLengths`] says
+/// otherwise
+#[cfg(feature = "decoders")]
+const DEFAULT_ALLOWED_LENGTHS: [u32; 1] = [4];
+
+/// The guard patterns and digit encoding of one of the 2 of 5 variants
+pub struct Variant<const S: usize, const E: usize, const C: usize> {
+    start: [u32; S],
+    stop: [u32; E],
+    patterns: &'static [[u32; C]; 10],
+}
+
+pub type IndustrialVariant = Variant<6, 5, 10>;
+pub type IATAVariant = Variant<4, 3, 10>;
+pub type MatrixVariant = Variant<6, 5, 6>;
+
+pub const INDUSTRIAL: IndustrialVariant = Variant {
+    start: [3, 1, 3, 1, 1, 1],
+    stop: [3, 1, 1, 1, 3],
+    patterns: &BAR_PATTERNS,
+};
+
+pub const IATA: IATAVariant = Variant {
+    start: [1, 1, 1, 1],
+    stop: [3, 1, 1],
+    patterns: &BAR_PATTERNS,
+};
+
+pub const MATRIX: MatrixVariant = Variant {
+    start: [4, 1, 1, 1, 1, 1],
+    stop: [4, 1, 1, 1, 1],
+    patterns: &MATRIX_PATTERNS,
+};
+
+/// The standard modulo 10 check digit, weighting digits 3 and 1 alternately from the right
+pub fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| if i % 2 == 0 { 3 * d as u32 } else { d as u32 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+impl<const S: usize, const E: usize, const C: usize> Variant<S, E, C> {
+    /// The element widths of `digits` between the start and stop patterns, bars first
+    #[cfg(feature = "encoders")]
+    pub fn encode(&self, digits: &[u8]) -> Vec<bool> {
+        let widths = self
+            .start
+            .iter()
+            .chain(digits.iter().flat_map(|&d| &self.patterns[d as usize]))
+            .chain(&self.stop);
+        let mut result = Vec::new();
+        for (i, &width) in widths.enumerate() {
+            result.extend(std::iter::repeat_n(i % 2 == 0, width as usize));
+        }
+        result
+    }
+
+    /// Decodes the digits of a symbol whose start pattern begins at run `start` of `runs`, as
+    /// recorded by [`one_d_reader::record_all_runs`]. Returns them along with the index of the
+    /// run following the stop pattern.
+    #[cfg(feature = "decoders")]
+    pub fn decode_runs(&self, runs: &[u32], start: usize) -> Option<(Vec<u8>, usize)> {
+        let start_width: u32 = runs.get(start..start + S)?.iter().sum();
+        if Self::variance(&runs[start..], &self.start)? >= MAX_AVG_VARIANCE {
+            return None;
+        }
+        let unit = start_width as f32 / self.start.iter().sum::<u32>() as f32;
+        // quiet zone of ten narrow elements, or as much as there is
+        let is_quiet = |run: Option<&u32>| run.is_none_or(|&w| w as f32 >= 10.0 * unit);
+        if start > 1 && !is_quiet(runs.get(start - 1)) {
+            return None;
+        }
+
+        let char_width = self.patterns[0].iter().sum::<u32>() as f32 * unit;
+        let mut digits = Vec::new();
+        let mut offset = start + S;
+        loop {
+            if Self::variance(&runs[offset..], &self.stop)
+                .is_some_and(|variance| variance < MAX_AVG_VARIANCE)
+                && is_quiet(runs.get(offset + E))
+            {
+                return Some((digits, offset + E));
+            }
+
+            let elements = runs.get(offset..offset + C)?;
+            let width = elements.iter().sum::<u32>() as f32;
+            if width < 0.75 * char_width || width > 1.25 * char_width {
+                return None;
+            }
+            digits.push(self.decode_digit(elements)?);
+            offset += C;
+        }
+    }
+
+    /// The digit whose pattern best matches `elements`, unless two match equally well
+    #[cfg(feature = "decoders")]
+    fn decode_digit(&self, elements: &[u32]) -> Option<u8> {
+        let mut best_variance = MAX_AVG_VARIANCE;
+        let mut best_match = None;
+        for (digit, pattern) in self.patterns.iter().enumerate() {
+            let variance = Self::variance(elements, pattern)?;
+            if variance < best_variance {
+                best_variance = variance;
+                best_match = Some(digit as u8);
+            } else if variance == best_variance {
+                best_match = None;
+            }
+        }
+        best_match
+    }
+
+    #[cfg(feature = "decoders")]
+    fn variance<const N: usize>(runs: &[u32], pattern: &[u32; N]) -> Option<f32> {
+        let counters: [u32; N] = runs.get(..N)?.try_into().ok()?;
+        Some(one_d_reader::pattern_match_variance(
+            &counters,
+            pattern,
+            MAX_INDIVIDUAL_VARIANCE,
+        ))
+    }
+}
+
+/// Applies the check digit and length hints to decoded `digits`, returning the text to report
+#[cfg(feature = "decoders")]
+pub fn validate(digits: &[u8], hints: &DecodeHints) -> Result<String> {
+    // as with ITF, lengths longer than the longest allowed one are accepted as well
+    let allowed_lengths = hints
+        .AllowedLengths
+        .as_deref()
+        .unwrap_or(&DEFAULT_ALLOWED_LENGTHS);
+    let length = digits.len() as u32;
+    let max_allowed_length = allowed_lengths.iter().copied().max().unwrap_or_default();
+    if !allowed_lengths.contains(&length) && length <= max_allowed_length {
+        return Err(Exceptions::FORMAT);
+    }
+
+    let data = if matches!(hints.AssumeTwoOfFiveCheckDigit, Some(true)) {
+        let (check, data) = digits.split_last().ok_or(Exceptions::FORMAT)?;
+        if check_digit(data) != *check {
+            return Err(Exceptions::CHECKSUM);
+        }
+        data
+    } else {
+        digits
+    };
+    Ok(data.iter().map(|&d| char::from(b'0' + d)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_digit;
+
+    #[test]
+    fn mod_10() {
+        assert_eq!(check_digit(&[1, 2, 3]), 6);
+        assert_eq!(check_digit(&[4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3]), 1);
+    }
+}
@@ -0,0 +1,187 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::common::{BitArray, Result};
+use crate::{
+    BarcodeFormat, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue, point,
+};
+
+use super::{OneDReader, record_all_runs, two_of_five_common};
+
+macro_rules! two_of_five_reader {
+    ($(#[$doc:meta])* $name:ident, $variant:expr, $format:expr, $symbology:expr) => {
+        $(#[$doc])*
+        #[derive(OneDReader, Clone, Default)]
+        pub struct $name;
+
+        impl OneDReader for $name {
+            fn decode_row(
+                &mut self,
+                rowNumber: u32,
+                row: &BitArray,
+                hints: &DecodeHints,
+            ) -> Result<RXingResult> {
+                let runs = record_all_runs(row);
+
+                let mut last_error = Exceptions::NOT_FOUND;
+                // black runs have odd indices
+                for start in (1..runs.len()).step_by(2) {
+                    let Some((digits, end)) = $variant.decode_runs(&runs, start) else {
+                        continue;
+                    };
+                    let text = match two_of_five_common::validate(&digits, hints) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            last_error = e;
+                            continue;
+                        }
+                    };
+
+                    let left = runs[..start].iter().sum::<u32>();
+                    let right = left + runs[start..end].iter().sum::<u32>();
+                    let mut result = RXingResult::new(
+                        &text,
+                        Vec::new(),
+                        vec![
+                            point(left as f32, rowNumber as f32),
+                            point(right as f32, rowNumber as f32),
+                        ],
+                        $format,
+                    );
+                    if let Some(symbology) = $symbology {
+                        result.putMetadata(
+                            RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+                            RXingResultMetadataValue::SymbologyIdentifier(
+                                String::from(symbology),
+                            ),
+                        );
+                    }
+                    return Ok(result);
+                }
+
+                Err(last_error)
+            }
+        }
+    };
+}
+
+two_of_five_reader!(
+    /**
+     * <p>Decodes Industrial (Standard) 2 of 5 barcodes, which carry their digits in the widths
+     * of the bars alone.</p>
+     *
+     * <p>Lengths are restricted with [`DecodeHints::AllowedLengths`] as in [`super::ITFReader`],
+     * counting the check digit, which is validated and removed when
+     * [`DecodeHints::AssumeTwoOfFiveCheckDigit`] is set.</p>
+     */
+    IndustrialTwoOfFiveReader,
+    two_of_five_common::INDUSTRIAL,
+    BarcodeFormat::INDUSTRIAL_2_OF_5,
+    Some("]S0")
+);
+
+two_of_five_reader!(
+    /**
+     * <p>Decodes IATA 2 of 5 barcodes, the airline variant of Industrial 2 of 5 with shorter
+     * start and stop patterns.</p>
+     *
+     * <p>Lengths and check digits are handled as by [`IndustrialTwoOfFiveReader`].</p>
+     */
+    IATATwoOfFiveReader,
+    two_of_five_common::IATA,
+    BarcodeFormat::IATA_2_OF_5,
+    Some("]R0")
+);
+
+two_of_five_reader!(
+    /**
+     * <p>Decodes Matrix 2 of 5 barcodes, which carry each digit in three bars and the two
+     * spaces between them.</p>
+     *
+     * <p>Lengths and check digits are handled as by [`IndustrialTwoOfFiveReader`].</p>
+     */
+    MatrixTwoOfFiveReader,
+    two_of_five_common::MATRIX,
+    BarcodeFormat::MATRIX_2_OF_5,
+    None::<&str>
+);
+
+#[cfg(test)]
+mod TwoOfFiveReaderTestCase {
+    use crate::{
+        DecodeHintValue, DecodeHints,
+        common::BitMatrix,
+        oned::{IATATwoOfFiveReader, IndustrialTwoOfFiveReader, MatrixTwoOfFiveReader, OneDReader},
+    };
+
+    const INDUSTRIAL_123: &str = "1110111010111010101011101011101010111011101110101010111010111";
+    const IATA_123: &str = "101011101010101110101110101011101110111010101011101";
+    const IATA_124: &str = "101011101010101110101110101011101010111010111011101";
+    const MATRIX_1236: &str = "111101010111010111010001011101110001010100011101011110101";
+
+    fn decode(reader: &mut impl OneDReader, encoded: &str, hints: &DecodeHints) -> Option<String> {
+        let padded = format!("0000000000{encoded}0000000000");
+        let matrix = BitMatrix::parse_strings(&padded, "1", "0").expect("bitmatrix parse");
+        reader
+            .decode_row(0, &matrix.getRow(0), hints)
+            .ok()
+            .map(|result| result.getText().to_owned())
+    }
+
+    fn lengths(lengths: Vec<u32>) -> DecodeHints {
+        DecodeHints::default().with(DecodeHintValue::AllowedLengths(lengths))
+    }
+
+    #[test]
+    fn testDecode() {
+        let hints = lengths(vec![3]);
+        assert_eq!(
+            decode(&mut IndustrialTwoOfFiveReader, INDUSTRIAL_123, &hints).as_deref(),
+            Some("123")
+        );
+        assert_eq!(
+            decode(&mut IATATwoOfFiveReader, IATA_123, &hints).as_deref(),
+            Some("123")
+        );
+        assert_eq!(
+            decode(&mut MatrixTwoOfFiveReader, MATRIX_1236, &hints).as_deref(),
+            Some("1236")
+        );
+        // each variant only reads its own guard patterns
+        assert!(decode(&mut IATATwoOfFiveReader, INDUSTRIAL_123, &hints).is_none());
+        assert!(decode(&mut MatrixTwoOfFiveReader, IATA_123, &hints).is_none());
+    }
+
+    #[test]
+    fn testAllowedLengths() {
+        // only 4 digits and up are read by default
+        let default = DecodeHints::default();
+        assert!(decode(&mut IndustrialTwoOfFiveReader, INDUSTRIAL_123, &default).is_none());
+        assert!(decode(&mut MatrixTwoOfFiveReader, MATRIX_1236, &default).is_some());
+        assert!(
+            decode(
+                &mut MatrixTwoOfFiveReader,
+                MATRIX_1236,
+                &lengths(vec![3, 5])
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn testCheckDigit() {
+        let hints = DecodeHints::default().with(DecodeHintValue::AssumeTwoOfFiveCheckDigit(true));
+        assert_eq!(
+            decode(&mut MatrixTwoOfFiveReader, MATRIX_1236, &hints).as_deref(),
+            Some("123")
+        );
+        let hints = hints.with(DecodeHintValue::AllowedLengths(vec![3]));
+        // 3 happens to be the check digit of "12"
+        assert_eq!(
+            decode(&mut IATATwoOfFiveReader, IATA_123, &hints).as_deref(),
+            Some("12")
+        );
+        // but 4 is not
+        assert!(decode(&mut IATATwoOfFiveReader, IATA_124, &hints).is_none());
+    }
+}
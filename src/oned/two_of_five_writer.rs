@@ -0,0 +1,147 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::common::Result;
+use crate::{BarcodeFormat, EncodeHints};
+
+use super::{OneDimensionalCodeWriter, two_of_five_common};
+
+macro_rules! two_of_five_writer {
+    ($(#[$doc:meta])* $name:ident, $variant:expr, $format:expr) => {
+        $(#[$doc])*
+        #[derive(OneDWriter, Default)]
+        pub struct $name;
+
+        impl OneDimensionalCodeWriter for $name {
+            fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+                self.encode_oned_with_hints(contents, &EncodeHints::default())
+            }
+
+            fn encode_oned_with_hints(
+                &self,
+                contents: &str,
+                hints: &EncodeHints,
+            ) -> Result<Vec<bool>> {
+                Self::checkNumeric(contents)?;
+                let mut digits: Vec<u8> = contents.bytes().map(|b| b - b'0').collect();
+                if matches!(hints.TwoOfFiveCheckDigit, Some(true)) {
+                    digits.push(two_of_five_common::check_digit(&digits));
+                }
+                Ok($variant.encode(&digits))
+            }
+
+            fn getSupportedWriteFormats(&self) -> Option<Vec<crate::BarcodeFormat>> {
+                Some(vec![$format])
+            }
+        }
+    };
+}
+
+two_of_five_writer!(
+    /**
+     * This object renders an Industrial (Standard) 2 of 5 barcode as a BitMatrix. A modulo 10
+     * check digit is appended when [`EncodeHints::TwoOfFiveCheckDigit`] is set.
+     */
+    IndustrialTwoOfFiveWriter,
+    two_of_five_common::INDUSTRIAL,
+    BarcodeFormat::INDUSTRIAL_2_OF_5
+);
+
+two_of_five_writer!(
+    /**
+     * This object renders an IATA 2 of 5 barcode as a BitMatrix. A modulo 10 check digit is
+     * appended when [`EncodeHints::TwoOfFiveCheckDigit`] is set.
+     */
+    IATATwoOfFiveWriter,
+    two_of_five_common::IATA,
+    BarcodeFormat::IATA_2_OF_5
+);
+
+two_of_five_writer!(
+    /**
+     * This object renders a Matrix 2 of 5 barcode as a BitMatrix. A modulo 10 check digit is
+     * appended when [`EncodeHints::TwoOfFiveCheckDigit`] is set.
+     */
+    MatrixTwoOfFiveWriter,
+    two_of_five_common::MATRIX,
+    BarcodeFormat::MATRIX_2_OF_5
+);
+
+#[cfg(test)]
+mod TwoOfFiveWriterTestCase {
+    use crate::{
+        BarcodeFormat, EncodeHintValue, EncodeHints, Writer,
+        common::{BitMatrix, bit_matrix_test_case},
+    };
+
+    use super::{IATATwoOfFiveWriter, IndustrialTwoOfFiveWriter, MatrixTwoOfFiveWriter};
+
+    fn encode(
+        writer: &impl Writer,
+        format: BarcodeFormat,
+        contents: &str,
+        hints: &EncodeHints,
+    ) -> String {
+        let result: BitMatrix = writer
+            .encode_with_hints(contents, &format, 0, 0, hints)
+            .expect("must encode");
+        bit_matrix_test_case::matrix_to_string(&result)
+    }
+
+    #[test]
+    fn testEncode() {
+        let hints = EncodeHints::default();
+        assert_eq!(
+            encode(
+                &IndustrialTwoOfFiveWriter,
+                BarcodeFormat::INDUSTRIAL_2_OF_5,
+                "123",
+                &hints
+            ),
+            concat!(
+                "00000",
+                "1110111010111010101011101011101010111011101110101010111010111",
+                "00000"
+            )
+        );
+        assert_eq!(
+            encode(
+                &IATATwoOfFiveWriter,
+                BarcodeFormat::IATA_2_OF_5,
+                "123",
+                &hints
+            ),
+            concat!(
+                "00000",
+                "101011101010101110101110101011101110111010101011101",
+                "00000"
+            )
+        );
+    }
+
+    #[test]
+    fn testCheckDigit() {
+        let hints = EncodeHints::default().with(EncodeHintValue::TwoOfFiveCheckDigit(true));
+        assert_eq!(
+            encode(
+                &MatrixTwoOfFiveWriter,
+                BarcodeFormat::MATRIX_2_OF_5,
+                "123",
+                &hints
+            ),
+            concat!(
+                "00000",
+                "111101010111010111010001011101110001010100011101011110101",
+                "00000"
+            )
+        );
+    }
+
+    #[test]
+    fn testNumericOnly() {
+        assert!(
+            MatrixTwoOfFiveWriter
+                .encode("12A", &BarcodeFormat::MATRIX_2_OF_5, 0, 0)
+                .is_err()
+        );
+    }
+}
@@ -14,7 +14,9 @@
  * limitations under the License.
  */
 
-use crate::{BarcodeFormat, Binarizer, Exceptions, RXingResult, Reader, common::Result};
+use crate::{
+    BarcodeFormat, Binarizer, Exceptions, ImmutableReader, RXingResult, Reader, common::Result,
+};
 
 use super::{EAN13Reader, OneDReader, UPCEANReader};
 
@@ -24,7 +26,7 @@ use super::{EAN13Reader, OneDReader, UPCEANReader};
  * @author dswitkin@google.com (Daniel Switkin)
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct UPCAReader(EAN13Reader);
 
 impl Reader for UPCAReader {
@@ -41,6 +43,16 @@ impl Reader for UPCAReader {
     }
 }
 
+impl ImmutableReader for UPCAReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut crate::BinaryBitmap<B>,
+        hints: &crate::DecodeHints,
+    ) -> Result<RXingResult> {
+        Self::maybeReturnRXingResult(self.0.immutable_decode_with_hints(image, hints)?)
+    }
+}
+
 impl OneDReader for UPCAReader {
     fn decode_row(
         &mut self,
@@ -32,7 +32,7 @@ use super::oned_constants::upc_ean_shared::L_AND_G_PATTERNS;
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(OneDReader, EANReader, Clone, Default)]
 pub struct UPCEReader;
 
 impl UPCEANReader for UPCEReader {
@@ -23,7 +23,7 @@ use crate::{
     point,
 };
 
-use super::{STAND_IN, UPCEANReader};
+use super::{STAND_IN, UPCEANReader, oned_constants::upc_ean_extension};
 
 /**
  * @see UPCEANExtension2Support
@@ -32,9 +32,6 @@ use super::{STAND_IN, UPCEANReader};
 pub struct UPCEANExtension5Support;
 
 impl UPCEANExtension5Support {
-    const CHECK_DIGIT_ENCODINGS: [usize; 10] =
-        [0x18, 0x14, 0x12, 0x11, 0x0C, 0x06, 0x03, 0x0A, 0x09, 0x05];
-
     pub fn decodeRow(
         &self,
         rowNumber: u32,
@@ -140,7 +137,7 @@ impl UPCEANExtension5Support {
 
     fn determineCheckDigit(lgPatternFound: usize) -> Result<usize> {
         for d in 0..10 {
-            if lgPatternFound == Self::CHECK_DIGIT_ENCODINGS[d] {
+            if lgPatternFound == upc_ean_extension::EXTENSION_5_CHECK_DIGIT_ENCODINGS[d] {
                 return Ok(d);
             }
         }
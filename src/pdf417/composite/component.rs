@@ -0,0 +1,290 @@
+//! Symbol layouts shared by the reader and the writer of the 2D component.
+//!
+//! CC-A and CC-B are MicroPDF417 symbols, told apart from plain MicroPDF417 by their own
+//! variant tables. Rows are addressed by Row Address Patterns (RAPs) instead of start and
+//! stop patterns. CC-C is a PDF417 symbol whose width follows the GS1-128 linear component.
+
+/// The kind of 2D component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompositeType {
+    /// MicroPDF417 layout with its own variants and base 928 compaction
+    CcA,
+    /// MicroPDF417 variant, byte compacted
+    CcB,
+    /// PDF417 symbol, byte compacted, only used with GS1-128
+    CcC,
+}
+
+/// One MicroPDF417 symbol size usable for a CC-A or CC-B component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MicroVariant {
+    pub columns: u32,
+    pub rows: u32,
+    pub ec_codewords: u32,
+    /// Left RAP of the first row, 0 when the layout has no left RAP
+    pub left_rap: u32,
+    /// Centre RAP of the first row, 0 when the layout has no centre RAP
+    pub centre_rap: u32,
+    pub right_rap: u32,
+    /// Cluster of the first row, 0, 1 or 2 for clusters 0, 3 and 6
+    pub cluster: u32,
+}
+
+impl MicroVariant {
+    const fn new(columns: u32, rows: u32, ec_codewords: u32, raps: [u32; 3], cluster: u32) -> Self {
+        Self {
+            columns,
+            rows,
+            ec_codewords,
+            left_rap: raps[0],
+            centre_rap: raps[1],
+            right_rap: raps[2],
+            cluster,
+        }
+    }
+
+    pub fn data_codewords(&self) -> u32 {
+        self.columns * self.rows - self.ec_codewords
+    }
+
+    /// The RAP values and cluster of row `row`.
+    pub fn row_address(&self, row: u32) -> ([u32; 3], u32) {
+        let step = |rap: u32| {
+            if rap == 0 {
+                0
+            } else {
+                (rap - 1 + row) % 52 + 1
+            }
+        };
+        (
+            [
+                step(self.left_rap),
+                step(self.centre_rap),
+                step(self.right_rap),
+            ],
+            (self.cluster + row) % 3,
+        )
+    }
+}
+
+/// CC-A sizes, ISO/IEC 24723 table 8.
+pub const CC_A_VARIANTS: [MicroVariant; 17] = [
+    MicroVariant::new(2, 5, 4, [39, 0, 19], 2),
+    MicroVariant::new(2, 6, 4, [1, 0, 33], 0),
+    MicroVariant::new(2, 7, 5, [32, 0, 12], 1),
+    MicroVariant::new(2, 8, 5, [8, 0, 40], 1),
+    MicroVariant::new(2, 9, 6, [14, 0, 46], 1),
+    MicroVariant::new(2, 10, 6, [43, 0, 23], 0),
+    MicroVariant::new(2, 12, 7, [20, 0, 52], 1),
+    MicroVariant::new(3, 4, 4, [0, 43, 23], 1),
+    MicroVariant::new(3, 5, 5, [0, 33, 13], 0),
+    MicroVariant::new(3, 6, 6, [0, 37, 17], 1),
+    MicroVariant::new(3, 7, 7, [0, 47, 27], 2),
+    MicroVariant::new(3, 8, 7, [0, 1, 33], 2),
+    MicroVariant::new(4, 3, 4, [40, 20, 52], 0),
+    MicroVariant::new(4, 4, 5, [43, 23, 3], 0),
+    MicroVariant::new(4, 5, 6, [46, 26, 6], 0),
+    MicroVariant::new(4, 6, 7, [34, 14, 46], 0),
+    MicroVariant::new(4, 7, 8, [29, 9, 41], 1),
+];
+
+/// The MicroPDF417 sizes with 2 to 4 columns that a CC-B component may use, ISO/IEC 24728 table 1.
+pub const CC_B_VARIANTS: [MicroVariant; 28] = [
+    MicroVariant::new(2, 8, 8, [1, 0, 1], 0),
+    MicroVariant::new(2, 11, 9, [1, 0, 9], 0),
+    MicroVariant::new(2, 14, 9, [8, 0, 8], 1),
+    MicroVariant::new(2, 17, 10, [36, 0, 36], 2),
+    MicroVariant::new(2, 20, 11, [19, 0, 19], 0),
+    MicroVariant::new(2, 23, 13, [9, 0, 17], 2),
+    MicroVariant::new(2, 26, 15, [27, 0, 35], 2),
+    MicroVariant::new(3, 6, 12, [1, 1, 1], 0),
+    MicroVariant::new(3, 8, 14, [7, 7, 7], 0),
+    MicroVariant::new(3, 10, 16, [15, 15, 15], 2),
+    MicroVariant::new(3, 12, 18, [25, 25, 25], 0),
+    MicroVariant::new(3, 15, 21, [37, 37, 37], 0),
+    MicroVariant::new(3, 20, 26, [1, 17, 33], 0),
+    MicroVariant::new(3, 26, 32, [1, 9, 17], 0),
+    MicroVariant::new(3, 32, 38, [21, 29, 37], 2),
+    MicroVariant::new(3, 38, 44, [15, 31, 47], 2),
+    MicroVariant::new(3, 44, 50, [1, 25, 49], 0),
+    MicroVariant::new(4, 4, 8, [47, 19, 43], 1),
+    MicroVariant::new(4, 6, 12, [1, 1, 1], 0),
+    MicroVariant::new(4, 8, 14, [7, 7, 7], 0),
+    MicroVariant::new(4, 10, 16, [15, 15, 15], 2),
+    MicroVariant::new(4, 12, 18, [25, 25, 25], 0),
+    MicroVariant::new(4, 15, 21, [37, 37, 37], 0),
+    MicroVariant::new(4, 20, 26, [1, 17, 33], 0),
+    MicroVariant::new(4, 26, 32, [1, 9, 17], 0),
+    MicroVariant::new(4, 32, 38, [21, 29, 37], 2),
+    MicroVariant::new(4, 38, 44, [15, 31, 47], 2),
+    MicroVariant::new(4, 44, 50, [1, 25, 49], 0),
+];
+
+/// Left and right Row Address Patterns, 10 modules each, indexed by RAP number - 1.
+pub const RAP_SIDE: [u32; 52] = [
+    0x322, 0x3A2, 0x3B2, 0x332, 0x372, 0x37A, 0x33A, 0x3BA, 0x39A, 0x3DA, 0x3CA, 0x38A, 0x30A,
+    0x31A, 0x312, 0x392, 0x3D2, 0x3D6, 0x3D4, 0x394, 0x3B4, 0x3A4, 0x3A6, 0x3AE, 0x3AC, 0x3A8,
+    0x328, 0x32C, 0x32E, 0x326, 0x336, 0x3B6, 0x396, 0x316, 0x314, 0x334, 0x374, 0x364, 0x366,
+    0x36E, 0x36C, 0x368, 0x348, 0x358, 0x35C, 0x35E, 0x34E, 0x34C, 0x344, 0x346, 0x342, 0x362,
+];
+
+/// Centre Row Address Patterns, 10 modules each, indexed by RAP number - 1.
+pub const RAP_CENTRE: [u32; 52] = [
+    0x2CE, 0x24E, 0x26E, 0x22E, 0x226, 0x236, 0x216, 0x212, 0x21A, 0x23A, 0x232, 0x222, 0x262,
+    0x272, 0x27A, 0x2FA, 0x2F2, 0x2F6, 0x276, 0x274, 0x264, 0x266, 0x246, 0x242, 0x2C2, 0x2E2,
+    0x2E6, 0x2E4, 0x2EC, 0x26C, 0x22C, 0x228, 0x268, 0x2E8, 0x2C8, 0x2CC, 0x2C4, 0x2C6, 0x286,
+    0x28E, 0x28C, 0x29C, 0x298, 0x2B8, 0x2B0, 0x290, 0x2D0, 0x250, 0x258, 0x25C, 0x2DC, 0x2DE,
+];
+
+/// PDF417 start and stop patterns used by CC-C rows.
+pub const START_PATTERN: u32 = 0x1FEA8;
+pub const STOP_PATTERN: u32 = 0x3FA29;
+
+/// The elements making up one row of a component, from left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowElement {
+    Start,
+    LeftRap,
+    CentreRap,
+    RightRap,
+    Codeword,
+    LeftIndicator,
+    RightIndicator,
+    Stop,
+}
+
+impl RowElement {
+    /// Width in modules.
+    pub fn modules(self, composite_type: CompositeType) -> u32 {
+        match self {
+            RowElement::LeftRap | RowElement::CentreRap | RowElement::RightRap => 10,
+            RowElement::Stop if composite_type == CompositeType::CcC => 18,
+            RowElement::Stop => 1,
+            _ => 17,
+        }
+    }
+
+    /// Number of bars.
+    pub fn bars(self, composite_type: CompositeType) -> u32 {
+        match self {
+            RowElement::LeftRap | RowElement::CentreRap | RowElement::RightRap => 3,
+            RowElement::Stop if composite_type == CompositeType::CcC => 5,
+            RowElement::Stop => 1,
+            _ => 4,
+        }
+    }
+}
+
+/// Lists the elements of a row of a component with `columns` data columns.
+pub fn row_layout(composite_type: CompositeType, columns: u32) -> Vec<RowElement> {
+    use RowElement::*;
+    match (composite_type, columns) {
+        (CompositeType::CcC, _) => {
+            let mut layout = vec![Start, LeftIndicator];
+            layout.extend(std::iter::repeat_n(Codeword, columns as usize));
+            layout.extend([RightIndicator, Stop]);
+            layout
+        }
+        (_, 2) => vec![LeftRap, Codeword, Codeword, RightRap, Stop],
+        (CompositeType::CcA, 3) => vec![Codeword, CentreRap, Codeword, Codeword, RightRap, Stop],
+        (_, 3) => vec![
+            LeftRap, Codeword, CentreRap, Codeword, Codeword, RightRap, Stop,
+        ],
+        _ => vec![
+            LeftRap, Codeword, Codeword, CentreRap, Codeword, Codeword, RightRap, Stop,
+        ],
+    }
+}
+
+/// Height of one row of the component in modules.
+pub fn row_height(composite_type: CompositeType) -> u32 {
+    if composite_type == CompositeType::CcC {
+        3
+    } else {
+        2
+    }
+}
+
+/// Number of error correction codewords of a CC-C component with the given level.
+pub fn cc_c_ec_codewords(ec_level: u32) -> u32 {
+    1 << (ec_level + 1)
+}
+
+/// Coefficients of the generator polynomial (x - 3)(x - 3^2)...(x - 3^k) over GF(929),
+/// lowest order first, without the leading 1.
+pub fn generator(k: usize) -> Vec<u32> {
+    let mut coefficients = vec![1u32];
+    let mut root = 1u32;
+    for _ in 0..k {
+        root = root * 3 % 929;
+        let mut next = vec![0u32; coefficients.len() + 1];
+        for (j, &c) in coefficients.iter().enumerate() {
+            next[j + 1] = (next[j + 1] + c) % 929;
+            next[j] = (next[j] + 929 - c * root % 929) % 929;
+        }
+        coefficients = next;
+    }
+    coefficients.truncate(k);
+    coefficients
+}
+
+/// Computes the `k` error correction codewords for `data`.
+pub fn error_correction(data: &[u32], k: usize) -> Vec<u32> {
+    let coefficients = generator(k);
+    let mut ec = vec![0u32; k];
+    for &d in data {
+        let total = (d + ec[k - 1]) % 929;
+        for j in (0..k).rev() {
+            let previous = if j == 0 { 0 } else { ec[j - 1] };
+            ec[j] = (previous + 929 - total * coefficients[j] % 929) % 929;
+        }
+    }
+    ec.iter()
+        .rev()
+        .map(|&c| if c == 0 { 0 } else { 929 - c })
+        .collect()
+}
+
+/// Data bits a CC-A component holds, by number of columns.
+pub const CC_A_BITS: [&[usize]; 3] = [
+    &[59, 78, 88, 108, 118, 138, 167],
+    &[78, 98, 118, 138, 167],
+    &[78, 108, 138, 167, 197],
+];
+
+/// Number of codewords in a CC-A component holding `bits` bits.
+pub fn cc_a_codewords(bits: usize) -> usize {
+    (bits / 69) * 7
+        + if bits % 69 == 0 {
+            0
+        } else {
+            (bits % 69) / 10 + 1
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_matches_specification() {
+        assert_eq!(generator(4), vec![522, 568, 723, 809]);
+    }
+
+    #[test]
+    fn row_widths() {
+        let width = |t, c| row_layout(t, c).iter().map(|e| e.modules(t)).sum::<u32>();
+        assert_eq!(width(CompositeType::CcA, 2), 55);
+        assert_eq!(width(CompositeType::CcA, 3), 72);
+        assert_eq!(width(CompositeType::CcB, 3), 82);
+        assert_eq!(width(CompositeType::CcB, 4), 99);
+        assert_eq!(width(CompositeType::CcC, 4), 137);
+    }
+
+    #[test]
+    fn row_addresses_wrap() {
+        let (raps, cluster) = CC_A_VARIANTS[6].row_address(1);
+        assert_eq!(raps, [21, 0, 1]);
+        assert_eq!(cluster, 2);
+    }
+}
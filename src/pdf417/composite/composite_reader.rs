@@ -0,0 +1,858 @@
+use std::collections::HashMap;
+
+use crate::{
+    BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, Point, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue,
+    common::{BitArray, BitMatrix, Result},
+    oned::{rss::expanded::decoders::field_parser, upcean_common::convertUPCEtoUPCA},
+    pdf417::{
+        decoder::ec::error_correction,
+        pdf_417_common::{MAX_CODEWORDS_IN_BARCODE, getCodeword},
+    },
+    point,
+};
+
+use super::{
+    component::{
+        CC_A_BITS, CC_A_VARIANTS, CC_B_VARIANTS, CompositeType, MicroVariant, RAP_CENTRE, RAP_SIDE,
+        RowElement, cc_a_codewords, cc_c_ec_codewords, row_layout,
+    },
+    encodation::{self, GS},
+};
+
+/// Rows without a component row that may separate the linear component from the 2D one, or
+/// end the 2D component, in multiples of the module width.
+const MAX_GAP_MODULES: f32 = 12.0;
+
+/**
+ * Looks for the 2D component of a GS1 Composite symbol above the linear component `linear`,
+ * found in `image`. Nothing is binarized for linear symbols that cannot carry a component.
+ *
+ * On success the result carries the element strings of the whole message in human readable
+ * form, those of the 2D component alone in the GS1_COMPOSITE metadata and the format of the
+ * linear component. The linear result is expected to have been read in the orientation
+ * recorded in its ORIENTATION metadata, 0 or 180 degrees.
+ */
+pub fn decode_composite<B: Binarizer>(
+    image: &BinaryBitmap<B>,
+    linear: &RXingResult,
+) -> Result<RXingResult> {
+    let linear_strings = linear_element_strings(linear).ok_or(Exceptions::NOT_FOUND)?;
+    let image = image.get_black_matrix();
+
+    let upside_down = matches!(
+        linear
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ORIENTATION),
+        Some(RXingResultMetadataValue::Orientation(180))
+    );
+    let rotated;
+    let (image, points) = if upside_down {
+        let mut copy = image.clone();
+        copy.rotate180();
+        rotated = copy;
+        let (width, height) = (image.getWidth() as f32, image.getHeight() as f32);
+        let points: Vec<Point> = linear
+            .getPoints()
+            .iter()
+            .map(|p| point(width - 1.0 - p.x, height - 1.0 - p.y))
+            .collect();
+        (&rotated, points)
+    } else {
+        (image, linear.getPoints().to_vec())
+    };
+
+    let (component_strings, top) = read_component(image, &points)?;
+
+    let mut text = linear_strings;
+    text.push_str(&component_strings);
+
+    let mut result_points = linear.getPoints().to_vec();
+    result_points.extend(top.iter().map(|p| {
+        if upside_down {
+            point(
+                image.getWidth() as f32 - 1.0 - p.x,
+                image.getHeight() as f32 - 1.0 - p.y,
+            )
+        } else {
+            *p
+        }
+    }));
+
+    let mut result = RXingResult::new(
+        &text,
+        linear.getRawBytes().to_vec(),
+        result_points,
+        *linear.getBarcodeFormat(),
+    );
+    result.putAllMetadata(linear.getRXingResultMetadata().clone());
+    result.putMetadata(
+        RXingResultMetadataType::GS1_COMPOSITE,
+        RXingResultMetadataValue::Gs1Composite(component_strings),
+    );
+    result.putMetadata(
+        RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+        RXingResultMetadataValue::SymbologyIdentifier("]e0".to_owned()),
+    );
+    Ok(result)
+}
+
+/// The element strings of a linear component able to carry a 2D component, None for other
+/// symbols and for GS1-128 symbols without the linkage flag.
+fn linear_element_strings(linear: &RXingResult) -> Option<String> {
+    let text = linear.getText();
+    match linear.getBarcodeFormat() {
+        BarcodeFormat::EAN_13 => Some(format!("(01)0{text}")),
+        BarcodeFormat::UPC_A => Some(format!("(01)00{text}")),
+        BarcodeFormat::EAN_8 => Some(format!("(01)000000{text}")),
+        BarcodeFormat::UPC_E => Some(format!("(01)00{}", convertUPCEtoUPCA(text)?)),
+        BarcodeFormat::RSS_14 => Some(format!("(01){text}")),
+        BarcodeFormat::RSS_EXPANDED => Some(text.to_owned()),
+        BarcodeFormat::CODE_128 => gs1_128_element_strings(linear.getRawBytes()),
+        _ => None,
+    }
+}
+
+/// Rebuilds the element strings of a GS1-128 symbol from its symbol character values, the
+/// start character first and the check character and stop last. The symbol must end with the
+/// redundant code set switch that ISO/IEC 24723 uses as linkage flag.
+fn gs1_128_element_strings(raw_codes: &[u8]) -> Option<String> {
+    const CODE_SHIFT: u8 = 98;
+    const CODE_CODE_C: u8 = 99;
+    const CODE_CODE_B: u8 = 100;
+    const CODE_CODE_A: u8 = 101;
+    const CODE_FNC_1: u8 = 102;
+
+    let (&start, rest) = raw_codes.split_first()?;
+    let codes = &rest[..rest.len().checked_sub(2)?];
+    if codes.first() != Some(&CODE_FNC_1) {
+        return None;
+    }
+    let mut code_set = match start {
+        103 => CODE_CODE_A,
+        104 => CODE_CODE_B,
+        105 => CODE_CODE_C,
+        _ => return None,
+    };
+
+    let mut data = String::new();
+    let mut linked = false;
+    let mut shifted = false;
+    for &code in &codes[1..] {
+        linked = false;
+        let set = if shifted {
+            if code_set == CODE_CODE_A {
+                CODE_CODE_B
+            } else {
+                CODE_CODE_A
+            }
+        } else {
+            code_set
+        };
+        shifted = false;
+        match code {
+            CODE_FNC_1 => data.push(GS),
+            _ if set == CODE_CODE_C && code < 100 => data.push_str(&format!("{code:02}")),
+            0..=63 => data.push((b' ' + code) as char),
+            64..=95 if set == CODE_CODE_A => data.push((code - 64) as char),
+            64..=95 => data.push((b' ' + code) as char),
+            CODE_SHIFT if set != CODE_CODE_C => shifted = true,
+            CODE_CODE_C..=CODE_CODE_A if code != set => {
+                code_set = code;
+                linked = true;
+            }
+            // FNC2, FNC3 and FNC4 have no place in GS1 data
+            _ => return None,
+        }
+    }
+    if !linked {
+        return None;
+    }
+
+    let mut element_strings = String::new();
+    for field in data.split(GS) {
+        element_strings.push_str(&field_parser::parseFieldsInGeneralPurpose(field).ok()?);
+    }
+    Some(element_strings)
+}
+
+/// A row of a 2D component as read from the image.
+struct ScannedRow {
+    /// Index into the bar counts of [`layouts`]
+    layout: usize,
+    raps: [u32; 3],
+    cluster: u32,
+    /// Left and right row indicators, CC-C only
+    indicators: [u32; 2],
+    codewords: Vec<Option<u32>>,
+}
+
+/// Row layouts by the number of bars they contain: the MicroPDF417 layouts of CC-A and CC-B
+/// components, told apart by their RAPs later on, then the CC-C layouts by number of columns.
+fn layouts(bars: usize) -> Option<(CompositeType, u32)> {
+    match bars {
+        15 => Some((CompositeType::CcA, 2)),
+        19 => Some((CompositeType::CcA, 3)),
+        22 => Some((CompositeType::CcB, 3)),
+        26 => Some((CompositeType::CcA, 4)),
+        21.. if (bars - 17) % 4 == 0 && bars <= 17 + 4 * 30 => {
+            Some((CompositeType::CcC, (bars as u32 - 17) / 4))
+        }
+        _ => None,
+    }
+}
+
+/// Scans upwards from the linear component for the rows of the 2D component and decodes them,
+/// returning its element strings and the top left and top right corners of the component.
+fn read_component(image: &BitMatrix, points: &[Point]) -> Result<(String, [Point; 2])> {
+    let left = points.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+    let right = points.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+    let linear_row = points.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+    if left >= right || linear_row < 1.0 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    // the component may overhang the linear component, by up to half its width for a CC-C
+    // component above a short GS1-128 symbol
+    let pad = (right - left) / 2.0;
+    let start = (left - pad).max(0.0) as u32;
+    let end = ((right + pad) as u32).min(image.getWidth());
+
+    let mut rows: Vec<ScannedRow> = Vec::new();
+    // UPC-E is the narrowest linear component, 51 modules between the points
+    let mut module = (right - left) / 51.0;
+    let mut gap = 0.0;
+    let mut top = [point(left, linear_row), point(right, linear_row)];
+    let linear_bars = row_runs(image, linear_row as u32, start, end).1.len();
+    let mut in_linear = true;
+    for y in (0..linear_row as u32).rev() {
+        let (x, runs) = row_runs(image, y, start, end);
+        // first cross the rest of the linear component
+        in_linear &= runs.len() == linear_bars;
+        if in_linear {
+            continue;
+        }
+        if let Some(row) = scan_row(&runs) {
+            let width: u32 = runs.iter().sum();
+            module =
+                width as f32 / row_layout_modules(row.layout).ok_or(Exceptions::NOT_FOUND)? as f32;
+            top = [
+                point(x as f32, y as f32),
+                point((x + width) as f32, y as f32),
+            ];
+            rows.push(row);
+            gap = 0.0;
+        } else {
+            gap += 1.0;
+            if gap > MAX_GAP_MODULES * module.max(1.0) {
+                break;
+            }
+        }
+    }
+
+    let Some(layout) = most_common(rows.iter().map(|r| r.layout)) else {
+        return Err(Exceptions::NOT_FOUND);
+    };
+    rows.retain(|r| r.layout == layout);
+    let (composite_type, columns) = layouts(layout).ok_or(Exceptions::NOT_FOUND)?;
+
+    let bits = if composite_type == CompositeType::CcC {
+        decode_cc_c(&rows, columns)?
+    } else {
+        decode_micro(&rows, layout, columns)?
+    };
+    Ok((encodation::decode(&bits)?, top))
+}
+
+fn row_layout_modules(bars: usize) -> Option<u32> {
+    let (composite_type, columns) = layouts(bars)?;
+    Some(
+        row_layout(composite_type, columns)
+            .iter()
+            .map(|e| e.modules(composite_type))
+            .sum(),
+    )
+}
+
+/// The x of the first bar and the widths of the bars and spaces of row `y` between `start`
+/// and `end`, from the first bar to the last one.
+fn row_runs(image: &BitMatrix, y: u32, start: u32, end: u32) -> (u32, Vec<u32>) {
+    let mut runs = Vec::new();
+    let mut first = None;
+    let mut colour = true;
+    let mut count = 0;
+    for x in start..end {
+        let black = image.get(x, y);
+        if first.is_none() {
+            if black {
+                first = Some(x);
+                count = 1;
+            }
+            continue;
+        }
+        if black == colour {
+            count += 1;
+        } else {
+            runs.push(count);
+            colour = black;
+            count = 1;
+        }
+    }
+    if colour && count > 0 {
+        runs.push(count);
+    }
+    (first.unwrap_or(start), runs)
+}
+
+/// Reads the RAPs, row indicators and codewords of a row of bar and space widths, None if the
+/// widths are not those of a component row.
+fn scan_row(runs: &[u32]) -> Option<ScannedRow> {
+    let bars = runs.len().div_ceil(2);
+    let (composite_type, columns) = layouts(bars)?;
+
+    let mut row = ScannedRow {
+        layout: bars,
+        raps: [0; 3],
+        cluster: 0,
+        indicators: [0; 2],
+        codewords: Vec::with_capacity(columns as usize),
+    };
+    let mut clusters = Vec::new();
+    let mut position = 0;
+    for element in row_layout(composite_type, columns) {
+        let count = (2 * element.bars(composite_type) as usize).min(runs.len() - position);
+        let widths = &runs[position..position + count];
+        position += count;
+        let modules = element.modules(composite_type);
+        let pattern = sample(widths, modules);
+
+        match element {
+            RowElement::LeftRap | RowElement::RightRap | RowElement::CentreRap => {
+                let table = if element == RowElement::CentreRap {
+                    &RAP_CENTRE
+                } else {
+                    &RAP_SIDE
+                };
+                let rap = table.iter().position(|&p| p == pattern)? as u32 + 1;
+                match element {
+                    RowElement::LeftRap => row.raps[0] = rap,
+                    RowElement::CentreRap => row.raps[1] = rap,
+                    _ => row.raps[2] = rap,
+                }
+            }
+            RowElement::Codeword | RowElement::LeftIndicator | RowElement::RightIndicator => {
+                let (value, cluster) = codeword(pattern)
+                    .map_or((None, None), |(value, cluster)| {
+                        (Some(value), Some(cluster))
+                    });
+                clusters.extend(cluster);
+                match element {
+                    RowElement::LeftIndicator => row.indicators[0] = value?,
+                    RowElement::RightIndicator => row.indicators[1] = value?,
+                    _ => row.codewords.push(value),
+                }
+            }
+            RowElement::Start | RowElement::Stop => {}
+        }
+    }
+
+    row.cluster = most_common(clusters.into_iter())?;
+    Some(row)
+}
+
+/// Samples the middle of each module of an element `modules` wide, most significant bit first.
+fn sample(widths: &[u32], modules: u32) -> u32 {
+    let total: u32 = widths.iter().sum();
+    let mut pattern = 0;
+    let mut run = 0;
+    let mut run_end = widths.first().copied().unwrap_or(0);
+    for m in 0..modules {
+        let centre = (2 * m + 1) * total;
+        while run + 1 < widths.len() && centre >= 2 * modules * run_end {
+            run += 1;
+            run_end += widths[run];
+        }
+        pattern = pattern << 1 | (run % 2 == 0) as u32;
+    }
+    pattern
+}
+
+/// The value and the cluster, 0, 1 or 2, of a 17 module codeword pattern.
+fn codeword(pattern: u32) -> Option<(u32, u32)> {
+    let value = getCodeword(pattern);
+    if value < 0 {
+        return None;
+    }
+    let mut bars = [0i32; 4];
+    let mut bar = 0;
+    for i in (0..17).rev() {
+        if pattern >> i & 1 == 1 {
+            bars[bar] += 1;
+        } else if i > 0 && pattern >> (i - 1) & 1 == 1 {
+            bar += 1;
+            if bar == 4 {
+                return None;
+            }
+        }
+    }
+    let cluster = (bars[0] - bars[1] + bars[2] - bars[3] + 9) % 9;
+    (cluster % 3 == 0).then_some((value as u32, cluster as u32 / 3))
+}
+
+fn most_common<T: Copy + Eq + std::hash::Hash>(values: impl Iterator<Item = T>) -> Option<T> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|&(_, count)| count)
+        .map(|(value, _)| value)
+}
+
+/// Votes for the codewords at each position of a symbol, then corrects them. Positions nothing
+/// was read at are erasures, if correcting them as errors fails.
+fn correct(votes: &[HashMap<u32, usize>], ec_codewords: u32) -> Result<Vec<u32>> {
+    let mut erasures = Vec::new();
+    let mut codewords: Vec<u32> = votes
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            v.iter()
+                .max_by_key(|&(_, count)| count)
+                .map(|(&value, _)| value)
+                .unwrap_or_else(|| {
+                    erasures.push(i as u32);
+                    0
+                })
+        })
+        .collect();
+    if error_correction::decode(&mut codewords, ec_codewords, &mut []).is_err() {
+        if erasures.is_empty() {
+            return Err(Exceptions::CHECKSUM);
+        }
+        error_correction::decode(&mut codewords, ec_codewords, &mut erasures)?;
+    }
+    Ok(codewords)
+}
+
+/// Decodes the rows of a CC-A or CC-B component to its bit stream.
+fn decode_micro(rows: &[ScannedRow], layout: usize, columns: u32) -> Result<BitArray> {
+    // with three columns the layouts of CC-A and CC-B differ, with two or four only the RAPs do
+    let layout_type = layouts(layout).map(|(t, _)| t);
+    let candidates = CC_A_VARIANTS
+        .iter()
+        .map(|v| (CompositeType::CcA, v))
+        .chain(CC_B_VARIANTS.iter().map(|v| (CompositeType::CcB, v)))
+        .filter(|&(composite_type, v)| {
+            v.columns == columns && (columns != 3 || layout_type == Some(composite_type))
+        });
+
+    // the variant is the one whose row addresses explain the most rows
+    let addresses = |variant: &MicroVariant| -> HashMap<([u32; 3], u32), u32> {
+        (0..variant.rows)
+            .map(|r| (variant.row_address(r), r))
+            .collect()
+    };
+    let (composite_type, variant, matched) = candidates
+        .map(|(t, v)| {
+            let addresses = addresses(v);
+            let matched = rows
+                .iter()
+                .filter(|r| addresses.contains_key(&(r.raps, r.cluster)))
+                .count();
+            (t, v, matched)
+        })
+        .max_by_key(|&(_, _, matched)| matched)
+        .ok_or(Exceptions::NOT_FOUND)?;
+    if matched == 0 {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let addresses = addresses(variant);
+    let mut votes = vec![HashMap::new(); (variant.rows * variant.columns) as usize];
+    for row in rows {
+        let Some(&r) = addresses.get(&(row.raps, row.cluster)) else {
+            continue;
+        };
+        for (c, value) in row.codewords.iter().enumerate() {
+            if let Some(value) = value {
+                *votes[(r * columns) as usize + c].entry(*value).or_default() += 1;
+            }
+        }
+    }
+    let codewords = correct(&votes, variant.ec_codewords)?;
+    let data = &codewords[..variant.data_codewords() as usize];
+
+    match composite_type {
+        CompositeType::CcA => decode_928(data, columns),
+        _ => {
+            if data.first() != Some(&920) {
+                return Err(Exceptions::format_with("CC-B component without 920"));
+            }
+            byte_decompaction(&data[1..])
+        }
+    }
+}
+
+/// Decodes the rows of a CC-C component to its bit stream.
+fn decode_cc_c(rows: &[ScannedRow], columns: u32) -> Result<BitArray> {
+    // row count, error correction level and column count, ISO/IEC 15438 5.7
+    let mut c1 = Vec::new();
+    let mut c2 = Vec::new();
+    let mut c3 = Vec::new();
+    for row in rows {
+        let [left, right] = row.indicators.map(|i| i % 30);
+        match row.cluster {
+            0 => {
+                c1.push(left);
+                c3.push(right);
+            }
+            1 => {
+                c2.push(left);
+                c1.push(right);
+            }
+            _ => {
+                c3.push(left);
+                c2.push(right);
+            }
+        }
+    }
+    let (Some(c1), Some(c2), Some(c3)) = (
+        most_common(c1.into_iter()),
+        most_common(c2.into_iter()),
+        most_common(c3.into_iter()),
+    ) else {
+        return Err(Exceptions::NOT_FOUND);
+    };
+    if c3 + 1 != columns {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    let row_count = 3 * c1 + c2 % 3 + 1;
+    let ec_codewords = cc_c_ec_codewords(c2 / 3);
+    // indicators misread from something other than a CC-C component can describe a symbol
+    // larger than PDF417 allows
+    if row_count * columns > MAX_CODEWORDS_IN_BARCODE || ec_codewords >= row_count * columns {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let mut votes = vec![HashMap::new(); (row_count * columns) as usize];
+    for row in rows {
+        let r = 3 * (row.indicators[0] / 30) + row.cluster;
+        if r >= row_count {
+            continue;
+        }
+        for (c, value) in row.codewords.iter().enumerate() {
+            if let Some(value) = value {
+                *votes[(r * columns) as usize + c].entry(*value).or_default() += 1;
+            }
+        }
+    }
+    let codewords = correct(&votes, ec_codewords)?;
+
+    let length = codewords[0] as usize;
+    if length < 2 || length > codewords.len() - ec_codewords as usize || codewords[1] != 920 {
+        return Err(Exceptions::format_with("invalid CC-C component"));
+    }
+    byte_decompaction(&codewords[2..length])
+}
+
+/// Converts the base 928 codewords of a CC-A component back to bits, 69 bits for each 7
+/// codewords.
+fn decode_928(codewords: &[u32], columns: u32) -> Result<BitArray> {
+    let size = CC_A_BITS[columns as usize - 2]
+        .iter()
+        .copied()
+        .find(|&bits| cc_a_codewords(bits) == codewords.len())
+        .ok_or(Exceptions::NOT_FOUND)?;
+
+    let mut bits = BitArray::new();
+    for (group, start) in codewords.chunks(7).zip((0..size).step_by(69)) {
+        let count = (size - start).min(69);
+        let value = group.iter().fold(0u128, |v, &c| v * 928 + c as u128);
+        if value >> count != 0 {
+            return Err(Exceptions::format_with("invalid CC-A codewords"));
+        }
+        for i in (0..count).rev() {
+            bits.appendBit(value >> i & 1 == 1);
+        }
+    }
+    Ok(bits)
+}
+
+/// Converts codewords in byte compaction mode, starting with the 901 or 924 latch, to bits.
+/// The data ends with the first codeword of 900 or more.
+fn byte_decompaction(codewords: &[u32]) -> Result<BitArray> {
+    let (&mode, rest) = codewords.split_first().ok_or(Exceptions::FORMAT)?;
+    if mode != 901 && mode != 924 {
+        return Err(Exceptions::format_with("component is not byte compacted"));
+    }
+    let data = &rest[..rest.iter().position(|&c| c >= 900).unwrap_or(rest.len())];
+
+    let mut bits = BitArray::new();
+    let mut position = 0;
+    while position < data.len() {
+        let remaining = data.len() - position;
+        // with 901 a last group of 5 codewords holds 5 bytes, not 6
+        if remaining >= 5 && (mode == 924 || remaining > 5) {
+            let total = data[position..position + 5]
+                .iter()
+                .fold(0u64, |t, &c| t * 900 + c as u64);
+            if total >> 48 != 0 {
+                return Err(Exceptions::format_with("invalid byte compaction group"));
+            }
+            for i in (0..6).rev() {
+                bits.appendBits((total >> (8 * i)) as usize & 0xFF, 8)?;
+            }
+            position += 5;
+        } else {
+            if data[position] > 0xFF {
+                return Err(Exceptions::format_with("invalid byte"));
+            }
+            bits.appendBits(data[position] as usize, 8)?;
+            position += 1;
+        }
+    }
+    Ok(bits)
+}
+
+#[cfg(all(test, feature = "image", feature = "encoders"))]
+mod tests {
+    use crate::{
+        BarcodeFormat, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintValue, DecodeHints,
+        MultiFormatReader, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
+        Writer,
+        common::{BitMatrix, HybridBinarizer},
+        pdf417::composite::CompositeWriter,
+    };
+
+    fn read_with_hints(matrix: &BitMatrix, hints: &DecodeHints) -> RXingResult {
+        let image: image::DynamicImage = matrix.into();
+        let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(
+            BufferedImageLuminanceSource::new(image),
+        ));
+        MultiFormatReader::default()
+            .decode_with_hints(&mut bitmap, hints)
+            .unwrap()
+    }
+
+    fn read(matrix: &BitMatrix) -> RXingResult {
+        read_with_hints(
+            matrix,
+            &DecodeHints::default().with(DecodeHintValue::DecodeComposite(true)),
+        )
+    }
+
+    fn assert_round_trip(contents: &str, format: BarcodeFormat, expected: &str) {
+        let matrix = CompositeWriter.encode(contents, &format, 400, 400).unwrap();
+        let result = read(&matrix);
+        assert_eq!(result.getText(), expected);
+        assert_eq!(result.getBarcodeFormat(), &format);
+        let (_, component) = contents.split_once('|').unwrap();
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::GS1_COMPOSITE),
+            Some(&RXingResultMetadataValue::Gs1Composite(
+                component.to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn ean_13_cc_a() {
+        assert_round_trip(
+            "331234567890|(10)ABC123",
+            BarcodeFormat::EAN_13,
+            "(01)03312345678903(10)ABC123",
+        );
+    }
+
+    #[test]
+    fn upc_e_cc_b() {
+        assert_round_trip(
+            "0123456|(10)ABCDEFGHIJKLMNOPQRST(21)abcdefghij",
+            BarcodeFormat::UPC_E,
+            "(01)00012345000065(10)ABCDEFGHIJKLMNOPQRST(21)abcdefghij",
+        );
+    }
+
+    #[test]
+    fn ean_8_cc_a_date() {
+        assert_round_trip(
+            "1234567|(17)251231(10)LOT",
+            BarcodeFormat::EAN_8,
+            "(01)00000012345670(17)251231(10)LOT",
+        );
+    }
+
+    #[test]
+    fn databar_cc_b() {
+        assert_round_trip(
+            "0331234567890|(17)251231(10)ABCDEFGHIJKLMNOPQRST(91)0123456789",
+            BarcodeFormat::RSS_14,
+            "(01)03312345678903(17)251231(10)ABCDEFGHIJKLMNOPQRST(91)0123456789",
+        );
+    }
+
+    #[test]
+    fn databar_expanded_cc_b() {
+        assert_round_trip(
+            "(01)93312345678906(3103)001234(15)251231|(21)abcdefghijklmnopqrst(91)XYZ",
+            BarcodeFormat::RSS_EXPANDED,
+            "(01)93312345678906(3103)001234(15)251231(21)abcdefghijklmnopqrst(91)XYZ",
+        );
+    }
+
+    #[test]
+    fn gs1_128_cc_b() {
+        let component = format!("(91){}(92){}", "A1b2C3d4e5".repeat(9), "x9Y8z7".repeat(13));
+        assert_round_trip(
+            &format!("(01)03812345678908|{component}"),
+            BarcodeFormat::CODE_128,
+            &format!("(01)03812345678908{component}"),
+        );
+    }
+
+    #[test]
+    fn gs1_128_cc_c() {
+        let component = format!(
+            "(91){}(92){}(93){}",
+            "A1b2C3d4e5".repeat(9),
+            "x9Y8z7".repeat(13),
+            "Qq12".repeat(20)
+        );
+        assert_round_trip(
+            &format!("(01)03812345678908|{component}"),
+            BarcodeFormat::CODE_128,
+            &format!("(01)03812345678908{component}"),
+        );
+    }
+
+    /// Scales the rows of a reference symbol, heights in modules and 0 for the linear row,
+    /// to 3 pixels a module inside a quiet zone.
+    fn reference_symbol(rows: &[(u32, &str)]) -> BitMatrix {
+        const SCALE: u32 = 3;
+        const QUIET: u32 = 10 * SCALE;
+        let width = rows[0].1.len() as u32 * SCALE + 2 * QUIET;
+        let height: u32 = rows
+            .iter()
+            .map(|(h, _)| if *h == 0 { 33 } else { *h })
+            .sum();
+        let mut matrix = BitMatrix::new(width, height * SCALE + 2 * QUIET).unwrap();
+        let mut top = QUIET;
+        for (h, row) in rows {
+            let h = if *h == 0 { 33 } else { *h } * SCALE;
+            for (x, module) in row.chars().enumerate() {
+                if module == '1' {
+                    matrix
+                        .setRegion(QUIET + x as u32 * SCALE, top, SCALE, h)
+                        .unwrap();
+                }
+            }
+            top += h;
+        }
+        matrix
+    }
+
+    #[test]
+    fn databar_cc_a() {
+        let matrix = reference_symbol(&[
+            (
+                2,
+                "1101101110100001001000010001011011110001110010011101001111101111010011010100110011000000110110001010",
+            ),
+            (
+                2,
+                "1101101100100001000111101001111101011011100010011001001111011001100100011101011001000000110010001010",
+            ),
+            (
+                2,
+                "1101101000100111000100001101100011111010001010011001101011000010111000010100011001111100111010001010",
+            ),
+            (
+                1,
+                "0000000000100011001110100001010101001001011011111001011101000011101010000000101010110000101111000000",
+            ),
+            (
+                0,
+                "0000010011011100110001001110000000010110100100000110100010111100010101111111000001001111010000110101",
+            ),
+        ]);
+        let result = read(&matrix);
+        assert_eq!(result.getText(), "(01)03312345678903(10)ABC123");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::RSS_14);
+    }
+
+    #[test]
+    fn databar_expanded_cc_a() {
+        let matrix = reference_symbol(&[
+            (
+                2,
+                "00110110111010000100100001000101101111000111001001110100111110111101001101010011001100000011011000101000000000000000000000000000000000",
+            ),
+            (
+                2,
+                "00110110110010000100011110100111110101101110001001100100111101100110010001110101100100000011001000101000000000000000000000000000000000",
+            ),
+            (
+                2,
+                "00110110100010011100010000110110001111101000101001100110101100001011100001010001100111110011101000101000000000000000000000000000000000",
+            ),
+            (
+                1,
+                "00001101011111101001000000001010010001100010000101101100000100010111010000101010000111010011110010001110000100101000010100000010100000",
+            ),
+            (
+                0,
+                "01000010100000010110111111110000101110011101111010010011111011101000101111000000111000101100001101110001111011010111100011111100001010",
+            ),
+        ]);
+        let result = read(&matrix);
+        assert_eq!(result.getText(), "(01)03312345678903(10)ABC123");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::RSS_EXPANDED);
+    }
+
+    #[test]
+    fn upside_down() {
+        let mut matrix = CompositeWriter
+            .encode(
+                "(01)03812345678908|(10)ABC123",
+                &BarcodeFormat::CODE_128,
+                0,
+                0,
+            )
+            .unwrap();
+        matrix.rotate180();
+        let result = read(&matrix);
+        assert_eq!(result.getText(), "(01)03812345678908(10)ABC123");
+    }
+
+    #[test]
+    fn component_ignored_without_hint() {
+        let matrix = CompositeWriter
+            .encode("331234567890|(10)ABC123", &BarcodeFormat::EAN_13, 0, 0)
+            .unwrap();
+        let result = read_with_hints(&matrix, &DecodeHints::default());
+        assert_eq!(result.getText(), "3312345678903");
+        assert!(
+            !result
+                .getRXingResultMetadata()
+                .contains_key(&RXingResultMetadataType::GS1_COMPOSITE)
+        );
+    }
+
+    #[test]
+    fn linear_without_component() {
+        let matrix = crate::oned::EAN13Writer
+            .encode("3312345678903", &BarcodeFormat::EAN_13, 0, 0)
+            .unwrap();
+        let result = read(&matrix);
+        assert_eq!(result.getText(), "3312345678903");
+        assert!(
+            !result
+                .getRXingResultMetadata()
+                .contains_key(&RXingResultMetadataType::GS1_COMPOSITE)
+        );
+    }
+}
@@ -0,0 +1,983 @@
+use crate::{
+    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    common::{BitArray, BitMatrix, Result},
+    oned::{
+        EAN8Writer, EAN13Writer, OneDimensionalCodeWriter, UPCEWriter,
+        oned_constants::code_128::CODE_PATTERNS,
+    },
+    pdf417::encoder::CODEWORD_TABLE,
+};
+
+use super::{
+    component::{
+        CC_A_BITS, CC_A_VARIANTS, CC_B_VARIANTS, CompositeType, MicroVariant, RAP_CENTRE, RAP_SIDE,
+        RowElement, START_PATTERN, STOP_PATTERN, cc_a_codewords, cc_c_ec_codewords,
+        error_correction, row_height, row_layout,
+    },
+    databar,
+    encodation::{Encodation, GS},
+    gs1,
+};
+
+const DEFAULT_MARGIN: u32 = 10;
+
+/// Height of the linear component, in modules, when the requested height leaves less room.
+const MIN_LINEAR_HEIGHT: usize = 20;
+
+/// Data bits a CC-B component holds, by number of columns.
+const CC_B_BITS: [&[usize]; 3] = [
+    &[56, 104, 160, 208, 256, 296, 336],
+    &[32, 72, 112, 152, 208, 304, 416, 536, 648, 768],
+    &[56, 96, 152, 208, 264, 352, 496, 672, 840, 1016, 1184],
+];
+
+const CODE_START_B: u32 = 104;
+const CODE_START_C: u32 = 105;
+const CODE_CODE_A: u32 = 101;
+const CODE_CODE_B: u32 = 100;
+const CODE_CODE_C: u32 = 99;
+const CODE_FNC_1: u32 = 102;
+const CODE_STOP: usize = 106;
+
+/**
+ * Renders a GS1 Composite symbol: a linear component with a CC-A, CC-B or CC-C 2D component
+ * stacked above it.
+ *
+ * The format is that of the linear component, one of EAN_13, EAN_8, UPC_A, UPC_E, RSS_14 for
+ * GS1 DataBar Omnidirectional, RSS_EXPANDED for a single row GS1 DataBar Expanded or CODE_128
+ * for GS1-128. The contents are the linear data, a `|` and the element strings of the 2D
+ * component in human readable form, for example `"331234567890|(10)ABC123"` or
+ * `"(01)03812345678908|(10)ABC123"`. GS1 DataBar Omnidirectional takes a GTIN of 13 digits,
+ * or 14 with the check digit, GS1 DataBar Expanded and GS1-128 element strings. The smallest
+ * component that holds the data is used, CC-C being only available with GS1-128.
+ */
+#[derive(Default)]
+pub struct CompositeWriter;
+
+impl Writer for CompositeWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        if contents.is_empty() {
+            return Err(Exceptions::illegal_argument_with("Found empty contents"));
+        }
+
+        let margin = hints
+            .Margin
+            .as_ref()
+            .and_then(|m| m.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_MARGIN);
+
+        encode_symbol(contents, format)?.render(width, height, margin)
+    }
+}
+
+/// A composite symbol as rows of modules from top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositeSymbol {
+    pub composite_type: CompositeType,
+    /// The 2D component and the separator pattern, with the height of each row in modules
+    pub rows: Vec<(usize, Vec<bool>)>,
+    /// The linear component, as wide as the other rows
+    pub linear: Vec<bool>,
+}
+
+impl CompositeSymbol {
+    pub fn width(&self) -> usize {
+        self.linear.len()
+    }
+
+    fn render(&self, width: i32, height: i32, margin: u32) -> Result<BitMatrix> {
+        let symbol_width = self.width();
+        let full_width = symbol_width + margin as usize;
+        let output_width = (width.max(0) as usize).max(full_width);
+        let multiple = output_width / full_width;
+        let left = (output_width - symbol_width * multiple) / 2;
+
+        let fixed_height = self.rows.iter().map(|(h, _)| h).sum::<usize>() * multiple;
+        let output_height =
+            (height.max(0) as usize).max(fixed_height + MIN_LINEAR_HEIGHT * multiple);
+
+        let mut output = BitMatrix::new(output_width as u32, output_height as u32)?;
+        let mut top = 0;
+        let rows = self
+            .rows
+            .iter()
+            .map(|(h, row)| (h * multiple, row))
+            .chain(std::iter::once((
+                output_height - fixed_height,
+                &self.linear,
+            )));
+        for (row_height, row) in rows {
+            for (x, _) in row.iter().enumerate().filter(|(_, set)| **set) {
+                output.setRegion(
+                    (left + x * multiple) as u32,
+                    top as u32,
+                    multiple as u32,
+                    row_height as u32,
+                )?;
+            }
+            top += row_height;
+        }
+        Ok(output)
+    }
+}
+
+/// Lays out the composite symbol for `contents` with a linear component of `format`.
+pub fn encode_symbol(contents: &str, format: &BarcodeFormat) -> Result<CompositeSymbol> {
+    let (linear, composite) = contents.split_once('|').ok_or_else(|| {
+        Exceptions::illegal_argument_with(
+            "Contents must be the linear data and the composite data separated by '|'",
+        )
+    })?;
+    let encodation = Encodation::encode(&gs1::reduce(composite)?)?;
+
+    match format {
+        BarcodeFormat::CODE_128 => return encode_gs1_128(&gs1::reduce(linear)?, &encodation),
+        BarcodeFormat::RSS_14 | BarcodeFormat::RSS_EXPANDED => {
+            return encode_databar(format, linear, &encodation);
+        }
+        _ => {}
+    }
+
+    let (columns, linear) = match format {
+        BarcodeFormat::EAN_13 => (4, EAN13Writer.encode_oned(linear)?),
+        BarcodeFormat::UPC_A => (4, EAN13Writer.encode_oned(&format!("0{linear}"))?),
+        BarcodeFormat::EAN_8 => (3, EAN8Writer.encode_oned(linear)?),
+        BarcodeFormat::UPC_E => (2, UPCEWriter.encode_oned(linear)?),
+        _ => {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Can only encode composites with EAN_13, EAN_8, UPC_A, UPC_E, RSS_14, RSS_EXPANDED or CODE_128, but got {format}"
+            )));
+        }
+    };
+
+    let (composite_type, component) = encode_micro_component(&encodation, columns)
+        .ok_or_else(|| Exceptions::illegal_argument_with("Composite data is too long"))?;
+
+    // ISO/IEC 24723 figure 5, the component overhangs the guard bars
+    let offset = match (format, composite_type) {
+        (BarcodeFormat::EAN_8, CompositeType::CcA) => 4,
+        (BarcodeFormat::EAN_8, _) => 14,
+        _ => 3,
+    };
+    let width = component[0].len();
+    let first = offset;
+    let last = offset + linear.len() - 1;
+
+    let mut rows: Vec<(usize, Vec<bool>)> = component
+        .into_iter()
+        .map(|row| (row_height(composite_type) as usize, row))
+        .collect();
+    for (left, right) in [(first, last), (first - 1, last + 1), (first, last)] {
+        let mut separator = vec![false; width];
+        separator[left] = true;
+        separator[right] = true;
+        rows.push((2, separator));
+    }
+
+    let mut padded = vec![false; width];
+    padded[offset..offset + linear.len()].copy_from_slice(&linear);
+
+    Ok(CompositeSymbol {
+        composite_type,
+        rows,
+        linear: padded,
+    })
+}
+
+/// Encodes a CC-A component, or a CC-B one if the data is too long, returning its rows.
+fn encode_micro_component(
+    encodation: &Encodation,
+    columns: u32,
+) -> Option<(CompositeType, Vec<Vec<bool>>)> {
+    let first_fit = |sizes: &[usize], len: usize| sizes.iter().copied().find(|&bits| len <= bits);
+    let column = columns as usize - 2;
+
+    if let Some(bits) = encodation.finish(|len| first_fit(CC_A_BITS[column], len)) {
+        let codewords = encode_928(&bits);
+        let variant = CC_A_VARIANTS
+            .iter()
+            .find(|v| v.columns == columns && v.data_codewords() as usize == codewords.len())?;
+        return Some((
+            CompositeType::CcA,
+            micro_rows(&codewords, variant, CompositeType::CcA),
+        ));
+    }
+
+    let bits = encodation.finish(|len| first_fit(CC_B_BITS[column], len))?;
+    let mut codewords = vec![920];
+    byte_compaction(&bits, &mut codewords);
+    let variant = CC_B_VARIANTS
+        .iter()
+        .find(|v| v.columns == columns && v.data_codewords() as usize >= codewords.len())?;
+    codewords.resize(variant.data_codewords() as usize, 900);
+    Some((
+        CompositeType::CcB,
+        micro_rows(&codewords, variant, CompositeType::CcB),
+    ))
+}
+
+/// Converts the bit stream of a CC-A component to base 928, 10 codewords for each 69 bits.
+fn encode_928(bits: &BitArray) -> Vec<u32> {
+    let size = bits.get_size();
+    let mut codewords = Vec::with_capacity(cc_a_codewords(size));
+    for start in (0..size).step_by(69) {
+        let count = (size - start).min(69);
+        let mut group = vec![0u32; count / 10 + 1];
+        for i in start..start + count {
+            // multiply by 2 and add the next bit, in base 928
+            let mut carry = bits.get(i) as u32;
+            for digit in group.iter_mut().rev() {
+                let value = *digit * 2 + carry;
+                *digit = value % 928;
+                carry = value / 928;
+            }
+        }
+        codewords.extend(group);
+    }
+    codewords
+}
+
+/// Appends the bytes of `bits` in byte compaction mode.
+fn byte_compaction(bits: &BitArray, codewords: &mut Vec<u32>) {
+    let bytes: Vec<u8> = (0..bits.get_size() / 8)
+        .map(|i| (0..8).fold(0, |byte, j| byte << 1 | bits.get(i * 8 + j) as u8))
+        .collect();
+    codewords.push(if bytes.len() % 6 == 0 { 924 } else { 901 });
+    let mut chunks = bytes.chunks_exact(6);
+    for chunk in chunks.by_ref() {
+        let mut total = chunk.iter().fold(0u64, |t, &b| t << 8 | b as u64);
+        let mut group = [0u32; 5];
+        for digit in group.iter_mut().rev() {
+            *digit = (total % 900) as u32;
+            total /= 900;
+        }
+        codewords.extend(group);
+    }
+    codewords.extend(chunks.remainder().iter().map(|&b| b as u32));
+}
+
+fn append_pattern(row: &mut Vec<bool>, pattern: u32, length: u32) {
+    row.extend((0..length).rev().map(|i| pattern >> i & 1 == 1));
+}
+
+/// Adds error correction to the data codewords and lays out the rows of a MicroPDF417 style
+/// component.
+fn micro_rows(
+    codewords: &[u32],
+    variant: &MicroVariant,
+    composite_type: CompositeType,
+) -> Vec<Vec<bool>> {
+    let mut codewords = codewords.to_vec();
+    codewords.extend(error_correction(&codewords, variant.ec_codewords as usize));
+
+    let layout = row_layout(composite_type, variant.columns);
+    (0..variant.rows)
+        .map(|r| {
+            let ([left, centre, right], cluster) = variant.row_address(r);
+            let mut row_codewords = codewords
+                [(r * variant.columns) as usize..((r + 1) * variant.columns) as usize]
+                .iter();
+            let mut row = Vec::new();
+            for element in &layout {
+                match element {
+                    RowElement::LeftRap => {
+                        append_pattern(&mut row, RAP_SIDE[left as usize - 1], 10)
+                    }
+                    RowElement::CentreRap => {
+                        append_pattern(&mut row, RAP_CENTRE[centre as usize - 1], 10)
+                    }
+                    RowElement::RightRap => {
+                        append_pattern(&mut row, RAP_SIDE[right as usize - 1], 10)
+                    }
+                    RowElement::Codeword => {
+                        let codeword = *row_codewords.next().unwrap_or(&900);
+                        append_pattern(
+                            &mut row,
+                            CODEWORD_TABLE[cluster as usize][codeword as usize],
+                            17,
+                        );
+                    }
+                    _ => row.push(true),
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+/// Lays out the rows of a CC-C component.
+fn cc_c_rows(codewords: &[u32], columns: u32, ec_level: u32) -> Vec<Vec<bool>> {
+    let mut codewords = codewords.to_vec();
+    codewords.extend(error_correction(
+        &codewords,
+        cc_c_ec_codewords(ec_level) as usize,
+    ));
+
+    let rows = codewords.len() as u32 / columns;
+    let c1 = (rows - 1) / 3;
+    let c2 = ec_level * 3 + (rows - 1) % 3;
+    let c3 = columns - 1;
+    let layout = row_layout(CompositeType::CcC, columns);
+    (0..rows)
+        .map(|r| {
+            let k = (r / 3) * 30;
+            let cluster = (r % 3) as usize;
+            let (left, right) = match cluster {
+                0 => (k + c1, k + c3),
+                1 => (k + c2, k + c1),
+                _ => (k + c3, k + c2),
+            };
+            let mut row_codewords =
+                codewords[(r * columns) as usize..((r + 1) * columns) as usize].iter();
+            let mut row = Vec::new();
+            for element in &layout {
+                match element {
+                    RowElement::Start => append_pattern(&mut row, START_PATTERN, 17),
+                    RowElement::LeftIndicator => {
+                        append_pattern(&mut row, CODEWORD_TABLE[cluster][left as usize], 17)
+                    }
+                    RowElement::RightIndicator => {
+                        append_pattern(&mut row, CODEWORD_TABLE[cluster][right as usize], 17)
+                    }
+                    RowElement::Codeword => {
+                        let codeword = *row_codewords.next().unwrap_or(&900);
+                        append_pattern(&mut row, CODEWORD_TABLE[cluster][codeword as usize], 17);
+                    }
+                    _ => append_pattern(&mut row, STOP_PATTERN, 18),
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+/// Picks Code 128 code sets B and C for the fewest symbol characters, returning the values
+/// from the start character up to the last data character and the final code set.
+fn gs1_128_values(reduced: &str) -> Result<(Vec<u32>, u32)> {
+    let data = reduced.as_bytes();
+    if let Some(b) = data
+        .iter()
+        .find(|&&b| b != GS as u8 && !(32..127).contains(&b))
+    {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "character {:?} cannot be encoded in GS1-128",
+            *b as char
+        )));
+    }
+    let is_digit_pair =
+        |i: usize| i + 1 < data.len() && data[i..i + 2].iter().all(u8::is_ascii_digit);
+
+    // cost[i][set] is the number of characters to encode data[i..] starting in that set
+    const B: usize = 0;
+    const C: usize = 1;
+    let n = data.len();
+    let mut cost = vec![[0usize; 2]; n + 1];
+    for i in (0..n).rev() {
+        let in_b = 1 + cost[i + 1][B];
+        let in_c = if data[i] == GS as u8 {
+            Some(1 + cost[i + 1][C])
+        } else if is_digit_pair(i) {
+            Some(1 + cost[i + 2][C])
+        } else {
+            None
+        };
+        cost[i][B] = in_c.map_or(in_b, |c| in_b.min(c + 1));
+        cost[i][C] = in_c.map_or(in_b + 1, |c| c.min(in_b + 1));
+    }
+
+    let mut set = if cost[0][C] <= cost[0][B] { C } else { B };
+    let mut values = vec![
+        if set == C { CODE_START_C } else { CODE_START_B },
+        CODE_FNC_1,
+    ];
+    let mut i = 0;
+    while i < n {
+        let in_c_possible = data[i] == GS as u8 || is_digit_pair(i);
+        let stay = if set == C {
+            in_c_possible
+                && cost[i][C]
+                    == 1 + if data[i] == GS as u8 {
+                        cost[i + 1][C]
+                    } else {
+                        cost[i + 2][C]
+                    }
+        } else {
+            cost[i][B] == 1 + cost[i + 1][B]
+        };
+        if !stay {
+            set = 1 - set;
+            values.push(if set == C { CODE_CODE_C } else { CODE_CODE_B });
+            continue;
+        }
+        if data[i] == GS as u8 {
+            values.push(CODE_FNC_1);
+            i += 1;
+        } else if set == C {
+            values.push(((data[i] - b'0') * 10 + data[i + 1] - b'0') as u32);
+            i += 2;
+        } else {
+            values.push((data[i] - b' ') as u32);
+            i += 1;
+        }
+    }
+    Ok((values, if set == C { CODE_CODE_C } else { CODE_CODE_B }))
+}
+
+/// Appends the linkage flag, check character and stop character and expands to modules.
+fn gs1_128_modules(values: &[u32], final_set: u32, composite_type: CompositeType) -> Vec<bool> {
+    // ISO/IEC 24723 section 7.4, the linkage flag is a code set change after the data
+    let linkage = match (composite_type, final_set) {
+        (CompositeType::CcC, CODE_CODE_C) => CODE_CODE_B,
+        (CompositeType::CcC, _) => CODE_CODE_A,
+        (_, CODE_CODE_C) => CODE_CODE_A,
+        _ => CODE_CODE_C,
+    };
+    let mut values = values.to_vec();
+    values.push(linkage);
+    let check = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| i.max(1) as u32 * v)
+        .sum::<u32>()
+        % 103;
+    values.push(check);
+
+    let mut modules = Vec::new();
+    for pattern in values
+        .iter()
+        .map(|&v| CODE_PATTERNS[v as usize])
+        .chain(std::iter::once(CODE_PATTERNS[CODE_STOP]))
+    {
+        for (i, &width) in pattern.iter().enumerate() {
+            modules.extend(std::iter::repeat_n(i % 2 == 0, width as usize));
+        }
+    }
+    modules
+}
+
+/// Lays out a GS1-128 composite, ISO/IEC 24723 section 12.3.
+fn encode_gs1_128(linear: &str, encodation: &Encodation) -> Result<CompositeSymbol> {
+    let (values, final_set) = gs1_128_values(linear)?;
+
+    let (composite_type, component, linear, cc_shift, linear_shift) =
+        if let Some((composite_type, component)) = encode_micro_component(encodation, 4) {
+            let linear = gs1_128_modules(&values, final_set, composite_type);
+            // align with the last space of the character at position (characters - 9) / 2
+            // counted from the stop character
+            let characters = (linear.len() - 2) / 11;
+            let position = (characters - 9) / 2;
+            let mut shift =
+                linear.len() as isize - position as isize * 11 - 1 - component[0].len() as isize;
+            if position != 0 {
+                shift -= 2;
+            }
+            (
+                composite_type,
+                component,
+                linear,
+                shift.max(0) as usize,
+                (-shift).max(0) as usize,
+            )
+        } else {
+            let linear = gs1_128_modules(&values, final_set, CompositeType::CcC);
+            let component = encode_cc_c(encodation, linear.len())
+                .ok_or_else(|| Exceptions::illegal_argument_with("Composite data is too long"))?;
+            (CompositeType::CcC, component, linear, 0, 7)
+        };
+
+    let separator: Vec<bool> = linear.iter().map(|m| !m).collect();
+    Ok(stack(
+        composite_type,
+        &component,
+        cc_shift,
+        &separator,
+        &linear,
+        linear_shift,
+    ))
+}
+
+/// Lays out a GS1 DataBar Omnidirectional or Expanded composite, ISO/IEC 24723 section 12.3.
+fn encode_databar(
+    format: &BarcodeFormat,
+    linear: &str,
+    encodation: &Encodation,
+) -> Result<CompositeSymbol> {
+    let (linear, separator, cc_shift, linear_shift) = if format == &BarcodeFormat::RSS_14 {
+        let linear = databar::omnidirectional(linear, true)?;
+        let separator = databar::omnidirectional_separator(&linear);
+        // the component overhangs the linear component by 4 modules on the left
+        (linear, separator, 0, 4)
+    } else {
+        let linear = databar::expanded(&gs1::reduce(linear)?, true)?;
+        let separator = databar::expanded_separator(&linear);
+        // the component starts above the check character
+        (linear, separator, 2, 0)
+    };
+
+    let (composite_type, component) = encode_micro_component(encodation, 4)
+        .ok_or_else(|| Exceptions::illegal_argument_with("Composite data is too long"))?;
+    Ok(stack(
+        composite_type,
+        &component,
+        cc_shift,
+        &separator,
+        &linear,
+        linear_shift,
+    ))
+}
+
+/// Stacks the 2D component, shifted right by `cc_shift` modules, above a one module high
+/// separator pattern and the linear component, both shifted by `linear_shift`.
+fn stack(
+    composite_type: CompositeType,
+    component: &[Vec<bool>],
+    cc_shift: usize,
+    separator: &[bool],
+    linear: &[bool],
+    linear_shift: usize,
+) -> CompositeSymbol {
+    let width = (cc_shift + component[0].len()).max(linear_shift + linear.len());
+    let place = |row: &[bool], shift: usize| {
+        let mut placed = vec![false; width];
+        placed[shift..shift + row.len()].copy_from_slice(row);
+        placed
+    };
+
+    let mut rows: Vec<(usize, Vec<bool>)> = component
+        .iter()
+        .map(|row| (row_height(composite_type) as usize, place(row, cc_shift)))
+        .collect();
+    rows.push((1, place(separator, linear_shift)));
+
+    CompositeSymbol {
+        composite_type,
+        rows,
+        linear: place(linear, linear_shift),
+    }
+}
+
+/// Encodes a CC-C component for a GS1-128 symbol `linear_width` modules wide.
+fn encode_cc_c(encodation: &Encodation, linear_width: usize) -> Option<Vec<Vec<bool>>> {
+    // the component may not be wider than the linear one allows, 7 modules shifted
+    let mut columns = if linear_width == 68 {
+        1
+    } else {
+        ((linear_width - 52) / 17).min(30)
+    };
+    let mut ec_level = 0;
+
+    let bits = encodation.finish(|len| {
+        let bytes = len.div_ceil(8);
+        let data_codewords = bytes / 6 * 5 + bytes % 6;
+        // ISO/IEC 15438 annex E recommended levels, limited to 30 by 30 codewords
+        ec_level = match data_codewords {
+            0..=40 => 2,
+            41..=160 => 3,
+            161..=320 => 4,
+            321..=833 => 5,
+            834..=865 => 4,
+            _ => return None,
+        };
+        let total = data_codewords + cc_c_ec_codewords(ec_level) as usize + 3;
+        let mut rows = total.div_ceil(columns);
+        while rows > 30 && columns < 30 {
+            columns += 1;
+            rows = total.div_ceil(columns);
+        }
+        if rows > 30 {
+            return None;
+        }
+        let target = columns * rows.max(3) - cc_c_ec_codewords(ec_level) as usize - 3;
+        Some(8 * (6 * (target / 5) + target % 5))
+    })?;
+
+    let mut codewords = vec![0, 920];
+    byte_compaction(&bits, &mut codewords);
+    codewords[0] = codewords.len() as u32;
+    Some(cc_c_rows(&codewords, columns as u32, ec_level))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, Writer};
+
+    use super::{CompositeType, encode_symbol};
+
+    /// Rows as printed by the reference encoder, heights in modules, 0 for the linear row.
+    fn assert_symbol(contents: &str, format: BarcodeFormat, expected: &[(usize, &str)]) {
+        let symbol = encode_symbol(contents, &format).unwrap();
+        let to_string = |row: &[bool]| {
+            row.iter()
+                .map(|&m| if m { '1' } else { '0' })
+                .collect::<String>()
+        };
+        let mut rows: Vec<(usize, String)> = symbol
+            .rows
+            .iter()
+            .map(|(h, row)| (*h, to_string(row)))
+            .collect();
+        rows.push((0, to_string(&symbol.linear)));
+        let expected: Vec<(usize, String)> = expected
+            .iter()
+            .map(|(h, row)| (*h, row.to_string()))
+            .collect();
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn ean_13_cc_a() {
+        assert_symbol(
+            "331234567890|(10)ABC123",
+            BarcodeFormat::EAN_13,
+            &[
+                (
+                    2,
+                    "110110111010000100100001000101101111000111001001110100111110111101001101010011001100000011011000101",
+                ),
+                (
+                    2,
+                    "110110110010000100011110100111110101101110001001100100111101100110010001110101100100000011001000101",
+                ),
+                (
+                    2,
+                    "110110100010011100010000110110001111101000101001100110101100001011100001010001100111110011101000101",
+                ),
+                (
+                    2,
+                    "000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010",
+                ),
+                (
+                    2,
+                    "001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001",
+                ),
+                (
+                    2,
+                    "000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010",
+                ),
+                (
+                    0,
+                    "000101011110100110010011011010000100111010110001010101010000100010010010001110100111001010000101010",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn gs1_128_cc_a() {
+        assert_symbol(
+            "(01)03812345678908|(10)ABC123",
+            BarcodeFormat::CODE_128,
+            &[
+                (
+                    2,
+                    "0000000000000000000001101101110100001001000010001011011110001110010011101001111101111010011010100110011000000110110001010000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0000000000000000000001101101100100001000111101001111101011011100010011001001111011001100100011101011001000000110010001010000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0000000000000000000001101101000100111000100001101100011111010001010011001101011000010111000010100011001111100111010001010000000000000000000000000",
+                ),
+                (
+                    1,
+                    "0010110001100001010001001100100110110110011101101000011000100100010100010011101111010011001001000010111001101100010100001010000100010011100010100",
+                ),
+                (
+                    0,
+                    "1101001110011110101110110011011001001001100010010111100111011011101011101100010000101100110110111101000110010011101011110101111011101100011101011",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn upc_e_cc_b() {
+        assert_symbol(
+            "0123456|(10)ABCDEFGHIJKLMNOPQRST(21)abcdefghij",
+            BarcodeFormat::UPC_E,
+            &[
+                (2, "1111010100111011111011101001000001000010001011110101001"),
+                (2, "1110010100111100100110011001000010011110100011100101001"),
+                (2, "1110110100110111110000101001011000010011100011101101001"),
+                (2, "1110100100110001100001001001011001111000111011101001001"),
+                (2, "1110100110100001101111100101111010001101100011101001101"),
+                (2, "1110101110100010000101111001001111000010001011101011101"),
+                (2, "1110101100100100001010000001001100111000011011101011001"),
+                (2, "1110101000111100110011000101001101011111100011101010001"),
+                (2, "1100101000101110001000011001010011001111100011001010001"),
+                (2, "1100101100111010000110111101110110001000110011001011001"),
+                (2, "1100101110101001111100011001110110001110001011001011101"),
+                (2, "1100100110111011011111100101110111100100111011001001101"),
+                (2, "1100110110100111011111011101110010000010111011001101101"),
+                (2, "1110110110100010000011110101111010010100000011101101101"),
+                (2, "1110010110110101100111111001101100100111100011100101101"),
+                (2, "1100010110100000101101100001110110100001100011000101101"),
+                (2, "1100010100111000001000110101100110111100010011000101001"),
+                (2, "1100110100110001110010111001001110100000011011001101001"),
+                (2, "1101110100111001000001011101011101110010000011011101001"),
+                (2, "1101100100111110010100011001110010110001000011011001001"),
+                (2, "0001000000000000000000000000000000000000000000000000010"),
+                (2, "0010000000000000000000000000000000000000000000000000001"),
+                (2, "0001000000000000000000000000000000000000000000000000010"),
+                (0, "0001010110011001001101111010011101011100101011110101010"),
+            ],
+        );
+    }
+
+    #[test]
+    fn databar_cc_a() {
+        assert_symbol(
+            "0331234567890|(10)ABC123",
+            BarcodeFormat::RSS_14,
+            &[
+                (
+                    2,
+                    "1101101110100001001000010001011011110001110010011101001111101111010011010100110011000000110110001010",
+                ),
+                (
+                    2,
+                    "1101101100100001000111101001111101011011100010011001001111011001100100011101011001000000110010001010",
+                ),
+                (
+                    2,
+                    "1101101000100111000100001101100011111010001010011001101011000010111000010100011001111100111010001010",
+                ),
+                (
+                    1,
+                    "0000000000100011001110100001010101001001011011111001011101000011101010000000101010110000101111000000",
+                ),
+                (
+                    0,
+                    "0000010011011100110001001110000000010110100100000110100010111100010101111111000001001111010000110101",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn databar_cc_b() {
+        assert_symbol(
+            "03312345678903|(17)251231(10)ABCDEFGHIJKLMNOPQRST(91)0123456789",
+            BarcodeFormat::RSS_14,
+            &[
+                (
+                    2,
+                    "1100101110100110101111110001111100100110111010000110101001110101111100011001000001110100110110110010",
+                ),
+                (
+                    2,
+                    "1100100110100011011001111001111110101101000010001110101000111011001110010111111011100100110110100010",
+                ),
+                (
+                    2,
+                    "1100110110100001100110000101011110011110100010001100101111011101000011011100011000101100110100100010",
+                ),
+                (
+                    2,
+                    "1110110110111000010000110101101100000111101010001000101111100110110011010010111110110000110101100010",
+                ),
+                (
+                    2,
+                    "1110010110110110110011111001001100101110000010011000101101110101110000010010101111000000110101110010",
+                ),
+                (
+                    2,
+                    "1100010110101110001111001101000100001010000010011100101100111011000001010001000010010000110101111010",
+                ),
+                (
+                    2,
+                    "1100010100111101100001100101111011000110010010011110101000111110101110011110100010000010110100111010",
+                ),
+                (
+                    1,
+                    "0000000000100011001110100001010101001001011011111001011101000011101010000000101010110000101111000000",
+                ),
+                (
+                    0,
+                    "0000010011011100110001001110000000010110100100000110100010111100010101111111000001001111010000110101",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn databar_expanded_cc_a() {
+        assert_symbol(
+            "(01)03312345678903|(10)ABC123",
+            BarcodeFormat::RSS_EXPANDED,
+            &[
+                (
+                    2,
+                    "00110110111010000100100001000101101111000111001001110100111110111101001101010011001100000011011000101000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "00110110110010000100011110100111110101101110001001100100111101100110010001110101100100000011001000101000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "00110110100010011100010000110110001111101000101001100110101100001011100001010001100111110011101000101000000000000000000000000000000000",
+                ),
+                (
+                    1,
+                    "00001101011111101001000000001010010001100010000101101100000100010111010000101010000111010011110010001110000100101000010100000010100000",
+                ),
+                (
+                    0,
+                    "01000010100000010110111111110000101110011101111010010011111011101000101111000000111000101100001101110001111011010111100011111100001010",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn databar_expanded_general_field() {
+        assert_symbol(
+            "(10)X1(21)1234567|(10)ABC",
+            BarcodeFormat::RSS_EXPANDED,
+            &[
+                (
+                    2,
+                    "001101101110100001001000010001011011110001110010011101001111011110100100011011001100001100110110001010000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "001101101100111011011100001001111101100011011010011001001001100011111010011101011001000000110010001010000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "001101101000110111110101000001000111010000110010011001101001111001000001011100110010111110111010001010000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    1,
+                    "000001000111011110010000000010100101011100110000011000101001000000110100000010100001000100001001111011000001000010110101000000101001000010111101100010111110110100011101000000001000000",
+                ),
+                (
+                    0,
+                    "010010111000100001101111111100001010100011001111100111010110111111001011111100001110111011110110000100111110111101001000111111000010111101000010011101000001001011100010111111110011101",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn databar_expanded_cc_b() {
+        assert_symbol(
+            "(01)03312345678903(10)LOT42X(21)12345|(21)abcdefghijklmnopqrst(91)XYZ",
+            BarcodeFormat::RSS_EXPANDED,
+            &[
+                (
+                    2,
+                    "0011001011101101001111000011010000001011110010100001101010001001111000010111001101111001101101101100100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0011001001101001111101101000010010000010011110100011101011000101011111000101000111011111101101101000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0011001101101011000111000110011001110011100110100011001010000110011101100111011001000011001101001000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0011101101101011110011011111011110001000001010100010001011011011110010000111101000010000101101011000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0011100101101011001000011100010011001000001110100110001010011110100001000110111110001001001101011100100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0011000101101111010001001111010100011000110000100111001011001111011101000101110111001000001101011110100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    2,
+                    "0011000101001110000001001101011101101110000010100111101010000010111100100110100111100001101101001110100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                ),
+                (
+                    1,
+                    "0000111000010111000100000000101001001100110000001010110000010001011101000001010100011101001111001000111000010010100001010000001010010001110011011110101011100001001111010000000010000110101110000111001010000000101000011100111111110100110000100110001000010000101111010100000000011001011000001110000000",
+                ),
+                (
+                    0,
+                    "0101000111101000111011111111000010110011001111110101001111101110100010111110000001100010110000110111000111101101011110001111110000101110001100100001010100011110110000101111111100111001010001111000110101111111010111100011000000001011001111011001110111101111010000101011111111100110100111110001110101",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn chooses_smallest_component() {
+        let cc_a = encode_symbol("0123456|(10)A", &BarcodeFormat::UPC_E).unwrap();
+        assert_eq!(cc_a.composite_type, CompositeType::CcA);
+        let cc_b = encode_symbol(
+            "0123456|(10)ABCDEFGHIJKLMNOPQRST(21)abcdefghij",
+            &BarcodeFormat::UPC_E,
+        )
+        .unwrap();
+        assert_eq!(cc_b.composite_type, CompositeType::CcB);
+        let cc_c = encode_symbol(
+            &format!(
+                "(01)03812345678908|(91){}(92){}(93){}",
+                "A1b2C3d4e5".repeat(9),
+                "x9Y8z7".repeat(13),
+                "Qq12".repeat(20)
+            ),
+            &BarcodeFormat::CODE_128,
+        )
+        .unwrap();
+        assert_eq!(cc_c.composite_type, CompositeType::CcC);
+        assert_eq!(cc_c.rows.len(), 30);
+        assert!(
+            cc_c.rows[..29]
+                .iter()
+                .all(|(h, row)| *h == 3 && row.len() == 188)
+        );
+        assert_eq!(cc_c.rows[29].0, 1);
+        assert!(
+            encode_symbol(
+                &format!("0123456|(91){}", "abcdefghijklmnopqrstuvwxyz1234".repeat(4)),
+                &BarcodeFormat::UPC_E
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn renders_linear_below_component() {
+        let matrix = super::CompositeWriter
+            .encode("331234567890|(10)ABC123", &BarcodeFormat::EAN_13, 0, 0)
+            .unwrap();
+        // 99 modules and the default margin
+        assert_eq!(matrix.getWidth(), 109);
+        // 3 component rows and 3 separator rows of 2 modules, then the linear component
+        assert_eq!(matrix.getHeight(), 12 + 20);
+        assert!(matrix.get(5, 0) && !matrix.get(5, 11) && matrix.get(8, 12));
+    }
+
+    #[test]
+    fn rejects_bad_contents() {
+        assert!(encode_symbol("331234567890", &BarcodeFormat::EAN_13).is_err());
+        assert!(encode_symbol("331234567890|10ABC", &BarcodeFormat::EAN_13).is_err());
+        assert!(encode_symbol("331234567890|(10)ABC", &BarcodeFormat::QR_CODE).is_err());
+    }
+}
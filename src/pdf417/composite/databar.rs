@@ -0,0 +1,351 @@
+//! The GS1 DataBar Omnidirectional and GS1 DataBar Expanded linear components of composite
+//! symbols, ISO/IEC 24724, and the separator patterns between them and the 2D component.
+
+use crate::{
+    Exceptions,
+    common::Result,
+    oned::{rss::rss_utils::getRSSwidths, upcean_common::checkStandardUPCEANChecksum},
+};
+
+use super::encodation::Encodation;
+
+/// Offset of the first value of each group of DataBar Omnidirectional data characters,
+/// outside characters then inside ones, tables 1 and 2.
+const OMNI_GSUM: [u32; 9] = [0, 161, 961, 2015, 2715, 0, 336, 1036, 1516];
+/// Number of values of the even subset of outside characters and odd subset of inside ones.
+const OMNI_TOTAL_SUBSET: [u32; 9] = [1, 10, 34, 70, 126, 4, 20, 48, 81];
+const OMNI_ODD_MODULES: [u32; 9] = [12, 10, 8, 6, 4, 5, 7, 9, 11];
+const OMNI_EVEN_MODULES: [u32; 9] = [4, 6, 8, 10, 12, 10, 8, 6, 4];
+const OMNI_ODD_WIDEST: [u32; 9] = [8, 6, 4, 3, 1, 2, 4, 6, 8];
+
+/// Table 4.
+const OMNI_FINDER_PATTERNS: [[u32; 5]; 9] = [
+    [3, 8, 2, 1, 1],
+    [3, 5, 5, 1, 1],
+    [3, 3, 7, 1, 1],
+    [3, 1, 9, 1, 1],
+    [2, 7, 4, 1, 1],
+    [2, 5, 6, 1, 1],
+    [2, 3, 8, 1, 1],
+    [1, 5, 7, 1, 1],
+    [1, 3, 9, 1, 1],
+];
+
+/// Table 5.
+const OMNI_CHECKSUM_WEIGHTS: [[u32; 8]; 4] = [
+    [1, 3, 9, 27, 2, 6, 18, 54],
+    [4, 12, 36, 29, 8, 24, 72, 58],
+    [16, 48, 65, 37, 32, 17, 51, 74],
+    [64, 34, 23, 69, 49, 68, 46, 59],
+];
+
+/// Width of a DataBar Omnidirectional symbol in modules.
+const OMNI_WIDTH: usize = 96;
+/// Start of the two finder patterns of a DataBar Omnidirectional symbol.
+const OMNI_FINDERS: [usize; 2] = [18, 63];
+
+/// Table 8.
+const EXPANDED_GSUM: [u32; 5] = [0, 348, 1388, 2948, 3988];
+const EXPANDED_EVEN_TOTAL_SUBSET: [u32; 5] = [4, 20, 52, 104, 204];
+const EXPANDED_ODD_MODULES: [u32; 5] = [12, 10, 8, 6, 4];
+const EXPANDED_ODD_WIDEST: [u32; 5] = [7, 5, 4, 3, 1];
+
+/// Table 14.
+const EXPANDED_CHECKSUM_WEIGHTS: [[u32; 8]; 23] = [
+    [1, 3, 9, 27, 81, 32, 96, 77],
+    [20, 60, 180, 118, 143, 7, 21, 63],
+    [189, 145, 13, 39, 117, 140, 209, 205],
+    [193, 157, 49, 147, 19, 57, 171, 91],
+    [62, 186, 136, 197, 169, 85, 44, 132],
+    [185, 133, 188, 142, 4, 12, 36, 108],
+    [113, 128, 173, 97, 80, 29, 87, 50],
+    [150, 28, 84, 41, 123, 158, 52, 156],
+    [46, 138, 203, 187, 139, 206, 196, 166],
+    [76, 17, 51, 153, 37, 111, 122, 155],
+    [43, 129, 176, 106, 107, 110, 119, 146],
+    [16, 48, 144, 10, 30, 90, 59, 177],
+    [109, 116, 137, 200, 178, 112, 125, 164],
+    [70, 210, 208, 202, 184, 130, 179, 115],
+    [134, 191, 151, 31, 93, 68, 204, 190],
+    [148, 22, 66, 198, 172, 94, 71, 2],
+    [6, 18, 54, 162, 64, 192, 154, 40],
+    [120, 149, 25, 75, 14, 42, 126, 167],
+    [79, 26, 78, 23, 69, 207, 199, 175],
+    [103, 98, 83, 38, 114, 131, 182, 124],
+    [161, 61, 183, 127, 170, 88, 53, 159],
+    [55, 165, 73, 8, 24, 72, 5, 15],
+    [45, 135, 194, 160, 58, 174, 100, 89],
+];
+
+/// Table 15, finder patterns A1, A2, B1, ... F2.
+const EXPANDED_FINDER_PATTERNS: [[u32; 5]; 12] = [
+    [1, 8, 4, 1, 1],
+    [1, 1, 4, 8, 1],
+    [3, 6, 4, 1, 1],
+    [1, 1, 4, 6, 3],
+    [3, 4, 6, 1, 1],
+    [1, 1, 6, 4, 3],
+    [3, 2, 8, 1, 1],
+    [1, 1, 8, 2, 3],
+    [2, 6, 5, 1, 1],
+    [1, 1, 5, 6, 2],
+    [2, 2, 9, 1, 1],
+    [1, 1, 9, 2, 2],
+];
+
+/// Table 16, the finder patterns of a symbol by its number of finder patterns, less 2.
+const EXPANDED_FINDER_SEQUENCES: [&[usize]; 10] = [
+    &[1, 2],
+    &[1, 4, 3],
+    &[1, 6, 3, 8],
+    &[1, 10, 3, 8, 5],
+    &[1, 10, 3, 8, 7, 12],
+    &[1, 10, 3, 8, 9, 12, 11],
+    &[1, 2, 3, 4, 5, 6, 7, 8],
+    &[1, 2, 3, 4, 5, 6, 7, 10, 9],
+    &[1, 2, 3, 4, 5, 6, 7, 10, 11, 12],
+    &[1, 2, 3, 4, 5, 8, 7, 10, 9, 12, 11],
+];
+
+/// The checksum weights of each data character, by number of data characters less 2, halved.
+const EXPANDED_WEIGHT_ROWS: [&[usize]; 10] = [
+    &[0, 1, 2],
+    &[0, 5, 6, 3, 4],
+    &[0, 9, 10, 3, 4, 13, 14],
+    &[0, 17, 18, 3, 4, 13, 14, 7, 8],
+    &[0, 17, 18, 3, 4, 13, 14, 11, 12, 21, 22],
+    &[0, 17, 18, 3, 4, 13, 14, 15, 16, 21, 22, 19, 20],
+    &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+    &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 17, 18, 15, 16],
+    &[
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 17, 18, 19, 20, 21, 22,
+    ],
+    &[
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 13, 14, 11, 12, 17, 18, 15, 16, 21, 22, 19, 20,
+    ],
+];
+
+/// Interleaves the widths of the odd and even elements of a data character.
+fn interleave(odd: [u32; 4], even: [u32; 4]) -> [u32; 8] {
+    let mut widths = [0; 8];
+    for i in 0..4 {
+        widths[2 * i] = odd[i];
+        widths[2 * i + 1] = even[i];
+    }
+    widths
+}
+
+/// Turns element widths, starting with a space, into modules.
+fn expand(widths: &[u32]) -> Vec<bool> {
+    widths
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &width)| std::iter::repeat_n(i % 2 == 1, width as usize))
+        .collect()
+}
+
+/// Encodes a GTIN, 13 digits or 14 with the check digit, as a DataBar Omnidirectional
+/// symbol, ISO/IEC 24724 section 6.2, flagging the presence of a 2D component if `linked`.
+pub fn omnidirectional(gtin: &str, linked: bool) -> Result<Vec<bool>> {
+    if !(13..=14).contains(&gtin.len()) || !gtin.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "GS1 DataBar needs a GTIN of 13 or 14 digits, but got {gtin}"
+        )));
+    }
+    if gtin.len() == 14 && !checkStandardUPCEANChecksum(gtin)? {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "invalid check digit in GTIN {gtin}"
+        )));
+    }
+
+    let mut value: u64 = gtin[..13].parse().map_err(|_| Exceptions::PARSE)?;
+    if linked {
+        value += 10_000_000_000_000;
+    }
+    let (left, right) = ((value / 4537077) as u32, (value % 4537077) as u32);
+    let characters = [left / 1597, left % 1597, right / 1597, right % 1597];
+
+    let mut widths = [[0; 8]; 4];
+    for (i, &character) in characters.iter().enumerate() {
+        // characters 1 and 3, counting from 0, are the inside ones
+        let outside = i % 2 == 0;
+        let groups = if outside { 0..4 } else { 5..8 };
+        let last = groups.end;
+        let group = groups
+            .into_iter()
+            .find(|&g| character < OMNI_GSUM[g + 1])
+            .unwrap_or(last);
+        let value = character - OMNI_GSUM[group];
+        let (quotient, remainder) = (
+            value / OMNI_TOTAL_SUBSET[group],
+            value % OMNI_TOTAL_SUBSET[group],
+        );
+        let (odd, even) = if outside {
+            (quotient, remainder)
+        } else {
+            (remainder, quotient)
+        };
+        widths[i] = interleave(
+            getRSSwidths(
+                odd,
+                OMNI_ODD_MODULES[group],
+                OMNI_ODD_WIDEST[group],
+                !outside,
+            ),
+            getRSSwidths(
+                even,
+                OMNI_EVEN_MODULES[group],
+                9 - OMNI_ODD_WIDEST[group],
+                outside,
+            ),
+        );
+    }
+
+    let mut checksum = widths
+        .iter()
+        .zip(OMNI_CHECKSUM_WEIGHTS)
+        .map(|(w, weights)| w.iter().zip(weights).map(|(w, k)| w * k).sum::<u32>())
+        .sum::<u32>()
+        % 79;
+    if checksum >= 8 {
+        checksum += 1;
+    }
+    if checksum >= 72 {
+        checksum += 1;
+    }
+    let (left_finder, right_finder) = (
+        OMNI_FINDER_PATTERNS[checksum as usize / 9],
+        OMNI_FINDER_PATTERNS[checksum as usize % 9],
+    );
+
+    let mut elements = vec![1, 1];
+    elements.extend(widths[0]);
+    elements.extend(left_finder);
+    elements.extend(widths[1].iter().rev());
+    elements.extend(widths[3]);
+    elements.extend(right_finder.iter().rev());
+    elements.extend(widths[2].iter().rev());
+    elements.extend([1, 1]);
+    Ok(expand(&elements))
+}
+
+/// The separator pattern between a DataBar Omnidirectional linear component and the 2D
+/// component above it, ISO/IEC 24723 section 5.3.
+pub fn omnidirectional_separator(linear: &[bool]) -> Vec<bool> {
+    let mut row = vec![false; OMNI_WIDTH];
+    for i in 4..OMNI_WIDTH - 4 {
+        row[i] = !linear[i];
+    }
+    // spaces of the finder patterns alternate, starting dark, from their left edge
+    for start in OMNI_FINDERS {
+        let mut dark = true;
+        for i in start..start + 13 {
+            if linear[i] {
+                row[i] = false;
+                dark = true;
+            } else {
+                row[i] = dark;
+                dark = !dark;
+            }
+        }
+    }
+    row
+}
+
+/// The widths of a DataBar Expanded symbol character.
+fn expanded_character(value: u32) -> [u32; 8] {
+    let group = (0..4).find(|&g| value < EXPANDED_GSUM[g + 1]).unwrap_or(4);
+    let value = value - EXPANDED_GSUM[group];
+    let subset = EXPANDED_EVEN_TOTAL_SUBSET[group];
+    interleave(
+        getRSSwidths(
+            value / subset,
+            EXPANDED_ODD_MODULES[group],
+            EXPANDED_ODD_WIDEST[group],
+            true,
+        ),
+        getRSSwidths(
+            value % subset,
+            17 - EXPANDED_ODD_MODULES[group],
+            9 - EXPANDED_ODD_WIDEST[group],
+            false,
+        ),
+    )
+}
+
+/// Encodes element strings in reduced form as a single row DataBar Expanded symbol,
+/// ISO/IEC 24724 section 7.2, flagging the presence of a 2D component if `linked`.
+pub fn expanded(reduced: &str, linked: bool) -> Result<Vec<bool>> {
+    let bits = Encodation::encode_expanded(reduced, linked)?
+        .finish_expanded()
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with("GS1 DataBar Expanded data is too long")
+        })?;
+
+    let characters: Vec<[u32; 8]> = (0..bits.get_size() / 12)
+        .map(|i| {
+            let value = (0..12).fold(0, |v, j| v << 1 | bits.get(i * 12 + j) as u32);
+            expanded_character(value)
+        })
+        .collect();
+
+    let weight_rows = EXPANDED_WEIGHT_ROWS[(characters.len() - 2) / 2];
+    let checksum = characters
+        .iter()
+        .zip(weight_rows)
+        .map(|(widths, &row)| {
+            widths
+                .iter()
+                .zip(EXPANDED_CHECKSUM_WEIGHTS[row])
+                .map(|(w, k)| w * k)
+                .sum::<u32>()
+        })
+        .sum::<u32>();
+    let symbol_characters = characters.len() + 1;
+    let check = expanded_character(211 * (symbol_characters as u32 - 4) + checksum % 211);
+
+    // pairs of symbol characters around a finder pattern, the second one reversed
+    let sequence = EXPANDED_FINDER_SEQUENCES[(symbol_characters - 1) / 2 - 1];
+    let mut elements = vec![1, 1];
+    for (pair, &finder) in sequence.iter().enumerate() {
+        let left = if pair == 0 {
+            &check
+        } else {
+            &characters[2 * pair - 1]
+        };
+        elements.extend(left);
+        elements.extend(EXPANDED_FINDER_PATTERNS[finder - 1]);
+        if let Some(right) = characters.get(2 * pair) {
+            elements.extend(right.iter().rev());
+        }
+    }
+    elements.extend([1, 1]);
+    Ok(expand(&elements))
+}
+
+/// The separator pattern between a DataBar Expanded linear component and the 2D component
+/// above it, ISO/IEC 24723 section 5.3.
+pub fn expanded_separator(linear: &[bool]) -> Vec<bool> {
+    let width = linear.len();
+    let mut row = vec![false; width];
+    for i in 4..width - 4 {
+        row[i] = !linear[i];
+    }
+    // the spaces over the first 13 modules of each finder pattern alternate, starting dark,
+    // the finder patterns of a pair being mirrored
+    let mut dark = true;
+    for (pair, mirrored) in (0..4).zip([false, true].into_iter().cycle()) {
+        let start = 49 * pair + 19;
+        let modules = if mirrored { 2..15 } else { 0..13 };
+        for i in modules.map(|i| start + i).filter(|&i| i < width) {
+            if linear[i] {
+                row[i] = false;
+                dark = true;
+            } else {
+                row[i] = dark;
+                dark = !dark;
+            }
+        }
+    }
+    row
+}
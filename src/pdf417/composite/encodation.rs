@@ -0,0 +1,769 @@
+//! The data encodation of composite components, ISO/IEC 24723 section 5.
+//!
+//! The data is a bit stream starting with an encodation method: "0" for a plain general
+//! purpose field, "10" for a leading date and lot number and "11" for a leading AI 90.
+//! The general purpose field is the same as the one of GS1 DataBar Expanded.
+
+#[cfg(feature = "encoders")]
+pub use encoder::*;
+
+#[cfg(feature = "decoders")]
+pub use decoder::*;
+
+/// Letters that the AI 90 compaction encodes in four bits.
+const AI_90_LETTERS: &str = "BDHIJKLNPQRSTVWZ";
+
+/// The GS1 group separator standing in for FNC1.
+pub const GS: char = '\u{1d}';
+
+#[cfg(feature = "encoders")]
+mod encoder {
+    use crate::{
+        Exceptions,
+        common::{BitArray, Result},
+        oned::upcean_common::checkStandardUPCEANChecksum,
+    };
+
+    use super::{AI_90_LETTERS, GS};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Mode {
+        Numeric,
+        Alphanumeric,
+        IsoIec646,
+    }
+
+    const ALPHANUMERIC_PUNCTUATION: &[u8] = b"*,-./";
+    const ISO_IEC_646_PUNCTUATION: &[u8] = b"!\"%&'()*+,-./:;<=>?_ ";
+
+    /// Parses the six digits of a date at `position`, returning its packed 16 bit value.
+    fn date_value(data: &[u8], position: usize) -> Option<u32> {
+        let digits = data.get(position..position + 6)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let pair = |i: usize| (digits[i] - b'0') as u32 * 10 + (digits[i + 1] - b'0') as u32;
+        let (yy, mm, dd) = (pair(0), pair(2), pair(4));
+        if mm == 0 || mm > 12 || dd > 31 {
+            return None;
+        }
+        Some(yy * 384 + (mm - 1) * 32 + dd)
+    }
+
+    /// The unpadded bit stream of a component along with the state needed to pad it.
+    #[derive(Debug, Clone)]
+    pub struct Encodation {
+        bits: Vec<bool>,
+        mode: Mode,
+        last_digit: Option<u8>,
+        alpha_pad: bool,
+        /// Position of the variable length field of a GS1 DataBar Expanded symbol
+        variable_length: Option<usize>,
+    }
+
+    impl Encodation {
+        /// Encodes element strings in reduced form: AIs and values without brackets, with a
+        /// GS after every value not of predefined length except the last.
+        pub fn encode(data: &str) -> Result<Self> {
+            let data = data.as_bytes();
+            let mut bits = Vec::new();
+            let mut mode = Mode::Numeric;
+            let mut alpha_pad = false;
+            let mut general_field = Vec::with_capacity(data.len() + 1);
+            let mut read = 0;
+            let mut crop: Option<(usize, usize)> = None;
+
+            let ai_90 = if data.starts_with(b"90") {
+                ai_90_split(&data[2..])
+            } else {
+                None
+            };
+
+            if data.starts_with(b"10")
+                || ((data.starts_with(b"11") || data.starts_with(b"17"))
+                    && date_value(data, 2).is_some())
+            {
+                append(&mut bits, 0b10, 2);
+                if data[1] == b'0' {
+                    append(&mut bits, 0b11, 2);
+                    read = 2;
+                } else {
+                    append(&mut bits, date_value(data, 2).unwrap_or_default(), 16);
+                    append(&mut bits, (data[1] == b'7') as u32, 1);
+                    read = 8;
+                    if data[read..].starts_with(b"10") {
+                        read += 2;
+                    } else if read < data.len() {
+                        general_field.push(GS as u8);
+                    } else {
+                        // a lone FNC1 can only be encoded in alphanumeric mode, which it leaves
+                        append(&mut bits, 0b000001111, 9);
+                    }
+                }
+            } else if let Some(letter_position) = ai_90 {
+                let field_end = data[2..]
+                    .iter()
+                    .position(|&b| b == GS as u8)
+                    .map_or(data.len(), |p| p + 2);
+                let field = &data[2..field_end];
+                let alpha = field.iter().filter(|b| b.is_ascii_uppercase()).count() - 1;
+                let numeric = field.iter().filter(|b| b.is_ascii_digit()).count() - letter_position;
+                let other = field.len() - alpha - numeric - letter_position - 1;
+
+                append(&mut bits, 0b11, 2);
+                let alpha_mode = other == 0 && alpha > numeric;
+                if alpha_mode {
+                    append(&mut bits, 0b11, 2);
+                } else if other == 0 && alpha == 0 {
+                    append(&mut bits, 0b10, 2);
+                } else {
+                    append(&mut bits, 0, 1);
+                    mode = Mode::Alphanumeric;
+                }
+
+                let next_ai = data.get(field_end + 1..).unwrap_or_default();
+                if next_ai.len() > 2 && next_ai.starts_with(b"21") {
+                    append(&mut bits, 0b10, 2);
+                    crop = Some((field_end + 1, 2));
+                } else if next_ai.len() > 4 && next_ai.starts_with(b"8004") {
+                    append(&mut bits, 0b11, 2);
+                    crop = Some((field_end + 1, 4));
+                } else {
+                    append(&mut bits, 0, 1);
+                }
+
+                let number = field[..letter_position]
+                    .iter()
+                    .fold(0, |n, &d| n * 10 + (d - b'0') as u32);
+                let letter = field[letter_position];
+                match AI_90_LETTERS.bytes().position(|l| l == letter) {
+                    Some(index) if number < 31 => {
+                        append(&mut bits, number, 5);
+                        append(&mut bits, index as u32, 4);
+                    }
+                    _ => {
+                        append(&mut bits, 31, 5);
+                        append(&mut bits, number, 10);
+                        append(&mut bits, (letter - b'A') as u32, 5);
+                    }
+                }
+
+                read = letter_position + 3;
+                if alpha_mode {
+                    while read < data.len() {
+                        let b = data[read];
+                        read += 1;
+                        if b.is_ascii_uppercase() {
+                            append(&mut bits, (b - b'A') as u32, 5);
+                        } else if b.is_ascii_digit() {
+                            append(&mut bits, b as u32 + 4, 6);
+                        } else {
+                            append(&mut bits, 31, 5);
+                            break;
+                        }
+                    }
+                    alpha_pad = true;
+                }
+            } else {
+                append(&mut bits, 0, 1);
+            }
+
+            let mut i = read;
+            while i < data.len() {
+                match crop {
+                    Some((position, length)) if position == i => i += length,
+                    _ => {
+                        general_field.push(data[i]);
+                        i += 1;
+                    }
+                }
+            }
+
+            let mut last_digit = None;
+            if !general_field.is_empty() {
+                alpha_pad = false;
+                last_digit = encode_general_field(&general_field, &mut mode, &mut bits)?;
+            }
+
+            Ok(Self {
+                bits,
+                mode,
+                last_digit,
+                alpha_pad,
+                variable_length: None,
+            })
+        }
+
+        /// Encodes element strings in reduced form as the data of a GS1 DataBar Expanded
+        /// linear component, ISO/IEC 24724 section 7.2.5, behind the linkage flag.
+        pub fn encode_expanded(data: &str, linked: bool) -> Result<Self> {
+            let method = expanded_method(data.as_bytes());
+            if method != 2 && !checkStandardUPCEANChecksum(&data[2..16])? {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "invalid check digit in GTIN {}",
+                    &data[2..16]
+                )));
+            }
+            let data = data.as_bytes();
+            let number = |range: std::ops::Range<usize>| {
+                data[range]
+                    .iter()
+                    .fold(0, |n, &d| n * 10 + (d - b'0') as u32)
+            };
+
+            // the variable length field, two bits, is set by finish_expanded
+            let mut bits = vec![linked];
+            let mut variable_length = None;
+            let read = match method {
+                1 => {
+                    append(&mut bits, 0b1, 1);
+                    variable_length = Some(bits.len());
+                    append(&mut bits, 0, 2);
+                    16
+                }
+                2 => {
+                    append(&mut bits, 0b00, 2);
+                    variable_length = Some(bits.len());
+                    append(&mut bits, 0, 2);
+                    0
+                }
+                3 | 4 => {
+                    append(&mut bits, 0b0100 + method - 3, 4);
+                    26
+                }
+                5 | 6 => {
+                    append(&mut bits, 0b01100 + method - 5, 5);
+                    variable_length = Some(bits.len());
+                    append(&mut bits, 0, 2);
+                    if method == 5 { 20 } else { 23 }
+                }
+                _ => {
+                    append(&mut bits, 0b0111000 + method - 7, 7);
+                    data.len()
+                }
+            };
+
+            if method != 2 {
+                // the indicator digit, known to be 9 past method 1, and the GTIN without
+                // its check digit
+                if method == 1 {
+                    append(&mut bits, number(2..3), 4);
+                }
+                for start in (3..15).step_by(3) {
+                    append(&mut bits, number(start..start + 3), 10);
+                }
+            }
+            match method {
+                3 | 4 => {
+                    // pounds to 0.001 are offset past the weights to 0.01
+                    let offset = if method == 4 && data[19] == b'3' {
+                        10000
+                    } else {
+                        0
+                    };
+                    append(&mut bits, number(20..26) + offset, 15);
+                }
+                5 | 6 => {
+                    append(&mut bits, number(19..20), 2);
+                    if method == 6 {
+                        append(&mut bits, number(20..23), 10);
+                    }
+                }
+                7..=14 => {
+                    // the decimal point position and five digits of the weight, then the date
+                    append(&mut bits, number(19..20) * 100000 + number(21..26), 20);
+                    let date = if data.len() == 34 {
+                        date_value(data, 28).unwrap_or_default()
+                    } else {
+                        38400
+                    };
+                    append(&mut bits, date, 16);
+                }
+                _ => {}
+            }
+
+            let mut mode = Mode::Numeric;
+            let last_digit = if read < data.len() {
+                encode_general_field(&data[read..], &mut mode, &mut bits)?
+            } else {
+                None
+            };
+
+            Ok(Self {
+                bits,
+                mode,
+                last_digit,
+                alpha_pad: false,
+                variable_length,
+            })
+        }
+
+        /// Completes the bit stream of a GS1 DataBar Expanded symbol, at least 3 and at most
+        /// 21 data characters of 12 bits, and sets its variable length field.
+        pub fn finish_expanded(&self) -> Option<BitArray> {
+            let mut bits = self.finish(|len| {
+                let size = len.div_ceil(12).max(3) * 12;
+                (size <= 252).then_some(size)
+            })?;
+            if let Some(position) = self.variable_length {
+                // parity and size of the symbol, counting the check character
+                let symbol_characters = bits.get_size() / 12 + 1;
+                if symbol_characters % 2 == 1 {
+                    bits.set(position);
+                }
+                if symbol_characters > 14 {
+                    bits.set(position + 1);
+                }
+            }
+            Some(bits)
+        }
+
+        /// Length of the bit stream before padding.
+        pub fn size(&self) -> usize {
+            self.bits.len()
+        }
+
+        /// Completes the bit stream, `target` giving the padded size of a stream of the
+        /// given length or `None` when it does not fit.
+        pub fn finish(&self, mut target: impl FnMut(usize) -> Option<usize>) -> Option<BitArray> {
+            let mut bits = self.bits.clone();
+            let mut size = target(bits.len())?;
+            if let Some(digit) = self.last_digit {
+                let remainder = size - bits.len();
+                if (4..=6).contains(&remainder) {
+                    append(&mut bits, digit as u32 + 1, 4);
+                    append(&mut bits, 0, remainder - 4);
+                } else {
+                    append(&mut bits, 11 * digit as u32 + 18, 7);
+                }
+                size = target(bits.len())?;
+            }
+            if bits.len() < size {
+                if self.alpha_pad {
+                    append(&mut bits, 31, 5);
+                }
+                if self.mode == Mode::Numeric {
+                    append(&mut bits, 0, 4);
+                }
+                while bits.len() < size {
+                    append(&mut bits, 0b00100, 5);
+                }
+            }
+            bits.truncate(size);
+
+            let mut array = BitArray::with_size(size);
+            for (i, _) in bits.iter().enumerate().filter(|(_, b)| **b) {
+                array.set(i);
+            }
+            Some(array)
+        }
+    }
+
+    /// Picks the GS1 DataBar Expanded encodation method for reduced element strings, ISO/IEC
+    /// 24724 table 10: 2 unless they start with AI 01, 3 to 14 when a GTIN with indicator
+    /// digit 9 is followed by a weight or price that is compressed with it, 1 otherwise.
+    fn expanded_method(data: &[u8]) -> u32 {
+        if data.len() < 16 || !data.starts_with(b"01") {
+            return 2;
+        }
+        if data.len() < 20 || data[2] != b'9' || data[16] != b'3' {
+            return 1;
+        }
+        let number = |range: std::ops::Range<usize>| {
+            data.get(range)
+                .filter(|digits| digits.iter().all(u8::is_ascii_digit))
+                .map(|digits| digits.iter().fold(0, |n, &d| n * 10 + (d - b'0') as u32))
+        };
+        // a production, packaging, best before or expiry date may follow the weight
+        let date = (data.len() == 34
+            && data[26] == b'1'
+            && matches!(data[27], b'1' | b'3' | b'5' | b'7')
+            && date_value(data, 28).is_some())
+        .then(|| (data[27] - b'0') as u32);
+
+        match (&data[17..19], number(20..26)) {
+            (b"10", Some(weight)) if weight <= 99999 => match (data.len(), date) {
+                (26, _) if data[19] == b'3' && weight <= 32767 => 3,
+                (26, _) => 7,
+                (_, Some(ai)) => 6 + ai,
+                _ => 1,
+            },
+            (b"20", Some(weight)) if weight <= 99999 => match (data.len(), date) {
+                (26, _)
+                    if (data[19] == b'2' && weight <= 9999)
+                        || (data[19] == b'3' && weight <= 22767) =>
+                {
+                    4
+                }
+                (26, _) => 8,
+                (_, Some(ai)) => 7 + ai,
+                _ => 1,
+            },
+            _ if data[17] == b'9' && (b'0'..=b'3').contains(&data[19]) => match data[18] {
+                b'2' => 5,
+                b'3' if number(20..23).is_some() => 6,
+                _ => 1,
+            },
+            _ => 1,
+        }
+    }
+
+    /// Returns the position of the letter that ends the leading number of an AI 90 field,
+    /// if the field qualifies for the AI 90 encodation.
+    fn ai_90_split(field: &[u8]) -> Option<usize> {
+        if field.first() == Some(&b'0') {
+            return None;
+        }
+        for (i, b) in field.iter().take(4).enumerate() {
+            if b.is_ascii_uppercase() {
+                return Some(i);
+            }
+            if !b.is_ascii_digit() {
+                break;
+            }
+        }
+        None
+    }
+
+    fn append(bits: &mut Vec<bool>, value: u32, count: usize) {
+        bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+    }
+
+    fn mode_of(b: u8) -> Result<Mode> {
+        if b == GS as u8 || b.is_ascii_digit() {
+            Ok(Mode::Numeric)
+        } else if b.is_ascii_uppercase() || ALPHANUMERIC_PUNCTUATION.contains(&b) {
+            Ok(Mode::Alphanumeric)
+        } else if b.is_ascii_lowercase() || ISO_IEC_646_PUNCTUATION.contains(&b) {
+            Ok(Mode::IsoIec646)
+        } else {
+            Err(Exceptions::illegal_argument_with(format!(
+                "character {:?} cannot be encoded in a composite component",
+                b as char
+            )))
+        }
+    }
+
+    /// Whether the `count` characters from `i` exist and all are of one of `modes`.
+    fn next_are(field: &[u8], i: usize, count: usize, modes: &[Mode]) -> bool {
+        i + count <= field.len()
+            && field[i..i + count]
+                .iter()
+                .all(|&b| mode_of(b).is_ok_and(|m| modes.contains(&m)))
+    }
+
+    /// Encodes the general purpose field, ISO/IEC 24723 section 5.4, returning a final
+    /// digit left over in numeric mode.
+    fn encode_general_field(
+        field: &[u8],
+        mode: &mut Mode,
+        bits: &mut Vec<bool>,
+    ) -> Result<Option<u8>> {
+        let fnc1_or_digit = |b: u8| if b == GS as u8 { 10 } else { (b - b'0') as u32 };
+        let mut i = 0;
+        while i < field.len() {
+            let b = field[i];
+            let kind = mode_of(b)?;
+            match *mode {
+                Mode::Numeric => {
+                    if i + 1 < field.len() {
+                        if kind != Mode::Numeric || mode_of(field[i + 1])? != Mode::Numeric {
+                            append(bits, 0, 4);
+                            *mode = Mode::Alphanumeric;
+                        } else {
+                            append(
+                                bits,
+                                11 * fnc1_or_digit(b) + fnc1_or_digit(field[i + 1]) + 8,
+                                7,
+                            );
+                            i += 2;
+                        }
+                    } else if kind != Mode::Numeric {
+                        append(bits, 0, 4);
+                        *mode = Mode::Alphanumeric;
+                    } else if b == GS as u8 {
+                        // a final FNC1 is never produced by a reduced string
+                        i += 1;
+                    } else {
+                        return Ok(Some(b - b'0'));
+                    }
+                }
+                Mode::Alphanumeric => {
+                    if b == GS as u8 {
+                        append(bits, 0b01111, 5);
+                        *mode = Mode::Numeric;
+                        i += 1;
+                    } else if kind == Mode::IsoIec646 {
+                        append(bits, 0b00100, 5);
+                        *mode = Mode::IsoIec646;
+                    } else if next_are(field, i, 6, &[Mode::Numeric])
+                        || (field.len() - i <= 5
+                            && next_are(field, i, field.len() - i, &[Mode::Numeric])
+                            && field.len() - i >= 4)
+                    {
+                        append(bits, 0, 3);
+                        *mode = Mode::Numeric;
+                    } else if b.is_ascii_digit() {
+                        append(bits, b as u32 - 43, 5);
+                        i += 1;
+                    } else if b.is_ascii_uppercase() {
+                        append(bits, b as u32 - 33, 6);
+                        i += 1;
+                    } else {
+                        let index = ALPHANUMERIC_PUNCTUATION
+                            .iter()
+                            .position(|&p| p == b)
+                            .unwrap_or_default();
+                        append(bits, index as u32 + 58, 6);
+                        i += 1;
+                    }
+                }
+                Mode::IsoIec646 => {
+                    if b == GS as u8 {
+                        append(bits, 0b01111, 5);
+                        *mode = Mode::Numeric;
+                        i += 1;
+                        continue;
+                    }
+                    let next_10_not_iso = field[i..]
+                        .iter()
+                        .take(10)
+                        .all(|&b| mode_of(b).is_ok_and(|m| m != Mode::IsoIec646));
+                    if next_10_not_iso && next_are(field, i, 4, &[Mode::Numeric]) {
+                        append(bits, 0, 3);
+                        *mode = Mode::Numeric;
+                    } else if next_10_not_iso
+                        && next_are(field, i, 5, &[Mode::Alphanumeric, Mode::Numeric])
+                    {
+                        append(bits, 0b00100, 5);
+                        *mode = Mode::Alphanumeric;
+                    } else if b.is_ascii_digit() {
+                        append(bits, b as u32 - 43, 5);
+                        i += 1;
+                    } else if b.is_ascii_uppercase() {
+                        append(bits, b as u32 - 1, 7);
+                        i += 1;
+                    } else if b.is_ascii_lowercase() {
+                        append(bits, b as u32 - 7, 7);
+                        i += 1;
+                    } else {
+                        let index = ISO_IEC_646_PUNCTUATION
+                            .iter()
+                            .position(|&p| p == b)
+                            .unwrap_or_default();
+                        append(bits, index as u32 + 232, 8);
+                        i += 1;
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "decoders")]
+mod decoder {
+    use crate::{
+        Exceptions,
+        common::{BitArray, Result},
+        oned::rss::expanded::decoders::{
+            DecodedObject, GeneralAppIdDecoder, field_parser::parseFieldsInGeneralPurpose,
+        },
+    };
+
+    use super::AI_90_LETTERS;
+
+    fn read(bits: &BitArray, position: &mut usize, count: usize) -> Result<u32> {
+        if *position + count > bits.get_size() {
+            return Err(Exceptions::FORMAT);
+        }
+        let value = GeneralAppIdDecoder::extractNumericValueFromBitArrayWithInformation(
+            bits,
+            *position,
+            count as u32,
+        );
+        *position += count;
+        Ok(value)
+    }
+
+    /// Decodes the bit stream of a component to element strings in human readable form,
+    /// "(10)ABC123" for example.
+    pub fn decode(bits: &BitArray) -> Result<String> {
+        let mut position = 0;
+        let mut result = String::new();
+        let mut prefix = String::new();
+        let mut cropped_ai = None;
+        let mut alpha_general_field = false;
+
+        if read(bits, &mut position, 1)? == 1 {
+            if read(bits, &mut position, 1)? == 0 {
+                // date and lot number
+                if read(bits, &mut position, 2)? == 0b11 {
+                    prefix.push_str("10");
+                } else {
+                    position -= 2;
+                    let date = read(bits, &mut position, 16)?;
+                    let ai = if read(bits, &mut position, 1)? == 1 {
+                        "17"
+                    } else {
+                        "11"
+                    };
+                    prefix = format!(
+                        "{ai}{:02}{:02}{:02}",
+                        date / 384,
+                        date % 384 / 32 + 1,
+                        date % 32
+                    );
+                    // the lot number follows unless the general field opens with FNC1
+                    let first =
+                        GeneralAppIdDecoder::new(bits).decodeGeneralPurposeField(position, "")?;
+                    if !first.getNewString().is_empty() {
+                        prefix.push_str("10");
+                    }
+                }
+            } else {
+                // AI 90
+                let mode = if read(bits, &mut position, 1)? == 0 {
+                    0
+                } else {
+                    2 + read(bits, &mut position, 1)?
+                };
+                if read(bits, &mut position, 1)? == 1 {
+                    cropped_ai = Some(if read(bits, &mut position, 1)? == 0 {
+                        "21"
+                    } else {
+                        "8004"
+                    });
+                }
+                let short_number = read(bits, &mut position, 5)?;
+                let (number, letter) = if short_number < 31 {
+                    let index = read(bits, &mut position, 4)? as usize;
+                    (short_number, AI_90_LETTERS.as_bytes()[index] as char)
+                } else {
+                    let number = read(bits, &mut position, 10)?;
+                    let letter = read(bits, &mut position, 5)?;
+                    if letter > 25 {
+                        return Err(Exceptions::FORMAT);
+                    }
+                    (number, (b'A' + letter as u8) as char)
+                };
+                prefix.push_str("90");
+                if number > 0 {
+                    prefix.push_str(&number.to_string());
+                }
+                prefix.push(letter);
+
+                match mode {
+                    3 => {
+                        // alpha mode, up to an FNC1
+                        while position + 5 <= bits.get_size() {
+                            let value = read(bits, &mut position, 5)?;
+                            if value == 31 {
+                                break;
+                            } else if value < 26 {
+                                prefix.push((b'A' + value as u8) as char);
+                            } else {
+                                position -= 5;
+                                let value = read(bits, &mut position, 6)?;
+                                if !(52..62).contains(&value) {
+                                    return Err(Exceptions::FORMAT);
+                                }
+                                prefix.push((value - 4) as u8 as char);
+                            }
+                        }
+                        result.push_str(&parseFieldsInGeneralPurpose(&prefix)?);
+                        prefix = cropped_ai.take().unwrap_or_default().to_owned();
+                    }
+                    0 => alpha_general_field = true,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut decoder = GeneralAppIdDecoder::new(bits);
+        decoder.setFnc1LatchesNumeric();
+        if alpha_general_field {
+            decoder.setAlphaMode();
+        }
+        let mut remaining = String::new();
+        loop {
+            let info = decoder.decodeGeneralPurposeField(position, &(prefix + &remaining))?;
+            result.push_str(&parseFieldsInGeneralPurpose(info.getNewString())?);
+            remaining = if info.isRemaining() {
+                info.getRemainingValue().to_string()
+            } else {
+                String::new()
+            };
+            prefix = cropped_ai.take().unwrap_or_default().to_owned();
+            if position == info.getNewPosition() {
+                break;
+            }
+            position = info.getNewPosition();
+        }
+
+        if result.is_empty() {
+            return Err(Exceptions::FORMAT);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "encoders", feature = "decoders"))]
+mod tests {
+    use super::{Encodation, decode};
+
+    fn bits(data: &str, size: usize) -> String {
+        let encodation = Encodation::encode(data).unwrap();
+        let array = encodation
+            .finish(|len| (len <= size).then_some(size))
+            .unwrap();
+        (0..array.get_size())
+            .map(|i| if array.get(i) { '1' } else { '0' })
+            .collect()
+    }
+
+    fn round_trip(data: &str, expected: &str) {
+        let encodation = Encodation::encode(data).unwrap();
+        let size = encodation.size().div_ceil(8) * 8 + 8;
+        let array = encodation.finish(|_| Some(size)).unwrap();
+        assert_eq!(decode(&array).unwrap(), expected, "{data:?}");
+    }
+
+    #[test]
+    fn lot_number() {
+        // "10" method, no date, then "ABC123" in the general field
+        assert!(bits("10ABC123", 59).starts_with("1011"));
+        round_trip("10ABC123", "(10)ABC123");
+    }
+
+    #[test]
+    fn dates() {
+        round_trip("1719123110LOT7", "(17)191231(10)LOT7");
+        round_trip("1120010121SER", "(11)200101(21)SER");
+        round_trip("17250600", "(17)250600");
+        round_trip("1725010121X", "(17)250101(21)X");
+    }
+
+    #[test]
+    fn ai_90() {
+        round_trip("9012B3456", "(90)12B3456");
+        round_trip("90ZZCAB", "(90)ZZCAB");
+        round_trip("90AB12\u{1d}21987", "(90)AB12(21)987");
+        round_trip("9099XA-b\u{1d}8004123", "(90)99XA-b(8004)123");
+    }
+
+    #[test]
+    fn general_field() {
+        round_trip("21abc-DEF\u{1d}3103001234", "(21)abc-DEF(3103)001234");
+        round_trip("0112345678901231", "(01)12345678901231");
+        round_trip("240A1\u{1d}91X", "(240)A1(91)X");
+    }
+
+    #[test]
+    fn long_fields() {
+        let a = "A1b2C3d4e5".repeat(9);
+        let b = "x9Y8z7".repeat(13);
+        round_trip(&format!("91{a}\u{1d}92{b}"), &format!("(91){a}(92){b}"));
+    }
+}
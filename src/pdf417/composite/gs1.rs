@@ -0,0 +1,72 @@
+//! Conversion between GS1 element strings in human readable form, "(01)03812345678908(10)ABC",
+//! and the reduced form carried by symbols, where AIs and values follow each other without
+//! brackets and a GS ends every value of variable length that is followed by another.
+
+use crate::{Exceptions, common::Result};
+
+use super::encodation::GS;
+
+/// Two digit AI prefixes whose element strings have a predefined length and so need no
+/// separator, GS1 General Specifications figure 7.8.5-2.
+const PREDEFINED_LENGTH_PREFIXES: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "23",
+    "31", "32", "33", "34", "35", "36",
+];
+
+fn has_predefined_length(ai: &str) -> bool {
+    PREDEFINED_LENGTH_PREFIXES.contains(&&ai[..2]) || ai.starts_with("41")
+}
+
+/// Converts element strings in human readable form to the reduced form.
+pub fn reduce(element_strings: &str) -> Result<String> {
+    if !element_strings.starts_with('(') {
+        return Err(Exceptions::illegal_argument_with(
+            "GS1 element strings must start with a bracketed AI",
+        ));
+    }
+
+    let mut reduced = String::with_capacity(element_strings.len());
+    let mut previous_ai: Option<&str> = None;
+    for element in element_strings[1..].split('(') {
+        let (ai, value) = element.split_once(')').ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!("unterminated AI in {element_strings}"))
+        })?;
+        if !(2..=4).contains(&ai.len()) || !ai.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "invalid AI ({ai})"
+            )));
+        }
+        if value.is_empty() {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "missing data for AI ({ai})"
+            )));
+        }
+        if previous_ai.is_some_and(|previous| !has_predefined_length(previous)) {
+            reduced.push(GS);
+        }
+        reduced.push_str(ai);
+        reduced.push_str(value);
+        previous_ai = Some(ai);
+    }
+    Ok(reduced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reduce;
+
+    #[test]
+    fn separators_follow_variable_length_values() {
+        assert_eq!(
+            reduce("(01)03812345678908(10)ABC123(21)X").unwrap(),
+            "010381234567890810ABC123\u{1d}21X"
+        );
+        assert_eq!(
+            reduce("(17)251231(3103)000123").unwrap(),
+            "172512313103000123"
+        );
+        assert!(reduce("10ABC").is_err());
+        assert!(reduce("(10)").is_err());
+        assert!(reduce("(1A)X").is_err());
+    }
+}
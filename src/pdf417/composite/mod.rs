@@ -0,0 +1,24 @@
+//! GS1 Composite symbols, ISO/IEC 24723.
+//!
+//! A composite symbol stacks a 2D component, CC-A, CC-B or CC-C, above an EAN/UPC, GS1 DataBar
+//! or GS1-128 linear component. The linear component flags that a 2D component is present and
+//! together they carry one GS1 message.
+
+pub mod component;
+pub mod encodation;
+
+#[cfg(feature = "encoders")]
+mod databar;
+
+#[cfg(feature = "encoders")]
+pub mod gs1;
+
+#[cfg(feature = "encoders")]
+mod composite_writer;
+#[cfg(feature = "encoders")]
+pub use composite_writer::*;
+
+#[cfg(feature = "decoders")]
+mod composite_reader;
+#[cfg(feature = "decoders")]
+pub use composite_reader::*;
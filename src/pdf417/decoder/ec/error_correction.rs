@@ -16,7 +16,7 @@
 
 use crate::{
     Exceptions,
-    common::Result,
+    common::{Result, reedsolomon::ERASURE_MARGIN},
     pdf417::{decoder::ec::ModulusGF, pdf_417_common::NUMBER_OF_CODEWORDS},
 };
 
@@ -38,9 +38,9 @@ static FLD_INTERIOR: Lazy<ModulusGF> = Lazy::new(|| ModulusGF::new(NUMBER_OF_COD
  */
 
 /**
- * @param received received codewords
+ * @param received received codewords, corrected in place unless decoding fails
  * @param numECCodewords number of those codewords used for EC
- * @param erasures location of erasures
+ * @param erasures location of erasures, which leave ERASURE_MARGIN of the EC codewords spare
  * @return number of errors
  * @throws ChecksumException if errors cannot be corrected, maybe because of too many errors
  */
@@ -61,13 +61,16 @@ pub fn decode(received: &mut [u32], numECCodewords: u32, erasures: &mut [u32]) -
     if !error {
         return Ok(0);
     }
+    if !erasures.is_empty() && erasures.len() + ERASURE_MARGIN > numECCodewords as usize {
+        return Err(Exceptions::checksum_with(file!()));
+    }
 
     let mut knownErrors: ModulusPoly = ModulusPoly::getOne(field);
     let mut b;
     let mut term;
     let mut kE: ModulusPoly;
     if !erasures.is_empty() {
-        for erasure in erasures {
+        for erasure in erasures.iter() {
             // for (int erasure : erasures) {
             b = field.exp(received.len() as u32 - 1 - *erasure);
             // Add (1 - bx) term:
@@ -77,32 +80,59 @@ pub fn decode(received: &mut [u32], numECCodewords: u32, erasures: &mut [u32]) -
         }
     }
 
-    let syndrome = ModulusPoly::new(field, S)?;
-    //syndrome = syndrome.multiply(knownErrors);
+    let mut syndrome = ModulusPoly::new(field, S)?;
+    if !erasures.is_empty() {
+        // Fold the erasures into the syndrome, modulo x^numECCodewords, so that only the unknown
+        // errors are left to locate
+        syndrome = syndrome.multiply(knownErrors.clone())?;
+        let coefficients = syndrome.getCoefficients();
+        let keep = coefficients.len().min(numECCodewords as usize);
+        syndrome = ModulusPoly::new(field, coefficients[coefficients.len() - keep..].to_vec())?;
+    }
 
     let sigmaOmega = runEuclideanAlgorithm(
         ModulusPoly::buildMonomial(field, numECCodewords as usize, 1),
         syndrome,
-        numECCodewords,
+        numECCodewords + erasures.len() as u32,
         field,
     )?;
-    let sigma = sigmaOmega[0].clone();
+    let mut sigma = sigmaOmega[0].clone();
     let omega = sigmaOmega[1].clone();
 
-    //sigma = sigma.multiply(knownErrors);
+    // Locate the erasures along with the errors
+    sigma = sigma.multiply(knownErrors)?;
 
     let mut errorLocations = findErrorLocations(sigma.clone(), field)?;
+    // The erasures are among the error locations, keep spare codewords like ReedSolomonDecoder
+    if !erasures.is_empty()
+        && 2 * errorLocations.len().saturating_sub(erasures.len()) + erasures.len() + ERASURE_MARGIN
+            > numECCodewords as usize
+    {
+        return Err(Exceptions::checksum_with(file!()));
+    }
     let errorMagnitudes = findErrorMagnitudes(omega, sigma, &mut errorLocations, field);
 
+    // Correct a copy, so that received is left alone if the correction is rejected
+    let mut corrected = received.to_vec();
     for i in 0..errorLocations.len() {
         // for (int i = 0; i < errorLocations.length; i++) {
-        let position = received.len() as isize - 1 - field.log(errorLocations[i])? as isize;
+        let position = corrected.len() as isize - 1 - field.log(errorLocations[i])? as isize;
         if position < 0 {
             return Err(Exceptions::checksum_with(file!()));
         }
-        received[position as usize] =
-            field.subtract(received[position as usize], errorMagnitudes[i]);
+        corrected[position as usize] =
+            field.subtract(corrected[position as usize], errorMagnitudes[i]);
+    }
+
+    // With erasures the code is stretched closer to its limit, check that the correction did
+    // land on a codeword
+    if !erasures.is_empty() {
+        let poly = ModulusPoly::new(field, corrected.clone())?;
+        if (1..=numECCodewords).any(|i| poly.evaluateAt(field.exp(i)) != 0) {
+            return Err(Exceptions::checksum_with(file!()));
+        }
     }
+    received.copy_from_slice(&corrected);
 
     Ok(errorLocations.len())
 }
@@ -126,7 +156,7 @@ fn runEuclideanAlgorithm(
     let mut t = ModulusPoly::getOne(field);
 
     // Run Euclidean algorithm until r's degree is less than R/2
-    while r.getDegree() >= R / 2 {
+    while 2 * r.getDegree() >= R {
         let rLastLast = rLast.clone();
         let tLastLast = tLast.clone();
         rLast = r;
@@ -16,10 +16,10 @@
 
 use rand::RngExt;
 
-use crate::common::Result;
+use crate::common::{Result, reedsolomon::ERASURE_MARGIN};
 
 use super::{
-    abstract_error_correction_test_case::{corrupt, getRandom},
+    abstract_error_correction_test_case::{corrupt, erase, getRandom},
     error_correction::decode,
 };
 
@@ -43,7 +43,7 @@ const PDF417_TEST_WITH_EC: [u32; 112] = [
 const ECC_BYTES: usize = PDF417_TEST_WITH_EC.len() - PDF417_TEST.len();
 const ERROR_LIMIT: usize = ECC_BYTES;
 const MAX_ERRORS: usize = ERROR_LIMIT / 2;
-const _MAX_ERASURES: usize = ERROR_LIMIT;
+const MAX_ERASURES: usize = ERROR_LIMIT - ERASURE_MARGIN;
 
 // private final ErrorCorrection ec = new ErrorCorrection();
 
@@ -99,6 +99,45 @@ fn testTooManyErrors() {
     // }
 }
 
+#[test]
+fn testMaxErasures() {
+    let mut random = getRandom();
+    for _test in PDF417_TEST {
+        // # iterations is kind of arbitrary
+        let mut received = PDF417_TEST_WITH_EC;
+        let mut erasures = erase(&mut received, MAX_ERASURES as u32, &mut random);
+        checkDecodeErasures(&mut received, &mut erasures).expect("ok");
+    }
+}
+
+#[test]
+fn testTooManyErasures() {
+    let mut random = getRandom();
+    let mut received = PDF417_TEST_WITH_EC;
+    let mut erasures = erase(&mut received, MAX_ERASURES as u32 + 3, &mut random);
+    assert!(checkDecodeErasures(&mut received, &mut erasures).is_err());
+}
+
+#[test]
+fn testErrorsAndErasures() {
+    let mut random = getRandom();
+    for numErasures in 0..=MAX_ERASURES {
+        let mut received = PDF417_TEST_WITH_EC;
+        let mut erasures = erase(&mut received, numErasures as u32, &mut random);
+        // Only corrupt codewords that are not erased
+        let mut errors = 0;
+        while errors < (MAX_ERASURES - numErasures) / 2 {
+            let location = random.random_range(0..received.len());
+            if erasures.contains(&(location as u32)) || received[location] == 610 {
+                continue;
+            }
+            received[location] = 610;
+            errors += 1;
+        }
+        checkDecodeErasures(&mut received, &mut erasures).expect("ok");
+    }
+}
+
 fn checkDecode(received: &mut [u32]) -> Result<()> {
     checkDecodeErasures(received, &mut [0_u32; 0])
 }
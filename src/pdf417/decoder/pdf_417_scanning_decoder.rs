@@ -18,7 +18,7 @@ use std::sync::Arc;
 
 use crate::{
     Exceptions, Point,
-    common::{BitMatrix, DecoderRXingResult, Result},
+    common::{BitMatrix, DecoderRXingResult, Result, reedsolomon::ERASURE_MARGIN},
     pdf417::pdf_417_common,
 };
 
@@ -471,12 +471,12 @@ fn createDecoderRXingResult(
 }
 
 /**
- * This method deals with the fact, that the decoding process doesn't always yield a single most likely value. The
- * current error correction implementation doesn't deal with erasures very well, so it's better to provide a value
- * for these ambiguous codewords instead of treating it as an erasure. The problem is that we don't know which of
- * the ambiguous values to choose. We try decode using the first value, and if that fails, we use another of the
- * ambiguous values and try to decode again. This usually only happens on very hard to read and decode barcodes,
- * so decoding the normal barcodes is not affected by this.
+ * This method deals with the fact, that the decoding process doesn't always yield a single most likely value. Each
+ * erasure uses up an error correction codeword, so it's better to provide a value for these ambiguous codewords
+ * instead of treating it as an erasure. The problem is that we don't know which of the ambiguous values to choose.
+ * We try decode using the first value, and if that fails, we use another of the ambiguous values and try to decode
+ * again. Only once every combination failed are the ambiguous codewords treated as erasures. This usually only
+ * happens on very hard to read and decode barcodes, so decoding the normal barcodes is not affected by this.
  *
  * @param erasureArray contains the indexes of erasures
  * @param ambiguousIndexes array with the indexes that have more than one most likely value
@@ -493,7 +493,7 @@ fn createDecoderRXingResultFromAmbiguousValues(
     let mut ambiguousIndexCount = vec![0; ambiguousIndexes.len()];
 
     let mut tries = 100;
-    while tries > 0 {
+    'tries: while tries > 0 {
         for i in 0..ambiguousIndexCount.len() {
             // for (int i = 0; i < ambiguousIndexCount.length; i++) {
             codewords[ambiguousIndexes[i] as usize] =
@@ -519,14 +519,22 @@ fn createDecoderRXingResultFromAmbiguousValues(
             } else {
                 ambiguousIndexCount[i] = 0;
                 if i == ambiguousIndexCount.len() - 1 {
-                    return Err(Exceptions::CHECKSUM);
+                    break 'tries;
                 }
             }
         }
 
         tries -= 1;
     }
-    Err(Exceptions::CHECKSUM)
+
+    // No choice of values decoded, leave the ambiguous codewords to error correction instead, as
+    // long as that keeps the spare codewords error correction wants with erasures
+    let mut erasures = [&*erasureArray, &*ambiguousIndexes].concat();
+    if erasures.len() + ERASURE_MARGIN > 1 << (ecLevel + 1) {
+        return Err(Exceptions::CHECKSUM);
+    }
+    erasures.sort_unstable();
+    decodeCodewords(codewords, ecLevel, &mut erasures)
 }
 
 fn createBarcodeMatrix(detectionRXingResult: &mut DetectionRXingResult) -> Vec<Vec<BarcodeValue>> {
@@ -883,7 +891,14 @@ fn correctErrors(
         // Too many errors or EC Codewords is corrupted
         return Err(Exceptions::CHECKSUM);
     }
-    ec::error_correction::decode(codewords, numECCodewords, erasures)
+    // The erasures were read as 0, try correcting them as errors before spending the spare
+    // codewords erasures need
+    match ec::error_correction::decode(codewords, numECCodewords, &mut []) {
+        Err(error) if !erasures.is_empty() => {
+            ec::error_correction::decode(codewords, numECCodewords, erasures).map_err(|_| error)
+        }
+        corrected => corrected,
+    }
 }
 
 /**
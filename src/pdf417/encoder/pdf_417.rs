@@ -357,7 +357,7 @@ const STOP_PATTERN: u32 = 0x3fa29;
 /**
  * The codeword table from the Annex A of ISO/IEC 15438:2001(E).
  */
-const CODEWORD_TABLE: [[u32; 929]; 3] = [
+pub(crate) const CODEWORD_TABLE: [[u32; 929]; 3] = [
     [
         0x1d5c0, 0x1eaf0, 0x1f57c, 0x1d4e0, 0x1ea78, 0x1f53e, 0x1a8c0, 0x1d470, 0x1a860, 0x15040,
         0x1a830, 0x15020, 0x1adc0, 0x1d6f0, 0x1eb7c, 0x1ace0, 0x1d678, 0x1eb3e, 0x158c0, 0x1ac70,
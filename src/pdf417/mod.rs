@@ -7,6 +7,9 @@ pub mod encoder;
 
 pub mod pdf_417_common;
 
+#[cfg(feature = "oned")]
+pub mod composite;
+
 mod pdf_417_result_metadata;
 pub use pdf_417_result_metadata::*;
 
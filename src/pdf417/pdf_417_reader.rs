@@ -32,7 +32,7 @@ use super::{
  *
  * @author Guenther Grau
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PDF417Reader;
 
 impl Reader for PDF417Reader {
@@ -0,0 +1,588 @@
+use crate::{
+    BarcodeFormat, Exceptions,
+    common::{BitMatrix, Result},
+    render::Style,
+};
+
+const MM_PER_INCH: f32 = 25.4;
+
+/// Minimum height of the bars of one dimensional symbols, from ISO/IEC 15417
+const MIN_BAR_HEIGHT_MM: f32 = 6.35;
+
+/// Minimum height of a row of PDF417, in modules, from ISO/IEC 15438 section 5.8.2
+const MIN_PDF417_ROW_HEIGHT: u32 = 3;
+
+/// Nominal heights of the parts of the bars of postal symbols from the top, in millimeters,
+/// and the rows of the writer output holding each part
+fn postal_parts(format: &BarcodeFormat) -> Option<&'static [(f32, PostalRow)]> {
+    use PostalRow::*;
+
+    match format {
+        // Full bars 0.125" and half bars 0.050", from the USPS Domestic Mail Manual 708.4
+        BarcodeFormat::POSTNET | BarcodeFormat::PLANET => Some(&[(1.905, Top), (1.27, Bottom)]),
+        // Full bars 0.145" and the tracker 0.048", from USPS-B-3200
+        BarcodeFormat::INTELLIGENT_MAIL => {
+            Some(&[(1.2319, Top), (1.2192, Middle), (1.2319, Bottom)])
+        }
+        // Full bars 5.1 mm and the tracker 1.3 mm, the middle of the Royal Mail ranges
+        BarcodeFormat::RM4SCC | BarcodeFormat::KIX => {
+            Some(&[(1.9, Top), (1.3, Middle), (1.9, Bottom)])
+        }
+        // Full bars 5 mm and the tracker 1.3 mm, from the Australia Post Customer Barcoding
+        // Technical Specifications
+        BarcodeFormat::AUSTRALIA_POST => Some(&[(1.85, Top), (1.3, Middle), (1.85, Bottom)]),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PostalRow {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// A length on the printed label
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Millimeters(f32),
+    /// Thousandths of an inch
+    Mils(f32),
+}
+
+impl Length {
+    pub fn to_millimeters(self) -> f32 {
+        match self {
+            Self::Millimeters(mm) => mm,
+            Self::Mils(mils) => mils * MM_PER_INCH / 1000.0,
+        }
+    }
+
+    /// The length in whole printer dots, rounded to the nearest
+    fn to_dots(self, dpi: u32) -> i32 {
+        (self.to_millimeters() * dpi as f32 / MM_PER_INCH).round() as i32
+    }
+}
+
+/// How a barcode is printed, for [`crate::Writer::encode_physical`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrintSpec {
+    /// Width of a module. It is rounded to a whole number of dots.
+    pub x_dimension: Length,
+
+    /// Resolution of the printer, in dots per inch
+    pub dpi: u32,
+
+    /// Width taken off each bar, and each dark module of matrix symbols, to make up for ink
+    /// or heat spreading into the spaces. It is rounded to a whole number of dots, split
+    /// between both edges, and a negative value widens them instead.
+    pub bar_width_reduction: Length,
+
+    /// Height of the bars of one dimensional symbols encoded as a single row. `None` uses
+    /// 15% of the width of the symbol, and at least 6.35 mm.
+    ///
+    /// For postal symbols it is the height of the full bars, the other parts keeping their
+    /// proportions, and `None` uses the nominal height of the postal service. For PDF417 it
+    /// is the height of each row, at least 3 modules, and `None` uses 3 modules.
+    pub bar_height: Option<Length>,
+}
+
+impl Default for PrintSpec {
+    fn default() -> Self {
+        Self {
+            x_dimension: Length::Millimeters(0.33),
+            dpi: 203,
+            bar_width_reduction: Length::Millimeters(0.0),
+            bar_height: None,
+        }
+    }
+}
+
+/// A barcode encoded by [`crate::Writer::encode_physical`], with its size once printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalBarcode {
+    /// The barcode, one pixel per printer dot
+    pub matrix: BitMatrix,
+
+    /// Printer dots per module
+    pub dots_per_module: u32,
+
+    /// Dots taken off the width of each bar, or added to it when negative
+    pub bar_width_reduction: i32,
+
+    /// Resolution of the printer, in dots per inch
+    pub dpi: u32,
+}
+
+impl PhysicalBarcode {
+    /// Width of a module once snapped to whole dots, in millimeters
+    pub fn x_dimension(&self) -> f32 {
+        self.dots_to_millimeters(self.dots_per_module)
+    }
+
+    /// Width of the barcode, quiet zones included, in millimeters
+    pub fn width(&self) -> f32 {
+        self.dots_to_millimeters(self.matrix.getWidth())
+    }
+
+    /// Height of the barcode, quiet zones included, in millimeters
+    pub fn height(&self) -> f32 {
+        self.dots_to_millimeters(self.matrix.getHeight())
+    }
+
+    fn dots_to_millimeters(&self, dots: u32) -> f32 {
+        dots as f32 * MM_PER_INCH / self.dpi as f32
+    }
+}
+
+/// Scales `modules`, the output of a writer at one pixel per module, to printer dots
+pub(crate) fn print(
+    modules: &BitMatrix,
+    format: &BarcodeFormat,
+    spec: &PrintSpec,
+) -> Result<PhysicalBarcode> {
+    if spec.dpi == 0 {
+        return Err(Exceptions::illegal_argument_with("dpi must be positive"));
+    }
+    let dots_per_module = spec.x_dimension.to_dots(spec.dpi);
+    if dots_per_module < 1 {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "x-dimension of {} mm is smaller than a dot at {} dpi",
+            spec.x_dimension.to_millimeters(),
+            spec.dpi
+        )));
+    }
+    let reduction = spec.bar_width_reduction.to_dots(spec.dpi);
+    if reduction.abs() >= dots_per_module {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "bar width reduction of {reduction} dots must be less than a module of {dots_per_module}"
+        )));
+    }
+    let dots = dots_per_module as u32;
+
+    let bars = Style::of(format) == Style::Bars;
+    let (modules, row_heights, reduce_rows) = if let Some(parts) = postal_parts(format) {
+        let (modules, heights) = postal_rows(modules, parts, spec)?;
+        (modules, heights, false)
+    } else if format == &BarcodeFormat::PDF_417 {
+        let (modules, heights) = pdf417_rows(modules, dots, spec)?;
+        (modules, heights, false)
+    } else if bars && modules.getHeight() == 1 {
+        let bar_height = match spec.bar_height {
+            Some(bar_height) => bar_height.to_dots(spec.dpi).max(1) as u32,
+            None => {
+                let symbol_width = modules
+                    .getEnclosingRectangle()
+                    .map_or(modules.getWidth(), |[_, _, width, _]| width);
+                let minimum = Length::Millimeters(MIN_BAR_HEIGHT_MM).to_dots(spec.dpi) as u32;
+                (symbol_width * dots * 15).div_ceil(100).max(minimum)
+            }
+        };
+        (modules.clone(), vec![bar_height], false)
+    } else {
+        (
+            modules.clone(),
+            vec![dots; modules.getHeight() as usize],
+            !bars,
+        )
+    };
+
+    let width = modules.getWidth();
+    let height = modules.getHeight();
+    let row_tops: Vec<u32> = row_heights
+        .iter()
+        .scan(0, |top, &height| {
+            *top += height;
+            Some(*top - height)
+        })
+        .collect();
+    let output_height = row_heights.iter().sum();
+    let mut matrix = BitMatrix::new(width * dots, output_height)?;
+
+    // Edges of a dark module next to a light one move in by these many dots, or out when
+    // negative. Bars, and the rows of postal symbols and PDF417, only move their sides.
+    let (before, after) = (reduction / 2, reduction - reduction / 2);
+    let dark = |x: i64, y: i64| {
+        x >= 0 && y >= 0 && x < width as i64 && y < height as i64 && modules.get(x as u32, y as u32)
+    };
+    let span = |start: u32, end: u32, first: bool, last: bool, limit: u32| {
+        let start = start as i32 + if first { before } else { 0 };
+        let end = end as i32 - if last { after } else { 0 };
+        (start.max(0) as u32, (end.max(0) as u32).min(limit))
+    };
+    for y in 0..height {
+        let (row_top, row_bottom) = (
+            row_tops[y as usize],
+            row_tops[y as usize] + row_heights[y as usize],
+        );
+        for x in (0..width).filter(|&x| modules.get(x, y)) {
+            let (left, right) = span(
+                x * dots,
+                (x + 1) * dots,
+                !dark(x as i64 - 1, y as i64),
+                !dark(x as i64 + 1, y as i64),
+                matrix.getWidth(),
+            );
+            let (top, bottom) = if reduce_rows {
+                span(
+                    row_top,
+                    row_bottom,
+                    !dark(x as i64, y as i64 - 1),
+                    !dark(x as i64, y as i64 + 1),
+                    output_height,
+                )
+            } else {
+                (row_top, row_bottom)
+            };
+            matrix.setRegion(left, top, right - left, bottom - top)?;
+        }
+    }
+
+    Ok(PhysicalBarcode {
+        matrix,
+        dots_per_module: dots,
+        bar_width_reduction: reduction,
+        dpi: spec.dpi,
+    })
+}
+
+/// The parts of the bars of a postal symbol, one row each, and their heights in dots. The
+/// writers draw the parts in bands of several rows, with no quiet zone above or below.
+fn postal_rows(
+    modules: &BitMatrix,
+    parts: &[(f32, PostalRow)],
+    spec: &PrintSpec,
+) -> Result<(BitMatrix, Vec<u32>)> {
+    let (width, height) = (modules.getWidth(), modules.getHeight());
+    let mut rows = BitMatrix::new(width, parts.len() as u32)?;
+    for (y, (_, part)) in parts.iter().enumerate() {
+        let source = match part {
+            PostalRow::Top => 0,
+            PostalRow::Middle => height / 2,
+            PostalRow::Bottom => height - 1,
+        };
+        for x in (0..width).filter(|&x| modules.get(x, source)) {
+            rows.set(x, y as u32);
+        }
+    }
+
+    let nominal: f32 = parts.iter().map(|(mm, _)| mm).sum();
+    let scale = spec
+        .bar_height
+        .map_or(1.0, |bar_height| bar_height.to_millimeters() / nominal);
+    let heights = parts
+        .iter()
+        .map(|(mm, _)| Length::Millimeters(mm * scale).to_dots(spec.dpi).max(1) as u32)
+        .collect();
+    Ok((rows, heights))
+}
+
+/// The rows of a PDF417 symbol, one module row each with its quiet zone above and below, and
+/// their heights in dots. The writer draws each row 4 modules high, and turns symbols higher
+/// than they are wide on their side, which is undone here.
+fn pdf417_rows(modules: &BitMatrix, dots: u32, spec: &PrintSpec) -> Result<(BitMatrix, Vec<u32>)> {
+    let [left, top, width, height] = modules
+        .getEnclosingRectangle()
+        .ok_or_else(|| Exceptions::illegal_argument_with("empty PDF417 symbol"))?;
+    let margin = left;
+    // The start pattern begins with a bar across every row, on the left of an upright symbol
+    // and at the bottom of one on its side
+    let upright = (top..top + height).all(|y| modules.get(left, y));
+    let (columns, lines) = if upright {
+        (width, height)
+    } else {
+        (height, width)
+    };
+    let module = |x: u32, y: u32| {
+        if upright {
+            modules.get(left + x, top + y)
+        } else {
+            modules.get(left + lines - 1 - y, top + height - 1 - x)
+        }
+    };
+
+    let mut rows: Vec<Vec<bool>> = Vec::new();
+    for y in 0..lines {
+        let line: Vec<bool> = (0..columns).map(|x| module(x, y)).collect();
+        if rows.last() != Some(&line) {
+            rows.push(line);
+        }
+    }
+
+    let minimum = MIN_PDF417_ROW_HEIGHT * dots;
+    let row_height = match spec.bar_height {
+        Some(bar_height) => {
+            let row_height = bar_height.to_dots(spec.dpi).max(0) as u32;
+            if row_height < minimum {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "PDF417 rows of {row_height} dots are lower than 3 modules of {dots} dots"
+                )));
+            }
+            row_height
+        }
+        None => minimum,
+    };
+
+    let mut matrix = BitMatrix::new(columns + 2 * margin, rows.len() as u32 + 2)?;
+    for (y, row) in rows.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, dark)| **dark) {
+            matrix.set(margin + x as u32, y as u32 + 1);
+        }
+    }
+    let mut heights = vec![row_height; rows.len() + 2];
+    heights[0] = margin * dots;
+    heights[rows.len() + 1] = margin * dots;
+    Ok((matrix, heights))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, common::BitMatrix};
+
+    use super::{Length, PrintSpec, print};
+
+    fn parse(rows: &str) -> BitMatrix {
+        BitMatrix::parse_strings(rows, "1", "0").expect("bitmatrix parse")
+    }
+
+    fn row(matrix: &BitMatrix, y: u32) -> String {
+        (0..matrix.getWidth())
+            .map(|x| if matrix.get(x, y) { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn converts_lengths() {
+        assert_eq!(Length::Mils(1000.0).to_millimeters(), 25.4);
+        // 10 mils at 203 dpi is 2.03 dots
+        assert_eq!(Length::Mils(10.0).to_dots(203), 2);
+        assert_eq!(Length::Millimeters(0.33).to_dots(300), 4);
+    }
+
+    #[test]
+    fn snaps_bars_to_dots() {
+        let spec = PrintSpec {
+            x_dimension: Length::Millimeters(0.25),
+            dpi: 300,
+            bar_width_reduction: Length::Millimeters(0.0),
+            bar_height: Some(Length::Millimeters(10.0)),
+        };
+        let barcode = print(&parse("01101\n"), &BarcodeFormat::CODE_128, &spec).unwrap();
+        // 0.25 mm is 2.95 dots at 300 dpi
+        assert_eq!(barcode.dots_per_module, 3);
+        assert_eq!(
+            (barcode.matrix.getWidth(), barcode.matrix.getHeight()),
+            (15, 118)
+        );
+        assert!((barcode.x_dimension() - 0.254).abs() < 1e-6);
+        assert!((barcode.width() - 1.27).abs() < 1e-6);
+        assert_eq!(row(&barcode.matrix, 117), "000111111000111");
+    }
+
+    #[test]
+    fn reduces_bar_width() {
+        let spec = PrintSpec {
+            x_dimension: Length::Millimeters(25.4 * 4.0 / 300.0),
+            dpi: 300,
+            bar_width_reduction: Length::Millimeters(25.4 * 3.0 / 300.0),
+            ..PrintSpec::default()
+        };
+        let barcode = print(&parse("01101\n"), &BarcodeFormat::CODE_39, &spec).unwrap();
+        assert_eq!(barcode.bar_width_reduction, 3);
+        // One dot off the left edge and two off the right edge of each bar
+        assert_eq!(row(&barcode.matrix, 0), "00000111110000000100");
+        // 15% of the symbol, 16 dots wide, but at least 6.35 mm
+        assert_eq!(barcode.matrix.getHeight(), 75);
+
+        // Negative reductions widen bars
+        let spec = PrintSpec {
+            bar_width_reduction: Length::Millimeters(-25.4 * 2.0 / 300.0),
+            ..spec
+        };
+        let barcode = print(&parse("01101\n"), &BarcodeFormat::CODE_39, &spec).unwrap();
+        assert_eq!(row(&barcode.matrix, 0), "00011111111110011111");
+    }
+
+    #[test]
+    fn reduces_modules_on_both_axes() {
+        let spec = PrintSpec {
+            x_dimension: Length::Millimeters(25.4 * 3.0 / 100.0),
+            dpi: 100,
+            bar_width_reduction: Length::Millimeters(25.4 * 2.0 / 100.0),
+            ..PrintSpec::default()
+        };
+        let barcode = print(&parse("110\n010\n"), &BarcodeFormat::QR_CODE, &spec).unwrap();
+        let rows: Vec<String> = (0..6).map(|y| row(&barcode.matrix, y)).collect();
+        assert_eq!(
+            rows,
+            [
+                "000000000",
+                "011110000",
+                "000110000",
+                "000010000",
+                "000010000",
+                "000000000"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_impossible_specs() {
+        let matrix = parse("1\n");
+        let spec = PrintSpec {
+            dpi: 0,
+            ..PrintSpec::default()
+        };
+        assert!(print(&matrix, &BarcodeFormat::QR_CODE, &spec).is_err());
+        let spec = PrintSpec {
+            x_dimension: Length::Mils(1.0),
+            ..PrintSpec::default()
+        };
+        assert!(print(&matrix, &BarcodeFormat::QR_CODE, &spec).is_err());
+        let spec = PrintSpec {
+            bar_width_reduction: Length::Millimeters(0.5),
+            ..PrintSpec::default()
+        };
+        assert!(print(&matrix, &BarcodeFormat::QR_CODE, &spec).is_err());
+    }
+
+    #[cfg(feature = "full_barcode_format_support")]
+    fn encode(format: BarcodeFormat, contents: &str, spec: &PrintSpec) -> super::PhysicalBarcode {
+        use crate::{EncodeHints, MultiFormatWriter, Writer};
+
+        MultiFormatWriter
+            .encode_physical(contents, &format, spec, &EncodeHints::default())
+            .unwrap()
+    }
+
+    /// The top and bottom of the bar in each column holding one
+    #[cfg(feature = "full_barcode_format_support")]
+    fn bar_extents(matrix: &BitMatrix) -> Vec<(u32, u32)> {
+        (0..matrix.getWidth())
+            .filter_map(|x| {
+                let top = (0..matrix.getHeight()).find(|&y| matrix.get(x, y))?;
+                let bottom = (0..matrix.getHeight()).rfind(|&y| matrix.get(x, y))?;
+                Some((top, bottom + 1))
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "full_barcode_format_support")]
+    #[test]
+    fn prints_postal_bars_at_their_nominal_heights() {
+        let spec = PrintSpec {
+            x_dimension: Length::Millimeters(0.5),
+            dpi: 300,
+            ..PrintSpec::default()
+        };
+        // Full bars 0.125" and half bars 0.050" high, 38 and 15 dots
+        let barcode = encode(BarcodeFormat::POSTNET, "12345", &spec);
+        assert_eq!(barcode.matrix.getHeight(), 38);
+        let extents = bar_extents(&barcode.matrix);
+        assert!(extents.contains(&(0, 38)) && extents.contains(&(23, 38)));
+        assert!(extents.iter().all(|e| [(0, 38), (23, 38)].contains(e)));
+
+        // Ascenders and descenders of 1.9 mm around a 1.3 mm tracker, 22 and 15 dots
+        let barcode = encode(BarcodeFormat::RM4SCC, "SN34RD1A", &spec);
+        assert_eq!(barcode.matrix.getHeight(), 59);
+        let states = [(0, 59), (0, 37), (22, 59), (22, 37)];
+        let extents = bar_extents(&barcode.matrix);
+        assert!(states.iter().all(|state| extents.contains(state)));
+        assert!(extents.iter().all(|e| states.contains(e)));
+
+        // A bar height scales every part, here doubling them
+        let spec = PrintSpec {
+            bar_height: Some(Length::Millimeters(10.2)),
+            ..spec
+        };
+        let barcode = encode(BarcodeFormat::RM4SCC, "SN34RD1A", &spec);
+        assert_eq!(barcode.matrix.getHeight(), 121);
+    }
+
+    #[cfg(feature = "full_barcode_format_support")]
+    #[test]
+    fn prints_pdf417_rows() {
+        use crate::{
+            EncodeHintValue, EncodeHints, MultiFormatWriter, Writer, pdf417::encoder::Dimensions,
+        };
+
+        let spec = PrintSpec {
+            x_dimension: Length::Mils(10.0),
+            dpi: 300,
+            ..PrintSpec::default()
+        };
+        // The writer draws rows 4 modules high inside a 30 module quiet zone
+        let modules = MultiFormatWriter
+            .encode("rxing", &BarcodeFormat::PDF_417, 0, 0)
+            .unwrap();
+        let rows = (modules.getHeight() - 60) / 4;
+        let barcode = encode(BarcodeFormat::PDF_417, "rxing", &spec);
+        assert_eq!(barcode.matrix.getHeight(), 60 * 3 + rows * 9);
+        assert_eq!(barcode.matrix.getWidth(), modules.getWidth() * 3);
+
+        // Rows lower than 3 modules are not allowed
+        let low = PrintSpec {
+            bar_height: Some(Length::Mils(25.0)),
+            ..spec.clone()
+        };
+        assert!(
+            MultiFormatWriter
+                .encode_physical(
+                    "rxing",
+                    &BarcodeFormat::PDF_417,
+                    &low,
+                    &EncodeHints::default()
+                )
+                .is_err()
+        );
+
+        // A symbol the writer turns on its side is printed upright
+        let hints = EncodeHints::default().with(EncodeHintValue::Pdf417Dimensions(
+            Dimensions::new(1, 1, 3, 90),
+        ));
+        let contents = "rxing prints PDF417 upright";
+        let modules = MultiFormatWriter
+            .encode_with_hints(contents, &BarcodeFormat::PDF_417, 0, 0, &hints)
+            .unwrap();
+        assert!(modules.getWidth() > modules.getHeight());
+        let barcode = MultiFormatWriter
+            .encode_physical(contents, &BarcodeFormat::PDF_417, &spec, &hints)
+            .unwrap();
+        assert_eq!(barcode.matrix.getWidth(), modules.getHeight() * 3);
+        assert!((90..barcode.matrix.getHeight() - 90).all(|y| barcode.matrix.get(90, y)));
+    }
+
+    #[cfg(feature = "full_barcode_format_support")]
+    #[test]
+    fn encodes_every_format() {
+        use crate::{EncodeHints, MultiFormatWriter, Writer};
+
+        let spec = PrintSpec {
+            x_dimension: Length::Mils(10.0),
+            dpi: 300,
+            ..PrintSpec::default()
+        };
+        for (format, contents) in [
+            (BarcodeFormat::QR_CODE, "rxing"),
+            (BarcodeFormat::DATA_MATRIX, "rxing"),
+            (BarcodeFormat::AZTEC, "rxing"),
+            (BarcodeFormat::PDF_417, "rxing"),
+            (BarcodeFormat::CODE_128, "rxing"),
+            (BarcodeFormat::EAN_13, "590123412345"),
+            (BarcodeFormat::POSTNET, "12345"),
+        ] {
+            let modules = MultiFormatWriter
+                .encode_with_hints(contents, &format, 0, 0, &EncodeHints::default())
+                .unwrap();
+            let barcode = MultiFormatWriter
+                .encode_physical(contents, &format, &spec, &EncodeHints::default())
+                .unwrap();
+            assert_eq!(barcode.dots_per_module, 3, "{format}");
+            assert_eq!(
+                barcode.matrix.getWidth(),
+                modules.getWidth() * 3,
+                "{format}"
+            );
+        }
+    }
+}
@@ -133,7 +133,7 @@ use super::{
     },
 };
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct QrReader;
 
 impl Reader for QrReader {
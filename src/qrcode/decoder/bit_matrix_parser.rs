@@ -27,6 +27,7 @@ use crate::qrcode::common::{FormatInformation, Version, VersionRef};
  */
 pub struct BitMatrixParser {
     bitMatrix: BitMatrix,
+    uncertainModules: Option<BitMatrix>,
     parsedVersion: Option<VersionRef>,
     parsedFormatInfo: Option<FormatInformation>,
     mirror: bool,
@@ -46,6 +47,7 @@ impl BitMatrixParser {
         } else {
             Ok(Self {
                 bitMatrix: bit_matrix,
+                uncertainModules: None,
                 parsedVersion: None,
                 parsedFormatInfo: None,
                 mirror: false,
@@ -179,6 +181,38 @@ impl BitMatrixParser {
         let dimension = self.bitMatrix.getHeight();
         dataMask.unmaskBitMatrix(&mut self.bitMatrix, dimension);
 
+        Self::readModules(&self.bitMatrix, version)
+    }
+
+    /**
+     * <p>Reads which codewords hold a module whose color was in doubt when sampled, in the order
+     * of {@link #readCodewords()}.</p>
+     *
+     * @return a flag for each codeword, none set if no uncertain modules were given
+     */
+    pub fn readUncertainCodewords(&mut self) -> Result<Vec<bool>> {
+        let version = self.readVersion()?;
+        let Some(uncertainModules) = &self.uncertainModules else {
+            return Ok(vec![false; version.getTotalCodewords() as usize]);
+        };
+        if uncertainModules.getHeight() != self.bitMatrix.getHeight() {
+            return Err(Exceptions::FORMAT);
+        }
+        Ok(Self::readModules(uncertainModules, version)?
+            .into_iter()
+            .map(|codeword| codeword != 0)
+            .collect())
+    }
+
+    /**
+     * @param uncertainModules modules of the bit matrix whose color was in doubt when sampled
+     */
+    pub fn setUncertainModules(&mut self, uncertainModules: Option<BitMatrix>) {
+        self.uncertainModules = uncertainModules;
+    }
+
+    fn readModules(bitMatrix: &BitMatrix, version: VersionRef) -> Result<Vec<u8>> {
+        let dimension = bitMatrix.getHeight();
         let functionPattern = version.buildFunctionPattern()?;
 
         let mut readingUp = true;
@@ -207,7 +241,7 @@ impl BitMatrixParser {
                         // Read a bit
                         bitsRead += 1;
                         currentByte <<= 1;
-                        if self.bitMatrix.get(j as u32 - col, i) {
+                        if bitMatrix.get(j as u32 - col, i) {
                             currentByte |= 1;
                         }
                         // If we've made a whole byte, save it off
@@ -261,11 +295,18 @@ impl BitMatrixParser {
 
     /** Mirror the bit matrix in order to attempt a second reading. */
     pub fn mirror(&mut self) {
-        for x in 0..self.bitMatrix.getWidth() {
-            for y in (x + 1)..self.bitMatrix.getHeight() {
-                if self.bitMatrix.get(x, y) != self.bitMatrix.get(y, x) {
-                    self.bitMatrix.flip_coords(y, x);
-                    self.bitMatrix.flip_coords(x, y);
+        Self::transpose(&mut self.bitMatrix);
+        if let Some(uncertainModules) = &mut self.uncertainModules {
+            Self::transpose(uncertainModules);
+        }
+    }
+
+    fn transpose(bitMatrix: &mut BitMatrix) {
+        for x in 0..bitMatrix.getWidth() {
+            for y in (x + 1)..bitMatrix.getHeight() {
+                if bitMatrix.get(x, y) != bitMatrix.get(y, x) {
+                    bitMatrix.flip_coords(y, x);
+                    bitMatrix.flip_coords(x, y);
                 }
             }
         }
@@ -78,9 +78,45 @@ pub fn decode_bitmatrix(bits: &BitMatrix) -> Result<DecoderRXingResult> {
 pub fn decode_bitmatrix_with_hints(
     bits: &BitMatrix,
     hints: &DecodeHints,
+) -> Result<DecoderRXingResult> {
+    decode_bitmatrix_with_erasures(bits, None, hints)
+}
+
+/**
+ * <p>Decodes a QR Code represented as a {@link BitMatrix}, like {@link #decode_bitmatrix_with_hints}.
+ * Only if that fails are the uncertain modules asked for, and the codewords that hold one of them
+ * treated as erasures where error correction fails without.</p>
+ *
+ * @param bits booleans representing white/black QR Code modules
+ * @param uncertainModules gives the modules of bits whose color was in doubt when sampled
+ * @param hints decoding hints that should be used to influence decoding
+ * @return text and bytes encoded within the QR Code
+ * @throws FormatException if the QR Code cannot be decoded
+ * @throws ChecksumException if error correction fails
+ */
+pub fn decode_bitmatrix_with_uncertain_modules(
+    bits: &BitMatrix,
+    uncertainModules: impl FnOnce() -> Option<BitMatrix>,
+    hints: &DecodeHints,
+) -> Result<DecoderRXingResult> {
+    let decoded = decode_bitmatrix_with_hints(bits, hints);
+    if decoded.is_ok() {
+        return decoded;
+    }
+    match uncertainModules() {
+        Some(uncertain) => decode_bitmatrix_with_erasures(bits, Some(uncertain), hints).or(decoded),
+        None => decoded,
+    }
+}
+
+fn decode_bitmatrix_with_erasures(
+    bits: &BitMatrix,
+    uncertainModules: Option<BitMatrix>,
+    hints: &DecodeHints,
 ) -> Result<DecoderRXingResult> {
     // Construct a parser and read version, error-correction level
     let mut parser = BitMatrixParser::new(bits.clone())?;
+    parser.setUncertainModules(uncertainModules);
     let mut fe = None;
     let mut ce = None;
     match decode_bitmatrix_parser_with_hints(&mut parser, hints) {
@@ -146,8 +182,14 @@ fn decode_bitmatrix_parser_with_hints(
 
     // Read codewords
     let codewords = parser.readCodewords()?;
+    let uncertainCodewords: Vec<u8> = parser
+        .readUncertainCodewords()?
+        .into_iter()
+        .map(u8::from)
+        .collect();
     // Separate into data blocks
     let dataBlocks = DataBlock::getDataBlocks(&codewords, version, ecLevel)?;
+    let uncertainBlocks = DataBlock::getDataBlocks(&uncertainCodewords, version, ecLevel)?;
 
     // Count total number of data bytes
     let totalBytes = dataBlocks.iter().fold(0, |acc, dataBlock| {
@@ -158,10 +200,18 @@ fn decode_bitmatrix_parser_with_hints(
     let mut resultOffset = 0;
 
     // Error-correct and copy data blocks together into a stream of bytes
-    for dataBlock in &dataBlocks {
+    let mut erasures = 0;
+    for (dataBlock, uncertainBlock) in dataBlocks.iter().zip(&uncertainBlocks) {
         let mut codewordBytes = dataBlock.getCodewords().to_vec();
         let numDataCodewords = dataBlock.getNumDataCodewords() as usize;
-        correctErrors(&mut codewordBytes, numDataCodewords)?;
+        let uncertain: Vec<usize> = uncertainBlock
+            .getCodewords()
+            .iter()
+            .enumerate()
+            .filter(|(_, flag)| **flag != 0)
+            .map(|(i, _)| i)
+            .collect();
+        erasures += correctErrors(&mut codewordBytes, numDataCodewords, &uncertain)?;
         for codeword_byte in codewordBytes.iter().take(numDataCodewords) {
             resultBytes[resultOffset] = *codeword_byte;
             resultOffset += 1;
@@ -169,7 +219,9 @@ fn decode_bitmatrix_parser_with_hints(
     }
 
     // Decode the contents of that stream of bytes
-    decoded_bit_stream_parser::decode(&resultBytes, version, ecLevel, hints)
+    let mut result = decoded_bit_stream_parser::decode(&resultBytes, version, ecLevel, hints)?;
+    result.setErasures(erasures);
+    Ok(result)
 }
 
 /**
@@ -178,9 +230,16 @@ fn decode_bitmatrix_parser_with_hints(
  *
  * @param codewordBytes data and error correction codewords
  * @param numDataCodewords number of codewords that are data bytes
+ * @param erasures positions of the codewords holding uncertain modules, tried as erasures if
+ *   correcting them as errors fails
+ * @return number of erasures used
  * @throws ChecksumException if error correction fails
  */
-fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()> {
+fn correctErrors(
+    codewordBytes: &mut [u8],
+    numDataCodewords: usize,
+    erasures: &[usize],
+) -> Result<usize> {
     let numCodewords = codewordBytes.len();
     // First read into an array of ints
     let mut codewordsInts = vec![0u8; numCodewords];
@@ -188,11 +247,20 @@ fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()
 
     let mut sending_code_words: Vec<i32> = codewordsInts.iter().map(|x| *x as i32).collect();
 
-    if let Err(Exceptions::ReedSolomonException(error_str)) = RS_DECODER.decode(
-        &mut sending_code_words,
-        (codewordBytes.len() - numDataCodewords) as i32,
-    ) {
-        return Err(Exceptions::ChecksumException(error_str));
+    let twoS = (codewordBytes.len() - numDataCodewords) as i32;
+    let mut erasuresUsed = 0;
+    if let Err(Exceptions::ReedSolomonException(error_str)) =
+        RS_DECODER.decode(&mut sending_code_words, twoS)
+    {
+        sending_code_words = codewordsInts.iter().map(|x| *x as i32).collect();
+        if erasures.is_empty()
+            || RS_DECODER
+                .decode_with_erasures(&mut sending_code_words, twoS, erasures)
+                .is_err()
+        {
+            return Err(Exceptions::ChecksumException(error_str));
+        }
+        erasuresUsed = erasures.len();
     }
 
     // Copy back into array of bytes -- only need to worry about the bytes that were data
@@ -205,5 +273,93 @@ fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()
         *code_word = *sent_code_word as u8;
     }
 
-    Ok(())
+    Ok(erasuresUsed)
+}
+
+#[cfg(test)]
+#[cfg(feature = "encoders")]
+mod tests {
+    use crate::{
+        BarcodeFormat, BinaryBitmap, DecodeHints, EncodeHints, Luma8LuminanceSource, Reader,
+        Writer,
+        common::{DetectorRXingResult, HybridBinarizer},
+        qrcode::{QRCodeReader, QRCodeWriter, detector::Detector},
+    };
+
+    use super::{decode_bitmatrix, decode_bitmatrix_with_uncertain_modules};
+
+    const TEXT: &str = "Smudged codewords are erasures, at half the cost of errors";
+
+    /// The symbol at 4 pixels a module, with a 2 by 2 pixel checkerboard over the modules in
+    /// smudge: their centers read as one color whatever the module was, but not the rest of them
+    fn render(smudge: std::ops::Range<u32>) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+        const SCALE: u32 = 4;
+        let matrix = QRCodeWriter
+            .encode_with_hints(
+                TEXT,
+                &BarcodeFormat::QR_CODE,
+                0,
+                0,
+                &EncodeHints {
+                    ErrorCorrection: Some("L".to_owned()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let size = matrix.getWidth() * SCALE;
+        let mut pixels = vec![255u8; (size * size) as usize];
+        for y in 0..size {
+            for x in 0..size {
+                let (mx, my) = (x / SCALE, y / SCALE);
+                let dark = if smudge.contains(&mx) && smudge.contains(&my) {
+                    (x / 2 + y / 2) % 2 == 0
+                } else {
+                    matrix.get(mx, my)
+                };
+                if dark {
+                    pixels[(y * size + x) as usize] = 0;
+                }
+            }
+        }
+        BinaryBitmap::new(HybridBinarizer::new(Luma8LuminanceSource::new(
+            pixels, size, size,
+        )))
+    }
+
+    #[test]
+    fn corrects_smudged_modules_as_erasures() {
+        let mut image = render(13..22);
+        let black = image.get_black_matrix();
+        let detected = Detector::new(black).detect().unwrap();
+        assert!(decode_bitmatrix(detected.getBits()).is_err());
+        let result = decode_bitmatrix_with_uncertain_modules(
+            detected.getBits(),
+            || {
+                detected
+                    .getSampledGrid()?
+                    .sample_uncertain_modules(black)
+                    .ok()
+            },
+            &DecodeHints::default(),
+        )
+        .unwrap();
+        assert_eq!(result.getText(), TEXT);
+        assert!(result.getErasures() > 0);
+        assert_eq!(QRCodeReader.decode(&mut image).unwrap().getText(), TEXT);
+    }
+
+    #[test]
+    fn samples_uncertain_modules_only_when_decoding_fails() {
+        let image = render(0..0);
+        let detected = Detector::new(image.get_black_matrix()).detect().unwrap();
+        let result = decode_bitmatrix_with_uncertain_modules(
+            detected.getBits(),
+            || panic!("the symbol decodes without erasures"),
+            &DecodeHints::default(),
+        )
+        .unwrap();
+        assert_eq!(result.getText(), TEXT);
+        assert_eq!(result.getErasures(), 0);
+    }
 }
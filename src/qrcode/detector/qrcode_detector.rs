@@ -18,7 +18,7 @@ use crate::{
     DecodeHints, Exceptions, Point, PointCallback,
     common::{
         BitMatrix, DefaultGridSampler, GridSampler, PerspectiveTransform, Quadrilateral, Result,
-        SamplerControl,
+        SampledGrid, SamplerControl,
     },
     point,
     qrcode::common::Version,
@@ -139,7 +139,7 @@ impl<'a> Detector<'_> {
         )
         .ok_or(Exceptions::NOT_FOUND)?;
 
-        let bits = Detector::sampleGrid(self.image, transform, dimension)?;
+        let (bits, sampledGrid) = Detector::sampleGrid(self.image, transform, dimension)?;
 
         let mut points = vec![
             Point::from(bottomLeft),
@@ -151,7 +151,7 @@ impl<'a> Detector<'_> {
             points.push(alignmentPattern.ok_or(Exceptions::NOT_FOUND)?.into())
         }
 
-        Ok(QRCodeDetectorResult::new(bits, points))
+        Ok(QRCodeDetectorResult::new(bits, points).with_sampled_grid(sampledGrid))
     }
 
     fn createTransform<T: Into<Point>, X: Into<Point>>(
@@ -201,19 +201,18 @@ impl<'a> Detector<'_> {
         PerspectiveTransform::quadrilateralToQuadrilateral(dst, src).ok()
     }
 
+    /**
+     * @return the sampled modules, and where they were sampled from
+     */
     fn sampleGrid(
         image: &BitMatrix,
         transform: PerspectiveTransform,
         dimension: u32,
-    ) -> Result<BitMatrix> {
+    ) -> Result<(BitMatrix, SampledGrid)> {
         let sampler = DefaultGridSampler;
-        let (res, _) = sampler.sample_grid(
-            image,
-            dimension,
-            dimension,
-            &[SamplerControl::new(dimension, dimension, transform)],
-        )?;
-        Ok(res)
+        let controls = vec![SamplerControl::new(dimension, dimension, transform)];
+        let (res, _) = sampler.sample_grid(image, dimension, dimension, &controls)?;
+        Ok((res, SampledGrid::new(dimension, dimension, controls)))
     }
 
     /**
@@ -1,11 +1,12 @@
 use crate::{
     Point,
-    common::{BitMatrix, DetectorRXingResult},
+    common::{BitMatrix, DetectorRXingResult, SampledGrid},
 };
 
 pub struct QRCodeDetectorResult {
     bit_source: BitMatrix,
     result_points: Vec<Point>,
+    sampled_grid: Option<SampledGrid>,
 }
 
 impl QRCodeDetectorResult {
@@ -13,6 +14,14 @@ impl QRCodeDetectorResult {
         Self {
             bit_source,
             result_points,
+            sampled_grid: None,
+        }
+    }
+
+    pub fn with_sampled_grid(self, sampled_grid: SampledGrid) -> Self {
+        Self {
+            sampled_grid: Some(sampled_grid),
+            ..self
         }
     }
 }
@@ -25,4 +34,8 @@ impl DetectorRXingResult for QRCodeDetectorResult {
     fn getPoints(&self) -> &[crate::Point] {
         &self.result_points
     }
+
+    fn getSampledGrid(&self) -> Option<&SampledGrid> {
+        self.sampled_grid.as_ref()
+    }
 }
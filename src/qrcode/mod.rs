@@ -15,6 +15,9 @@ mod qr_code_writer;
 #[cfg(feature = "encoders")]
 pub use qr_code_writer::*;
 
+#[cfg(all(feature = "encoders", feature = "decoders", feature = "image"))]
+pub mod styled;
+
 pub mod cpp_port;
 
 #[cfg(test)]
@@ -31,7 +31,7 @@ use super::{
  *
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct QRCodeReader;
 // pub struct QRCodeReader;  {
 
@@ -200,10 +200,18 @@ impl QRCodeReader {
             decoderRXingResult = qrcode_decoder::decode_bitmatrix_with_hints(&bits, hints)?;
             points = Vec::new();
         } else {
-            let detectorRXingResult =
-                Detector::new(image.get_black_matrix()).detect_with_hints(hints)?;
-            decoderRXingResult =
-                qrcode_decoder::decode_bitmatrix_with_hints(detectorRXingResult.getBits(), hints)?;
+            let black = image.get_black_matrix();
+            let detectorRXingResult = Detector::new(black).detect_with_hints(hints)?;
+            decoderRXingResult = qrcode_decoder::decode_bitmatrix_with_uncertain_modules(
+                detectorRXingResult.getBits(),
+                || {
+                    detectorRXingResult
+                        .getSampledGrid()?
+                        .sample_uncertain_modules(black)
+                        .ok()
+                },
+                hints,
+            )?;
             points = detectorRXingResult.getPoints().to_vec();
         }
 
@@ -0,0 +1,14 @@
+//! Branded QR Codes, rendered to images.
+//!
+//! [`StyledQRCodeWriter`] draws an encoded [`super::encoder::QRCode`] with the colors,
+//! gradients, module shapes, finder pattern shapes and centered logo of a [`QrStyle`]. Only
+//! data modules take the module shape: timing, alignment and version patterns stay square,
+//! and the finder patterns take their own shapes. Encoding raises the error correction level
+//! until the modules hidden by the logo can be recovered, and checks that the image still
+//! decodes with [`super::QRCodeReader`].
+
+mod style;
+pub use style::*;
+
+mod styled_qr_code_writer;
+pub use styled_qr_code_writer::*;
@@ -0,0 +1,202 @@
+use image::RgbaImage;
+
+use crate::render::Color;
+
+/// Paint of the dark modules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fill {
+    Solid(Color),
+    /// From `from` to `to` across the symbol, `angle` degrees clockwise from left to right
+    LinearGradient {
+        from: Color,
+        to: Color,
+        angle: f32,
+    },
+    /// From `center` in the middle of the symbol to `edge` at its corners
+    RadialGradient {
+        center: Color,
+        edge: Color,
+    },
+}
+
+impl Fill {
+    /// The color at `x`, `y`, relative to the symbol from 0 to 1
+    pub(super) fn color_at(&self, x: f32, y: f32) -> Color {
+        let (x, y) = (x - 0.5, y - 0.5);
+        match *self {
+            Self::Solid(color) => color,
+            Self::LinearGradient { from, to, angle } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                // Scaled so that the corners furthest along the gradient are at 0 and 1
+                let extent = (cos.abs() + sin.abs()) / 2.0;
+                mix(from, to, (x * cos + y * sin) / extent / 2.0 + 0.5)
+            }
+            Self::RadialGradient { center, edge } => mix(
+                center,
+                edge,
+                (x * x + y * y).sqrt() / std::f32::consts::FRAC_1_SQRT_2,
+            ),
+        }
+    }
+}
+
+impl From<Color> for Fill {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color::new(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+    )
+}
+
+/// Shape of the data modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleShape {
+    #[default]
+    Square,
+    /// A circle in each module
+    Dot,
+    /// Squares with the corners rounded where they touch no other module
+    Rounded,
+}
+
+/// Shape of the outer frame or of the inner ball of the finder patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EyeShape {
+    #[default]
+    Square,
+    /// A square with rounded corners
+    Rounded,
+    Circle,
+}
+
+impl EyeShape {
+    /// Whether `x`, `y` is inside the shape filling the square `size` wide at `offset`
+    pub(super) fn contains(self, x: f32, y: f32, offset: f32, size: f32) -> bool {
+        let (x, y) = (x - offset, y - offset);
+        if !(0.0..size).contains(&x) || !(0.0..size).contains(&y) {
+            return false;
+        }
+        let radius = match self {
+            Self::Square => return true,
+            Self::Rounded => size / 4.0,
+            Self::Circle => size / 2.0,
+        };
+        let dx = x - x.clamp(radius, size - radius);
+        let dy = y - y.clamp(radius, size - radius);
+        dx * dx + dy * dy <= radius * radius
+    }
+}
+
+/// An image drawn in the middle of the symbol, over cleared modules
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logo {
+    pub image: RgbaImage,
+
+    /// Width of the logo, relative to that of the symbol
+    pub size: f32,
+
+    /// Modules cleared around the logo
+    pub padding: u32,
+}
+
+impl Logo {
+    pub fn new(image: RgbaImage) -> Self {
+        Self {
+            image,
+            size: 0.2,
+            padding: 1,
+        }
+    }
+}
+
+/// Look of a QR Code drawn by [`super::StyledQRCodeWriter`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrStyle {
+    /// Size of a module, in pixels
+    pub module_size: u32,
+
+    /// Quiet zone around the symbol, in modules
+    pub quiet_zone: u32,
+
+    pub foreground: Fill,
+
+    pub background: Color,
+
+    pub module_shape: ModuleShape,
+
+    /// Shape of the 7 by 7 outer frame of the finder patterns
+    pub eye_frame: EyeShape,
+
+    /// Shape of the 3 by 3 ball inside the finder patterns
+    pub eye_ball: EyeShape,
+
+    /// Paint of the finder patterns, `None` to use the foreground
+    pub eye_fill: Option<Fill>,
+
+    pub logo: Option<Logo>,
+}
+
+impl Default for QrStyle {
+    fn default() -> Self {
+        Self {
+            module_size: 10,
+            quiet_zone: 4,
+            foreground: Fill::Solid(Color::BLACK),
+            background: Color::WHITE,
+            module_shape: ModuleShape::default(),
+            eye_frame: EyeShape::default(),
+            eye_ball: EyeShape::default(),
+            eye_fill: None,
+            logo: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render::Color;
+
+    use super::{EyeShape, Fill};
+
+    #[test]
+    fn fills_gradients() {
+        let red = Color::new(255, 0, 0);
+        let blue = Color::new(0, 0, 255);
+        let linear = Fill::LinearGradient {
+            from: red,
+            to: blue,
+            angle: 45.0,
+        };
+        assert_eq!(linear.color_at(0.0, 0.0), red);
+        assert_eq!(linear.color_at(1.0, 1.0), blue);
+        assert_eq!(linear.color_at(1.0, 0.0), Color::new(128, 0, 128));
+
+        let radial = Fill::RadialGradient {
+            center: red,
+            edge: blue,
+        };
+        assert_eq!(radial.color_at(0.5, 0.5), red);
+        assert_eq!(radial.color_at(0.0, 1.0), blue);
+    }
+
+    #[test]
+    fn contains_eye_shapes() {
+        for shape in [EyeShape::Square, EyeShape::Rounded, EyeShape::Circle] {
+            assert!(shape.contains(3.5, 3.5, 0.0, 7.0));
+            assert!(shape.contains(3.5, 0.1, 0.0, 7.0));
+            assert!(!shape.contains(7.5, 3.5, 0.0, 7.0));
+        }
+        assert!(EyeShape::Square.contains(0.1, 0.1, 0.0, 7.0));
+        assert!(!EyeShape::Rounded.contains(0.1, 0.1, 0.0, 7.0));
+        assert!(EyeShape::Rounded.contains(1.0, 1.0, 0.0, 7.0));
+        assert!(!EyeShape::Circle.contains(1.0, 1.0, 0.0, 7.0));
+    }
+}
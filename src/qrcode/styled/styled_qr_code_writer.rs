@@ -0,0 +1,418 @@
+use image::{DynamicImage, Rgba, RgbaImage, imageops};
+
+use crate::{
+    BinaryBitmap, BufferedImageLuminanceSource, EncodeHints, Exceptions, Reader,
+    common::{BitMatrix, HybridBinarizer, Result},
+    qrcode::{
+        QRCodeReader,
+        common::ErrorCorrectionLevel,
+        encoder::{QRCode, qrcode_encoder},
+    },
+    render::Color,
+};
+
+use super::{Fill, ModuleShape, QrStyle};
+
+/// Samples per pixel along each axis, to smooth the edges of shapes
+const SAMPLES: u32 = 4;
+
+/// Size of the finder patterns, in modules
+const FINDER_SIZE: u32 = 7;
+
+/// Levels to raise the error correction to, with the share of codewords each recovers
+const LEVELS: [(ErrorCorrectionLevel, f32); 4] = [
+    (ErrorCorrectionLevel::L, 0.07),
+    (ErrorCorrectionLevel::M, 0.15),
+    (ErrorCorrectionLevel::Q, 0.25),
+    (ErrorCorrectionLevel::H, 0.30),
+];
+
+/// A QR Code rendered by [`StyledQRCodeWriter::encode`]
+#[derive(Debug, Clone)]
+pub struct StyledQRCode {
+    pub image: RgbaImage,
+
+    /// The encoded symbol, at the error correction level it needed
+    pub code: QRCode,
+}
+
+/// Renders QR Codes in a [`QrStyle`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StyledQRCodeWriter;
+
+impl StyledQRCodeWriter {
+    /// Encodes `contents` and renders it in `style`. The error correction level, `L` unless
+    /// [`EncodeHints::ErrorCorrection`] asks for another, is raised until the modules under
+    /// the logo can be recovered and the image decodes back to `contents`.
+    pub fn encode(
+        &self,
+        contents: &str,
+        style: &QrStyle,
+        hints: &EncodeHints,
+    ) -> Result<StyledQRCode> {
+        if contents.is_empty() {
+            return Err(Exceptions::illegal_argument_with("found empty contents"));
+        }
+        let requested = match &hints.ErrorCorrection {
+            Some(level) => level.parse()?,
+            None => ErrorCorrectionLevel::L,
+        };
+        let first = LEVELS
+            .iter()
+            .position(|&(level, _)| level == requested)
+            .ok_or(Exceptions::illegal_argument_with(
+                "invalid error correction level",
+            ))?;
+
+        for &(level, recoverable) in &LEVELS[first..] {
+            let code = qrcode_encoder::encode_with_hints(contents, level, hints)?;
+            let symbol = Symbol::new(&code, style)?;
+            if symbol.hidden_share() >= recoverable {
+                continue;
+            }
+            let image = symbol.render(style);
+            if decodes_to(&image, contents) {
+                return Ok(StyledQRCode { image, code });
+            }
+        }
+        Err(Exceptions::writer_with(
+            "styled symbol does not decode, even at error correction level H",
+        ))
+    }
+
+    /// Renders an encoded symbol in `style`, without checking that it decodes
+    pub fn render(&self, code: &QRCode, style: &QrStyle) -> Result<RgbaImage> {
+        Ok(Symbol::new(code, style)?.render(style))
+    }
+}
+
+/// Whether `image` decodes with [`QRCodeReader`] to `contents`
+fn decodes_to(image: &RgbaImage, contents: &str) -> bool {
+    let mut bitmap = BinaryBitmap::new(HybridBinarizer::new(BufferedImageLuminanceSource::new(
+        DynamicImage::ImageRgba8(image.clone()),
+    )));
+    QRCodeReader
+        .decode(&mut bitmap)
+        .is_ok_and(|result| result.getText() == contents)
+}
+
+/// What covers a point of the symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Paint {
+    Light,
+    Module,
+    Eye,
+}
+
+/// The modules of a symbol, with those cleared for the logo
+struct Symbol {
+    modules: BitMatrix,
+    function_pattern: BitMatrix,
+    /// First module and width of the square cleared for the logo
+    logo_area: Option<(u32, u32)>,
+}
+
+impl Symbol {
+    fn new(code: &QRCode, style: &QrStyle) -> Result<Self> {
+        let (Some(matrix), Some(version)) = (code.getMatrix(), code.getVersion()) else {
+            return Err(Exceptions::illegal_state_with("matrix is empty"));
+        };
+        let dimension = matrix.getWidth();
+        let mut modules = BitMatrix::with_single_dimension(dimension)?;
+        for y in 0..dimension {
+            for x in (0..dimension).filter(|&x| matrix.get(x, y) == 1) {
+                modules.set(x, y);
+            }
+        }
+
+        // A square of whole modules, centered as exactly as the dimension allows
+        let logo_area = style.logo.as_ref().map(|logo| {
+            let mut width = (logo.size * dimension as f32).ceil() as u32 + 2 * logo.padding;
+            if width % 2 != dimension % 2 {
+                width += 1;
+            }
+            let width = width.min(dimension);
+            ((dimension - width) / 2, width)
+        });
+
+        Ok(Self {
+            modules,
+            function_pattern: version.buildFunctionPattern()?,
+            logo_area,
+        })
+    }
+
+    fn dimension(&self) -> u32 {
+        self.modules.getWidth()
+    }
+
+    fn under_logo(&self, x: u32, y: u32) -> bool {
+        self.logo_area.is_some_and(|(start, width)| {
+            (start..start + width).contains(&x) && (start..start + width).contains(&y)
+        })
+    }
+
+    /// Share of the data modules hidden by the logo
+    fn hidden_share(&self) -> f32 {
+        let dimension = self.dimension();
+        let (mut data, mut hidden) = (0, 0);
+        for y in 0..dimension {
+            for x in (0..dimension).filter(|&x| !self.function_pattern.get(x, y)) {
+                data += 1;
+                if self.under_logo(x, y) {
+                    hidden += 1;
+                }
+            }
+        }
+        hidden as f32 / data as f32
+    }
+
+    fn dark(&self, x: i64, y: i64) -> bool {
+        let dimension = self.dimension() as i64;
+        (0..dimension).contains(&x)
+            && (0..dimension).contains(&y)
+            && !self.under_logo(x as u32, y as u32)
+            && self.modules.get(x as u32, y as u32)
+    }
+
+    /// Top left corner of the finder pattern covering module `x`, `y`
+    fn finder_at(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        let far = self.dimension() - FINDER_SIZE;
+        [(0, 0), (far, 0), (0, far)]
+            .into_iter()
+            .find(|&(left, top)| {
+                (left..left + FINDER_SIZE).contains(&x) && (top..top + FINDER_SIZE).contains(&y)
+            })
+    }
+
+    /// What covers the point `x`, `y`, in modules from the top left corner of the symbol
+    fn paint_at(&self, x: f32, y: f32, style: &QrStyle) -> Paint {
+        let dimension = self.dimension() as f32;
+        if !(0.0..dimension).contains(&x) || !(0.0..dimension).contains(&y) {
+            return Paint::Light;
+        }
+        let (mx, my) = (x as u32, y as u32);
+        if let Some((left, top)) = self.finder_at(mx, my) {
+            let (u, v) = (x - left as f32, y - top as f32);
+            let size = FINDER_SIZE as f32;
+            let frame = style.eye_frame.contains(u, v, 0.0, size)
+                && !style.eye_frame.contains(u, v, 1.0, size - 2.0);
+            let ball = style.eye_ball.contains(u, v, 2.0, size - 4.0);
+            return if frame || ball {
+                Paint::Eye
+            } else {
+                Paint::Light
+            };
+        }
+        if !self.dark(mx as i64, my as i64) {
+            return Paint::Light;
+        }
+        if self.function_pattern.get(mx, my) {
+            return Paint::Module;
+        }
+
+        let (u, v) = (x.fract() - 0.5, y.fract() - 0.5);
+        let round = match style.module_shape {
+            ModuleShape::Square => false,
+            ModuleShape::Dot => true,
+            ModuleShape::Rounded => {
+                // Round the corner of this quadrant when neither neighbor next to it is dark
+                let (dx, dy) = (if u < 0.0 { -1 } else { 1 }, if v < 0.0 { -1 } else { 1 });
+                !self.dark(mx as i64 + dx, my as i64) && !self.dark(mx as i64, my as i64 + dy)
+            }
+        };
+        if round && u * u + v * v > 0.25 {
+            Paint::Light
+        } else {
+            Paint::Module
+        }
+    }
+
+    fn render(&self, style: &QrStyle) -> RgbaImage {
+        let module_size = style.module_size.max(1);
+        let side = (self.dimension() + 2 * style.quiet_zone) * module_size;
+        let dimension = self.dimension() as f32;
+        let mut image = RgbaImage::from_pixel(side, side, rgba(style.background));
+
+        let samples = (SAMPLES * SAMPLES) as f32;
+        for (px, py, pixel) in image.enumerate_pixels_mut() {
+            let (mut module, mut eye) = (0, 0);
+            for sy in 0..SAMPLES {
+                for sx in 0..SAMPLES {
+                    let at = |p: u32, s: u32| {
+                        (p as f32 + (s as f32 + 0.5) / SAMPLES as f32) / module_size as f32
+                            - style.quiet_zone as f32
+                    };
+                    match self.paint_at(at(px, sx), at(py, sy), style) {
+                        Paint::Light => {}
+                        Paint::Module => module += 1,
+                        Paint::Eye => eye += 1,
+                    }
+                }
+            }
+            if module + eye == 0 {
+                continue;
+            }
+
+            // Position in the symbol, from 0 to 1, for gradients
+            let x = ((px as f32 + 0.5) / module_size as f32 - style.quiet_zone as f32) / dimension;
+            let y = ((py as f32 + 0.5) / module_size as f32 - style.quiet_zone as f32) / dimension;
+            let mut color = [0.0; 3];
+            for (fill, count) in [
+                (&style.foreground, module),
+                (style.eye_fill.as_ref().unwrap_or(&style.foreground), eye),
+            ] {
+                let fill: &Fill = fill;
+                let paint = fill.color_at(x, y);
+                for (channel, value) in color.iter_mut().zip([paint.r, paint.g, paint.b]) {
+                    *channel += value as f32 * count as f32;
+                }
+            }
+            let light = samples - (module + eye) as f32;
+            let background = [style.background.r, style.background.g, style.background.b];
+            for (i, channel) in color.iter().enumerate() {
+                pixel.0[i] = ((channel + background[i] as f32 * light) / samples).round() as u8;
+            }
+        }
+
+        if let (Some(logo), Some((start, width))) = (&style.logo, self.logo_area) {
+            let inner = width.saturating_sub(2 * logo.padding).max(1) * module_size;
+            let (logo_width, logo_height) = logo.image.dimensions();
+            let scale = inner as f32 / logo_width.max(logo_height).max(1) as f32;
+            let scaled = imageops::resize(
+                &logo.image,
+                ((logo_width as f32 * scale).round() as u32).max(1),
+                ((logo_height as f32 * scale).round() as u32).max(1),
+                imageops::FilterType::Triangle,
+            );
+            let center = (style.quiet_zone + start) * module_size + width * module_size / 2;
+            imageops::overlay(
+                &mut image,
+                &scaled,
+                center as i64 - scaled.width() as i64 / 2,
+                center as i64 - scaled.height() as i64 / 2,
+            );
+        }
+        image
+    }
+}
+
+fn rgba(color: Color) -> Rgba<u8> {
+    Rgba([color.r, color.g, color.b, u8::MAX])
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use crate::{
+        EncodeHints,
+        qrcode::{
+            common::ErrorCorrectionLevel,
+            styled::{EyeShape, Fill, Logo, ModuleShape, QrStyle},
+        },
+        render::Color,
+    };
+
+    use super::{StyledQRCodeWriter, decodes_to};
+
+    const CONTENTS: &str = "https://github.com/rxing-core/rxing";
+
+    #[test]
+    fn renders_plain_symbol() {
+        let styled = StyledQRCodeWriter
+            .encode(CONTENTS, &QrStyle::default(), &EncodeHints::default())
+            .unwrap();
+        let dimension = styled.code.getVersion().unwrap().getDimensionForVersion();
+        assert_eq!(styled.image.width(), (dimension + 8) * 10);
+        assert_eq!(styled.code.getECLevel(), &Some(ErrorCorrectionLevel::L));
+        // The corner of the top left finder pattern, after the quiet zone
+        assert_eq!(styled.image.get_pixel(39, 39), &Rgba([255, 255, 255, 255]));
+        assert_eq!(styled.image.get_pixel(40, 40), &Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn renders_shapes_and_gradients() {
+        let style = QrStyle {
+            foreground: Fill::LinearGradient {
+                from: Color::new(0, 0, 128),
+                to: Color::new(96, 0, 64),
+                angle: 45.0,
+            },
+            background: Color::new(255, 250, 240),
+            module_shape: ModuleShape::Dot,
+            eye_frame: EyeShape::Rounded,
+            eye_ball: EyeShape::Circle,
+            eye_fill: Some(Fill::Solid(Color::new(200, 0, 0))),
+            ..QrStyle::default()
+        };
+        let styled = StyledQRCodeWriter
+            .encode(CONTENTS, &style, &EncodeHints::default())
+            .unwrap();
+        // The rounded corner of the frame is left out
+        assert_eq!(styled.image.get_pixel(40, 40), &Rgba([255, 250, 240, 255]));
+        // The middle of the frame's top side and of the ball take the eye color
+        assert_eq!(styled.image.get_pixel(75, 45), &Rgba([200, 0, 0, 255]));
+        assert_eq!(styled.image.get_pixel(75, 75), &Rgba([200, 0, 0, 255]));
+
+        let style = QrStyle {
+            module_shape: ModuleShape::Rounded,
+            ..style
+        };
+        assert!(
+            StyledQRCodeWriter
+                .encode(CONTENTS, &style, &EncodeHints::default())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn raises_error_correction_for_logo() {
+        let mut logo = Logo::new(RgbaImage::from_pixel(64, 32, Rgba([220, 20, 60, 255])));
+        logo.size = 0.25;
+        let style = QrStyle {
+            logo: Some(logo),
+            ..QrStyle::default()
+        };
+        let styled = StyledQRCodeWriter
+            .encode(CONTENTS, &style, &EncodeHints::default())
+            .unwrap();
+        let level = styled.code.getECLevel().unwrap();
+        assert!(matches!(
+            level,
+            ErrorCorrectionLevel::Q | ErrorCorrectionLevel::H
+        ));
+        assert!(decodes_to(&styled.image, CONTENTS));
+        let center = styled.image.width() / 2;
+        assert_eq!(
+            styled.image.get_pixel(center, center),
+            &Rgba([220, 20, 60, 255])
+        );
+
+        // Without enough error correction to hide it, the logo is rejected
+        let mut logo = Logo::new(RgbaImage::new(10, 10));
+        logo.size = 0.6;
+        let style = QrStyle {
+            logo: Some(logo),
+            ..QrStyle::default()
+        };
+        assert!(
+            StyledQRCodeWriter
+                .encode(CONTENTS, &style, &EncodeHints::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_unreadable_colors() {
+        let style = QrStyle {
+            foreground: Fill::Solid(Color::new(250, 250, 250)),
+            ..QrStyle::default()
+        };
+        assert!(
+            StyledQRCodeWriter
+                .encode(CONTENTS, &style, &EncodeHints::default())
+                .is_err()
+        );
+    }
+}
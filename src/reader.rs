@@ -78,3 +78,76 @@ pub trait ImmutableReader {
         hints: &DecodeHints,
     ) -> Result<RXingResult>;
 }
+
+#[cfg(test)]
+#[cfg(feature = "full_barcode_format_support")]
+mod tests {
+    use crate::{
+        ImmutableReader, MultiFormatReader, MultiUseMultiFormatReader, Reader,
+        aztec::AztecReader,
+        datamatrix::DataMatrixReader,
+        dotcode::DotCodeReader,
+        filtered_image_reader::FilteredImageReader,
+        hanxin::HanXinReader,
+        maxicode::MaxiCodeReader,
+        multi::ByQuadrantReader,
+        oned::{
+            CodaBarReader, Code11Reader, Code32Reader, Code39Reader, Code93Reader, Code128Reader,
+            EAN8Reader, EAN13Reader, IATATwoOfFiveReader, ITFReader, IndustrialTwoOfFiveReader,
+            MSIReader, MatrixTwoOfFiveReader, MultiFormatOneDReader, MultiFormatUPCEANReader,
+            PZNReader, PharmacodeReader, PharmacodeTwoTrackReader, TelepenReader, UPCAReader,
+            UPCEReader,
+            cpp::ODReader,
+            postal::{FourStateReader, PostnetReader},
+            rss::{RSS14Reader, expanded::RSSExpandedReader},
+            stacked::StackedReader,
+        },
+        pdf417::PDF417Reader,
+        qrcode::{QRCodeReader, cpp_port::QrReader},
+    };
+
+    fn assert_shareable<R: Reader + ImmutableReader + Send + Sync>() {}
+
+    #[test]
+    fn readers_are_shareable() {
+        assert_shareable::<MultiFormatReader>();
+        assert_shareable::<MultiUseMultiFormatReader>();
+        assert_shareable::<QRCodeReader>();
+        assert_shareable::<QrReader>();
+        assert_shareable::<DataMatrixReader>();
+        assert_shareable::<AztecReader>();
+        assert_shareable::<PDF417Reader>();
+        assert_shareable::<MaxiCodeReader>();
+        assert_shareable::<DotCodeReader>();
+        assert_shareable::<HanXinReader>();
+        assert_shareable::<MultiFormatOneDReader>();
+        assert_shareable::<MultiFormatUPCEANReader>();
+        assert_shareable::<CodaBarReader>();
+        assert_shareable::<Code11Reader>();
+        assert_shareable::<Code32Reader>();
+        assert_shareable::<Code39Reader>();
+        assert_shareable::<Code93Reader>();
+        assert_shareable::<Code128Reader>();
+        assert_shareable::<EAN8Reader>();
+        assert_shareable::<EAN13Reader>();
+        assert_shareable::<UPCAReader>();
+        assert_shareable::<UPCEReader>();
+        assert_shareable::<ITFReader>();
+        assert_shareable::<IndustrialTwoOfFiveReader>();
+        assert_shareable::<IATATwoOfFiveReader>();
+        assert_shareable::<MatrixTwoOfFiveReader>();
+        assert_shareable::<MSIReader>();
+        assert_shareable::<PZNReader>();
+        assert_shareable::<PharmacodeReader>();
+        assert_shareable::<PharmacodeTwoTrackReader>();
+        assert_shareable::<TelepenReader>();
+        assert_shareable::<RSS14Reader>();
+        assert_shareable::<RSSExpandedReader>();
+        assert_shareable::<FourStateReader>();
+        assert_shareable::<PostnetReader>();
+        assert_shareable::<StackedReader>();
+        assert_shareable::<ODReader<'static>>();
+        assert_shareable::<ByQuadrantReader<QRCodeReader>>();
+        assert_shareable::<FilteredImageReader<MultiFormatReader>>();
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{BarcodeFormat, common::BitMatrix};
+
+#[cfg(feature = "oned")]
+use crate::oned::hri::HriLayout;
+
+use super::RenderOptions;
+
+/// A rectangle, in modules
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// A shape of a [`Drawing`], in modules from its top left corner
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Rect(Rect),
+    /// A path made of rectangular runs of modules, filled at once
+    Path(Vec<Rect>),
+    Circle {
+        x: f32,
+        y: f32,
+        radius: f32,
+    },
+    /// Text centered on `x`, its baseline at `y`, `size` modules high
+    Text {
+        text: String,
+        x: f32,
+        y: f32,
+        size: f32,
+    },
+}
+
+/// How the modules of a format are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Style {
+    /// Each bar one rectangle, spanning the modules it covers in each column
+    Bars,
+    /// All modules in one path of horizontal runs
+    Modules,
+    /// Each module a circle
+    Dots,
+}
+
+impl Style {
+    pub(crate) fn of(format: &BarcodeFormat) -> Self {
+        match format {
+            BarcodeFormat::DOTCODE => Self::Dots,
+            BarcodeFormat::AUSTRALIA_POST
+            | BarcodeFormat::CODABAR
+            | BarcodeFormat::CODE_11
+            | BarcodeFormat::CODE_32
+            | BarcodeFormat::CODE_39
+            | BarcodeFormat::CODE_93
+            | BarcodeFormat::CODE_128
+            | BarcodeFormat::DXFilmEdge
+            | BarcodeFormat::EAN_8
+            | BarcodeFormat::EAN_13
+            | BarcodeFormat::IATA_2_OF_5
+            | BarcodeFormat::INDUSTRIAL_2_OF_5
+            | BarcodeFormat::INTELLIGENT_MAIL
+            | BarcodeFormat::ITF
+            | BarcodeFormat::KIX
+            | BarcodeFormat::MATRIX_2_OF_5
+            | BarcodeFormat::MSI
+            | BarcodeFormat::PHARMACODE
+            | BarcodeFormat::PHARMACODE_TWO_TRACK
+            | BarcodeFormat::PLANET
+            | BarcodeFormat::POSTNET
+            | BarcodeFormat::PZN
+            | BarcodeFormat::RM4SCC
+            | BarcodeFormat::RSS_14
+            | BarcodeFormat::RSS_EXPANDED
+            | BarcodeFormat::TELEPEN
+            | BarcodeFormat::UPC_A
+            | BarcodeFormat::UPC_E
+            | BarcodeFormat::UPC_EAN_EXTENSION => Self::Bars,
+            _ => Self::Modules,
+        }
+    }
+}
+
+/// Shapes of a barcode, measured in modules, ready to be written out by a
+/// [`super::Renderer`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drawing {
+    pub width: f32,
+    pub height: f32,
+    pub shapes: Vec<Shape>,
+}
+
+impl Drawing {
+    /// Draws the output of a [`crate::Writer`] encoding `format`, one pixel of `matrix` per
+    /// module. One dimensional symbols encoded as a single row get bars
+    /// `options.bar_height` modules high, and only get a quiet zone to their sides.
+    pub fn from_bit_matrix(
+        matrix: &BitMatrix,
+        format: &BarcodeFormat,
+        options: &RenderOptions,
+    ) -> Self {
+        let style = Style::of(format);
+        let (left, top, width, height) = match options.quiet_zone {
+            Some(_) => match matrix.getEnclosingRectangle() {
+                Some([left, top, width, height]) => (left, top, width, height),
+                None => (0, 0, 0, 0),
+            },
+            None => (0, 0, matrix.getWidth(), matrix.getHeight()),
+        };
+        let zone = options.quiet_zone.unwrap_or(0);
+        let (zone_x, zone_y) = if style == Style::Bars {
+            (zone, 0)
+        } else {
+            (zone, zone)
+        };
+        let row_height = if style == Style::Bars && height == 1 {
+            options.bar_height
+        } else {
+            1
+        };
+        let dark = |x: u32, y: u32| matrix.get(left + x, top + y);
+        let origin = |x: u32, y: u32| ((x + zone_x) as f32, (y * row_height + zone_y) as f32);
+
+        let mut shapes = Vec::new();
+        match style {
+            Style::Bars => {
+                // Vertical runs of each column, merged with those of the next columns
+                // when they are the same
+                let column_runs = |x: u32| runs((0..height).map(|y| dark(x, y)));
+                let mut x = 0;
+                while x < width {
+                    let column = column_runs(x);
+                    let columns = (x + 1..width)
+                        .take_while(|&next| column_runs(next) == column)
+                        .count() as u32
+                        + 1;
+                    for (start, length) in column {
+                        let (rx, ry) = origin(x, start);
+                        shapes.push(Shape::Rect(Rect {
+                            x: rx,
+                            y: ry,
+                            width: columns as f32,
+                            height: (length * row_height) as f32,
+                        }));
+                    }
+                    x += columns;
+                }
+            }
+            Style::Modules => {
+                let mut path = Vec::new();
+                for y in 0..height {
+                    for (start, length) in runs((0..width).map(|x| dark(x, y))) {
+                        let (rx, ry) = origin(start, y);
+                        path.push(Rect {
+                            x: rx,
+                            y: ry,
+                            width: length as f32,
+                            height: 1.0,
+                        });
+                    }
+                }
+                if !path.is_empty() {
+                    shapes.push(Shape::Path(path));
+                }
+            }
+            Style::Dots => {
+                for y in 0..height {
+                    for x in (0..width).filter(|&x| dark(x, y)) {
+                        let (cx, cy) = origin(x, y);
+                        shapes.push(Shape::Circle {
+                            x: cx + 0.5,
+                            y: cy + 0.5,
+                            radius: 0.5,
+                        });
+                    }
+                }
+            }
+        }
+
+        Self {
+            width: (width + 2 * zone_x) as f32,
+            height: (height * row_height + 2 * zone_y) as f32,
+            shapes,
+        }
+    }
+}
+
+/// Draws the bars and the human readable text of a one dimensional barcode, keeping the
+/// quiet zones of its layout
+#[cfg(feature = "oned")]
+impl From<&HriLayout> for Drawing {
+    fn from(layout: &HriLayout) -> Self {
+        let mut shapes: Vec<Shape> = layout
+            .bars
+            .iter()
+            .map(|bar| {
+                Shape::Rect(Rect {
+                    x: bar.x as f32,
+                    y: bar.top,
+                    width: bar.width as f32,
+                    height: bar.bottom - bar.top,
+                })
+            })
+            .collect();
+        for text in &layout.texts {
+            let y = text.top + text.size;
+            if text.spread {
+                let cell = text.width / text.text.chars().count() as f32;
+                for (i, c) in text.text.chars().enumerate() {
+                    shapes.push(Shape::Text {
+                        text: c.to_string(),
+                        x: text.left + (i as f32 + 0.5) * cell,
+                        y,
+                        size: text.size,
+                    });
+                }
+            } else {
+                shapes.push(Shape::Text {
+                    text: text.text.clone(),
+                    x: text.left + text.width / 2.0,
+                    y,
+                    size: text.size,
+                });
+            }
+        }
+        Self {
+            width: layout.width as f32,
+            height: layout.height,
+            shapes,
+        }
+    }
+}
+
+/// Start and length of each run of dark modules
+fn runs(modules: impl Iterator<Item = bool>) -> Vec<(u32, u32)> {
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+    let mut previous = false;
+    for (i, dark) in modules.enumerate() {
+        match (dark, previous) {
+            (true, true) => runs.last_mut().expect("run in progress").1 += 1,
+            (true, false) => runs.push((i as u32, 1)),
+            _ => {}
+        }
+        previous = dark;
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, common::BitMatrix};
+
+    use super::{super::RenderOptions, Drawing, Rect, Shape};
+
+    fn parse(rows: &str) -> BitMatrix {
+        BitMatrix::parse_strings(rows, "1", "0").expect("bitmatrix parse")
+    }
+
+    #[test]
+    fn merges_bars() {
+        let matrix = parse("0011010011100\n");
+        let drawing =
+            Drawing::from_bit_matrix(&matrix, &BarcodeFormat::CODE_128, &RenderOptions::default());
+        assert_eq!((drawing.width, drawing.height), (13.0, 50.0));
+        let bar = |x, width| {
+            Shape::Rect(Rect {
+                x,
+                y: 0.0,
+                width,
+                height: 50.0,
+            })
+        };
+        assert_eq!(
+            drawing.shapes,
+            [bar(2.0, 2.0), bar(5.0, 1.0), bar(8.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn replaces_quiet_zone() {
+        let matrix = parse("00001010000\n00001010000\n");
+        let options = RenderOptions {
+            quiet_zone: Some(2),
+            ..RenderOptions::default()
+        };
+        // One dimensional symbols of several rows keep them, with a quiet zone to the sides
+        let drawing = Drawing::from_bit_matrix(&matrix, &BarcodeFormat::POSTNET, &options);
+        assert_eq!((drawing.width, drawing.height), (7.0, 2.0));
+        assert_eq!(drawing.shapes.len(), 2);
+
+        // Matrix symbols get it on all sides
+        let drawing = Drawing::from_bit_matrix(&matrix, &BarcodeFormat::QR_CODE, &options);
+        assert_eq!((drawing.width, drawing.height), (7.0, 6.0));
+        let run = |x, y| Rect {
+            x,
+            y,
+            width: 1.0,
+            height: 1.0,
+        };
+        assert_eq!(
+            drawing.shapes,
+            [Shape::Path(vec![
+                run(2.0, 2.0),
+                run(4.0, 2.0),
+                run(2.0, 3.0),
+                run(4.0, 3.0)
+            ])]
+        );
+    }
+
+    #[test]
+    fn draws_dots() {
+        let matrix = parse("101\n010\n");
+        let drawing =
+            Drawing::from_bit_matrix(&matrix, &BarcodeFormat::DOTCODE, &RenderOptions::default());
+        assert_eq!(
+            drawing.shapes[2],
+            Shape::Circle {
+                x: 1.5,
+                y: 1.5,
+                radius: 0.5
+            }
+        );
+        assert_eq!(drawing.shapes.len(), 3);
+    }
+
+    #[cfg(feature = "oned")]
+    #[test]
+    fn draws_human_readable_text() {
+        use crate::oned::hri::{HriLayout, HriOptions};
+
+        let layout =
+            HriLayout::new("1234567", &BarcodeFormat::EAN_8, &HriOptions::default()).unwrap();
+        let drawing = Drawing::from(&layout);
+        assert_eq!((drawing.width, drawing.height), (81.0, 59.0));
+        assert_eq!(
+            drawing.shapes.last(),
+            Some(&Shape::Text {
+                text: "0".to_owned(),
+                x: 67.5,
+                y: 59.0,
+                size: 8.0
+            })
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Write;
+
+use super::{Drawing, POINTS_PER_MM, RenderOptions, Renderer, Shape, number};
+
+/// Renders a [`Drawing`] as an Encapsulated PostScript file, bounded by the drawing. Text is
+/// set in Helvetica.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EpsRenderer;
+
+impl Renderer for EpsRenderer {
+    fn render(&self, drawing: &Drawing, options: &RenderOptions) -> Vec<u8> {
+        let scale = options.module_size * POINTS_PER_MM;
+        let width = drawing.width * scale;
+        let height = drawing.height * scale;
+
+        let mut eps = format!(
+            "%!PS-Adobe-3.0 EPSF-3.0\n\
+             %%Creator: rxing\n\
+             %%BoundingBox: 0 0 {} {}\n\
+             %%HiResBoundingBox: 0 0 {} {}\n\
+             %%EndComments\n",
+            width.ceil() as u32,
+            height.ceil() as u32,
+            number(width),
+            number(height)
+        );
+        // Drawn in modules, from the top left corner
+        let _ = writeln!(
+            eps,
+            "0 {} translate\n{} {} scale",
+            number(height),
+            number(scale),
+            number(-scale)
+        );
+        if let Some(background) = options.background {
+            let _ = writeln!(
+                eps,
+                "{} setrgbcolor\n0 0 {} {} rectfill",
+                background.to_unit(),
+                number(drawing.width),
+                number(drawing.height)
+            );
+        }
+        let _ = writeln!(eps, "{} setrgbcolor", options.foreground.to_unit());
+
+        for shape in &drawing.shapes {
+            match shape {
+                Shape::Rect(rect) => {
+                    let _ = writeln!(
+                        eps,
+                        "{} {} {} {} rectfill",
+                        number(rect.x),
+                        number(rect.y),
+                        number(rect.width),
+                        number(rect.height)
+                    );
+                }
+                Shape::Path(runs) => {
+                    // rectfill takes an array of rectangles as operands
+                    eps.push('[');
+                    for run in runs {
+                        let _ = write!(
+                            eps,
+                            "{} {} {} {} ",
+                            number(run.x),
+                            number(run.y),
+                            number(run.width),
+                            number(run.height)
+                        );
+                    }
+                    eps.pop();
+                    eps.push_str("] rectfill\n");
+                }
+                Shape::Circle { x, y, radius } => {
+                    let _ = writeln!(
+                        eps,
+                        "newpath {} {} {} 0 360 arc fill",
+                        number(*x),
+                        number(*y),
+                        number(*radius)
+                    );
+                }
+                Shape::Text { text, x, y, size } => {
+                    let _ = writeln!(
+                        eps,
+                        "gsave {} {} translate 1 -1 scale /Helvetica findfont {} scalefont setfont \
+                         ({}) dup stringwidth pop -2 div 0 moveto show grestore",
+                        number(*x),
+                        number(*y),
+                        number(*size),
+                        escape(text)
+                    );
+                }
+            }
+        }
+        eps.push_str("showpage\n%%EOF\n");
+        eps.into_bytes()
+    }
+}
+
+/// Escapes `text` for a PostScript string, replacing characters outside of printable ASCII
+/// with `?`
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        let c = if (' '..='~').contains(&c) { c } else { '?' };
+        if matches!(c, '(' | ')' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BarcodeFormat,
+        common::BitMatrix,
+        render::{Color, Drawing, RenderOptions, Renderer, Shape},
+    };
+
+    use super::EpsRenderer;
+
+    #[test]
+    fn renders_bounding_box() {
+        let matrix = BitMatrix::parse_strings("110\n011\n", "1", "0").expect("bitmatrix parse");
+        let options = RenderOptions {
+            module_size: 1.0,
+            background: None,
+            foreground: Color::new(255, 0, 0),
+            ..RenderOptions::default()
+        };
+        let eps = String::from_utf8(EpsRenderer.render_bit_matrix(
+            &matrix,
+            &BarcodeFormat::AZTEC,
+            &options,
+        ))
+        .unwrap();
+
+        assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0\n"));
+        assert!(eps.contains("%%BoundingBox: 0 0 9 6\n"));
+        assert!(eps.contains("%%HiResBoundingBox: 0 0 8.5039 5.6693\n"));
+        assert!(eps.contains("1 0 0 setrgbcolor\n[0 0 2 1 1 1 2 1] rectfill\n"));
+        assert!(!eps.contains("0 0 3 2 rectfill"));
+        assert!(eps.ends_with("showpage\n%%EOF\n"));
+    }
+
+    #[test]
+    fn renders_text_and_circles() {
+        let drawing = Drawing {
+            width: 20.0,
+            height: 10.0,
+            shapes: vec![
+                Shape::Circle {
+                    x: 1.5,
+                    y: 0.5,
+                    radius: 0.5,
+                },
+                Shape::Text {
+                    text: "A(1)".to_owned(),
+                    x: 10.0,
+                    y: 10.0,
+                    size: 5.0,
+                },
+            ],
+        };
+        let eps =
+            String::from_utf8(EpsRenderer.render(&drawing, &RenderOptions::default())).unwrap();
+        assert!(eps.contains("1 1 1 setrgbcolor\n0 0 20 10 rectfill\n"));
+        assert!(eps.contains("newpath 1.5 0.5 0.5 0 360 arc fill\n"));
+        assert!(eps.contains("gsave 10 10 translate 1 -1 scale"));
+        assert!(eps.contains("(A\\(1\\)) dup stringwidth"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Vector output of encoded barcodes.
+//!
+//! The output of a [`crate::Writer`] is first turned into a [`Drawing`], a list of shapes
+//! measured in modules: merged bar rectangles for one dimensional formats, one path of
+//! merged runs for matrix formats and circles for formats printed as dots, such as DotCode.
+//! A [`Renderer`] then writes the drawing out at a physical module size, in the colors of
+//! [`RenderOptions`]. SVG, with the `svg_write` feature, PDF and EPS renderers are provided.
+
+mod drawing;
+pub use drawing::*;
+
+mod eps;
+pub use eps::*;
+
+mod pdf;
+pub use pdf::*;
+
+#[cfg(feature = "svg_write")]
+mod svg;
+#[cfg(feature = "svg_write")]
+pub use self::svg::*;
+
+use crate::{BarcodeFormat, common::BitMatrix};
+
+/// Points per millimeter, for PDF and EPS
+const POINTS_PER_MM: f32 = 72.0 / 25.4;
+
+/// A color given by its red, green and blue components
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(u8::MAX, u8::MAX, u8::MAX);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The color as `#rrggbb`
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// The components scaled from 0 to 1, as PDF and PostScript take them
+    fn to_unit(self) -> String {
+        [self.r, self.g, self.b]
+            .map(|c| number(c as f32 / u8::MAX as f32))
+            .join(" ")
+    }
+}
+
+/// Options of a [`Drawing`] and of its [`Renderer`]
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Size of a module, in millimeters
+    pub module_size: f32,
+
+    /// Color of the bars, modules and text
+    pub foreground: Color,
+
+    /// Color of the background, `None` to leave it transparent
+    pub background: Option<Color>,
+
+    /// Quiet zone around the symbol, in modules. `None` keeps the margins of the encoded
+    /// [`BitMatrix`], otherwise they are trimmed and replaced by this many modules.
+    pub quiet_zone: Option<u32>,
+
+    /// Height, in modules, of the bars of one dimensional symbols encoded as a single row
+    pub bar_height: u32,
+
+    /// Font family of text in SVG documents. PDF and EPS documents use Helvetica.
+    pub font_family: String,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            module_size: 0.33,
+            foreground: Color::BLACK,
+            background: Some(Color::WHITE),
+            quiet_zone: None,
+            bar_height: 50,
+            font_family: "OCR-B, monospace".to_owned(),
+        }
+    }
+}
+
+/// Writes a [`Drawing`] out as a document
+pub trait Renderer {
+    /// Renders `drawing` to the contents of a document
+    fn render(&self, drawing: &Drawing, options: &RenderOptions) -> Vec<u8>;
+
+    /// Renders the output of a [`crate::Writer`] encoding `format`. The matrix should
+    /// have one pixel per module, as the writers return it when asked for a width and
+    /// height of 0.
+    fn render_bit_matrix(
+        &self,
+        matrix: &BitMatrix,
+        format: &BarcodeFormat,
+        options: &RenderOptions,
+    ) -> Vec<u8> {
+        self.render(&Drawing::from_bit_matrix(matrix, format, options), options)
+    }
+}
+
+/// Formats a coordinate without trailing zeros, as the documents are text
+fn number(value: f32) -> String {
+    let text = format!("{value:.4}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_owned()
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, number};
+
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(0.33), "0.33");
+        assert_eq!(number(2.0 / 3.0), "0.6667");
+        assert_eq!(number(-0.00001), "0");
+    }
+
+    #[test]
+    fn formats_colors() {
+        assert_eq!(Color::new(255, 128, 0).to_hex(), "#ff8000");
+        assert_eq!(Color::new(255, 0, 51).to_unit(), "1 0 0.2");
+    }
+}
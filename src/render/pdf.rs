@@ -0,0 +1,243 @@
+use std::fmt::Write;
+
+use super::{Drawing, POINTS_PER_MM, RenderOptions, Renderer, Shape, number};
+
+/// Widths of the Helvetica glyphs of `' '` to `'~'`, in thousandths of the font size, to
+/// center text
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 222, 333, 333, 389, 584, 278, 333, 278,
+    278, // ' ' to /
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // 0 to 9
+    278, 278, 584, 584, 584, 556, 1015, // : to @
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // A to M
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // N to Z
+    278, 278, 278, 469, 556, 222, // [ to `
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // a to m
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // n to z
+    334, 260, 334, 584, // { to ~
+];
+
+/// Control point distance of the Bézier curves approximating a quarter circle
+const KAPPA: f32 = 0.552_284_8;
+
+/// Renders a [`Drawing`] as a single page PDF document the size of the drawing. Text is
+/// set in Helvetica.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PdfRenderer;
+
+impl Renderer for PdfRenderer {
+    fn render(&self, drawing: &Drawing, options: &RenderOptions) -> Vec<u8> {
+        let scale = options.module_size * POINTS_PER_MM;
+        let page_width = drawing.width * scale;
+        let page_height = drawing.height * scale;
+
+        // Drawn in modules, from the top left corner
+        let mut content = format!(
+            "{} 0 0 {} 0 {} cm\n",
+            number(scale),
+            number(-scale),
+            number(page_height)
+        );
+        if let Some(background) = options.background {
+            let _ = writeln!(
+                content,
+                "{} rg\n0 0 {} {} re f",
+                background.to_unit(),
+                number(drawing.width),
+                number(drawing.height)
+            );
+        }
+        let _ = writeln!(content, "{} rg", options.foreground.to_unit());
+
+        let mut path = String::new();
+        let mut text = String::new();
+        for shape in &drawing.shapes {
+            match shape {
+                Shape::Rect(rect) => rectangle(&mut path, rect),
+                Shape::Path(runs) => runs.iter().for_each(|run| rectangle(&mut path, run)),
+                Shape::Circle { x, y, radius } => circle(&mut path, *x, *y, *radius),
+                Shape::Text {
+                    text: string,
+                    x,
+                    y,
+                    size,
+                } => {
+                    let (string, width) = encode_text(string);
+                    let _ = writeln!(
+                        text,
+                        "BT /F1 {} Tf 1 0 0 -1 {} {} Tm ({string}) Tj ET",
+                        number(*size),
+                        number(x - width * size / 2.0),
+                        number(*y)
+                    );
+                }
+            }
+        }
+        if !path.is_empty() {
+            content.push_str(&path);
+            content.push_str("f\n");
+        }
+        content.push_str(&text);
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_owned(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_owned(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Contents 4 0 R \
+                 /Resources << /Font << /F1 5 0 R >> >> >>",
+                number(page_width),
+                number(page_height)
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{content}endstream",
+                content.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_owned(),
+        ];
+
+        let mut document = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(document.len());
+            document.extend(format!("{} 0 obj\n{object}\nendobj\n", i + 1).bytes());
+        }
+        let xref = document.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        document.extend(trailer.bytes());
+        document
+    }
+}
+
+fn rectangle(path: &mut String, rect: &super::Rect) {
+    let _ = writeln!(
+        path,
+        "{} {} {} {} re",
+        number(rect.x),
+        number(rect.y),
+        number(rect.width),
+        number(rect.height)
+    );
+}
+
+/// Adds a circle as four Bézier curves
+fn circle(path: &mut String, x: f32, y: f32, radius: f32) {
+    let k = radius * KAPPA;
+    let point = |dx: f32, dy: f32| format!("{} {}", number(x + dx), number(y + dy));
+    let _ = writeln!(path, "{} m", point(radius, 0.0));
+    for [c1, c2, end] in [
+        [(radius, k), (k, radius), (0.0, radius)],
+        [(-k, radius), (-radius, k), (-radius, 0.0)],
+        [(-radius, -k), (-k, -radius), (0.0, -radius)],
+        [(k, -radius), (radius, -k), (radius, 0.0)],
+    ] {
+        let _ = writeln!(
+            path,
+            "{} {} {} c",
+            point(c1.0, c1.1),
+            point(c2.0, c2.1),
+            point(end.0, end.1)
+        );
+    }
+    path.push_str("h\n");
+}
+
+/// Escapes `text` for a PDF string, replacing characters outside of printable ASCII with
+/// `?`, and returns it with its width in Helvetica relative to the font size
+fn encode_text(text: &str) -> (String, f32) {
+    let mut encoded = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let c = if (' '..='~').contains(&c) { c } else { '?' };
+        if matches!(c, '(' | ')' | '\\') {
+            encoded.push('\\');
+        }
+        encoded.push(c);
+        width += HELVETICA_WIDTHS[c as usize - ' ' as usize] as u32;
+    }
+    (encoded, width as f32 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BarcodeFormat,
+        common::BitMatrix,
+        render::{Drawing, RenderOptions, Renderer, Shape},
+    };
+
+    use super::{PdfRenderer, encode_text};
+
+    fn render(drawing: &Drawing) -> Vec<u8> {
+        let options = RenderOptions {
+            module_size: 25.4 / 72.0,
+            ..RenderOptions::default()
+        };
+        PdfRenderer.render(drawing, &options)
+    }
+
+    #[test]
+    fn renders_page() {
+        let matrix = BitMatrix::parse_strings("0110\n", "1", "0").expect("bitmatrix parse");
+        let drawing =
+            Drawing::from_bit_matrix(&matrix, &BarcodeFormat::CODE_128, &RenderOptions::default());
+        let bytes = render(&drawing);
+        let pdf = String::from_utf8_lossy(&bytes);
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        // A module is a point
+        assert!(pdf.contains("/MediaBox [0 0 4 50]"));
+        assert!(
+            pdf.contains("1 0 0 -1 0 50 cm\n1 1 1 rg\n0 0 4 50 re f\n0 0 0 rg\n1 0 2 50 re\nf\n")
+        );
+
+        // The cross reference table points at each object, counting the binary comment
+        // as bytes
+        let startxref: usize = pdf.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert!(bytes[startxref..].starts_with(b"xref\n"));
+        let table = String::from_utf8(bytes[startxref..].to_vec()).unwrap();
+        for (i, entry) in table.lines().skip(3).take(5).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(bytes[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+
+    #[test]
+    fn renders_text_and_circles() {
+        let drawing = Drawing {
+            width: 20.0,
+            height: 10.0,
+            shapes: vec![
+                Shape::Circle {
+                    x: 1.0,
+                    y: 1.0,
+                    radius: 1.0,
+                },
+                Shape::Text {
+                    text: "12".to_owned(),
+                    x: 10.0,
+                    y: 10.0,
+                    size: 5.0,
+                },
+            ],
+        };
+        let pdf = String::from_utf8_lossy(&render(&drawing)).into_owned();
+        assert!(pdf.contains("2 1 m\n2 1.5523 1.5523 2 1 2 c\n"));
+        // Two digits of 0.556 of the font size, centered
+        assert!(pdf.contains("BT /F1 5 Tf 1 0 0 -1 7.22 10 Tm (12) Tj ET"));
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(encode_text("(a\\b)é"), ("\\(a\\\\b\\)?".to_owned(), 2.612));
+    }
+}
@@ -0,0 +1,136 @@
+use svg::{
+    Document,
+    node::element::{Circle, Group, Path, Rectangle, Text},
+};
+
+use super::{Drawing, RenderOptions, Renderer, Shape, number};
+
+/// Renders a [`Drawing`] as an SVG document, sized in millimeters with one user unit per
+/// module
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SvgRenderer;
+
+impl SvgRenderer {
+    /// Renders `drawing` as an [`svg::Document`], to be changed further or saved with
+    /// [`svg::save`]
+    pub fn document(&self, drawing: &Drawing, options: &RenderOptions) -> Document {
+        let mut document = Document::new()
+            .set(
+                "width",
+                format!("{}mm", number(drawing.width * options.module_size)),
+            )
+            .set(
+                "height",
+                format!("{}mm", number(drawing.height * options.module_size)),
+            )
+            .set(
+                "viewBox",
+                format!("0 0 {} {}", number(drawing.width), number(drawing.height)),
+            );
+        if let Some(background) = options.background {
+            document = document.add(
+                Rectangle::new()
+                    .set("width", number(drawing.width))
+                    .set("height", number(drawing.height))
+                    .set("fill", background.to_hex()),
+            );
+        }
+
+        let mut group = Group::new().set("fill", options.foreground.to_hex());
+        for shape in &drawing.shapes {
+            group = match shape {
+                Shape::Rect(rect) => group.add(
+                    Rectangle::new()
+                        .set("x", number(rect.x))
+                        .set("y", number(rect.y))
+                        .set("width", number(rect.width))
+                        .set("height", number(rect.height)),
+                ),
+                Shape::Path(runs) => {
+                    let data: String = runs
+                        .iter()
+                        .map(|run| {
+                            format!(
+                                "M{} {}h{}v{}h-{}z",
+                                number(run.x),
+                                number(run.y),
+                                number(run.width),
+                                number(run.height),
+                                number(run.width)
+                            )
+                        })
+                        .collect();
+                    group.add(Path::new().set("d", data))
+                }
+                Shape::Circle { x, y, radius } => group.add(
+                    Circle::new()
+                        .set("cx", number(*x))
+                        .set("cy", number(*y))
+                        .set("r", number(*radius)),
+                ),
+                Shape::Text { text, x, y, size } => group.add(
+                    Text::new(text.as_str())
+                        .set("x", number(*x))
+                        .set("y", number(*y))
+                        .set("font-family", options.font_family.as_str())
+                        .set("font-size", number(*size))
+                        .set("text-anchor", "middle"),
+                ),
+            };
+        }
+        document.add(group)
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn render(&self, drawing: &Drawing, options: &RenderOptions) -> Vec<u8> {
+        self.document(drawing, options).to_string().into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BarcodeFormat,
+        common::BitMatrix,
+        render::{Color, RenderOptions, Renderer},
+    };
+
+    use super::SvgRenderer;
+
+    #[test]
+    fn renders_runs_in_millimeters() {
+        let matrix = BitMatrix::parse_strings("110\n011\n", "1", "0").expect("bitmatrix parse");
+        let options = RenderOptions {
+            module_size: 0.5,
+            foreground: Color::new(0, 0, 128),
+            background: None,
+            ..RenderOptions::default()
+        };
+        let svg = String::from_utf8(SvgRenderer.render_bit_matrix(
+            &matrix,
+            &BarcodeFormat::DATA_MATRIX,
+            &options,
+        ))
+        .unwrap();
+
+        assert!(svg.contains(r#"height="1mm" viewBox="0 0 3 2" width="1.5mm""#));
+        assert!(svg.contains(r##"<g fill="#000080">"##));
+        assert!(svg.contains(r#"<path d="M0 0h2v1h-2zM1 1h2v1h-2z"/>"#));
+        assert!(!svg.contains("<rect"));
+    }
+
+    #[test]
+    fn renders_background_and_circles() {
+        let matrix = BitMatrix::parse_strings("10\n01\n", "1", "0").expect("bitmatrix parse");
+        let svg = String::from_utf8(SvgRenderer.render_bit_matrix(
+            &matrix,
+            &BarcodeFormat::DOTCODE,
+            &RenderOptions::default(),
+        ))
+        .unwrap();
+
+        assert!(svg.contains(r##"<rect fill="#ffffff" height="2" width="2"/>"##));
+        assert!(svg.contains(r#"<circle cx="1.5" cy="1.5" r="0.5"/>"#));
+    }
+}
@@ -86,6 +86,16 @@ pub enum RXingResultMetadataType {
      */
     UPC_EAN_EXTENSION,
 
+    /**
+     * For GS1 Composite symbols, the element strings of the 2D component in human readable form
+     */
+    GS1_COMPOSITE,
+
+    /**
+     * For ITF-14 symbols, the GTIN-14 they carry, its check digit verified
+     */
+    GTIN,
+
     /**
      * PDF417-specific metadata
      */
@@ -137,6 +147,8 @@ impl From<String> for RXingResultMetadataType {
             "SUGGESTED_PRICE" | "SUGGESTEDPRICE" => RXingResultMetadataType::SUGGESTED_PRICE,
             "POSSIBLE_COUNTRY" | "POSSIBLECOUNTRY" => RXingResultMetadataType::POSSIBLE_COUNTRY,
             "UPC_EAN_EXTENSION" | "UPCEANEXTENSION" => RXingResultMetadataType::UPC_EAN_EXTENSION,
+            "GS1_COMPOSITE" | "GS1COMPOSITE" => RXingResultMetadataType::GS1_COMPOSITE,
+            "GTIN" => RXingResultMetadataType::GTIN,
             #[cfg(feature = "pdf417")]
             "PDF417_EXTRA_METADATA" | "PDF417EXTRAMETADATA" => {
                 RXingResultMetadataType::PDF417_EXTRA_METADATA
@@ -216,6 +228,16 @@ pub enum RXingResultMetadataValue {
      */
     UpcEanExtension(String),
 
+    /**
+     * For GS1 Composite symbols, the element strings of the 2D component in human readable form
+     */
+    Gs1Composite(String),
+
+    /**
+     * For ITF-14 symbols, the GTIN-14 they carry, its check digit verified
+     */
+    Gtin(String),
+
     /**
      * PDF417-specific metadata
      */
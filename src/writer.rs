@@ -17,7 +17,7 @@
 //package com.google.zxing;
 
 use crate::{
-    BarcodeFormat, EncodeHints,
+    BarcodeFormat, EncodeHints, PhysicalBarcode, PrintSpec,
     common::{BitMatrix, Result},
 };
 
@@ -62,4 +62,28 @@ pub trait Writer {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<BitMatrix>;
+
+    /**
+     * Encode a barcode to be printed, each module a whole number of printer dots wide rather
+     * than scaled to fit a size in pixels.
+     *
+     * @param contents The contents to encode in the barcode
+     * @param format The barcode format to generate
+     * @param spec The x-dimension, printer resolution and bar width reduction to print at
+     * @param hints Additional parameters to supply to the encoder
+     * @return {@link PhysicalBarcode} holding the barcode, one pixel per dot, and its printed size
+     * @throws WriterException if contents cannot be encoded legally in a format, if the
+     *  x-dimension or the bar width reduction cannot be printed at the resolution, or if the
+     *  rows of a PDF417 symbol would be lower than 3 modules
+     */
+    fn encode_physical(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        spec: &PrintSpec,
+        hints: &EncodeHints,
+    ) -> Result<PhysicalBarcode> {
+        let modules = self.encode_with_hints(contents, format, 0, 0, hints)?;
+        crate::physical_size::print(&modules, format, spec)
+    }
 }
@@ -106,6 +106,15 @@ impl<T: Reader> AbstractBlackBoxTestCase<T> {
             DecodeHintValue::AllowedEanExtensions(v) => self.hints.AllowedEanExtensions = Some(v),
             DecodeHintValue::AlsoInverted(v) => self.hints.AlsoInverted = Some(v),
             DecodeHintValue::TelepenAsNumeric(v) => self.hints.TelepenAsNumeric = Some(v),
+            #[cfg(feature = "oned")]
+            DecodeHintValue::Code11CheckDigits(v) => self.hints.Code11CheckDigits = Some(v),
+            #[cfg(feature = "oned")]
+            DecodeHintValue::MsiChecksum(v) => self.hints.MsiChecksum = Some(v),
+            #[cfg(feature = "oned")]
+            DecodeHintValue::AssumeTwoOfFiveCheckDigit(v) => {
+                self.hints.AssumeTwoOfFiveCheckDigit = Some(v)
+            }
+            DecodeHintValue::DecodeComposite(v) => self.hints.DecodeComposite = Some(v),
             DecodeHintValue::MinimumModuleSize(v) => self.hints.MinimumModuleSize = Some(v),
             #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
             DecodeHintValue::QrAssumeSpecConformInput(v) => {
@@ -278,6 +287,10 @@ impl<T: Reader> AbstractBlackBoxTestCase<T> {
                     RXingResultMetadataType::CONTENT_TYPE => {
                         RXingResultMetadataValue::ContentType(v)
                     }
+                    RXingResultMetadataType::GS1_COMPOSITE => {
+                        RXingResultMetadataValue::Gs1Composite(v)
+                    }
+                    RXingResultMetadataType::GTIN => RXingResultMetadataValue::Gtin(v),
                     RXingResultMetadataType::IS_INVERTED => {
                         RXingResultMetadataValue::IsInverted(v.parse().unwrap())
                     }